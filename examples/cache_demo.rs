@@ -1,4 +1,6 @@
-use project_manager_cli::scanner::{SizeCalculator, CacheConfig};
+use project_manager_cli::config::settings::CacheValidation;
+use project_manager_cli::scanner::SizeCalculator;
+use project_manager_cli::scanner::size_cache::CacheConfig;
 use std::time::{Duration, Instant};
 use anyhow::Result;
 
@@ -19,10 +21,10 @@ async fn main() -> Result<()> {
     let duration1 = start.elapsed();
     
     println!("计算用时: {:?}", duration1);
-    println!("代码大小: {} bytes ({:.2} MB)", size_info1.code_size, size_info1.code_size as f64 / (1024.0 * 1024.0));
-    println!("依赖大小: {} bytes ({:.2} MB)", size_info1.dependency_size, size_info1.dependency_size as f64 / (1024.0 * 1024.0));
-    println!("gitignore 排除大小: {} bytes ({:.2} MB)", size_info1.gitignore_excluded_size, size_info1.gitignore_excluded_size as f64 / (1024.0 * 1024.0));
-    println!("总大小: {} bytes ({:.2} MB)", size_info1.total_size, size_info1.total_size as f64 / (1024.0 * 1024.0));
+    println!("代码大小: {} bytes ({:.2} MB)", size_info1.code_size.bytes(), size_info1.code_size.bytes() as f64 / (1024.0 * 1024.0));
+    println!("依赖大小: {} bytes ({:.2} MB)", size_info1.dependency_size.bytes(), size_info1.dependency_size.bytes() as f64 / (1024.0 * 1024.0));
+    println!("gitignore 排除大小: {} bytes ({:.2} MB)", size_info1.gitignore_excluded_size.bytes(), size_info1.gitignore_excluded_size.bytes() as f64 / (1024.0 * 1024.0));
+    println!("总大小: {} bytes ({:.2} MB)", size_info1.total_size.bytes(), size_info1.total_size.bytes() as f64 / (1024.0 * 1024.0));
     println!("文件数量: {} 个", size_info1.total_file_count);
     println!();
     
@@ -33,6 +35,9 @@ async fn main() -> Result<()> {
         enabled: true,
         expiry_duration: Duration::from_secs(60 * 60), // 1小时
         max_entries: 100,
+        backup_count: 3,
+        hash_manifests: false,
+        validation: CacheValidation::Mtime,
     };
     
     let mut calculator_with_cache = SizeCalculator::new_with_cache(cache_config).await?;
@@ -44,10 +49,10 @@ async fn main() -> Result<()> {
     let duration2 = start.elapsed();
     
     println!("计算用时: {:?}", duration2);
-    println!("代码大小: {} bytes ({:.2} MB)", size_info2.code_size, size_info2.code_size as f64 / (1024.0 * 1024.0));
-    println!("依赖大小: {} bytes ({:.2} MB)", size_info2.dependency_size, size_info2.dependency_size as f64 / (1024.0 * 1024.0));
-    println!("gitignore 排除大小: {} bytes ({:.2} MB)", size_info2.gitignore_excluded_size, size_info2.gitignore_excluded_size as f64 / (1024.0 * 1024.0));
-    println!("总大小: {} bytes ({:.2} MB)", size_info2.total_size, size_info2.total_size as f64 / (1024.0 * 1024.0));
+    println!("代码大小: {} bytes ({:.2} MB)", size_info2.code_size.bytes(), size_info2.code_size.bytes() as f64 / (1024.0 * 1024.0));
+    println!("依赖大小: {} bytes ({:.2} MB)", size_info2.dependency_size.bytes(), size_info2.dependency_size.bytes() as f64 / (1024.0 * 1024.0));
+    println!("gitignore 排除大小: {} bytes ({:.2} MB)", size_info2.gitignore_excluded_size.bytes(), size_info2.gitignore_excluded_size.bytes() as f64 / (1024.0 * 1024.0));
+    println!("总大小: {} bytes ({:.2} MB)", size_info2.total_size.bytes(), size_info2.total_size.bytes() as f64 / (1024.0 * 1024.0));
     println!();
     
     // 第二次计算（从缓存获取）
@@ -57,10 +62,10 @@ async fn main() -> Result<()> {
     let duration3 = start.elapsed();
     
     println!("计算用时: {:?}", duration3);
-    println!("代码大小: {} bytes ({:.2} MB)", size_info3.code_size, size_info3.code_size as f64 / (1024.0 * 1024.0));
-    println!("依赖大小: {} bytes ({:.2} MB)", size_info3.dependency_size, size_info3.dependency_size as f64 / (1024.0 * 1024.0));
-    println!("gitignore 排除大小: {} bytes ({:.2} MB)", size_info3.gitignore_excluded_size, size_info3.gitignore_excluded_size as f64 / (1024.0 * 1024.0));
-    println!("总大小: {} bytes ({:.2} MB)", size_info3.total_size, size_info3.total_size as f64 / (1024.0 * 1024.0));
+    println!("代码大小: {} bytes ({:.2} MB)", size_info3.code_size.bytes(), size_info3.code_size.bytes() as f64 / (1024.0 * 1024.0));
+    println!("依赖大小: {} bytes ({:.2} MB)", size_info3.dependency_size.bytes(), size_info3.dependency_size.bytes() as f64 / (1024.0 * 1024.0));
+    println!("gitignore 排除大小: {} bytes ({:.2} MB)", size_info3.gitignore_excluded_size.bytes(), size_info3.gitignore_excluded_size.bytes() as f64 / (1024.0 * 1024.0));
+    println!("总大小: {} bytes ({:.2} MB)", size_info3.total_size.bytes(), size_info3.total_size.bytes() as f64 / (1024.0 * 1024.0));
     println!();
     
     // 验证结果一致性
@@ -1,6 +1,9 @@
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
+use crate::operations::archive::ConflictPolicy;
+use crate::operations::scan_output::Column;
+
 #[derive(Parser)]
 #[command(name = "project-manager-cli")]
 #[command(about = "一个用于扫描和管理代码项目的 CLI 工具")]
@@ -20,11 +23,54 @@ pub struct Cli {
     /// 详细输出
     #[arg(short, long)]
     pub verbose: bool,
+    
+    /// 禁用鼠标捕获，保留终端原生的文本选择和复制
+    #[arg(long, global = true)]
+    pub no_mouse: bool,
+    
+    /// 琐碎项目阈值（如 1MB）：低于该大小且没有 Git 提交历史的项目以暗色显示
+    #[arg(long, global = true, value_name = "SIZE", value_parser = parse_size_arg)]
+    pub min_size: Option<u64>,
+    
+    /// 扫描时直接跳过琐碎项目，而不是暗色显示（未设置阈值时使用 1MB）
+    #[arg(long, global = true)]
+    pub skip_trivial: bool,
+    
+    /// 允许清理、删除和归档扫描根目录之外的路径（覆盖配置中的 cleanup.restrict_to_scan_paths）
+    #[arg(long, global = true)]
+    pub allow_outside_roots: bool,
+    
+    /// 运行结束后将报告摘要 POST 到该 webhook（Slack/Discord 等）
+    #[arg(long, global = true, value_name = "WEBHOOK_URL")]
+    pub notify: Option<String>,
+}
+
+/// 解析 `--columns` 中的列名
+fn parse_column_arg(value: &str) -> Result<Column, String> {
+    value.parse()
+}
+
+/// 解析 `--on-conflict` 中的冲突处理方式
+fn parse_conflict_arg(value: &str) -> Result<ConflictPolicy, String> {
+    value.parse()
+}
+
+/// 解析命令行中以天为单位的时长参数
+fn parse_days_arg(value: &str) -> Result<u64, String> {
+    crate::utils::time_format::parse_days(value)
+        .ok_or_else(|| format!("无法解析时长: {}（示例: 180d、26w、6m、1y）", value))
+}
+
+/// 解析命令行中的大小参数
+fn parse_size_arg(value: &str) -> Result<u64, String> {
+    crate::utils::size_format::parse_size(value)
+        .ok_or_else(|| format!("无法解析大小: {}（示例: 1MB、500KB）", value))
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// 扫描指定目录中的项目
+    #[command(visible_alias = "list")]
     Scan {
         /// 要扫描的目录路径
         #[arg(default_value = ".")]
@@ -38,6 +84,10 @@ pub enum Commands {
         #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
         format: OutputFormat,
         
+        /// CSV/TSV 输出的列，逗号分隔（如 name,path,code_size,dep_size,last_commit）
+        #[arg(long, value_delimiter = ',', value_name = "COLUMNS", value_parser = parse_column_arg)]
+        columns: Vec<Column>,
+        
         /// 保存结果到文件
         #[arg(short, long)]
         output: Option<PathBuf>,
@@ -53,15 +103,24 @@ pub enum Commands {
     /// 清理项目依赖
     Clean {
         /// 项目路径
-        project_path: String,
+        #[arg(required_unless_present = "tag")]
+        project_path: Option<String>,
+        
+        /// 清理带有该标签的所有项目（在 TUI 中按 t 设置标签）
+        #[arg(long, conflicts_with = "project_path")]
+        tag: Option<String>,
         
-        /// 清理类型
-        #[arg(short, long, value_enum, default_value_t = CleanType::Dependencies)]
-        clean_type: CleanType,
+        /// 只清理指定名称的目录，逗号分隔（如 node_modules,target），默认清理全部依赖目录
+        #[arg(short, long, value_delimiter = ',', value_name = "DIRS")]
+        clean_type: Vec<String>,
         
         /// 强制删除，不询问确认
         #[arg(short, long)]
         force: bool,
+        
+        /// 只列出将要删除的目录和大小，不删除
+        #[arg(long)]
+        dry_run: bool,
     },
     
     /// 删除项目到回收站
@@ -80,6 +139,32 @@ pub enum Commands {
         action: ConfigAction,
     },
     
+    /// 列出最值得优先清理的项目
+    Suggest {
+        /// 要分析的目录路径
+        #[arg(default_value = ".")]
+        paths: Vec<String>,
+        
+        /// 最多显示的项目数
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+    },
+    
+    /// 交互式清理向导：依次处理最值得清理的项目
+    CleanupWizard {
+        /// 要分析的目录路径
+        #[arg(default_value = ".")]
+        paths: Vec<String>,
+        
+        /// 最多处理的项目数
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+        
+        /// 归档目录中已有同名项目时的处理方式（overwrite、rename、skip），不指定时逐个询问
+        #[arg(long, value_name = "POLICY", value_parser = parse_conflict_arg)]
+        on_conflict: Option<ConflictPolicy>,
+    },
+    
     /// 项目统计信息
     Stats {
         /// 要分析的目录路径
@@ -89,7 +174,137 @@ pub enum Commands {
         /// 显示详细统计
         #[arg(short, long)]
         detailed: bool,
+        
+        /// 输出格式；table 以外的格式只输出各项目明细（大小以字节为单位）
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        
+        /// CSV/TSV 输出的列，逗号分隔（如 name,path,code_size,dep_size,last_commit）
+        #[arg(long, value_delimiter = ',', value_name = "COLUMNS", value_parser = parse_column_arg)]
+        columns: Vec<Column>,
+        
+        /// 只列出超过该时长未活动（最后提交和目录修改时间都更早）的项目，按可释放的依赖大小排序（如 180d、6m）
+        #[arg(long, value_name = "AGE", value_parser = parse_days_arg)]
+        stale: Option<u64>,
+    },
+    
+    /// Git 仓库相关命令
+    Git {
+        #[command(subcommand)]
+        action: GitAction,
     },
+    
+    /// 保存或浏览项目索引快照
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+    
+    /// 查看或清理项目大小缓存
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    
+    /// 本地使用统计：年度清理回顾、导出和开关（默认关闭，数据只保存在本地）
+    Telemetry {
+        #[command(subcommand)]
+        action: TelemetryAction,
+    },
+    
+    /// 输出环境诊断信息（配置和缓存位置、编辑器、Git 版本、终端、扫描路径权限），提交问题时附上
+    Doctor,
+    
+    /// 输出上次会话缓存的项目路径，供 shell 补全使用
+    #[command(name = "_complete-projects", hide = true)]
+    CompleteProjects,
+}
+
+#[derive(Subcommand)]
+pub enum GitAction {
+    /// 列出有未提交更改、未推送提交、stash 或未跟踪文件的仓库
+    Unpushed {
+        /// 要检查的目录路径
+        #[arg(default_value = ".")]
+        paths: Vec<String>,
+
+        /// 同时检查远程仓库是否仍然存在（通过 git ls-remote，不会提示输入凭据）
+        #[arg(long)]
+        check_remotes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SnapshotAction {
+    /// 扫描项目并将大小和 Git 信息保存到快照文件
+    Save {
+        /// 快照文件路径（如 disk-2024.json）
+        file: PathBuf,
+        
+        /// 要扫描的目录路径
+        #[arg(default_value = ".")]
+        paths: Vec<String>,
+    },
+    
+    /// 在 TUI 中只读浏览快照，目录已删除也可查看
+    Browse {
+        /// 快照文件路径
+        file: PathBuf,
+    },
+    
+    /// 对比两个快照，列出新增、移除的项目和大小变化
+    Diff {
+        /// 旧快照文件路径
+        old: PathBuf,
+        
+        /// 新快照文件路径
+        new: PathBuf,
+        
+        /// 输出格式
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheAction {
+    /// 显示缓存统计：条目数、过期条目数、缓存的项目大小合计和缓存文件大小
+    Stats,
+    
+    /// 清除全部缓存，或只使指定项目的缓存失效
+    Clear {
+        /// 只清除该项目的缓存，下次扫描时重新计算大小
+        #[arg(long, value_name = "PATH")]
+        project: Option<String>,
+    },
+    
+    /// 删除过期的缓存条目
+    Prune,
+}
+
+#[derive(Subcommand)]
+pub enum TelemetryAction {
+    /// 显示年度清理回顾：扫描和清理次数、释放的空间、估计节省的时间
+    Summary {
+        /// 年份，默认为今年
+        #[arg(long)]
+        year: Option<i32>,
+    },
+    
+    /// 开始在本地记录使用统计（不含路径和项目名，不会上传）
+    Enable,
+    
+    /// 停止记录使用统计（已有记录保留，可用 clear 删除）
+    Disable,
+    
+    /// 将全部使用记录导出为 JSON 文件
+    Export {
+        /// 导出文件路径
+        file: PathBuf,
+    },
+    
+    /// 删除本地的使用记录
+    Clear,
 }
 
 #[derive(Subcommand)]
@@ -114,6 +329,24 @@ pub enum ConfigAction {
         /// 要取消忽略的路径
         path: String,
     },
+    
+    /// 清理项目时保留指定的依赖目录（如生成的站点输出 build/）
+    Keep {
+        /// 项目路径
+        project_path: String,
+        
+        /// 相对于项目根目录的目录
+        dir: String,
+    },
+    
+    /// 取消保留项目中的目录
+    Unkeep {
+        /// 项目路径
+        project_path: String,
+        
+        /// 相对于项目根目录的目录
+        dir: String,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone)]
@@ -124,14 +357,8 @@ pub enum OutputFormat {
     Json,
     /// CSV 格式
     Csv,
+    /// TSV 格式（制表符分隔）
+    Tsv,
+    /// Markdown 格式
+    Markdown,
 }
-
-#[derive(clap::ValueEnum, Clone)]
-pub enum CleanType {
-    /// 清理依赖文件夹 (node_modules, target 等)
-    Dependencies,
-    /// 清理缓存文件
-    Cache,
-    /// 清理所有临时文件
-    All,
-}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use anyhow::Result;
+
+/// 展开字符串中的 `${VAR}` 环境变量引用
+///
+/// `key` 为配置项名称，用于错误信息。引用的变量未设置或 `${` 没有闭合时返回错误。
+pub fn expand_env_vars(value: &str, key: &str) -> Result<String> {
+    expand_with(value, key, |name| std::env::var(name).ok())
+}
+
+/// 使用指定的变量查找函数展开 `${VAR}` 引用
+fn expand_with(value: &str, key: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            anyhow::bail!("配置项 {} 中的 \"{}\" 缺少右花括号", key, value);
+        };
+
+        let name = &after[..end];
+        if name.is_empty() {
+            anyhow::bail!("配置项 {} 中的 \"{}\" 包含空的变量名", key, value);
+        }
+        match lookup(name) {
+            Some(var) => expanded.push_str(&var),
+            None => anyhow::bail!("配置项 {} 引用的环境变量 {} 未设置（{}）", key, name, value),
+        }
+
+        rest = &after[end + 1..];
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/me".to_string()),
+            "WORKSPACE_ROOT" => Some("/ci/workspace".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_env_vars() {
+        assert_eq!(expand_with("${HOME}/code", "scan_paths", lookup).unwrap(), "/home/me/code");
+        assert_eq!(
+            expand_with("${WORKSPACE_ROOT}/${HOME}", "scan_paths", lookup).unwrap(),
+            "/ci/workspace//home/me"
+        );
+        assert_eq!(expand_with("/plain/$HOME", "scan_paths", lookup).unwrap(), "/plain/$HOME");
+    }
+
+    #[test]
+    fn test_expand_env_vars_errors() {
+        let error = expand_with("${MISSING}/code", "ignore.paths", lookup).unwrap_err();
+        assert_eq!(error.to_string(), "配置项 ignore.paths 引用的环境变量 MISSING 未设置（${MISSING}/code）");
+        assert!(expand_with("${HOME/code", "scan_paths", lookup).is_err());
+        assert!(expand_with("${}/code", "scan_paths", lookup).is_err());
+    }
+}
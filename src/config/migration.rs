@@ -0,0 +1,155 @@
+use anyhow::Result;
+use std::fmt;
+use toml::{Table, Value};
+
+/// 当前配置文件格式版本
+pub const CURRENT_VERSION: u32 = 1;
+
+/// 配置文件版本高于当前程序支持的版本
+#[derive(Debug)]
+pub struct UnsupportedVersion(pub u32);
+
+impl fmt::Display for UnsupportedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "配置文件版本 {} 高于当前支持的版本 {}，请升级 project-manager-cli",
+            self.0,
+            CURRENT_VERSION
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedVersion {}
+
+/// 迁移步骤：`MIGRATIONS[n]` 将版本 n 的配置升级到版本 n + 1
+///
+/// 只有配置文件格式的实际变化（改名、拆分、改变取值含义等）才需要新增版本；
+/// 新增的配置段和字段由版本 0 → 1 的补全步骤和 `#[serde(default)]` 处理。
+const MIGRATIONS: &[fn(&mut Table, &Table)] = &[
+    migrate_v0_to_v1,
+];
+
+/// 版本 0（没有 version 字段的旧配置）→ 版本 1：补全缺失的配置段和字段
+fn migrate_v0_to_v1(table: &mut Table, defaults: &Table) {
+    fill_missing(table, defaults);
+}
+
+/// 递归补全缺失的键，已有的值保持不变
+fn fill_missing(table: &mut Table, defaults: &Table) {
+    for (key, default_value) in defaults {
+        match (table.get_mut(key), default_value) {
+            (Some(Value::Table(existing)), Value::Table(default_table)) => {
+                fill_missing(existing, default_table);
+            }
+            (Some(_), _) => {}
+            (None, _) => {
+                table.insert(key.clone(), default_value.clone());
+            }
+        }
+    }
+}
+
+/// 读取配置表中的版本号，缺失时视为版本 0
+pub fn version_of(table: &Table) -> Result<u32> {
+    match table.get("version") {
+        None => Ok(0),
+        Some(Value::Integer(version)) if *version >= 0 => Ok(*version as u32),
+        Some(value) => anyhow::bail!("无效的配置版本: {}", value),
+    }
+}
+
+/// 依次执行迁移步骤，将配置表升级到当前版本，返回原始版本号
+///
+/// `defaults` 为默认配置序列化后的表，供迁移步骤补全缺失字段。
+/// 配置版本高于当前程序支持的版本时返回错误，避免用旧格式覆盖新配置。
+pub fn migrate(table: &mut Table, defaults: &Table) -> Result<u32> {
+    let from = version_of(table)?;
+    if from > CURRENT_VERSION {
+        return Err(UnsupportedVersion(from).into());
+    }
+
+    for step in &MIGRATIONS[from as usize..] {
+        step(table, defaults);
+    }
+    table.insert("version".to_string(), Value::Integer(CURRENT_VERSION as i64));
+
+    Ok(from)
+}
+
+/// 列出 `original` 中存在、但解析后的配置 `parsed` 中没有的键（以点号连接的路径表示）
+pub fn unknown_keys(original: &Table, parsed: &Table) -> Vec<String> {
+    let mut keys = Vec::new();
+    collect_unknown_keys(original, parsed, "", &mut keys);
+    keys.sort();
+    keys
+}
+
+fn collect_unknown_keys(original: &Table, parsed: &Table, prefix: &str, keys: &mut Vec<String>) {
+    for (key, value) in original {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        match (value, parsed.get(key)) {
+            (_, None) => keys.push(path),
+            (Value::Table(original_table), Some(Value::Table(parsed_table))) => {
+                collect_unknown_keys(original_table, parsed_table, &path, keys);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(content: &str) -> Table {
+        content.parse().unwrap()
+    }
+
+    #[test]
+    fn test_migrate_legacy_config() {
+        let defaults = table("version = 1\n[scan]\nmax_depth = 10\nscan_hidden = false\n[git]\nstale_branch_days = 90\n");
+        let mut config = table("[scan]\nmax_depth = 3\n");
+
+        assert_eq!(migrate(&mut config, &defaults).unwrap(), 0);
+        assert_eq!(config, table("version = 1\n[scan]\nmax_depth = 3\nscan_hidden = false\n[git]\nstale_branch_days = 90\n"));
+    }
+
+    #[test]
+    fn test_migrate_fills_new_sections_and_fields() {
+        let defaults = table("version = 1\n[scan]\nmax_depth = 10\nmax_files_per_project = 1000000\n[telemetry]\nenabled = false\n");
+        let mut config = table("[scan]\nmax_depth = 3\n");
+
+        assert_eq!(migrate(&mut config, &defaults).unwrap(), 0);
+        assert_eq!(
+            config,
+            table("version = 1\n[scan]\nmax_depth = 3\nmax_files_per_project = 1000000\n[telemetry]\nenabled = false\n")
+        );
+
+        // 已是当前版本的配置不再补全，缺失的字段由 serde 默认值处理
+        let mut current = table("version = 1\n[scan]\nmax_depth = 3\n");
+        assert_eq!(migrate(&mut current, &defaults).unwrap(), 1);
+        assert_eq!(current, table("version = 1\n[scan]\nmax_depth = 3\n"));
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_version() {
+        let mut config = table("version = 99\n");
+        let error = migrate(&mut config, &Table::new()).unwrap_err();
+        assert!(error.downcast_ref::<UnsupportedVersion>().is_some());
+        assert_eq!(version_of(&config).unwrap(), 99);
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let original = table("colour = \"red\"\n[scan]\nmax_depth = 3\nlegacy_mode = true\n[plugins]\nenabled = []\n");
+        let parsed = table("[scan]\nmax_depth = 3\n");
+
+        assert_eq!(unknown_keys(&original, &parsed), ["colour", "plugins", "scan.legacy_mode"]);
+    }
+}
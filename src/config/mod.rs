@@ -1,4 +1,6 @@
 pub mod defaults;
+pub mod interpolate;
+pub mod migration;
 pub mod settings;
 
 pub use settings::Config;
\ No newline at end of file
@@ -1,11 +1,20 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use anyhow::Result;
+use crate::models::ProjectType;
+use crate::utils::path_match;
+
+use super::interpolate;
+use super::migration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// 配置文件格式版本
+    #[serde(default)]
+    pub version: u32,
+    
     /// 扫描的根目录列表
     pub scan_paths: Vec<String>,
     
@@ -20,6 +29,31 @@ pub struct Config {
     
     /// 缓存配置
     pub cache: CacheConfig,
+    
+    /// Git 配置
+    pub git: GitConfig,
+    
+    /// 归档配置
+    pub archive: ArchiveConfig,
+    
+    /// 通知配置
+    pub notify: NotifyConfig,
+    
+    /// 编辑器配置
+    pub editor: EditorConfig,
+    
+    /// 清理配置
+    pub cleanup: CleanupConfig,
+    
+    /// 项目检测配置
+    pub detection: DetectionConfig,
+    
+    /// 本地使用统计配置
+    pub telemetry: TelemetryConfig,
+    
+    /// 加载时展开过环境变量的值（展开后的值 → 原始值），保存时写回原始值
+    #[serde(skip)]
+    interpolated: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,19 +71,76 @@ pub struct ProjectIgnoreConfig {
     pub projects: HashSet<String>,
 }
 
+/// 项目被忽略的原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum IgnoreSource {
+    /// 手动标记为忽略
+    Manual,
+
+    /// 匹配忽略路径规则
+    Path(String),
+
+    /// 项目目录或某一级父目录名在忽略目录列表中
+    Directory(String),
+}
+
+impl IgnoreSource {
+    /// 显示说明
+    pub fn describe(&self) -> String {
+        match self {
+            IgnoreSource::Manual => "手动忽略".to_string(),
+            IgnoreSource::Path(pattern) => format!("忽略路径规则 {}", pattern),
+            IgnoreSource::Directory(name) => format!("忽略目录名 {}", name),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanConfig {
     /// 最大扫描深度
     pub max_depth: Option<usize>,
     
-    /// 是否跟随符号链接
+    /// 是否跟随符号链接：发现项目时跟随所有链接，统计大小时只跟随指向文件的链接且同一目标只计一次
     pub follow_symlinks: bool,
     
-    /// 并发扫描线程数
+    /// 并发上限：同时检测项目的目录数、TUI 中同时计算大小的项目数，以及单个项目并发读取文件信息的任务数
     pub concurrent_scans: usize,
     
     /// 是否扫描隐藏目录
     pub scan_hidden: bool,
+    
+    /// 琐碎项目阈值（字节）：总大小低于该值且没有 Git 提交历史的项目视为琐碎项目，0 表示不检查
+    #[serde(default)]
+    pub min_project_size: u64,
+    
+    /// 琐碎项目的处理方式
+    #[serde(default)]
+    pub trivial_projects: TrivialProjectMode,
+    
+    /// 单个项目最多统计的文件数，超出后停止计算并标记结果不完整，0 表示不限制
+    pub max_files_per_project: usize,
+    
+    /// 单个项目最多统计的字节数，超出后停止计算并标记结果不完整，0 表示不限制
+    pub max_bytes_per_project: u64,
+    
+    /// TUI 中是否监视扫描路径，项目新增、删除或依赖目录大小明显变化时自动更新列表
+    #[serde(default)]
+    pub watch: bool,
+    
+    /// 监视时两次检查之间的间隔（秒）
+    #[serde(default = "default_watch_interval_secs")]
+    pub watch_interval_secs: u64,
+}
+
+/// 琐碎项目（脚手架试验等）的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum TrivialProjectMode {
+    /// 保留在列表中，但以暗色显示
+    #[default]
+    Dim,
+    
+    /// 扫描时直接跳过
+    Skip,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +148,10 @@ pub struct DisplayConfig {
     /// 默认排序字段
     pub default_sort: SortField,
     
+    /// 是否按降序排列
+    #[serde(default = "default_sort_descending")]
+    pub sort_descending: bool,
+    
     /// 大小显示单位
     pub size_unit: SizeUnit,
     
@@ -65,18 +160,132 @@ pub struct DisplayConfig {
     
     /// 是否显示隐藏项目
     pub show_hidden: bool,
+    
+    /// 被隐藏的项目路径（仍会扫描和统计，但默认不在列表中显示）
+    #[serde(default)]
+    pub hidden_projects: HashSet<String>,
+    
+    /// 项目的标签（项目路径 → 标签），可以按标签批量操作项目
+    #[serde(default)]
+    pub project_tags: HashMap<String, BTreeSet<String>>,
+    
+    /// 项目列表行密度
+    #[serde(default)]
+    pub row_density: RowDensity,
+    
+    /// 是否启用鼠标捕获（关闭后可使用终端原生的文本选择和复制）
+    #[serde(default = "default_mouse")]
+    pub mouse: bool,
+    
+    /// 项目列表分组时使用的工作区路径前缀，项目归入路径最长的工作区或扫描根目录
+    #[serde(default)]
+    pub workspaces: Vec<String>,
+    
+    /// 是否在项目行中显示代码/依赖/gitignore 排除部分的占比条
+    #[serde(default = "default_show_ratio_bar")]
+    pub show_ratio_bar: bool,
+    
+    /// 超过该天数未活动（最后提交和目录修改时间都更早）的项目视为长期未活动，在 TUI 中按 z 只显示这些项目
+    #[serde(default = "default_stale_project_days")]
+    pub stale_project_days: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+fn default_show_ratio_bar() -> bool {
+    true
+}
+
+fn default_stale_project_days() -> u64 {
+    180
+}
+
+fn default_mouse() -> bool {
+    true
+}
+
+fn default_sort_descending() -> bool {
+    true
+}
+
+fn default_watch_interval_secs() -> u64 {
+    10
+}
+
+/// 项目列表行密度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum RowDensity {
+    /// 紧凑模式：每个项目一行
+    #[default]
+    Compact,
+    
+    /// 舒适模式：名称下方额外显示路径和描述
+    Comfortable,
+}
+
+impl RowDensity {
+    /// 每个项目占用的行数
+    pub fn row_height(&self) -> u16 {
+        match self {
+            RowDensity::Compact => 1,
+            RowDensity::Comfortable => 2,
+        }
+    }
+    
+    /// 切换到另一种密度
+    pub fn toggle(&self) -> Self {
+        match self {
+            RowDensity::Compact => RowDensity::Comfortable,
+            RowDensity::Comfortable => RowDensity::Compact,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SortField {
     Name,
+    
+    /// 代码大小（不包含依赖）
     Size,
+    
+    /// 磁盘占用（代码、依赖和 gitignore 排除的文件）
+    TotalSize,
+    
+    DependencySize,
     LastModified,
     ProjectType,
-    DependencySize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl SortField {
+    /// 按 s 键切换的顺序
+    const CYCLE: [SortField; 6] = [
+        SortField::Name,
+        SortField::Size,
+        SortField::TotalSize,
+        SortField::DependencySize,
+        SortField::LastModified,
+        SortField::ProjectType,
+    ];
+    
+    /// 切换到下一个排序字段
+    pub fn next(&self) -> Self {
+        let index = Self::CYCLE.iter().position(|field| field == self).unwrap_or(0);
+        Self::CYCLE[(index + 1) % Self::CYCLE.len()]
+    }
+    
+    /// 显示名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortField::Name => "名称",
+            SortField::Size => "代码大小",
+            SortField::TotalSize => "总大小",
+            SortField::DependencySize => "依赖大小",
+            SortField::LastModified => "最后修改",
+            SortField::ProjectType => "类型",
+        }
+    }
+}
+
+/// 大小显示单位
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SizeUnit {
     Auto,
     Bytes,
@@ -100,6 +309,163 @@ pub struct CacheConfig {
     /// 自动清理过期缓存的间隔（小时）
     #[serde(with = "duration_hours")]
     pub cleanup_interval: Duration,
+    
+    /// 缓存文件保留的轮换备份数量（缓存损坏时用于恢复）
+    pub backup_count: usize,
+    
+    /// 是否记录关键文件（Cargo.toml、package.json 等）的内容摘要：文件被更新修改时间但内容未变时不使缓存失效
+    #[serde(default)]
+    pub hash_manifests: bool,
+    
+    /// 判断缓存的大小是否仍然有效的方式
+    #[serde(default)]
+    pub validation: CacheValidation,
+}
+
+/// 判断缓存的项目大小是否仍然有效的方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum CacheValidation {
+    /// 比较项目目录和关键文件的修改时间：最快，但修改深层目录中的文件不会使缓存失效
+    #[default]
+    Mtime,
+    
+    /// 比较内容指纹：Git 仓库使用 HEAD 和工作区状态，其他项目抽样目录树中的修改时间
+    Fingerprint,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitConfig {
+    /// 分支最后一次提交超过该天数即视为陈旧分支
+    pub stale_branch_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveConfig {
+    /// 归档项目的存放目录
+    pub dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    /// 统计报告的 webhook 地址（Slack/Discord 等），未设置时不发送
+    pub webhook_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TelemetryConfig {
+    /// 是否在本地记录使用统计（扫描、清理次数和释放的空间，不含路径），默认关闭，数据不会上传
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorConfig {
+    /// 默认打开命令，按顺序使用第一个已安装的命令
+    pub default: Vec<String>,
+    
+    /// 按项目类型指定的打开命令（键为 rust、nodejs、python、notebook 等），优先于默认命令
+    pub by_type: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanupConfig {
+    /// 按项目保留的依赖目录（键为项目路径，值为相对于项目根目录的目录），清理时始终跳过
+    #[serde(default)]
+    pub keep: HashMap<String, Vec<String>>,
+    
+    /// 按项目类型（如 "nodejs"、"rust"）追加清理的目录，与内置的清理策略合并
+    #[serde(default)]
+    pub strategies: HashMap<String, Vec<String>>,
+    
+    /// 是否只允许清理、删除和归档扫描根目录（scan_paths）之下的路径，防止误操作系统目录
+    #[serde(default)]
+    pub restrict_to_scan_paths: bool,
+}
+
+impl CleanupConfig {
+    /// 项目中标记为保留的目录（绝对路径）
+    pub fn kept_dirs(&self, project_path: &Path) -> Vec<PathBuf> {
+        self.keep.get(&project_path.display().to_string())
+            .map(|dirs| dirs.iter().map(|dir| project_path.join(dir)).collect())
+            .unwrap_or_default()
+    }
+    
+    /// 配置中为某类项目追加的清理目录
+    pub fn extra_dirs(&self, project_type: &ProjectType) -> &[String] {
+        self.strategies.get(project_type.as_str()).map(Vec::as_slice).unwrap_or_default()
+    }
+    
+    /// 将项目中的目录标记为保留，已标记时返回 false
+    pub fn add_kept_dir(&mut self, project_path: &Path, dir: &str) -> bool {
+        let dirs = self.keep.entry(project_path.display().to_string()).or_default();
+        if dirs.iter().any(|kept| kept == dir) {
+            return false;
+        }
+        dirs.push(dir.to_string());
+        true
+    }
+    
+    /// 取消目录的保留标记，未标记时返回 false
+    pub fn remove_kept_dir(&mut self, project_path: &Path, dir: &str) -> bool {
+        let key = project_path.display().to_string();
+        let Some(dirs) = self.keep.get_mut(&key) else {
+            return false;
+        };
+        let before = dirs.len();
+        dirs.retain(|kept| kept != dir);
+        let removed = dirs.len() < before;
+        if dirs.is_empty() {
+            self.keep.remove(&key);
+        }
+        removed
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetectionConfig {
+    /// 自定义项目类型，与内置的检测规则合并
+    #[serde(default)]
+    pub custom_types: Vec<CustomProjectType>,
+}
+
+impl DetectionConfig {
+    /// 所有自定义项目类型的依赖目录名，大小计算时归入依赖大小
+    pub fn dependency_dirs(&self) -> Vec<String> {
+        self.custom_types.iter()
+            .flat_map(|custom| custom.dependency_dir_names())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomProjectType {
+    /// 显示名称
+    pub name: String,
+    
+    /// 标识文件名，项目根目录中存在任一文件即匹配，支持 * 和 ? 通配符（如 "*.xcodeproj"）
+    pub markers: Vec<String>,
+    
+    /// 类型列中显示的图标
+    #[serde(default = "default_custom_icon")]
+    pub icon: String,
+    
+    /// 依赖目录（相对于项目根目录，如 ".terraform/"、"Pods/"）
+    #[serde(default)]
+    pub dependency_dirs: Vec<String>,
+}
+
+impl CustomProjectType {
+    /// 去掉末尾 "/" 的依赖目录名
+    pub fn dependency_dir_names(&self) -> impl Iterator<Item = &str> {
+        self.dependency_dirs.iter()
+            .map(|dir| dir.trim_end_matches('/'))
+            .filter(|dir| !dir.is_empty())
+    }
+}
+
+fn default_custom_icon() -> String {
+    "📄".to_string()
 }
 
 /// Duration 序列化为小时数
@@ -131,6 +497,9 @@ impl Default for CacheConfig {
             expiry_duration: Duration::from_secs(24 * 3600), // 24 小时
             max_entries: 1000,
             cleanup_interval: Duration::from_secs(6 * 3600), // 6 小时
+            backup_count: 3,
+            hash_manifests: false,
+            validation: CacheValidation::Mtime,
         }
     }
 }
@@ -138,15 +507,90 @@ impl Default for CacheConfig {
 impl Default for Config {
     fn default() -> Self {
         Self {
+            version: migration::CURRENT_VERSION,
             scan_paths: crate::config::defaults::DefaultConfig::default_scan_paths(),
             ignore: ProjectIgnoreConfig::default(),
             scan: ScanConfig::default(),
             display: DisplayConfig::default(),
             cache: CacheConfig::default(),
+            git: GitConfig::default(),
+            archive: ArchiveConfig::default(),
+            notify: NotifyConfig::default(),
+            editor: EditorConfig::default(),
+            cleanup: CleanupConfig::default(),
+            detection: DetectionConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            interpolated: HashMap::new(),
+        }
+    }
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            stale_branch_days: 90,
+        }
+    }
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            dir: dirs::home_dir().unwrap_or_default().join("Archive"),
+        }
+    }
+}
+
+impl Default for EditorConfig {
+    fn default() -> Self {
+        Self {
+            default: vec!["nvim".to_string(), "vim".to_string(), "code".to_string()],
+            by_type: HashMap::new(),
         }
     }
 }
 
+impl ProjectIgnoreConfig {
+    /// 扫描时是否会跳过该项目：项目目录或它在扫描根目录下的某一级父目录名在忽略目录列表中，
+    /// 或路径匹配某条忽略路径规则（不含手动忽略的项目，它们仍显示在列表中）
+    pub fn excludes(&self, project_path: &Path, scan_roots: &[String]) -> bool {
+        self.rule_source(project_path, scan_roots).is_some()
+    }
+
+    /// 项目被忽略的原因，手动忽略优先于规则；未被忽略时返回 None
+    pub fn source(&self, project_path: &Path, scan_roots: &[String]) -> Option<IgnoreSource> {
+        if self.projects.contains(&project_path.display().to_string()) {
+            return Some(IgnoreSource::Manual);
+        }
+        self.rule_source(project_path, scan_roots)
+    }
+
+    /// 排除项目的忽略目录名或忽略路径规则
+    fn rule_source(&self, project_path: &Path, scan_roots: &[String]) -> Option<IgnoreSource> {
+        let relative = scan_roots.iter()
+            .find_map(|root| project_path.strip_prefix(root).ok())
+            .filter(|relative| relative.components().next().is_some());
+        let excluded_dir = match relative {
+            Some(relative) => relative.components()
+                .map(|component| component.as_os_str().to_string_lossy().to_string())
+                .find(|name| self.directories.contains(name)),
+            None => project_path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .filter(|name| self.directories.contains(name)),
+        };
+        if let Some(name) = excluded_dir {
+            return Some(IgnoreSource::Directory(name));
+        }
+
+        let mut patterns: Vec<&String> = self.paths.iter()
+            .filter(|pattern| path_match::matches_ignore_pattern(project_path, pattern))
+            .collect();
+        // 多条规则匹配时取固定的一条，显示结果不随集合顺序变化
+        patterns.sort();
+        patterns.first().map(|pattern| IgnoreSource::Path(pattern.to_string()))
+    }
+}
+
 impl Default for ProjectIgnoreConfig {
     fn default() -> Self {
         Self {
@@ -165,6 +609,12 @@ impl Default for ScanConfig {
             follow_symlinks: false,
             concurrent_scans: 4,
             scan_hidden: false,
+            min_project_size: 0,
+            trivial_projects: TrivialProjectMode::default(),
+            max_files_per_project: 1_000_000,
+            max_bytes_per_project: 0,
+            watch: false,
+            watch_interval_secs: default_watch_interval_secs(),
         }
     }
 }
@@ -173,24 +623,104 @@ impl Default for DisplayConfig {
     fn default() -> Self {
         Self {
             default_sort: SortField::LastModified,
+            sort_descending: default_sort_descending(),
             size_unit: SizeUnit::Auto,
             time_format: "%Y-%m-%d %H:%M:%S".to_string(),
             show_hidden: false,
+            hidden_projects: HashSet::new(),
+            project_tags: HashMap::new(),
+            row_density: RowDensity::default(),
+            mouse: default_mouse(),
+            workspaces: Vec::new(),
+            show_ratio_bar: default_show_ratio_bar(),
+            stale_project_days: default_stale_project_days(),
         }
     }
 }
 
 impl Config {
-    /// 从文件加载配置
+    /// 从文件加载配置（旧版本配置只在内存中迁移，不写回文件）
     pub fn load_from_file(path: &PathBuf) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
-        let config: Self = toml::from_str(&content)?;
+        let (mut config, _) = Self::read_and_migrate(path)?;
+        config.expand_env_vars()?;
         Ok(config)
     }
     
+    /// 展开 scan_paths 和 ignore.paths 中的 `${VAR}` 环境变量引用
+    ///
+    /// 原始值会被记录下来，保存配置时写回，使同一份配置文件可以在不同机器和 CI 中使用。
+    pub fn expand_env_vars(&mut self) -> Result<()> {
+        for (index, value) in self.scan_paths.iter_mut().enumerate() {
+            let expanded = interpolate::expand_env_vars(value, &format!("scan_paths[{}]", index))?;
+            if expanded != *value {
+                self.interpolated.insert(expanded.clone(), std::mem::replace(value, expanded));
+            }
+        }
+        
+        for (index, value) in self.display.workspaces.iter_mut().enumerate() {
+            let expanded = interpolate::expand_env_vars(value, &format!("display.workspaces[{}]", index))?;
+            if expanded != *value {
+                self.interpolated.insert(expanded.clone(), std::mem::replace(value, expanded));
+            }
+        }
+        
+        let mut ignore_paths = HashSet::with_capacity(self.ignore.paths.len());
+        for value in self.ignore.paths.drain() {
+            let expanded = interpolate::expand_env_vars(&value, "ignore.paths")?;
+            if expanded != value {
+                self.interpolated.insert(expanded.clone(), value);
+            }
+            ignore_paths.insert(expanded);
+        }
+        self.ignore.paths = ignore_paths;
+        
+        Ok(())
+    }
+    
+    /// 将展开过的值还原为配置文件中的原始写法
+    fn with_raw_values(&self) -> Self {
+        let raw = |value: &String| self.interpolated.get(value).unwrap_or(value).clone();
+        let mut config = self.clone();
+        config.scan_paths = self.scan_paths.iter().map(raw).collect();
+        config.display.workspaces = self.display.workspaces.iter().map(raw).collect();
+        config.ignore.paths = self.ignore.paths.iter().map(raw).collect();
+        config
+    }
+    
+    /// 读取配置并迁移到当前版本，返回配置和原始版本号；无法识别的键会给出警告
+    fn read_and_migrate(path: &PathBuf) -> Result<(Self, u32)> {
+        let content = std::fs::read_to_string(path)?;
+        let original: toml::Table = toml::from_str(&content)?;
+        
+        let defaults = toml::Table::try_from(Self::default())?;
+        let mut table = original.clone();
+        let from_version = migration::migrate(&mut table, &defaults)?;
+        let config: Self = table.try_into()?;
+        
+        let unknown_keys = migration::unknown_keys(&original, &toml::Table::try_from(&config)?);
+        if !unknown_keys.is_empty() {
+            tracing::warn!(
+                "配置文件 {} 中有无法识别的键（将被忽略）: {}",
+                path.display(),
+                unknown_keys.join(", ")
+            );
+        }
+        
+        Ok((config, from_version))
+    }
+    
+    /// 重写配置文件前备份原文件，返回备份路径
+    fn backup_config_file(path: &Path, label: &str) -> Result<PathBuf> {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(format!(".{}.bak", label));
+        let backup = PathBuf::from(backup);
+        std::fs::copy(path, &backup)?;
+        Ok(backup)
+    }
+    
     /// 保存配置到文件
     pub fn save_to_file(&self, path: &PathBuf) -> Result<()> {
-        let content = toml::to_string_pretty(self)?;
+        let content = toml::to_string_pretty(&self.with_raw_values())?;
         
         // 确保目录存在
         if let Some(parent) = path.parent() {
@@ -211,64 +741,72 @@ impl Config {
     }
     
     /// 加载配置，如果文件不存在则创建默认配置
+    ///
+    /// 旧版本的配置会先备份原文件，再迁移到当前版本并写回。
     pub fn load_or_create_default() -> Result<Self> {
         let config_path = Self::default_config_path()?;
         
-        if config_path.exists() {
-            // 尝试加载现有配置，如果失败则尝试兼容性加载
-            match Self::load_from_file(&config_path) {
-                Ok(config) => Ok(config),
-                Err(_) => {
-                    // 尝试向后兼容的配置加载
-                    Self::load_with_backward_compatibility(&config_path)
-                }
-            }
-        } else {
+        if !config_path.exists() {
             let config = Self::default();
             config.save_to_file(&config_path)?;
-            Ok(config)
+            return Ok(config);
         }
-    }
-    
-    /// 向后兼容的配置加载
-    fn load_with_backward_compatibility(path: &PathBuf) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
         
-        // 尝试解析为部分配置（允许缺少字段）
-        match toml::from_str::<PartialConfig>(&content) {
-            Ok(partial_config) => {
-                // 将部分配置转换为完整配置
-                let mut config = Self::default();
-                
-                if let Some(scan_paths) = partial_config.scan_paths {
-                    config.scan_paths = scan_paths;
-                }
-                if let Some(ignore) = partial_config.ignore {
-                    config.ignore = ignore;
-                }
-                if let Some(scan) = partial_config.scan {
-                    config.scan = scan;
+        match Self::read_and_migrate(&config_path) {
+            Ok((mut config, from_version)) => {
+                if from_version < migration::CURRENT_VERSION {
+                    let backup = Self::backup_config_file(&config_path, &format!("v{}", from_version))?;
+                    config.save_to_file(&config_path)?;
+                    tracing::info!(
+                        "配置文件已从版本 {} 迁移到版本 {}，原文件备份在 {}",
+                        from_version,
+                        migration::CURRENT_VERSION,
+                        backup.display()
+                    );
                 }
-                if let Some(display) = partial_config.display {
-                    config.display = display;
-                }
-                if let Some(cache) = partial_config.cache {
-                    config.cache = cache;
-                }
-                
-                // 保存更新后的配置文件
-                config.save_to_file(path)?;
+                // 环境变量未设置时直接报错，不能当作格式错误重置配置
+                config.expand_env_vars()?;
                 Ok(config)
             }
+            // 不能用旧格式覆盖新版本程序写入的配置
+            Err(e) if e.is::<migration::UnsupportedVersion>() => Err(e),
             Err(e) => {
-                eprintln!("警告: 配置文件格式错误，使用默认配置。错误: {}", e);
+                let backup = Self::backup_config_file(&config_path, "invalid")?;
+                tracing::warn!(
+                    "配置文件格式错误，使用默认配置（原文件备份在 {}）。错误: {}",
+                    backup.display(),
+                    e
+                );
                 let config = Self::default();
-                config.save_to_file(path)?;
+                config.save_to_file(&config_path)?;
                 Ok(config)
             }
         }
     }
     
+    /// 开启 `cleanup.restrict_to_scan_paths` 时检查路径是否位于某个扫描根目录之下（根目录本身不算）
+    ///
+    /// 两边都解析符号链接后再比较，指向根目录之外的链接同样会被拒绝。
+    pub fn check_operation_path(&self, path: &Path) -> Result<()> {
+        if !self.cleanup.restrict_to_scan_paths {
+            return Ok(());
+        }
+        
+        let resolve = |path: &Path| path.canonicalize().unwrap_or_else(|_| path_match::normalize_lexically(path));
+        let target = resolve(path);
+        let inside_root = self.scan_paths.iter().any(|root| {
+            let root = resolve(&path_match::expand_home(root));
+            target.starts_with(&root) && target != root
+        });
+        if !inside_root {
+            anyhow::bail!(
+                "{} 不在扫描根目录（scan_paths）之下，已拒绝操作；确认无误时可加 --allow-outside-roots",
+                path.display()
+            );
+        }
+        Ok(())
+    }
+    
     /// 将配置转换为 SizeCache 可以使用的 CacheConfig
     #[allow(dead_code)]
     pub fn to_size_cache_config(&self) -> crate::scanner::size_cache::CacheConfig {
@@ -276,8 +814,58 @@ impl Config {
             enabled: self.cache.enabled,
             expiry_duration: self.cache.expiry_duration,
             max_entries: self.cache.max_entries,
+            backup_count: self.cache.backup_count,
+            hash_manifests: self.cache.hash_manifests,
+            validation: self.cache.validation,
+        }
+    }
+}
+
+impl ScanConfig {
+    /// 转换为大小计算器使用的统计上限
+    pub fn to_size_limits(&self) -> crate::scanner::SizeLimits {
+        crate::scanner::SizeLimits {
+            max_files: (self.max_files_per_project > 0).then_some(self.max_files_per_project),
+            max_bytes: (self.max_bytes_per_project > 0).then_some(self.max_bytes_per_project),
+        }
+    }
+    
+    /// 监视的检查间隔，未启用监视时返回 None
+    pub fn watch_interval(&self) -> Option<Duration> {
+        self.watch.then(|| Duration::from_secs(self.watch_interval_secs.max(1)))
+    }
+}
+
+impl DisplayConfig {
+    /// 项目的标签（按名称排序）
+    pub fn tags_for(&self, path: &Path) -> Vec<String> {
+        self.project_tags.get(&path.display().to_string())
+            .map(|tags| tags.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+    
+    /// 为项目添加或移除标签，没有标签的项目不再保留记录
+    pub fn set_tag(&mut self, path: &Path, tag: &str, tagged: bool) {
+        let key = path.display().to_string();
+        if tagged {
+            self.project_tags.entry(key).or_default().insert(tag.to_string());
+        } else if let Some(tags) = self.project_tags.get_mut(&key) {
+            tags.remove(tag);
+            if tags.is_empty() {
+                self.project_tags.remove(&key);
+            }
         }
     }
+    
+    /// 带有指定标签的项目路径（按路径排序）
+    pub fn projects_tagged(&self, tag: &str) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self.project_tags.iter()
+            .filter(|(_, tags)| tags.contains(tag))
+            .map(|(path, _)| PathBuf::from(path))
+            .collect();
+        paths.sort();
+        paths
+    }
 }
 
 impl CacheConfig {
@@ -287,25 +875,139 @@ impl CacheConfig {
             enabled: self.enabled,
             expiry_duration: self.expiry_duration,
             max_entries: self.max_entries,
+            backup_count: self.backup_count,
+            hash_manifests: self.hash_manifests,
+            validation: self.validation,
         }
     }
 }
 
-/// 部分配置结构体，用于向后兼容
-#[derive(Debug, Serialize, Deserialize)]
-struct PartialConfig {
-    /// 扫描路径
-    pub scan_paths: Option<Vec<String>>,
-    
-    /// 忽略配置
-    pub ignore: Option<ProjectIgnoreConfig>,
-    
-    /// 扫描配置
-    pub scan: Option<ScanConfig>,
-    
-    /// 显示配置
-    pub display: Option<DisplayConfig>,
-    
-    /// 缓存配置
-    pub cache: Option<CacheConfig>,
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_check_operation_path() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().join("code");
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        let mut config = Config {
+            scan_paths: vec![root.display().to_string()],
+            ..Config::default()
+        };
+
+        // 未开启时不检查
+        assert!(config.check_operation_path(Path::new("/etc/hosts")).is_ok());
+
+        config.cleanup.restrict_to_scan_paths = true;
+        assert!(config.check_operation_path(&root.join("app")).is_ok());
+        assert!(config.check_operation_path(&root.join("app/node_modules")).is_ok());
+        assert!(config.check_operation_path(&root).is_err());
+        assert!(config.check_operation_path(&temp_dir.path().join("code-old")).is_err());
+        assert!(config.check_operation_path(&root.join("../outside")).is_err());
+    }
+
+    #[test]
+    fn test_ignore_excludes() {
+        let mut ignore = ProjectIgnoreConfig {
+            directories: HashSet::from(["vendor".to_string(), "code".to_string()]),
+            paths: HashSet::from(["/code/old".to_string()]),
+            ..ProjectIgnoreConfig::default()
+        };
+        let roots = ["/code".to_string()];
+
+        // 目录名只与扫描根目录以下的各级目录比较
+        assert!(!ignore.excludes(Path::new("/code/api"), &roots));
+        assert!(ignore.excludes(Path::new("/code/vendor/lib"), &roots));
+        assert!(ignore.excludes(Path::new("/code/old/app"), &roots));
+        assert!(ignore.excludes(Path::new("/elsewhere/vendor"), &roots));
+
+        ignore.projects.insert("/code/old/app".to_string());
+        assert_eq!(ignore.source(Path::new("/code/old/app"), &roots), Some(IgnoreSource::Manual));
+        assert_eq!(ignore.source(Path::new("/code/old/web"), &roots), Some(IgnoreSource::Path("/code/old".to_string())));
+        assert_eq!(ignore.source(Path::new("/code/vendor/lib"), &roots), Some(IgnoreSource::Directory("vendor".to_string())));
+        assert!(!ignore.excludes(Path::new("/code/api"), &roots));
+        assert_eq!(ignore.source(Path::new("/code/api"), &roots), None);
+    }
+
+    #[test]
+    fn test_project_tags() {
+        let mut display = DisplayConfig::default();
+        display.set_tag(Path::new("/code/web"), "archive-candidate", true);
+        display.set_tag(Path::new("/code/api"), "archive-candidate", true);
+        display.set_tag(Path::new("/code/api"), "work", true);
+
+        assert_eq!(display.tags_for(Path::new("/code/api")), ["archive-candidate", "work"]);
+        assert_eq!(display.projects_tagged("archive-candidate"), [PathBuf::from("/code/api"), PathBuf::from("/code/web")]);
+
+        display.set_tag(Path::new("/code/web"), "archive-candidate", false);
+        assert!(display.tags_for(Path::new("/code/web")).is_empty());
+        assert!(!display.project_tags.contains_key("/code/web"));
+        assert_eq!(display.projects_tagged("archive-candidate"), [PathBuf::from("/code/api")]);
+    }
+
+    #[test]
+    fn test_load_legacy_config_file() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        let legacy = "scan_paths = [\"/code\"]\nunknown_option = 1\n\n[git]\nstale_branch_days = 30\n";
+        std::fs::write(&path, legacy).unwrap();
+
+        let (config, from_version) = Config::read_and_migrate(&path).unwrap();
+        assert_eq!(from_version, 0);
+        assert_eq!(config.version, migration::CURRENT_VERSION);
+        assert_eq!(config.scan_paths, ["/code"]);
+        assert_eq!(config.git.stale_branch_days, 30);
+        assert_eq!(config.scan.max_depth, ScanConfig::default().max_depth);
+
+        // 只在内存中迁移，原文件保持不变
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), legacy);
+
+        let backup = Config::backup_config_file(&path, "v0").unwrap();
+        assert_eq!(backup, temp_dir.path().join("config.toml.v0.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), legacy);
+    }
+
+    #[test]
+    fn test_default_config_round_trip() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        Config::default().save_to_file(&path).unwrap();
+
+        let (config, from_version) = Config::read_and_migrate(&path).unwrap();
+        assert_eq!(from_version, migration::CURRENT_VERSION);
+        assert_eq!(config.version, migration::CURRENT_VERSION);
+    }
+
+    #[test]
+    fn test_env_vars_expanded_on_load_and_kept_on_save() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        std::env::set_var("PM_CLI_TEST_WORKSPACE_ROOT", "/ci/workspace");
+
+        let mut config = Config {
+            scan_paths: vec!["${PM_CLI_TEST_WORKSPACE_ROOT}/code".to_string()],
+            ..Config::default()
+        };
+        config.ignore.paths.insert("${PM_CLI_TEST_WORKSPACE_ROOT}/vendor".to_string());
+        config.save_to_file(&path).unwrap();
+
+        let mut config = Config::load_from_file(&path).unwrap();
+        assert_eq!(config.scan_paths, ["/ci/workspace/code"]);
+        assert!(config.ignore.paths.contains("/ci/workspace/vendor"));
+
+        // 保存时写回原始写法，新增的值保持不变
+        config.scan_paths.push("/other".to_string());
+        config.save_to_file(&path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("${PM_CLI_TEST_WORKSPACE_ROOT}/code"));
+        assert!(content.contains("${PM_CLI_TEST_WORKSPACE_ROOT}/vendor"));
+        assert!(content.contains("/other"));
+
+        config.scan_paths = vec!["${PM_CLI_TEST_UNSET_VAR}/code".to_string()];
+        config.save_to_file(&path).unwrap();
+        let error = Config::load_from_file(&path).unwrap_err();
+        assert!(error.to_string().contains("环境变量 PM_CLI_TEST_UNSET_VAR 未设置"));
+    }
+}
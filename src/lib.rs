@@ -6,4 +6,5 @@ pub mod tui;
 pub mod operations;
 
 // 重新导出常用模块
-pub use scanner::SizeCalculator;
\ No newline at end of file
+pub use scanner::SizeCalculator;
+pub use scanner::{scan_stream, ScanEvent};
\ No newline at end of file
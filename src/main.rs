@@ -9,35 +9,89 @@ mod utils;
 use anyhow::Result;
 use clap::Parser;
 
-use cli::{Cli, Commands, ConfigAction};
+use cli::{CacheAction, Cli, Commands, ConfigAction, GitAction, OutputFormat, SnapshotAction, TelemetryAction};
 use config::Config;
+use config::settings::TrivialProjectMode;
+use chrono::{Datelike, Utc};
+use models::{ByteSize, Project};
+use models::scan_result::ScanResult;
+use operations::archive::{ArchiveOperation, ConflictPolicy};
+use operations::cleanup::CleanupOperation;
+use operations::doctor::{self, Diagnosis};
+use operations::report::{self, SizeReport};
+use operations::scan_output::{self, Column, ScanRecord};
+use operations::snapshot_diff::SnapshotDiff;
+use operations::suggest;
+use operations::telemetry::{UsageEvent, UsageKind, UsageLog, YearSummary};
+use operations::remote_check;
+use operations::unpushed;
+use operations::wizard::{CleanupWizard, WizardAction};
+use futures::StreamExt;
+use scanner::{scan_stream, stream, FileWalker, GitAnalyzer, ProjectDetector, ScanEvent, SizeCache, SizeCalculator};
+use scanner::access::AccessChecker;
+use scanner::size_cache::CacheStats;
 use tui::app::App;
+use tui::session::SessionSnapshot;
+use utils::{completion, editor, path_match, signal, size_format, time_format};
+use utils::csv::Delimiter;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// 统计报告中列出的增长最多的项目数
+const REPORT_TOP_GROWERS: usize = 5;
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 初始化日志，设置日志级别为 INFO
+    // 初始化日志，设置日志级别为 INFO；日志输出到 stderr，避免混入 scan 等命令的输出
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::INFO)
+        .with_writer(std::io::stderr)
         .init();
     
     let cli = Cli::parse();
     
-    // 加载配置
-    let config = if let Some(config_path) = cli.config {
+    // 加载配置；TUI 中修改的设置写回同一个文件
+    let config_path = match &cli.config {
+        Some(path) => path.clone(),
+        None => Config::default_config_path()?,
+    };
+    let mut config = if cli.config.is_some() {
         Config::load_from_file(&config_path)?
     } else {
         Config::load_or_create_default()?
     };
     
+    // 命令行参数只覆盖本次运行的配置，TUI 保存设置时不会写入配置文件
+    if cli.no_mouse {
+        config.display.mouse = false;
+    }
+    if cli.allow_outside_roots {
+        config.cleanup.restrict_to_scan_paths = false;
+    }
+    
+    if let Some(min_size) = cli.min_size {
+        config.scan.min_project_size = min_size;
+    }
+    if let Some(url) = cli.notify {
+        config.notify.webhook_url = Some(url);
+    }
+    if cli.skip_trivial {
+        config.scan.trivial_projects = TrivialProjectMode::Skip;
+        // 未设置阈值时使用 1 MB
+        if config.scan.min_project_size == 0 {
+            config.scan.min_project_size = 1024 * 1024;
+        }
+    }
+    size_format::set_display_unit(config.display.size_unit);
+    
     // 根据命令执行相应操作
     match cli.command {
-        Some(Commands::Scan { paths, depth, format: _, output: _ }) => {
-            println!("扫描功能待实现");
-            println!("扫描路径: {:?}", paths);
-            if let Some(d) = depth {
-                println!("最大深度: {}", d);
+        Some(Commands::Scan { paths, depth, format, columns, output }) => {
+            if depth.is_some() {
+                config.scan.max_depth = depth;
             }
+            run_scan(paths, format, columns, output, config).await?;
         }
         Some(Commands::Tui { paths }) => {
             let scan_paths = if paths.is_empty() {
@@ -46,15 +100,23 @@ async fn main() -> Result<()> {
                 paths
             };
             
-            let mut app = App::new(config, scan_paths);
+            let mut app = App::new(config, scan_paths).with_config_path(config_path);
             app.run().await?;
         }
-        Some(Commands::Clean { project_path, clean_type: _, force }) => {
-            println!("清理功能待实现");
-            println!("项目路径: {}", project_path);
-            println!("强制执行: {}", force);
+        Some(Commands::Clean { project_path, tag, clean_type, force, dry_run }) => {
+            let project_paths = match (project_path, tag) {
+                (Some(project_path), _) => vec![std::path::PathBuf::from(normalize_path(&project_path)?)],
+                (None, Some(tag)) => {
+                    let paths = config.display.projects_tagged(&tag);
+                    println!("🏷️  带标签 #{} 的项目共 {} 个", tag, paths.len());
+                    paths
+                }
+                (None, None) => Vec::new(),
+            };
+            clean_projects(project_paths, clean_type, force, dry_run, &config).await?;
         }
         Some(Commands::Delete { project_path, force }) => {
+            config.check_operation_path(Path::new(&normalize_path(&project_path)?))?;
             println!("删除功能待实现");
             println!("项目路径: {}", project_path);
             println!("强制执行: {}", force);
@@ -62,10 +124,37 @@ async fn main() -> Result<()> {
         Some(Commands::Config { action }) => {
             handle_config_command(action, &config).await?;
         }
-        Some(Commands::Stats { paths, detailed }) => {
-            println!("统计功能待实现");
-            println!("分析路径: {:?}", paths);
-            println!("详细统计: {}", detailed);
+        Some(Commands::Suggest { paths, limit }) => {
+            suggest_cleanup(paths, limit, config).await?;
+        }
+        Some(Commands::CleanupWizard { paths, limit, on_conflict }) => {
+            run_cleanup_wizard(paths, limit, on_conflict, config).await?;
+        }
+        Some(Commands::Stats { paths, detailed, format, columns, stale }) => {
+            run_stats(paths, detailed, format, columns, stale, config).await?;
+        }
+        Some(Commands::Git { action: GitAction::Unpushed { paths, check_remotes } }) => {
+            report_unpushed_work(paths, check_remotes, config).await?;
+        }
+        Some(Commands::Snapshot { action }) => {
+            handle_snapshot_command(action, config, config_path).await?;
+        }
+        Some(Commands::Cache { action }) => {
+            handle_cache_command(action, &config).await?;
+        }
+        Some(Commands::Telemetry { action }) => {
+            handle_telemetry_command(action, &config)?;
+        }
+        Some(Commands::Doctor) => {
+            run_doctor(&config_path, &config).await;
+        }
+        Some(Commands::CompleteProjects) => {
+            // 补全时不输出错误，没有缓存时不提供候选
+            if let Ok(snapshot) = SessionSnapshot::default_path()
+                .and_then(|path| SessionSnapshot::load_from_file(&path))
+            {
+                print!("{}", completion::zsh_candidates(&snapshot.projects));
+            }
         }
         None => {
             // 默认启动 TUI 模式
@@ -75,7 +164,7 @@ async fn main() -> Result<()> {
                 cli.paths
             };
             
-            let mut app = App::new(config, scan_paths);
+            let mut app = App::new(config, scan_paths).with_config_path(config_path);
             app.run().await?;
         }
     }
@@ -83,14 +172,684 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// 清理一个或多个项目的依赖目录（可只清理指定名称的目录），先列出所有项目的清理计划并统一确认；
+/// 预览模式只列出计划不删除，Ctrl+C 时完成当前目录后输出部分结果
+async fn clean_projects(project_paths: Vec<std::path::PathBuf>, only: Vec<String>, force: bool, dry_run: bool, config: &Config) -> Result<()> {
+    use std::io::{self, Write};
+
+    let operation = CleanupOperation::from_config(config).with_only(only);
+    let mut targets = Vec::new();
+    for project_path in project_paths {
+        config.check_operation_path(&project_path)?;
+        let plan = operation.plan(&project_path).await;
+
+        for dir in operation.kept_dirs(&project_path) {
+            println!("🔒 保留目录: {}", dir.display());
+        }
+
+        if plan.is_empty() {
+            println!("✨ 没有需要清理的依赖目录: {}", project_path.display());
+            continue;
+        }
+
+        let native_cleaner = operation.native_cleaner(&project_path);
+        if let Some(cleaner) = &native_cleaner {
+            println!("🔧 将先运行 {}，再删除剩余目录", cleaner);
+        }
+
+        print!("🧹 {}", plan);
+        targets.push((project_path, native_cleaner));
+    }
+
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("💡 预览模式，未删除任何目录");
+        return Ok(());
+    }
+
+    if !force {
+        print!("确认要继续吗？ (y/N): ");
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim().to_lowercase();
+        if input != "y" && input != "yes" {
+            println!("❌ 操作已取消");
+            return Ok(());
+        }
+    }
+
+    let token = signal::install_shutdown_handler();
+    let usage = UsageLog::new(config.telemetry.enabled);
+
+    for (project_path, native_cleaner) in &targets {
+        if let Some(cleaner) = native_cleaner {
+            println!("🔧 正在运行 {} ...", cleaner);
+        }
+        let mut summary = operation.clean_project(project_path, &token).await;
+        print!("{}", summary);
+        if !summary.removed.is_empty() {
+            let mut event = UsageEvent::new(UsageKind::Clean)
+                .with_directories(summary.removed.len())
+                .with_freed(ByteSize::new(summary.freed_bytes()));
+            if let Some(project_type) = ProjectDetector::marker_types(project_path).first() {
+                event = event.with_project_type(project_type.as_str());
+            }
+            usage.record(event);
+        }
+
+        if summary.cancelled {
+            std::process::exit(130);
+        }
+        summary.wait_for_deletion().await;
+    }
+
+    Ok(())
+}
+
+/// 扫描路径中的项目并分析 Git 状态，供清理候选排序使用
+async fn scan_cleanup_candidates(paths: Vec<String>, config: Config) -> Result<Vec<Project>> {
+    let paths = paths.iter()
+        .map(|path| normalize_path(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    println!("🔍 正在扫描: {}", paths.join(", "));
+    let (detected, skipped) = FileWalker::new(config).scan_paths_with_skipped(&paths).await?;
+    if !skipped.is_empty() {
+        println!("⚠️  {}", skipped);
+    }
+
+    let git_analyzer = GitAnalyzer::new();
+    Ok(detected.into_iter()
+        .map(|detected| stream::analyze_detected(detected, &git_analyzer))
+        .collect())
+}
+
+/// 扫描项目并列出最值得优先清理的项目
+async fn suggest_cleanup(paths: Vec<String>, limit: usize, config: Config) -> Result<()> {
+    let projects = scan_cleanup_candidates(paths, config).await?;
+    let candidates = suggest::rank_cleanup_candidates(&projects, Utc::now());
+    if candidates.is_empty() {
+        println!("✨ 没有可清理的依赖目录");
+        return Ok(());
+    }
+
+    println!("🧹 建议优先清理 (共 {} 个项目):", candidates.len());
+    for (i, candidate) in candidates.iter().take(limit).enumerate() {
+        println!(
+            "  {}. {} [评分 {:.1}]",
+            i + 1,
+            candidate.project.name,
+            candidate.score,
+        );
+        println!("     {}", candidate.project.path.display());
+        println!("     {}", candidate.reasons().join(" · "));
+    }
+
+    let reclaimable: ByteSize = candidates.iter().take(limit).map(|c| c.reclaimable).sum();
+    println!("💾 以上项目共可释放 {}", reclaimable);
+    println!("使用 `project-manager-cli clean <路径>` 清理单个项目");
+
+    let cloud_warnings = suggest::cloud_synced_projects(&projects);
+    if !cloud_warnings.is_empty() {
+        println!();
+        println!("☁️  以下项目位于云同步目录中，依赖目录会被反复同步 (共 {} 个):", cloud_warnings.len());
+        for warning in cloud_warnings.iter().take(limit) {
+            println!("  - {} ({})", warning.project.name, warning.project.path.display());
+            println!("     {}", warning.advice());
+        }
+        println!("也可以用 `project-manager-cli config ignore <路径>` 不再扫描这些项目");
+    }
+
+    Ok(())
+}
+
+/// 列出所有有未保存工作的仓库
+async fn report_unpushed_work(paths: Vec<String>, check_remotes: bool, config: Config) -> Result<()> {
+    let projects = scan_cleanup_candidates(paths, config).await?;
+    let repo_count = projects.iter().filter(|project| project.git_info.is_some()).count();
+    let repos = unpushed::repos_with_unpushed_work(&projects);
+    if repos.is_empty() {
+        println!("✅ {} 个仓库的工作都已提交并推送", repo_count);
+    } else {
+        println!("⚠️  {} 个仓库（共 {} 个）有未保存的工作:", repos.len(), repo_count);
+        for repo in &repos {
+            println!("  • {}", repo);
+        }
+    }
+
+    if check_remotes {
+        println!("🔌 正在检查远程仓库...");
+        let checks = remote_check::check_remotes(&projects).await;
+        let lost: Vec<_> = checks.iter().filter(|check| check.status.is_lost()).collect();
+        let unverified: Vec<_> = checks.iter()
+            .filter(|check| matches!(check.status, remote_check::RemoteStatus::Unverified(_)))
+            .collect();
+
+        if lost.is_empty() {
+            println!("✅ {} 个远程仓库中没有丢失的", checks.len());
+        } else {
+            println!("⚠️  {} 个仓库的远程仓库已丢失，删除本地仓库将失去最后一份副本:", lost.len());
+            for check in &lost {
+                println!("  • {}", check);
+            }
+        }
+        if !unverified.is_empty() {
+            println!("❔ {} 个远程仓库无法确认:", unverified.len());
+            for check in &unverified {
+                println!("  • {}", check);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 交互式清理向导：依次展示清理候选项目，逐个选择清理、跳过、归档或删除
+///
+/// 归档目录中已有同名项目时按 `on_conflict` 处理，未指定时逐个询问。
+async fn run_cleanup_wizard(paths: Vec<String>, limit: usize, on_conflict: Option<ConflictPolicy>, config: Config) -> Result<()> {
+    use std::io::{self, Write};
+
+    let archive = |policy: Option<ConflictPolicy>| ArchiveOperation::new(&config.archive.dir)
+        .with_cleanup(CleanupOperation::from_config(&config))
+        .with_conflict_policy(policy);
+    let cleanup = CleanupOperation::from_config(&config);
+    let usage = UsageLog::new(config.telemetry.enabled);
+    let projects = scan_cleanup_candidates(paths, config.clone()).await?;
+    let candidates: Vec<_> = suggest::rank_cleanup_candidates(&projects, Utc::now())
+        .into_iter()
+        .take(limit)
+        .collect();
+    if candidates.is_empty() {
+        println!("✨ 没有可清理的依赖目录");
+        return Ok(());
+    }
+
+    let mut wizard = CleanupWizard::new(candidates.iter().map(|c| c.project.path.clone()).collect());
+    let token = signal::install_shutdown_handler();
+
+    for candidate in &candidates {
+        let project = candidate.project;
+        let (index, total) = wizard.progress();
+        println!();
+        println!("[{}/{}] {} [评分 {:.1}]", index, total, project.name, candidate.score);
+        println!("  {}", project.path.display());
+        println!("  {}", candidate.reasons().join(" · "));
+        let unsaved_warnings = project.unsaved_work_warnings();
+        for warning in &unsaved_warnings {
+            println!("  ⚠️  {}", warning);
+        }
+
+        let action = loop {
+            print!("[c] 清理依赖  [s] 跳过  [a] 归档  [d] 删除  [q] 退出: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            match input.trim().to_lowercase().as_str() {
+                "c" => break Some(WizardAction::Clean),
+                "s" | "" => break Some(WizardAction::Skip),
+                "a" => break Some(WizardAction::Archive),
+                "d" => break Some(WizardAction::Delete),
+                "q" => break None,
+                _ => println!("无效的选择"),
+            }
+        };
+        let Some(mut action) = action.filter(|_| !token.is_cancelled()) else {
+            break;
+        };
+
+        // 有未保存工作的项目删除前需要再次确认
+        if action == WizardAction::Delete && !unsaved_warnings.is_empty() {
+            print!("项目有未保存的工作，输入 yes 确认删除: ");
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            if input.trim() != "yes" {
+                action = WizardAction::Skip;
+            }
+        }
+
+        if action != WizardAction::Skip {
+            if let Err(e) = config.check_operation_path(&project.path) {
+                println!("  ⛔ {}", e);
+                action = WizardAction::Skip;
+            }
+        }
+
+        // 归档目录中已有同名项目时选择覆盖、重命名或跳过
+        let mut policy = None;
+        if let Some(existing) = archive(None).conflicting_target(&project.path).filter(|_| action == WizardAction::Archive) {
+            println!("  ⚠️  归档目录中已有同名项目: {}", existing.display());
+            let renamed = archive(None).renamed_target(&project.path);
+            policy = match on_conflict {
+                Some(policy) => Some(policy),
+                None => loop {
+                    print!(
+                        "[o] 覆盖（已有目录移到回收站）  [r] 重命名为 {}  [s] 跳过: ",
+                        renamed.file_name().unwrap_or_default().to_string_lossy()
+                    );
+                    io::stdout().flush()?;
+
+                    let mut input = String::new();
+                    io::stdin().read_line(&mut input)?;
+                    match input.trim().to_lowercase().as_str() {
+                        "o" => break Some(ConflictPolicy::Overwrite),
+                        "r" => break Some(ConflictPolicy::Rename),
+                        "s" | "" => break Some(ConflictPolicy::Skip),
+                        _ => println!("无效的选择"),
+                    }
+                },
+            };
+            if policy == Some(ConflictPolicy::Skip) {
+                action = WizardAction::Skip;
+            }
+        }
+
+        wizard.advance(action);
+        let result = match action {
+            WizardAction::Clean => {
+                let mut summary = cleanup.clean_project(&project.path, &token).await;
+                summary.wait_for_deletion().await;
+                Ok(ByteSize::new(summary.freed_bytes()))
+            }
+            WizardAction::Archive => archive(policy).archive_project(&project.path, &token).await
+                .map(|result| {
+                    println!("  📦 已归档到 {}", result.target.display());
+                    ByteSize::new(result.cleanup.freed_bytes())
+                })
+                .map_err(|e| e.to_string()),
+            WizardAction::Delete => trash::delete(&project.path)
+                .map(|_| project.disk_usage())
+                .map_err(|e| e.to_string()),
+            WizardAction::Skip => Ok(ByteSize::ZERO),
+        };
+
+        match &result {
+            Ok(freed) if action != WizardAction::Skip => {
+                println!("  ✓ {}完成，释放 {}", action.label(), freed);
+                if let Some(event) = UsageEvent::for_wizard_action(action, project, *freed) {
+                    usage.record(event);
+                }
+            }
+            Err(e) => println!("  ✗ {}失败: {}", action.label(), e),
+            _ => {}
+        }
+        wizard.record_result(action, result);
+        println!("  💾 {}", wizard);
+
+        if token.is_cancelled() {
+            break;
+        }
+    }
+
+    println!();
+    println!("🏁 清理向导结束: {}", wizard);
+
+    Ok(())
+}
+
+/// 扫描项目并计算各项目大小，进度和警告输出到 stderr
+async fn scan_projects_with_sizes(paths: Vec<String>, config: Config) -> Result<Vec<Project>> {
+    eprintln!("🔍 正在扫描: {}", paths.join(", "));
+    let usage = UsageLog::new(config.telemetry.enabled);
+    let mut projects: Vec<Project> = Vec::new();
+    let mut events = scan_stream(paths, config);
+    let interrupted = signal::wait_for_signal();
+    tokio::pin!(interrupted);
+    loop {
+        // 收到 SIGINT/SIGTERM 时停止接收，扫描任务随之结束
+        let event = tokio::select! {
+            event = events.next() => event,
+            Ok(()) = &mut interrupted => anyhow::bail!("扫描已中断"),
+        };
+        let Some(event) = event else {
+            break;
+        };
+        match event {
+            ScanEvent::CacheRecovered(recovery) => eprintln!("⚠️  {}", recovery),
+            ScanEvent::LocationsSkipped(skipped) => eprintln!("⚠️  {}", skipped),
+            ScanEvent::ProjectFound(project) => projects.push(*project),
+            ScanEvent::SizeCalculated { path, size } => {
+                if let Some(project) = projects.iter_mut().find(|project| project.path == path) {
+                    project.code_size = size.code_size;
+                    project.cached_dependency_size = Some(size.dependency_size);
+                    project.total_size = size.total_size;
+                    project.gitignore_excluded_size = size.gitignore_excluded_size;
+                    project.size_truncated = size.truncated;
+                    if let Some(modified) = size.last_modified {
+                        project.last_modified = modified.into();
+                    }
+                }
+                if !size.broken_links.is_empty() {
+                    eprintln!("⚠️  {} 中有 {} 个失效的符号链接，已按链接自身计算大小", path.display(), size.broken_links.len());
+                }
+            }
+            ScanEvent::SizeFailed { path, error } => tracing::warn!("计算 {} 的大小失败: {}", path.display(), error),
+            ScanEvent::Failed(error) => anyhow::bail!("扫描失败: {}", error),
+            ScanEvent::Completed { project_count } => {
+                eprintln!("✅ 已统计 {} 个项目", project_count);
+                usage.record(UsageEvent::new(UsageKind::Scan).with_projects(project_count));
+            }
+        }
+    }
+
+    Ok(projects)
+}
+
+/// `--columns` 只适用于 CSV/TSV，在扫描前检查以免白白等待
+fn check_columns(format: &OutputFormat, columns: &[Column]) -> Result<()> {
+    if !columns.is_empty() && !matches!(format, OutputFormat::Csv | OutputFormat::Tsv) {
+        anyhow::bail!("--columns 只适用于 csv 和 tsv 格式");
+    }
+    Ok(())
+}
+
+/// 按输出格式生成项目记录；`columns` 为空时 CSV/TSV 输出默认列
+fn render_records(records: &[ScanRecord], format: &OutputFormat, columns: &[Column]) -> Result<String> {
+    let columns = if columns.is_empty() { &Column::DEFAULT[..] } else { columns };
+    Ok(match format {
+        OutputFormat::Table => scan_output::to_table(records),
+        OutputFormat::Json => serde_json::to_string_pretty(records)? + "\n",
+        OutputFormat::Csv => scan_output::to_delimited(records, columns, Delimiter::Comma),
+        OutputFormat::Tsv => scan_output::to_delimited(records, columns, Delimiter::Tab),
+        OutputFormat::Markdown => scan_output::to_markdown(records),
+    })
+}
+
+/// 扫描项目并按指定格式输出到终端或文件
+async fn run_scan(paths: Vec<String>, format: OutputFormat, columns: Vec<Column>, output: Option<PathBuf>, config: Config) -> Result<()> {
+    check_columns(&format, &columns)?;
+    let paths = paths.iter()
+        .map(|path| normalize_path(path))
+        .collect::<Result<Vec<_>>>()?;
+    let projects = scan_projects_with_sizes(paths, config).await?;
+    let records: Vec<ScanRecord> = projects.iter().map(ScanRecord::from).collect();
+
+    let content = render_records(&records, &format, &columns)?;
+    match output {
+        Some(path) => {
+            std::fs::write(&path, content)
+                .map_err(|e| anyhow::anyhow!("无法写入 {}: {}", path.display(), e))?;
+            eprintln!("💾 已保存 {} 个项目到 {}", records.len(), path.display());
+        }
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// 统计项目大小，与上次运行的报告比较，并按配置发送 webhook 通知
+///
+/// table 以外的格式只向 stdout 输出按大小排序的各项目明细，提示信息输出到 stderr。
+async fn run_stats(paths: Vec<String>, detailed: bool, format: OutputFormat, columns: Vec<Column>, stale: Option<u64>, config: Config) -> Result<()> {
+    check_columns(&format, &columns)?;
+    let machine_readable = !matches!(format, OutputFormat::Table);
+    let webhook_url = config.notify.webhook_url.clone();
+    let paths = paths.iter()
+        .map(|path| normalize_path(path))
+        .collect::<Result<Vec<_>>>()?;
+    let mut result = ScanResult::new(paths.clone());
+    let projects = scan_projects_with_sizes(paths, config).await?;
+    result.add_projects(projects.iter().filter(|project| !project.is_ignored).cloned());
+    result.finish_scan();
+    let truncated: Vec<&str> = projects.iter()
+        .filter(|project| project.size_truncated)
+        .map(|project| project.name.as_str())
+        .collect();
+    if !truncated.is_empty() {
+        eprintln!("⚠️  以下项目达到统计上限（[scan] max_files_per_project / max_bytes_per_project），大小不完整: {}", truncated.join(", "));
+    }
+    if let Some(max_idle_days) = stale {
+        return report_stale_projects(&projects, max_idle_days, &format, &columns);
+    }
+
+    let report = SizeReport::from_projects(&projects);
+    let report_path = SizeReport::default_path()?;
+    let previous = SizeReport::load_from_file(&report_path).ok();
+    let summary = report.summarize(previous.as_ref(), REPORT_TOP_GROWERS);
+
+    let records: Vec<ScanRecord> = result.largest_projects(result.projects.len()).into_iter()
+        .map(ScanRecord::from)
+        .collect();
+    if machine_readable {
+        print!("{}", render_records(&records, &format, &columns)?);
+    } else {
+        println!("📊 项目统计");
+        print!("{}", result);
+        print!("{}", summary);
+        if detailed {
+            println!("各项目明细:");
+            print!("{}", scan_output::to_table(&records));
+        }
+    }
+
+    if let Err(e) = report.save_to_file(&report_path) {
+        tracing::warn!("保存报告失败: {}", e);
+    }
+
+    // 报告已经输出并保存，通知失败只警告，不影响命令结果
+    if let Some(url) = webhook_url {
+        match report::send_webhook(&url, &summary).await {
+            Ok(()) => eprintln!("📨 已发送报告到 webhook"),
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+
+    Ok(())
+}
+
+/// 列出长期未活动的项目，按可释放的依赖大小排序，并给出归档建议
+fn report_stale_projects(projects: &[Project], max_idle_days: u64, format: &OutputFormat, columns: &[Column]) -> Result<()> {
+    let stale = suggest::stale_projects(projects, max_idle_days, Utc::now());
+    if !matches!(format, OutputFormat::Table) {
+        let records: Vec<ScanRecord> = stale.iter().map(|stale| ScanRecord::from(stale.project)).collect();
+        print!("{}", render_records(&records, format, columns)?);
+        return Ok(());
+    }
+    if stale.is_empty() {
+        println!("✨ 没有超过 {} 天未活动的项目", max_idle_days);
+        return Ok(());
+    }
+
+    println!("🕸️  超过 {} 天未活动的项目 (共 {} 个):", max_idle_days, stale.len());
+    for (i, stale) in stale.iter().enumerate() {
+        println!("  {}. {} [{} 天未活动 · 依赖 {} · 共 {}]", i + 1, stale.project.name, stale.idle_days, stale.reclaimable, stale.project.disk_usage());
+        println!("     {}", stale.project.path.display());
+        println!("     💡 {}", stale.advice());
+    }
+
+    let reclaimable: ByteSize = stale.iter().map(|stale| stale.reclaimable).sum();
+    let total: ByteSize = stale.iter().map(|stale| stale.project.disk_usage()).sum();
+    println!("💾 清理依赖可释放 {}，全部归档或删除可释放 {}", reclaimable, total);
+    println!("使用 `project-manager-cli clean <路径>` 清理依赖，或在 cleanup-wizard 中按 a 归档");
+    Ok(())
+}
+
+/// 处理本地使用统计相关命令
+fn handle_telemetry_command(action: TelemetryAction, config: &Config) -> Result<()> {
+    let path = UsageLog::default_path()?;
+    match action {
+        TelemetryAction::Summary { year } => {
+            let events = UsageLog::load(&path)?;
+            print!("{}", YearSummary::from_events(&events, year.unwrap_or_else(|| Utc::now().year())));
+            if !config.telemetry.enabled {
+                println!("💡 使用统计未开启，运行 `project-manager-cli telemetry enable` 开始在本地记录");
+            }
+        }
+        TelemetryAction::Enable | TelemetryAction::Disable => {
+            let enabled = matches!(action, TelemetryAction::Enable);
+            let config_path = Config::default_config_path()?;
+            let mut config = Config::load_or_create_default()?;
+            config.telemetry.enabled = enabled;
+            config.save_to_file(&config_path)?;
+            if enabled {
+                println!("✅ 已开启使用统计，记录保存在 {}（不含路径和项目名，不会上传）", path.display());
+            } else {
+                println!("✅ 已停止记录使用统计，已有记录可用 `project-manager-cli telemetry clear` 删除");
+            }
+        }
+        TelemetryAction::Export { file } => {
+            let events = UsageLog::load(&path)?;
+            std::fs::write(&file, serde_json::to_string_pretty(&events)? + "\n")
+                .map_err(|e| anyhow::anyhow!("无法写入 {}: {}", file.display(), e))?;
+            println!("💾 已导出 {} 条使用记录到 {}", events.len(), file.display());
+        }
+        TelemetryAction::Clear => match std::fs::remove_file(&path) {
+            Ok(()) => println!("🗑️  已删除使用记录: {}", path.display()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => println!("✨ 没有使用记录"),
+            Err(e) => anyhow::bail!("无法删除 {}: {}", path.display(), e),
+        },
+    }
+    Ok(())
+}
+
+/// 处理快照相关命令
+async fn handle_snapshot_command(action: SnapshotAction, config: Config, config_path: PathBuf) -> Result<()> {
+    match action {
+        SnapshotAction::Save { file, paths } => {
+            let paths = paths.iter()
+                .map(|path| normalize_path(path))
+                .collect::<Result<Vec<_>>>()?;
+            let projects = scan_projects_with_sizes(paths.clone(), config).await?;
+            SessionSnapshot::new(&projects)
+                .with_scan_paths(paths)
+                .save_to_file(&file)?;
+            let total_size: ByteSize = projects.iter().map(|project| project.disk_usage()).sum();
+            println!(
+                "📸 已保存 {} 个项目（共 {}）到 {}",
+                projects.len(),
+                total_size,
+                file.display()
+            );
+        }
+        SnapshotAction::Browse { file } => {
+            let snapshot = load_snapshot(&file)?;
+            let mut app = App::from_snapshot(config, file, snapshot).with_config_path(config_path);
+            app.run().await?;
+        }
+        SnapshotAction::Diff { old, new, format } => {
+            let diff = SnapshotDiff::between(&load_snapshot(&old)?, &load_snapshot(&new)?);
+            match format {
+                OutputFormat::Table => print!("{}", diff),
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+                OutputFormat::Csv => print!("{}", diff.to_delimited(Delimiter::Comma)),
+                OutputFormat::Tsv => print!("{}", diff.to_delimited(Delimiter::Tab)),
+                OutputFormat::Markdown => print!("{}", diff.to_markdown()),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 处理大小缓存相关命令
+async fn handle_cache_command(action: CacheAction, config: &Config) -> Result<()> {
+    if !config.cache.enabled {
+        println!("💾 大小缓存未启用（[cache] enabled = false）");
+        return Ok(());
+    }
+    let mut calculator = SizeCalculator::new_with_cache(config.cache.to_size_cache_config()).await?;
+    if let Some(recovery) = calculator.cache_recovery() {
+        eprintln!("⚠️  {}", recovery);
+    }
+    
+    match action {
+        CacheAction::Stats => {
+            let Some(stats) = calculator.get_cache_stats() else {
+                return Ok(());
+            };
+            println!("💾 大小缓存: {} ({})", stats.cache_file.display(), size_format::format_size(stats.cache_file_size));
+            println!("  条目数: {}（已过期 {} 个，Git 仓库 {} 个）", stats.total_entries, stats.expired_entries, stats.git_repositories);
+            println!(
+                "  缓存的项目大小: {}（代码 {}，依赖 {}，gitignore 排除 {}）",
+                size_format::format_size(stats.total_cached_size),
+                size_format::format_size(stats.total_code_size),
+                size_format::format_size(stats.total_dependency_size),
+                size_format::format_size(stats.total_gitignore_size)
+            );
+            println!("  最后更新: {}", time_format::format_time(stats.last_updated));
+            if stats.expired_entries > 0 {
+                println!("💡 运行 cache prune 删除过期的条目");
+            }
+        }
+        CacheAction::Clear { project: Some(project) } => {
+            let project_path = PathBuf::from(normalize_path(&project)?);
+            if calculator.invalidate_cache(&project_path).await? {
+                println!("🗑️  已清除 {} 的缓存，下次扫描时重新计算", project_path.display());
+            } else {
+                println!("✨ {} 没有缓存记录", project_path.display());
+            }
+        }
+        CacheAction::Clear { project: None } => {
+            let entries = calculator.get_cache_stats().map_or(0, |stats| stats.total_entries);
+            calculator.clear_cache().await?;
+            println!("🗑️  已清除全部 {} 条缓存记录", entries);
+        }
+        CacheAction::Prune => {
+            let removed = calculator.cleanup_cache().await?;
+            println!("🧹 已删除 {} 条过期的缓存记录", removed);
+        }
+    }
+    Ok(())
+}
+
+/// 读取已有缓存的统计信息；缓存未启用或尚未创建时返回 None（不会创建缓存文件）
+async fn existing_cache_stats(config: &Config) -> Option<CacheStats> {
+    if !config.cache.enabled || !SizeCache::default_cache_file().exists() {
+        return None;
+    }
+    match SizeCalculator::new_with_cache(config.cache.to_size_cache_config()).await {
+        Ok(calculator) => calculator.get_cache_stats(),
+        Err(e) => {
+            tracing::warn!("无法打开大小缓存: {}", e);
+            None
+        }
+    }
+}
+
+/// 输出环境诊断信息
+async fn run_doctor(config_path: &Path, config: &Config) {
+    let cache_stats = existing_cache_stats(config).await;
+    let git_version = Command::new("git").arg("--version").output().ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    let env = |var: &str| std::env::var(var).ok().filter(|value| !value.is_empty());
+
+    let mut diagnosis = Diagnosis::default();
+    diagnosis.push("程序", vec![doctor::Check::ok(
+        "版本",
+        format!("{} ({} {})", env!("CARGO_PKG_VERSION"), std::env::consts::OS, std::env::consts::ARCH),
+    )]);
+    diagnosis.push("配置", doctor::config_checks(config_path));
+    diagnosis.push("缓存", doctor::cache_checks(config.cache.enabled, &SizeCache::default_cache_file(), cache_stats.as_ref()));
+    diagnosis.push("编辑器", doctor::editor_checks(&config.editor, env("VISUAL").or_else(|| env("EDITOR")), editor::find_in_path));
+    diagnosis.push("Git", doctor::git_checks(git2::Version::get().libgit2_version(), git_version));
+    diagnosis.push("终端", doctor::terminal_checks(env, std::io::stdout().is_terminal(), crossterm::terminal::size().ok()));
+    diagnosis.push("扫描路径", doctor::scan_path_checks(&config.scan_paths, &AccessChecker::new()));
+    println!("{}", diagnosis);
+}
+
+/// 读取快照文件
+fn load_snapshot(path: &Path) -> Result<SessionSnapshot> {
+    SessionSnapshot::load_from_file(path)
+        .map_err(|e| anyhow::anyhow!("无法读取快照 {}: {}", path.display(), e))
+}
+
 /// 处理配置相关命令
 async fn handle_config_command(action: ConfigAction, config: &Config) -> Result<()> {
     match action {
         ConfigAction::Show => {
-            show_config(config)?;
+            show_config(config).await?;
         }
         ConfigAction::Edit => {
-            edit_config().await?;
+            edit_config(config).await?;
         }
         ConfigAction::Reset => {
             reset_config().await?;
@@ -101,12 +860,18 @@ async fn handle_config_command(action: ConfigAction, config: &Config) -> Result<
         ConfigAction::Unignore { path } => {
             remove_ignore_path(path).await?;
         }
+        ConfigAction::Keep { project_path, dir } => {
+            set_kept_dir(project_path, dir, true).await?;
+        }
+        ConfigAction::Unkeep { project_path, dir } => {
+            set_kept_dir(project_path, dir, false).await?;
+        }
     }
     Ok(())
 }
 
 /// 显示当前配置
-fn show_config(config: &Config) -> Result<()> {
+async fn show_config(config: &Config) -> Result<()> {
     println!("📋 项目管理器配置信息");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     
@@ -167,6 +932,36 @@ fn show_config(config: &Config) -> Result<()> {
     println!("  启用缓存: {}", if config.cache.enabled { "是" } else { "否" });
     println!("  过期时间: {} 小时", config.cache.expiry_duration.as_secs() / 3600);
     println!("  最大条目数: {}", config.cache.max_entries);
+    println!("  缓存文件: {}", SizeCache::default_cache_file().display());
+    match existing_cache_stats(config).await {
+        Some(stats) => println!(
+            "  已缓存: {} 个项目（已过期 {} 个），文件大小 {}",
+            stats.total_entries,
+            stats.expired_entries,
+            size_format::format_size(stats.cache_file_size)
+        ),
+        None if config.cache.enabled => println!("  已缓存: (尚未创建)"),
+        None => {}
+    }
+    
+    println!("\n📨 通知:");
+    println!("  webhook: {}", config.notify.webhook_url.as_deref().unwrap_or("(未设置)"));
+    
+    println!("\n📈 本地使用统计: {}", if config.telemetry.enabled { "已开启" } else { "未开启" });
+    
+    println!("\n📦 归档目录:");
+    println!("  {}", config.archive.dir.display());
+    
+    println!("\n🛡️  只操作扫描路径之下的项目: {}", if config.cleanup.restrict_to_scan_paths { "是" } else { "否" });
+    
+    if !config.cleanup.keep.is_empty() {
+        println!("\n🔒 清理时保留的目录:");
+        let mut projects: Vec<_> = config.cleanup.keep.iter().collect();
+        projects.sort();
+        for (project, dirs) in projects {
+            println!("  {}: {}", project, dirs.join(", "));
+        }
+    }
     
     println!("\n📁 配置文件位置:");
     if let Ok(config_path) = Config::default_config_path() {
@@ -176,20 +971,22 @@ fn show_config(config: &Config) -> Result<()> {
     Ok(())
 }
 
-/// 编辑配置文件
-async fn edit_config() -> Result<()> {
+/// 编辑配置文件（使用 [editor] 中的默认命令）
+async fn edit_config(config: &Config) -> Result<()> {
     let config_path = Config::default_config_path()?;
     
     println!("📝 打开配置文件进行编辑...");
     println!("文件路径: {}", config_path.display());
     
-    // 尝试不同的编辑器
-    let editors = ["nvim", "vim", "nano", "code", "subl"];
-    let mut editor_found = false;
+    let command = editor::select_command(&config.editor, &[], |program| {
+        editor::find_in_path(program).is_some()
+    });
     
-    for editor in &editors {
-        if Command::new("which").arg(editor).output().is_ok() {
-            let status = Command::new(editor)
+    match command {
+        Some(command) => {
+            let mut parts = command.split_whitespace();
+            let status = Command::new(parts.next().unwrap_or_default())
+                .args(parts)
                 .arg(&config_path)
                 .status()?;
             
@@ -198,14 +995,11 @@ async fn edit_config() -> Result<()> {
             } else {
                 println!("⚠️  编辑器异常退出");
             }
-            editor_found = true;
-            break;
         }
-    }
-    
-    if !editor_found {
-        println!("❌ 未找到可用的编辑器");
-        println!("请手动编辑配置文件: {}", config_path.display());
+        None => {
+            println!("❌ 未找到可用的编辑器");
+            println!("请手动编辑配置文件: {}", config_path.display());
+        }
     }
     
     Ok(())
@@ -242,7 +1036,7 @@ async fn add_ignore_path(path: String) -> Result<()> {
     let mut config = Config::load_or_create_default()?;
     
     // 规范化路径
-    let normalized_path = normalize_path(&path)?;
+    let normalized_path = normalize_ignore_pattern(&path)?;
     
     // 检查路径是否已存在
     if config.ignore.paths.contains(&normalized_path) {
@@ -268,7 +1062,7 @@ async fn remove_ignore_path(path: String) -> Result<()> {
     let mut config = Config::load_or_create_default()?;
     
     // 规范化路径
-    let normalized_path = normalize_path(&path)?;
+    let normalized_path = normalize_ignore_pattern(&path)?;
     
     // 尝试移除路径
     if config.ignore.paths.remove(&normalized_path) {
@@ -277,9 +1071,12 @@ async fn remove_ignore_path(path: String) -> Result<()> {
         println!("✅ 已从忽略列表移除: {}", normalized_path);
         println!("💾 配置已保存");
     } else {
-        // 尝试模糊匹配
+        // 尝试按路径组件匹配：忽略规则覆盖该路径，或规则位于该路径之下
         let matches: Vec<_> = config.ignore.paths.iter()
-            .filter(|p| p.contains(&normalized_path) || normalized_path.contains(p.as_str()))
+            .filter(|p| {
+                path_match::matches_ignore_pattern(Path::new(&normalized_path), p)
+                    || Path::new(p.as_str()).starts_with(&normalized_path)
+            })
             .cloned()
             .collect();
         
@@ -303,10 +1100,53 @@ async fn remove_ignore_path(path: String) -> Result<()> {
     Ok(())
 }
 
+/// 标记或取消标记项目中清理时保留的目录
+async fn set_kept_dir(project_path: String, dir: String, keep: bool) -> Result<()> {
+    let config_path = Config::default_config_path()?;
+    let mut config = Config::load_or_create_default()?;
+    
+    let project_path = std::path::PathBuf::from(normalize_path(&project_path)?);
+    let dir = dir.trim_end_matches('/').to_string();
+    if Path::new(&dir).is_absolute() || dir.split('/').any(|part| part == "..") {
+        anyhow::bail!("保留目录需为项目内的相对路径: {}", dir);
+    }
+    
+    let changed = if keep {
+        config.cleanup.add_kept_dir(&project_path, &dir)
+    } else {
+        config.cleanup.remove_kept_dir(&project_path, &dir)
+    };
+    
+    match (changed, keep) {
+        (true, true) => println!("✅ 清理 {} 时将保留: {}", project_path.display(), dir),
+        (true, false) => println!("✅ 已取消保留: {}", project_path.join(&dir).display()),
+        (false, true) => {
+            println!("⚠️  目录已标记为保留: {}", project_path.join(&dir).display());
+            return Ok(());
+        }
+        (false, false) => {
+            println!("❌ 目录未标记为保留: {}", project_path.join(&dir).display());
+            return Ok(());
+        }
+    }
+    
+    config.save_to_file(&config_path)?;
+    println!("💾 配置已保存");
+    
+    Ok(())
+}
+
+/// 规范化忽略规则：不含分隔符的通配模式（如 `*-archive`）按目录名匹配，保持原样
+fn normalize_ignore_pattern(pattern: &str) -> Result<String> {
+    if path_match::is_glob(pattern) && !pattern.contains('/') {
+        Ok(pattern.to_string())
+    } else {
+        normalize_path(pattern)
+    }
+}
+
 /// 规范化路径
 fn normalize_path(path: &str) -> Result<String> {
-    use std::path::Path;
-    
     let path = if path.starts_with('~') {
         // 展开 ~ 为用户主目录
         if let Some(home) = dirs::home_dir() {
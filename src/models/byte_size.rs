@@ -0,0 +1,112 @@
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::size_format;
+
+/// 以字节为单位的大小
+///
+/// 序列化为普通整数，与旧的缓存、会话快照和导出文件兼容；显示时使用配置的单位（`display.size_unit`）。
+/// 减法在结果为负时取 0，大小不会出现下溢。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    /// 零字节
+    pub const ZERO: Self = Self(0);
+
+    /// 从字节数创建
+    pub const fn new(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    /// 字节数
+    pub const fn bytes(self) -> u64 {
+        self.0
+    }
+
+    /// 是否为零
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&size_format::format_size(self.0))
+    }
+}
+
+impl Add for ByteSize {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+}
+
+impl AddAssign for ByteSize {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for ByteSize {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+}
+
+impl SubAssign for ByteSize {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for ByteSize {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a ByteSize> for ByteSize {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_size_arithmetic_and_serde() {
+        let code = ByteSize::new(1536);
+        let dependencies = ByteSize::from(512);
+        assert_eq!(code + dependencies, ByteSize::new(2048));
+        assert_eq!(dependencies - code, ByteSize::ZERO);
+        assert_eq!([code, dependencies].iter().sum::<ByteSize>().bytes(), 2048);
+        assert_eq!(format!("{:>8}", code), "  1.5 KB");
+
+        // 序列化为普通整数，可以读取旧格式的数据
+        assert_eq!(serde_json::to_string(&code).unwrap(), "1536");
+        assert_eq!(serde_json::from_str::<ByteSize>("2048").unwrap(), ByteSize::new(2048));
+    }
+}
@@ -1,4 +1,6 @@
+pub mod byte_size;
 pub mod project;
 pub mod scan_result;
 
-pub use project::{Project, ProjectType, GitInfo, DependencyInfo, DependencyType, DependencyCalculationStatus};
+pub use byte_size::ByteSize;
+pub use project::{Project, ProjectType, GitInfo, GitBadge, AheadBehind, CommitSummary, BranchInfo, DependencyInfo, DependencyType, DependencyCalculationStatus, CleanupSuggestion, SuggestionStatus, CloudProvider, PackageStats, PackageSize};
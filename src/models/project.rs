@@ -4,10 +4,13 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::ByteSize;
+
 /// 依赖计算状态
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum DependencyCalculationStatus {
     /// 未开始计算
+    #[default]
     NotCalculated,
     /// 正在计算中
     Calculating,
@@ -15,9 +18,11 @@ pub enum DependencyCalculationStatus {
     Completed,
     /// 计算失败
     Failed(String),
+    /// 计算被用户取消
+    Cancelled,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Project {
     /// 项目名称
     pub name: String,
@@ -29,13 +34,20 @@ pub struct Project {
     pub project_type: ProjectType,
     
     /// 代码大小（不包含依赖）
-    pub code_size: u64,
+    pub code_size: ByteSize,
     
     /// 总大小（包含依赖）
-    pub total_size: u64,
+    pub total_size: ByteSize,
     
     /// 被 gitignore 排除的文件大小（不含依赖目录，避免重复计算）
-    pub gitignore_excluded_size: u64,
+    pub gitignore_excluded_size: ByteSize,
+    
+    /// 代码大小是否仍为快速估算值（精确计算完成前为 true）
+    pub size_is_estimate: bool,
+    
+    /// 大小是否因达到统计上限而不完整（实际大小和文件数更大）
+    #[serde(default)]
+    pub size_truncated: bool,
     
     /// 代码文件数量（不包含依赖）
     pub code_file_count: usize,
@@ -61,6 +73,9 @@ pub struct Project {
     /// 是否被用户标记为忽略
     pub is_ignored: bool,
     
+    /// 是否被用户隐藏（仍参与统计，但默认不在列表中显示）
+    pub is_hidden: bool,
+    
     /// 项目描述（从 package.json、Cargo.toml 等获取）
     pub description: Option<String>,
     
@@ -68,10 +83,48 @@ pub struct Project {
     pub dependency_calculation_status: DependencyCalculationStatus,
     
     /// 缓存的依赖总大小（从异步计算中获得）
-    pub cached_dependency_size: Option<u64>,
+    pub cached_dependency_size: Option<ByteSize>,
+    
+    /// 基于 gitignore 分析得出的清理建议（None 表示尚未分析）
+    pub cleanup_suggestions: Option<Vec<CleanupSuggestion>>,
+    
+    /// 最近的提交记录（None 表示尚未加载）
+    pub recent_commits: Option<Vec<CommitSummary>>,
+    
+    /// 是否是琐碎项目（体积很小且没有 Git 提交历史）
+    #[serde(default)]
+    pub is_trivial: bool,
+    
+    /// 项目所在的云同步目录（不在同步目录中为 None）
+    #[serde(default)]
+    pub cloud_sync: Option<CloudProvider>,
+    
+    /// 所属工作区（monorepo）的根目录，工作区成员的大小已包含在根项目中
+    #[serde(default)]
+    pub workspace_root: Option<PathBuf>,
+    
+    /// 用户为项目设置的标签（保存在配置中）
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// 云同步服务
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CloudProvider {
+    /// Dropbox
+    Dropbox,
+    
+    /// iCloud Drive
+    ICloud,
+    
+    /// OneDrive
+    OneDrive,
+    
+    /// Google Drive
+    GoogleDrive,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub enum ProjectType {
     /// Git 仓库（可能包含多种语言）
     Git,
@@ -94,14 +147,57 @@ pub enum ProjectType {
     /// C++ 项目
     Cpp,
     
+    /// Terraform 基础设施项目
+    Terraform,
+    
+    /// Ansible 自动化项目
+    Ansible,
+    
+    /// Helm Chart
+    Helm,
+    
+    /// Nix 项目（flake 或 default.nix）
+    Nix,
+    
+    /// Unity 游戏项目
+    Unity,
+    
+    /// Unreal Engine 游戏项目
+    Unreal,
+    
+    /// Swift / Xcode 项目（iOS、macOS）
+    Swift,
+    
+    /// Android Gradle 项目
+    Android,
+    
+    /// .NET 项目（C#、F#、VB）
+    DotNet,
+    
+    /// Dart / Flutter 项目
+    Dart,
+    
+    /// PHP Composer 项目
+    Php,
+    
+    /// Elixir Mix 项目
+    Elixir,
+    
+    /// Zig 项目
+    Zig,
+    
+    /// 配置中自定义的项目类型（[detection] custom_types）
+    Custom { name: String, icon: String },
+    
     /// 混合项目（包含多种项目类型）
     Mixed(Vec<ProjectType>),
     
     /// 未知类型
+    #[default]
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GitInfo {
     /// Git 远程仓库 URL
     pub remote_url: Option<String>,
@@ -123,6 +219,189 @@ pub struct GitInfo {
     
     /// 是否有未推送的提交
     pub has_unpushed_commits: bool,
+    
+    /// stash 条目数量
+    #[serde(default)]
+    pub stash_count: usize,
+    
+    /// 未跟踪文件数量
+    #[serde(default)]
+    pub untracked_count: usize,
+    
+    /// 已跟踪文件中未提交的更改数量（包括已暂存的）
+    #[serde(default)]
+    pub changed_file_count: usize,
+    
+    /// 有已暂存更改的文件数量
+    #[serde(default)]
+    pub staged_file_count: usize,
+    
+    /// 工作区中有未暂存修改的已跟踪文件数量
+    #[serde(default)]
+    pub modified_file_count: usize,
+    
+    /// HEAD 是否处于游离状态（不在任何分支上，切换分支后其上的提交容易丢失）
+    #[serde(default)]
+    pub head_detached: bool,
+    
+    /// 所有本地分支上尚未推送到任何远程分支的提交数量
+    #[serde(default)]
+    pub unpushed_commit_count: usize,
+    
+    /// 是否是裸仓库（没有工作区）
+    #[serde(default)]
+    pub is_bare: bool,
+    
+    /// 链接工作树所属的主仓库路径（普通仓库为 None）
+    #[serde(default)]
+    pub worktree_of: Option<PathBuf>,
+    
+    /// 当前分支相对上游分支的领先/落后提交数（HEAD 游离或没有对应的远程分支时为 None）
+    #[serde(default)]
+    pub ahead_behind: Option<AheadBehind>,
+}
+
+impl GitInfo {
+    /// 是否只有未跟踪文件，没有已暂存或已修改的文件
+    pub fn is_untracked_only(&self) -> bool {
+        self.untracked_count > 0 && !self.has_tracked_changes()
+    }
+    
+    /// 已跟踪文件是否有未提交的更改（旧快照中没有文件数时按 has_uncommitted_changes 判断）
+    fn has_tracked_changes(&self) -> bool {
+        self.changed_file_count > 0 || (self.has_uncommitted_changes && self.untracked_count == 0)
+    }
+    
+    /// 项目列表 Git 列中显示的状态标记，没有标记时仓库是干净的（stash 数量在 Git 状态标签页中显示）
+    pub fn badges(&self) -> Vec<GitBadge> {
+        let mut badges = Vec::new();
+        if self.head_detached {
+            badges.push(GitBadge::Detached);
+        }
+        if self.staged_file_count > 0 {
+            badges.push(GitBadge::Staged);
+        }
+        if self.modified_file_count > 0 {
+            badges.push(GitBadge::Modified);
+        }
+        if badges.iter().all(|badge| *badge == GitBadge::Detached) && self.has_tracked_changes() {
+            badges.push(GitBadge::Uncommitted);
+        }
+        if self.is_untracked_only() {
+            badges.push(GitBadge::UntrackedOnly);
+        }
+        badges
+    }
+}
+
+/// 项目列表 Git 列中的仓库状态标记
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitBadge {
+    /// HEAD 游离
+    Detached,
+    
+    /// 有已暂存的更改
+    Staged,
+    
+    /// 有未暂存的修改
+    Modified,
+    
+    /// 有未提交的更改（没有按暂存/修改区分的旧数据）
+    Uncommitted,
+    
+    /// 只有未跟踪文件
+    UntrackedOnly,
+}
+
+impl std::fmt::Display for GitBadge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitBadge::Detached => write!(f, "游离"),
+            GitBadge::Staged => write!(f, "暂存"),
+            GitBadge::Modified => write!(f, "修改"),
+            GitBadge::Uncommitted => write!(f, "未提交"),
+            GitBadge::UntrackedOnly => write!(f, "未跟踪"),
+        }
+    }
+}
+
+/// 当前分支相对上游分支的领先/落后提交数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AheadBehind {
+    /// 本地有、上游没有的提交数
+    pub ahead: usize,
+    
+    /// 上游有、本地没有的提交数
+    pub behind: usize,
+}
+
+impl AheadBehind {
+    /// 是否与上游一致
+    pub fn is_synced(&self) -> bool {
+        self.ahead == 0 && self.behind == 0
+    }
+}
+
+impl std::fmt::Display for AheadBehind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.ahead, self.behind) {
+            (0, 0) => write!(f, "已同步"),
+            (ahead, 0) => write!(f, "↑{}", ahead),
+            (0, behind) => write!(f, "↓{}", behind),
+            (ahead, behind) => write!(f, "↑{} ↓{}", ahead, behind),
+        }
+    }
+}
+
+/// 单条提交记录摘要
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitSummary {
+    /// 提交 ID（短格式）
+    pub id: String,
+    
+    /// 作者名称
+    pub author: String,
+    
+    /// 提交时间
+    pub time: DateTime<Utc>,
+    
+    /// 提交信息的第一行
+    pub subject: String,
+}
+
+/// 本地分支信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchInfo {
+    /// 分支名称
+    pub name: String,
+    
+    /// 分支最后一次提交时间
+    pub last_commit_time: Option<DateTime<Utc>>,
+    
+    /// 是否为当前检出的分支
+    pub is_current: bool,
+    
+    /// 是否已合并到默认分支（origin/HEAD、main 或 master）
+    pub is_merged: bool,
+    
+    /// 是否为默认分支
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+impl BranchInfo {
+    /// 判断分支是否陈旧（最后提交早于指定天数）
+    pub fn is_stale(&self, stale_after_days: u32) -> bool {
+        match self.last_commit_time {
+            Some(time) => Utc::now() - time > chrono::Duration::days(stale_after_days as i64),
+            None => false,
+        }
+    }
+    
+    /// 是否可以被批量删除（已合并，且不是默认分支或当前分支）
+    pub fn is_deletable(&self) -> bool {
+        self.is_merged && !self.is_current && !self.is_default
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,10 +413,44 @@ pub struct DependencyInfo {
     pub path: PathBuf,
     
     /// 依赖大小
-    pub size: u64,
+    pub size: ByteSize,
     
     /// 包数量（对于 node_modules）
     pub package_count: Option<usize>,
+    
+    /// 解析清单文件得到的包统计（node_modules 和 target，打开项目详情时计算）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packages: Option<PackageStats>,
+}
+
+/// 依赖目录中的包统计
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackageStats {
+    /// 清单文件中直接声明的依赖数量
+    pub direct_count: usize,
+    
+    /// 锁文件中的间接依赖数量
+    pub transitive_count: usize,
+    
+    /// 占用最大的包，按大小降序
+    pub largest: Vec<PackageSize>,
+}
+
+impl PackageStats {
+    /// 是否没有任何统计结果（分析中或不适用）
+    pub fn is_empty(&self) -> bool {
+        self.direct_count == 0 && self.transitive_count == 0 && self.largest.is_empty()
+    }
+}
+
+/// 单个包在依赖目录中的占用
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageSize {
+    /// 包名
+    pub name: String,
+    
+    /// 大小
+    pub size: ByteSize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -160,13 +473,71 @@ pub enum DependencyType {
     /// Maven .m2
     Maven,
     
+    /// Unity 缓存目录（Library、Temp 等）
+    UnityCache(String),
+    
+    /// Unreal 缓存目录（Intermediate、DerivedDataCache）
+    UnrealCache(String),
+    
+    /// CocoaPods 的 Pods 目录
+    CocoaPods,
+    
+    /// Carthage 的构建和检出目录
+    Carthage,
+    
+    /// Xcode DerivedData 中属于该项目的构建目录
+    XcodeDerivedData,
+    
     /// 其他类型的依赖
     Other(String),
 }
 
+/// 清理建议（被 gitignore 忽略、且不在默认依赖列表中的目录）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupSuggestion {
+    /// 建议清理的目录路径
+    pub path: PathBuf,
+    
+    /// 目录大小
+    pub size: ByteSize,
+    
+    /// 目录中的文件数量
+    pub file_count: usize,
+    
+    /// 占项目总大小的比例（0.0 - 1.0）
+    pub share: f64,
+    
+    /// 建议的处理状态
+    pub status: SuggestionStatus,
+}
+
+/// 清理建议的处理状态
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SuggestionStatus {
+    /// 等待用户决定
+    Pending,
+    
+    /// 用户已接受（目录已清理）
+    Accepted,
+    
+    /// 用户已拒绝
+    Rejected,
+}
+
 impl Project {
+    /// 以名称和路径开始构造项目，未设置的字段使用默认值
+    pub fn builder(name: impl Into<String>, path: impl Into<PathBuf>) -> ProjectBuilder {
+        ProjectBuilder {
+            project: Project {
+                name: name.into(),
+                path: path.into(),
+                ..Project::default()
+            },
+        }
+    }
+    
     /// 获取依赖总大小
-    pub fn dependency_size(&self) -> u64 {
+    pub fn dependency_size(&self) -> ByteSize {
         // 优先使用缓存的依赖大小（从异步计算得到的准确值）
         // 如果没有缓存值，则使用 dependencies 向量计算
         self.cached_dependency_size
@@ -174,10 +545,15 @@ impl Project {
     }
     
     /// 获取项目代码大小（不包含依赖）
-    pub fn size(&self) -> u64 {
+    pub fn size(&self) -> ByteSize {
         self.code_size
     }
     
+    /// 获取项目在磁盘上的估算总占用（代码 + 依赖 + gitignore 排除的文件）
+    pub fn disk_usage(&self) -> ByteSize {
+        self.total_size.max(self.size() + self.dependency_size()) + self.gitignore_excluded_size
+    }
+    
     /// 获取代码文件数量（准确值）
     pub fn file_count(&self) -> usize {
         self.code_file_count
@@ -193,6 +569,18 @@ impl Project {
         self.dependency_file_count
     }
     
+    /// 获取最后活动时间（优先使用最后一次提交时间）
+    pub fn last_activity(&self) -> DateTime<Utc> {
+        self.git_info.as_ref()
+            .and_then(|info| info.last_commit_time)
+            .unwrap_or(self.last_modified)
+    }
+
+    /// 获取最后改动时间：最后一次提交和文件修改时间中较晚的一个
+    pub fn last_touched(&self) -> DateTime<Utc> {
+        self.last_activity().max(self.last_modified)
+    }
+
     /// 检查是否有未提交的更改
     pub fn has_uncommitted_changes(&self) -> bool {
         self.git_info.as_ref()
@@ -200,6 +588,37 @@ impl Project {
             .unwrap_or(false)
     }
     
+    /// 获取删除前需要提醒的未保存工作（未提交更改、未推送提交、stash、未跟踪文件）
+    pub fn unsaved_work_warnings(&self) -> Vec<String> {
+        let Some(info) = &self.git_info else {
+            return Vec::new();
+        };
+        
+        let mut warnings = Vec::new();
+        if info.changed_file_count > 0 {
+            warnings.push(format!("有 {} 个文件的更改未提交", info.changed_file_count));
+        } else if info.has_uncommitted_changes {
+            warnings.push("有未提交的更改".to_string());
+        }
+        if info.unpushed_commit_count > 0 {
+            warnings.push(format!("有 {} 个未推送的提交", info.unpushed_commit_count));
+        } else if info.has_unpushed_commits {
+            warnings.push("有未推送的提交".to_string());
+        }
+        if info.stash_count > 0 {
+            warnings.push(format!("有 {} 个 stash", info.stash_count));
+        }
+        if info.untracked_count > 0 {
+            warnings.push(format!("有 {} 个未跟踪文件", info.untracked_count));
+        }
+        warnings
+    }
+    
+    /// 是否是工作区成员（汇总大小时不重复计入）
+    pub fn is_workspace_member(&self) -> bool {
+        self.workspace_root.is_some()
+    }
+    
     /// 检查是否是 monorepo
     pub fn is_monorepo(&self) -> bool {
         match &self.project_type {
@@ -218,6 +637,20 @@ impl Project {
             ProjectType::Go => "Go".to_string(),
             ProjectType::Java => "Java".to_string(),
             ProjectType::Cpp => "C++".to_string(),
+            ProjectType::Terraform => "Terraform".to_string(),
+            ProjectType::Ansible => "Ansible".to_string(),
+            ProjectType::Helm => "Helm".to_string(),
+            ProjectType::Nix => "Nix".to_string(),
+            ProjectType::Unity => "Unity".to_string(),
+            ProjectType::Unreal => "Unreal".to_string(),
+            ProjectType::Swift => "Swift".to_string(),
+            ProjectType::Android => "Android".to_string(),
+            ProjectType::DotNet => ".NET".to_string(),
+            ProjectType::Dart => "Dart".to_string(),
+            ProjectType::Php => "PHP".to_string(),
+            ProjectType::Elixir => "Elixir".to_string(),
+            ProjectType::Zig => "Zig".to_string(),
+            ProjectType::Custom { name, .. } => name.clone(),
             ProjectType::Mixed(types) => {
                 let type_names: Vec<String> = types.iter()
                     .map(|t| match t {
@@ -227,6 +660,20 @@ impl Project {
                         ProjectType::Go => "Go",
                         ProjectType::Java => "Java",
                         ProjectType::Cpp => "C++",
+                        ProjectType::Terraform => "Terraform",
+                        ProjectType::Ansible => "Ansible",
+                        ProjectType::Helm => "Helm",
+                        ProjectType::Nix => "Nix",
+                        ProjectType::Unity => "Unity",
+                        ProjectType::Unreal => "Unreal",
+                        ProjectType::Swift => "Swift",
+                        ProjectType::Android => "Android",
+                        ProjectType::DotNet => ".NET",
+                        ProjectType::Dart => "Dart",
+                        ProjectType::Php => "PHP",
+                        ProjectType::Elixir => "Elixir",
+                        ProjectType::Zig => "Zig",
+                        ProjectType::Custom { name, .. } => name.as_str(),
                         _ => "Other",
                     })
                     .map(|s| s.to_string())
@@ -237,6 +684,15 @@ impl Project {
         }
     }
     
+    /// 获取待处理的清理建议
+    pub fn pending_cleanup_suggestions(&self) -> Vec<&CleanupSuggestion> {
+        self.cleanup_suggestions.as_ref()
+            .map(|suggestions| suggestions.iter()
+                .filter(|s| s.status == SuggestionStatus::Pending)
+                .collect())
+            .unwrap_or_default()
+    }
+    
     /// 获取依赖计算状态的显示文本
     pub fn dependency_status_display(&self) -> &str {
         match &self.dependency_calculation_status {
@@ -244,6 +700,7 @@ impl Project {
             DependencyCalculationStatus::Calculating => "计算中...",
             DependencyCalculationStatus::Completed => "",
             DependencyCalculationStatus::Failed(_) => "计算失败",
+            DependencyCalculationStatus::Cancelled => "已取消",
         }
     }
     
@@ -265,6 +722,79 @@ impl Project {
     }
 }
 
+/// 项目构造器：大小和文件数为 0、依赖等待计算、没有 Git 信息，新增字段时只需在此设置默认值
+#[derive(Debug, Clone)]
+pub struct ProjectBuilder {
+    project: Project,
+}
+
+impl ProjectBuilder {
+    /// 设置项目类型
+    pub fn project_type(mut self, project_type: ProjectType) -> Self {
+        self.project.project_type = project_type;
+        self
+    }
+    
+    /// 设置检测到的依赖目录，依赖大小和文件数按依赖目录汇总（代码大小留待后续计算）
+    pub fn dependencies(mut self, dependencies: Vec<DependencyInfo>) -> Self {
+        let dependency_size: ByteSize = dependencies.iter().map(|d| d.size).sum();
+        let dependency_file_count: usize = dependencies.iter().map(|d| d.package_count.unwrap_or(0)).sum();
+        self.project.total_size = self.project.code_size + dependency_size;
+        self.project.cached_dependency_size = Some(dependency_size);
+        self.project.dependency_file_count = dependency_file_count;
+        self.project.total_file_count = self.project.code_file_count + dependency_file_count;
+        self.project.dependencies = dependencies;
+        self
+    }
+    
+    /// 设置依赖计算状态
+    pub fn dependency_calculation_status(mut self, status: DependencyCalculationStatus) -> Self {
+        self.project.dependency_calculation_status = status;
+        self
+    }
+    
+    /// 设置最后修改时间
+    pub fn last_modified(mut self, last_modified: DateTime<Utc>) -> Self {
+        self.project.last_modified = last_modified;
+        self
+    }
+    
+    /// 设置 Git 信息
+    pub fn git_info(mut self, git_info: Option<GitInfo>) -> Self {
+        self.project.git_info = git_info;
+        self
+    }
+    
+    /// 设置项目描述
+    pub fn description(mut self, description: Option<String>) -> Self {
+        self.project.description = description;
+        self
+    }
+    
+    /// 设置是否是琐碎项目
+    pub fn trivial(mut self, is_trivial: bool) -> Self {
+        self.project.is_trivial = is_trivial;
+        self
+    }
+    
+    /// 设置所在的云同步目录
+    pub fn cloud_sync(mut self, cloud_sync: Option<CloudProvider>) -> Self {
+        self.project.cloud_sync = cloud_sync;
+        self
+    }
+    
+    /// 设置所属工作区的根目录
+    pub fn workspace_root(mut self, workspace_root: Option<PathBuf>) -> Self {
+        self.project.workspace_root = workspace_root;
+        self
+    }
+    
+    /// 完成构造
+    pub fn build(self) -> Project {
+        self.project
+    }
+}
+
 impl ProjectType {
     /// 获取项目类型的字符串表示
     pub fn as_str(&self) -> &str {
@@ -276,6 +806,20 @@ impl ProjectType {
             ProjectType::Go => "go",
             ProjectType::Java => "java",
             ProjectType::Cpp => "cpp",
+            ProjectType::Terraform => "terraform",
+            ProjectType::Ansible => "ansible",
+            ProjectType::Helm => "helm",
+            ProjectType::Nix => "nix",
+            ProjectType::Unity => "unity",
+            ProjectType::Unreal => "unreal",
+            ProjectType::Swift => "swift",
+            ProjectType::Android => "android",
+            ProjectType::DotNet => "dotnet",
+            ProjectType::Dart => "dart",
+            ProjectType::Php => "php",
+            ProjectType::Elixir => "elixir",
+            ProjectType::Zig => "zig",
+            ProjectType::Custom { name, .. } => name,
             ProjectType::Mixed(_) => "mixed",
             ProjectType::Unknown => "unknown",
         }
@@ -292,7 +836,43 @@ impl ProjectType {
             ProjectType::Go => 6,
             ProjectType::Java => 7,
             ProjectType::Cpp => 8,
-            ProjectType::Unknown => 9,
+            ProjectType::Terraform => 9,
+            ProjectType::Ansible => 10,
+            ProjectType::Helm => 11,
+            ProjectType::Nix => 12,
+            ProjectType::Unity => 13,
+            ProjectType::Unreal => 14,
+            ProjectType::Swift => 15,
+            ProjectType::Android => 16,
+            ProjectType::DotNet => 17,
+            ProjectType::Dart => 18,
+            ProjectType::Php => 19,
+            ProjectType::Elixir => 20,
+            ProjectType::Zig => 21,
+            ProjectType::Custom { .. } => 22,
+            ProjectType::Unknown => 23,
+        }
+    }
+}
+
+impl CloudProvider {
+    /// 获取同步服务的显示名称
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CloudProvider::Dropbox => "Dropbox",
+            CloudProvider::ICloud => "iCloud Drive",
+            CloudProvider::OneDrive => "OneDrive",
+            CloudProvider::GoogleDrive => "Google Drive",
+        }
+    }
+    
+    /// 让同步服务跳过依赖目录的办法
+    pub fn exclusion_hint(&self) -> &'static str {
+        match self {
+            CloudProvider::Dropbox => "用 `xattr -w com.dropbox.ignored 1 <目录>`（Linux: `attr -s com.dropbox.ignored -V 1 <目录>`）将依赖目录标记为不同步",
+            CloudProvider::ICloud => "将依赖目录重命名为 `<目录>.nosync` 并在原位置创建符号链接",
+            CloudProvider::OneDrive => "OneDrive 无法排除单个目录，建议将项目移出同步目录",
+            CloudProvider::GoogleDrive => "Google Drive 无法排除单个目录，建议将项目移出同步目录",
         }
     }
 }
@@ -307,7 +887,90 @@ impl DependencyType {
             DependencyType::PythonVenv => "venv",
             DependencyType::GoMod => "go.mod",
             DependencyType::Maven => ".m2",
+            DependencyType::UnityCache(name) => name,
+            DependencyType::UnrealCache(name) => name,
+            DependencyType::CocoaPods => "Pods",
+            DependencyType::Carthage => "Carthage",
+            DependencyType::XcodeDerivedData => "DerivedData",
             DependencyType::Other(name) => name,
         }
     }
-}
\ No newline at end of file
+    
+    /// 删除后重新生成代价较高时的提示
+    pub fn regeneration_warning(&self) -> Option<&'static str> {
+        match self {
+            DependencyType::UnityCache(_) => Some("下次打开 Unity 时需要重新导入全部资源，大型项目可能耗时数十分钟"),
+            DependencyType::UnrealCache(_) => Some("下次打开 Unreal 时需要重新编译模块和着色器，可能耗时数小时"),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_sums_dependencies() {
+        let dependency = |name: &str, size: u64, packages: usize| DependencyInfo {
+            dependency_type: DependencyType::Other(name.to_string()),
+            path: PathBuf::from("/code/web").join(name),
+            size: ByteSize::new(size),
+            package_count: Some(packages),
+            packages: None,
+        };
+        let project = Project::builder("web", "/code/web")
+            .project_type(ProjectType::NodeJs)
+            .dependencies(vec![dependency("node_modules", 2048, 30), dependency(".next", 1024, 5)])
+            .build();
+
+        assert_eq!(project.project_type, ProjectType::NodeJs);
+        assert_eq!(project.dependency_size(), ByteSize::new(3072));
+        assert_eq!(project.total_size, ByteSize::new(3072));
+        assert_eq!((project.dependency_file_count, project.total_file_count), (35, 35));
+        assert_eq!(project.dependency_calculation_status, DependencyCalculationStatus::NotCalculated);
+        assert!(project.git_info.is_none() && project.tags.is_empty());
+    }
+
+    #[test]
+    fn test_git_badges_combinations() {
+        let git_info = GitInfo {
+            head_detached: true,
+            has_uncommitted_changes: true,
+            changed_file_count: 2,
+            staged_file_count: 1,
+            modified_file_count: 1,
+            untracked_count: 3,
+            stash_count: 2,
+            ..GitInfo::default()
+        };
+        // 最宽的组合，Git 列按它的宽度设置
+        assert_eq!(git_info.badges(), vec![GitBadge::Detached, GitBadge::Staged, GitBadge::Modified]);
+
+        let untracked_only = GitInfo { has_uncommitted_changes: true, untracked_count: 1, ..GitInfo::default() };
+        assert_eq!(untracked_only.badges(), vec![GitBadge::UntrackedOnly]);
+
+        // 旧快照只有 has_uncommitted_changes
+        let legacy = GitInfo { has_uncommitted_changes: true, ..GitInfo::default() };
+        assert_eq!(legacy.badges(), vec![GitBadge::Uncommitted]);
+        assert!(GitInfo { stash_count: 1, ..GitInfo::default() }.badges().is_empty());
+    }
+
+    #[test]
+    fn test_git_info_loads_without_stash_and_untracked_counts() {
+        // 加入 stash/未跟踪文件数量之前写入的快照和缓存
+        let json = r#"{
+            "remote_url": null,
+            "current_branch": "main",
+            "last_commit_time": null,
+            "last_commit_message": null,
+            "last_commit_author": null,
+            "has_uncommitted_changes": true,
+            "has_unpushed_commits": false
+        }"#;
+        let git_info: GitInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(git_info.current_branch.as_deref(), Some("main"));
+        assert_eq!(git_info.stash_count, 0);
+        assert_eq!(git_info.untracked_count, 0);
+    }
+}
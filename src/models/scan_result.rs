@@ -2,9 +2,13 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::time::Duration;
 
-use crate::models::Project;
+use crate::models::{ByteSize, Project};
+
+/// 统计摘要中列出的最大项目数量
+const LARGEST_PROJECTS_SHOWN: usize = 5;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanResult {
@@ -33,10 +37,14 @@ pub struct ScanStats {
     pub projects_by_type: std::collections::HashMap<String, usize>,
     
     /// 总的代码大小
-    pub total_code_size: u64,
+    pub total_code_size: ByteSize,
     
     /// 总的依赖大小
-    pub total_dependency_size: u64,
+    pub total_dependency_size: ByteSize,
+    
+    /// 总的被 gitignore 排除的文件大小
+    #[serde(default)]
+    pub total_gitignore_excluded_size: ByteSize,
     
     /// 扫描的目录数量
     pub scanned_directories: usize,
@@ -58,6 +66,14 @@ pub struct ScanStats {
     
     /// 有未提交更改的项目数量
     pub uncommitted_changes_count: usize,
+    
+    /// 有未推送提交的项目数量
+    #[serde(default)]
+    pub unpushed_projects_count: usize,
+    
+    /// 未推送的提交总数
+    #[serde(default)]
+    pub unpushed_commit_count: usize,
 }
 
 impl ScanResult {
@@ -78,6 +94,12 @@ impl ScanResult {
         self.update_stats();
     }
     
+    /// 批量添加项目到扫描结果
+    pub fn add_projects(&mut self, projects: impl IntoIterator<Item = Project>) {
+        self.projects.extend(projects);
+        self.update_stats();
+    }
+    
     /// 完成扫描
     pub fn finish_scan(&mut self) {
         self.scan_end_time = Some(Utc::now());
@@ -97,8 +119,12 @@ impl ScanResult {
         let projects = &self.projects;
         
         self.stats.total_projects = projects.len();
-        self.stats.total_code_size = projects.iter().map(|p| p.code_size).sum();
-        self.stats.total_dependency_size = projects.iter().map(|p| p.dependency_size()).sum();
+        
+        // 工作区成员的大小已包含在根项目中
+        let sized = || projects.iter().filter(|p| !p.is_workspace_member());
+        self.stats.total_code_size = sized().map(|p| p.code_size).sum();
+        self.stats.total_dependency_size = sized().map(|p| p.dependency_size()).sum();
+        self.stats.total_gitignore_excluded_size = sized().map(|p| p.gitignore_excluded_size).sum();
         
         // 按类型统计项目数量
         self.stats.projects_by_type.clear();
@@ -118,9 +144,16 @@ impl ScanResult {
                 .unwrap_or(false))
             .count();
         
+        let unpushed: Vec<_> = projects.iter()
+            .filter_map(|p| p.git_info.as_ref())
+            .filter(|info| info.has_unpushed_commits)
+            .collect();
+        self.stats.unpushed_projects_count = unpushed.len();
+        self.stats.unpushed_commit_count = unpushed.iter().map(|info| info.unpushed_commit_count).sum();
+        
         // 找出最大的项目
         self.stats.largest_project = projects.iter()
-            .max_by_key(|p| p.total_size)
+            .max_by_key(|p| p.disk_usage())
             .map(|p| p.name.clone());
         
         // 找出最大的依赖
@@ -128,7 +161,7 @@ impl ScanResult {
             .flat_map(|p| &p.dependencies)
             .max_by_key(|d| d.size)
             .map(|d| format!("{} ({})", 
-                d.path.display(), 
+                crate::utils::path_format::abbreviate_home(&d.path), 
                 d.dependency_type.display_name()));
     }
     
@@ -169,19 +202,65 @@ impl ScanResult {
         })
     }
     
+    /// 按磁盘占用从大到小排列的前 `limit` 个项目
+    pub fn largest_projects(&self, limit: usize) -> Vec<&Project> {
+        let mut projects: Vec<&Project> = self.projects.iter().collect();
+        projects.sort_by_key(|p| std::cmp::Reverse(p.disk_usage()));
+        projects.truncate(limit);
+        projects
+    }
+    
     /// 获取大型项目（大于指定大小）
-    pub fn large_projects(&self, min_size: u64) -> Vec<&Project> {
+    pub fn large_projects(&self, min_size: ByteSize) -> Vec<&Project> {
         self.filter_projects(|p| p.total_size > min_size)
     }
 }
 
+impl fmt::Display for ScanResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stats = &self.stats;
+        writeln!(f, "项目: {} 个（Git 仓库 {} 个），扫描耗时 {}", stats.total_projects, stats.git_projects_count, self.scan_duration_display())?;
+
+        let mut types: Vec<(&String, &usize)> = stats.projects_by_type.iter().collect();
+        types.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        if !types.is_empty() {
+            let types: Vec<String> = types.iter().map(|(name, count)| format!("{} {}", name, count)).collect();
+            writeln!(f, "  {}", types.join("，"))?;
+        }
+
+        writeln!(
+            f,
+            "大小: 代码 {}，依赖 {}，gitignore 排除 {}",
+            stats.total_code_size,
+            stats.total_dependency_size,
+            stats.total_gitignore_excluded_size
+        )?;
+
+        let largest = self.largest_projects(LARGEST_PROJECTS_SHOWN);
+        if !largest.is_empty() {
+            writeln!(f, "最大的项目:")?;
+            for project in largest {
+                writeln!(f, "  {:>10}  {} ({})", project.disk_usage(), project.name, project.path.display())?;
+            }
+        }
+
+        writeln!(
+            f,
+            "Git: {} 个仓库有未提交的更改，{} 个仓库有 {} 个未推送的提交",
+            stats.uncommitted_changes_count,
+            stats.unpushed_projects_count,
+            stats.unpushed_commit_count
+        )
+    }
+}
+
 impl Default for ScanStats {
     fn default() -> Self {
         Self {
             total_projects: 0,
             projects_by_type: std::collections::HashMap::new(),
-            total_code_size: 0,
-            total_dependency_size: 0,
+            total_code_size: ByteSize::ZERO,
+            total_dependency_size: ByteSize::ZERO,
             scanned_directories: 0,
             skipped_directories: 0,
             scan_duration: None,
@@ -189,6 +268,53 @@ impl Default for ScanStats {
             largest_dependency: None,
             git_projects_count: 0,
             uncommitted_changes_count: 0,
+            total_gitignore_excluded_size: ByteSize::ZERO,
+            unpushed_projects_count: 0,
+            unpushed_commit_count: 0,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectType;
+    use crate::tui::fixtures::{GitState, ProjectFixture};
+
+    #[test]
+    fn test_scan_stats_summary() {
+        let mut web = ProjectFixture::new("/code/web")
+            .project_type(ProjectType::NodeJs)
+            .sizes(1000, 8000)
+            .git(GitState::Unpushed)
+            .build();
+        web.gitignore_excluded_size = ByteSize::new(500);
+        if let Some(git) = web.git_info.as_mut() {
+            git.unpushed_commit_count = 4;
+        }
+
+        let mut result = ScanResult::new(vec!["/code".to_string()]);
+        result.add_projects([
+            web,
+            ProjectFixture::new("/code/api").sizes(3000, 2000).git(GitState::Dirty).build(),
+            ProjectFixture::new("/code/cli").sizes(200, 0).build(),
+        ]);
+        result.finish_scan();
+
+        let stats = &result.stats;
+        assert_eq!(stats.total_projects, 3);
+        assert_eq!(stats.total_code_size, ByteSize::new(4200));
+        assert_eq!(stats.total_dependency_size, ByteSize::new(10000));
+        assert_eq!(stats.total_gitignore_excluded_size, ByteSize::new(500));
+        assert_eq!(stats.git_projects_count, 2);
+        assert_eq!(stats.uncommitted_changes_count, 1);
+        assert_eq!((stats.unpushed_projects_count, stats.unpushed_commit_count), (1, 4));
+
+        let names: Vec<&str> = result.largest_projects(2).iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["web", "api"]);
+
+        let summary = result.to_string();
+        assert!(summary.contains("Rust 2，Node.js 1"), "{}", summary);
+        assert!(summary.contains("Git: 1 个仓库有未提交的更改，1 个仓库有 4 个未推送的提交"));
+    }
+}
@@ -0,0 +1,214 @@
+// 归档操作
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use tokio_util::sync::CancellationToken;
+
+use super::cleanup::{CleanupOperation, CleanupSummary};
+
+/// 归档结果
+#[derive(Debug)]
+pub struct ArchiveResult {
+    /// 项目移动后的位置
+    pub target: PathBuf,
+
+    /// 归档前清理依赖目录的结果
+    pub cleanup: CleanupSummary,
+}
+
+/// 归档目录中已有同名项目时的处理方式（只用于归档，目前没有从归档目录恢复项目的操作）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// 将已有的同名目录移到回收站后再归档
+    Overwrite,
+
+    /// 在名称后加数字后缀，如 `app-2`
+    Rename,
+
+    /// 跳过该项目，不做任何修改
+    Skip,
+}
+
+impl ConflictPolicy {
+    pub const ALL: [ConflictPolicy; 3] = [ConflictPolicy::Overwrite, ConflictPolicy::Rename, ConflictPolicy::Skip];
+
+    /// 命令行中使用的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Overwrite => "overwrite",
+            ConflictPolicy::Rename => "rename",
+            ConflictPolicy::Skip => "skip",
+        }
+    }
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_ascii_lowercase();
+        ConflictPolicy::ALL.into_iter()
+            .find(|policy| policy.name() == value)
+            .ok_or_else(|| format!(
+                "未知的冲突处理方式: {}（可选: {}）",
+                value,
+                ConflictPolicy::ALL.map(|policy| policy.name()).join(", ")
+            ))
+    }
+}
+
+/// 将项目移动到归档目录
+pub struct ArchiveOperation {
+    /// 归档目录
+    archive_root: PathBuf,
+
+    /// 归档前的依赖清理
+    cleanup: CleanupOperation,
+
+    /// 目标已存在时的处理方式，未指定时拒绝归档
+    on_conflict: Option<ConflictPolicy>,
+}
+
+impl ArchiveOperation {
+    pub fn new(archive_root: impl Into<PathBuf>) -> Self {
+        Self {
+            archive_root: archive_root.into(),
+            cleanup: CleanupOperation::new(),
+            on_conflict: None,
+        }
+    }
+
+    /// 使用指定的清理操作（如带有保留目录的配置）清理依赖目录
+    pub fn with_cleanup(mut self, cleanup: CleanupOperation) -> Self {
+        self.cleanup = cleanup;
+        self
+    }
+
+    /// 归档目录中已有同名项目时的处理方式
+    pub fn with_conflict_policy(mut self, policy: Option<ConflictPolicy>) -> Self {
+        self.on_conflict = policy;
+        self
+    }
+
+    /// 项目归档后的路径：归档目录下的同名目录
+    pub fn target_path(&self, project_path: &Path) -> PathBuf {
+        let name = project_path.file_name().unwrap_or(project_path.as_os_str());
+        self.archive_root.join(name)
+    }
+
+    /// 归档目录中已有的同名项目，没有冲突时返回 None
+    pub fn conflicting_target(&self, project_path: &Path) -> Option<PathBuf> {
+        Some(self.target_path(project_path)).filter(|target| target.exists())
+    }
+
+    /// 重命名时使用的路径：在名称后加从 2 开始的第一个未被占用的数字后缀
+    pub fn renamed_target(&self, project_path: &Path) -> PathBuf {
+        let target = self.target_path(project_path);
+        let name = target.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        (2..)
+            .map(|suffix| self.archive_root.join(format!("{}-{}", name, suffix)))
+            .find(|candidate| !candidate.exists())
+            .unwrap_or(target)
+    }
+
+    /// 归档项目：先清理依赖目录，再将项目移动到归档目录
+    ///
+    /// 归档目录中已有同名项目时按冲突处理方式覆盖或重命名；未指定或为跳过时不做任何修改并返回错误，
+    /// 跳过由调用方在归档前通过 `conflicting_target` 判断。
+    pub async fn archive_project(&self, project_path: &Path, token: &CancellationToken) -> Result<ArchiveResult> {
+        let mut target = self.target_path(project_path);
+        let mut overwrite = false;
+        if target.exists() {
+            match self.on_conflict {
+                Some(ConflictPolicy::Overwrite) => overwrite = true,
+                Some(ConflictPolicy::Rename) => target = self.renamed_target(project_path),
+                Some(ConflictPolicy::Skip) | None => {
+                    anyhow::bail!("归档目录中已存在同名项目: {}", target.display());
+                }
+            }
+        }
+
+        let mut cleanup = self.cleanup.clean_project(project_path, token).await;
+        if cleanup.cancelled {
+            anyhow::bail!("归档已中断，依赖目录未完全清理");
+        }
+        // 移动项目前等待后台删除完成，避免临时目录随项目一起归档
+        cleanup.wait_for_deletion().await;
+
+        if overwrite {
+            trash::delete(&target)
+                .map_err(|e| anyhow::anyhow!("无法将已有的 {} 移到回收站: {}", target.display(), e))?;
+        }
+        tokio::fs::create_dir_all(&self.archive_root).await?;
+        tokio::fs::rename(project_path, &target).await
+            .map_err(|e| anyhow::anyhow!("移动项目到 {} 失败: {}", target.display(), e))?;
+
+        Ok(ArchiveResult { target, cleanup })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_archive_project() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("code/app");
+        fs::create_dir_all(project.join("node_modules/pkg")).unwrap();
+        fs::write(project.join("node_modules/pkg/index.js"), "x".repeat(100)).unwrap();
+        fs::write(project.join("package.json"), "{}").unwrap();
+
+        let operation = ArchiveOperation::new(temp_dir.path().join("archive"));
+        let result = operation.archive_project(&project, &CancellationToken::new()).await.unwrap();
+
+        assert_eq!(result.target, temp_dir.path().join("archive/app"));
+        assert_eq!(result.cleanup.freed_bytes(), 100);
+        assert!(!project.exists());
+        assert!(result.target.join("package.json").exists());
+        assert!(!result.target.join("node_modules").exists());
+    }
+
+    #[tokio::test]
+    async fn test_archive_refuses_existing_target() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("code/app");
+        fs::create_dir_all(project.join("node_modules")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("archive/app")).unwrap();
+
+        let operation = ArchiveOperation::new(temp_dir.path().join("archive"));
+        assert!(operation.archive_project(&project, &CancellationToken::new()).await.is_err());
+        assert!(project.join("node_modules").exists());
+    }
+
+    #[tokio::test]
+    async fn test_archive_renames_on_conflict() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("code/app");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("package.json"), "{}").unwrap();
+        fs::create_dir_all(temp_dir.path().join("archive/app")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("archive/app-2")).unwrap();
+
+        let operation = ArchiveOperation::new(temp_dir.path().join("archive"))
+            .with_conflict_policy(Some(ConflictPolicy::Rename));
+        assert_eq!(operation.conflicting_target(&project), Some(temp_dir.path().join("archive/app")));
+        let result = operation.archive_project(&project, &CancellationToken::new()).await.unwrap();
+
+        assert_eq!(result.target, temp_dir.path().join("archive/app-3"));
+        assert!(result.target.join("package.json").exists());
+        assert!(!project.exists());
+
+        // 跳过同样不做任何修改
+        fs::create_dir_all(&project).unwrap();
+        let operation = ArchiveOperation::new(temp_dir.path().join("archive"))
+            .with_conflict_policy(Some(ConflictPolicy::Skip));
+        assert!(operation.archive_project(&project, &CancellationToken::new()).await.is_err());
+        assert!(project.exists());
+        assert_eq!("Rename".parse(), Ok(ConflictPolicy::Rename));
+        assert!("keep".parse::<ConflictPolicy>().is_err());
+    }
+}
@@ -1,10 +1,794 @@
 // 清理操作
-#[allow(dead_code)]
-pub struct CleanupOperation;
+#![allow(dead_code)]
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use walkdir::WalkDir;
+
+use crate::config::settings::{CleanupConfig, Config};
+use crate::models::ProjectType;
+use crate::scanner::ProjectDetector;
+use crate::utils::{long_path, size_format};
+
+use super::cleanup_strategy;
+use super::deleter::{DeleteProgress, ParallelDeleter};
+
+/// 没有适用的清理策略时（如未知类型的项目）默认清理的依赖目录
+pub const DEFAULT_CLEAN_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "build",
+    "dist",
+    "__pycache__",
+    ".venv",
+    "venv",
+    ".terraform",
+    ".terragrunt-cache",
+    ".cxx",
+];
+
+/// 使用稳健删除的依赖目录（包管理器生成的深层目录树，常含只读文件和目录联接）
+const NODE_MODULES_DIR: &str = "node_modules";
+
+/// 待删除目录重命名后的名称标记（`.<原名>.pm-cleanup-<时间戳>`）
+const STAGING_MARKER: &str = ".pm-cleanup-";
+
+/// 删除进度回调：参数为正在删除的原目录和当前进度
+pub type DeleteProgressCallback = Arc<dyn Fn(&Path, DeleteProgress) + Send + Sync>;
+
+/// 删除前检查目录是否允许操作，返回拒绝原因
+pub type PathCheck = Arc<dyn Fn(&Path) -> Result<()> + Send + Sync>;
+
+/// 清理结果汇总
+#[derive(Debug, Default)]
+pub struct CleanupSummary {
+    /// 已删除的目录及释放的空间
+    pub removed: Vec<(PathBuf, u64)>,
+
+    /// 因取消而未处理的目录
+    pub skipped: Vec<PathBuf>,
+
+    /// 删除失败的目录及错误信息
+    pub failed: Vec<(PathBuf, String)>,
+
+    /// 是否被取消
+    pub cancelled: bool,
+
+    /// 后台删除已移走目录的任务
+    pub deletion: Option<JoinHandle<()>>,
+}
+
+impl CleanupSummary {
+    /// 释放的总空间
+    pub fn freed_bytes(&self) -> u64 {
+        self.removed.iter().map(|(_, size)| size).sum()
+    }
+
+    /// 等待后台删除完成
+    pub async fn wait_for_deletion(&mut self) {
+        if let Some(deletion) = self.deletion.take() {
+            let _ = deletion.await;
+        }
+    }
+}
+
+impl fmt::Display for CleanupSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.cancelled {
+            writeln!(f, "清理已中断，以下为部分结果:")?;
+        } else {
+            writeln!(f, "清理完成:")?;
+        }
+
+        writeln!(
+            f,
+            "  已删除 {} 个目录，释放 {}",
+            self.removed.len(),
+            size_format::format_size(self.freed_bytes())
+        )?;
+
+        for (path, size) in &self.removed {
+            writeln!(f, "    ✓ {} ({})", path.display(), size_format::format_size(*size))?;
+        }
+
+        if !self.failed.is_empty() {
+            writeln!(f, "  删除失败 {} 个目录:", self.failed.len())?;
+            for (path, error) in &self.failed {
+                writeln!(f, "    ✗ {}: {}", path.display(), error)?;
+            }
+        }
+
+        if !self.skipped.is_empty() {
+            writeln!(f, "  未处理 {} 个目录:", self.skipped.len())?;
+            for path in &self.skipped {
+                writeln!(f, "    - {}", path.display())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 待清理目录的来源
+#[derive(Debug, Clone, PartialEq)]
+pub enum CleanupDirKind {
+    /// 按项目类型的清理策略选出的目录（Unknown 表示默认的依赖目录列表）
+    Strategy(ProjectType),
+
+    /// Unity / Unreal 缓存目录
+    GameEngineCache,
+
+    /// Xcode / Swift 依赖目录（Pods、Carthage 等）
+    AppleDependency,
+
+    /// Android 模块的构建目录
+    AndroidModuleBuild,
+
+    /// .NET 解决方案中各子项目的构建目录
+    DotNetBuild,
+
+    /// Nix 的 result 链接
+    NixResult,
+
+    /// Xcode DerivedData 中属于该项目的构建目录
+    XcodeDerivedData,
+}
+
+impl fmt::Display for CleanupDirKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CleanupDirKind::Strategy(ProjectType::Unknown) => write!(f, "依赖目录"),
+            CleanupDirKind::Strategy(project_type) => write!(f, "{}", project_type.as_str()),
+            CleanupDirKind::GameEngineCache => write!(f, "引擎缓存"),
+            CleanupDirKind::AppleDependency => write!(f, "Xcode 依赖"),
+            CleanupDirKind::AndroidModuleBuild => write!(f, "Android 构建"),
+            CleanupDirKind::DotNetBuild => write!(f, ".NET 构建"),
+            CleanupDirKind::NixResult => write!(f, "Nix 链接"),
+            CleanupDirKind::XcodeDerivedData => write!(f, "DerivedData"),
+        }
+    }
+}
+
+/// 清理计划中的一个目录
+#[derive(Debug, Clone, PartialEq)]
+pub struct CleanupPlanEntry {
+    /// 将要删除的目录
+    pub path: PathBuf,
+
+    /// 目录大小（符号链接为 0）
+    pub size: u64,
+
+    /// 目录的来源
+    pub kind: CleanupDirKind,
+
+    /// 删除后重新生成耗时较长时的提示
+    pub warning: Option<&'static str>,
+}
+
+/// 清理前的预览：将要删除的目录及其大小，不做任何修改
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CleanupPlan {
+    pub entries: Vec<CleanupPlanEntry>,
+}
+
+impl CleanupPlan {
+    /// 预计释放的总空间
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.size).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for CleanupPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "将要删除以下 {} 个目录（共 {}）:", self.entries.len(), size_format::format_size(self.total_size()))?;
+        for entry in &self.entries {
+            writeln!(f, "  • {} ({}) [{}]", entry.path.display(), size_format::format_size(entry.size), entry.kind)?;
+            if let Some(warning) = entry.warning {
+                writeln!(f, "    ⚠️  {}", warning)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 项目自带的清理命令（如 Android 项目的 gradlew clean）
+#[derive(Debug, Clone, PartialEq)]
+pub struct NativeCleaner {
+    /// 可执行文件路径
+    pub program: PathBuf,
+
+    /// 命令参数
+    pub args: Vec<String>,
+}
+
+impl fmt::Display for NativeCleaner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let program = self.program.file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_else(|| self.program.to_string_lossy());
+        write!(f, "{} {}", program, self.args.join(" "))
+    }
+}
+
+#[derive(Default)]
+pub struct CleanupOperation {
+    /// 清理配置：按项目保留的目录和按项目类型追加的清理目录
+    config: CleanupConfig,
+
+    /// 后台删除的进度回调
+    progress: Option<DeleteProgressCallback>,
+
+    /// 只清理这些名称的目录，为空时清理全部依赖目录
+    only: Vec<String>,
+
+    /// 每个待清理目录都要通过的检查（如必须在扫描根目录之下），未通过的目录不清理
+    path_check: Option<PathCheck>,
+}
 
-#[allow(dead_code)]
 impl CleanupOperation {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// 创建清理操作，跳过配置中按项目保留的目录，并使用配置中追加的清理目录
+    pub fn with_keep(config: CleanupConfig) -> Self {
+        Self { config, ..Self::default() }
+    }
+
+    /// 按完整配置创建清理操作：除 `with_keep` 外，每个待清理目录都要通过 `Config::check_operation_path`，
+    /// 例如开启 restrict_to_scan_paths 时不会清理扫描根目录之外的 Xcode DerivedData
+    pub fn from_config(config: &Config) -> Self {
+        let scope = config.clone();
+        Self::with_keep(config.cleanup.clone())
+            .with_path_check(Arc::new(move |path| scope.check_operation_path(path)))
+    }
+
+    /// 设置待清理目录的检查
+    pub fn with_path_check(mut self, check: PathCheck) -> Self {
+        self.path_check = Some(check);
+        self
+    }
+
+    /// 只清理指定名称的目录（如 node_modules、target）
+    pub fn with_only(mut self, names: Vec<String>) -> Self {
+        self.only = names;
+        self
+    }
+
+    /// 设置后台删除的进度回调
+    pub fn with_progress(mut self, progress: DeleteProgressCallback) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// 项目中标记为保留且存在的目录
+    pub fn kept_dirs(&self, project_path: &Path) -> Vec<PathBuf> {
+        self.config.kept_dirs(project_path)
+            .into_iter()
+            .filter(|dir| dir.exists() || dir.is_symlink())
+            .collect()
+    }
+
+    /// 列出项目中存在的依赖目录（按项目类型的清理策略）、游戏引擎缓存和 Xcode 依赖目录、
+    /// Android 模块和 .NET 子项目的构建目录、Nix 的 result 链接，以及 Xcode DerivedData 中属于该项目的构建目录
+    /// （不含保留的目录，指定了目录名称时只保留名称匹配的目录）
+    pub fn find_dependency_dirs(&self, project_path: &Path) -> Vec<PathBuf> {
+        self.classified_dirs(project_path).into_iter().map(|(dir, _)| dir).collect()
+    }
+
+    /// 同 `find_dependency_dirs`，同时给出每个目录的来源
+    fn classified_dirs(&self, project_path: &Path) -> Vec<(PathBuf, CleanupDirKind)> {
+        let existing = |names: &[&str], kind: CleanupDirKind| -> Vec<(PathBuf, CleanupDirKind)> {
+            names.iter()
+                .map(|name| project_path.join(name))
+                .filter(|path| path.is_dir())
+                .map(|path| (path, kind.clone()))
+                .collect()
+        };
+
+        let mut dirs = Vec::new();
+        for strategy in cleanup_strategy::strategies_for_project(project_path, &self.config) {
+            let names: Vec<&str> = strategy.dirs.iter().map(String::as_str).collect();
+            dirs.extend(existing(&names, CleanupDirKind::Strategy(strategy.project_type)));
+        }
+        dirs.extend(existing(ProjectDetector::game_engine_cache_dir_names(project_path), CleanupDirKind::GameEngineCache));
+        dirs.extend(existing(ProjectDetector::apple_dependency_dir_names(project_path), CleanupDirKind::AppleDependency));
+        dirs.extend(ProjectDetector::android_module_build_dirs(project_path).into_iter()
+            .map(|dir| (dir, CleanupDirKind::AndroidModuleBuild)));
+        dirs.extend(ProjectDetector::dotnet_build_dirs(project_path).into_iter()
+            .map(|dir| (dir, CleanupDirKind::DotNetBuild)));
+        dirs.extend(ProjectDetector::nix_result_links(project_path).into_iter()
+            .map(|dir| (dir, CleanupDirKind::NixResult)));
+        dirs.extend(ProjectDetector::xcode_derived_data_dirs(project_path).into_iter()
+            .map(|dir| (dir, CleanupDirKind::XcodeDerivedData)));
+
+        let kept = self.config.kept_dirs(project_path);
+        let mut seen = HashSet::new();
+        dirs.retain(|(dir, _)| !kept.contains(dir) && seen.insert(dir.clone()));
+        if !self.only.is_empty() {
+            dirs.retain(|(dir, _)| dir.file_name()
+                .is_some_and(|name| self.only.iter().any(|only| name == only.as_str())));
+        }
+        if let Some(check) = &self.path_check {
+            dirs.retain(|(dir, _)| match check(dir) {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::warn!("跳过清理 {}: {}", dir.display(), e);
+                    false
+                }
+            });
+        }
+        dirs
+    }
+
+    /// 预览清理：列出将要删除的目录、大小和来源，不做任何修改
+    pub async fn plan(&self, project_path: &Path) -> CleanupPlan {
+        let (dirs, kinds): (Vec<PathBuf>, Vec<CleanupDirKind>) = self.classified_dirs(project_path).into_iter().unzip();
+        let entries = measure_dirs(dirs).await
+            .into_iter()
+            .zip(kinds)
+            .map(|((path, size), kind)| CleanupPlanEntry {
+                warning: self.regeneration_warning(&path),
+                path,
+                size,
+                kind,
+            })
+            .collect();
+        CleanupPlan { entries }
+    }
+
+    /// 检测项目自带的清理命令：Android 项目存在 Gradle Wrapper 时使用 gradlew clean
+    ///
+    /// 项目中有保留的目录或只清理指定的目录时不使用清理命令，避免其删除不该删除的构建目录。
+    pub fn native_cleaner(&self, project_path: &Path) -> Option<NativeCleaner> {
+        if !ProjectDetector::is_android_project(project_path)
+            || !self.only.is_empty()
+            || !self.kept_dirs(project_path).is_empty()
+        {
+            return None;
+        }
+
+        let wrapper = project_path.join(if cfg!(windows) { "gradlew.bat" } else { "gradlew" });
+        wrapper.is_file().then(|| NativeCleaner {
+            program: wrapper,
+            args: vec!["clean".to_string()],
+        })
+    }
+
+    /// 在项目目录中运行清理命令，输出不显示在终端上，失败时返回错误输出的最后一行
+    pub async fn run_native_cleaner(&self, cleaner: &NativeCleaner, project_path: &Path) -> Result<()> {
+        let output = tokio::process::Command::new(&cleaner.program)
+            .args(&cleaner.args)
+            .current_dir(project_path)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let reason = stderr.lines().rev()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or("未知错误")
+                .to_string();
+            anyhow::bail!("{} 执行失败（{}）: {}", cleaner, output.status, reason);
+        }
+
+        Ok(())
+    }
+
+    /// 删除后重新生成耗时较长的目录给出提示
+    pub fn regeneration_warning(&self, dir: &Path) -> Option<&'static str> {
+        ProjectDetector::game_engine_cache_type(dir)
+            .and_then(|dependency_type| dependency_type.regeneration_warning())
+    }
+
+    /// 清理项目的全部依赖目录：先运行项目自带的清理命令，再删除剩余的目录
+    ///
+    /// 清理命令删除的目录按运行前测得的大小计入结果；清理命令失败时直接删除目录。
+    pub async fn clean_project(&self, project_path: &Path, token: &CancellationToken) -> CleanupSummary {
+        let dirs = self.find_dependency_dirs(project_path);
+        let mut removed_by_cleaner = Vec::new();
+
+        // 上次清理中途退出时残留的临时目录
+        let mut parents: Vec<&Path> = dirs.iter().filter_map(|dir| dir.parent()).collect();
+        parents.push(project_path);
+        parents.sort();
+        parents.dedup();
+        let leftovers: Vec<(PathBuf, PathBuf)> = parents.into_iter()
+            .flat_map(staged_leftovers)
+            .map(|dir| (dir.clone(), dir))
+            .collect();
+        if !leftovers.is_empty() {
+            tokio::task::spawn_blocking(move || delete_staged(leftovers, None));
+        }
+
+        if let Some(cleaner) = self.native_cleaner(project_path) {
+            let sizes = measure_dirs(dirs.clone()).await;
+
+            match self.run_native_cleaner(&cleaner, project_path).await {
+                Ok(()) => {
+                    removed_by_cleaner = sizes.into_iter()
+                        .filter(|(dir, _)| !dir.exists())
+                        .collect();
+                }
+                Err(e) => tracing::warn!("{}", e),
+            }
+        }
+
+        let remaining: Vec<_> = dirs.into_iter()
+            .filter(|dir| dir.exists() || dir.is_symlink())
+            .collect();
+        let mut summary = self.remove_directories(&remaining, token).await;
+        summary.removed.splice(0..0, removed_by_cleaner);
+        summary
+    }
+
+    /// 删除目录：先逐个测量大小并重命名为同级的临时目录，全部移走后在后台删除
+    ///
+    /// 移动过程中被取消时，已移走的目录会恢复原位，不会留下删除到一半的目录。
+    /// 后台删除任务保存在 `CleanupSummary::deletion` 中。
+    pub async fn remove_directories(&self, dirs: &[PathBuf], token: &CancellationToken) -> CleanupSummary {
+        let mut summary = CleanupSummary::default();
+        let mut staged = StagedDirs::default();
+
+        for dir in dirs {
+            // 符号链接（如 Nix 的 result）只删除链接本身，不计入释放的空间
+            if dir.is_symlink() {
+                match std::fs::remove_file(dir) {
+                    Ok(()) => summary.removed.push((dir.clone(), 0)),
+                    Err(e) => summary.failed.push((dir.clone(), e.to_string())),
+                }
+                continue;
+            }
+
+            let measured = dir.clone();
+            let size = tokio::select! {
+                biased;
+                _ = token.cancelled() => break,
+                size = tokio::task::spawn_blocking(move || directory_size(&measured)) => size.unwrap_or(0),
+            };
+
+            let staging = staging_path(dir);
+            match std::fs::rename(dir, &staging) {
+                Ok(()) => staged.dirs.push((dir.clone(), staging, size)),
+                Err(e) => summary.failed.push((dir.clone(), e.to_string())),
+            }
+        }
+
+        if token.is_cancelled() {
+            summary.cancelled = true;
+            summary.failed.extend(staged.restore());
+            let handled: Vec<&PathBuf> = summary.removed.iter().map(|(path, _)| path)
+                .chain(summary.failed.iter().map(|(path, _)| path))
+                .collect();
+            let skipped = dirs.iter().filter(|dir| !handled.contains(dir)).cloned().collect();
+            summary.skipped = skipped;
+            return summary;
+        }
+
+        let staged = staged.commit();
+        let staging_dirs: Vec<(PathBuf, PathBuf)> = staged.iter()
+            .map(|(dir, staging, _)| (dir.clone(), staging.clone()))
+            .collect();
+        summary.removed.extend(staged.into_iter().map(|(dir, _, size)| (dir, size)));
+        if !staging_dirs.is_empty() {
+            let progress = self.progress.clone();
+            summary.deletion = Some(tokio::task::spawn_blocking(move || delete_staged(staging_dirs, progress)));
+        }
+
+        summary
+    }
+}
+
+/// 已移走、等待删除的目录（原路径、临时路径、大小）
+///
+/// 未确认删除就被丢弃时（如所在任务被中止）将目录恢复原位。
+#[derive(Default)]
+struct StagedDirs {
+    dirs: Vec<(PathBuf, PathBuf, u64)>,
+}
+
+impl StagedDirs {
+    /// 按移走的相反顺序恢复全部目录，返回恢复失败的目录及错误信息
+    fn restore(&mut self) -> Vec<(PathBuf, String)> {
+        self.dirs.drain(..)
+            .rev()
+            .filter_map(|(dir, staging, _)| {
+                std::fs::rename(&staging, &dir).err()
+                    .map(|e| (dir, format!("恢复失败（目录位于 {}）: {}", staging.display(), e)))
+            })
+            .collect()
+    }
+
+    /// 确认删除，交出已移走的目录
+    fn commit(mut self) -> Vec<(PathBuf, PathBuf, u64)> {
+        std::mem::take(&mut self.dirs)
+    }
+}
+
+impl Drop for StagedDirs {
+    fn drop(&mut self) {
+        for (dir, error) in self.restore() {
+            tracing::warn!("{}: {}", dir.display(), error);
+        }
+    }
+}
+
+/// 待删除目录重命名后的路径：与原目录同级的隐藏目录，保证重命名不跨文件系统
+fn staging_path(dir: &Path) -> PathBuf {
+    let name = dir.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+    dir.with_file_name(format!(".{}{}{}", name, STAGING_MARKER, nanos))
+}
+
+/// 目录中残留的待删除临时目录
+fn staged_leftovers(parent: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(parent)
+        .map(|entries| entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().contains(STAGING_MARKER))
+            .map(|entry| entry.path())
+            .collect())
+        .unwrap_or_default()
+}
+
+/// 并行删除已移走的目录（原路径、临时路径），按原路径报告进度，失败时只记录日志
+fn delete_staged(dirs: Vec<(PathBuf, PathBuf)>, progress: Option<DeleteProgressCallback>) {
+    for (dir, staging) in dirs {
+        let is_node_modules = dir.file_name().is_some_and(|name| name == NODE_MODULES_DIR);
+        let deleter = ParallelDeleter::new().robust(is_node_modules);
+        let result = deleter.delete(&staging, |current| {
+            if let Some(progress) = &progress {
+                progress(&dir, current);
+            }
+        });
+        if let Err(e) = result {
+            tracing::warn!("删除临时目录 {} 失败: {}", staging.display(), e);
+        }
+    }
+}
+
+/// 在后台线程中测量目录大小，符号链接不计入
+async fn measure_dirs(dirs: Vec<PathBuf>) -> Vec<(PathBuf, u64)> {
+    tokio::task::spawn_blocking(move || {
+        dirs.into_iter()
+            .map(|dir| {
+                let size = if dir.is_symlink() { 0 } else { directory_size(&dir) };
+                (dir, size)
+            })
+            .collect()
+    }).await.unwrap_or_default()
+}
+
+/// 计算目录中所有文件的总大小
+fn directory_size(path: &Path) -> u64 {
+    WalkDir::new(long_path::extended(path))
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_kept_dirs_are_not_cleaned() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join("node_modules/pkg")).unwrap();
+        fs::create_dir_all(project.join("build")).unwrap();
+        fs::write(project.join("build/index.html"), "<html></html>").unwrap();
+
+        let mut keep = CleanupConfig::default();
+        assert!(keep.add_kept_dir(project, "build"));
+        assert!(!keep.add_kept_dir(project, "build"));
+
+        let operation = CleanupOperation::with_keep(keep);
+        assert_eq!(operation.find_dependency_dirs(project), vec![project.join("node_modules")]);
+        assert_eq!(operation.kept_dirs(project), vec![project.join("build")]);
+
+        let summary = operation.clean_project(project, &CancellationToken::new()).await;
+        assert_eq!(summary.removed.len(), 1);
+        assert!(!project.join("node_modules").exists());
+        assert!(project.join("build/index.html").exists());
+    }
+
+    #[tokio::test]
+    async fn test_only_named_dirs_are_cleaned() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join("node_modules/pkg")).unwrap();
+        fs::write(project.join("node_modules/pkg/index.js"), "x".repeat(100)).unwrap();
+        fs::create_dir_all(project.join("target")).unwrap();
+        fs::write(project.join("target/app"), "x".repeat(50)).unwrap();
+        fs::create_dir_all(project.join("dist")).unwrap();
+
+        let operation = CleanupOperation::new()
+            .with_only(vec!["node_modules".to_string(), "target".to_string()]);
+        let plan = operation.plan(project).await;
+        let mut entries: Vec<(PathBuf, u64)> = plan.entries.iter().map(|entry| (entry.path.clone(), entry.size)).collect();
+        entries.sort();
+        assert_eq!(entries, vec![(project.join("node_modules"), 100), (project.join("target"), 50)]);
+        assert_eq!(plan.total_size(), 150);
+        assert_eq!(plan.entries[0].kind, CleanupDirKind::Strategy(ProjectType::Unknown));
+
+        let mut summary = operation.clean_project(project, &CancellationToken::new()).await;
+        summary.wait_for_deletion().await;
+        assert_eq!(summary.freed_bytes(), 150);
+        assert!(!project.join("node_modules").exists());
+        assert!(project.join("dist").exists());
+    }
+
+    #[tokio::test]
+    async fn test_dirs_outside_scan_paths_are_not_cleaned() {
+        let temp_dir = tempdir().unwrap();
+        let inside = temp_dir.path().join("code/app");
+        let outside = temp_dir.path().join("elsewhere/app");
+        for project in [&inside, &outside] {
+            fs::create_dir_all(project.join("node_modules/pkg")).unwrap();
+        }
+
+        let mut config = Config::default();
+        config.scan_paths = vec![temp_dir.path().join("code").display().to_string()];
+        config.cleanup.restrict_to_scan_paths = true;
+        let operation = CleanupOperation::from_config(&config);
+        assert_eq!(operation.find_dependency_dirs(&inside), vec![inside.join("node_modules")]);
+        assert!(operation.plan(&outside).await.is_empty());
+
+        let summary = operation.clean_project(&outside, &CancellationToken::new()).await;
+        assert!(summary.removed.is_empty());
+        assert!(outside.join("node_modules").exists());
+
+        config.cleanup.restrict_to_scan_paths = false;
+        let operation = CleanupOperation::from_config(&config);
+        assert_eq!(operation.find_dependency_dirs(&outside), vec![outside.join("node_modules")]);
+    }
+
+    #[tokio::test]
+    async fn test_remove_directories() {
+        let temp_dir = tempdir().unwrap();
+        let node_modules = temp_dir.path().join("node_modules");
+        fs::create_dir_all(node_modules.join("pkg")).unwrap();
+        fs::write(node_modules.join("pkg").join("index.js"), "x".repeat(100)).unwrap();
+        let target = temp_dir.path().join("target");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("app"), "x".repeat(50)).unwrap();
+
+        let operation = CleanupOperation::new();
+        let dirs = operation.find_dependency_dirs(temp_dir.path());
+        assert_eq!(dirs.len(), 2);
+
+        let mut summary = operation.remove_directories(&dirs, &CancellationToken::new()).await;
+        assert!(!summary.cancelled);
+        assert_eq!(summary.removed.len(), 2);
+        assert_eq!(summary.freed_bytes(), 150);
+        assert!(!node_modules.exists());
+        assert!(!target.exists());
+
+        // 移走的临时目录在后台删除
+        summary.wait_for_deletion().await;
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_staged_dirs_restored_when_dropped() {
+        let temp_dir = tempdir().unwrap();
+        let node_modules = temp_dir.path().join("node_modules");
+        fs::create_dir_all(node_modules.join("pkg")).unwrap();
+
+        let staging = staging_path(&node_modules);
+        assert!(staging.file_name().unwrap().to_string_lossy().starts_with(".node_modules.pm-cleanup-"));
+        fs::rename(&node_modules, &staging).unwrap();
+
+        // 模拟清理任务在移走目录后被中止
+        let staged = StagedDirs { dirs: vec![(node_modules.clone(), staging.clone(), 0)] };
+        drop(staged);
+
+        assert!(node_modules.join("pkg").exists());
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn test_find_unity_cache_dirs() {
+        let temp_dir = tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join("Assets")).unwrap();
+        fs::create_dir(temp_dir.path().join("ProjectSettings")).unwrap();
+        fs::write(
+            temp_dir.path().join("ProjectSettings").join("ProjectVersion.txt"),
+            "m_EditorVersion: 2022.3.10f1\n",
+        ).unwrap();
+        fs::create_dir(temp_dir.path().join("Library")).unwrap();
+        fs::create_dir(temp_dir.path().join("Temp")).unwrap();
+
+        let operation = CleanupOperation::new();
+        let dirs = operation.find_dependency_dirs(temp_dir.path());
+        assert_eq!(dirs, vec![temp_dir.path().join("Library"), temp_dir.path().join("Temp")]);
+        assert!(operation.regeneration_warning(&dirs[0]).is_some());
+
+        // 非 Unity 项目中的同名目录不会被清理
+        fs::remove_dir_all(temp_dir.path().join("ProjectSettings")).unwrap();
+        assert!(operation.find_dependency_dirs(temp_dir.path()).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_android_native_cleaner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path();
+        fs::write(project.join("settings.gradle"), "include ':app'").unwrap();
+        fs::create_dir_all(project.join("app").join("src").join("main")).unwrap();
+        fs::write(project.join("app").join("build.gradle"), "").unwrap();
+        fs::write(project.join("app").join("src").join("main").join("AndroidManifest.xml"), "<manifest/>").unwrap();
+        fs::create_dir_all(project.join("app").join("build")).unwrap();
+
+        let operation = CleanupOperation::new();
+        assert_eq!(operation.find_dependency_dirs(project), vec![project.join("app").join("build")]);
+        assert!(operation.native_cleaner(project).is_none());
+
+        // 模拟 gradlew clean：删除模块的 build 目录
+        let gradlew = project.join("gradlew");
+        fs::write(&gradlew, "#!/bin/sh\n[ \"$1\" = clean ] && rm -rf app/build\n").unwrap();
+        fs::set_permissions(&gradlew, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let cleaner = operation.native_cleaner(project).unwrap();
+        assert_eq!(cleaner.to_string(), "gradlew clean");
+        operation.run_native_cleaner(&cleaner, project).await.unwrap();
+        assert!(!project.join("app").join("build").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_remove_nix_result_link_keeps_target() {
+        let temp_dir = tempdir().unwrap();
+        let store_path = temp_dir.path().join("store-output");
+        fs::create_dir(&store_path).unwrap();
+        fs::write(store_path.join("bin"), "x".repeat(10)).unwrap();
+        let project = temp_dir.path().join("project");
+        fs::create_dir(&project).unwrap();
+        std::os::unix::fs::symlink(&store_path, project.join("result")).unwrap();
+
+        let operation = CleanupOperation::new();
+        let dirs = operation.find_dependency_dirs(&project);
+        assert_eq!(dirs, vec![project.join("result")]);
+
+        let summary = operation.remove_directories(&dirs, &CancellationToken::new()).await;
+        assert_eq!(summary.removed, vec![(project.join("result"), 0)]);
+        assert!(!project.join("result").exists());
+        assert!(store_path.join("bin").exists());
+    }
+
+    #[tokio::test]
+    async fn test_remove_directories_cancelled() {
+        let temp_dir = tempdir().unwrap();
+        let node_modules = temp_dir.path().join("node_modules");
+        fs::create_dir(&node_modules).unwrap();
+
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let operation = CleanupOperation::new();
+        let summary = operation.remove_directories(std::slice::from_ref(&node_modules), &token).await;
+        assert!(summary.cancelled);
+        assert_eq!(summary.skipped, vec![node_modules.clone()]);
+        assert!(node_modules.exists());
+        assert!(summary.to_string().contains("清理已中断"));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,109 @@
+// 按项目类型决定清理哪些目录：每类项目有内置的清理策略，配置中可以为各类型追加目录
+
+use std::path::Path;
+
+use crate::config::settings::CleanupConfig;
+use crate::models::ProjectType;
+use crate::scanner::ProjectDetector;
+
+use super::cleanup::DEFAULT_CLEAN_DIRS;
+
+/// 某类项目的清理策略
+#[derive(Debug, Clone, PartialEq)]
+pub struct CleanupStrategy {
+    /// 适用的项目类型
+    pub project_type: ProjectType,
+
+    /// 删除的目录（相对于项目根目录）
+    pub dirs: Vec<String>,
+}
+
+impl CleanupStrategy {
+    /// 内置的清理策略，没有内置策略的类型返回 None
+    pub fn builtin(project_type: &ProjectType) -> Option<Self> {
+        let dirs: &[&str] = match project_type {
+            // 相当于 cargo clean：只删除构建目录，保留 Cargo.lock
+            ProjectType::Rust => &["target"],
+            ProjectType::NodeJs => &["node_modules", ".next", ".nuxt", ".svelte-kit", ".parcel-cache", ".turbo", "dist", "build"],
+            ProjectType::Python => &["venv", ".venv", "__pycache__", ".pytest_cache", ".mypy_cache", ".ruff_cache", ".tox", "build", "dist"],
+            ProjectType::Java => &["target", "build", ".gradle"],
+            ProjectType::Android => &["build", ".gradle", ".cxx"],
+            ProjectType::Cpp => &["build", "cmake-build-debug", "cmake-build-release"],
+            ProjectType::Terraform => &[".terraform", ".terragrunt-cache"],
+            ProjectType::Swift => &[".build"],
+            ProjectType::DotNet => &["bin", "obj"],
+            ProjectType::Dart => &[".dart_tool", "build"],
+            ProjectType::Php => &["vendor"],
+            ProjectType::Elixir => &["_build", "deps"],
+            ProjectType::Zig => &["zig-cache", ".zig-cache", "zig-out"],
+            _ => return None,
+        };
+        Some(Self {
+            project_type: project_type.clone(),
+            dirs: dirs.iter().map(|dir| dir.to_string()).collect(),
+        })
+    }
+
+    /// 内置策略加上配置中为该类型追加的目录，两者都没有时返回 None
+    pub fn resolve(project_type: &ProjectType, config: &CleanupConfig) -> Option<Self> {
+        let extra = config.extra_dirs(project_type);
+        let mut strategy = match Self::builtin(project_type) {
+            Some(strategy) => strategy,
+            None if extra.is_empty() => return None,
+            None => Self { project_type: project_type.clone(), dirs: Vec::new() },
+        };
+        for dir in extra {
+            if !strategy.dirs.contains(dir) {
+                strategy.dirs.push(dir.clone());
+            }
+        }
+        Some(strategy)
+    }
+}
+
+/// 项目适用的清理策略：按根目录的标识文件判断项目类型，每种类型一个策略；
+/// 没有适用的策略时（如未知类型或纯 Git 仓库）返回清理默认依赖目录列表的 Unknown 策略
+pub fn strategies_for_project(project_path: &Path, config: &CleanupConfig) -> Vec<CleanupStrategy> {
+    let strategies: Vec<CleanupStrategy> = ProjectDetector::marker_types(project_path).iter()
+        .filter_map(|project_type| CleanupStrategy::resolve(project_type, config))
+        .collect();
+
+    if strategies.is_empty() {
+        return vec![CleanupStrategy {
+            project_type: ProjectType::Unknown,
+            dirs: DEFAULT_CLEAN_DIRS.iter().map(|dir| dir.to_string()).collect(),
+        }];
+    }
+    strategies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_strategies_for_project() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path();
+        let mut config = CleanupConfig::default();
+
+        // 没有标识文件时使用默认目录
+        let strategies = strategies_for_project(project, &config);
+        assert_eq!(strategies[0].project_type, ProjectType::Unknown);
+        assert!(strategies[0].dirs.contains(&"node_modules".to_string()));
+
+        fs::write(project.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        assert_eq!(strategies_for_project(project, &config)[0].dirs, ["target"]);
+
+        // 混合项目每种类型一个策略，配置中追加的目录排在内置目录之后
+        fs::write(project.join("package.json"), "{}").unwrap();
+        config.strategies.insert("rust".to_string(), vec!["coverage".to_string(), "target".to_string()]);
+        let strategies = strategies_for_project(project, &config);
+        let types: Vec<&ProjectType> = strategies.iter().map(|strategy| &strategy.project_type).collect();
+        assert_eq!(types, [&ProjectType::NodeJs, &ProjectType::Rust]);
+        assert!(strategies[0].dirs.starts_with(&["node_modules".to_string(), ".next".to_string(), ".nuxt".to_string()]));
+        assert_eq!(strategies[1].dirs, ["target", "coverage"]);
+    }
+}
@@ -0,0 +1,265 @@
+// 并行删除大型目录
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use walkdir::WalkDir;
+
+use crate::utils::long_path;
+
+/// 每删除多少个文件报告一次进度
+const PROGRESS_INTERVAL: usize = 256;
+
+/// 稳健模式下删除失败后的重试次数
+const ROBUST_RETRIES: u32 = 3;
+
+/// 稳健模式下首次重试前的等待时间，之后每次翻倍
+const ROBUST_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// 删除进度
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DeleteProgress {
+    /// 已删除的文件数
+    pub deleted_files: usize,
+
+    /// 文件总数
+    pub total_files: usize,
+
+    /// 已删除的字节数
+    pub deleted_bytes: u64,
+
+    /// 总字节数
+    pub total_bytes: u64,
+}
+
+impl DeleteProgress {
+    /// 是否已删除全部文件
+    pub fn is_complete(&self) -> bool {
+        self.deleted_files >= self.total_files
+    }
+}
+
+/// 并行删除器：先遍历出目录中的全部文件，再由有限数量的线程分块删除，最后自底向上删除空目录
+///
+/// 遍历顺序使同一子树中的文件落在相邻的分块中，每个线程大致负责若干棵子树。
+/// 在 Windows 上以扩展长度路径访问，深层 node_modules 超过 MAX_PATH 也能删除。
+pub struct ParallelDeleter {
+    /// 最多同时删除的线程数
+    max_tasks: usize,
+
+    /// 是否使用稳健删除（见 [`ParallelDeleter::robust`]）
+    robust: bool,
+}
+
+impl Default for ParallelDeleter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParallelDeleter {
+    pub fn new() -> Self {
+        Self {
+            max_tasks: num_cpus::get().clamp(2, 8),
+            robust: false,
+        }
+    }
+
+    /// 稳健删除，用于 node_modules 这类包管理器生成的目录树：
+    /// 去掉只读属性后重试、把指向目录的链接（Windows 目录联接）作为目录删除，
+    /// 并对杀毒软件或索引服务短暂占用造成的失败稍后重试。
+    pub fn robust(mut self, robust: bool) -> Self {
+        self.robust = robust;
+        self
+    }
+
+    /// 删除目录及其全部内容（阻塞执行）；进度回调可能在多个线程中调用
+    pub fn delete(&self, dir: &Path, on_progress: impl Fn(DeleteProgress) + Sync) -> io::Result<DeleteProgress> {
+        let dir = long_path::extended(dir);
+        let mut files: Vec<(PathBuf, u64)> = Vec::new();
+        let mut dirs = Vec::new();
+        for entry in WalkDir::new(&dir).follow_links(false) {
+            let entry = entry?;
+            if entry.file_type().is_dir() {
+                dirs.push(entry.into_path());
+            } else {
+                // 符号链接只删除链接本身
+                let size = if entry.file_type().is_file() {
+                    entry.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+                } else {
+                    0
+                };
+                files.push((entry.into_path(), size));
+            }
+        }
+
+        let total = DeleteProgress {
+            total_files: files.len(),
+            total_bytes: files.iter().map(|(_, size)| size).sum(),
+            ..DeleteProgress::default()
+        };
+        let progress_at = |deleted_files, deleted_bytes| DeleteProgress { deleted_files, deleted_bytes, ..total };
+        on_progress(total);
+
+        let deleted_files = &AtomicUsize::new(0);
+        let deleted_bytes = &AtomicU64::new(0);
+        let first_error: &Mutex<Option<io::Error>> = &Mutex::new(None);
+        let on_progress = &on_progress;
+        let chunk_size = files.len().div_ceil(self.max_tasks).max(1);
+        let robust = self.robust;
+
+        std::thread::scope(|scope| {
+            for chunk in files.chunks(chunk_size) {
+                scope.spawn(move || {
+                    for (path, size) in chunk {
+                        match remove_file(path, robust) {
+                            Ok(()) => {}
+                            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                            Err(e) => {
+                                first_error.lock().unwrap().get_or_insert(e);
+                                continue;
+                            }
+                        }
+
+                        let bytes = deleted_bytes.fetch_add(*size, Ordering::Relaxed) + size;
+                        let count = deleted_files.fetch_add(1, Ordering::Relaxed) + 1;
+                        if count.is_multiple_of(PROGRESS_INTERVAL) {
+                            on_progress(progress_at(count, bytes));
+                        }
+                    }
+                });
+            }
+        });
+
+        let progress = progress_at(deleted_files.load(Ordering::Relaxed), deleted_bytes.load(Ordering::Relaxed));
+        on_progress(progress);
+        if let Some(e) = first_error.lock().unwrap().take() {
+            return Err(e);
+        }
+
+        // 遍历时父目录先于子目录，倒序删除即可保证目录已清空
+        for dir in dirs.iter().rev() {
+            remove_dir(dir, self.robust)?;
+        }
+
+        Ok(progress)
+    }
+}
+
+/// 删除文件或链接；稳健模式下按 [`retry_robustly`] 重试
+fn remove_file(path: &Path, robust: bool) -> io::Result<()> {
+    if !robust {
+        return std::fs::remove_file(path);
+    }
+
+    retry_robustly(path, || match std::fs::remove_file(path) {
+        // Windows 上的目录联接和目录符号链接需要按目录删除
+        Err(e) if cfg!(windows) && path.is_dir() => std::fs::remove_dir(path).map_err(|_| e),
+        result => result,
+    })
+}
+
+/// 删除空目录；稳健模式下按 [`retry_robustly`] 重试
+fn remove_dir(path: &Path, robust: bool) -> io::Result<()> {
+    if !robust {
+        return std::fs::remove_dir(path);
+    }
+
+    retry_robustly(path, || std::fs::remove_dir(path))
+}
+
+/// 执行删除操作，失败时去掉路径及其上级目录的只读属性，等待后重试
+fn retry_robustly(path: &Path, remove: impl Fn() -> io::Result<()>) -> io::Result<()> {
+    let mut delay = ROBUST_RETRY_DELAY;
+    for _ in 0..ROBUST_RETRIES {
+        match remove() {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                make_writable(path);
+                if let Some(parent) = path.parent() {
+                    make_writable(parent);
+                }
+                std::thread::sleep(delay);
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+    remove()
+}
+
+/// 去掉路径的只读属性（不跟随符号链接）
+fn make_writable(path: &Path) {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return;
+    };
+    if metadata.file_type().is_symlink() {
+        return;
+    }
+
+    let mut permissions = metadata.permissions();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode(permissions.mode() | 0o700);
+    }
+    #[cfg(not(unix))]
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(false);
+    let _ = std::fs::set_permissions(path, permissions);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parallel_delete_reports_progress() {
+        let temp_dir = tempdir().unwrap();
+        let target = temp_dir.path().join("target");
+        for module in ["debug/deps", "debug/build/foo", "release"] {
+            let dir = target.join(module);
+            fs::create_dir_all(&dir).unwrap();
+            for i in 0..200 {
+                fs::write(dir.join(format!("file_{}", i)), "x".repeat(10)).unwrap();
+            }
+        }
+
+        let reports = Mutex::new(Vec::new());
+        let progress = ParallelDeleter { max_tasks: 3, robust: false }
+            .delete(&target, |progress| reports.lock().unwrap().push(progress))
+            .unwrap();
+
+        assert!(!target.exists());
+        assert!(progress.is_complete());
+        assert_eq!(progress.deleted_files, 600);
+        assert_eq!(progress.deleted_bytes, 6000);
+
+        let reports = reports.into_inner().unwrap();
+        assert_eq!(reports.first().map(|p| p.deleted_files), Some(0));
+        assert_eq!(reports.last(), Some(&progress));
+        assert!(reports.len() > 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_robust_delete_clears_read_only_dirs() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let node_modules = temp_dir.path().join("node_modules");
+        let package = node_modules.join("left-pad/lib");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("index.js"), "module.exports = {};").unwrap();
+        fs::set_permissions(&package, fs::Permissions::from_mode(0o555)).unwrap();
+        fs::set_permissions(node_modules.join("left-pad"), fs::Permissions::from_mode(0o555)).unwrap();
+
+        let progress = ParallelDeleter::new().robust(true).delete(&node_modules, |_| {}).unwrap();
+
+        assert!(!node_modules.exists());
+        assert_eq!(progress.deleted_files, 1);
+    }
+}
@@ -0,0 +1,253 @@
+// 环境诊断：汇总配置、缓存、编辑器、Git、终端和扫描路径的状态，提交问题报告时直接附上输出
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::config::settings::EditorConfig;
+use crate::scanner::access::AccessChecker;
+use crate::scanner::size_cache::CacheStats;
+use crate::utils::{path_match, size_format, time_format};
+
+/// TUI 正常显示所需的最小终端尺寸（列, 行）
+const MIN_TERMINAL_SIZE: (u16, u16) = (80, 24);
+
+/// 单个诊断项
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    /// 名称
+    pub label: String,
+
+    /// 检查结果
+    pub value: String,
+
+    /// 是否是需要处理的问题
+    pub problem: bool,
+}
+
+impl Check {
+    /// 正常的诊断项
+    pub fn ok(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self { label: label.into(), value: value.into(), problem: false }
+    }
+
+    /// 需要处理的问题
+    pub fn problem(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self { label: label.into(), value: value.into(), problem: true }
+    }
+}
+
+/// 诊断报告，按分组输出
+#[derive(Debug, Default)]
+pub struct Diagnosis {
+    sections: Vec<(&'static str, Vec<Check>)>,
+}
+
+impl Diagnosis {
+    /// 添加一组诊断项
+    pub fn push(&mut self, title: &'static str, checks: Vec<Check>) {
+        self.sections.push((title, checks));
+    }
+
+    /// 发现的问题数量
+    pub fn problem_count(&self) -> usize {
+        self.sections.iter()
+            .flat_map(|(_, checks)| checks)
+            .filter(|check| check.problem)
+            .count()
+    }
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (title, checks) in &self.sections {
+            writeln!(f, "[{}]", title)?;
+            for check in checks {
+                let marker = if check.problem { "✗" } else { " " };
+                writeln!(f, " {} {}: {}", marker, check.label, check.value)?;
+            }
+            writeln!(f)?;
+        }
+        match self.problem_count() {
+            0 => write!(f, "未发现问题"),
+            count => write!(f, "发现 {} 个问题（标记为 ✗）", count),
+        }
+    }
+}
+
+/// 配置文件：实际使用的路径和能否写入（TUI 中的设置修改会写回配置文件）
+pub fn config_checks(config_path: &Path) -> Vec<Check> {
+    let mut checks = vec![Check::ok("配置文件", config_path.display().to_string())];
+    match std::fs::metadata(config_path) {
+        Ok(metadata) if metadata.permissions().readonly() => {
+            checks.push(Check::problem("状态", "只读，TUI 中修改的设置无法保存"));
+        }
+        Ok(_) => checks.push(Check::ok("状态", "可读写")),
+        Err(_) => checks.push(Check::ok("状态", "不存在，使用默认配置")),
+    }
+    checks
+}
+
+/// 大小缓存：位置、条目数和文件大小；`stats` 为 None 表示缓存文件尚未创建
+pub fn cache_checks(enabled: bool, cache_file: &Path, stats: Option<&CacheStats>) -> Vec<Check> {
+    let mut checks = vec![Check::ok("缓存文件", cache_file.display().to_string())];
+    if !enabled {
+        checks.push(Check::ok("状态", "未启用（[cache] enabled = false）"));
+        return checks;
+    }
+    let Some(stats) = stats else {
+        checks.push(Check::ok("状态", "尚未创建，首次扫描后生成"));
+        return checks;
+    };
+    checks.push(Check::ok("文件大小", size_format::format_size(stats.cache_file_size)));
+    checks.push(Check::ok("条目数", format!("{}（已过期 {} 个）", stats.total_entries, stats.expired_entries)));
+    checks.push(Check::ok("最后更新", time_format::format_time(stats.last_updated)));
+    if cache_file.parent().and_then(|dir| std::fs::metadata(dir).ok()).is_some_and(|metadata| metadata.permissions().readonly()) {
+        checks.push(Check::problem("缓存目录", "只读，计算结果无法写入缓存"));
+    }
+    checks
+}
+
+/// 编辑器：配置的打开命令能否在 PATH 中找到；找不到的命令会跳过，默认命令都找不到时才是问题
+pub fn editor_checks(
+    config: &EditorConfig,
+    env_editor: Option<String>,
+    find: impl Fn(&str) -> Option<PathBuf>,
+) -> Vec<Check> {
+    let mut checks = vec![Check::ok("$VISUAL/$EDITOR", env_editor.unwrap_or_else(|| "(未设置)".to_string()))];
+    if config.default.is_empty() && config.by_type.is_empty() {
+        checks.push(Check::problem("打开命令", "未配置（[editor] default）"));
+        return checks;
+    }
+
+    let mut types: Vec<_> = config.by_type.iter().collect();
+    types.sort();
+    let commands = config.default.iter()
+        .map(|command| ("默认".to_string(), command))
+        .chain(types.into_iter().flat_map(|(key, commands)| commands.iter().map(move |command| (key.clone(), command))));
+    let mut default_available = false;
+    for (key, command) in commands {
+        let program = command.split_whitespace().next().unwrap_or_default();
+        let label = format!("{} ({})", command, key);
+        checks.push(match find(program) {
+            Some(path) => {
+                default_available |= key == "默认";
+                Check::ok(label, path.display().to_string())
+            }
+            None => Check::ok(label, "在 PATH 中未找到"),
+        });
+    }
+    if !default_available {
+        checks.push(Check::problem("打开命令", "默认命令都不可用，无法打开项目"));
+    }
+    checks
+}
+
+/// Git：内置的 libgit2 版本和 git 命令的版本（检查远程仓库时需要 git 命令）
+pub fn git_checks(libgit2_version: (u32, u32, u32), git_version: Option<String>) -> Vec<Check> {
+    let (major, minor, patch) = libgit2_version;
+    vec![
+        Check::ok("libgit2", format!("{}.{}.{}", major, minor, patch)),
+        match git_version {
+            Some(version) => Check::ok("git", version),
+            None => Check::problem("git", "未找到（git unpushed --check-remotes 需要）"),
+        },
+    ]
+}
+
+/// 终端：类型、颜色支持、窗口大小和会话环境
+pub fn terminal_checks(
+    env: impl Fn(&str) -> Option<String>,
+    is_terminal: bool,
+    size: Option<(u16, u16)>,
+) -> Vec<Check> {
+    let colors = match env("COLORTERM").as_deref() {
+        Some("truecolor" | "24bit") => "真彩色",
+        _ if env("TERM").is_some_and(|term| term.contains("256color")) => "256 色",
+        _ => "基本颜色",
+    };
+    let mut checks = vec![
+        Check::ok("TERM", env("TERM").unwrap_or_else(|| "(未设置)".to_string())),
+        Check::ok("颜色", colors),
+        Check::ok("标准输出是终端", if is_terminal { "是" } else { "否（输出被重定向）" }),
+    ];
+    if let Some((columns, rows)) = size {
+        let (min_columns, min_rows) = MIN_TERMINAL_SIZE;
+        let value = format!("{}x{}", columns, rows);
+        checks.push(if columns < min_columns || rows < min_rows {
+            Check::problem("窗口大小", format!("{}，TUI 至少需要 {}x{}", value, min_columns, min_rows))
+        } else {
+            Check::ok("窗口大小", value)
+        });
+    }
+    if env("NO_COLOR").is_some() {
+        checks.push(Check::ok("NO_COLOR", "已设置"));
+    }
+    let sessions: Vec<&str> = [("TMUX", "tmux"), ("STY", "screen"), ("SSH_CONNECTION", "SSH")]
+        .iter()
+        .filter(|(var, _)| env(var).is_some())
+        .map(|&(_, name)| name)
+        .collect();
+    if !sessions.is_empty() {
+        checks.push(Check::ok("会话", sessions.join(" + ")));
+    }
+    checks
+}
+
+/// 扫描路径：是否存在、能否读取，只读的目录无法清理依赖
+pub fn scan_path_checks(paths: &[String], checker: &AccessChecker) -> Vec<Check> {
+    paths.iter()
+        .map(|path| {
+            let expanded = path_match::expand_home(path);
+            match std::fs::metadata(&expanded) {
+                Err(_) => Check::problem(path, "不存在"),
+                Ok(metadata) if !metadata.is_dir() => Check::problem(path, "不是目录"),
+                Ok(metadata) => match checker.check(&expanded) {
+                    Err(reason) => Check::problem(path, reason.label()),
+                    Ok(()) if metadata.permissions().readonly() => Check::problem(path, "只读，无法清理其中的依赖目录"),
+                    Ok(()) => Check::ok(path, "可读写"),
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_doctor_reports_missing_editor_and_scan_path() {
+        let editor = EditorConfig {
+            default: vec!["nvim".to_string(), "code --wait".to_string()],
+            by_type: HashMap::new(),
+        };
+        let checks = editor_checks(&editor, None, |program| (program == "nvim").then(|| PathBuf::from("/usr/bin/nvim")));
+        assert_eq!(checks[1], Check::ok("nvim (默认)", "/usr/bin/nvim"));
+        assert_eq!(checks[2], Check::ok("code --wait (默认)", "在 PATH 中未找到"));
+        assert!(editor_checks(&editor, None, |_| None).last().is_some_and(|check| check.problem));
+
+        let dir = tempdir().unwrap();
+        let existing = dir.path().display().to_string();
+        let missing = dir.path().join("missing").display().to_string();
+        let checks = scan_path_checks(&[existing.clone(), missing.clone()], &AccessChecker::default());
+        assert_eq!(checks, [Check::ok(&existing, "可读写"), Check::problem(&missing, "不存在")]);
+
+        let env = |var: &str| match var {
+            "TERM" => Some("xterm-256color".to_string()),
+            "TMUX" => Some("/tmp/tmux".to_string()),
+            _ => None,
+        };
+        let terminal = terminal_checks(env, true, Some((60, 20)));
+        assert_eq!(terminal[1], Check::ok("颜色", "256 色"));
+        assert!(terminal[3].problem);
+        assert_eq!(terminal[4], Check::ok("会话", "tmux"));
+
+        let mut diagnosis = Diagnosis::default();
+        diagnosis.push("扫描路径", checks);
+        diagnosis.push("Git", git_checks((1, 8, 1), None));
+        assert_eq!(diagnosis.problem_count(), 2);
+        assert!(diagnosis.to_string().ends_with("发现 2 个问题（标记为 ✗）"));
+    }
+}
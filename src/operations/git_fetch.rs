@@ -0,0 +1,119 @@
+// 批量 git fetch：从 origin 获取最新提交，再比较当前分支与 origin 上对应分支的领先/落后提交数
+
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use git2::Repository;
+
+use super::remote_check::{last_error_line, non_interactive_git};
+use crate::scanner::GitAnalyzer;
+
+pub use crate::models::AheadBehind;
+
+/// 单个 git fetch 的超时时间
+const FETCH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 同时运行的 git fetch 数量
+pub const CONCURRENT_FETCHES: usize = 4;
+
+/// 仓库的 fetch 状态
+#[derive(Debug, Clone, PartialEq)]
+pub enum FetchStatus {
+    /// 等待 fetch
+    Queued,
+
+    /// 正在 fetch
+    Fetching,
+
+    /// 已 fetch；当前分支在 origin 上没有对应分支（或 HEAD 游离）时为 None
+    Fetched(Option<AheadBehind>),
+
+    /// fetch 失败，附带原因
+    Failed(String),
+}
+
+impl FetchStatus {
+    /// 是否还在等待或进行中
+    pub fn is_pending(&self) -> bool {
+        matches!(self, FetchStatus::Queued | FetchStatus::Fetching)
+    }
+}
+
+impl fmt::Display for FetchStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchStatus::Queued => write!(f, "等待 fetch"),
+            FetchStatus::Fetching => write!(f, "fetch 中…"),
+            FetchStatus::Fetched(Some(counts)) => write!(f, "{}", counts),
+            FetchStatus::Fetched(None) => write!(f, "origin 上没有当前分支"),
+            FetchStatus::Failed(reason) => write!(f, "fetch 失败: {}", reason),
+        }
+    }
+}
+
+/// 从 origin fetch，完成后比较当前分支与 origin 的差异
+pub async fn fetch_and_compare(project_path: &Path) -> FetchStatus {
+    let command = non_interactive_git(project_path)
+        .args(["fetch", "--quiet", "origin"])
+        .output();
+
+    match tokio::time::timeout(FETCH_TIMEOUT, command).await {
+        Ok(Ok(output)) if output.status.success() => {}
+        Ok(Ok(output)) => return FetchStatus::Failed(last_error_line(&String::from_utf8_lossy(&output.stderr))),
+        Ok(Err(e)) => return FetchStatus::Failed(format!("无法运行 git: {}", e)),
+        Err(_) => return FetchStatus::Failed("超时".to_string()),
+    }
+
+    let path = project_path.to_path_buf();
+    match tokio::task::spawn_blocking(move || ahead_behind(&path)).await {
+        Ok(Ok(counts)) => FetchStatus::Fetched(counts),
+        Ok(Err(e)) => FetchStatus::Failed(e.message().to_string()),
+        Err(e) => FetchStatus::Failed(e.to_string()),
+    }
+}
+
+/// 打开仓库，计算当前分支相对上游分支的领先/落后提交数（见 `GitAnalyzer::ahead_behind`）
+pub fn ahead_behind(project_path: &Path) -> Result<Option<AheadBehind>, git2::Error> {
+    GitAnalyzer::ahead_behind(&Repository::open(project_path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use tempfile::tempdir;
+
+    fn commit(repo: &Repository, message: &str) -> git2::Oid {
+        let signature = Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &signature, &signature, message, &tree, &parents).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reports_ahead_behind_origin() {
+        let temp_dir = tempdir().unwrap();
+        let upstream_path = temp_dir.path().join("upstream");
+        let upstream = Repository::init(&upstream_path).unwrap();
+        commit(&upstream, "initial");
+
+        let local_path = temp_dir.path().join("local");
+        let local = Repository::clone(&upstream_path.to_string_lossy(), &local_path).unwrap();
+        assert_eq!(ahead_behind(&local_path).unwrap(), Some(AheadBehind::default()));
+
+        // origin 上有新提交，本地也有未推送的提交
+        commit(&upstream, "upstream change");
+        commit(&local, "local change");
+        assert_eq!(ahead_behind(&local_path).unwrap(), Some(AheadBehind { ahead: 1, behind: 0 }));
+
+        let status = fetch_and_compare(&local_path).await;
+        assert_eq!(status, FetchStatus::Fetched(Some(AheadBehind { ahead: 1, behind: 1 })));
+        assert_eq!(status.to_string(), "↑1 ↓1");
+
+        // 没有 origin 的仓库 fetch 失败
+        Repository::init(temp_dir.path().join("orphan")).unwrap();
+        assert!(matches!(fetch_and_compare(&temp_dir.path().join("orphan")).await, FetchStatus::Failed(_)));
+    }
+}
@@ -1,3 +1,16 @@
+pub mod archive;
 pub mod cleanup;
+pub mod cleanup_strategy;
+pub mod deleter;
+pub mod doctor;
+pub mod git_fetch;
 pub mod project_manager;
-
+pub mod project_report;
+pub mod remote_check;
+pub mod report;
+pub mod scan_output;
+pub mod snapshot_diff;
+pub mod suggest;
+pub mod telemetry;
+pub mod unpushed;
+pub mod wizard;
@@ -0,0 +1,174 @@
+// 单个项目的完整报告（Markdown），用于粘贴到仓库清理相关的工单中
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::models::{Project, SuggestionStatus};
+
+/// 生成项目的 Markdown 报告：大小、Git 状态、依赖目录和清理建议
+pub fn to_markdown(project: &Project) -> String {
+    let mut lines = vec![
+        format!("# {}", project.name),
+        String::new(),
+        format!("- 路径: `{}`", project.path.display()),
+        format!("- 类型: {}", project.type_display_name()),
+        format!("- 最后修改: {}", format_time(project.last_modified)),
+    ];
+    if let Some(description) = &project.description {
+        lines.push(format!("- 描述: {}", description));
+    }
+
+    lines.push(String::new());
+    lines.push("## 大小".to_string());
+    lines.push(String::new());
+    lines.push("| 项 | 大小 | 文件数 |".to_string());
+    lines.push("| --- | ---: | ---: |".to_string());
+    lines.push(format!("| 代码 | {} | {} |", project.size(), project.file_count()));
+    lines.push(format!("| 依赖 | {} | {} |", project.dependency_size(), project.dependency_files()));
+    if !project.gitignore_excluded_size.is_zero() {
+        lines.push(format!(
+            "| gitignore 排除 | {} | {} |",
+            project.gitignore_excluded_size, project.gitignore_excluded_file_count
+        ));
+    }
+    lines.push(format!("| 磁盘占用 | {} | {} |", project.disk_usage(), project.total_files()));
+    if project.size_is_estimate {
+        lines.push(String::new());
+        lines.push("> 代码大小为估算值，精确计算尚未完成".to_string());
+    } else if project.size_truncated {
+        lines.push(String::new());
+        lines.push("> 已达到统计上限，实际大小更大".to_string());
+    }
+
+    lines.push(String::new());
+    lines.push("## Git".to_string());
+    lines.push(String::new());
+    match &project.git_info {
+        Some(info) => {
+            lines.push(format!("- 当前分支: {}", info.current_branch.as_deref().unwrap_or("unknown")));
+            if let Some(remote_url) = &info.remote_url {
+                lines.push(format!("- 远程仓库: {}", remote_url));
+            }
+            if let Some(time) = info.last_commit_time {
+                let message = info.last_commit_message.as_deref().map(first_line).unwrap_or_default();
+                lines.push(format!("- 最后提交: {} {}", format_time(time), message).trim_end().to_string());
+            }
+            let warnings = project.unsaved_work_warnings();
+            if info.is_bare {
+                lines.push("- 工作区状态: 裸仓库（无工作区）".to_string());
+            } else if warnings.is_empty() {
+                lines.push("- 工作区状态: 清洁".to_string());
+            } else {
+                lines.push(format!("- 工作区状态: {}", warnings.join("，")));
+            }
+        }
+        None => lines.push("不是 Git 仓库".to_string()),
+    }
+
+    if !project.dependencies.is_empty() {
+        lines.push(String::new());
+        lines.push("## 依赖目录".to_string());
+        lines.push(String::new());
+        lines.push("| 类型 | 路径 | 大小 | 包数量 |".to_string());
+        lines.push("| --- | --- | ---: | ---: |".to_string());
+        for dependency in &project.dependencies {
+            lines.push(format!(
+                "| {} | `{}` | {} | {} |",
+                dependency.dependency_type.display_name(),
+                relative_path(project, &dependency.path),
+                dependency.size,
+                dependency.package_count.map(|count| count.to_string()).unwrap_or_else(|| "-".to_string())
+            ));
+        }
+    }
+
+    if let Some(suggestions) = project.cleanup_suggestions.as_ref().filter(|suggestions| !suggestions.is_empty()) {
+        lines.push(String::new());
+        lines.push("## 清理建议".to_string());
+        lines.push(String::new());
+        lines.push("| 目录 | 大小 | 文件数 | 占比 | 状态 |".to_string());
+        lines.push("| --- | ---: | ---: | ---: | --- |".to_string());
+        for suggestion in suggestions {
+            let status = match suggestion.status {
+                SuggestionStatus::Pending => "待处理",
+                SuggestionStatus::Accepted => "已清理",
+                SuggestionStatus::Rejected => "已拒绝",
+            };
+            lines.push(format!(
+                "| `{}` | {} | {} | {:.1}% | {} |",
+                relative_path(project, &suggestion.path),
+                suggestion.size,
+                suggestion.file_count,
+                suggestion.share * 100.0,
+                status
+            ));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// 报告的默认文件名（项目名中的路径分隔符替换为 "-"）
+pub fn file_name(project: &Project) -> String {
+    format!("{}-report.md", project.name.replace(['/', '\\'], "-"))
+}
+
+/// 相对于项目根目录的路径，不在项目中时显示完整路径
+fn relative_path(project: &Project, path: &std::path::Path) -> String {
+    path.strip_prefix(&project.path).unwrap_or(path).display().to_string()
+}
+
+fn first_line(message: &str) -> &str {
+    message.lines().next().unwrap_or_default()
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ByteSize, CleanupSuggestion, GitInfo};
+    use crate::tui::fixtures::ProjectFixture;
+
+    #[test]
+    fn test_project_report_markdown() {
+        let mut project = ProjectFixture::new("/code/web")
+            .dependency("node_modules", 2048)
+            .build();
+        project.git_info = Some(GitInfo {
+            remote_url: Some("git@example.com:me/web.git".to_string()),
+            current_branch: Some("main".to_string()),
+            last_commit_time: None,
+            last_commit_message: None,
+            last_commit_author: None,
+            has_uncommitted_changes: true,
+            has_unpushed_commits: false,
+            stash_count: 1,
+            untracked_count: 0,
+            changed_file_count: 3,
+            staged_file_count: 0,
+            modified_file_count: 3,
+            head_detached: false,
+            unpushed_commit_count: 0,
+            is_bare: false,
+            worktree_of: None,
+            ahead_behind: None,
+        });
+        project.cleanup_suggestions = Some(vec![CleanupSuggestion {
+            path: project.path.join("coverage"),
+            size: ByteSize::new(512),
+            file_count: 4,
+            share: 0.25,
+            status: SuggestionStatus::Pending,
+        }]);
+
+        let report = to_markdown(&project);
+        assert!(report.starts_with("# web\n"));
+        assert!(report.contains("| 依赖 | 2.0 KB |"));
+        assert!(report.contains("- 工作区状态: 有 3 个文件的更改未提交，有 1 个 stash"));
+        assert!(report.contains("| node_modules | `node_modules` | 2.0 KB | - |"));
+        assert!(report.contains("| `coverage` | 512 B | 4 | 25.0% | 待处理 |"));
+        assert_eq!(file_name(&project), "web-report.md");
+    }
+}
@@ -0,0 +1,286 @@
+// 检查仓库的远程仓库是否仍然存在：按主机分组先测试连通性，再用 git ls-remote 确认仓库，全程不提示输入凭据
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use futures::StreamExt;
+
+use crate::models::Project;
+
+/// 连接远程主机的超时时间
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 单个 git ls-remote 的超时时间
+const LS_REMOTE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 同时运行的 git ls-remote 数量
+const CONCURRENT_CHECKS: usize = 8;
+
+/// 远程仓库的检查结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteStatus {
+    /// 远程仓库可以访问
+    Available,
+
+    /// 远程主机无法连接
+    HostUnreachable,
+
+    /// 远程仓库已不存在
+    Missing,
+
+    /// 无法确认（如需要凭据），附带原因
+    Unverified(String),
+}
+
+impl RemoteStatus {
+    /// 远程副本是否已丢失，删除本地仓库将失去最后一份
+    pub fn is_lost(&self) -> bool {
+        matches!(self, RemoteStatus::HostUnreachable | RemoteStatus::Missing)
+    }
+}
+
+impl fmt::Display for RemoteStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteStatus::Available => write!(f, "可以访问"),
+            RemoteStatus::HostUnreachable => write!(f, "远程主机无法连接"),
+            RemoteStatus::Missing => write!(f, "远程仓库已不存在"),
+            RemoteStatus::Unverified(reason) => write!(f, "无法确认（{}）", reason),
+        }
+    }
+}
+
+/// 单个仓库的远程检查结果
+#[derive(Debug, Clone)]
+pub struct RemoteCheck<'a> {
+    /// 所属项目
+    pub project: &'a Project,
+
+    /// 检查的远程仓库 URL
+    pub url: String,
+
+    /// 检查结果
+    pub status: RemoteStatus,
+}
+
+impl fmt::Display for RemoteCheck<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}) {}: {}", self.project.name, self.project.path.display(), self.url, self.status)
+    }
+}
+
+/// 远程仓库所在的网络主机
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RemoteHost {
+    pub host: String,
+    pub port: u16,
+}
+
+/// 从远程 URL 中解析主机和端口，本地路径和 file:// 返回 None
+///
+/// 支持 `scheme://[user@]host[:port]/path` 和 scp 风格的 `[user@]host:path`。
+pub fn remote_host(url: &str) -> Option<RemoteHost> {
+    if let Some((scheme, rest)) = url.split_once("://") {
+        let port = match scheme {
+            "https" => 443,
+            "http" => 80,
+            "git" => 9418,
+            "ssh" | "git+ssh" | "ssh+git" => 22,
+            _ => return None,
+        };
+        let authority = rest.split('/').next().unwrap_or(rest);
+        let authority = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+        let (host, explicit_port) = match authority.strip_prefix('[') {
+            Some(ipv6) => {
+                let (host, rest) = ipv6.split_once(']')?;
+                (host, rest.strip_prefix(':'))
+            }
+            None => match authority.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            },
+        };
+        let port = explicit_port.and_then(|port| port.parse().ok()).unwrap_or(port);
+        return (!host.is_empty()).then(|| RemoteHost { host: host.to_string(), port });
+    }
+
+    // scp 风格：冒号前不含路径分隔符，且不是 Windows 盘符
+    let (authority, _) = url.split_once(':')?;
+    if authority.contains('/') || authority.contains('\\') || authority.len() <= 1 {
+        return None;
+    }
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    Some(RemoteHost { host: host.to_string(), port: 22 })
+}
+
+/// 检查项目的远程仓库（不含被忽略的项目和没有远程仓库的项目），按路径排序
+///
+/// 同一主机只测试一次连通性，主机无法连接时不再运行 git ls-remote。
+pub async fn check_remotes<'a>(projects: impl IntoIterator<Item = &'a Project>) -> Vec<RemoteCheck<'a>> {
+    let mut targets: Vec<(&Project, String)> = projects.into_iter()
+        .filter(|project| !project.is_ignored)
+        .filter_map(|project| {
+            let url = project.git_info.as_ref()?.remote_url.clone()?;
+            Some((project, url))
+        })
+        .collect();
+    targets.sort_by(|a, b| a.0.path.cmp(&b.0.path));
+
+    let hosts: HashSet<RemoteHost> = targets.iter().filter_map(|(_, url)| remote_host(url)).collect();
+    let reachable: HashMap<RemoteHost, bool> = futures::future::join_all(hosts.into_iter().map(|host| async move {
+        let reachable = is_host_reachable(&host).await;
+        (host, reachable)
+    })).await.into_iter().collect();
+
+    futures::stream::iter(targets)
+        .map(|(project, url)| {
+            let reachable = remote_host(&url).is_none_or(|host| reachable.get(&host).copied().unwrap_or(false));
+            async move {
+                let status = if reachable {
+                    ls_remote(&project.path, &url).await
+                } else {
+                    RemoteStatus::HostUnreachable
+                };
+                RemoteCheck { project, url, status }
+            }
+        })
+        .buffered(CONCURRENT_CHECKS)
+        .collect()
+        .await
+}
+
+/// 尝试与主机建立 TCP 连接
+async fn is_host_reachable(host: &RemoteHost) -> bool {
+    let connect = tokio::net::TcpStream::connect((host.host.as_str(), host.port));
+    matches!(tokio::time::timeout(CONNECT_TIMEOUT, connect).await, Ok(Ok(_)))
+}
+
+/// 在项目目录中运行的 git 命令（使用项目的 Git 配置），禁止提示输入凭据，被取消时结束进程
+pub fn non_interactive_git(project_path: &Path) -> tokio::process::Command {
+    let mut command = tokio::process::Command::new("git");
+    command
+        .current_dir(project_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GCM_INTERACTIVE", "never")
+        .env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes -o ConnectTimeout=5")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    command
+}
+
+/// 在项目目录中运行 git ls-remote
+async fn ls_remote(project_path: &Path, url: &str) -> RemoteStatus {
+    let command = non_interactive_git(project_path)
+        .args(["ls-remote", url, "HEAD"])
+        .output();
+
+    match tokio::time::timeout(LS_REMOTE_TIMEOUT, command).await {
+        Ok(Ok(output)) if output.status.success() => RemoteStatus::Available,
+        Ok(Ok(output)) => classify_ls_remote_error(&String::from_utf8_lossy(&output.stderr)),
+        Ok(Err(e)) => RemoteStatus::Unverified(format!("无法运行 git: {}", e)),
+        Err(_) => RemoteStatus::Unverified("超时".to_string()),
+    }
+}
+
+/// 根据 git ls-remote 的错误输出判断原因
+fn classify_ls_remote_error(stderr: &str) -> RemoteStatus {
+    const UNREACHABLE: &[&str] = &[
+        "could not resolve host",
+        "could not resolve hostname",
+        "connection refused",
+        "connection timed out",
+        "operation timed out",
+        "network is unreachable",
+        "no route to host",
+        "failed to connect",
+    ];
+    const MISSING: &[&str] = &[
+        "repository not found",
+        "not found",
+        "does not exist",
+        "does not appear to be a git repository",
+    ];
+
+    let lower = stderr.to_lowercase();
+    if UNREACHABLE.iter().any(|pattern| lower.contains(pattern)) {
+        return RemoteStatus::HostUnreachable;
+    }
+    if MISSING.iter().any(|pattern| lower.contains(pattern)) {
+        return RemoteStatus::Missing;
+    }
+
+    RemoteStatus::Unverified(last_error_line(stderr))
+}
+
+/// git 错误输出的最后一行，去掉 "fatal: " 前缀
+pub fn last_error_line(stderr: &str) -> String {
+    stderr.lines().rev()
+        .find(|line| !line.trim().is_empty())
+        .map(|line| line.trim().trim_start_matches("fatal: ").to_string())
+        .unwrap_or_else(|| "未知错误".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::{GitState, ProjectFixture};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_remote_host() {
+        let host = |url| remote_host(url).map(|host| (host.host, host.port));
+        assert_eq!(host("https://github.com/me/app.git"), Some(("github.com".to_string(), 443)));
+        assert_eq!(host("ssh://git@git.example.com:2222/me/app.git"), Some(("git.example.com".to_string(), 2222)));
+        assert_eq!(host("git@github.com:me/app.git"), Some(("github.com".to_string(), 22)));
+        assert_eq!(host("/srv/git/app.git"), None);
+        assert_eq!(host("file:///srv/git/app.git"), None);
+        assert_eq!(host("C:\\repos\\app.git"), None);
+    }
+
+    #[test]
+    fn test_classify_ls_remote_error() {
+        assert_eq!(
+            classify_ls_remote_error("ERROR: Repository not found.\nfatal: Could not read from remote repository.\n"),
+            RemoteStatus::Missing
+        );
+        assert_eq!(
+            classify_ls_remote_error("fatal: unable to access 'https://git.old-corp.com/app.git/': Could not resolve host: git.old-corp.com\n"),
+            RemoteStatus::HostUnreachable
+        );
+        assert_eq!(
+            classify_ls_remote_error("fatal: could not read Username for 'https://github.com': terminal prompts disabled\n"),
+            RemoteStatus::Unverified("could not read Username for 'https://github.com': terminal prompts disabled".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_local_remotes() {
+        let temp_dir = tempdir().unwrap();
+        let upstream = temp_dir.path().join("upstream.git");
+        git2::Repository::init_bare(&upstream).unwrap();
+
+        let with_remote = |name: &str, url: &Path| {
+            let mut project = ProjectFixture::new(&temp_dir.path().join(name).to_string_lossy())
+                .git(GitState::Clean)
+                .build();
+            std::fs::create_dir_all(&project.path).unwrap();
+            if let Some(git) = project.git_info.as_mut() {
+                git.remote_url = Some(url.to_string_lossy().to_string());
+            }
+            project
+        };
+        let projects = [
+            with_remote("app", &upstream),
+            with_remote("legacy", &temp_dir.path().join("deleted.git")),
+        ];
+
+        let checks = check_remotes(&projects).await;
+        let statuses: Vec<(&str, &RemoteStatus)> = checks.iter()
+            .map(|check| (check.project.name.as_str(), &check.status))
+            .collect();
+        assert_eq!(statuses, [("app", &RemoteStatus::Available), ("legacy", &RemoteStatus::Missing)]);
+    }
+}
@@ -0,0 +1,250 @@
+// 大小报告与 webhook 通知
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::models::Project;
+use crate::utils::size_format;
+
+/// webhook 请求的超时时间（连接、发送和等待响应合计）
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 报告中单个项目的大小
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectSize {
+    /// 项目名称
+    pub name: String,
+
+    /// 项目路径
+    pub path: PathBuf,
+
+    /// 磁盘占用
+    pub size: u64,
+}
+
+/// 一次运行的大小报告，保存后作为下次运行计算变化量的基准
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizeReport {
+    /// 生成时间
+    pub generated_at: DateTime<Utc>,
+
+    /// 各项目的大小
+    pub projects: Vec<ProjectSize>,
+}
+
+/// 项目大小的变化
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SizeDelta {
+    /// 项目名称
+    pub name: String,
+
+    /// 项目路径
+    pub path: PathBuf,
+
+    /// 当前大小
+    pub size: u64,
+
+    /// 与上次报告相比的变化量（新项目为其全部大小）
+    pub delta: i64,
+}
+
+/// 发送给 webhook 的报告摘要
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportSummary {
+    /// 生成时间
+    pub generated_at: DateTime<Utc>,
+
+    /// 项目数量
+    pub project_count: usize,
+
+    /// 总大小
+    pub total_size: u64,
+
+    /// 与上次报告相比的总变化量（没有上次报告时为 None）
+    pub total_delta: Option<i64>,
+
+    /// 上次报告的生成时间
+    pub previous_generated_at: Option<DateTime<Utc>>,
+
+    /// 增长最多的项目
+    pub top_growers: Vec<SizeDelta>,
+}
+
+impl SizeReport {
+    /// 由项目列表生成报告（被忽略的项目和工作区成员不计入）
+    pub fn from_projects(projects: &[Project]) -> Self {
+        Self {
+            generated_at: Utc::now(),
+            projects: projects.iter()
+                .filter(|project| !project.is_ignored && !project.is_workspace_member())
+                .map(|project| ProjectSize {
+                    name: project.name.clone(),
+                    path: project.path.clone(),
+                    size: project.disk_usage().bytes(),
+                })
+                .collect(),
+        }
+    }
+
+    /// 默认报告文件路径
+    pub fn default_path() -> Result<PathBuf> {
+        let mut path = dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("无法找到配置目录"))?;
+        path.push("project-manager-cli");
+        path.push("last_report.json");
+        Ok(path)
+    }
+
+    /// 从文件加载报告
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 保存报告到文件
+    pub fn save_to_file(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// 总大小
+    pub fn total_size(&self) -> u64 {
+        self.projects.iter().map(|project| project.size).sum()
+    }
+
+    /// 与上次报告比较，生成摘要；只列出增长的项目，最多 `limit` 个
+    pub fn summarize(&self, previous: Option<&SizeReport>, limit: usize) -> ReportSummary {
+        let previous_sizes: HashMap<&Path, u64> = previous
+            .map(|report| report.projects.iter()
+                .map(|project| (project.path.as_path(), project.size))
+                .collect())
+            .unwrap_or_default();
+
+        let mut top_growers: Vec<SizeDelta> = self.projects.iter()
+            .map(|project| SizeDelta {
+                name: project.name.clone(),
+                path: project.path.clone(),
+                size: project.size,
+                delta: project.size as i64 - previous_sizes.get(project.path.as_path()).copied().unwrap_or(0) as i64,
+            })
+            .filter(|delta| delta.delta > 0)
+            .collect();
+        top_growers.sort_by_key(|delta| std::cmp::Reverse(delta.delta));
+        top_growers.truncate(limit);
+
+        ReportSummary {
+            generated_at: self.generated_at,
+            project_count: self.projects.len(),
+            total_size: self.total_size(),
+            total_delta: previous.map(|report| self.total_size() as i64 - report.total_size() as i64),
+            previous_generated_at: previous.map(|report| report.generated_at),
+            top_growers,
+        }
+    }
+}
+
+/// 带符号的大小变化，例如 `+1.5 MB`
+pub fn format_delta(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, size_format::format_size(delta.unsigned_abs()))
+}
+
+impl fmt::Display for ReportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "项目数: {}，总大小: {}",
+            self.project_count,
+            size_format::format_size(self.total_size)
+        )?;
+        match self.total_delta {
+            Some(delta) => writeln!(f, "（较上次 {}）", format_delta(delta))?,
+            None => writeln!(f)?,
+        }
+
+        if !self.top_growers.is_empty() {
+            writeln!(f, "增长最多的项目:")?;
+            for grower in &self.top_growers {
+                writeln!(
+                    f,
+                    "  • {} {}（{}）",
+                    grower.name,
+                    format_delta(grower.delta),
+                    size_format::format_size(grower.size)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 将报告摘要 POST 到 webhook
+///
+/// 请求体同时包含 Slack 使用的 `text` 和 Discord 使用的 `content` 字段，以及结构化的摘要数据；
+/// 超过 `WEBHOOK_TIMEOUT` 没有完成时返回错误。
+pub async fn send_webhook(url: &str, summary: &ReportSummary) -> Result<()> {
+    let text = format!("📊 项目管理器报告\n{}", summary);
+    let mut payload = serde_json::to_value(summary)?;
+    payload["text"] = serde_json::Value::String(text.clone());
+    payload["content"] = serde_json::Value::String(text);
+
+    let url = url.to_string();
+    tokio::task::spawn_blocking(move || {
+        ureq::AgentBuilder::new()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .post(&url)
+            .send_json(payload)
+            .map(|_| ())
+            .map_err(|e| anyhow::anyhow!("发送 webhook 通知失败: {}", e))
+    })
+    .await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::ProjectFixture;
+
+    #[test]
+    fn test_summarize_against_previous_report() {
+        let previous = SizeReport::from_projects(&[
+            ProjectFixture::new("/work/api").sizes(100, 1000).build(),
+            ProjectFixture::new("/work/web").sizes(100, 5000).build(),
+        ]);
+        let current = SizeReport::from_projects(&[
+            ProjectFixture::new("/work/api").sizes(100, 3000).build(),
+            ProjectFixture::new("/work/web").sizes(100, 1000).build(),
+            ProjectFixture::new("/work/new").sizes(500, 0).build(),
+            ProjectFixture::new("/work/old").sizes(9999, 0).ignored().build(),
+        ]);
+
+        let summary = current.summarize(Some(&previous), 5);
+        assert_eq!(summary.project_count, 3);
+        assert_eq!(summary.total_size, 4700);
+        assert_eq!(summary.total_delta, Some(-1500));
+
+        let growers: Vec<_> = summary.top_growers.iter().map(|d| (d.name.as_str(), d.delta)).collect();
+        assert_eq!(growers, [("api", 2000), ("new", 500)]);
+        assert!(summary.to_string().starts_with("项目数: 3，总大小: 4.6 KB（较上次 -1.5 KB）"));
+    }
+
+    #[test]
+    fn test_summarize_without_previous_report() {
+        let current = SizeReport::from_projects(&[ProjectFixture::new("/work/api").sizes(100, 0).build()]);
+
+        let summary = current.summarize(None, 0);
+        assert_eq!(summary.total_delta, None);
+        assert!(summary.top_growers.is_empty());
+    }
+}
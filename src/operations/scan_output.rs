@@ -0,0 +1,448 @@
+// scan 命令的机器可读输出：每个项目一条扁平记录，可输出为 JSON、CSV、表格或 Markdown
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::models::Project;
+use crate::utils::csv::Delimiter;
+use crate::utils::size_format;
+use crate::utils::table::{Align, Table};
+
+/// 单个项目的扫描结果
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ScanRecord {
+    /// 项目名称
+    pub name: String,
+
+    /// 项目路径
+    pub path: PathBuf,
+
+    /// 项目类型
+    pub project_type: String,
+
+    /// 代码大小（不包含依赖）
+    pub code_size: u64,
+
+    /// 依赖大小
+    pub dependency_size: u64,
+
+    /// 磁盘占用
+    pub total_size: u64,
+
+    /// 大小是否因达到统计上限而不完整
+    pub size_truncated: bool,
+
+    /// 最后修改时间
+    pub last_modified: DateTime<Utc>,
+
+    /// 最后提交时间（非 Git 项目或没有提交时为 None）
+    pub last_commit: Option<DateTime<Utc>>,
+
+    /// 当前分支（非 Git 项目为 None）
+    pub git_branch: Option<String>,
+
+    /// 是否有未提交的更改
+    pub git_dirty: bool,
+
+    /// 是否有未推送的提交
+    pub git_unpushed: bool,
+
+    /// 各依赖目录的大小
+    pub dependencies: Vec<DependencyRecord>,
+}
+
+/// 单个依赖目录
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DependencyRecord {
+    /// 依赖类型
+    pub kind: String,
+
+    /// 依赖目录路径
+    pub path: PathBuf,
+
+    /// 依赖大小
+    pub size: u64,
+}
+
+/// TUI 导出文件的格式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl ExportFormat {
+    /// 显示名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Markdown => "Markdown",
+        }
+    }
+
+    /// 文件扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+        }
+    }
+
+    /// 切换到下一种格式
+    pub fn next(&self) -> Self {
+        match self {
+            ExportFormat::Json => ExportFormat::Csv,
+            ExportFormat::Csv => ExportFormat::Markdown,
+            ExportFormat::Markdown => ExportFormat::Json,
+        }
+    }
+
+    /// 根据文件扩展名判断格式，无法识别时返回 None
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(ExportFormat::Json),
+            "csv" => Some(ExportFormat::Csv),
+            "md" | "markdown" => Some(ExportFormat::Markdown),
+            _ => None,
+        }
+    }
+
+    /// 按该格式生成文件内容
+    pub fn render(&self, records: &[ScanRecord]) -> Result<String> {
+        Ok(match self {
+            ExportFormat::Json => serde_json::to_string_pretty(records)? + "\n",
+            ExportFormat::Csv => to_csv(records),
+            ExportFormat::Markdown => to_markdown(records),
+        })
+    }
+}
+
+/// CSV/TSV 输出中可选择的列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Name,
+    Path,
+    ProjectType,
+    CodeSize,
+    DependencySize,
+    TotalSize,
+    SizeTruncated,
+    LastModified,
+    LastCommit,
+    GitBranch,
+    GitDirty,
+    GitUnpushed,
+    Dependencies,
+}
+
+impl Column {
+    /// 未指定 `--columns` 时输出的列
+    pub const DEFAULT: [Column; 12] = [
+        Column::Name,
+        Column::Path,
+        Column::ProjectType,
+        Column::CodeSize,
+        Column::DependencySize,
+        Column::TotalSize,
+        Column::SizeTruncated,
+        Column::LastModified,
+        Column::GitBranch,
+        Column::GitDirty,
+        Column::GitUnpushed,
+        Column::Dependencies,
+    ];
+
+    /// 所有可选择的列
+    pub const ALL: [Column; 13] = [
+        Column::Name,
+        Column::Path,
+        Column::ProjectType,
+        Column::CodeSize,
+        Column::DependencySize,
+        Column::TotalSize,
+        Column::SizeTruncated,
+        Column::LastModified,
+        Column::LastCommit,
+        Column::GitBranch,
+        Column::GitDirty,
+        Column::GitUnpushed,
+        Column::Dependencies,
+    ];
+
+    /// 列名，同时用作表头
+    pub fn name(&self) -> &'static str {
+        match self {
+            Column::Name => "name",
+            Column::Path => "path",
+            Column::ProjectType => "project_type",
+            Column::CodeSize => "code_size",
+            Column::DependencySize => "dependency_size",
+            Column::TotalSize => "total_size",
+            Column::SizeTruncated => "size_truncated",
+            Column::LastModified => "last_modified",
+            Column::LastCommit => "last_commit",
+            Column::GitBranch => "git_branch",
+            Column::GitDirty => "git_dirty",
+            Column::GitUnpushed => "git_unpushed",
+            Column::Dependencies => "dependencies",
+        }
+    }
+
+    /// 该列的值（未转义，大小以字节为单位，时间为 RFC 3339）
+    fn value(&self, record: &ScanRecord) -> String {
+        match self {
+            Column::Name => record.name.clone(),
+            Column::Path => record.path.display().to_string(),
+            Column::ProjectType => record.project_type.clone(),
+            Column::CodeSize => record.code_size.to_string(),
+            Column::DependencySize => record.dependency_size.to_string(),
+            Column::TotalSize => record.total_size.to_string(),
+            Column::SizeTruncated => record.size_truncated.to_string(),
+            Column::LastModified => record.last_modified.to_rfc3339(),
+            Column::LastCommit => record.last_commit.map(|time| time.to_rfc3339()).unwrap_or_default(),
+            Column::GitBranch => record.git_branch.clone().unwrap_or_default(),
+            Column::GitDirty => record.git_dirty.to_string(),
+            Column::GitUnpushed => record.git_unpushed.to_string(),
+            Column::Dependencies => record.dependencies_field(),
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    /// 解析列名，同时接受常用简写（如 `dep_size`、`type`、`branch`）
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim().to_ascii_lowercase();
+        let column = match value.as_str() {
+            "type" => Column::ProjectType,
+            "dep_size" | "deps_size" => Column::DependencySize,
+            "size" => Column::TotalSize,
+            "truncated" => Column::SizeTruncated,
+            "branch" => Column::GitBranch,
+            "dirty" => Column::GitDirty,
+            "unpushed" => Column::GitUnpushed,
+            "deps" => Column::Dependencies,
+            name => match Column::ALL.into_iter().find(|column| column.name() == name) {
+                Some(column) => column,
+                None => return Err(format!(
+                    "未知的列: {}（可选: {}）",
+                    value,
+                    Column::ALL.map(|column| column.name()).join(", ")
+                )),
+            },
+        };
+        Ok(column)
+    }
+}
+
+impl From<&Project> for ScanRecord {
+    fn from(project: &Project) -> Self {
+        let git_info = project.git_info.as_ref();
+        Self {
+            name: project.name.clone(),
+            path: project.path.clone(),
+            project_type: project.project_type.as_str().to_string(),
+            code_size: project.code_size.bytes(),
+            dependency_size: project.dependency_size().bytes(),
+            total_size: project.disk_usage().bytes(),
+            size_truncated: project.size_truncated,
+            last_modified: project.last_modified,
+            last_commit: git_info.and_then(|git| git.last_commit_time),
+            git_branch: git_info.and_then(|git| git.current_branch.clone()),
+            git_dirty: git_info.is_some_and(|git| git.has_uncommitted_changes),
+            git_unpushed: git_info.is_some_and(|git| git.has_unpushed_commits),
+            dependencies: project.dependencies.iter()
+                .map(|dependency| DependencyRecord {
+                    kind: dependency.dependency_type.display_name().to_string(),
+                    path: dependency.path.clone(),
+                    size: dependency.size.bytes(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ScanRecord {
+    /// 分支名，有未提交的更改时加 `*`
+    fn git_label(&self) -> String {
+        match &self.git_branch {
+            Some(branch) if self.git_dirty => format!("{}*", branch),
+            Some(branch) => branch.clone(),
+            None => "-".to_string(),
+        }
+    }
+
+    /// 磁盘占用，达到统计上限时加 `≥`
+    fn total_label(&self) -> String {
+        let size = size_format::format_size(self.total_size);
+        if self.size_truncated {
+            format!("≥{}", size)
+        } else {
+            size
+        }
+    }
+
+    /// 依赖明细，如 `node_modules=1024;target=2048`（大小以字节为单位）
+    fn dependencies_field(&self) -> String {
+        self.dependencies.iter()
+            .map(|dependency| format!("{}={}", dependency.kind, dependency.size))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// 依赖明细，如 `node_modules 1.0 KB, target 2.0 KB`
+    fn dependencies_label(&self) -> String {
+        if self.dependencies.is_empty() {
+            return "-".to_string();
+        }
+        self.dependencies.iter()
+            .map(|dependency| format!("{} {}", dependency.kind, size_format::format_size(dependency.size)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// 生成 CSV（大小以字节为单位）
+pub fn to_csv(records: &[ScanRecord]) -> String {
+    to_delimited(records, &Column::DEFAULT, Delimiter::Comma)
+}
+
+/// 按指定的列生成 CSV 或 TSV（大小以字节为单位）
+pub fn to_delimited(records: &[ScanRecord], columns: &[Column], delimiter: Delimiter) -> String {
+    let header: Vec<String> = columns.iter().map(|column| column.name().to_string()).collect();
+    let mut output = delimiter.join(&header);
+    for record in records {
+        let fields: Vec<String> = columns.iter()
+            .map(|column| delimiter.escape(&column.value(record)))
+            .collect();
+        output.push_str(&delimiter.join(&fields));
+    }
+    output
+}
+
+/// 生成终端表格：列宽按显示宽度计算，大小列按小数点和单位对齐
+pub fn to_table(records: &[ScanRecord]) -> String {
+    let mut table = Table::new(&[
+        ("项目", Align::Left),
+        ("类型", Align::Left),
+        ("代码", Align::Number),
+        ("依赖", Align::Number),
+        ("总计", Align::Number),
+        ("Git", Align::Left),
+        ("路径", Align::Left),
+    ]);
+    for record in records {
+        table.push_row(vec![
+            record.name.clone(),
+            record.project_type.clone(),
+            size_format::format_size(record.code_size),
+            size_format::format_size(record.dependency_size),
+            record.total_label(),
+            record.git_label(),
+            record.path.display().to_string(),
+        ]);
+    }
+
+    let total: u64 = records.iter().map(|record| record.total_size).sum();
+    format!("{}共 {} 个项目，总计 {}\n", table.render(), records.len(), size_format::format_size(total))
+}
+
+/// 生成 Markdown 表格
+pub fn to_markdown(records: &[ScanRecord]) -> String {
+    let mut lines = vec![
+        "| 项目 | 类型 | 代码 | 依赖 | 总计 | Git | 依赖明细 | 路径 |".to_string(),
+        "| --- | --- | ---: | ---: | ---: | --- | --- | --- |".to_string(),
+    ];
+    lines.extend(records.iter().map(|record| format!(
+        "| {} | {} | {} | {} | {} | {} | {} | `{}` |",
+        record.name.replace('|', "\\|"),
+        record.project_type,
+        size_format::format_size(record.code_size),
+        size_format::format_size(record.dependency_size),
+        record.total_label(),
+        record.git_label(),
+        record.dependencies_label().replace('|', "\\|"),
+        record.path.display()
+    )));
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ByteSize, DependencyInfo, DependencyType};
+    use crate::tui::fixtures::{GitState, ProjectFixture};
+
+    #[test]
+    fn test_scan_record_csv() {
+        let projects = [
+            ProjectFixture::new("/code/api").sizes(1000, 4000).git(GitState::Dirty).build(),
+            ProjectFixture::new("/code/notes, old").sizes(300, 0).build(),
+        ];
+        let records: Vec<ScanRecord> = projects.iter().map(ScanRecord::from).collect();
+
+        assert_eq!(records[0].dependency_size, 4000);
+        assert!(records[0].git_dirty);
+
+        let csv = to_csv(&records);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("api,/code/api,rust,1000,4000,5000,false,"));
+        assert!(lines[2].starts_with("\"notes, old\",\"/code/notes, old\","));
+    }
+
+    #[test]
+    fn test_selected_columns() {
+        let projects = [ProjectFixture::new("/code/api").sizes(1000, 4000).git(GitState::Dirty).build()];
+        let records: Vec<ScanRecord> = projects.iter().map(ScanRecord::from).collect();
+        let columns: Vec<Column> = "name,path,code_size,dep_size".split(',')
+            .map(|name| name.parse().unwrap())
+            .collect();
+
+        let tsv = to_delimited(&records, &columns, Delimiter::Tab);
+        assert_eq!(tsv, "name\tpath\tcode_size\tdependency_size\napi\t/code/api\t1000\t4000\n");
+        assert!("last_commit".parse::<Column>().is_ok());
+        assert!("bogus".parse::<Column>().unwrap_err().contains("last_commit"));
+    }
+
+    #[test]
+    fn test_export_dependency_breakdown() {
+        let mut project = ProjectFixture::new("/code/web").sizes(1000, 3072).build();
+        project.dependencies = vec![
+            DependencyInfo { dependency_type: DependencyType::NodeModules, path: "/code/web/node_modules".into(), size: ByteSize::new(2048), package_count: None, packages: None },
+            DependencyInfo { dependency_type: DependencyType::Other(".next".to_string()), path: "/code/web/.next".into(), size: ByteSize::new(1024), package_count: None, packages: None },
+        ];
+        let records = vec![ScanRecord::from(&project)];
+
+        assert!(to_csv(&records).lines().nth(1).unwrap().ends_with(",node_modules=2048;.next=1024"));
+        assert!(to_markdown(&records).contains("| node_modules 2.0 KB, .next 1.0 KB |"));
+        let json = ExportFormat::Json.render(&records).unwrap();
+        assert!(json.contains("\"kind\": \"node_modules\""));
+
+        assert_eq!(ExportFormat::from_path(Path::new("out/report.MD")), Some(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::from_path(Path::new("report.txt")), None);
+    }
+
+    #[test]
+    fn test_table_aligns_cjk_names() {
+        let projects = [
+            ProjectFixture::new("/code/博客").sizes(512, 0).build(),
+            ProjectFixture::new("/code/api").sizes(1536, 3 << 30).git(GitState::Dirty).build(),
+        ];
+        let records: Vec<ScanRecord> = projects.iter().map(ScanRecord::from).collect();
+
+        let table = to_table(&records);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        let path_column = |line: &str| unicode_width::UnicodeWidthStr::width(&line[..line.find("/code").unwrap()]);
+        assert_eq!(path_column(lines[1]), path_column(lines[2]));
+        assert!(lines[3].starts_with("共 2 个项目"));
+    }
+}
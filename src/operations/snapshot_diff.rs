@@ -0,0 +1,299 @@
+// 对比两个项目索引快照，生成磁盘占用变化报告
+
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::models::Project;
+use crate::operations::report::format_delta;
+use crate::tui::session::SessionSnapshot;
+use crate::utils::csv::Delimiter;
+use crate::utils::size_format;
+use crate::utils::table::{Align, Table};
+
+/// 项目在两个快照间的变化
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffStatus {
+    /// 只在新快照中存在
+    Added,
+
+    /// 只在旧快照中存在
+    Removed,
+
+    /// 两个快照中都存在且大小不同
+    Changed,
+}
+
+impl DiffStatus {
+    /// 表格中的标记
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            DiffStatus::Added => "+",
+            DiffStatus::Removed => "-",
+            DiffStatus::Changed => "~",
+        }
+    }
+}
+
+/// 单个项目的变化
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DiffEntry {
+    /// 变化类型
+    pub status: DiffStatus,
+
+    /// 项目名称
+    pub name: String,
+
+    /// 项目路径（已移除的项目为旧路径）
+    pub path: PathBuf,
+
+    /// 旧快照中的大小
+    pub before: Option<u64>,
+
+    /// 新快照中的大小
+    pub after: Option<u64>,
+}
+
+impl DiffEntry {
+    /// 大小变化量
+    pub fn delta(&self) -> i64 {
+        self.after.unwrap_or(0) as i64 - self.before.unwrap_or(0) as i64
+    }
+}
+
+/// 两个快照的对比结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotDiff {
+    /// 旧快照保存时间
+    pub old_saved_at: DateTime<Utc>,
+
+    /// 新快照保存时间
+    pub new_saved_at: DateTime<Utc>,
+
+    /// 旧快照总大小
+    pub old_total: u64,
+
+    /// 新快照总大小
+    pub new_total: u64,
+
+    /// 发生变化的项目，按变化量绝对值从大到小排列
+    pub entries: Vec<DiffEntry>,
+}
+
+impl SnapshotDiff {
+    /// 对比两个快照
+    ///
+    /// 项目按相对于扫描根目录的路径匹配，迁移到新机器后根目录不同（如 /Users → /home）也能对应上。
+    pub fn between(old: &SessionSnapshot, new: &SessionSnapshot) -> Self {
+        let old_projects: HashMap<PathBuf, &Project> = old.projects.iter()
+            .map(|project| (match_key(&project.path, &old.scan_paths), project))
+            .collect();
+        let mut matched = std::collections::HashSet::new();
+
+        let mut entries = Vec::new();
+        for project in &new.projects {
+            let key = match_key(&project.path, &new.scan_paths);
+            let after = project.disk_usage().bytes();
+            match old_projects.get(&key) {
+                Some(previous) => {
+                    matched.insert(key);
+                    let before = previous.disk_usage().bytes();
+                    if before != after {
+                        entries.push(DiffEntry {
+                            status: DiffStatus::Changed,
+                            name: project.name.clone(),
+                            path: project.path.clone(),
+                            before: Some(before),
+                            after: Some(after),
+                        });
+                    }
+                }
+                None => entries.push(DiffEntry {
+                    status: DiffStatus::Added,
+                    name: project.name.clone(),
+                    path: project.path.clone(),
+                    before: None,
+                    after: Some(after),
+                }),
+            }
+        }
+        entries.extend(old_projects.iter()
+            .filter(|(key, _)| !matched.contains(*key))
+            .map(|(_, project)| DiffEntry {
+                status: DiffStatus::Removed,
+                name: project.name.clone(),
+                path: project.path.clone(),
+                before: Some(project.disk_usage().bytes()),
+                after: None,
+            }));
+        entries.sort_by(|a, b| b.delta().unsigned_abs().cmp(&a.delta().unsigned_abs()).then_with(|| a.path.cmp(&b.path)));
+
+        Self {
+            old_saved_at: old.saved_at,
+            new_saved_at: new.saved_at,
+            old_total: old.projects.iter().map(|project| project.disk_usage().bytes()).sum(),
+            new_total: new.projects.iter().map(|project| project.disk_usage().bytes()).sum(),
+            entries,
+        }
+    }
+
+    /// 总大小变化量
+    pub fn total_delta(&self) -> i64 {
+        self.new_total as i64 - self.old_total as i64
+    }
+
+    /// 某类变化的项目数量
+    pub fn count(&self, status: DiffStatus) -> usize {
+        self.entries.iter().filter(|entry| entry.status == status).count()
+    }
+
+    /// 摘要行：总大小变化和各类项目数量
+    fn summary(&self) -> String {
+        format!(
+            "总大小: {} → {}（{}），新增 {} 个项目，移除 {} 个项目，{} 个项目大小变化",
+            size_format::format_size(self.old_total),
+            size_format::format_size(self.new_total),
+            format_delta(self.total_delta()),
+            self.count(DiffStatus::Added),
+            self.count(DiffStatus::Removed),
+            self.count(DiffStatus::Changed)
+        )
+    }
+
+    /// 快照时间范围
+    fn period(&self) -> String {
+        format!("{} → {}", format_time(self.old_saved_at), format_time(self.new_saved_at))
+    }
+
+    /// 生成 Markdown 报告
+    pub fn to_markdown(&self) -> String {
+        let mut lines = vec![
+            "## 快照对比".to_string(),
+            String::new(),
+            format!("- 时间: {}", self.period()),
+            format!("- {}", self.summary()),
+        ];
+        if !self.entries.is_empty() {
+            lines.push(String::new());
+            lines.push("| 状态 | 项目 | 路径 | 之前 | 之后 | 变化 |".to_string());
+            lines.push("| --- | --- | --- | ---: | ---: | ---: |".to_string());
+            for entry in &self.entries {
+                lines.push(format!(
+                    "| {} | {} | `{}` | {} | {} | {} |",
+                    entry.status.symbol(),
+                    entry.name.replace('|', "\\|"),
+                    entry.path.display(),
+                    format_optional_size(entry.before),
+                    format_optional_size(entry.after),
+                    format_delta(entry.delta())
+                ));
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// 生成 CSV 或 TSV 报告（大小以字节为单位）
+    pub fn to_delimited(&self, delimiter: Delimiter) -> String {
+        let mut output = delimiter.join(&["status", "name", "path", "before", "after", "delta"].map(String::from));
+        for entry in &self.entries {
+            let status = match entry.status {
+                DiffStatus::Added => "added",
+                DiffStatus::Removed => "removed",
+                DiffStatus::Changed => "changed",
+            };
+            output.push_str(&delimiter.join(&[
+                status.to_string(),
+                delimiter.escape(&entry.name),
+                delimiter.escape(&entry.path.display().to_string()),
+                entry.before.map(|size| size.to_string()).unwrap_or_default(),
+                entry.after.map(|size| size.to_string()).unwrap_or_default(),
+                entry.delta().to_string(),
+            ]));
+        }
+        output
+    }
+}
+
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "快照对比: {}", self.period())?;
+        writeln!(f, "{}", self.summary())?;
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(f)?;
+        let mut table = Table::new(&[
+            ("状态", Align::Left),
+            ("之前", Align::Number),
+            ("之后", Align::Number),
+            ("变化", Align::Number),
+            ("项目", Align::Left),
+        ]);
+        for entry in &self.entries {
+            table.push_row(vec![
+                entry.status.symbol().to_string(),
+                format_optional_size(entry.before),
+                format_optional_size(entry.after),
+                format_delta(entry.delta()),
+                entry.path.display().to_string(),
+            ]);
+        }
+        write!(f, "{}", table.render())?;
+        Ok(())
+    }
+}
+
+/// 匹配项目使用的键：相对于所在扫描根目录的路径，不在任何根目录下时使用完整路径
+fn match_key(path: &Path, scan_paths: &[String]) -> PathBuf {
+    scan_paths.iter()
+        .filter_map(|root| path.strip_prefix(root).ok())
+        .min_by_key(|relative| relative.components().count())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+fn format_optional_size(size: Option<u64>) -> String {
+    size.map(size_format::format_size).unwrap_or_else(|| "-".to_string())
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::ProjectFixture;
+
+    fn snapshot(root: &str, projects: &[(&str, u64)]) -> SessionSnapshot {
+        let projects: Vec<Project> = projects.iter()
+            .map(|(name, size)| ProjectFixture::new(&format!("{}/{}", root, name)).sizes(*size, 0).build())
+            .collect();
+        SessionSnapshot::new(&projects).with_scan_paths(vec![root.to_string()])
+    }
+
+    #[test]
+    fn test_diff_matches_projects_across_roots() {
+        let old = snapshot("/Users/me/code", &[("api", 1000), ("web", 5000), ("legacy", 300)]);
+        let new = snapshot("/home/me/code", &[("api", 1000), ("web", 2000), ("blog", 700)]);
+
+        let diff = SnapshotDiff::between(&old, &new);
+        let entries: Vec<(DiffStatus, &str, i64)> = diff.entries.iter()
+            .map(|entry| (entry.status, entry.name.as_str(), entry.delta()))
+            .collect();
+        assert_eq!(entries, [
+            (DiffStatus::Changed, "web", -3000),
+            (DiffStatus::Added, "blog", 700),
+            (DiffStatus::Removed, "legacy", -300),
+        ]);
+        assert_eq!(diff.total_delta(), -2600);
+
+        assert!(diff.to_delimited(Delimiter::Comma).contains("removed,legacy,/Users/me/code/legacy,300,,-300\n"));
+        assert!(diff.to_delimited(Delimiter::Tab).contains("removed\tlegacy\t/Users/me/code/legacy\t300\t\t-300\n"));
+        assert!(diff.to_markdown().contains("| + | blog | `/home/me/code/blog` | - | 700 B | +700 B |"));
+    }
+}
@@ -0,0 +1,266 @@
+// 清理候选排序
+
+use chrono::{DateTime, Utc};
+
+use crate::models::{ByteSize, CloudProvider, Project};
+
+/// 超过该天数未活动的项目视为完全不活跃
+const INACTIVITY_HORIZON_DAYS: f64 = 365.0;
+
+/// 刚活动过的项目仍保留的最低权重
+const MIN_INACTIVITY_WEIGHT: f64 = 0.2;
+
+/// 有未保存工作的 Git 项目的权重
+const UNSAVED_WORK_WEIGHT: f64 = 0.5;
+
+/// 非 Git 项目的权重（无法判断是否有未保存的工作）
+const NON_GIT_WEIGHT: f64 = 0.8;
+
+/// 清理候选项目
+#[derive(Debug, Clone)]
+pub struct CleanupCandidate<'a> {
+    /// 候选项目
+    pub project: &'a Project,
+
+    /// 综合评分，越高越值得优先清理
+    pub score: f64,
+
+    /// 可释放的依赖大小
+    pub reclaimable: ByteSize,
+
+    /// 距最后活动的天数
+    pub inactive_days: i64,
+}
+
+impl CleanupCandidate<'_> {
+    /// 评分依据说明
+    pub fn reasons(&self) -> Vec<String> {
+        let mut reasons = vec![
+            format!("依赖 {}", self.reclaimable),
+            format!("{} 天未活动", self.inactive_days),
+        ];
+        reasons.push(match &self.project.git_info {
+            Some(_) if !self.project.unsaved_work_warnings().is_empty() => "有未保存的工作".to_string(),
+            Some(_) => "工作区干净".to_string(),
+            None => "非 Git 项目".to_string(),
+        });
+        reasons
+    }
+}
+
+/// 计算项目的清理评分，没有可清理依赖或已忽略的项目返回 None
+///
+/// 评分 = 依赖大小权重 × 不活跃权重 × Git 干净程度权重。
+/// 依赖大小取对数，避免单个超大项目压过所有长期不用的项目。
+pub fn score_project(project: &Project, now: DateTime<Utc>) -> Option<CleanupCandidate<'_>> {
+    let reclaimable = project.dependency_size();
+    if project.is_ignored || reclaimable.is_zero() {
+        return None;
+    }
+
+    let size_weight = (reclaimable.bytes() as f64 / (1024.0 * 1024.0)).ln_1p();
+
+    let inactive_days = (now - project.last_activity()).num_days().max(0);
+    let inactive_ratio = (inactive_days as f64 / INACTIVITY_HORIZON_DAYS).min(1.0);
+    let inactivity_weight = MIN_INACTIVITY_WEIGHT + (1.0 - MIN_INACTIVITY_WEIGHT) * inactive_ratio;
+
+    let cleanliness_weight = match &project.git_info {
+        Some(_) if !project.unsaved_work_warnings().is_empty() => UNSAVED_WORK_WEIGHT,
+        Some(_) => 1.0,
+        None => NON_GIT_WEIGHT,
+    };
+
+    Some(CleanupCandidate {
+        project,
+        score: size_weight * inactivity_weight * cleanliness_weight * 10.0,
+        reclaimable,
+        inactive_days,
+    })
+}
+
+/// 按评分降序列出最值得优先清理的项目
+pub fn rank_cleanup_candidates<'a, I>(projects: I, now: DateTime<Utc>) -> Vec<CleanupCandidate<'a>>
+where
+    I: IntoIterator<Item = &'a Project>,
+{
+    let mut candidates: Vec<_> = projects.into_iter()
+        .filter_map(|project| score_project(project, now))
+        .collect();
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates
+}
+
+/// 长期未活动的项目
+#[derive(Debug, Clone)]
+pub struct StaleProject<'a> {
+    /// 项目
+    pub project: &'a Project,
+
+    /// 距最后活动（最后提交和目录修改时间中较晚的一个）的天数
+    pub idle_days: i64,
+
+    /// 可释放的依赖大小
+    pub reclaimable: ByteSize,
+}
+
+impl StaleProject<'_> {
+    /// 归档建议：有未保存的工作时先处理，有依赖目录时先清理再归档
+    pub fn advice(&self) -> &'static str {
+        if !self.project.unsaved_work_warnings().is_empty() {
+            "有未保存的工作，先提交或推送"
+        } else if !self.reclaimable.is_zero() {
+            "清理依赖后归档"
+        } else {
+            "可归档或删除"
+        }
+    }
+}
+
+/// 列出超过 `max_idle_days` 天未活动的项目（不含已忽略的项目），按可释放的依赖大小降序排列
+pub fn stale_projects<'a, I>(projects: I, max_idle_days: u64, now: DateTime<Utc>) -> Vec<StaleProject<'a>>
+where
+    I: IntoIterator<Item = &'a Project>,
+{
+    let mut stale: Vec<_> = projects.into_iter()
+        .filter(|project| !project.is_ignored)
+        .map(|project| StaleProject {
+            project,
+            idle_days: (now - project.last_touched()).num_days(),
+            reclaimable: project.dependency_size(),
+        })
+        .filter(|stale| stale.idle_days >= 0 && stale.idle_days as u64 >= max_idle_days)
+        .collect();
+    stale.sort_by(|a, b| b.reclaimable.cmp(&a.reclaimable).then(b.idle_days.cmp(&a.idle_days)));
+    stale
+}
+
+/// 位于云同步目录中且有依赖目录的项目
+#[derive(Debug, Clone)]
+pub struct CloudSyncWarning<'a> {
+    /// 项目
+    pub project: &'a Project,
+
+    /// 所在的同步服务
+    pub provider: CloudProvider,
+
+    /// 会被同步的依赖大小
+    pub synced_dependency_size: ByteSize,
+}
+
+impl CloudSyncWarning<'_> {
+    /// 处理建议：移出同步目录，或让同步服务跳过依赖目录
+    pub fn advice(&self) -> String {
+        format!(
+            "{} 会同步 {} 的依赖目录；建议将项目移出同步目录，或{}",
+            self.provider.display_name(),
+            self.synced_dependency_size,
+            self.provider.exclusion_hint(),
+        )
+    }
+}
+
+/// 列出位于云同步目录中且有依赖目录的项目，按依赖大小降序排列
+pub fn cloud_synced_projects<'a, I>(projects: I) -> Vec<CloudSyncWarning<'a>>
+where
+    I: IntoIterator<Item = &'a Project>,
+{
+    let mut warnings: Vec<_> = projects.into_iter()
+        .filter(|project| !project.is_ignored)
+        .filter_map(|project| {
+            let provider = project.cloud_sync?;
+            let synced_dependency_size = project.dependency_size();
+            (!synced_dependency_size.is_zero()).then_some(CloudSyncWarning { project, provider, synced_dependency_size })
+        })
+        .collect();
+    warnings.sort_by_key(|warning| std::cmp::Reverse(warning.synced_dependency_size));
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::{fixed_time, GitState, ProjectFixture};
+
+    const MB: u64 = 1024 * 1024;
+
+    fn days_later(days: i64) -> DateTime<Utc> {
+        fixed_time() + chrono::Duration::days(days)
+    }
+
+    #[test]
+    fn test_skips_projects_without_dependencies() {
+        let projects = vec![
+            ProjectFixture::new("/work/empty").sizes(10 * MB, 0).build(),
+            ProjectFixture::new("/work/ignored").sizes(0, 500 * MB).ignored().build(),
+            ProjectFixture::new("/work/app").sizes(0, 10 * MB).build(),
+        ];
+
+        let ranked = rank_cleanup_candidates(&projects, days_later(30));
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].project.name, "app");
+    }
+
+    #[test]
+    fn test_ranking_combines_size_activity_and_cleanliness() {
+        let projects = vec![
+            ProjectFixture::new("/work/dirty").sizes(0, 800 * MB).git(GitState::Dirty).build(),
+            ProjectFixture::new("/work/clean").sizes(0, 800 * MB).git(GitState::Clean).build(),
+            ProjectFixture::new("/work/small").sizes(0, MB).git(GitState::Clean).build(),
+        ];
+
+        let ranked = rank_cleanup_candidates(&projects, days_later(200));
+        let names: Vec<_> = ranked.iter().map(|c| c.project.name.as_str()).collect();
+        assert_eq!(names, ["clean", "dirty", "small"]);
+        assert_eq!(ranked[0].inactive_days, 200);
+        assert_eq!(ranked[0].reasons(), ["依赖 800.0 MB", "200 天未活动", "工作区干净"]);
+    }
+
+    #[test]
+    fn test_recent_activity_lowers_score() {
+        let project = ProjectFixture::new("/work/app").sizes(0, 100 * MB).git(GitState::Clean).build();
+
+        let fresh = score_project(&project, days_later(0)).unwrap();
+        let stale = score_project(&project, days_later(400)).unwrap();
+        let older = score_project(&project, days_later(800)).unwrap();
+
+        assert!(stale.score > fresh.score);
+        assert_eq!(stale.score, older.score);
+    }
+
+    #[test]
+    fn test_stale_projects_ranked_by_reclaimable_size() {
+        let mut recently_edited = ProjectFixture::new("/work/edited").sizes(0, 900 * MB).git(GitState::Clean).build();
+        recently_edited.last_modified = days_later(150);
+        let projects = vec![
+            ProjectFixture::new("/work/small").sizes(0, MB).git(GitState::Clean).build(),
+            ProjectFixture::new("/work/big").sizes(0, 500 * MB).git(GitState::Dirty).build(),
+            ProjectFixture::new("/work/no-deps").sizes(10 * MB, 0).build(),
+            ProjectFixture::new("/work/ignored").sizes(0, 800 * MB).ignored().build(),
+            recently_edited,
+        ];
+
+        let stale = stale_projects(&projects, 180, days_later(200));
+        let names: Vec<_> = stale.iter().map(|s| s.project.name.as_str()).collect();
+        assert_eq!(names, ["big", "small", "no-deps"]);
+        assert_eq!(stale[0].idle_days, 200);
+        assert_eq!(stale[0].advice(), "有未保存的工作，先提交或推送");
+        assert_eq!(stale[1].advice(), "清理依赖后归档");
+        assert_eq!(stale[2].advice(), "可归档或删除");
+        assert!(stale_projects(&projects, 365, days_later(200)).is_empty());
+    }
+
+    #[test]
+    fn test_cloud_synced_projects_with_dependencies() {
+        let projects = vec![
+            ProjectFixture::new("/Dropbox/small").sizes(0, MB).cloud_synced(CloudProvider::Dropbox).build(),
+            ProjectFixture::new("/OneDrive/big").sizes(0, 300 * MB).cloud_synced(CloudProvider::OneDrive).build(),
+            ProjectFixture::new("/Dropbox/no-deps").sizes(10 * MB, 0).cloud_synced(CloudProvider::Dropbox).build(),
+            ProjectFixture::new("/work/local").sizes(0, 500 * MB).build(),
+        ];
+
+        let warnings = cloud_synced_projects(&projects);
+        let names: Vec<_> = warnings.iter().map(|w| w.project.name.as_str()).collect();
+        assert_eq!(names, ["big", "small"]);
+        assert!(warnings[0].advice().starts_with("OneDrive 会同步 300.0 MB 的依赖目录"));
+    }
+}
@@ -0,0 +1,368 @@
+// 本地使用统计：需要在配置中开启（[telemetry] enabled = true），只记录扫描、清理、归档和删除的次数、
+// 释放的空间和项目类型，不记录路径和项目名；数据只保存在本地文件中，不会发送到任何地方，
+// 用于生成"年度清理回顾"，需要时可以导出为 JSON
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::models::{ByteSize, Project};
+use crate::operations::wizard::WizardAction;
+
+/// 年度回顾中列出的最常清理的项目类型数
+const TOP_PROJECT_TYPES: usize = 3;
+
+/// 使用统计记录的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageKind {
+    /// 扫描项目
+    Scan,
+
+    /// 清理依赖目录
+    Clean,
+
+    /// 归档项目
+    Archive,
+
+    /// 删除项目到回收站
+    Delete,
+}
+
+impl UsageKind {
+    /// 手动完成一次该操作估计需要的秒数（找到目录、确认、删除），用于估算节省的时间；
+    /// 清理按依赖目录计算，扫描不计入
+    fn manual_seconds(&self) -> u64 {
+        match self {
+            UsageKind::Scan => 0,
+            UsageKind::Clean => 60,
+            UsageKind::Archive => 300,
+            UsageKind::Delete => 30,
+        }
+    }
+}
+
+/// 一条使用记录，不包含路径和项目名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageEvent {
+    /// 记录时间
+    pub time: DateTime<Utc>,
+
+    /// 操作
+    pub kind: UsageKind,
+
+    /// 涉及的项目数（扫描时为找到的项目数）
+    #[serde(default)]
+    pub projects: usize,
+
+    /// 删除的依赖目录数
+    #[serde(default)]
+    pub directories: usize,
+
+    /// 释放的空间（字节）
+    #[serde(default)]
+    pub freed_bytes: u64,
+
+    /// 项目类型（如 "nodejs"），扫描时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_type: Option<String>,
+}
+
+impl UsageEvent {
+    /// 创建当前时间的记录
+    pub fn new(kind: UsageKind) -> Self {
+        Self {
+            time: Utc::now(),
+            kind,
+            projects: 1,
+            directories: 0,
+            freed_bytes: 0,
+            project_type: None,
+        }
+    }
+
+    /// 设置涉及的项目数
+    pub fn with_projects(mut self, projects: usize) -> Self {
+        self.projects = projects;
+        self
+    }
+
+    /// 设置删除的依赖目录数
+    pub fn with_directories(mut self, directories: usize) -> Self {
+        self.directories = directories;
+        self
+    }
+
+    /// 设置释放的空间
+    pub fn with_freed(mut self, freed: ByteSize) -> Self {
+        self.freed_bytes = freed.bytes();
+        self
+    }
+
+    /// 设置项目类型
+    pub fn with_project_type(mut self, project_type: impl Into<String>) -> Self {
+        self.project_type = Some(project_type.into());
+        self
+    }
+
+    /// 清理向导中对项目执行的操作的记录，跳过时为 None
+    pub fn for_wizard_action(action: WizardAction, project: &Project, freed: ByteSize) -> Option<Self> {
+        let event = match action {
+            WizardAction::Clean => Self::new(UsageKind::Clean).with_directories(project.dependencies.len()),
+            WizardAction::Archive => Self::new(UsageKind::Archive),
+            WizardAction::Delete => Self::new(UsageKind::Delete),
+            WizardAction::Skip => return None,
+        };
+        Some(event.with_freed(freed).with_project_type(project.project_type.as_str()))
+    }
+
+    /// 手动完成该操作估计需要的秒数
+    fn manual_seconds(&self) -> u64 {
+        let units = match self.kind {
+            UsageKind::Clean => self.directories.max(1),
+            _ => self.projects,
+        };
+        self.kind.manual_seconds() * units as u64
+    }
+}
+
+/// 使用统计文件（每行一条 JSON 记录）；未开启时不读写任何文件
+#[derive(Debug, Clone, Default)]
+pub struct UsageLog {
+    path: Option<PathBuf>,
+}
+
+impl UsageLog {
+    /// 按配置创建：`enabled` 为 false 时记录操作不做任何事
+    pub fn new(enabled: bool) -> Self {
+        match Self::default_path() {
+            Ok(path) if enabled => Self::at(path),
+            _ => Self::default(),
+        }
+    }
+
+    /// 使用指定文件记录
+    pub fn at(path: PathBuf) -> Self {
+        Self { path: Some(path) }
+    }
+
+    /// 默认的统计文件路径
+    pub fn default_path() -> Result<PathBuf> {
+        let mut path = dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("无法找到配置目录"))?;
+        path.push("project-manager-cli");
+        path.push("usage.jsonl");
+        Ok(path)
+    }
+
+    /// 追加一条记录；写入失败只记录警告，不影响操作本身
+    pub fn record(&self, event: UsageEvent) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(e) = Self::append(path, &event) {
+            tracing::warn!("写入使用统计失败: {}", e);
+        }
+    }
+
+    fn append(path: &Path, event: &UsageEvent) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(event)?)?;
+        Ok(())
+    }
+
+    /// 读取全部记录，跳过无法解析的行；文件不存在时返回空列表
+    pub fn load(path: &Path) -> Result<Vec<UsageEvent>> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(content.lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+/// 年度清理回顾
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct YearSummary {
+    /// 年份
+    pub year: i32,
+
+    /// 扫描次数
+    pub scans: usize,
+
+    /// 清理次数
+    pub cleans: usize,
+
+    /// 清理的依赖目录数
+    pub cleaned_directories: usize,
+
+    /// 归档的项目数
+    pub archived: usize,
+
+    /// 删除的项目数
+    pub deleted: usize,
+
+    /// 释放的空间合计
+    pub freed: ByteSize,
+
+    /// 单次操作释放的最大空间
+    pub largest_freed: ByteSize,
+
+    /// 释放空间最多的月份及释放的空间
+    pub busiest_month: Option<(u32, ByteSize)>,
+
+    /// 最常清理的项目类型及次数，按次数从多到少
+    pub top_project_types: Vec<(String, usize)>,
+
+    /// 估计节省的手动清理时间（秒）
+    pub saved_seconds: u64,
+}
+
+impl YearSummary {
+    /// 汇总指定年份（UTC）的记录
+    pub fn from_events(events: &[UsageEvent], year: i32) -> Self {
+        let mut summary = Self { year, ..Self::default() };
+        let mut months: HashMap<u32, ByteSize> = HashMap::new();
+        let mut types: HashMap<&str, usize> = HashMap::new();
+
+        for event in events.iter().filter(|event| event.time.year() == year) {
+            let freed = ByteSize::new(event.freed_bytes);
+            match event.kind {
+                UsageKind::Scan => summary.scans += 1,
+                UsageKind::Clean => {
+                    summary.cleans += 1;
+                    summary.cleaned_directories += event.directories;
+                }
+                UsageKind::Archive => summary.archived += event.projects,
+                UsageKind::Delete => summary.deleted += event.projects,
+            }
+            if event.kind != UsageKind::Scan {
+                if let Some(project_type) = &event.project_type {
+                    *types.entry(project_type).or_default() += 1;
+                }
+            }
+            summary.freed += freed;
+            summary.largest_freed = summary.largest_freed.max(freed);
+            *months.entry(event.time.month()).or_default() += freed;
+            summary.saved_seconds += event.manual_seconds();
+        }
+
+        summary.busiest_month = months.into_iter()
+            .filter(|(_, freed)| !freed.is_zero())
+            .max_by_key(|&(month, freed)| (freed, std::cmp::Reverse(month)));
+        let mut types: Vec<(String, usize)> = types.into_iter()
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+        types.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        types.truncate(TOP_PROJECT_TYPES);
+        summary.top_project_types = types;
+        summary
+    }
+
+    /// 这一年是否没有任何记录
+    pub fn is_empty(&self) -> bool {
+        self.scans + self.cleans + self.archived + self.deleted == 0
+    }
+}
+
+impl fmt::Display for YearSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "📭 {} 年还没有使用记录", self.year);
+        }
+
+        writeln!(f, "🧹 {} 年清理回顾", self.year)?;
+        writeln!(
+            f,
+            "  扫描 {} 次，清理 {} 次（依赖目录 {} 个），归档 {} 个项目，删除 {} 个项目",
+            self.scans, self.cleans, self.cleaned_directories, self.archived, self.deleted
+        )?;
+        if !self.freed.is_zero() {
+            writeln!(f, "  共释放 {}，单次最多释放 {}", self.freed, self.largest_freed)?;
+        }
+        if !self.top_project_types.is_empty() {
+            let types: Vec<String> = self.top_project_types.iter()
+                .map(|(name, count)| format!("{} {} 次", name, count))
+                .collect();
+            writeln!(f, "  最常清理: {}", types.join("，"))?;
+        }
+        if let Some((month, freed)) = self.busiest_month {
+            writeln!(f, "  释放最多的月份: {} 月（{}）", month, freed)?;
+        }
+        if self.saved_seconds > 0 {
+            writeln!(f, "  估计节省手动清理时间约 {}", format_duration(self.saved_seconds))?;
+        }
+        Ok(())
+    }
+}
+
+/// 将秒数格式化为 "1 小时 12 分钟"，不足一分钟时按一分钟计
+fn format_duration(seconds: u64) -> String {
+    let minutes = seconds.div_ceil(60);
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{} 分钟", minutes),
+        (hours, 0) => format!("{} 小时", hours),
+        (hours, minutes) => format!("{} 小时 {} 分钟", hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn at(month: u32, kind: UsageKind) -> UsageEvent {
+        UsageEvent {
+            time: Utc.with_ymd_and_hms(2026, month, 1, 12, 0, 0).unwrap(),
+            ..UsageEvent::new(kind)
+        }
+    }
+
+    #[test]
+    fn test_year_summary_from_local_log() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("usage.jsonl");
+
+        // 未开启时不写文件
+        UsageLog::default().record(UsageEvent::new(UsageKind::Scan));
+        assert!(UsageLog::load(&path).unwrap().is_empty());
+
+        let log = UsageLog::at(path.clone());
+        log.record(at(1, UsageKind::Scan).with_projects(40));
+        log.record(at(3, UsageKind::Clean).with_directories(2).with_freed(ByteSize::new(3 << 30)).with_project_type("nodejs"));
+        log.record(at(3, UsageKind::Clean).with_directories(1).with_freed(ByteSize::new(1 << 30)).with_project_type("rust"));
+        log.record(at(5, UsageKind::Clean).with_directories(1).with_freed(ByteSize::new(2 << 30)).with_project_type("nodejs"));
+        log.record(at(6, UsageKind::Archive).with_freed(ByteSize::new(1 << 20)).with_project_type("python"));
+        log.record(UsageEvent {
+            time: Utc.with_ymd_and_hms(2025, 12, 31, 12, 0, 0).unwrap(),
+            ..UsageEvent::new(UsageKind::Delete)
+        });
+
+        let events = UsageLog::load(&path).unwrap();
+        assert_eq!(events.len(), 6);
+        assert!(!std::fs::read_to_string(&path).unwrap().contains('/'));
+
+        let summary = YearSummary::from_events(&events, 2026);
+        assert_eq!((summary.scans, summary.cleans, summary.cleaned_directories, summary.archived, summary.deleted), (1, 3, 4, 1, 0));
+        assert_eq!(summary.largest_freed, ByteSize::new(3 << 30));
+        assert_eq!(summary.busiest_month, Some((3, ByteSize::new(4 << 30))));
+        assert_eq!(summary.top_project_types[0], ("nodejs".to_string(), 2));
+        assert_eq!(summary.saved_seconds, 4 * 60 + 300);
+
+        let text = summary.to_string();
+        assert!(text.contains("释放最多的月份: 3 月"));
+        assert!(text.contains("估计节省手动清理时间约 9 分钟"));
+        assert_eq!(YearSummary::from_events(&events, 2024).to_string(), "📭 2024 年还没有使用记录\n");
+    }
+}
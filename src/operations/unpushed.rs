@@ -0,0 +1,66 @@
+// 汇总所有仓库中未提交、未推送的工作，清空机器前确认不会丢失内容
+
+use std::fmt;
+
+use crate::models::Project;
+
+/// 有未保存工作的仓库
+#[derive(Debug, Clone)]
+pub struct UnpushedRepo<'a> {
+    /// 所属项目
+    pub project: &'a Project,
+
+    /// 未保存的工作说明
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for UnpushedRepo<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.project.name, self.project.path.display())?;
+        if let Some(branch) = self.project.git_info.as_ref().and_then(|git| git.current_branch.as_deref()) {
+            write!(f, " [{}]", branch)?;
+        }
+        write!(f, ": {}", self.warnings.join("，"))
+    }
+}
+
+/// 找出有未提交更改、未推送提交、stash 或未跟踪文件的仓库（不含被忽略的项目），按路径排序
+pub fn repos_with_unpushed_work<'a>(projects: impl IntoIterator<Item = &'a Project>) -> Vec<UnpushedRepo<'a>> {
+    let mut repos: Vec<UnpushedRepo> = projects.into_iter()
+        .filter(|project| !project.is_ignored)
+        .filter_map(|project| {
+            let warnings = project.unsaved_work_warnings();
+            (!warnings.is_empty()).then_some(UnpushedRepo { project, warnings })
+        })
+        .collect();
+    repos.sort_by(|a, b| a.project.path.cmp(&b.project.path));
+    repos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::{GitState, ProjectFixture};
+
+    #[test]
+    fn test_repos_with_unpushed_work() {
+        let mut web = ProjectFixture::new("/code/web").git(GitState::Unpushed).build();
+        if let Some(git) = web.git_info.as_mut() {
+            git.unpushed_commit_count = 3;
+        }
+        let projects = vec![
+            web,
+            ProjectFixture::new("/code/api").git(GitState::Dirty).build(),
+            ProjectFixture::new("/code/clean").git(GitState::Clean).build(),
+            ProjectFixture::new("/code/ignored").git(GitState::Dirty).ignored().build(),
+            ProjectFixture::new("/code/notes").build(),
+        ];
+
+        let repos = repos_with_unpushed_work(&projects);
+        let lines: Vec<String> = repos.iter().map(|repo| repo.to_string()).collect();
+        assert_eq!(lines, [
+            "api (/code/api) [main]: 有未提交的更改",
+            "web (/code/web) [main]: 有 3 个未推送的提交",
+        ]);
+    }
+}
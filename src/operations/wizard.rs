@@ -0,0 +1,164 @@
+// 清理向导
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::models::ByteSize;
+
+/// 向导中对单个项目的处理方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WizardAction {
+    /// 清理依赖目录
+    Clean,
+
+    /// 跳过
+    Skip,
+
+    /// 清理依赖后移动到归档目录
+    Archive,
+
+    /// 删除到回收站
+    Delete,
+}
+
+impl WizardAction {
+    /// 操作名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            WizardAction::Clean => "清理",
+            WizardAction::Skip => "跳过",
+            WizardAction::Archive => "归档",
+            WizardAction::Delete => "删除",
+        }
+    }
+}
+
+/// 清理向导：依次处理排好序的清理候选项目，并累计释放的空间
+#[derive(Debug, Clone, Default)]
+pub struct CleanupWizard {
+    /// 候选项目路径（按评分降序）
+    candidates: Vec<PathBuf>,
+
+    /// 当前处理到的位置
+    position: usize,
+
+    /// 累计释放的空间
+    pub freed: ByteSize,
+
+    /// 已清理的项目数
+    pub cleaned: usize,
+
+    /// 已跳过的项目数
+    pub skipped: usize,
+
+    /// 已归档的项目数
+    pub archived: usize,
+
+    /// 已删除的项目数
+    pub deleted: usize,
+
+    /// 操作失败的项目数
+    pub failed: usize,
+}
+
+impl CleanupWizard {
+    pub fn new(candidates: Vec<PathBuf>) -> Self {
+        Self {
+            candidates,
+            ..Self::default()
+        }
+    }
+
+    /// 当前待处理的项目
+    pub fn current(&self) -> Option<&PathBuf> {
+        self.candidates.get(self.position)
+    }
+
+    /// 当前序号（从 1 开始）与候选总数
+    pub fn progress(&self) -> (usize, usize) {
+        ((self.position + 1).min(self.candidates.len()), self.candidates.len())
+    }
+
+    /// 是否已处理完全部候选项目
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.candidates.len()
+    }
+
+    /// 对当前项目执行操作后前进到下一个项目
+    pub fn advance(&mut self, action: WizardAction) {
+        if self.is_finished() {
+            return;
+        }
+        match action {
+            WizardAction::Clean => self.cleaned += 1,
+            WizardAction::Skip => self.skipped += 1,
+            WizardAction::Archive => self.archived += 1,
+            WizardAction::Delete => self.deleted += 1,
+        }
+        self.position += 1;
+    }
+
+    /// 记录操作结果：成功时累计释放的空间，失败时从对应计数中扣除
+    pub fn record_result(&mut self, action: WizardAction, result: Result<ByteSize, String>) {
+        match result {
+            Ok(freed) => self.freed += freed,
+            Err(_) => {
+                let count = match action {
+                    WizardAction::Clean => &mut self.cleaned,
+                    WizardAction::Skip => &mut self.skipped,
+                    WizardAction::Archive => &mut self.archived,
+                    WizardAction::Delete => &mut self.deleted,
+                };
+                *count = count.saturating_sub(1);
+                self.failed += 1;
+            }
+        }
+    }
+}
+
+impl fmt::Display for CleanupWizard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "已释放 {}（清理 {} · 归档 {} · 删除 {} · 跳过 {}",
+            self.freed,
+            self.cleaned,
+            self.archived,
+            self.deleted,
+            self.skipped,
+        )?;
+        if self.failed > 0 {
+            write!(f, " · 失败 {}", self.failed)?;
+        }
+        write!(f, "）")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wizard_walks_candidates_and_tracks_freed_space() {
+        let mut wizard = CleanupWizard::new(vec![
+            PathBuf::from("/work/a"),
+            PathBuf::from("/work/b"),
+            PathBuf::from("/work/c"),
+        ]);
+        assert_eq!(wizard.progress(), (1, 3));
+
+        wizard.advance(WizardAction::Clean);
+        wizard.record_result(WizardAction::Clean, Ok(ByteSize::new(2048)));
+        wizard.advance(WizardAction::Skip);
+        assert_eq!(wizard.current(), Some(&PathBuf::from("/work/c")));
+
+        wizard.advance(WizardAction::Archive);
+        wizard.record_result(WizardAction::Archive, Err("目标已存在".to_string()));
+        assert!(wizard.is_finished());
+        assert_eq!(wizard.progress(), (3, 3));
+
+        assert_eq!(wizard.freed, ByteSize::new(2048));
+        assert_eq!((wizard.cleaned, wizard.skipped, wizard.archived, wizard.failed), (1, 1, 0, 1));
+        assert_eq!(wizard.to_string(), "已释放 2.0 KB（清理 1 · 归档 0 · 删除 0 · 跳过 1 · 失败 1）");
+    }
+}
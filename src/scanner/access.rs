@@ -0,0 +1,213 @@
+// 扫描前的目录访问检查：跳过无权限读取的目录和其他用户的主目录
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 汇总中最多列出的路径数量
+const SUMMARY_PATH_LIMIT: usize = 3;
+
+/// 跳过目录的原因
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkipReason {
+    /// 没有读取权限
+    PermissionDenied,
+
+    /// 其他用户的主目录
+    OtherUserHome,
+}
+
+impl SkipReason {
+    /// 原因说明
+    pub fn label(&self) -> &'static str {
+        match self {
+            SkipReason::PermissionDenied => "无权限读取",
+            SkipReason::OtherUserHome => "其他用户的主目录",
+        }
+    }
+}
+
+/// 扫描时跳过的位置
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedLocation {
+    /// 目录路径
+    pub path: PathBuf,
+
+    /// 跳过原因
+    pub reason: SkipReason,
+}
+
+/// 一次扫描中跳过的全部位置，扫描结束后汇总提示一次
+#[derive(Debug, Clone, Default)]
+pub struct SkippedLocations {
+    locations: Vec<SkippedLocation>,
+}
+
+impl SkippedLocations {
+    /// 记录跳过的位置
+    #[allow(dead_code)]
+    pub fn push(&mut self, path: impl Into<PathBuf>, reason: SkipReason) {
+        self.locations.push(SkippedLocation { path: path.into(), reason });
+    }
+
+    /// 是否没有跳过任何位置
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// 跳过的位置数量
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+}
+
+impl Extend<SkippedLocation> for SkippedLocations {
+    fn extend<I: IntoIterator<Item = SkippedLocation>>(&mut self, iter: I) {
+        self.locations.extend(iter);
+    }
+}
+
+impl fmt::Display for SkippedLocations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts: Vec<String> = [SkipReason::PermissionDenied, SkipReason::OtherUserHome]
+            .iter()
+            .filter_map(|reason| {
+                let count = self.locations.iter().filter(|l| l.reason == *reason).count();
+                (count > 0).then(|| format!("{} {} 个", reason.label(), count))
+            })
+            .collect();
+        let paths: Vec<String> = self.locations.iter()
+            .take(SUMMARY_PATH_LIMIT)
+            .map(|location| location.path.display().to_string())
+            .collect();
+
+        write!(f, "已跳过 {} 个无法访问的位置（{}）: {}", self.len(), counts.join("，"), paths.join(", "))?;
+        if self.len() > SUMMARY_PATH_LIMIT {
+            write!(f, " 等")?;
+        }
+        Ok(())
+    }
+}
+
+/// 目录访问检查
+#[derive(Debug, Clone, Default)]
+pub struct AccessChecker {
+    /// 存放各用户主目录的目录（当前用户主目录的上级，如 /home、/Users）
+    home_base: Option<PathBuf>,
+
+    /// 当前用户的 uid（取自当前用户主目录的所有者）
+    current_uid: Option<u32>,
+}
+
+impl AccessChecker {
+    /// 根据当前用户的主目录创建检查器
+    pub fn new() -> Self {
+        dirs::home_dir()
+            .map(|home| Self::for_home(&home))
+            .unwrap_or_default()
+    }
+
+    /// 以 `home` 作为当前用户主目录创建检查器
+    pub fn for_home(home: &Path) -> Self {
+        Self {
+            home_base: home.parent().map(Path::to_path_buf),
+            current_uid: owner_uid(home),
+        }
+    }
+
+    /// 检查目录能否扫描，不能扫描时返回原因
+    pub fn check(&self, path: &Path) -> Result<(), SkipReason> {
+        if self.is_other_users_home(path) {
+            return Err(SkipReason::OtherUserHome);
+        }
+
+        match std::fs::read_dir(path) {
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => Err(SkipReason::PermissionDenied),
+            _ => Ok(()),
+        }
+    }
+
+    /// 是否是其他用户的主目录：位于主目录的上级目录下，且所有者不是当前用户
+    pub fn is_other_users_home(&self, path: &Path) -> bool {
+        let (Some(home_base), Some(current_uid)) = (&self.home_base, self.current_uid) else {
+            return false;
+        };
+
+        path.parent() == Some(home_base.as_path())
+            && owner_uid(path).is_some_and(|uid| uid != current_uid)
+    }
+}
+
+/// 目录所有者的 uid（非 Unix 平台不区分所有者）
+#[cfg(unix)]
+fn owner_uid(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path).ok().map(|metadata| metadata.uid())
+}
+
+#[cfg(not(unix))]
+fn owner_uid(_path: &Path) -> Option<u32> {
+    None
+}
+
+/// 遍历时的 IO 错误是否是权限不足
+pub fn is_permission_denied(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::PermissionDenied
+}
+
+/// 目录是否已被删除（ENOENT）；权限不足等无法判断的情况视为仍存在
+pub fn is_removed(path: &Path) -> bool {
+    matches!(std::fs::symlink_metadata(path), Err(error) if error.kind() == io::ErrorKind::NotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_other_users_home_is_skipped() {
+        let temp_dir = tempdir().unwrap();
+        let home_base = temp_dir.path().join("home");
+        std::fs::create_dir_all(home_base.join("me/code")).unwrap();
+        std::fs::create_dir_all(home_base.join("bob")).unwrap();
+
+        let checker = AccessChecker::for_home(&home_base.join("me"));
+        assert_eq!(checker.check(&home_base.join("bob")), Ok(()));
+        assert_eq!(checker.check(&home_base.join("me/code")), Ok(()));
+
+        // 模拟以另一个用户的身份扫描：同一上级目录下的主目录都属于其他用户
+        let other_user = AccessChecker {
+            current_uid: checker.current_uid.map(|uid| uid.wrapping_add(1)),
+            ..checker
+        };
+        assert_eq!(other_user.check(&home_base.join("bob")), Err(SkipReason::OtherUserHome));
+        assert_eq!(other_user.check(&home_base.join("me/code")), Ok(()));
+    }
+
+    #[test]
+    fn test_skipped_locations_summary() {
+        let mut skipped = SkippedLocations::default();
+        skipped.push("/home/bob", SkipReason::OtherUserHome);
+        skipped.push("/home/alice", SkipReason::OtherUserHome);
+        skipped.push("/srv/secret", SkipReason::PermissionDenied);
+        skipped.push("/srv/private", SkipReason::PermissionDenied);
+
+        assert_eq!(
+            skipped.to_string(),
+            "已跳过 4 个无法访问的位置（无权限读取 2 个，其他用户的主目录 2 个）: /home/bob, /home/alice, /srv/secret 等"
+        );
+    }
+
+    #[test]
+    fn test_is_removed() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("project");
+        std::fs::create_dir(&project).unwrap();
+        assert!(!is_removed(&project));
+
+        std::fs::remove_dir(&project).unwrap();
+        assert!(is_removed(&project));
+    }
+}
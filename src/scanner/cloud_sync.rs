@@ -0,0 +1,128 @@
+// 云同步目录检测：识别 Dropbox、iCloud Drive、OneDrive、Google Drive 的本地同步根目录
+
+use std::path::{Path, PathBuf};
+
+use crate::models::CloudProvider;
+
+/// macOS 上 iCloud Drive 的同步目录（相对主目录）
+const ICLOUD_MAC_DIR: &str = "Library/Mobile Documents/com~apple~CloudDocs";
+
+/// macOS 上第三方同步服务（File Provider）的挂载目录（相对主目录）
+const MAC_CLOUD_STORAGE_DIR: &str = "Library/CloudStorage";
+
+/// 主目录下已存在的云同步根目录
+#[derive(Debug, Clone, Default)]
+pub struct CloudSyncRoots {
+    roots: Vec<(PathBuf, CloudProvider)>,
+}
+
+impl CloudSyncRoots {
+    /// 检测当前用户主目录下的同步根目录
+    pub fn detect() -> Self {
+        dirs::home_dir()
+            .map(|home| Self::for_home(&home))
+            .unwrap_or_default()
+    }
+
+    /// 检测 `home` 下的同步根目录
+    ///
+    /// 各平台的默认位置：
+    /// - Dropbox: `~/Dropbox`、`~/Dropbox (团队名)`
+    /// - OneDrive: `~/OneDrive`、`~/OneDrive - 组织名`（Windows）
+    /// - iCloud Drive: `~/Library/Mobile Documents/com~apple~CloudDocs`（macOS）、`~/iCloudDrive`（Windows）
+    /// - Google Drive: `~/Google Drive`
+    /// - macOS 12 起的 `~/Library/CloudStorage/<服务>-<账号>`
+    pub fn for_home(home: &Path) -> Self {
+        let mut roots: Vec<(PathBuf, CloudProvider)> = Vec::new();
+        for base in [home.to_path_buf(), home.join(MAC_CLOUD_STORAGE_DIR)] {
+            let Ok(entries) = std::fs::read_dir(&base) else {
+                continue;
+            };
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let is_dir = entry.file_type().is_ok_and(|file_type| file_type.is_dir());
+                let name = entry.file_name();
+                if let Some(provider) = is_dir.then(|| provider_for_dir_name(&name.to_string_lossy())).flatten() {
+                    roots.push((entry.path(), provider));
+                }
+            }
+        }
+
+        let icloud = home.join(ICLOUD_MAC_DIR);
+        if icloud.is_dir() {
+            roots.push((icloud, CloudProvider::ICloud));
+        }
+
+        roots.sort_by(|a, b| a.0.cmp(&b.0));
+        Self { roots }
+    }
+
+    /// 项目所在的同步服务，不在任何同步根目录中时返回 None
+    pub fn provider_for(&self, path: &Path) -> Option<CloudProvider> {
+        self.roots.iter()
+            .find(|(root, _)| path.starts_with(root))
+            .map(|(_, provider)| *provider)
+    }
+
+    /// 是否没有检测到任何同步根目录
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+}
+
+/// 根据目录名判断同步服务
+fn provider_for_dir_name(name: &str) -> Option<CloudProvider> {
+    let matches = |prefix: &str| {
+        name == prefix
+            || name.strip_prefix(prefix).is_some_and(|rest| rest.starts_with([' ', '-', '(']))
+    };
+
+    if matches("Dropbox") {
+        Some(CloudProvider::Dropbox)
+    } else if matches("OneDrive") {
+        Some(CloudProvider::OneDrive)
+    } else if matches("iCloudDrive") || matches("iCloud Drive") {
+        Some(CloudProvider::ICloud)
+    } else if matches("Google Drive") || matches("GoogleDrive") {
+        Some(CloudProvider::GoogleDrive)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_detects_well_known_sync_roots() {
+        let home = tempdir().unwrap();
+        for dir in [
+            "Dropbox (Acme)",
+            "OneDrive - Contoso",
+            "Library/Mobile Documents/com~apple~CloudDocs",
+            "Library/CloudStorage/GoogleDrive-me@example.com",
+            "DropboxBackup",
+            "code",
+        ] {
+            std::fs::create_dir_all(home.path().join(dir)).unwrap();
+        }
+
+        let roots = CloudSyncRoots::for_home(home.path());
+        let provider = |dir: &str| roots.provider_for(&home.path().join(dir));
+        assert_eq!(provider("Dropbox (Acme)/web/app"), Some(CloudProvider::Dropbox));
+        assert_eq!(provider("OneDrive - Contoso/api"), Some(CloudProvider::OneDrive));
+        assert_eq!(provider("Library/Mobile Documents/com~apple~CloudDocs/site"), Some(CloudProvider::ICloud));
+        assert_eq!(provider("Library/CloudStorage/GoogleDrive-me@example.com/x"), Some(CloudProvider::GoogleDrive));
+        assert_eq!(provider("DropboxBackup/app"), None);
+        assert_eq!(provider("code/app"), None);
+    }
+
+    #[test]
+    fn test_missing_home_has_no_roots() {
+        let roots = CloudSyncRoots::for_home(Path::new("/nonexistent/home"));
+        assert!(roots.is_empty());
+        assert_eq!(roots.provider_for(Path::new("/nonexistent/home/Dropbox/app")), None);
+    }
+}
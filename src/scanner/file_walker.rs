@@ -1,21 +1,47 @@
 #![allow(dead_code)]
 
 use std::path::{Path, PathBuf};
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
 use anyhow::Result;
 use tokio::sync::mpsc;
-use walkdir::WalkDir;
+use tokio::task::JoinSet;
 use indicatif::{ProgressBar, ProgressStyle};
 
 use crate::config::Config;
+use crate::config::settings::TrivialProjectMode;
 use crate::scanner::{ProjectDetector, DetectedProject};
+use crate::scanner::cloud_sync::CloudSyncRoots;
+use crate::scanner::access::{self, AccessChecker, SkipReason, SkippedLocation, SkippedLocations};
+use crate::utils::metrics::metrics;
+use crate::utils::path_match;
+
+/// 遍历时不进入的依赖、构建和工具目录：其中不会有独立的项目，跳过可大幅提升性能
+const SKIPPED_DIR_NAMES: &[&str] = &[
+    "node_modules", ".git", "target", ".svn",
+    "__pycache__", ".pytest_cache",
+    "venv", ".venv", "env", ".env",
+    ".idea", ".vscode", ".vs",
+    "dist", "build", "out",
+    ".gradle", ".mvn",
+    "vendor", "bower_components",
+    ".sass-cache", ".cache",
+    "coverage", ".nyc_output",
+    ".next", ".nuxt", ".parcel-cache",
+    ".terraform", ".terragrunt-cache",
+    "Pods", "Carthage", ".build", ".cxx",
+];
 
 /// 文件遍历器 - 负责扫描目录并发现项目
 pub struct FileWalker {
     config: Config,
     project_detector: ProjectDetector,
+    cloud_sync_roots: CloudSyncRoots,
     max_depth: Option<usize>,
     follow_symlinks: bool,
+    
+    /// 是否在终端显示扫描进度条（TUI 中需要关闭）
+    show_progress: bool,
 }
 
 /// 扫描进度信息
@@ -55,18 +81,36 @@ impl FileWalker {
         Self {
             max_depth: config.scan.max_depth,
             follow_symlinks: config.scan.follow_symlinks,
+            project_detector: ProjectDetector::new().with_custom_types(config.detection.custom_types.clone()),
             config,
-            project_detector: ProjectDetector::new(),
+            cloud_sync_roots: CloudSyncRoots::detect(),
+            show_progress: true,
         }
     }
     
-    /// 扫描指定路径，返回发现的项目列表
+    /// 设置是否在终端显示扫描进度条
+    pub fn with_progress_bar(mut self, show_progress: bool) -> Self {
+        self.show_progress = show_progress;
+        self
+    }
+    
+    /// 扫描指定路径，返回发现的项目列表（跳过的位置只记录到日志）
     pub async fn scan_paths(&self, paths: &[String]) -> Result<Vec<DetectedProject>> {
+        let (projects, skipped) = self.scan_paths_with_skipped(paths).await?;
+        if !skipped.is_empty() {
+            tracing::warn!("{}", skipped);
+        }
+        Ok(projects)
+    }
+    
+    /// 扫描指定路径，返回发现的项目列表和因权限等原因跳过的位置
+    pub async fn scan_paths_with_skipped(&self, paths: &[String]) -> Result<(Vec<DetectedProject>, SkippedLocations)> {
         let (tx, mut rx) = mpsc::channel(1000);
         let mut projects = Vec::new();
+        let mut skipped = SkippedLocations::default();
         
         // 创建进度条（在测试时禁用）
-        let progress = if cfg!(test) {
+        let progress = if cfg!(test) || !self.show_progress {
             ProgressBar::hidden()
         } else {
             self.create_progress_bar()
@@ -75,8 +119,8 @@ impl FileWalker {
         // 启动扫描任务
         let scan_task = {
             let paths = paths.to_vec();
-            let tx = tx.clone();
-            let walker = self.clone();
+            // 发送端移入任务，扫描结束后通道关闭，接收循环才能退出
+            let walker = Arc::new(self.clone());
             tokio::spawn(async move {
                 walker.scan_paths_internal(paths, tx).await
             })
@@ -107,21 +151,25 @@ impl FileWalker {
                         ));
                     }
                 }
+                ScanResult::Skipped(location) => {
+                    skipped.extend([location]);
+                }
                 ScanResult::Error(err) => {
                     tracing::warn!("扫描时出错: {}", err);
                 }
             }
         }
         
-        // 等待扫描完成
+        // 等待扫描完成；检测并发进行，按路径排序使结果与完成顺序无关
         scan_task.await??;
+        projects.sort_by(|a, b| a.path.cmp(&b.path));
         progress.finish_with_message(format!(
             "扫描完成！发现 {} 个项目，扫描了 {} 个目录", 
             projects.len(),
             scan_progress.scanned_dirs
         ));
         
-        Ok(projects)
+        Ok((projects, skipped))
     }
     
     /// 创建进度条
@@ -139,11 +187,12 @@ impl FileWalker {
     
     /// 内部扫描实现
     async fn scan_paths_internal(
-        &self, 
+        self: &Arc<Self>, 
         paths: Vec<String>, 
         tx: mpsc::Sender<ScanResult>
     ) -> Result<()> {
         let mut scan_progress = ScanProgress::default();
+        let access_checker = AccessChecker::new();
         
         for path_str in paths {
             let path = PathBuf::from(&path_str);
@@ -152,6 +201,12 @@ impl FileWalker {
                 continue;
             }
             
+            // 无法读取的根目录直接跳过，不再逐个报告其下的错误
+            if let Err(reason) = access_checker.check(&path) {
+                let _ = tx.send(ScanResult::Skipped(SkippedLocation { path, reason })).await;
+                continue;
+            }
+            
             if path.is_dir() {
                 self.scan_directory(&path, &mut scan_progress, &tx).await?;
             } else {
@@ -163,158 +218,194 @@ impl FileWalker {
     }
     
     /// 扫描单个目录
+    ///
+    /// 按层展开目录：待检测的目录交给最多 `concurrent_scans` 个并发任务检测，
+    /// 发现的项目由任务直接发送；不是项目的目录再把子目录加入队列，项目内部不再进入。
     async fn scan_directory(
-        &self,
+        self: &Arc<Self>,
         root_path: &Path,
         progress: &mut ScanProgress,
         tx: &mpsc::Sender<ScanResult>,
     ) -> Result<()> {
         tracing::info!("开始扫描目录: {}", root_path.display());
         
-        // 用于追踪已发现的项目路径，避免重复扫描子项目
-        let mut discovered_projects: HashSet<PathBuf> = HashSet::new();
+        let concurrency = self.config.scan.concurrent_scans.max(1);
+        // 扫描共享机器的上级目录时不进入其他用户的主目录
+        let access_checker = Arc::new(AccessChecker::new());
+        let mut pending = VecDeque::from([(root_path.to_path_buf(), 0)]);
+        // 跟随符号链接时记录已进入的真实路径，避免循环
+        let mut visited: HashSet<PathBuf> = HashSet::new();
+        let mut tasks = JoinSet::new();
         
-        // 配置 WalkDir，使用 filter_entry 在进入目录前过滤
-        let walker = WalkDir::new(root_path)
-            .follow_links(self.follow_symlinks)
-            .max_depth(self.max_depth.unwrap_or(usize::MAX))
-            .into_iter()
-            .filter_entry(|entry| {
-                // 在进入目录前就决定是否要遍历
-                let path = entry.path();
-                
-                // 只过滤目录，不过滤文件
-                if !entry.file_type().is_dir() {
-                    return true;
-                }
-                
-                // 获取目录名
-                let dir_name = match path.file_name().and_then(|n| n.to_str()) {
-                    Some(name) => name,
-                    None => return true,
+        loop {
+            while tasks.len() < concurrency {
+                let Some((path, depth)) = pending.pop_front() else {
+                    break;
                 };
-                
-                // 跳过应该完全忽略的目录（如 node_modules、.git 等）
-                // 这些目录完全不需要进入扫描，可以大幅提升性能
-                let should_skip = matches!(dir_name,
-                    "node_modules" | ".git" | "target" | ".svn" | 
-                    "__pycache__" | ".pytest_cache" |
-                    "venv" | ".venv" | "env" | ".env" |
-                    ".idea" | ".vscode" | ".vs" | 
-                    "dist" | "build" | "out" |
-                    ".gradle" | ".mvn" | 
-                    "vendor" | "bower_components" |
-                    ".sass-cache" | ".cache" |
-                    "coverage" | ".nyc_output" |
-                    ".next" | ".nuxt" | ".parcel-cache"
-                );
-                
-                if should_skip {
-                    tracing::debug!("跳过目录（filter_entry）: {}", path.display());
+                let walker = Arc::clone(self);
+                let access_checker = Arc::clone(&access_checker);
+                let tx = tx.clone();
+                tasks.spawn(async move {
+                    let _running = metrics().walker_tasks.track();
+                    walker.visit_directory(path, depth, &access_checker, &tx).await
+                });
+            }
+            
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            let visit = match joined {
+                Ok(visit) => visit,
+                Err(e) => {
+                    tracing::warn!("目录检测任务异常退出: {}", e);
+                    continue;
                 }
-                
-                !should_skip
-            });
-        
-        let mut entry_count = 0;
-        
-        // 遍历过滤后的目录
-        for entry in walker {
-            entry_count += 1;
+            };
             
-            // 每处理100个条目打印一次日志
-            if entry_count % 100 == 0 {
-                tracing::info!("已处理 {} 个条目，当前进度: {} 个目录", entry_count, progress.scanned_dirs);
+            progress.scanned_dirs += 1;
+            metrics().walker_dirs.inc();
+            if visit.skipped_trivial {
+                progress.skipped_dirs += 1;
             }
+            progress.current_path = Some(visit.path);
             
-            match entry {
-                Ok(entry) => {
-                    let path = entry.path();
-                    
-                    // 记录详细日志
-                    if entry.file_type().is_dir() {
-                        tracing::debug!("处理目录: {}", path.display());
-                    } else {
-                        // 如果卡在某个文件上，这个日志会帮助我们发现
-                        if path.to_string_lossy().contains("poetry.lock") {
-                            tracing::warn!("正在处理 poetry.lock 文件: {}", path.display());
-                        }
-                    }
-                    
-                    // 更新当前扫描路径
-                    progress.current_path = Some(path.to_path_buf());
-                    
-                    // 发送进度更新
-                    if let Err(_) = tx.send(ScanResult::Progress(progress.clone())).await {
-                        tracing::warn!("接收端已关闭，停止扫描");
-                        break; // 接收端已关闭
-                    }
-                    
-                    if entry.file_type().is_dir() {
-                        progress.scanned_dirs += 1;
-                        
-                        // 检查是否在已发现的项目内部
-                        if self.is_inside_discovered_project(path, &discovered_projects) {
-                            tracing::debug!("跳过子项目目录: {}", path.display());
-                            // 跳过已发现项目的子目录，避免重复识别
-                            continue;
-                        }
-                        
-                        // 检查是否应该忽略此目录（用于其他忽略规则）
-                        if self.should_ignore_directory(path) {
-                            tracing::debug!("忽略目录: {}", path.display());
-                            progress.skipped_dirs += 1;
-                            continue;
-                        }
-                        
-                        // 检测是否是项目
-                        tracing::debug!("开始检测项目: {}", path.display());
-                        match self.project_detector.detect_project(path).await {
-                            Ok(Some(detected_project)) => {
-                                tracing::info!("发现项目: {} at {}", detected_project.name, path.display());
-                                // 记录已发现的项目路径
-                                discovered_projects.insert(path.to_path_buf());
-                                
-                                // 发送发现的项目
-                                if let Err(_) = tx.send(ScanResult::Project(detected_project)).await {
-                                    tracing::warn!("发送项目失败，接收端已关闭");
-                                    break; // 接收端已关闭
-                                }
-                            }
-                            Ok(None) => {
-                                // 不是项目，继续
-                            }
-                            Err(e) => {
-                                tracing::warn!("检测项目时出错 {}: {}", path.display(), e);
-                            }
-                        }
-                        tracing::debug!("完成检测项目: {}", path.display());
+            // 发送进度更新
+            if visit.closed || tx.send(ScanResult::Progress(progress.clone())).await.is_err() {
+                tracing::warn!("接收端已关闭，停止扫描");
+                break;
+            }
+            metrics().walker_queue_depth.set((tx.max_capacity() - tx.capacity()) as i64);
+            
+            if progress.scanned_dirs.is_multiple_of(100) {
+                tracing::info!("已扫描 {} 个目录，待检测 {} 个", progress.scanned_dirs, pending.len());
+            }
+            
+            for child in visit.children {
+                if self.follow_symlinks {
+                    let real_path = child.canonicalize().unwrap_or_else(|_| child.clone());
+                    if !visited.insert(real_path) {
+                        continue;
                     }
                 }
-                Err(err) => {
-                    tracing::error!("遍历目录时出错: {}", err);
-                    // 发送错误
-                    let _ = tx.send(ScanResult::Error(
-                        anyhow::anyhow!("遍历目录时出错: {}", err)
-                    )).await;
-                }
+                pending.push_back((child, visit.depth + 1));
             }
         }
         
-        tracing::info!("扫描完成，共处理 {} 个条目，扫描了 {} 个目录", entry_count, progress.scanned_dirs);
+        tracing::info!("扫描完成，扫描了 {} 个目录", progress.scanned_dirs);
         Ok(())
     }
     
-    
-    /// 检查路径是否在已发现的项目内部
-    fn is_inside_discovered_project(&self, path: &Path, discovered_projects: &HashSet<PathBuf>) -> bool {
-        for project_path in discovered_projects {
-            // 如果当前路径是某个已发现项目的子路径（但不是项目本身）
-            if path.starts_with(project_path) && path != project_path {
-                return true;
+    /// 检测单个目录：是项目时发送项目，否则列出需要继续检测的子目录
+    async fn visit_directory(
+        &self,
+        path: PathBuf,
+        depth: usize,
+        access_checker: &AccessChecker,
+        tx: &mpsc::Sender<ScanResult>,
+    ) -> DirectoryVisit {
+        let mut visit = DirectoryVisit {
+            path: path.clone(),
+            depth,
+            children: Vec::new(),
+            skipped_trivial: false,
+            closed: false,
+        };
+        
+        tracing::debug!("开始检测项目: {}", path.display());
+        match self.project_detector.detect_project(&path).await {
+            Ok(Some(mut detected_project)) => {
+                tracing::info!("发现项目: {} at {}", detected_project.name, path.display());
+                
+                // 琐碎项目按配置跳过或标记
+                let scan_config = &self.config.scan;
+                if ProjectDetector::is_trivial_project(&path, scan_config.min_project_size) {
+                    if scan_config.trivial_projects == TrivialProjectMode::Skip {
+                        tracing::debug!("跳过琐碎项目: {}", path.display());
+                        visit.skipped_trivial = true;
+                        return visit;
+                    }
+                    detected_project.is_trivial = true;
+                }
+                detected_project.cloud_sync = self.cloud_sync_roots.provider_for(&path);
+                
+                // 工作区成员作为嵌套项目单独发送（不会再进入根项目内部）
+                visit.closed = tx.send(ScanResult::Project(detected_project)).await.is_err()
+                    || !self.send_workspace_members(&path, tx).await;
+                return visit;
+            }
+            Ok(None) => {
+                // 不是项目，继续检测子目录
+            }
+            Err(e) => {
+                tracing::warn!("检测项目时出错 {}: {}", path.display(), e);
             }
         }
-        false
+        
+        if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            return visit;
+        }
+        
+        let mut entries = match tokio::fs::read_dir(&path).await {
+            Ok(entries) => entries,
+            Err(e) if access::is_permission_denied(&e) => {
+                let location = SkippedLocation { path, reason: SkipReason::PermissionDenied };
+                let _ = tx.send(ScanResult::Skipped(location)).await;
+                return visit;
+            }
+            Err(e) => {
+                tracing::error!("遍历目录时出错: {}", e);
+                let _ = tx.send(ScanResult::Error(
+                    anyhow::anyhow!("遍历目录时出错 {}: {}", path.display(), e)
+                )).await;
+                return visit;
+            }
+        };
+        
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            metrics().walker_entries.inc();
+            let child = entry.path();
+            let is_dir = match entry.file_type().await {
+                Ok(file_type) if file_type.is_symlink() => {
+                    self.follow_symlinks && tokio::fs::metadata(&child).await.is_ok_and(|metadata| metadata.is_dir())
+                }
+                Ok(file_type) => file_type.is_dir(),
+                Err(_) => false,
+            };
+            if !is_dir || !self.should_descend(&child) {
+                continue;
+            }
+            
+            if access_checker.is_other_users_home(&child) {
+                let location = SkippedLocation { path: child, reason: SkipReason::OtherUserHome };
+                let _ = tx.send(ScanResult::Skipped(location)).await;
+                continue;
+            }
+            
+            visit.children.push(child);
+        }
+        
+        visit.children.sort();
+        visit
+    }
+    
+    /// 判断是否进入子目录检测：跳过依赖和构建目录，以及配置的忽略规则和隐藏目录
+    fn should_descend(&self, path: &Path) -> bool {
+        let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) else {
+            return true;
+        };
+        
+        if SKIPPED_DIR_NAMES.contains(&dir_name) {
+            tracing::debug!("跳过目录: {}", path.display());
+            return false;
+        }
+        
+        // 配置的忽略规则和隐藏目录：不进入，其中的项目也不会被发现
+        if self.should_ignore_directory(path) {
+            tracing::debug!("忽略目录: {}", path.display());
+            return false;
+        }
+        
+        true
     }
     
     /// 检查是否应该忽略指定目录（用于其他忽略规则）
@@ -329,14 +420,15 @@ impl FileWalker {
             return true;
         }
         
-        // 检查是否在忽略路径列表中
-        let path_str = path.to_string_lossy();
+        // 检查是否在忽略路径列表中（按路径组件匹配，支持通配符）
         if self.config.ignore.paths.iter().any(|ignored_path| {
-            path_str.contains(ignored_path)
+            path_match::matches_ignore_pattern(path, ignored_path)
         }) {
             return true;
         }
         
+        let path_str = path.to_string_lossy();
+        
         // 检查是否在手动忽略的项目列表中
         if self.config.ignore.projects.contains(&path_str.to_string()) {
             return true;
@@ -350,6 +442,28 @@ impl FileWalker {
         false
     }
     
+    /// 检测并发送工作区根目录下的成员项目，接收端已关闭时返回 false
+    async fn send_workspace_members(&self, root: &Path, tx: &mpsc::Sender<ScanResult>) -> bool {
+        for member in ProjectDetector::workspace_members(root) {
+            let mut detected_member = match self.project_detector.detect_project(&member).await {
+                Ok(Some(detected_member)) => detected_member,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!("检测工作区成员时出错 {}: {}", member.display(), e);
+                    continue;
+                }
+            };
+            
+            tracing::info!("发现工作区成员: {} at {}", detected_member.name, member.display());
+            detected_member.workspace_root = Some(root.to_path_buf());
+            detected_member.cloud_sync = self.cloud_sync_roots.provider_for(&member);
+            if tx.send(ScanResult::Project(detected_member)).await.is_err() {
+                return false;
+            }
+        }
+        true
+    }
+    
     /// 获取扫描配置的克隆
     pub fn get_config(&self) -> &Config {
         &self.config
@@ -361,13 +475,33 @@ impl Clone for FileWalker {
     fn clone(&self) -> Self {
         Self {
             config: self.config.clone(),
-            project_detector: ProjectDetector::new(), // 重新创建检测器
+            project_detector: self.project_detector.clone(),
+            cloud_sync_roots: self.cloud_sync_roots.clone(),
             max_depth: self.max_depth,
             follow_symlinks: self.follow_symlinks,
+            show_progress: self.show_progress,
         }
     }
 }
 
+/// 单个目录的检测结果
+struct DirectoryVisit {
+    /// 目录路径
+    path: PathBuf,
+    
+    /// 相对扫描根目录的深度
+    depth: usize,
+    
+    /// 需要继续检测的子目录（目录是项目时为空）
+    children: Vec<PathBuf>,
+    
+    /// 是否是被跳过的琐碎项目
+    skipped_trivial: bool,
+    
+    /// 接收端是否已关闭
+    closed: bool,
+}
+
 /// 扫描结果枚举
 #[derive(Debug)]
 enum ScanResult {
@@ -377,6 +511,9 @@ enum ScanResult {
     /// 进度更新
     Progress(ScanProgress),
     
+    /// 因权限等原因跳过的位置
+    Skipped(SkippedLocation),
+    
     /// 扫描错误
     Error(anyhow::Error),
 }
@@ -425,4 +562,62 @@ description = "A test project"
         assert!(walker.should_ignore_directory(&node_modules));
         assert!(!walker.should_ignore_directory(&normal_dir));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ignore_paths_match_by_component() {
+        let mut config = Config::default();
+        config.ignore.paths.insert("/tmp".to_string());
+        config.ignore.paths.insert("/code/clients/*".to_string());
+
+        let walker = FileWalker::new(config);
+
+        assert!(walker.should_ignore_directory(Path::new("/tmp/scratch")));
+        assert!(!walker.should_ignore_directory(Path::new("/home/me/tmp-project")));
+        assert!(walker.should_ignore_directory(Path::new("/code/clients/acme")));
+        assert!(!walker.should_ignore_directory(Path::new("/code/internal")));
+    }
+
+    #[tokio::test]
+    async fn test_scan_skips_ignored_projects() {
+        let temp_dir = tempdir().unwrap();
+        for name in ["app", "legacy"] {
+            let project = temp_dir.path().join(name);
+            std::fs::create_dir_all(&project).unwrap();
+            std::fs::write(project.join("package.json"), "{}").unwrap();
+        }
+
+        let mut config = Config::default();
+        config.ignore.projects.insert(temp_dir.path().join("legacy").display().to_string());
+        let root = temp_dir.path().display().to_string();
+        let detected = FileWalker::new(config).scan_paths(&[root]).await.unwrap();
+        let names: Vec<&str> = detected.iter().map(|project| project.name.as_str()).collect();
+        assert_eq!(names, ["app"]);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_scan_stops_at_projects() {
+        let temp_dir = tempdir().unwrap();
+        let manifests = [
+            "apps/web/package.json",
+            "apps/api/Cargo.toml",
+            "apps/api/examples/demo/Cargo.toml",
+            "libs/core/go.mod",
+            "libs/core/node_modules/left-pad/package.json",
+            "scripts/tool/package.json",
+        ];
+        for manifest in manifests {
+            let path = temp_dir.path().join(manifest);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "{}").unwrap();
+        }
+
+        let mut config = Config::default();
+        config.scan.concurrent_scans = 3;
+        let root = temp_dir.path().display().to_string();
+        let detected = FileWalker::new(config).scan_paths(&[root]).await.unwrap();
+
+        // 项目内部的目录不再检测，结果按路径排序
+        let names: Vec<&str> = detected.iter().map(|project| project.name.as_str()).collect();
+        assert_eq!(names, ["api", "web", "core", "tool"]);
+    }
+}
@@ -0,0 +1,179 @@
+// 项目内容指纹：比只看项目目录修改时间更可靠地判断缓存的大小是否过期
+//
+// Git 仓库使用 HEAD 提交、工作区改动文件的状态和修改时间；其他项目按广度优先抽样目录树中的修改时间。
+// 两者都额外记录项目第一层条目的修改时间，安装或删除依赖目录时指纹也会变化。
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use git2::Repository;
+use sha2::{Digest, Sha256};
+
+/// 非 Git 项目最多抽样的条目数
+const MAX_SAMPLED_ENTRIES: usize = 4096;
+
+/// 非 Git 项目每个目录最多抽样的条目数，避免 node_modules 等大目录占满抽样名额
+const MAX_ENTRIES_PER_DIR: usize = 64;
+
+/// 计算项目的内容指纹（十六进制 SHA-256），项目目录无法读取时返回 None
+///
+/// 只有项目根目录本身是 Git 仓库时才使用 Git 状态，嵌套在其他仓库中的项目按普通目录抽样。
+pub fn project_fingerprint(project_path: &Path) -> Option<String> {
+    let mut hasher = Sha256::new();
+    hash_top_level(&mut hasher, project_path)?;
+
+    match Repository::open(project_path) {
+        Ok(repo) => {
+            if hash_git_state(&mut hasher, &repo, project_path).is_err() {
+                hash_sampled_tree(&mut hasher, project_path);
+            }
+        }
+        Err(_) => hash_sampled_tree(&mut hasher, project_path),
+    }
+
+    Some(hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// 记录项目第一层条目的名称和修改时间（.git 在每次 fetch 时都会变化，不记录）
+fn hash_top_level(hasher: &mut Sha256, project_path: &Path) -> Option<()> {
+    let mut entries: Vec<_> = fs::read_dir(project_path).ok()?.flatten()
+        .filter(|entry| entry.file_name() != ".git")
+        .collect();
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        hasher.update(entry.file_name().as_encoded_bytes());
+        hash_metadata(hasher, entry.metadata().ok());
+    }
+    Some(())
+}
+
+/// 记录 HEAD 提交和工作区中每个改动文件的路径、状态和修改时间（不含被忽略的文件）
+fn hash_git_state(hasher: &mut Sha256, repo: &Repository, project_path: &Path) -> Result<(), git2::Error> {
+    match repo.head().ok().and_then(|head| head.target()) {
+        Some(oid) => hasher.update(oid.as_bytes()),
+        None => hasher.update(b"unborn"),
+    }
+
+    let mut options = git2::StatusOptions::new();
+    options.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false)
+        .exclude_submodules(true);
+    let statuses = repo.statuses(Some(&mut options))?;
+
+    let mut changed: Vec<(Vec<u8>, u32)> = statuses.iter()
+        .map(|status| (status.path_bytes().to_vec(), status.status().bits()))
+        .collect();
+    changed.sort();
+    for (path, bits) in changed {
+        hasher.update(&path);
+        hasher.update(bits.to_le_bytes());
+        let file = String::from_utf8_lossy(&path).into_owned();
+        hash_metadata(hasher, fs::symlink_metadata(project_path.join(file)).ok());
+    }
+    Ok(())
+}
+
+/// 按广度优先抽样目录树中条目的相对路径、大小和修改时间（跳过 .git）
+///
+/// 目录的修改时间会在增删文件时变化，抽样到的文件还能发现内容修改；超出抽样名额的深层文件只修改内容时无法发现。
+fn hash_sampled_tree(hasher: &mut Sha256, project_path: &Path) {
+    let mut queue = VecDeque::from([project_path.to_path_buf()]);
+    let mut sampled = 0;
+
+    while let Some(dir) = queue.pop_front() {
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<_> = read_dir.flatten().collect();
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries.into_iter().take(MAX_ENTRIES_PER_DIR) {
+            if sampled >= MAX_SAMPLED_ENTRIES {
+                return;
+            }
+            if entry.file_name() == ".git" {
+                continue;
+            }
+            let path = entry.path();
+            let metadata = fs::symlink_metadata(&path).ok();
+            hasher.update(path.strip_prefix(project_path).unwrap_or(&path).as_os_str().as_encoded_bytes());
+            hash_metadata(hasher, metadata.clone());
+            sampled += 1;
+
+            if metadata.is_some_and(|metadata| metadata.is_dir()) {
+                queue.push_back(path);
+            }
+        }
+    }
+}
+
+/// 记录条目的大小和修改时间（纳秒），无法读取时记录为 0
+fn hash_metadata(hasher: &mut Sha256, metadata: Option<fs::Metadata>) {
+    let (len, modified) = metadata
+        .map(|metadata| {
+            let modified = metadata.modified().ok()
+                .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+                .map_or(0, |duration| duration.as_nanos());
+            (metadata.len(), modified)
+        })
+        .unwrap_or((0, 0));
+    hasher.update(len.to_le_bytes());
+    hasher.update(modified.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    fn touch(path: &Path, offset: u64) {
+        let later = SystemTime::now() + Duration::from_secs(offset);
+        fs::File::options().write(true).open(path).unwrap().set_modified(later).unwrap();
+    }
+
+    #[test]
+    fn test_fingerprint_detects_deep_edits() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path();
+        let deep_file = project.join("src/app/handlers/mod.rs");
+        fs::create_dir_all(deep_file.parent().unwrap()).unwrap();
+        fs::write(&deep_file, "fn main() {}").unwrap();
+
+        let before = project_fingerprint(project).unwrap();
+        assert_eq!(project_fingerprint(project).unwrap(), before);
+
+        // 深层文件只修改内容，项目目录的修改时间不变，指纹仍会变化
+        touch(&deep_file, 60);
+        assert_ne!(project_fingerprint(project).unwrap(), before);
+        assert!(project_fingerprint(&project.join("missing")).is_none());
+    }
+
+    #[test]
+    fn test_git_fingerprint_tracks_head_and_worktree() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path();
+        let repo = Repository::init(project).unwrap();
+        let deep_file = project.join("src/lib.rs");
+        fs::create_dir_all(deep_file.parent().unwrap()).unwrap();
+        fs::write(&deep_file, "pub fn a() {}").unwrap();
+
+        let untracked = project_fingerprint(project).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("src/lib.rs")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[]).unwrap();
+        let committed = project_fingerprint(project).unwrap();
+        assert_ne!(committed, untracked);
+
+        // 已提交的深层文件被修改后出现在工作区状态中
+        fs::write(&deep_file, "pub fn b() {}").unwrap();
+        assert_ne!(project_fingerprint(project).unwrap(), committed);
+    }
+}
@@ -1,15 +1,28 @@
 #![allow(dead_code)]
 
-use std::path::Path;
-use git2::{Repository, RepositoryState, Status};
+use std::path::{Path, PathBuf};
+use git2::{BranchType, Repository, RepositoryState, Status};
 use chrono::{DateTime, Utc};
 use anyhow::Result;
 
-use crate::models::GitInfo;
+use crate::models::{AheadBehind, GitInfo, CommitSummary, BranchInfo};
 
 /// Git 仓库分析器 - 负责提取 Git 仓库的详细信息
 pub struct GitAnalyzer;
 
+/// 已跟踪文件的未提交更改数量
+#[derive(Debug, Default)]
+struct FileChanges {
+    /// 有任何未提交更改的文件
+    changed: usize,
+    
+    /// 有已暂存更改的文件
+    staged: usize,
+    
+    /// 有未暂存修改的文件
+    modified: usize,
+}
+
 impl GitAnalyzer {
     pub fn new() -> Self {
         Self
@@ -18,7 +31,7 @@ impl GitAnalyzer {
     /// 分析指定路径的 Git 仓库，返回仓库信息
     pub fn analyze_repository(&self, repo_path: &Path) -> Result<Option<GitInfo>> {
         // 尝试打开 Git 仓库
-        let repo = match Repository::discover(repo_path) {
+        let mut repo = match Repository::discover(repo_path) {
             Ok(repo) => repo,
             Err(_) => return Ok(None), // 不是 Git 仓库
         };
@@ -31,6 +44,16 @@ impl GitAnalyzer {
             last_commit_author: None,
             has_uncommitted_changes: false,
             has_unpushed_commits: false,
+            stash_count: 0,
+            untracked_count: 0,
+            changed_file_count: 0,
+            staged_file_count: 0,
+            modified_file_count: 0,
+            head_detached: repo.head_detached().unwrap_or(false),
+            unpushed_commit_count: 0,
+            is_bare: repo.is_bare(),
+            worktree_of: Self::main_repository_path(&repo),
+            ahead_behind: None,
         };
         
         // 获取远程仓库 URL
@@ -46,15 +69,72 @@ impl GitAnalyzer {
             git_info.last_commit_author = Some(author);
         }
         
-        // 检查是否有未提交的更改
-        git_info.has_uncommitted_changes = self.has_uncommitted_changes(&repo)?;
+        // 裸仓库没有工作区，也不存在未提交、未推送或未跟踪的内容
+        if !git_info.is_bare {
+            // 检查是否有未提交的更改
+            git_info.has_uncommitted_changes = self.has_uncommitted_changes(&repo)?;
+            
+            let changes = self.count_changed_files(&repo)?;
+            git_info.changed_file_count = changes.changed;
+            git_info.staged_file_count = changes.staged;
+            git_info.modified_file_count = changes.modified;
+            
+            // 检查是否有未推送的提交（包括其他本地分支上的）
+            git_info.unpushed_commit_count = self.count_unpushed_commits(&repo).unwrap_or(0);
+            git_info.has_unpushed_commits = self.has_unpushed_commits(&repo)? || git_info.unpushed_commit_count > 0;
+            
+            // 当前分支相对上游分支的领先/落后提交数
+            git_info.ahead_behind = Self::ahead_behind(&repo).ok().flatten();
+            
+            // 统计未跟踪文件数量
+            git_info.untracked_count = self.count_untracked_files(&repo)?;
+        }
         
-        // 检查是否有未推送的提交
-        git_info.has_unpushed_commits = self.has_unpushed_commits(&repo)?;
+        // 统计 stash 数量
+        git_info.stash_count = self.count_stashes(&mut repo)?;
         
         Ok(Some(git_info))
     }
     
+    /// 检查目录本身是否是至少有一次提交的 Git 仓库（不向上查找父目录中的仓库）
+    pub fn has_commit_history(path: &Path) -> bool {
+        Repository::open(path)
+            .ok()
+            .is_some_and(|repo| repo.head().ok().and_then(|head| head.peel_to_commit().ok()).is_some())
+    }
+    
+    /// 检查目录是否是 Git 仓库根目录（普通仓库、链接工作树或裸仓库）
+    pub fn is_repository_root(path: &Path) -> bool {
+        // 链接工作树中的 .git 是指向主仓库的文件，exists() 同样成立
+        path.join(".git").exists() || Self::is_bare_repository(path)
+    }
+    
+    /// 检查目录是否是裸仓库（目录本身包含 HEAD、objects 和 refs）
+    pub fn is_bare_repository(path: &Path) -> bool {
+        path.join("HEAD").is_file()
+            && path.join("objects").is_dir()
+            && path.join("refs").is_dir()
+    }
+    
+    /// 获取链接工作树所属的主仓库路径，非工作树返回 None
+    fn main_repository_path(repo: &Repository) -> Option<PathBuf> {
+        if !repo.is_worktree() {
+            return None;
+        }
+        
+        // 工作树的 Git 目录中 commondir 文件记录主仓库的 Git 目录（通常为相对路径）
+        let content = std::fs::read_to_string(repo.path().join("commondir")).ok()?;
+        let common_dir = repo.path().join(content.trim());
+        let common_dir = common_dir.canonicalize().unwrap_or(common_dir);
+        
+        // 普通仓库取 .git 的上级目录，裸仓库取其本身
+        if common_dir.file_name().is_some_and(|name| name == ".git") {
+            common_dir.parent().map(Path::to_path_buf)
+        } else {
+            Some(common_dir.to_path_buf())
+        }
+    }
+    
     /// 获取远程仓库 URL（通常是 origin）
     fn get_remote_url(&self, repo: &Repository) -> Result<Option<String>> {
         let remotes = repo.remotes()?;
@@ -162,6 +242,115 @@ impl GitAnalyzer {
         Ok(false)
     }
     
+    /// 统计已跟踪文件中未提交的更改数量（已暂存或工作区修改，不含未跟踪文件），
+    /// 同时分别统计有已暂存更改和有未暂存修改的文件数（同一文件可能两者都有）
+    fn count_changed_files(&self, repo: &Repository) -> Result<FileChanges> {
+        let staged = Status::INDEX_NEW
+            | Status::INDEX_MODIFIED
+            | Status::INDEX_DELETED
+            | Status::INDEX_RENAMED
+            | Status::INDEX_TYPECHANGE;
+        let modified = Status::WT_MODIFIED
+            | Status::WT_DELETED
+            | Status::WT_RENAMED
+            | Status::WT_TYPECHANGE;
+        
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(false).include_ignored(false);
+        
+        let statuses = repo.statuses(Some(&mut options))?;
+        let mut changes = FileChanges::default();
+        for status in statuses.iter() {
+            let flags = status.status();
+            changes.changed += usize::from(flags.intersects(staged | modified));
+            changes.staged += usize::from(flags.intersects(staged));
+            changes.modified += usize::from(flags.intersects(modified));
+        }
+        Ok(changes)
+    }
+    
+    /// 统计所有本地分支（及分离的 HEAD）上不包含在任何远程分支中的提交数量
+    fn count_unpushed_commits(&self, repo: &Repository) -> Result<usize> {
+        let mut revwalk = repo.revwalk()?;
+        let mut has_local = false;
+        
+        for branch in repo.branches(Some(git2::BranchType::Local))? {
+            let (branch, _) = branch?;
+            if let Some(oid) = branch.get().target() {
+                revwalk.push(oid)?;
+                has_local = true;
+            }
+        }
+        if let Ok(head) = repo.head() {
+            if let Some(oid) = head.target() {
+                revwalk.push(oid)?;
+                has_local = true;
+            }
+        }
+        if !has_local {
+            return Ok(0);
+        }
+        
+        for branch in repo.branches(Some(git2::BranchType::Remote))? {
+            let (branch, _) = branch?;
+            if let Some(oid) = branch.get().target() {
+                revwalk.hide(oid)?;
+            }
+        }
+        
+        Ok(revwalk.count())
+    }
+    
+    /// 统计未跟踪文件数量（不包括被 gitignore 忽略的文件）
+    fn count_untracked_files(&self, repo: &Repository) -> Result<usize> {
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+        
+        let statuses = repo.statuses(Some(&mut options))?;
+        
+        Ok(statuses.iter()
+            .filter(|status| status.status().contains(Status::WT_NEW))
+            .count())
+    }
+    
+    /// 统计 stash 数量
+    fn count_stashes(&self, repo: &mut Repository) -> Result<usize> {
+        let mut count = 0;
+        repo.stash_foreach(|_, _, _| {
+            count += 1;
+            true
+        })?;
+        
+        Ok(count)
+    }
+    
+    /// 当前分支相对其上游分支（未设置时为 origin 上的同名分支）的领先/落后提交数
+    ///
+    /// HEAD 游离、还没有提交或没有对应的远程分支时返回 None。
+    pub fn ahead_behind(repo: &Repository) -> std::result::Result<Option<AheadBehind>, git2::Error> {
+        let Ok(head) = repo.head() else {
+            return Ok(None);
+        };
+        if !head.is_branch() {
+            return Ok(None);
+        }
+        let (Some(local), Some(name)) = (head.target(), head.shorthand()) else {
+            return Ok(None);
+        };
+        
+        let upstream = repo.find_branch(name, BranchType::Local)
+            .and_then(|branch| branch.upstream())
+            .or_else(|_| repo.find_branch(&format!("origin/{}", name), BranchType::Remote));
+        let Some(upstream) = upstream.ok().and_then(|branch| branch.get().target()) else {
+            return Ok(None);
+        };
+        
+        let (ahead, behind) = repo.graph_ahead_behind(local, upstream)?;
+        Ok(Some(AheadBehind { ahead, behind }))
+    }
+    
     /// 检查是否有未推送的提交
     fn has_unpushed_commits(&self, repo: &Repository) -> Result<bool> {
         // 获取当前分支的HEAD
@@ -200,6 +389,141 @@ impl GitAnalyzer {
         Ok(local_commit.id() != remote_commit.id())
     }
     
+    /// 获取最近的提交记录（从 HEAD 开始，按时间倒序，最多 limit 条）
+    pub fn get_recent_commits(&self, repo_path: &Path, limit: usize) -> Result<Vec<CommitSummary>> {
+        let repo = match Repository::discover(repo_path) {
+            Ok(repo) => repo,
+            Err(_) => return Ok(Vec::new()),
+        };
+        
+        let mut revwalk = repo.revwalk()?;
+        if revwalk.push_head().is_err() {
+            // 空仓库，没有任何提交
+            return Ok(Vec::new());
+        }
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+        
+        let mut commits = Vec::new();
+        for oid in revwalk.take(limit) {
+            let commit = repo.find_commit(oid?)?;
+            
+            let time = DateTime::from_timestamp(commit.time().seconds(), 0)
+                .unwrap_or_else(Utc::now);
+            
+            let subject = commit.summary()
+                .unwrap_or("(无提交信息)")
+                .to_string();
+            
+            let author = commit.author()
+                .name()
+                .unwrap_or("(未知)")
+                .to_string();
+            
+            let id = commit.id().to_string();
+            
+            commits.push(CommitSummary {
+                id: id[..7.min(id.len())].to_string(),
+                author,
+                time,
+                subject,
+            });
+        }
+        
+        Ok(commits)
+    }
+    
+    /// 获取本地分支列表（按最后提交时间倒序），并标记是否已合并到默认分支
+    ///
+    /// 合并状态与当前检出的分支无关；找不到默认分支时才以 HEAD 为准。
+    pub fn get_branches(&self, repo_path: &Path) -> Result<Vec<BranchInfo>> {
+        let repo = match Repository::discover(repo_path) {
+            Ok(repo) => repo,
+            Err(_) => return Ok(Vec::new()),
+        };
+        
+        let default_branch = Self::default_branch(&repo);
+        let base_id = match &default_branch {
+            Some((_, id)) => Some(*id),
+            None => repo.head().ok().and_then(|head| head.target()),
+        };
+        
+        let mut branches = Vec::new();
+        for branch in repo.branches(Some(git2::BranchType::Local))? {
+            let (branch, _) = branch?;
+            
+            let name = match branch.name()? {
+                Some(name) => name.to_string(),
+                None => continue, // 跳过非 UTF-8 分支名
+            };
+            
+            let commit = match branch.get().peel_to_commit() {
+                Ok(commit) => commit,
+                Err(_) => continue,
+            };
+            
+            let last_commit_time = DateTime::from_timestamp(commit.time().seconds(), 0);
+            
+            // 分支提交是默认分支本身或其祖先，则视为已合并
+            let is_merged = match base_id {
+                Some(base_id) => base_id == commit.id()
+                    || repo.graph_descendant_of(base_id, commit.id()).unwrap_or(false),
+                None => false,
+            };
+            let is_default = default_branch.as_ref().is_some_and(|(default, _)| *default == name);
+            
+            branches.push(BranchInfo {
+                name,
+                last_commit_time,
+                is_current: branch.is_head(),
+                is_merged,
+                is_default,
+            });
+        }
+        
+        branches.sort_by_key(|b| std::cmp::Reverse(b.last_commit_time));
+        
+        Ok(branches)
+    }
+    
+    /// 判断合并状态时使用的默认分支名及其提交：优先 origin/HEAD 指向的远程分支，
+    /// 其次是 init.defaultBranch 配置的、main 或 master 本地分支
+    fn default_branch(repo: &Repository) -> Option<(String, git2::Oid)> {
+        if let Ok(reference) = repo.find_reference("refs/remotes/origin/HEAD") {
+            let name = reference.symbolic_target()
+                .and_then(|target| target.strip_prefix("refs/remotes/origin/"))
+                .map(str::to_string);
+            if let (Some(name), Some(id)) = (name, reference.resolve().ok().and_then(|r| r.target())) {
+                return Some((name, id));
+            }
+        }
+        
+        let configured = repo.config().ok().and_then(|config| config.get_string("init.defaultBranch").ok());
+        configured.into_iter()
+            .chain(["main".to_string(), "master".to_string()])
+            .find_map(|name| {
+                let id = repo.find_branch(&name, BranchType::Local).ok()?.get().target()?;
+                Some((name, id))
+            })
+    }
+    
+    /// 删除所有已合并到默认分支的本地分支（默认分支和当前分支除外），返回被删除的分支名
+    pub fn delete_merged_branches(&self, repo_path: &Path) -> Result<Vec<String>> {
+        let repo = Repository::discover(repo_path)?;
+        
+        let mut deleted = Vec::new();
+        for branch_info in self.get_branches(repo_path)? {
+            if !branch_info.is_deletable() {
+                continue;
+            }
+            
+            let mut branch = repo.find_branch(&branch_info.name, git2::BranchType::Local)?;
+            branch.delete()?;
+            deleted.push(branch_info.name);
+        }
+        
+        Ok(deleted)
+    }
+    
     /// 检查仓库状态
     pub fn get_repository_state(&self, repo_path: &Path) -> Result<Option<String>> {
         let repo = match Repository::discover(repo_path) {
@@ -287,6 +611,7 @@ mod tests {
     use tempfile::tempdir;
     use std::fs;
     use git2::{Repository, Signature, Oid};
+    use crate::models::GitBadge;
 
     #[test]
     fn test_analyze_non_git_directory() {
@@ -335,5 +660,282 @@ mod tests {
                 git_info.current_branch == Some("master".to_string()));
         assert!(git_info.last_commit_message.is_some());
         assert!(git_info.last_commit_author.is_some());
+        assert_eq!(git_info.stash_count, 0);
+        assert_eq!(git_info.untracked_count, 0);
+    }
+
+    #[test]
+    fn test_stash_and_untracked_counts() {
+        let analyzer = GitAnalyzer::new();
+        let temp_dir = tempdir().unwrap();
+        let mut repo = Repository::init(temp_dir.path()).unwrap();
+        
+        fs::write(temp_dir.path().join("tracked.txt"), "v1").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("tracked.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        drop(tree);
+        
+        // 修改已跟踪文件并 stash
+        fs::write(temp_dir.path().join("tracked.txt"), "v2").unwrap();
+        repo.stash_save(&sig, "wip", None).unwrap();
+        
+        // 新增两个未跟踪文件
+        fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        fs::create_dir(temp_dir.path().join("dir")).unwrap();
+        fs::write(temp_dir.path().join("dir").join("b.txt"), "b").unwrap();
+        
+        let git_info = analyzer.analyze_repository(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(git_info.stash_count, 1);
+        assert_eq!(git_info.untracked_count, 2);
+    }
+
+    #[test]
+    fn test_change_kinds_and_detached_head() {
+        let analyzer = GitAnalyzer::new();
+        let temp_dir = tempdir().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+
+        // 只有未跟踪文件
+        fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+        let git_info = analyzer.analyze_repository(temp_dir.path()).unwrap().unwrap();
+        assert!(git_info.is_untracked_only());
+        assert_eq!(git_info.badges(), vec![GitBadge::UntrackedOnly]);
+
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let oid = repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+
+        // 暂存一个新文件，再修改已跟踪文件
+        fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+        index.add_path(std::path::Path::new("b.txt")).unwrap();
+        index.write().unwrap();
+        fs::write(temp_dir.path().join("a.txt"), "a2").unwrap();
+
+        let git_info = analyzer.analyze_repository(temp_dir.path()).unwrap().unwrap();
+        assert_eq!((git_info.staged_file_count, git_info.modified_file_count), (1, 1));
+        assert!(!git_info.is_untracked_only() && !git_info.head_detached);
+        assert_eq!(git_info.badges(), vec![GitBadge::Staged, GitBadge::Modified]);
+
+        repo.set_head_detached(oid).unwrap();
+        let git_info = analyzer.analyze_repository(temp_dir.path()).unwrap().unwrap();
+        assert!(git_info.head_detached);
+        assert_eq!(git_info.badges()[0], GitBadge::Detached);
+    }
+
+    #[test]
+    fn test_unpushed_commit_and_changed_file_counts() {
+        let analyzer = GitAnalyzer::new();
+        let temp_dir = tempdir().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        
+        fs::write(temp_dir.path().join("tracked.txt"), "v1").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("tracked.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        
+        // 第一个提交已推送到 origin
+        let pushed = repo.commit(Some("HEAD"), &sig, &sig, "Pushed", &tree, &[]).unwrap();
+        let pushed = repo.find_commit(pushed).unwrap();
+        repo.reference("refs/remotes/origin/main", pushed.id(), true, "push").unwrap();
+        
+        // 当前分支和另一个本地分支上各有一个未推送的提交
+        repo.commit(Some("HEAD"), &sig, &sig, "Local work", &tree, &[&pushed]).unwrap();
+        let feature = repo.branch("feature", &pushed, false).unwrap();
+        repo.commit(feature.get().name(), &sig, &sig, "Feature work", &tree, &[&pushed]).unwrap();
+        
+        fs::write(temp_dir.path().join("tracked.txt"), "v2").unwrap();
+        fs::write(temp_dir.path().join("new.txt"), "new").unwrap();
+        
+        let git_info = analyzer.analyze_repository(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(git_info.unpushed_commit_count, 2);
+        assert!(git_info.has_unpushed_commits);
+        assert_eq!(git_info.changed_file_count, 1);
+        assert_eq!(git_info.untracked_count, 1);
+    }
+
+    #[test]
+    fn test_ahead_behind_upstream() {
+        let analyzer = GitAnalyzer::new();
+        let temp_dir = tempdir().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        
+        let base = repo.commit(Some("HEAD"), &sig, &sig, "Base", &tree, &[]).unwrap();
+        let base = repo.find_commit(base).unwrap();
+        
+        // 没有对应的远程分支
+        let git_info = analyzer.analyze_repository(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(git_info.ahead_behind, None);
+        
+        // 本地领先 origin 上的同名分支 1 个提交，origin 上另有 2 个本地没有的提交
+        repo.commit(Some("HEAD"), &sig, &sig, "Local", &tree, &[&base]).unwrap();
+        let upstream = repo.commit(None, &sig, &sig, "Upstream 1", &tree, &[&base]).unwrap();
+        let upstream = repo.commit(None, &sig, &sig, "Upstream 2", &tree, &[&repo.find_commit(upstream).unwrap()]).unwrap();
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.reference(&format!("refs/remotes/origin/{}", branch), upstream, true, "fetch").unwrap();
+        
+        let git_info = analyzer.analyze_repository(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(git_info.ahead_behind, Some(AheadBehind { ahead: 1, behind: 2 }));
+        assert_eq!(git_info.ahead_behind.unwrap().to_string(), "↑1 ↓2");
+    }
+
+    #[test]
+    fn test_get_recent_commits() {
+        let analyzer = GitAnalyzer::new();
+        let temp_dir = tempdir().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        
+        // 空仓库没有提交记录
+        assert!(analyzer.get_recent_commits(temp_dir.path(), 20).unwrap().is_empty());
+        
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        
+        let first = repo.commit(Some("HEAD"), &sig, &sig, "First commit", &tree, &[]).unwrap();
+        let first = repo.find_commit(first).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Second commit\n\nWith body", &tree, &[&first]).unwrap();
+        
+        let commits = analyzer.get_recent_commits(temp_dir.path(), 20).unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject, "Second commit");
+        assert_eq!(commits[0].author, "Test User");
+        assert_eq!(commits[0].id.len(), 7);
+        
+        // 数量限制
+        let commits = analyzer.get_recent_commits(temp_dir.path(), 1).unwrap();
+        assert_eq!(commits.len(), 1);
+    }
+
+    #[test]
+    fn test_branches_and_delete_merged() {
+        let analyzer = GitAnalyzer::new();
+        let temp_dir = tempdir().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        
+        let base = repo.commit(Some("HEAD"), &sig, &sig, "Base", &tree, &[]).unwrap();
+        let base = repo.find_commit(base).unwrap();
+        
+        // merged 指向 HEAD 的祖先，已合并
+        repo.branch("merged", &base, false).unwrap();
+        
+        // feature 上有 HEAD 没有的提交，未合并
+        let feature = repo.branch("feature", &base, false).unwrap();
+        repo.commit(feature.get().name(), &sig, &sig, "Feature work", &tree, &[&base]).unwrap();
+        
+        repo.commit(Some("HEAD"), &sig, &sig, "Main work", &tree, &[&base]).unwrap();
+        
+        let branches = analyzer.get_branches(temp_dir.path()).unwrap();
+        assert_eq!(branches.len(), 3);
+        
+        let find = |name: &str| branches.iter().find(|b| b.name == name).unwrap();
+        assert!(find("merged").is_merged);
+        assert!(!find("feature").is_merged);
+        assert!(branches.iter().any(|b| b.is_current && b.is_merged));
+        assert!(!find("merged").is_stale(90));
+        
+        let deleted = analyzer.delete_merged_branches(temp_dir.path()).unwrap();
+        assert_eq!(deleted, vec!["merged".to_string()]);
+        
+        let remaining = analyzer.get_branches(temp_dir.path()).unwrap();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|b| b.name != "merged"));
+    }
+
+    #[test]
+    fn test_delete_merged_from_feature_checkout_keeps_main() {
+        let analyzer = GitAnalyzer::new();
+        let temp_dir = tempdir().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let base = repo.commit(None, &sig, &sig, "Base", &tree, &[]).unwrap();
+        let base = repo.find_commit(base).unwrap();
+        
+        // main 与 old 指向同一提交；feature 在其上继续提交并被检出
+        repo.branch("main", &base, false).unwrap();
+        repo.branch("old", &base, false).unwrap();
+        let feature = repo.branch("feature", &base, false).unwrap();
+        repo.commit(feature.get().name(), &sig, &sig, "Feature work", &tree, &[&base]).unwrap();
+        repo.set_head("refs/heads/feature").unwrap();
+        
+        let branches = analyzer.get_branches(temp_dir.path()).unwrap();
+        let find = |name: &str| branches.iter().find(|b| b.name == name).unwrap();
+        assert!(find("main").is_default && !find("main").is_deletable());
+        assert!(find("feature").is_current && !find("feature").is_merged);
+        
+        let deleted = analyzer.delete_merged_branches(temp_dir.path()).unwrap();
+        assert_eq!(deleted, vec!["old".to_string()]);
+        assert!(repo.find_branch("main", BranchType::Local).is_ok());
+        assert!(repo.find_branch("feature", BranchType::Local).is_ok());
+    }
+
+    #[test]
+    fn test_bare_repository() {
+        let analyzer = GitAnalyzer::new();
+        let temp_dir = tempdir().unwrap();
+        let bare_path = temp_dir.path().join("mirror.git");
+        let repo = Repository::init_bare(&bare_path).unwrap();
+        
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.treebuilder(None).unwrap().write().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        
+        assert!(GitAnalyzer::is_bare_repository(&bare_path));
+        assert!(GitAnalyzer::is_repository_root(&bare_path));
+        
+        let git_info = analyzer.analyze_repository(&bare_path).unwrap().unwrap();
+        assert!(git_info.is_bare);
+        assert!(git_info.worktree_of.is_none());
+        assert!(!git_info.has_uncommitted_changes);
+        assert!(!git_info.has_unpushed_commits);
+        assert_eq!(git_info.last_commit_message.as_deref(), Some("Initial commit"));
+    }
+
+    #[test]
+    fn test_linked_worktree() {
+        let analyzer = GitAnalyzer::new();
+        let temp_dir = tempdir().unwrap();
+        let main_path = temp_dir.path().join("main");
+        let repo = Repository::init(&main_path).unwrap();
+        
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        
+        let worktree_path = temp_dir.path().join("feature-wt");
+        repo.worktree("feature-wt", &worktree_path, None).unwrap();
+        
+        // 工作树中的 .git 是文件，同样视为仓库根目录
+        assert!(worktree_path.join(".git").is_file());
+        assert!(GitAnalyzer::is_repository_root(&worktree_path));
+        
+        let git_info = analyzer.analyze_repository(&worktree_path).unwrap().unwrap();
+        assert!(!git_info.is_bare);
+        assert_eq!(git_info.current_branch.as_deref(), Some("feature-wt"));
+        assert_eq!(
+            git_info.worktree_of.map(|p| p.canonicalize().unwrap()),
+            Some(main_path.canonicalize().unwrap())
+        );
+        
+        // 主仓库本身不是工作树
+        let main_info = analyzer.analyze_repository(&main_path).unwrap().unwrap();
+        assert!(main_info.worktree_of.is_none());
     }
 }
\ No newline at end of file
@@ -5,6 +5,9 @@ use std::collections::HashSet;
 use anyhow::Result;
 use git2::Repository;
 use ignore::WalkBuilder;
+use tokio_util::sync::CancellationToken;
+
+use crate::models::{ByteSize, CleanupSuggestion, SuggestionStatus};
 
 /// Git 忽略规则分析器 - 负责解析和应用 .gitignore 规则
 pub struct GitIgnoreAnalyzer {
@@ -21,6 +24,11 @@ pub struct GitIgnoreAnalyzer {
 impl GitIgnoreAnalyzer {
     /// 创建新的 Git 忽略分析器
     pub fn new(project_path: &Path) -> Result<Self> {
+        Self::new_cancellable(project_path, &CancellationToken::new())
+    }
+    
+    /// 创建可取消的 Git 忽略分析器：遍历项目构建忽略集合时检查取消令牌，取消后返回错误
+    pub fn new_cancellable(project_path: &Path, cancellation_token: &CancellationToken) -> Result<Self> {
         let mut analyzer = Self {
             root_path: project_path.to_path_buf(),
             is_git_repo: false,
@@ -31,7 +39,7 @@ impl GitIgnoreAnalyzer {
         analyzer.is_git_repo = Repository::discover(project_path).is_ok();
         
         if analyzer.is_git_repo {
-            analyzer.build_ignore_set()?;
+            analyzer.build_ignore_set(cancellation_token)?;
         }
         
         Ok(analyzer)
@@ -87,22 +95,13 @@ impl GitIgnoreAnalyzer {
             .git_exclude(true)
             .git_global(true)
             .hidden(false) // 包含隐藏文件，但排除 .git 目录
+            .filter_entry(|entry| entry.file_name() != ".git") // 不进入 .git 目录
             .build();
         
         for result in walker {
             match result {
                 Ok(entry) => {
-                    let path = entry.path();
-                    
-                    // 手动排除 .git 目录
-                    if path.file_name()
-                        .and_then(|name| name.to_str())
-                        .map(|name| name == ".git")
-                        .unwrap_or(false) {
-                        continue;
-                    }
-                    
-                    entries.push(path.to_path_buf());
+                    entries.push(entry.path().to_path_buf());
                 }
                 Err(_) => continue,
             }
@@ -112,7 +111,7 @@ impl GitIgnoreAnalyzer {
     }
     
     /// 构建忽略路径集合
-    fn build_ignore_set(&mut self) -> Result<()> {
+    fn build_ignore_set(&mut self, cancellation_token: &CancellationToken) -> Result<()> {
         let walker = WalkBuilder::new(&self.root_path)
             .git_ignore(true)
             .git_exclude(true)
@@ -130,6 +129,9 @@ impl GitIgnoreAnalyzer {
             .build();
         
         for result in walker_all {
+            if cancellation_token.is_cancelled() {
+                anyhow::bail!("分析已取消");
+            }
             if let Ok(entry) = result {
                 all_paths.insert(entry.path().to_path_buf());
             }
@@ -138,6 +140,9 @@ impl GitIgnoreAnalyzer {
         // 然后获取不被忽略的路径
         let mut not_ignored = HashSet::new();
         for result in walker {
+            if cancellation_token.is_cancelled() {
+                anyhow::bail!("分析已取消");
+            }
             if let Ok(entry) = result {
                 let path = entry.path();
                 
@@ -253,6 +258,70 @@ impl GitIgnoreAnalyzer {
         Ok((total_size, file_count))
     }
     
+    /// 根据被忽略的目录生成清理建议（排除依赖目录，按大小降序）
+    ///
+    /// 只返回最顶层的被忽略目录（父目录本身未被忽略），并过滤掉小于 `min_size` 的目录。
+    pub async fn suggest_cleanup_candidates(
+        &self,
+        exclude_dirs: &[&str],
+        min_size: ByteSize,
+        project_total_size: ByteSize,
+    ) -> Result<Vec<CleanupSuggestion>> {
+        if !self.is_git_repo {
+            return Ok(Vec::new());
+        }
+
+        let mut suggestions = Vec::new();
+
+        for ignored_path in &self.ignored_paths {
+            if !ignored_path.is_dir() {
+                continue;
+            }
+
+            // 只保留最顶层的忽略目录，子目录的大小已包含在父目录中
+            if let Some(parent) = ignored_path.parent() {
+                if self.ignored_paths.contains(parent) {
+                    continue;
+                }
+            }
+
+            // 跳过依赖目录及位于依赖目录内部的路径
+            let relative = ignored_path.strip_prefix(&self.root_path).unwrap_or(ignored_path);
+            let inside_dependency = relative.components().any(|component| {
+                component.as_os_str().to_str()
+                    .map(|name| exclude_dirs.contains(&name))
+                    .unwrap_or(false)
+            });
+            if inside_dependency {
+                continue;
+            }
+
+            let (size, file_count) = self.calculate_directory_size(ignored_path).await?;
+            let size = ByteSize::new(size);
+            if size < min_size {
+                continue;
+            }
+
+            let share = if !project_total_size.is_zero() {
+                (size.bytes() as f64 / project_total_size.bytes() as f64).min(1.0)
+            } else {
+                0.0
+            };
+
+            suggestions.push(CleanupSuggestion {
+                path: ignored_path.clone(),
+                size,
+                file_count,
+                share,
+                status: SuggestionStatus::Pending,
+            });
+        }
+
+        suggestions.sort_by_key(|s| std::cmp::Reverse(s.size));
+
+        Ok(suggestions)
+    }
+
     /// 递归计算目录大小
     fn calculate_directory_size<'a>(&'a self, dir_path: &'a Path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<(u64, usize)>> + Send + 'a>> {
         Box::pin(async move {
@@ -370,6 +439,43 @@ mod tests {
         assert!(analyzer.should_ignore(&temp_dir.path().join(".git").join("objects")));
     }
 
+    #[tokio::test]
+    async fn test_suggest_cleanup_candidates() {
+        let temp_dir = tempdir().unwrap();
+        Repository::init(temp_dir.path()).unwrap();
+        
+        fs::write(temp_dir.path().join(".gitignore"), ".cache/\nnode_modules/\n").unwrap();
+        
+        // 非依赖的忽略目录，应该出现在建议中
+        let cache_dir = temp_dir.path().join(".cache").join("nested");
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(cache_dir.join("blob.bin"), vec![0u8; 4096]).unwrap();
+        
+        // 依赖目录，不应出现在建议中
+        let node_modules = temp_dir.path().join("node_modules");
+        fs::create_dir_all(&node_modules).unwrap();
+        fs::write(node_modules.join("index.js"), vec![0u8; 8192]).unwrap();
+        
+        let analyzer = GitIgnoreAnalyzer::new(temp_dir.path()).unwrap();
+        let suggestions = analyzer
+            .suggest_cleanup_candidates(&["node_modules", ".git"], ByteSize::new(1024), ByteSize::new(8192))
+            .await
+            .unwrap();
+        
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].path, temp_dir.path().join(".cache"));
+        assert_eq!(suggestions[0].size, ByteSize::new(4096));
+        assert_eq!(suggestions[0].file_count, 1);
+        assert!((suggestions[0].share - 0.5).abs() < f64::EPSILON);
+        
+        // 最小大小阈值以下的目录会被过滤
+        let filtered = analyzer
+            .suggest_cleanup_candidates(&["node_modules", ".git"], ByteSize::new(1024 * 1024), ByteSize::new(8192))
+            .await
+            .unwrap();
+        assert!(filtered.is_empty());
+    }
+
     #[test]
     fn test_get_ignore_stats() {
         let temp_dir = tempdir().unwrap();
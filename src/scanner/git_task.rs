@@ -0,0 +1,95 @@
+// 在阻塞线程池中运行 libgit2 分析，避免大仓库占满 tokio 工作线程；全局限制同时运行的分析数量
+
+use std::sync::LazyLock;
+
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+
+use crate::utils::metrics::metrics;
+
+/// 同时运行的 Git 分析数量上限
+static GIT_TASK_PERMITS: LazyLock<Semaphore> = LazyLock::new(|| Semaphore::new(max_concurrent_git_tasks()));
+
+/// 同时运行的 Git 分析数量上限：libgit2 的状态检查以磁盘 IO 为主，并发过多反而更慢
+pub fn max_concurrent_git_tasks() -> usize {
+    num_cpus::get().clamp(2, 4)
+}
+
+/// 在阻塞线程中运行 Git 分析，等待空闲名额后开始
+///
+/// 取消令牌被触发（或返回的 future 被丢弃）时立即返回 None；传给任务的子令牌同时被取消，
+/// 耗时的任务应定期检查并尽早退出，以释放名额。任务异常退出时同样返回 None。
+pub async fn run_git_task<T, F>(cancellation_token: &CancellationToken, task: F) -> Option<T>
+where
+    F: FnOnce(&CancellationToken) -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let permit = tokio::select! {
+        _ = cancellation_token.cancelled() => return None,
+        permit = GIT_TASK_PERMITS.acquire() => permit.ok()?,
+    };
+
+    let task_token = cancellation_token.child_token();
+    let _cancel_on_drop = task_token.clone().drop_guard();
+    let handle = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        let _running = metrics().git_tasks.track();
+        task(&task_token)
+    });
+
+    tokio::select! {
+        _ = cancellation_token.cancelled() => None,
+        result = handle => match result {
+            Ok(value) => Some(value),
+            Err(e) => {
+                tracing::warn!("Git 分析任务异常退出: {}", e);
+                None
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_run_git_task_limits_and_cancels() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let token = CancellationToken::new();
+        let tasks = (0..max_concurrent_git_tasks() * 3).map(|i| {
+            let (running, peak) = (running.clone(), peak.clone());
+            run_git_task(&token, move |_| {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(20));
+                running.fetch_sub(1, Ordering::SeqCst);
+                i
+            })
+        });
+        let results = futures::future::join_all(tasks).await;
+        assert!(results.iter().all(Option::is_some));
+        assert!(peak.load(Ordering::SeqCst) <= max_concurrent_git_tasks());
+
+        // 取消后立即返回，运行中的任务通过子令牌得知取消
+        let token = CancellationToken::new();
+        let (observed_tx, observed_rx) = std::sync::mpsc::channel();
+        let task = run_git_task(&token, move |task_token| {
+            while !task_token.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            let _ = observed_tx.send(());
+        });
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            canceller.cancel();
+        });
+        assert_eq!(task.await, None);
+        assert!(observed_rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+}
@@ -0,0 +1,99 @@
+// 增量刷新：把重新发现的项目与当前列表比较，只有新增、消失和修改过的项目需要重新检测
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::scanner::SizeCache;
+
+/// 增量刷新中项目的变化
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectChange {
+    /// 列表中还没有的项目
+    Found(PathBuf),
+
+    /// 自上次计算大小以来修改过的项目，或没有有效缓存的项目
+    Updated(PathBuf),
+
+    /// 已找不到的项目
+    Removed(PathBuf),
+}
+
+/// 比较重新发现的项目和当前列表
+///
+/// 两边都有的项目根据大小缓存中记录的修改时间判断是否变化，没有变化的项目不出现在结果中；
+/// 缓存未启用时所有已有项目都视为修改过。结果按新增、修改、消失的顺序排列，各组内保持输入顺序。
+pub async fn diff_projects(discovered: &[PathBuf], known: &[PathBuf], cache: Option<&SizeCache>) -> Vec<ProjectChange> {
+    let known_set: HashSet<&Path> = known.iter().map(PathBuf::as_path).collect();
+    let discovered_set: HashSet<&Path> = discovered.iter().map(PathBuf::as_path).collect();
+
+    let mut changes: Vec<ProjectChange> = discovered.iter()
+        .filter(|path| !known_set.contains(path.as_path()))
+        .map(|path| ProjectChange::Found(path.clone()))
+        .collect();
+
+    for path in discovered.iter().filter(|path| known_set.contains(path.as_path())) {
+        let unchanged = match cache {
+            Some(cache) => cache.is_unchanged(path).await,
+            None => false,
+        };
+        if !unchanged {
+            changes.push(ProjectChange::Updated(path.clone()));
+        }
+    }
+
+    changes.extend(known.iter()
+        .filter(|path| !discovered_set.contains(path.as_path()))
+        .map(|path| ProjectChange::Removed(path.clone())));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ByteSize;
+    use crate::scanner::size_cache::{CacheConfig, CachedSizeInfo};
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_diff_projects() {
+        let temp_dir = tempdir().unwrap();
+        let [cached, modified, new, removed] = ["cached", "modified", "new", "removed"].map(|name| {
+            let path = temp_dir.path().join(name);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("package.json"), "{}").unwrap();
+            path
+        });
+
+        let mut cache = SizeCache::with_cache_file(temp_dir.path().join("cache.db"), CacheConfig::default()).await.unwrap();
+        let size_info = CachedSizeInfo {
+            code_size: ByteSize::new(100),
+            dependency_size: ByteSize::ZERO,
+            total_size: ByteSize::new(100),
+            gitignore_excluded_size: ByteSize::ZERO,
+            code_file_count: 1,
+            dependency_file_count: 0,
+            total_file_count: 1,
+            gitignore_excluded_file_count: 0,
+            last_modified: Some(SystemTime::now()),
+            truncated: false,
+        };
+        cache.put(&cached, size_info.clone(), false).await.unwrap();
+        cache.put(&modified, size_info, false).await.unwrap();
+        let later = SystemTime::now() + Duration::from_secs(60);
+        fs::File::options().write(true).open(modified.join("package.json")).unwrap().set_modified(later).unwrap();
+
+        let discovered = [cached.clone(), modified.clone(), new.clone()];
+        let known = [cached.clone(), removed.clone(), modified.clone()];
+        assert_eq!(diff_projects(&discovered, &known, Some(&cache)).await, [
+            ProjectChange::Found(new),
+            ProjectChange::Updated(modified.clone()),
+            ProjectChange::Removed(removed),
+        ]);
+
+        // 没有缓存时无法判断，已有项目全部重新检测
+        let changes = diff_projects(&discovered[..2], &known[..1], None).await;
+        assert_eq!(changes, [ProjectChange::Found(modified), ProjectChange::Updated(cached)]);
+    }
+}
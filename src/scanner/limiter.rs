@@ -0,0 +1,66 @@
+// 限制同时进行的大小计算数量：磁盘较慢时调低 `scan.concurrent_scans`，避免大量计算同时争抢 IO
+
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_util::sync::CancellationToken;
+
+/// 基于信号量的并发限制器，克隆后共享同一组名额
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimiter {
+    permits: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimiter {
+    /// 创建限制器，名额数至少为 1
+    pub fn new(limit: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(limit.max(1))),
+        }
+    }
+
+    /// 等待空闲名额，持有返回的许可期间占用一个名额；取消令牌被触发时返回 None
+    pub async fn acquire(&self, cancellation_token: &CancellationToken) -> Option<OwnedSemaphorePermit> {
+        tokio::select! {
+            _ = cancellation_token.cancelled() => None,
+            permit = self.permits.clone().acquire_owned() => permit.ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_limiter_bounds_concurrency_and_cancels() {
+        let limiter = ConcurrencyLimiter::new(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let token = CancellationToken::new();
+        let tasks = (0..6).map(|_| {
+            let (limiter, running, peak, token) = (limiter.clone(), running.clone(), peak.clone(), token.clone());
+            async move {
+                let _permit = limiter.acquire(&token).await.expect("未取消时应获得名额");
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            }
+        });
+        futures::future::join_all(tasks).await;
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+
+        // 名额被占满时，取消等待立即返回
+        let _held = limiter.acquire(&token).await;
+        let _also_held = limiter.acquire(&token).await;
+        let waiting = CancellationToken::new();
+        waiting.cancel();
+        assert!(limiter.acquire(&waiting).await.is_none());
+
+        // 名额数为 0 时按 1 处理，不会永远等待
+        assert!(ConcurrencyLimiter::new(0).acquire(&token).await.is_some());
+    }
+}
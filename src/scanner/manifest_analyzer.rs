@@ -0,0 +1,243 @@
+// 依赖清单分析：解析 package.json / package-lock.json 和 Cargo.toml / Cargo.lock，
+// 统计直接依赖和间接依赖的数量，并找出 node_modules 和 target 中占用最大的包
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::models::{ByteSize, DependencyInfo, DependencyType, PackageSize, PackageStats};
+
+/// 显示占用最大的包的数量
+pub const TOP_PACKAGES: usize = 10;
+
+/// package.json 中计入直接依赖的字段
+const NODE_DEPENDENCY_FIELDS: &[&str] = &["dependencies", "devDependencies", "optionalDependencies"];
+
+/// Cargo.toml 中计入直接依赖的表
+const CARGO_DEPENDENCY_TABLES: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// 分析依赖目录中的包，只支持 node_modules 和 Rust 的 target 目录，其他类型返回 None
+pub fn analyze(project_path: &Path, dependency: &DependencyInfo) -> Option<PackageStats> {
+    match dependency.dependency_type {
+        DependencyType::NodeModules => Some(analyze_node(project_path, &dependency.path)),
+        DependencyType::RustTarget => Some(analyze_rust(project_path, &dependency.path)),
+        _ => None,
+    }
+}
+
+/// Node.js 项目：直接依赖来自 package.json，全部依赖来自 package-lock.json（没有锁文件时按已安装的包计算）
+pub fn analyze_node(project_path: &Path, node_modules: &Path) -> PackageStats {
+    let direct: HashSet<String> = read_json(&project_path.join("package.json"))
+        .map(|json| NODE_DEPENDENCY_FIELDS.iter()
+            .filter_map(|field| json.get(field).and_then(|deps| deps.as_object()))
+            .flat_map(|deps| deps.keys().cloned())
+            .collect())
+        .unwrap_or_default();
+
+    let packages = node_modules_packages(node_modules);
+    let total = read_json(&project_path.join("package-lock.json"))
+        .and_then(|lock| lock.get("packages").and_then(|packages| packages.as_object()).map(|packages| {
+            // lockfile v2/v3：键为 "node_modules/a/node_modules/b"，同名包的不同版本只计一次
+            packages.keys()
+                .filter_map(|key| key.rsplit_once("node_modules/").map(|(_, name)| name.to_string()))
+                .collect::<HashSet<_>>()
+                .len()
+        }))
+        .unwrap_or(packages.len());
+
+    PackageStats {
+        direct_count: direct.len(),
+        transitive_count: total.saturating_sub(direct.len()),
+        largest: largest(packages.into_iter().map(|(name, path)| (name, dir_size(&path)))),
+    }
+}
+
+/// Rust 项目：直接依赖来自 Cargo.toml，全部依赖为 Cargo.lock 中来自注册表或 Git 的包（不含工作区成员）
+pub fn analyze_rust(project_path: &Path, target_dir: &Path) -> PackageStats {
+    let manifest = read_toml(&project_path.join("Cargo.toml"));
+    let mut direct = HashSet::new();
+    if let Some(manifest) = &manifest {
+        let tables = CARGO_DEPENDENCY_TABLES.iter().filter_map(|table| manifest.get(table))
+            .chain(manifest.get("workspace").and_then(|workspace| workspace.get("dependencies")));
+        for table in tables.filter_map(|table| table.as_table()) {
+            direct.extend(table.keys().cloned());
+        }
+    }
+
+    let total = read_toml(&project_path.join("Cargo.lock"))
+        .and_then(|lock| lock.get("package").and_then(|packages| packages.as_array()).map(|packages| {
+            packages.iter()
+                .filter(|package| package.get("source").is_some())
+                .filter_map(|package| package.get("name").and_then(|name| name.as_str()))
+                .collect::<HashSet<_>>()
+                .len()
+        }))
+        .unwrap_or(0);
+
+    PackageStats {
+        direct_count: direct.len(),
+        transitive_count: total.saturating_sub(direct.len()),
+        largest: largest(target_crate_sizes(target_dir)),
+    }
+}
+
+/// 填充项目中 node_modules 和 target 依赖的包统计，返回 (依赖目录, 统计) 列表
+pub fn analyze_dependencies(project_path: &Path, dependencies: &[DependencyInfo]) -> Vec<(PathBuf, PackageStats)> {
+    dependencies.iter()
+        .filter_map(|dependency| analyze(project_path, dependency).map(|stats| (dependency.path.clone(), stats)))
+        .collect()
+}
+
+/// node_modules 第一层的包（@scope 下的包名为 "@scope/name"），跳过 .bin 等隐藏目录
+fn node_modules_packages(node_modules: &Path) -> Vec<(String, PathBuf)> {
+    let mut packages = Vec::new();
+    for entry in read_dirs(node_modules) {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        if name.starts_with('@') {
+            for scoped in read_dirs(&entry.path()) {
+                packages.push((format!("{}/{}", name, scoped.file_name().to_string_lossy()), scoped.path()));
+            }
+        } else {
+            packages.push((name, entry.path()));
+        }
+    }
+    packages
+}
+
+/// target 中各 crate 的构建产物大小：按 crate 名合并各构建配置下 deps/ 中的文件和 build/ 中的目录
+fn target_crate_sizes(target_dir: &Path) -> impl Iterator<Item = (String, ByteSize)> {
+    let mut sizes: HashMap<String, ByteSize> = HashMap::new();
+    for profile in read_dirs(target_dir) {
+        for entry in fs::read_dir(profile.path().join("deps")).into_iter().flatten().flatten() {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            if let (Some(name), Ok(metadata)) = (crate_name(&file_name), entry.metadata()) {
+                *sizes.entry(name).or_default() += ByteSize::new(metadata.len());
+            }
+        }
+        for entry in read_dirs(&profile.path().join("build")) {
+            if let Some(name) = crate_name(&entry.file_name().to_string_lossy()) {
+                *sizes.entry(name).or_default() += dir_size(&entry.path());
+            }
+        }
+    }
+    sizes.into_iter()
+}
+
+/// 从构建产物名中取出 crate 名：去掉扩展名、库文件的 lib 前缀和 "-<16 位哈希>" 后缀（包名中的 - 在产物名中为 _），没有哈希后缀时返回 None
+fn crate_name(file_name: &str) -> Option<String> {
+    let (stem, extension) = match file_name.split_once('.') {
+        Some((stem, extension)) => (stem, Some(extension)),
+        None => (file_name, None),
+    };
+    let (name, hash) = stem.rsplit_once('-')?;
+    if hash.len() != 16 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let is_library = extension.is_some_and(|extension| matches!(extension, "rlib" | "rmeta" | "so" | "dylib" | "a"));
+    let name = if is_library { name.strip_prefix("lib").unwrap_or(name) } else { name };
+    Some(name.to_string())
+}
+
+/// 按大小降序取前 TOP_PACKAGES 个，大小相同时按名称排序
+fn largest(sizes: impl Iterator<Item = (String, ByteSize)>) -> Vec<PackageSize> {
+    let mut packages: Vec<PackageSize> = sizes
+        .filter(|(_, size)| !size.is_zero())
+        .map(|(name, size)| PackageSize { name, size })
+        .collect();
+    packages.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+    packages.truncate(TOP_PACKAGES);
+    packages
+}
+
+/// 目录中的子目录（不跟随符号链接）
+fn read_dirs(path: &Path) -> impl Iterator<Item = fs::DirEntry> {
+    fs::read_dir(path).into_iter().flatten().flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+}
+
+/// 目录中全部文件的大小（不跟随符号链接）
+fn dir_size(path: &Path) -> ByteSize {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| ByteSize::new(metadata.len()))
+        .sum()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    fs::read_to_string(path).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_analyze_node_project() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path();
+        write(&project.join("package.json"), r#"{"dependencies": {"react": "^18"}, "devDependencies": {"@types/node": "^20"}}"#);
+        write(&project.join("package-lock.json"), r#"{"packages": {
+            "": {},
+            "node_modules/react": {},
+            "node_modules/loose-envify": {},
+            "node_modules/js-tokens": {},
+            "node_modules/@types/node": {},
+            "node_modules/react/node_modules/js-tokens": {}
+        }}"#);
+        let node_modules = project.join("node_modules");
+        write(&node_modules.join("react/index.js"), &"x".repeat(300));
+        write(&node_modules.join("@types/node/index.d.ts"), &"x".repeat(500));
+        write(&node_modules.join("js-tokens/index.js"), &"x".repeat(10));
+        write(&node_modules.join(".bin/react"), "x");
+
+        let stats = analyze_node(project, &node_modules);
+        assert_eq!((stats.direct_count, stats.transitive_count), (2, 2));
+        let names: Vec<&str> = stats.largest.iter().map(|package| package.name.as_str()).collect();
+        assert_eq!(names, ["@types/node", "react", "js-tokens"]);
+        assert_eq!(stats.largest[0].size, ByteSize::new(500));
+    }
+
+    #[test]
+    fn test_analyze_rust_project() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path();
+        write(&project.join("Cargo.toml"), "[package]\nname = \"app\"\n[dependencies]\nserde = \"1\"\n[dev-dependencies]\ntempfile = \"3\"\n");
+        write(&project.join("Cargo.lock"), concat!(
+            "[[package]]\nname = \"app\"\nversion = \"0.1.0\"\n",
+            "[[package]]\nname = \"serde\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            "[[package]]\nname = \"serde_derive\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            "[[package]]\nname = \"tempfile\"\nversion = \"3.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+        ));
+        let target = project.join("target");
+        write(&target.join("debug/deps/libserde-0123456789abcdef.rlib"), &"x".repeat(400));
+        write(&target.join("debug/deps/serde-0123456789abcdef.d"), &"x".repeat(10));
+        write(&target.join("release/deps/libserde_derive-fedcba9876543210.so"), &"x".repeat(200));
+        write(&target.join("debug/build/serde-1111111111111111/out/generated.rs"), &"x".repeat(90));
+        write(&target.join("debug/app"), &"x".repeat(1000));
+
+        let stats = analyze_rust(project, &target);
+        assert_eq!((stats.direct_count, stats.transitive_count), (2, 1));
+        assert_eq!(stats.largest, [
+            PackageSize { name: "serde".to_string(), size: ByteSize::new(500) },
+            PackageSize { name: "serde_derive".to_string(), size: ByteSize::new(200) },
+        ]);
+    }
+}
@@ -1,13 +1,29 @@
+pub mod access;
+pub mod cloud_sync;
 pub mod file_walker;
+pub mod fingerprint;
 pub mod git_analyzer;
 pub mod git_ignore_analyzer;
+pub mod git_task;
+pub mod incremental;
+pub mod limiter;
+pub mod manifest_analyzer;
 pub mod parallel_file_walker;
 pub mod project_detector;
 pub mod size_cache;
 pub mod size_calculator;
+pub mod stream;
+pub mod symlinks;
+pub mod watcher;
+pub mod workspace;
 
 pub use file_walker::FileWalker;
 pub use git_analyzer::GitAnalyzer;
+pub use git_ignore_analyzer::GitIgnoreAnalyzer;
+pub use git_task::run_git_task;
+pub use limiter::ConcurrencyLimiter;
 pub use project_detector::{ProjectDetector, DetectedProject};
-pub use size_calculator::SizeCalculator;
-pub use parallel_file_walker::ScanStage;
\ No newline at end of file
+pub use size_calculator::{SizeCalculator, SizeLimits, SizeTreeNode};
+pub use size_cache::{SizeCache, CacheStatus};
+pub use parallel_file_walker::ScanStage;
+pub use stream::{scan_stream, ScanEvent};
\ No newline at end of file
@@ -2,13 +2,23 @@
 
 use std::path::{Path, PathBuf};
 use std::collections::HashSet;
+use std::time::SystemTime;
 use anyhow::Result;
 use tokio::sync::mpsc;
 // use tokio_stream::wrappers::ReceiverStream;  // 暂时未使用
 // use futures::stream::StreamExt;  // 暂时未使用
-use rayon::prelude::*;
 use walkdir::WalkDir;
 
+use super::size_calculator::SizeLimits;
+use super::symlinks::SymlinkTracker;
+use crate::utils::metrics::metrics;
+
+/// 发现阶段每找到多少个文件报告一次进度
+const DISCOVERY_PROGRESS_INTERVAL: usize = 500;
+
+/// 发现阶段进度通道的容量，通道已满时丢弃进度而不阻塞遍历
+const DISCOVERY_PROGRESS_BUFFER: usize = 16;
+
 /// 并发文件系统扫描器 - 充分利用 Rust 的并发能力
 pub struct ParallelFileWalker {
     /// 需要忽略的目录
@@ -22,6 +32,12 @@ pub struct ParallelFileWalker {
     
     /// 工作队列大小
     queue_size: usize,
+    
+    /// 统计上限
+    limits: SizeLimits,
+    
+    /// 是否跟随指向文件的符号链接
+    follow_symlinks: bool,
 }
 
 /// 文件信息结构
@@ -31,6 +47,7 @@ pub struct FileInfo {
     pub size: u64,
     pub is_code_file: bool,
     pub is_dependency_file: bool,
+    pub modified: Option<SystemTime>,
 }
 
 /// 目录信息结构
@@ -67,10 +84,12 @@ impl ParallelFileWalker {
             ignore_extensions: Self::default_ignore_extensions(),
             max_concurrent_tasks: num_cpus::get().max(4), // 至少4个并发任务
             queue_size: 1000,
+            limits: SizeLimits::default(),
+            follow_symlinks: false,
         }
     }
     
-    /// 使用自定义配置创建扫描器
+    /// 使用自定义配置创建扫描器，并发任务数至少为 1
     pub fn with_config(
         ignore_dirs: HashSet<String>,
         ignore_extensions: HashSet<String>,
@@ -79,17 +98,44 @@ impl ParallelFileWalker {
         Self {
             ignore_dirs,
             ignore_extensions,
-            max_concurrent_tasks,
+            max_concurrent_tasks: max_concurrent_tasks.max(1),
             queue_size: 1000,
+            limits: SizeLimits::default(),
+            follow_symlinks: false,
         }
     }
     
+    /// 设置统计上限：文件数在发现阶段限制，字节数在获取元数据时限制
+    pub fn with_limits(mut self, limits: SizeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+    
+    /// 设置是否跟随指向文件的符号链接，默认按链接自身计算
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+    
     /// 并发扫描指定路径的所有文件
     pub async fn scan_parallel<F>(
         &self, 
         root_path: &Path, 
         progress_callback: F
     ) -> Result<Vec<FileInfo>>
+    where
+        F: Fn(ScanProgress) + Send + Sync + 'static,
+    {
+        let (file_infos, _, _) = self.scan_parallel_limited(root_path, progress_callback).await?;
+        Ok(file_infos)
+    }
+    
+    /// 并发扫描指定路径的文件，达到统计上限时提前停止，并返回是否被截断和发现的失效符号链接
+    pub async fn scan_parallel_limited<F>(
+        &self, 
+        root_path: &Path, 
+        progress_callback: F
+    ) -> Result<(Vec<FileInfo>, bool, Vec<PathBuf>)>
     where
         F: Fn(ScanProgress) + Send + Sync + 'static,
     {
@@ -104,7 +150,7 @@ impl ParallelFileWalker {
             stage: ScanStage::Discovery,
         });
         
-        let file_paths = self.discover_files_fast(root_path).await?;
+        let (file_paths, files_truncated) = self.discover_files_fast(root_path, &progress_callback).await?;
         let total_files = file_paths.len();
         
         // 阶段2：并发获取文件元数据
@@ -116,7 +162,13 @@ impl ParallelFileWalker {
             stage: ScanStage::Metadata,
         });
         
-        let file_infos = self.process_files_parallel(file_paths, progress_callback.clone()).await?;
+        let symlinks = std::sync::Arc::new(std::sync::Mutex::new(SymlinkTracker::new(root_path, self.follow_symlinks)));
+        let (file_infos, bytes_truncated) = self.process_files_parallel(file_paths, symlinks.clone(), progress_callback.clone()).await?;
+        // 工作任务都已结束，这里持有最后一个引用
+        let broken_links = std::sync::Arc::try_unwrap(symlinks).ok()
+            .and_then(|symlinks| symlinks.into_inner().ok())
+            .map(SymlinkTracker::into_broken_links)
+            .unwrap_or_default();
         
         // 阶段3：完成
         progress_callback(ScanProgress {
@@ -127,56 +179,85 @@ impl ParallelFileWalker {
             stage: ScanStage::Completed,
         });
         
-        Ok(file_infos)
+        Ok((file_infos, files_truncated || bytes_truncated, broken_links))
     }
     
-    /// 快速发现所有文件路径（使用 walkdir + rayon 并行化）
-    async fn discover_files_fast(&self, root_path: &Path) -> Result<Vec<PathBuf>> {
+    /// 快速发现文件路径（在线程池中遍历），文件数达到上限时停止遍历并返回是否被截断
+    ///
+    /// 遍历期间通过通道定期报告已发现的文件数和当前目录，大项目在发现阶段不会显示为卡住。
+    async fn discover_files_fast<F>(
+        &self,
+        root_path: &Path,
+        progress_callback: &std::sync::Arc<F>,
+    ) -> Result<(Vec<PathBuf>, bool)>
+    where
+        F: Fn(ScanProgress) + Send + Sync + 'static,
+    {
         let root_path = root_path.to_path_buf();
         let ignore_dirs = self.ignore_dirs.clone();
+        let max_files = self.limits.max_files.unwrap_or(usize::MAX);
+        let (progress_tx, mut progress_rx) = mpsc::channel(DISCOVERY_PROGRESS_BUFFER);
         
         // 使用 tokio::task::spawn_blocking 在线程池中运行 CPU 密集型任务
-        let file_paths = tokio::task::spawn_blocking(move || {
-            WalkDir::new(&root_path)
+        let walk = tokio::task::spawn_blocking(move || {
+            let mut file_paths: Vec<PathBuf> = WalkDir::new(&root_path)
                 .follow_links(false)
                 .into_iter()
                 .filter_map(|entry| entry.ok())
-                .collect::<Vec<_>>()
-                .into_par_iter() // 并行化
-                .filter_map(|entry| {
+                .filter(|entry| {
                     let path = entry.path();
                     
-                    // 跳过目录和应该忽略的文件
-                    if path.is_dir() {
-                        return None;
+                    // 跳过目录和应该忽略的文件（符号链接保留，统计时再按设置处理）
+                    if entry.file_type().is_dir() {
+                        return false;
                     }
                     
                     // 检查是否在忽略的目录中
-                    if path.ancestors().any(|ancestor| {
-                        if let Some(name) = ancestor.file_name() {
-                            if let Some(name_str) = name.to_str() {
-                                return ignore_dirs.contains(name_str);
-                            }
-                        }
-                        false
-                    }) {
-                        return None;
+                    !path.ancestors().any(|ancestor| {
+                        ancestor.file_name()
+                            .and_then(|name| name.to_str())
+                            .is_some_and(|name| ignore_dirs.contains(name))
+                    })
+                })
+                .map(|entry| entry.into_path())
+                // 多取一个用于判断是否超出上限
+                .take(max_files.saturating_add(1))
+                .enumerate()
+                .inspect(|(index, path)| {
+                    if (index + 1) % DISCOVERY_PROGRESS_INTERVAL == 0 {
+                        let current_dir = path.parent().unwrap_or(path).to_path_buf();
+                        let _ = progress_tx.try_send((index + 1, current_dir));
                     }
-                    
-                    Some(path.to_path_buf())
                 })
-                .collect()
-        }).await?;
+                .map(|(_, path)| path)
+                .collect();
+            
+            let truncated = file_paths.len() > max_files;
+            file_paths.truncate(max_files);
+            (file_paths, truncated)
+        });
+        
+        // 遍历结束时发送端随之释放，转发循环随即结束
+        while let Some((found_files, current_dir)) = progress_rx.recv().await {
+            progress_callback(ScanProgress {
+                processed_files: found_files,
+                total_estimated: None,
+                current_path: current_dir,
+                bytes_processed: 0,
+                stage: ScanStage::Discovery,
+            });
+        }
         
-        Ok(file_paths)
+        Ok(walk.await?)
     }
     
-    /// 并发处理文件元数据获取
+    /// 并发处理文件元数据获取，字节数达到上限时停止接收并返回是否被截断
     async fn process_files_parallel<F>(
         &self,
         file_paths: Vec<PathBuf>,
+        symlinks: std::sync::Arc<std::sync::Mutex<SymlinkTracker>>,
         progress_callback: std::sync::Arc<F>
-    ) -> Result<Vec<FileInfo>>
+    ) -> Result<(Vec<FileInfo>, bool)>
     where
         F: Fn(ScanProgress) + Send + Sync + 'static,
     {
@@ -192,16 +273,25 @@ impl ParallelFileWalker {
                 let tx = tx.clone();
                 let ignore_extensions = self.ignore_extensions.clone();
                 let progress_callback = progress_callback.clone();
+                let symlinks = symlinks.clone();
                 
                 tokio::spawn(async move {
+                    let _running = metrics().size_tasks.track();
                     for (i, path) in chunk.iter().enumerate() {
-                        match tokio::fs::metadata(path).await {
-                            Ok(metadata) if metadata.is_file() => {
+                        let metadata = tokio::fs::symlink_metadata(path).await.ok()
+                            .and_then(|metadata| match symlinks.lock() {
+                                Ok(mut symlinks) => symlinks.resolve(path, metadata),
+                                Err(_) => None,
+                            });
+                        match metadata {
+                            Some(metadata) if metadata.is_file() || metadata.is_symlink() => {
+                                metrics().size_files.inc();
                                 let file_info = FileInfo {
                                     path: path.clone(),
                                     size: metadata.len(),
                                     is_code_file: Self::is_code_file(path, &ignore_extensions),
                                     is_dependency_file: Self::is_dependency_file(path),
+                                    modified: metadata.modified().ok(),
                                 };
                                 
                                 if tx.send(file_info).await.is_err() {
@@ -231,16 +321,29 @@ impl ParallelFileWalker {
         
         // 收集所有结果
         let mut results = Vec::new();
+        let mut bytes_processed = 0u64;
+        let mut truncated = false;
         while let Some(file_info) = rx.recv().await {
+            metrics().size_queue_depth.set(rx.len() as i64);
+            bytes_processed += file_info.size;
             results.push(file_info);
+            
+            if self.limits.bytes_reached(bytes_processed) {
+                truncated = true;
+                break;
+            }
         }
         
+        // 提前停止时关闭接收端，工作任务发送失败后自行退出
+        drop(rx);
+        metrics().size_queue_depth.set(0);
+        
         // 等待所有任务完成
         for task in tasks {
             let _ = task.await;
         }
         
-        Ok(results)
+        Ok((results, truncated))
     }
     
     /// 判断是否为代码文件
@@ -270,7 +373,8 @@ impl ParallelFileWalker {
                     matches!(name_str, 
                         "node_modules" | "target" | "build" | "dist" | "out" | 
                         "bin" | "obj" | "__pycache__" | "venv" | "env" | 
-                        ".venv" | ".env" | "site-packages" | "vendor" | "bower_components"
+                        ".venv" | ".env" | "site-packages" | "vendor" | "bower_components" |
+                        ".terraform" | ".terragrunt-cache" | ".cxx"
                     )
                 } else {
                     false
@@ -289,6 +393,7 @@ impl ParallelFileWalker {
             "bin", "obj", "__pycache__", "venv", "env",
             ".venv", ".env", "site-packages", "vendor",
             "bower_components", ".idea", ".vscode", ".vs",
+            ".terraform", ".terragrunt-cache", ".cxx",
         ]
         .iter()
         .map(|s| s.to_string())
@@ -319,6 +424,8 @@ pub struct SizeCalculationResult {
     pub code_file_count: usize,
     pub dependency_file_count: usize,
     pub total_file_count: usize,
+    /// 最新的文件修改时间（不含依赖文件，安装依赖不算项目改动）
+    pub last_modified: Option<SystemTime>,
 }
 
 impl SizeCalculationResult {
@@ -340,6 +447,10 @@ impl SizeCalculationResult {
             code_file_count: code_files.len(),
             dependency_file_count: dependency_only_files.len(),
             total_file_count: file_infos.len(),
+            last_modified: file_infos.iter()
+                .filter(|f| !f.is_dependency_file)
+                .filter_map(|f| f.modified)
+                .max(),
         }
     }
 }
@@ -386,4 +497,52 @@ mod tests {
         assert!(reports.iter().any(|r| r.stage == ScanStage::Discovery));
         assert!(reports.iter().any(|r| r.stage == ScanStage::Completed));
     }
-}
\ No newline at end of file
+    
+    #[tokio::test]
+    async fn test_discovery_reports_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let root_path = temp_dir.path();
+        for dir in 0..4 {
+            let dir_path = root_path.join(format!("module_{}", dir));
+            fs::create_dir(&dir_path).await.unwrap();
+            for file in 0..300 {
+                fs::write(dir_path.join(format!("file_{}.rs", file)), "// code").await.unwrap();
+            }
+        }
+        
+        let reports = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let progress_callback = {
+            let reports = reports.clone();
+            move |progress: ScanProgress| reports.lock().unwrap().push(progress)
+        };
+        ParallelFileWalker::new().scan_parallel(root_path, progress_callback).await.unwrap();
+        
+        // 发现阶段在遍历过程中报告已发现的文件数和所在目录
+        let reports = reports.lock().unwrap();
+        let discovered: Vec<&ScanProgress> = reports.iter()
+            .filter(|r| r.stage == ScanStage::Discovery && r.processed_files > 0)
+            .collect();
+        assert_eq!(discovered.iter().map(|r| r.processed_files).collect::<Vec<_>>(), vec![500, 1000]);
+        assert!(discovered.iter().all(|r| r.current_path.starts_with(root_path) && r.current_path != root_path));
+    }
+    
+    #[test]
+    fn test_last_modified_ignores_dependency_files() {
+        let file = |path: &str, is_dependency_file: bool, secs: u64| FileInfo {
+            path: PathBuf::from(path),
+            size: 1,
+            is_code_file: !is_dependency_file,
+            is_dependency_file,
+            modified: Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs)),
+        };
+        
+        // 安装依赖不算项目改动，最后修改时间取代码文件中最新的一个
+        let result = SizeCalculationResult::from_file_infos(&[
+            file("/code/web/src/main.rs", false, 100),
+            file("/code/web/README.md", false, 300),
+            file("/code/web/node_modules/lib/index.js", true, 900),
+        ]);
+        assert_eq!(result.last_modified, Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(300)));
+        assert_eq!(SizeCalculationResult::from_file_infos(&[]).last_modified, None);
+    }
+}
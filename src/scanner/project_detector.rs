@@ -1,11 +1,60 @@
 #![allow(dead_code)]
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use crate::models::{ProjectType, DependencyInfo, DependencyType};
+use crate::models::{ByteSize, CloudProvider, DependencyCalculationStatus, DependencyInfo, DependencyType, GitInfo, Project, ProjectType};
+use crate::config::settings::CustomProjectType;
+use super::git_analyzer::GitAnalyzer;
+use super::workspace::wildcard_match;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use walkdir::WalkDir;
+
+/// 扩展名推断时最多检查的文件数
+const MAX_HEURISTIC_FILES: usize = 2000;
+
+/// 扩展名推断时的最大目录深度
+const MAX_HEURISTIC_DEPTH: usize = 4;
+
+/// 查找 README 标题时读取的字节数
+const README_PEEK_BYTES: u64 = 1024;
+
+/// 按顺序查找的 README 文件名
+const README_FILE_NAMES: &[&str] = &["README.md", "readme.md", "Readme.md", "README.markdown", "README"];
+
+/// Unity 项目中可重新生成的缓存目录
+pub const UNITY_CACHE_DIRS: &[&str] = &["Library", "Temp", "Obj"];
+
+/// Unreal 项目中可重新生成的缓存目录
+pub const UNREAL_CACHE_DIRS: &[&str] = &["Intermediate", "DerivedDataCache"];
+
+/// Android 项目各模块中可重新生成的构建目录
+pub const ANDROID_BUILD_DIRS: &[&str] = &["build", ".cxx"];
+
+/// .NET 项目的构建输出目录
+pub const DOTNET_BUILD_DIRS: &[&str] = &["bin", "obj"];
+
+/// .NET 项目文件的扩展名
+const DOTNET_PROJECT_EXTENSIONS: &[&str] = &["csproj", "fsproj", "vbproj"];
+
+/// Dart / Flutter 项目中可重新生成的目录
+pub const DART_DEPENDENCY_DIRS: &[&str] = &[".dart_tool", "build"];
+
+/// Elixir 项目的构建目录和依赖目录
+pub const ELIXIR_DEPENDENCY_DIRS: &[&str] = &["_build", "deps"];
+
+/// Zig 项目的构建缓存和输出目录（0.12 起缓存目录为 .zig-cache）
+pub const ZIG_DEPENDENCY_DIRS: &[&str] = &["zig-cache", ".zig-cache", "zig-out"];
+
+/// Xcode / Swift 项目中的依赖目录（CocoaPods、Carthage、SwiftPM）
+pub const APPLE_DEPENDENCY_DIRS: &[&str] = &["Pods", "Carthage", ".build"];
 
 /// 项目类型检测器
-pub struct ProjectDetector;
+#[derive(Debug, Clone, Default)]
+pub struct ProjectDetector {
+    /// 配置中的自定义项目类型，与内置规则一起检测
+    custom_types: Vec<CustomProjectType>,
+}
 
 /// 检测到的项目信息
 #[derive(Debug, Clone)]
@@ -27,11 +76,43 @@ pub struct DetectedProject {
     
     /// 是否是 Git 仓库
     pub is_git_repo: bool,
+    
+    /// 是否是琐碎项目（由扫描器根据配置判断）
+    pub is_trivial: bool,
+    
+    /// 所在的云同步目录（由扫描器判断）
+    pub cloud_sync: Option<CloudProvider>,
+    
+    /// 所属工作区的根目录（工作区成员才有，由扫描器设置）
+    pub workspace_root: Option<PathBuf>,
+}
+
+impl DetectedProject {
+    /// 转换为完整的项目信息（代码大小等字段留待后续计算）
+    pub fn into_project(self, git_info: Option<GitInfo>, last_modified: DateTime<Utc>) -> Project {
+        Project::builder(self.name, self.path)
+            .project_type(self.project_type)
+            .dependencies(self.dependencies)
+            .dependency_calculation_status(DependencyCalculationStatus::Completed)
+            .last_modified(last_modified)
+            .git_info(git_info)
+            .description(self.description)
+            .trivial(self.is_trivial)
+            .cloud_sync(self.cloud_sync)
+            .workspace_root(self.workspace_root)
+            .build()
+    }
 }
 
 impl ProjectDetector {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+    
+    /// 设置自定义项目类型
+    pub fn with_custom_types(mut self, custom_types: Vec<CustomProjectType>) -> Self {
+        self.custom_types = custom_types;
+        self
     }
     
     /// 检测指定路径是否是一个项目，并返回项目信息
@@ -45,7 +126,7 @@ impl ProjectDetector {
         let mut description = None;
         
         // 检测 Git 仓库
-        let is_git_repo = path.join(".git").exists();
+        let is_git_repo = GitAnalyzer::is_repository_root(path);
         if is_git_repo {
             detected_types.push(ProjectType::Git);
         }
@@ -86,8 +167,14 @@ impl ProjectDetector {
             dependencies.extend(go_deps);
         }
         
-        // 检测 Java 项目
-        if let Some((java_desc, java_deps)) = self.detect_java(path).await? {
+        // 检测 Android 项目（包含 Java 检测的 build 目录，因此不再重复检测 Java）
+        if let Some((android_desc, android_deps)) = self.detect_android(path).await? {
+            detected_types.push(ProjectType::Android);
+            if description.is_none() {
+                description = android_desc;
+            }
+            dependencies.extend(android_deps);
+        } else if let Some((java_desc, java_deps)) = self.detect_java(path).await? {
             detected_types.push(ProjectType::Java);
             if description.is_none() {
                 description = java_desc;
@@ -104,6 +191,115 @@ impl ProjectDetector {
             dependencies.extend(cpp_deps);
         }
         
+        // 检测 Terraform 项目
+        if let Some((tf_desc, tf_deps)) = self.detect_terraform(path).await? {
+            detected_types.push(ProjectType::Terraform);
+            if description.is_none() {
+                description = tf_desc;
+            }
+            dependencies.extend(tf_deps);
+        }
+        
+        // 检测 Ansible 项目
+        if let Some((ansible_desc, ansible_deps)) = self.detect_ansible(path).await? {
+            detected_types.push(ProjectType::Ansible);
+            if description.is_none() {
+                description = ansible_desc;
+            }
+            dependencies.extend(ansible_deps);
+        }
+        
+        // 检测 Helm Chart
+        if let Some((helm_desc, helm_deps)) = self.detect_helm(path).await? {
+            detected_types.push(ProjectType::Helm);
+            if description.is_none() {
+                description = helm_desc;
+            }
+            dependencies.extend(helm_deps);
+        }
+        
+        // 检测 Nix 项目
+        if let Some((nix_desc, nix_deps)) = self.detect_nix(path).await? {
+            detected_types.push(ProjectType::Nix);
+            if description.is_none() {
+                description = nix_desc;
+            }
+            dependencies.extend(nix_deps);
+        }
+        
+        // 检测 Xcode / Swift 项目
+        if let Some((swift_desc, swift_deps)) = self.detect_swift(path).await? {
+            detected_types.push(ProjectType::Swift);
+            if description.is_none() {
+                description = swift_desc;
+            }
+            dependencies.extend(swift_deps);
+        }
+        
+        // 检测 .NET 项目
+        if let Some((dotnet_desc, dotnet_deps)) = self.detect_dotnet(path).await? {
+            detected_types.push(ProjectType::DotNet);
+            if description.is_none() {
+                description = dotnet_desc;
+            }
+            dependencies.extend(dotnet_deps);
+        }
+        
+        // 检测 Dart / Flutter 项目
+        if let Some((dart_desc, dart_deps)) = self.detect_dart(path).await? {
+            detected_types.push(ProjectType::Dart);
+            if description.is_none() {
+                description = dart_desc;
+            }
+            dependencies.extend(dart_deps);
+        }
+        
+        // 检测 PHP 项目
+        if let Some((php_desc, php_deps)) = self.detect_php(path).await? {
+            detected_types.push(ProjectType::Php);
+            if description.is_none() {
+                description = php_desc;
+            }
+            dependencies.extend(php_deps);
+        }
+        
+        // 检测 Elixir 项目
+        if let Some((elixir_desc, elixir_deps)) = self.detect_elixir(path).await? {
+            detected_types.push(ProjectType::Elixir);
+            if description.is_none() {
+                description = elixir_desc;
+            }
+            dependencies.extend(elixir_deps);
+        }
+        
+        // 检测 Zig 项目
+        if let Some((zig_desc, zig_deps)) = self.detect_zig(path).await? {
+            detected_types.push(ProjectType::Zig);
+            if description.is_none() {
+                description = zig_desc;
+            }
+            dependencies.extend(zig_deps);
+        }
+        
+        // 检测 Unity / Unreal 游戏项目
+        if let Some((game_type, game_desc, game_deps)) = self.detect_game_engine(path).await? {
+            detected_types.push(game_type);
+            if description.is_none() {
+                description = game_desc;
+            }
+            dependencies.extend(game_deps);
+        }
+        
+        // 检测配置中的自定义项目类型，内置检测已计入的依赖目录不重复计入
+        for (custom_type, custom_deps) in self.detect_custom(path).await? {
+            detected_types.push(custom_type);
+            for dependency in custom_deps {
+                if !dependencies.iter().any(|existing: &DependencyInfo| existing.path == dependency.path) {
+                    dependencies.push(dependency);
+                }
+            }
+        }
+        
         // 如果没有检测到任何项目类型，但是是 Git 仓库，仍然返回项目信息
         if detected_types.is_empty() && !is_git_repo {
             return Ok(None);
@@ -128,11 +324,29 @@ impl ProjectDetector {
             }
         };
         
+        // 没有清单文件时，根据最常见的源码扩展名推断类型
+        let project_type = match project_type {
+            ProjectType::Git | ProjectType::Unknown => {
+                let dir = path.to_path_buf();
+                tokio::task::spawn_blocking(move || Self::infer_type_from_extensions(&dir))
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or(project_type)
+            }
+            other => other,
+        };
+        
         let name = path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("Unknown")
             .to_string();
         
+        // 清单中没有描述时使用 README 的第一个标题，便于辨认 test2、tmp 之类命名随意的目录
+        if description.is_none() {
+            description = Self::readme_title(path).filter(|title| !title.eq_ignore_ascii_case(&name));
+        }
+        
         Ok(Some(DetectedProject {
             path: path.to_path_buf(),
             project_type,
@@ -140,9 +354,98 @@ impl ProjectDetector {
             description,
             dependencies,
             is_git_repo,
+            is_trivial: false,
+            cloud_sync: None,
+            workspace_root: None,
         }))
     }
     
+    /// 列出工作区（pnpm、Cargo、lerna、go.work）的成员目录，不是工作区根目录时为空
+    pub fn workspace_members(path: &Path) -> Vec<PathBuf> {
+        super::workspace::members(path)
+    }
+    
+    /// 判断是否是琐碎项目：总大小低于 min_size 且没有 Git 提交历史
+    ///
+    /// 累计大小达到阈值后立即停止遍历，大项目的判断开销很小。
+    pub fn is_trivial_project(path: &Path, min_size: u64) -> bool {
+        if min_size == 0 || GitAnalyzer::has_commit_history(path) {
+            return false;
+        }
+        
+        let mut total = 0u64;
+        for entry in WalkDir::new(path).into_iter().filter_map(|entry| entry.ok()) {
+            if entry.file_type().is_file() {
+                total += entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+                if total >= min_size {
+                    return false;
+                }
+            }
+        }
+        
+        true
+    }
+    
+    /// 根据源码文件扩展名出现频率推断项目类型，没有可识别的源码文件时返回 None
+    pub fn infer_type_from_extensions(path: &Path) -> Option<ProjectType> {
+        let mut counts: HashMap<u8, (ProjectType, usize)> = HashMap::new();
+        
+        let files = WalkDir::new(path)
+            .max_depth(MAX_HEURISTIC_DEPTH)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !Self::is_skipped_dir(entry))
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .take(MAX_HEURISTIC_FILES);
+        
+        for entry in files {
+            let Some(project_type) = entry.path().extension()
+                .and_then(|ext| ext.to_str())
+                .and_then(Self::type_for_extension)
+            else {
+                continue;
+            };
+            counts.entry(project_type.priority())
+                .or_insert((project_type, 0))
+                .1 += 1;
+        }
+        
+        // 数量相同时按类型优先级决定，保证结果稳定
+        counts.into_iter()
+            .max_by_key(|(priority, (_, count))| (*count, std::cmp::Reverse(*priority)))
+            .map(|(_, (project_type, _))| project_type)
+    }
+    
+    /// 源码扩展名对应的项目类型
+    fn type_for_extension(extension: &str) -> Option<ProjectType> {
+        match extension {
+            "rs" => Some(ProjectType::Rust),
+            "py" | "pyi" => Some(ProjectType::Python),
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Some(ProjectType::NodeJs),
+            "go" => Some(ProjectType::Go),
+            "java" | "kt" | "scala" => Some(ProjectType::Java),
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" => Some(ProjectType::Cpp),
+            "tf" => Some(ProjectType::Terraform),
+            "nix" => Some(ProjectType::Nix),
+            "swift" => Some(ProjectType::Swift),
+            "cs" | "fs" => Some(ProjectType::DotNet),
+            "dart" => Some(ProjectType::Dart),
+            "php" => Some(ProjectType::Php),
+            "ex" | "exs" => Some(ProjectType::Elixir),
+            "zig" => Some(ProjectType::Zig),
+            _ => None,
+        }
+    }
+    
+    /// 推断类型时跳过隐藏目录和依赖目录
+    fn is_skipped_dir(entry: &walkdir::DirEntry) -> bool {
+        entry.file_type().is_dir()
+            && entry.file_name().to_str().is_some_and(|name| {
+                name.starts_with('.')
+                    || matches!(name, "node_modules" | "target" | "vendor" | "venv" | "__pycache__" | "build" | "dist")
+            })
+    }
+    
     /// 检测 Node.js 项目
     async fn detect_nodejs(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
         let package_json = path.join("package.json");
@@ -175,6 +478,7 @@ impl ProjectDetector {
                     path: node_modules,
                     size,
                     package_count: Some(package_count),
+                    packages: None,
                 });
             }
         }
@@ -212,6 +516,7 @@ impl ProjectDetector {
                     path: target_dir,
                     size,
                     package_count: None,
+                    packages: None,
                 });
             }
         }
@@ -259,6 +564,7 @@ impl ProjectDetector {
                         path: venv_path,
                         size,
                         package_count: None,
+                        packages: None,
                     });
                 }
             }
@@ -272,6 +578,7 @@ impl ProjectDetector {
                     path: pycache,
                     size,
                     package_count: None,
+                    packages: None,
                 });
             }
         }
@@ -315,6 +622,7 @@ impl ProjectDetector {
                     path: target_dir,
                     size,
                     package_count: None,
+                    packages: None,
                 });
             }
         }
@@ -328,6 +636,7 @@ impl ProjectDetector {
                     path: build_dir,
                     size,
                     package_count: None,
+                    packages: None,
                 });
             }
         }
@@ -357,6 +666,7 @@ impl ProjectDetector {
                     path: build_dir,
                     size,
                     package_count: None,
+                    packages: None,
                 });
             }
         }
@@ -364,19 +674,685 @@ impl ProjectDetector {
         Ok(Some((None, dependencies)))
     }
     
+    /// 检测 Terraform 项目（目录中有 .tf 文件）
+    async fn detect_terraform(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        if !Self::has_terraform_files(path) {
+            return Ok(None);
+        }
+        
+        let mut dependencies = Vec::new();
+        
+        // .terraform 保存 provider 插件和模块，.terragrunt-cache 保存 Terragrunt 的工作副本
+        for cache_dir in [".terraform", ".terragrunt-cache"] {
+            let cache_path = path.join(cache_dir);
+            if cache_path.is_dir() {
+                if let Ok(size) = self.calculate_dependency_directory_size(&cache_path).await {
+                    dependencies.push(DependencyInfo {
+                        dependency_type: DependencyType::Other(cache_dir.to_string()),
+                        path: cache_path,
+                        size,
+                        package_count: None,
+                        packages: None,
+                    });
+                }
+            }
+        }
+        
+        Ok(Some((None, dependencies)))
+    }
+    
+    /// 检测 Ansible 项目
+    async fn detect_ansible(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        let markers = ["playbook.yml", "playbook.yaml", "ansible.cfg"];
+        if !markers.iter().any(|marker| path.join(marker).is_file()) {
+            return Ok(None);
+        }
+        
+        Ok(Some((None, Vec::new())))
+    }
+    
+    /// 检测 Helm Chart
+    async fn detect_helm(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        let chart_yaml = path.join("Chart.yaml");
+        if !chart_yaml.is_file() {
+            return Ok(None);
+        }
+        
+        // 读取 Chart.yaml 顶层的 description 字段
+        let description = tokio::fs::read_to_string(&chart_yaml).await
+            .ok()
+            .and_then(|content| content.lines()
+                .find_map(|line| line.strip_prefix("description:"))
+                .map(|value| value.trim().trim_matches(|c| c == '"' || c == '\'').to_string()))
+            .filter(|description| !description.is_empty());
+        
+        let mut dependencies = Vec::new();
+        
+        // charts/ 中是 helm dependency update 下载的子 Chart 压缩包
+        let charts_dir = path.join("charts");
+        if charts_dir.is_dir() {
+            if let Ok(size) = self.calculate_dependency_directory_size(&charts_dir).await {
+                dependencies.push(DependencyInfo {
+                    dependency_type: DependencyType::Other("charts".to_string()),
+                    path: charts_dir,
+                    size,
+                    package_count: None,
+                    packages: None,
+                });
+            }
+        }
+        
+        Ok(Some((description, dependencies)))
+    }
+    
+    /// 检测 Nix 项目
+    async fn detect_nix(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        let markers = ["flake.nix", "default.nix", "shell.nix"];
+        if !markers.iter().any(|marker| path.join(marker).is_file()) {
+            return Ok(None);
+        }
+        
+        // nix build 生成的 result 链接会让 Nix store 中的构建产物无法被垃圾回收
+        let dependencies = Self::nix_result_links(path)
+            .into_iter()
+            .map(|link| DependencyInfo {
+                dependency_type: DependencyType::Other("result".to_string()),
+                path: link,
+                size: ByteSize::ZERO,
+                package_count: None,
+                packages: None,
+            })
+            .collect();
+        
+        Ok(Some((None, dependencies)))
+    }
+    
+    /// 检测 Android Gradle 项目，根目录和各模块的 build/、.cxx 目录作为依赖计入
+    async fn detect_android(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        if !Self::is_android_project(path) {
+            return Ok(None);
+        }
+        
+        let mut dependencies = Vec::new();
+        let root_build_dirs = ANDROID_BUILD_DIRS.iter().map(|name| path.join(name));
+        
+        for build_dir in root_build_dirs.chain(Self::android_module_build_dirs(path)) {
+            if !build_dir.is_dir() {
+                continue;
+            }
+            if let Ok(size) = self.calculate_dependency_directory_size(&build_dir).await {
+                let name = build_dir.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                dependencies.push(DependencyInfo {
+                    dependency_type: DependencyType::Other(name),
+                    path: build_dir,
+                    size,
+                    package_count: None,
+                    packages: None,
+                });
+            }
+        }
+        
+        Ok(Some((None, dependencies)))
+    }
+    
+    /// 只根据根目录下的标识文件判断项目类型（不分析依赖），用于清理等只需要类型的场景
+    pub fn marker_types(path: &Path) -> Vec<ProjectType> {
+        let has_any = |markers: &[&str]| markers.iter().any(|marker| path.join(marker).is_file());
+        let mut types = Vec::new();
+        
+        if has_any(&["package.json"]) {
+            types.push(ProjectType::NodeJs);
+        }
+        if has_any(&["Cargo.toml"]) {
+            types.push(ProjectType::Rust);
+        }
+        if has_any(&["requirements.txt", "pyproject.toml", "setup.py"]) {
+            types.push(ProjectType::Python);
+        }
+        if has_any(&["go.mod"]) {
+            types.push(ProjectType::Go);
+        }
+        if Self::is_android_project(path) {
+            types.push(ProjectType::Android);
+        } else if has_any(&["pom.xml", "build.gradle", "build.gradle.kts"]) {
+            types.push(ProjectType::Java);
+        }
+        if has_any(&["CMakeLists.txt"]) {
+            types.push(ProjectType::Cpp);
+        }
+        if Self::has_terraform_files(path) {
+            types.push(ProjectType::Terraform);
+        }
+        if Self::is_apple_project(path) {
+            types.push(ProjectType::Swift);
+        }
+        if Self::is_dotnet_project(path) {
+            types.push(ProjectType::DotNet);
+        }
+        if has_any(&["pubspec.yaml"]) {
+            types.push(ProjectType::Dart);
+        }
+        if has_any(&["composer.json"]) {
+            types.push(ProjectType::Php);
+        }
+        if has_any(&["mix.exs"]) {
+            types.push(ProjectType::Elixir);
+        }
+        if has_any(&["build.zig"]) {
+            types.push(ProjectType::Zig);
+        }
+        types.extend(Self::game_engine_type(path));
+        
+        types
+    }
+    
+    /// 判断目录是否是 Android 项目（有 settings.gradle，且某个模块包含 AndroidManifest.xml）
+    pub fn is_android_project(path: &Path) -> bool {
+        let has_settings = path.join("settings.gradle").is_file() || path.join("settings.gradle.kts").is_file();
+        has_settings && Self::gradle_modules(path).iter()
+            .any(|module| module.join("src").join("main").join("AndroidManifest.xml").is_file())
+    }
+    
+    /// 项目根目录下的 Gradle 模块（包含 build.gradle 或 build.gradle.kts 的子目录）
+    fn gradle_modules(path: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return Vec::new();
+        };
+        
+        let mut modules: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|dir| dir.join("build.gradle").is_file() || dir.join("build.gradle.kts").is_file())
+            .collect();
+        modules.sort();
+        modules
+    }
+    
+    /// Android 项目各模块中存在的 build/ 和 .cxx 目录（不含项目根目录），其他项目返回空列表
+    pub fn android_module_build_dirs(path: &Path) -> Vec<PathBuf> {
+        if !Self::is_android_project(path) {
+            return Vec::new();
+        }
+        
+        Self::gradle_modules(path).into_iter()
+            .flat_map(|module| ANDROID_BUILD_DIRS.iter().map(move |name| module.join(name)))
+            .filter(|dir| dir.is_dir())
+            .collect()
+    }
+    
+    /// 检测 .NET 项目（*.csproj、*.fsproj、*.vbproj 或 *.sln），根目录和各子项目的 bin/、obj/ 作为依赖计入
+    async fn detect_dotnet(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        if !Self::is_dotnet_project(path) {
+            return Ok(None);
+        }
+        
+        let mut dependencies = Vec::new();
+        for dir_path in Self::dotnet_build_dirs(path) {
+            if let Ok(size) = self.calculate_dependency_directory_size(&dir_path).await {
+                let name = dir_path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+                dependencies.push(DependencyInfo {
+                    dependency_type: DependencyType::Other(name),
+                    path: dir_path,
+                    size,
+                    package_count: None,
+                    packages: None,
+                });
+            }
+        }
+        
+        Ok(Some((None, dependencies)))
+    }
+    
+    /// 判断目录是否是 .NET 项目或解决方案的根目录
+    pub fn is_dotnet_project(path: &Path) -> bool {
+        Self::has_file_with_extension(path, &["sln"]) || Self::has_file_with_extension(path, DOTNET_PROJECT_EXTENSIONS)
+    }
+    
+    /// .NET 项目中存在的 bin/、obj/ 目录：根目录的，以及解决方案中各子项目目录（第一层）的，其他项目返回空列表
+    pub fn dotnet_build_dirs(path: &Path) -> Vec<PathBuf> {
+        if !Self::is_dotnet_project(path) {
+            return Vec::new();
+        }
+        
+        let mut project_dirs = vec![path.to_path_buf()];
+        if let Ok(entries) = std::fs::read_dir(path) {
+            let mut subprojects: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|dir| dir.is_dir() && Self::has_file_with_extension(dir, DOTNET_PROJECT_EXTENSIONS))
+                .collect();
+            subprojects.sort();
+            project_dirs.extend(subprojects);
+        }
+        
+        project_dirs.iter()
+            .flat_map(|dir| DOTNET_BUILD_DIRS.iter().map(move |name| dir.join(name)))
+            .filter(|dir| dir.is_dir())
+            .collect()
+    }
+    
+    /// 目录中是否有指定扩展名的文件
+    fn has_file_with_extension(path: &Path, extensions: &[&str]) -> bool {
+        std::fs::read_dir(path)
+            .map(|entries| entries
+                .filter_map(|entry| entry.ok())
+                .any(|entry| entry.path().extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| extensions.contains(&ext))
+                    && entry.file_type().is_ok_and(|file_type| file_type.is_file())))
+            .unwrap_or(false)
+    }
+    
+    /// 检测 Dart / Flutter 项目（pubspec.yaml），.dart_tool 和 build 目录作为依赖计入
+    async fn detect_dart(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        let pubspec = path.join("pubspec.yaml");
+        if !pubspec.is_file() {
+            return Ok(None);
+        }
+        
+        // 读取 pubspec.yaml 顶层的 description 字段
+        let description = tokio::fs::read_to_string(&pubspec).await
+            .ok()
+            .and_then(|content| content.lines()
+                .find_map(|line| line.strip_prefix("description:"))
+                .map(|value| value.trim().trim_matches(|c| c == '"' || c == '\'').to_string()))
+            .filter(|description| !description.is_empty());
+        
+        Ok(Some((description, self.existing_dependency_dirs(path, DART_DEPENDENCY_DIRS).await)))
+    }
+    
+    /// 检测 PHP Composer 项目（composer.json），vendor 目录作为依赖计入
+    async fn detect_php(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        let composer_json = path.join("composer.json");
+        if !composer_json.is_file() {
+            return Ok(None);
+        }
+        
+        let description = tokio::fs::read_to_string(&composer_json).await
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| json.get("description").and_then(|d| d.as_str()).map(|s| s.to_string()))
+            .filter(|description| !description.is_empty());
+        
+        Ok(Some((description, self.existing_dependency_dirs(path, &["vendor"]).await)))
+    }
+    
+    /// 检测 Elixir 项目（mix.exs），_build 和 deps 目录作为依赖计入
+    async fn detect_elixir(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        if !path.join("mix.exs").is_file() {
+            return Ok(None);
+        }
+        
+        Ok(Some((None, self.existing_dependency_dirs(path, ELIXIR_DEPENDENCY_DIRS).await)))
+    }
+    
+    /// 检测 Zig 项目（build.zig），构建缓存和 zig-out 目录作为依赖计入
+    async fn detect_zig(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        if !path.join("build.zig").is_file() {
+            return Ok(None);
+        }
+        
+        Ok(Some((None, self.existing_dependency_dirs(path, ZIG_DEPENDENCY_DIRS).await)))
+    }
+    
+    /// 项目根目录下存在的依赖目录及其大小
+    async fn existing_dependency_dirs(&self, path: &Path, dir_names: &[&str]) -> Vec<DependencyInfo> {
+        let mut dependencies = Vec::new();
+        for dir_name in dir_names {
+            let dir_path = path.join(dir_name);
+            if !dir_path.is_dir() {
+                continue;
+            }
+            if let Ok(size) = self.calculate_dependency_directory_size(&dir_path).await {
+                dependencies.push(DependencyInfo {
+                    dependency_type: DependencyType::Other(dir_name.to_string()),
+                    path: dir_path,
+                    size,
+                    package_count: None,
+                    packages: None,
+                });
+            }
+        }
+        dependencies
+    }
+    
+    /// 检测 Xcode / Swift 项目，Pods、Carthage、.build 和 DerivedData 中的构建目录作为依赖计入
+    async fn detect_swift(&self, path: &Path) -> Result<Option<(Option<String>, Vec<DependencyInfo>)>> {
+        if !Self::is_apple_project(path) {
+            return Ok(None);
+        }
+        
+        let mut dependencies = Vec::new();
+        
+        for dir_name in APPLE_DEPENDENCY_DIRS {
+            let dir_path = path.join(dir_name);
+            if !dir_path.is_dir() {
+                continue;
+            }
+            if let Ok(size) = self.calculate_dependency_directory_size(&dir_path).await {
+                dependencies.push(DependencyInfo {
+                    dependency_type: Self::apple_dependency_type(dir_name),
+                    path: dir_path,
+                    size,
+                    package_count: None,
+                    packages: None,
+                });
+            }
+        }
+        
+        for derived_data in Self::xcode_derived_data_dirs(path) {
+            if let Ok(size) = self.calculate_dependency_directory_size(&derived_data).await {
+                dependencies.push(DependencyInfo {
+                    dependency_type: DependencyType::XcodeDerivedData,
+                    path: derived_data,
+                    size,
+                    package_count: None,
+                    packages: None,
+                });
+            }
+        }
+        
+        Ok(Some((None, dependencies)))
+    }
+    
+    /// 判断目录是否是 Xcode / Swift 项目（xcodeproj、xcworkspace、Package.swift、Podfile 或 Cartfile）
+    pub fn is_apple_project(path: &Path) -> bool {
+        if ["Package.swift", "Podfile", "Cartfile"].iter().any(|marker| path.join(marker).is_file()) {
+            return true;
+        }
+        
+        std::fs::read_dir(path)
+            .map(|entries| entries
+                .filter_map(|entry| entry.ok())
+                .any(|entry| entry.path().extension()
+                    .is_some_and(|ext| ext == "xcodeproj" || ext == "xcworkspace")))
+            .unwrap_or(false)
+    }
+    
+    /// Xcode / Swift 项目的依赖目录名，其他项目返回空列表
+    pub fn apple_dependency_dir_names(path: &Path) -> &'static [&'static str] {
+        if Self::is_apple_project(path) {
+            APPLE_DEPENDENCY_DIRS
+        } else {
+            &[]
+        }
+    }
+    
+    /// 判断目录是否是 Xcode / Swift 项目根目录下的依赖目录
+    pub fn is_apple_dependency_dir(dir: &Path) -> bool {
+        dir.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| APPLE_DEPENDENCY_DIRS.contains(&name))
+            && dir.parent().is_some_and(Self::is_apple_project)
+    }
+    
+    /// 依赖目录名对应的依赖类型
+    fn apple_dependency_type(dir_name: &str) -> DependencyType {
+        match dir_name {
+            "Pods" => DependencyType::CocoaPods,
+            "Carthage" => DependencyType::Carthage,
+            other => DependencyType::Other(other.to_string()),
+        }
+    }
+    
+    /// 查找 ~/Library/Developer/Xcode/DerivedData 中属于该项目的构建目录
+    pub fn xcode_derived_data_dirs(path: &Path) -> Vec<PathBuf> {
+        dirs::home_dir()
+            .map(|home| home.join("Library").join("Developer").join("Xcode").join("DerivedData"))
+            .map(|root| Self::derived_data_dirs_in(&root, path))
+            .unwrap_or_default()
+    }
+    
+    /// 在指定的 DerivedData 目录中查找属于项目的构建目录
+    ///
+    /// Xcode 为每个工程创建 `<工程名>-<哈希>` 目录，并在其 info.plist 的 WorkspacePath 中记录工程路径。
+    pub fn derived_data_dirs_in(derived_data_root: &Path, path: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(derived_data_root) else {
+            return Vec::new();
+        };
+        
+        let mut dirs: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|dir| dir.is_dir())
+            .filter(|dir| Self::derived_data_workspace_path(dir)
+                .is_some_and(|workspace| workspace.parent() == Some(path)))
+            .collect();
+        dirs.sort();
+        dirs
+    }
+    
+    /// 读取 DerivedData 子目录 info.plist 中的 WorkspacePath
+    fn derived_data_workspace_path(dir: &Path) -> Option<PathBuf> {
+        let content = std::fs::read_to_string(dir.join("info.plist")).ok()?;
+        let after_key = &content[content.find("<key>WorkspacePath</key>")?..];
+        let start = after_key.find("<string>")? + "<string>".len();
+        let end = after_key[start..].find("</string>")? + start;
+        Some(PathBuf::from(&after_key[start..end]))
+    }
+    
+    /// 检测配置中的自定义项目类型：根目录中存在任一标识文件即匹配，存在的依赖目录作为依赖计入
+    async fn detect_custom(&self, path: &Path) -> Result<Vec<(ProjectType, Vec<DependencyInfo>)>> {
+        if self.custom_types.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let names: Vec<String> = std::fs::read_dir(path)?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        
+        let mut detected = Vec::new();
+        for custom in &self.custom_types {
+            let matched = custom.markers.iter()
+                .any(|marker| names.iter().any(|name| wildcard_match(marker, name)));
+            if !matched {
+                continue;
+            }
+            
+            let mut dependencies = Vec::new();
+            for dir in custom.dependency_dir_names() {
+                let dir_path = path.join(dir);
+                if dir_path.is_dir() {
+                    if let Ok(size) = self.calculate_dependency_directory_size(&dir_path).await {
+                        dependencies.push(DependencyInfo {
+                            dependency_type: DependencyType::Other(dir.to_string()),
+                            path: dir_path,
+                            size,
+                            package_count: None,
+                            packages: None,
+                        });
+                    }
+                }
+            }
+            
+            let project_type = ProjectType::Custom { name: custom.name.clone(), icon: custom.icon.clone() };
+            detected.push((project_type, dependencies));
+        }
+        
+        Ok(detected)
+    }
+    
+    /// 检测 Unity / Unreal 项目，缓存目录作为依赖计入
+    async fn detect_game_engine(&self, path: &Path) -> Result<Option<(ProjectType, Option<String>, Vec<DependencyInfo>)>> {
+        let Some(game_type) = Self::game_engine_type(path) else {
+            return Ok(None);
+        };
+        
+        let description = match game_type {
+            ProjectType::Unity => Self::unity_version(path).map(|version| format!("Unity {}", version)),
+            _ => Self::find_uproject(path).and_then(|uproject| Self::unreal_description(&uproject)),
+        };
+        
+        let mut dependencies = Vec::new();
+        for cache_dir in Self::game_engine_cache_dir_names(path) {
+            let cache_path = path.join(cache_dir);
+            if !cache_path.is_dir() {
+                continue;
+            }
+            if let Ok(size) = self.calculate_dependency_directory_size(&cache_path).await {
+                let dependency_type = match game_type {
+                    ProjectType::Unity => DependencyType::UnityCache(cache_dir.to_string()),
+                    _ => DependencyType::UnrealCache(cache_dir.to_string()),
+                };
+                dependencies.push(DependencyInfo {
+                    dependency_type,
+                    path: cache_path,
+                    size,
+                    package_count: None,
+                    packages: None,
+                });
+            }
+        }
+        
+        Ok(Some((game_type, description, dependencies)))
+    }
+    
+    /// 判断目录是否是 Unity 或 Unreal 项目
+    pub fn game_engine_type(path: &Path) -> Option<ProjectType> {
+        if path.join("Assets").is_dir() && path.join("ProjectSettings").join("ProjectVersion.txt").is_file() {
+            Some(ProjectType::Unity)
+        } else if Self::find_uproject(path).is_some() {
+            Some(ProjectType::Unreal)
+        } else {
+            None
+        }
+    }
+    
+    /// 游戏引擎项目中可重新生成的缓存目录名，其他项目返回空列表
+    pub fn game_engine_cache_dir_names(path: &Path) -> &'static [&'static str] {
+        match Self::game_engine_type(path) {
+            Some(ProjectType::Unity) => UNITY_CACHE_DIRS,
+            Some(ProjectType::Unreal) => UNREAL_CACHE_DIRS,
+            _ => &[],
+        }
+    }
+    
+    /// 判断目录是否是游戏引擎项目根目录下的缓存目录，返回对应的依赖类型
+    pub fn game_engine_cache_type(dir: &Path) -> Option<DependencyType> {
+        let name = dir.file_name()?.to_str()?;
+        // 先按目录名过滤，避免对每个目录都检查上级目录
+        if !UNITY_CACHE_DIRS.contains(&name) && !UNREAL_CACHE_DIRS.contains(&name) {
+            return None;
+        }
+        
+        match Self::game_engine_type(dir.parent()?)? {
+            ProjectType::Unity if UNITY_CACHE_DIRS.contains(&name) => Some(DependencyType::UnityCache(name.to_string())),
+            ProjectType::Unreal if UNREAL_CACHE_DIRS.contains(&name) => Some(DependencyType::UnrealCache(name.to_string())),
+            _ => None,
+        }
+    }
+    
+    /// 查找目录中的 Unreal 项目文件（*.uproject）
+    fn find_uproject(path: &Path) -> Option<PathBuf> {
+        std::fs::read_dir(path).ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .find(|file| file.extension().is_some_and(|ext| ext == "uproject") && file.is_file())
+    }
+    
+    /// 从 ProjectSettings/ProjectVersion.txt 读取 Unity 编辑器版本
+    fn unity_version(path: &Path) -> Option<String> {
+        let content = std::fs::read_to_string(path.join("ProjectSettings").join("ProjectVersion.txt")).ok()?;
+        content.lines()
+            .find_map(|line| line.strip_prefix("m_EditorVersion:"))
+            .map(|version| version.trim().to_string())
+    }
+    
+    /// 从 .uproject 读取描述，没有描述时使用引擎版本
+    fn unreal_description(uproject: &Path) -> Option<String> {
+        let content = std::fs::read_to_string(uproject).ok()?;
+        let json = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+        
+        json.get("Description")
+            .and_then(|d| d.as_str())
+            .filter(|d| !d.is_empty())
+            .map(|d| d.to_string())
+            .or_else(|| json.get("EngineAssociation")
+                .and_then(|e| e.as_str())
+                .filter(|e| !e.is_empty())
+                .map(|e| format!("Unreal Engine {}", e)))
+    }
+    
+    /// 读取 README 开头的第一个标题（`# 标题` 或下一行为 `===`/`---` 的标题）
+    fn readme_title(path: &Path) -> Option<String> {
+        use std::io::Read;
+        
+        let file = README_FILE_NAMES.iter()
+            .find_map(|name| std::fs::File::open(path.join(name)).ok())?;
+        let mut bytes = Vec::new();
+        file.take(README_PEEK_BYTES).read_to_end(&mut bytes).ok()?;
+        
+        let content = String::from_utf8_lossy(&bytes);
+        let mut lines: Vec<&str> = content.lines().collect();
+        // 读满时最后一行可能被截断
+        if bytes.len() as u64 == README_PEEK_BYTES {
+            lines.pop();
+        }
+        // 跳过 YAML front matter
+        if lines.first().is_some_and(|line| line.trim() == "---") {
+            let end = lines.iter().skip(1).position(|line| line.trim() == "---").map_or(lines.len(), |i| i + 2);
+            lines.drain(..end);
+        }
+        
+        let title = lines.iter().enumerate().find_map(|(i, line)| {
+            let line = line.trim();
+            if let Some(heading) = line.strip_prefix('#') {
+                let heading = heading.trim_start_matches('#');
+                return heading.starts_with([' ', '\t']).then(|| heading.trim().trim_end_matches('#').trim());
+            }
+            let underline = lines.get(i + 1).map(|next| next.trim()).unwrap_or_default();
+            let is_setext = !line.is_empty()
+                && !underline.is_empty()
+                && (underline.chars().all(|c| c == '=') || underline.chars().all(|c| c == '-'));
+            is_setext.then_some(line)
+        })?;
+        
+        Some(title.to_string()).filter(|title| !title.is_empty())
+    }
+    
+    /// 检查目录中是否有 Terraform 配置文件
+    pub fn has_terraform_files(path: &Path) -> bool {
+        std::fs::read_dir(path)
+            .map(|entries| entries
+                .filter_map(|entry| entry.ok())
+                .any(|entry| entry.path().extension().is_some_and(|ext| ext == "tf")
+                    && entry.file_type().is_ok_and(|file_type| file_type.is_file())))
+            .unwrap_or(false)
+    }
+    
+    /// 列出项目根目录下 nix build 生成的 result 符号链接（result、result-dev 等）
+    pub fn nix_result_links(path: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return Vec::new();
+        };
+        
+        let mut links: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_symlink()))
+            .filter(|entry| entry.file_name().to_str()
+                .is_some_and(|name| name == "result" || name.starts_with("result-")))
+            .map(|entry| entry.path())
+            .collect();
+        links.sort();
+        links
+    }
+    
     /// 计算依赖目录的大小（不跳过任何子目录）
-    fn calculate_dependency_directory_size<'a>(&'a self, path: &'a Path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<u64>> + Send + 'a>> {
+    fn calculate_dependency_directory_size<'a>(&'a self, path: &'a Path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<ByteSize>> + Send + 'a>> {
         Box::pin(async move {
-            let mut total_size = 0;
+            let mut total_size = ByteSize::ZERO;
             let mut entries = tokio::fs::read_dir(path).await?;
             
             while let Some(entry) = entries.next_entry().await? {
                 let metadata = entry.metadata().await?;
                 if metadata.is_file() {
-                    total_size += metadata.len();
+                    total_size += ByteSize::new(metadata.len());
                 } else if metadata.is_dir() {
                     // 递归计算所有子目录大小
-                    total_size += self.calculate_dependency_directory_size(&entry.path()).await.unwrap_or(0);
+                    total_size += self.calculate_dependency_directory_size(&entry.path()).await.unwrap_or_default();
                 }
             }
             
@@ -410,7 +1386,9 @@ impl ProjectDetector {
                         "vendor" | "bower_components" |
                         ".sass-cache" | ".cache" |
                         "coverage" | ".nyc_output" |
-                        ".next" | ".nuxt" | ".parcel-cache"
+                        ".next" | ".nuxt" | ".parcel-cache" |
+                        ".terraform" | ".terragrunt-cache" |
+                        "Pods" | "Carthage" | ".build" | ".cxx"
                     ) {
                         // 对于这些目录，跳过以避免递归计算导致性能问题
                         continue;
@@ -519,4 +1497,284 @@ impl ProjectDetector {
         
         Ok(false)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_infer_type_from_extensions() {
+        let temp_dir = tempdir().unwrap();
+        let src = temp_dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("main.c"), "int main() {}").unwrap();
+        fs::write(src.join("util.c"), "").unwrap();
+        fs::write(src.join("util.h"), "").unwrap();
+        fs::write(temp_dir.path().join("build.py"), "").unwrap();
+
+        // 依赖和隐藏目录中的文件不参与统计
+        let vendor = temp_dir.path().join("node_modules").join("pkg");
+        fs::create_dir_all(&vendor).unwrap();
+        for i in 0..10 {
+            fs::write(vendor.join(format!("{}.js", i)), "").unwrap();
+        }
+
+        assert_eq!(ProjectDetector::infer_type_from_extensions(temp_dir.path()), Some(ProjectType::Cpp));
+    }
+
+    #[tokio::test]
+    async fn test_git_repo_without_manifest_uses_heuristics() {
+        let temp_dir = tempdir().unwrap();
+        fs::create_dir(temp_dir.path().join(".git")).unwrap();
+        fs::write(temp_dir.path().join("app.py"), "print('hi')").unwrap();
+
+        let detected = ProjectDetector::new().detect_project(temp_dir.path()).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Python);
+
+        fs::remove_file(temp_dir.path().join("app.py")).unwrap();
+        let detected = ProjectDetector::new().detect_project(temp_dir.path()).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Git);
+    }
+
+    #[tokio::test]
+    async fn test_readme_title_as_description() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("test2");
+        fs::create_dir_all(project.join(".git")).unwrap();
+        fs::write(project.join("README.md"), "<img src=\"logo.png\">\n\n## Invoice Parser ##\n\nText\n").unwrap();
+
+        let detected = ProjectDetector::new().detect_project(&project).await.unwrap().unwrap();
+        assert_eq!(detected.description.as_deref(), Some("Invoice Parser"));
+
+        // 清单中的描述优先
+        fs::write(project.join("package.json"), r#"{"name": "x", "description": "From manifest"}"#).unwrap();
+        let detected = ProjectDetector::new().detect_project(&project).await.unwrap().unwrap();
+        assert_eq!(detected.description.as_deref(), Some("From manifest"));
+
+        let setext = temp_dir.path().join("setext");
+        fs::create_dir_all(&setext).unwrap();
+        fs::write(setext.join("README"), "---\nlayout: page\n---\nWeather Bot\n===========\n").unwrap();
+        assert_eq!(ProjectDetector::readme_title(&setext).as_deref(), Some("Weather Bot"));
+
+        // 只读取开头，之后的标题不会被使用
+        fs::write(setext.join("README"), format!("{}\n# Late Title\n", "text ".repeat(300))).unwrap();
+        assert_eq!(ProjectDetector::readme_title(&setext), None);
+    }
+
+    #[tokio::test]
+    async fn test_detect_infra_projects() {
+        let temp_dir = tempdir().unwrap();
+        let infra = temp_dir.path().join("infra");
+        fs::create_dir_all(infra.join(".terraform").join("providers")).unwrap();
+        fs::write(infra.join("main.tf"), "terraform {}").unwrap();
+        fs::write(infra.join(".terraform").join("providers").join("aws"), "x".repeat(100)).unwrap();
+
+        let detected = ProjectDetector::new().detect_project(&infra).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Terraform);
+        assert_eq!(detected.dependencies.len(), 1);
+        assert_eq!(detected.dependencies[0].size, ByteSize::new(100));
+
+        let chart = temp_dir.path().join("chart");
+        fs::create_dir(&chart).unwrap();
+        fs::write(chart.join("Chart.yaml"), "apiVersion: v2\nname: web\ndescription: \"Web frontend\"\n").unwrap();
+        let detected = ProjectDetector::new().detect_project(&chart).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Helm);
+        assert_eq!(detected.description.as_deref(), Some("Web frontend"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_more_ecosystems() {
+        let temp_dir = tempdir().unwrap();
+        let write = |path: PathBuf, content: &str| {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+
+        // 解决方案根目录和子项目的 bin/、obj/ 都计入
+        let solution = temp_dir.path().join("shop");
+        write(solution.join("Shop.sln"), "");
+        write(solution.join("Shop.Api").join("Shop.Api.csproj"), "<Project />");
+        write(solution.join("Shop.Api").join("bin").join("Shop.Api.dll"), &"x".repeat(100));
+        write(solution.join("Shop.Api").join("obj").join("project.assets.json"), &"x".repeat(50));
+        let detected = ProjectDetector::new().detect_project(&solution).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::DotNet);
+        assert_eq!(detected.dependencies.iter().map(|d| d.size).sum::<ByteSize>(), ByteSize::new(150));
+
+        let cases = [
+            ("app", "pubspec.yaml", "name: app\ndescription: \"Flutter app\"\n", ".dart_tool", ProjectType::Dart),
+            ("site", "composer.json", r#"{"description": "Blog engine"}"#, "vendor", ProjectType::Php),
+            ("api", "mix.exs", "defmodule Api.MixProject do\nend\n", "deps", ProjectType::Elixir),
+            ("tool", "build.zig", "const std = @import(\"std\");\n", ".zig-cache", ProjectType::Zig),
+        ];
+        for (name, marker, content, dependency_dir, project_type) in cases {
+            let project = temp_dir.path().join(name);
+            write(project.join(marker), content);
+            write(project.join(dependency_dir).join("artifact"), &"x".repeat(10));
+
+            let detected = ProjectDetector::new().detect_project(&project).await.unwrap().unwrap();
+            assert_eq!(detected.project_type, project_type);
+            assert_eq!(detected.dependencies.len(), 1);
+            assert_eq!(detected.dependencies[0].path, project.join(dependency_dir));
+            assert_eq!(ProjectDetector::marker_types(&project), [project_type]);
+        }
+        let app = ProjectDetector::new().detect_project(&temp_dir.path().join("app")).await.unwrap().unwrap();
+        assert_eq!(app.description.as_deref(), Some("Flutter app"));
+    }
+
+    #[tokio::test]
+    async fn test_detect_custom_project_type() {
+        let temp_dir = tempdir().unwrap();
+        let game = temp_dir.path().join("game");
+        fs::create_dir_all(game.join(".godot").join("imported")).unwrap();
+        fs::write(game.join("project.godot"), "config_version=5").unwrap();
+        fs::write(game.join(".godot").join("imported").join("icon.ctex"), "x".repeat(200)).unwrap();
+
+        let custom = CustomProjectType {
+            name: "Godot".to_string(),
+            markers: vec!["*.godot".to_string()],
+            icon: "🤖".to_string(),
+            dependency_dirs: vec![".godot/".to_string(), "addons/".to_string()],
+        };
+        let detector = ProjectDetector::new().with_custom_types(vec![custom]);
+        let detected = detector.detect_project(&game).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Custom { name: "Godot".to_string(), icon: "🤖".to_string() });
+        assert_eq!(detected.project_type.as_str(), "Godot");
+        assert_eq!(detected.dependencies.len(), 1);
+        assert_eq!(detected.dependencies[0].path, game.join(".godot"));
+        assert_eq!(detected.dependencies[0].size, ByteSize::new(200));
+
+        // 没有配置自定义类型时不是项目
+        assert!(ProjectDetector::new().detect_project(&game).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_is_trivial_project() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("package.json"), "{}").unwrap();
+        assert!(ProjectDetector::is_trivial_project(temp_dir.path(), 1024));
+        assert!(!ProjectDetector::is_trivial_project(temp_dir.path(), 0));
+
+        // 超过阈值的项目不是琐碎项目
+        fs::write(temp_dir.path().join("data.bin"), "x".repeat(2048)).unwrap();
+        assert!(!ProjectDetector::is_trivial_project(temp_dir.path(), 1024));
+
+        // 有提交历史的小项目也不是琐碎项目
+        let repo_dir = tempdir().unwrap();
+        let repo = git2::Repository::init(repo_dir.path()).unwrap();
+        assert!(ProjectDetector::is_trivial_project(repo_dir.path(), 1 << 20));
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        assert!(!ProjectDetector::is_trivial_project(repo_dir.path(), 1 << 20));
+    }
+
+    #[tokio::test]
+    async fn test_detect_android_project() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path();
+        fs::write(project.join("settings.gradle.kts"), "include(\":app\")").unwrap();
+        fs::write(project.join("build.gradle.kts"), "").unwrap();
+        fs::create_dir_all(project.join("app").join("src").join("main")).unwrap();
+        fs::write(project.join("app").join("build.gradle.kts"), "").unwrap();
+        fs::write(project.join("app").join("src").join("main").join("AndroidManifest.xml"), "<manifest/>").unwrap();
+        fs::create_dir_all(project.join("app").join("build")).unwrap();
+        fs::write(project.join("app").join("build").join("app.apk"), "x".repeat(100)).unwrap();
+        fs::create_dir_all(project.join("app").join(".cxx")).unwrap();
+        fs::write(project.join("app").join(".cxx").join("libnative.so"), "x".repeat(50)).unwrap();
+
+        let detected = ProjectDetector::new().detect_project(project).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Android);
+        assert_eq!(detected.dependencies.iter().map(|d| d.size).sum::<ByteSize>(), ByteSize::new(150));
+        assert_eq!(
+            ProjectDetector::android_module_build_dirs(project),
+            vec![project.join("app").join("build"), project.join("app").join(".cxx")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_detect_xcode_project() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("MyApp");
+        fs::create_dir_all(project.join("MyApp.xcodeproj")).unwrap();
+        fs::write(project.join("Podfile"), "platform :ios").unwrap();
+        fs::create_dir_all(project.join("Pods").join("Alamofire")).unwrap();
+        fs::write(project.join("Pods").join("Alamofire").join("lib.swift"), "x".repeat(100)).unwrap();
+
+        let detected = ProjectDetector::new().detect_project(&project).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Swift);
+        assert!(detected.dependencies.iter()
+            .any(|d| matches!(d.dependency_type, DependencyType::CocoaPods) && d.size == ByteSize::new(100)));
+        assert!(ProjectDetector::is_apple_dependency_dir(&project.join("Pods")));
+        assert!(!ProjectDetector::is_apple_dependency_dir(&temp_dir.path().join("Pods")));
+
+        // DerivedData 中只匹配 WorkspacePath 指向该项目的目录
+        let derived_data = temp_dir.path().join("DerivedData");
+        let plist = |workspace: &Path| format!(
+            "<plist><dict><key>WorkspacePath</key>\n\t<string>{}</string></dict></plist>",
+            workspace.display()
+        );
+        let own = derived_data.join("MyApp-abcdef");
+        fs::create_dir_all(&own).unwrap();
+        fs::write(own.join("info.plist"), plist(&project.join("MyApp.xcodeproj"))).unwrap();
+        let other = derived_data.join("MyApp-123456");
+        fs::create_dir_all(&other).unwrap();
+        fs::write(other.join("info.plist"), plist(&temp_dir.path().join("Other").join("MyApp.xcodeproj"))).unwrap();
+
+        assert_eq!(ProjectDetector::derived_data_dirs_in(&derived_data, &project), vec![own]);
+    }
+
+    #[tokio::test]
+    async fn test_detect_game_engine_projects() {
+        let temp_dir = tempdir().unwrap();
+
+        let unity = temp_dir.path().join("unity-game");
+        fs::create_dir_all(unity.join("Assets")).unwrap();
+        fs::create_dir_all(unity.join("ProjectSettings")).unwrap();
+        fs::write(unity.join("ProjectSettings").join("ProjectVersion.txt"), "m_EditorVersion: 2022.3.10f1\n").unwrap();
+        fs::create_dir_all(unity.join("Library").join("Artifacts")).unwrap();
+        fs::write(unity.join("Library").join("Artifacts").join("asset"), "x".repeat(100)).unwrap();
+
+        let detected = ProjectDetector::new().detect_project(&unity).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Unity);
+        assert_eq!(detected.description.as_deref(), Some("Unity 2022.3.10f1"));
+        assert_eq!(detected.dependencies.len(), 1);
+        assert_eq!(detected.dependencies[0].size, ByteSize::new(100));
+        assert!(detected.dependencies[0].dependency_type.regeneration_warning().is_some());
+
+        let unreal = temp_dir.path().join("unreal-game");
+        fs::create_dir_all(unreal.join("DerivedDataCache")).unwrap();
+        fs::write(unreal.join("Shooter.uproject"), r#"{"EngineAssociation": "5.3"}"#).unwrap();
+
+        let detected = ProjectDetector::new().detect_project(&unreal).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Unreal);
+        assert_eq!(detected.description.as_deref(), Some("Unreal Engine 5.3"));
+        assert!(ProjectDetector::game_engine_cache_type(&unreal.join("DerivedDataCache")).is_some());
+        assert!(ProjectDetector::game_engine_cache_type(&unreal.join("Library")).is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_detect_nix_result_links() {
+        let temp_dir = tempdir().unwrap();
+        let store_path = temp_dir.path().join("store-output");
+        fs::create_dir(&store_path).unwrap();
+        let project = temp_dir.path().join("flake");
+        fs::create_dir(&project).unwrap();
+        fs::write(project.join("flake.nix"), "{}").unwrap();
+        std::os::unix::fs::symlink(&store_path, project.join("result")).unwrap();
+        std::os::unix::fs::symlink(&store_path, project.join("result-dev")).unwrap();
+        // 普通目录不算 result 链接
+        fs::create_dir(project.join("results")).unwrap();
+
+        let detected = ProjectDetector::new().detect_project(&project).await.unwrap().unwrap();
+        assert_eq!(detected.project_type, ProjectType::Nix);
+        assert_eq!(
+            ProjectDetector::nix_result_links(&project),
+            vec![project.join("result"), project.join("result-dev")]
+        );
+        assert_eq!(detected.dependencies.len(), 2);
+    }
+}
@@ -1,24 +1,102 @@
 #![allow(dead_code)]
 
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, Duration};
 use serde::{Serialize, Deserialize};
-use anyhow::Result;
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, ErrorCode, OptionalExtension};
 use tokio::fs;
 
+use crate::config::settings::CacheValidation;
+use crate::models::ByteSize;
+use crate::scanner::fingerprint;
+use crate::utils::metrics::metrics;
+
 /// 项目大小缓存管理器
+///
+/// 缓存保存在 SQLite 数据库中：每次写入只更新一条记录，多个进程同时扫描时由 SQLite 的文件锁协调。
 pub struct SizeCache {
-    /// 缓存文件路径
+    /// 缓存数据库路径
     cache_file: PathBuf,
     
-    /// 内存中的缓存数据
-    cache_data: CacheData,
+    /// 数据库连接（写入在阻塞线程池中执行，因此可以在线程间共享）
+    connection: Arc<Mutex<Connection>>,
     
     /// 缓存配置
     config: CacheConfig,
+    
+    /// 加载时缓存文件已损坏并完成恢复的结果
+    recovery: Option<CacheRecovery>,
+}
+
+/// 决定缓存是否有效的关键文件：它们的修改时间与项目目录一起比较
+const MANIFEST_FILES: &[&str] = &[
+    ".gitignore", "Cargo.toml", "package.json", "requirements.txt", "go.mod", "pom.xml",
+];
+
+/// 两次轮换备份之间的最短间隔：缓存会被频繁加载，每次都轮换会让所有备份变得相同
+const BACKUP_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 其他进程正在写入缓存时最多等待的时间
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 缓存数据库的表结构
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS entries (
+        project_path TEXT PRIMARY KEY,
+        code_size INTEGER NOT NULL,
+        dependency_size INTEGER NOT NULL,
+        total_size INTEGER NOT NULL,
+        gitignore_excluded_size INTEGER NOT NULL,
+        code_file_count INTEGER NOT NULL,
+        dependency_file_count INTEGER NOT NULL,
+        total_file_count INTEGER NOT NULL,
+        gitignore_excluded_file_count INTEGER NOT NULL,
+        size_last_modified INTEGER,
+        truncated INTEGER NOT NULL,
+        created_at INTEGER NOT NULL,
+        last_modified INTEGER NOT NULL,
+        is_git_repo INTEGER NOT NULL,
+        manifest_hashes TEXT NOT NULL,
+        fingerprint TEXT
+    );
+    CREATE INDEX IF NOT EXISTS entries_created_at ON entries (created_at);
+    CREATE TABLE IF NOT EXISTS metadata (
+        key TEXT PRIMARY KEY,
+        value INTEGER NOT NULL
+    );
+";
+
+/// 读取一条缓存记录的查询，列顺序与 `CacheEntry::from_row` 一致
+const SELECT_ENTRY: &str = "
+    SELECT project_path, code_size, dependency_size, total_size, gitignore_excluded_size,
+           code_file_count, dependency_file_count, total_file_count, gitignore_excluded_file_count,
+           size_last_modified, truncated, created_at, last_modified, is_git_repo, manifest_hashes, fingerprint
+    FROM entries WHERE project_path = ?1
+";
+
+/// 写入一条缓存记录的语句，参数顺序与 `CacheEntry::write` 一致；`$conflict` 决定已有同一项目的记录时的处理方式
+macro_rules! insert_entry {
+    ($conflict:literal) => {
+        concat!(
+            "INSERT OR ", $conflict, " INTO entries (
+                project_path, code_size, dependency_size, total_size, gitignore_excluded_size,
+                code_file_count, dependency_file_count, total_file_count, gitignore_excluded_file_count,
+                size_last_modified, truncated, created_at, last_modified, is_git_repo, manifest_hashes, fingerprint
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"
+        )
+    };
 }
 
+/// 写入一条缓存记录，覆盖同一项目已有的记录
+const UPSERT_ENTRY: &str = insert_entry!("REPLACE");
+
+/// 导入一条缓存记录，保留同一项目已有的记录
+const IMPORT_ENTRY: &str = insert_entry!("IGNORE");
+
 /// 缓存配置
 #[derive(Debug, Clone)]
 pub struct CacheConfig {
@@ -30,6 +108,15 @@ pub struct CacheConfig {
     
     /// 是否启用缓存
     pub enabled: bool,
+    
+    /// 保留的轮换备份数量（默认 3）
+    pub backup_count: usize,
+    
+    /// 是否记录关键文件的内容摘要：修改时间变化但内容未变时缓存仍然有效（默认关闭）
+    pub hash_manifests: bool,
+    
+    /// 判断缓存是否有效的方式（默认比较修改时间）
+    pub validation: CacheValidation,
 }
 
 impl Default for CacheConfig {
@@ -38,11 +125,65 @@ impl Default for CacheConfig {
             expiry_duration: Duration::from_secs(24 * 60 * 60), // 24 小时
             max_entries: 1000,
             enabled: true,
+            backup_count: 3,
+            hash_manifests: false,
+            validation: CacheValidation::Mtime,
         }
     }
 }
 
-/// 缓存数据结构
+/// 损坏缓存的数据来源
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecoverySource {
+    /// 从损坏文件中读出了可解析的条目
+    Partial,
+    
+    /// 从轮换备份恢复
+    Backup(PathBuf),
+    
+    /// 没有可恢复的数据，缓存已重建为空
+    Empty,
+}
+
+/// 缓存文件损坏后的恢复结果
+#[derive(Debug, Clone)]
+pub struct CacheRecovery {
+    /// 数据来源
+    pub source: RecoverySource,
+    
+    /// 恢复的条目数
+    pub recovered_entries: usize,
+    
+    /// 无法解析而丢弃的条目数（仅部分恢复时已知）
+    pub discarded_entries: usize,
+    
+    /// 损坏文件的副本
+    pub corrupt_copy: PathBuf,
+}
+
+impl fmt::Display for CacheRecovery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "大小缓存文件已损坏，")?;
+        match &self.source {
+            RecoverySource::Partial => write!(
+                f,
+                "已恢复 {} 条记录、丢弃 {} 条无法解析的记录",
+                self.recovered_entries,
+                self.discarded_entries
+            )?,
+            RecoverySource::Backup(backup) => write!(
+                f,
+                "已从备份 {} 恢复 {} 条记录",
+                backup.display(),
+                self.recovered_entries
+            )?,
+            RecoverySource::Empty => write!(f, "没有可用的备份，缓存已重建")?,
+        }
+        write!(f, "（损坏的文件保存在 {}）", self.corrupt_copy.display())
+    }
+}
+
+/// 旧版本 JSON 缓存文件的数据结构，只用于迁移
 #[derive(Debug, Serialize, Deserialize, Default)]
 struct CacheData {
     /// 缓存条目
@@ -69,22 +210,81 @@ struct CacheEntry {
     
     /// 是否是 Git 项目
     is_git_repo: bool,
+    
+    /// 关键文件的 SHA-256 摘要（文件名 → 十六进制），只在启用 `hash_manifests` 时记录
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    manifest_hashes: HashMap<String, String>,
+    
+    /// 项目的内容指纹，只在 `validation` 为 `Fingerprint` 时记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+}
+
+impl CacheEntry {
+    /// 从 `SELECT_ENTRY` 查询的一行读取记录
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        let manifest_hashes: String = row.get(14)?;
+        Ok(Self {
+            project_path: row.get(0)?,
+            size_info: CachedSizeInfo {
+                code_size: ByteSize::new(row.get::<_, i64>(1)? as u64),
+                dependency_size: ByteSize::new(row.get::<_, i64>(2)? as u64),
+                total_size: ByteSize::new(row.get::<_, i64>(3)? as u64),
+                gitignore_excluded_size: ByteSize::new(row.get::<_, i64>(4)? as u64),
+                code_file_count: row.get::<_, i64>(5)? as usize,
+                dependency_file_count: row.get::<_, i64>(6)? as usize,
+                total_file_count: row.get::<_, i64>(7)? as usize,
+                gitignore_excluded_file_count: row.get::<_, i64>(8)? as usize,
+                last_modified: row.get::<_, Option<i64>>(9)?.map(from_nanos),
+                truncated: row.get(10)?,
+            },
+            created_at: from_nanos(row.get(11)?),
+            last_modified: from_nanos(row.get(12)?),
+            is_git_repo: row.get(13)?,
+            manifest_hashes: serde_json::from_str(&manifest_hashes).unwrap_or_default(),
+            fingerprint: row.get(15)?,
+        })
+    }
+    
+    /// 以 `UPSERT_ENTRY` 或 `IMPORT_ENTRY` 写入数据库
+    fn write(&self, statement: &mut rusqlite::Statement) -> Result<()> {
+        let info = &self.size_info;
+        statement.execute(params![
+            self.project_path,
+            info.code_size.bytes() as i64,
+            info.dependency_size.bytes() as i64,
+            info.total_size.bytes() as i64,
+            info.gitignore_excluded_size.bytes() as i64,
+            info.code_file_count as i64,
+            info.dependency_file_count as i64,
+            info.total_file_count as i64,
+            info.gitignore_excluded_file_count as i64,
+            info.last_modified.map(to_nanos),
+            info.truncated,
+            to_nanos(self.created_at),
+            to_nanos(self.last_modified),
+            self.is_git_repo,
+            serde_json::to_string(&self.manifest_hashes)?,
+            self.fingerprint,
+        ])?;
+        Ok(())
+    }
 }
 
 /// 可序列化的项目大小信息
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CachedSizeInfo {
     /// 代码文件总大小（不包含依赖）
-    pub code_size: u64,
+    pub code_size: ByteSize,
     
     /// 依赖总大小
-    pub dependency_size: u64,
+    pub dependency_size: ByteSize,
     
     /// 项目总大小（包含所有文件）
-    pub total_size: u64,
+    pub total_size: ByteSize,
     
     /// 被 gitignore 排除的文件大小（不含依赖目录，避免重复计算）
-    pub gitignore_excluded_size: u64,
+    pub gitignore_excluded_size: ByteSize,
     
     /// 代码文件数量
     pub code_file_count: usize,
@@ -101,6 +301,10 @@ pub struct CachedSizeInfo {
     /// 最后修改时间（序列化为时间戳）
     #[serde(with = "systemtime_serde")]
     pub last_modified: Option<SystemTime>,
+    
+    /// 是否因达到统计上限而提前停止
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 /// SystemTime 序列化模块
@@ -131,6 +335,18 @@ mod systemtime_serde {
     }
 }
 
+/// 时间在数据库中保存为距 UNIX 纪元的纳秒数，保证比较修改时间时不丢失精度
+fn to_nanos(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos().min(i64::MAX as u128) as i64)
+        .unwrap_or(0)
+}
+
+/// 从纳秒数还原时间
+fn from_nanos(nanos: i64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_nanos(nanos.max(0) as u64)
+}
+
 /// 缓存元数据
 #[derive(Debug, Serialize, Deserialize)]
 struct CacheMetadata {
@@ -158,176 +374,243 @@ impl Default for CacheMetadata {
 impl SizeCache {
     /// 创建新的缓存管理器
     pub async fn new(config: CacheConfig) -> Result<Self> {
-        let cache_dir = dirs::cache_dir()
-            .or_else(|| dirs::home_dir().map(|p| p.join(".cache")))
-            .unwrap_or_else(|| PathBuf::from("."));
-        
-        let cache_dir = cache_dir.join("project-manager-cli");
+        let cache_file = Self::default_cache_file();
         
         // 确保缓存目录存在
-        if !cache_dir.exists() {
-            fs::create_dir_all(&cache_dir).await?;
+        if let Some(cache_dir) = cache_file.parent() {
+            if !cache_dir.exists() {
+                fs::create_dir_all(cache_dir).await?;
+            }
         }
         
-        let cache_file = cache_dir.join("size_cache.json");
+        Self::with_cache_file(cache_file, config).await
+    }
+    
+    /// 默认的缓存数据库位置（不会创建文件）
+    pub fn default_cache_file() -> PathBuf {
+        dirs::cache_dir()
+            .or_else(|| dirs::home_dir().map(|p| p.join(".cache")))
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("project-manager-cli")
+            .join("size_cache.db")
+    }
+    
+    /// 使用指定的缓存数据库创建缓存管理器，同目录下有旧版本的 JSON 缓存文件（同名、扩展名为 json）时先迁移
+    pub async fn with_cache_file(cache_file: PathBuf, config: CacheConfig) -> Result<Self> {
+        let (connection, recovery) = match Self::open_database(&cache_file)? {
+            Some(connection) => (connection, None),
+            None => {
+                let (connection, recovery) = Self::recover_database(&cache_file, config.backup_count)?;
+                (connection, Some(recovery))
+            }
+        };
         
         let mut cache = Self {
             cache_file,
-            cache_data: CacheData::default(),
+            connection: Arc::new(Mutex::new(connection)),
             config,
+            recovery,
         };
         
-        // 加载现有缓存
-        cache.load_cache().await?;
+        if cache.recovery.is_none() {
+            if let Err(e) = cache.rotate_backups() {
+                tracing::warn!("轮换缓存备份失败: {}", e);
+            }
+        }
+        cache.migrate_legacy_json().await?;
         
         Ok(cache)
     }
     
+    /// 加载时缓存文件已损坏并完成恢复的结果，用于提示用户
+    pub fn recovery(&self) -> Option<&CacheRecovery> {
+        self.recovery.as_ref()
+    }
+    
     /// 从缓存获取项目大小信息
     pub async fn get(&self, project_path: &Path) -> Option<CachedSizeInfo> {
         if !self.config.enabled {
             return None;
         }
         
-        let key = self.generate_cache_key(project_path);
-        
-        if let Some(entry) = self.cache_data.entries.get(&key) {
-            // 检查缓存是否过期
-            if !self.is_cache_expired(&entry) {
-                // 检查项目是否有更新
-                if let Ok(last_modified) = self.get_project_last_modified(project_path).await {
-                    if last_modified <= entry.last_modified {
-                        return Some(entry.size_info.clone());
-                    }
-                }
+        if self.is_unchanged(project_path).await {
+            if let Some(entry) = self.load_entry(project_path) {
+                metrics().cache_hits.inc();
+                return Some(entry.size_info);
             }
         }
         
+        metrics().cache_misses.inc();
         None
     }
     
-    /// 将项目大小信息存入缓存
+    /// 项目自缓存记录以来是否没有修改：缓存未过期，且项目目录和关键文件的修改时间不晚于缓存中记录的时间
+    ///
+    /// 记录了关键文件摘要时，只有关键文件的修改时间变化也可以通过比较内容确认没有修改
+    /// （一些编辑器和构建工具会只更新文件的修改时间）。按内容指纹判断且记录中有指纹时，只比较指纹。
+    pub async fn is_unchanged(&self, project_path: &Path) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+        
+        let Some(entry) = self.load_entry(project_path) else {
+            return false;
+        };
+        if self.is_cache_expired(&entry) {
+            return false;
+        }
+        
+        if let Some(recorded) = entry.fingerprint.as_ref().filter(|_| self.config.validation == CacheValidation::Fingerprint) {
+            return Self::fingerprint(project_path).await.as_ref() == Some(recorded);
+        }
+        
+        if self.get_project_last_modified(project_path).await
+            .is_ok_and(|last_modified| last_modified <= entry.last_modified)
+        {
+            return true;
+        }
+        
+        if !self.config.hash_manifests || entry.manifest_hashes.is_empty() {
+            return false;
+        }
+        let directory_unchanged = fs::metadata(project_path).await
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| modified <= entry.last_modified);
+        directory_unchanged && Self::manifest_hashes(project_path).await == entry.manifest_hashes
+    }
+    
+    /// 将项目大小信息存入缓存（只写入这一条记录）
     pub async fn put(&mut self, project_path: &Path, size_info: CachedSizeInfo, is_git_repo: bool) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
         }
         
-        let key = self.generate_cache_key(project_path);
         let last_modified = self.get_project_last_modified(project_path).await?;
+        let manifest_hashes = if self.config.hash_manifests {
+            Self::manifest_hashes(project_path).await
+        } else {
+            HashMap::new()
+        };
+        let fingerprint = match self.config.validation {
+            CacheValidation::Fingerprint => Self::fingerprint(project_path).await,
+            CacheValidation::Mtime => None,
+        };
         
         let entry = CacheEntry {
-            project_path: project_path.to_string_lossy().to_string(),
+            project_path: Self::generate_cache_key(project_path),
             size_info,
             created_at: SystemTime::now(),
             last_modified,
             is_git_repo,
+            manifest_hashes,
+            fingerprint,
         };
         
-        self.cache_data.entries.insert(key, entry);
-        
-        // 检查是否超过最大条目数
-        if self.cache_data.entries.len() > self.config.max_entries {
-            self.cleanup_old_entries().await;
-        }
-        
-        // 更新元数据
-        self.cache_data.metadata.updated_at = SystemTime::now();
-        
-        // 保存缓存
-        self.save_cache().await?;
-        
-        Ok(())
+        let max_entries = self.config.max_entries as i64;
+        self.with_connection_blocking(move |connection| {
+            let transaction = connection.unchecked_transaction()?;
+            entry.write(&mut transaction.prepare(UPSERT_ENTRY)?)?;
+            
+            // 超过最大条目数时才删除最旧的条目
+            let count: i64 = transaction.query_row("SELECT COUNT(*) FROM entries", [], |row| row.get(0))?;
+            if count > max_entries {
+                transaction.execute(
+                    "DELETE FROM entries WHERE project_path IN
+                        (SELECT project_path FROM entries ORDER BY created_at ASC LIMIT ?1)",
+                    params![count - max_entries],
+                )?;
+            }
+            Self::touch(&transaction)?;
+            transaction.commit()?;
+            Ok(())
+        }).await
     }
     
     /// 清除过期的缓存条目
     pub async fn cleanup_expired(&mut self) -> Result<usize> {
-        let initial_count = self.cache_data.entries.len();
-        let now = SystemTime::now();
-        
-        // 收集需要删除的键
-        let expired_keys: Vec<String> = self.cache_data.entries
-            .iter()
-            .filter_map(|(key, entry)| {
-                if let Ok(elapsed) = entry.created_at.elapsed() {
-                    if elapsed > self.config.expiry_duration {
-                        Some(key.clone())
-                    } else {
-                        None
-                    }
-                } else {
-                    Some(key.clone()) // 时间异常，认为已过期
-                }
-            })
-            .collect();
-        
-        // 删除过期的条目
-        for key in &expired_keys {
-            self.cache_data.entries.remove(key);
-        }
-        
-        let removed_count = initial_count - self.cache_data.entries.len();
-        
-        if removed_count > 0 {
-            self.cache_data.metadata.updated_at = now;
-            self.save_cache().await?;
-        }
-        
-        Ok(removed_count)
+        let (expired_before, now) = self.expiry_window();
+        self.with_connection_blocking(move |connection| {
+            let removed = connection.execute(
+                "DELETE FROM entries WHERE created_at < ?1 OR created_at > ?2",
+                params![expired_before, now],
+            )?;
+            if removed > 0 {
+                Self::touch(connection)?;
+            }
+            Ok(removed)
+        }).await
+    }
+    
+    /// 删除指定项目的缓存记录，下次计算时重新统计；返回是否存在记录
+    pub async fn invalidate(&mut self, project_path: &Path) -> Result<bool> {
+        let key = Self::generate_cache_key(project_path);
+        self.with_connection_blocking(move |connection| {
+            let removed = connection.execute("DELETE FROM entries WHERE project_path = ?1", params![key])?;
+            if removed > 0 {
+                Self::touch(connection)?;
+            }
+            Ok(removed > 0)
+        }).await
     }
     
     /// 清除所有缓存
     pub async fn clear_all(&mut self) -> Result<()> {
-        self.cache_data.entries.clear();
-        self.cache_data.metadata.updated_at = SystemTime::now();
-        self.save_cache().await?;
-        Ok(())
+        self.with_connection_blocking(|connection| {
+            connection.execute("DELETE FROM entries", [])?;
+            Self::touch(connection)
+        }).await
     }
     
     /// 获取缓存统计信息
     pub fn get_stats(&self) -> CacheStats {
-        let _now = SystemTime::now();
-        let mut expired_count = 0;
-        let mut git_repos = 0;
-        let mut total_cached_size = 0u64;
-        let mut total_code_size = 0u64;
-        let mut total_dependency_size = 0u64;
-        let mut total_gitignore_size = 0u64;
-        
-        for entry in self.cache_data.entries.values() {
-            if self.is_cache_expired(entry) {
-                expired_count += 1;
-            }
-            
-            if entry.is_git_repo {
-                git_repos += 1;
+        let (expired_before, now) = self.expiry_window();
+        let totals = self.with_connection(|connection| {
+            let totals = connection.query_row(
+                "SELECT COUNT(*),
+                        COALESCE(SUM(created_at < ?1 OR created_at > ?2), 0),
+                        COALESCE(SUM(is_git_repo), 0),
+                        COALESCE(SUM(total_size), 0),
+                        COALESCE(SUM(code_size), 0),
+                        COALESCE(SUM(dependency_size), 0),
+                        COALESCE(SUM(gitignore_excluded_size), 0)
+                 FROM entries",
+                params![expired_before, now],
+                |row| Ok([row.get::<_, i64>(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?]),
+            )?;
+            let updated_at: Option<i64> = connection.query_row(
+                "SELECT value FROM metadata WHERE key = 'updated_at'",
+                [],
+                |row| row.get(0),
+            ).optional()?;
+            Ok((totals, updated_at))
+        });
+        let ([entries, expired, git_repos, total, code, dependency, gitignore], updated_at) = match totals {
+            Ok(totals) => totals,
+            Err(e) => {
+                tracing::warn!("读取缓存统计失败: {}", e);
+                ([0; 7], None)
             }
-            
-            total_cached_size += entry.size_info.total_size;
-            total_code_size += entry.size_info.code_size;
-            total_dependency_size += entry.size_info.dependency_size;
-            total_gitignore_size += entry.size_info.gitignore_excluded_size;
-        }
+        };
         
         CacheStats {
-            total_entries: self.cache_data.entries.len(),
-            expired_entries: expired_count,
-            git_repositories: git_repos,
-            total_cached_size,
-            total_code_size,
-            total_dependency_size,
-            total_gitignore_size,
+            total_entries: entries as usize,
+            expired_entries: expired as usize,
+            git_repositories: git_repos as usize,
+            total_cached_size: total as u64,
+            total_code_size: code as u64,
+            total_dependency_size: dependency as u64,
+            total_gitignore_size: gitignore as u64,
+            cache_file: self.cache_file.clone(),
             cache_file_size: self.get_cache_file_size(),
-            last_updated: self.cache_data.metadata.updated_at,
+            last_updated: updated_at.map(from_nanos).unwrap_or_else(SystemTime::now),
         }
     }
     
     /// 检查特定路径的缓存状态
     pub fn check_cache_status(&self, project_path: &Path) -> CacheStatus {
-        let key = self.generate_cache_key(project_path);
-        
-        match self.cache_data.entries.get(&key) {
+        match self.load_entry(project_path) {
             Some(entry) => {
-                if self.is_cache_expired(entry) {
+                if self.is_cache_expired(&entry) {
                     CacheStatus::Expired
                 } else {
                     CacheStatus::Valid
@@ -339,14 +622,61 @@ impl SizeCache {
     
     // 私有方法
     
-    /// 生成缓存键
-    fn generate_cache_key(&self, project_path: &Path) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        
-        let mut hasher = DefaultHasher::new();
-        project_path.to_string_lossy().hash(&mut hasher);
-        format!("{:x}", hasher.finish())
+    /// 生成缓存键（项目路径）
+    fn generate_cache_key(project_path: &Path) -> String {
+        project_path.to_string_lossy().to_string()
+    }
+    
+    /// 读取项目的缓存记录，读取失败时视为未缓存
+    fn load_entry(&self, project_path: &Path) -> Option<CacheEntry> {
+        let key = Self::generate_cache_key(project_path);
+        self.with_connection(|connection| {
+            Ok(connection.prepare_cached(SELECT_ENTRY)?
+                .query_row(params![key], CacheEntry::from_row)
+                .optional()?)
+        })
+        .unwrap_or_else(|e| {
+            tracing::warn!("读取 {} 的大小缓存失败: {}", project_path.display(), e);
+            None
+        })
+    }
+    
+    /// 在数据库连接上执行操作
+    fn with_connection<T>(&self, operation: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+        let connection = self.connection.lock()
+            .map_err(|_| anyhow::anyhow!("大小缓存的数据库连接不可用"))?;
+        operation(&connection)
+    }
+    
+    /// 在阻塞线程池中执行数据库写入，不在异步任务中持锁等待 SQLite
+    async fn with_connection_blocking<T: Send + 'static>(
+        &self,
+        operation: impl FnOnce(&Connection) -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let connection = Arc::clone(&self.connection);
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock()
+                .map_err(|_| anyhow::anyhow!("大小缓存的数据库连接不可用"))?;
+            operation(&connection)
+        })
+        .await?
+    }
+    
+    /// 记录缓存的最后更新时间
+    fn touch(connection: &Connection) -> Result<()> {
+        connection.execute(
+            "INSERT INTO metadata (key, value) VALUES ('updated_at', ?1)
+             ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+            params![to_nanos(SystemTime::now())],
+        )?;
+        Ok(())
+    }
+    
+    /// 过期判断的时间范围（纳秒）：早于第一个值创建的条目已过期，晚于第二个值（当前时间）的视为时间异常
+    fn expiry_window(&self) -> (i64, i64) {
+        let now = SystemTime::now();
+        let expired_before = now.checked_sub(self.config.expiry_duration).unwrap_or(SystemTime::UNIX_EPOCH);
+        (to_nanos(expired_before), to_nanos(now))
     }
     
     /// 检查缓存是否过期
@@ -358,28 +688,11 @@ impl SizeCache {
         }
     }
     
-    /// 获取项目最后修改时间（考虑 .gitignore 文件的变化）
+    /// 获取项目最后修改时间（项目目录和关键文件中最晚的修改时间）
     async fn get_project_last_modified(&self, project_path: &Path) -> Result<SystemTime> {
         let mut last_modified = fs::metadata(project_path).await?.modified()?;
         
-        // 检查 .gitignore 文件的修改时间
-        let gitignore_path = project_path.join(".gitignore");
-        if gitignore_path.exists() {
-            if let Ok(gitignore_metadata) = fs::metadata(&gitignore_path).await {
-                if let Ok(gitignore_modified) = gitignore_metadata.modified() {
-                    if gitignore_modified > last_modified {
-                        last_modified = gitignore_modified;
-                    }
-                }
-            }
-        }
-        
-        // 也检查一些重要文件的修改时间
-        let important_files = [
-            "Cargo.toml", "package.json", "requirements.txt", "go.mod", "pom.xml"
-        ];
-        
-        for file_name in &important_files {
+        for file_name in MANIFEST_FILES {
             let file_path = project_path.join(file_name);
             if file_path.exists() {
                 if let Ok(file_metadata) = fs::metadata(&file_path).await {
@@ -395,54 +708,246 @@ impl SizeCache {
         Ok(last_modified)
     }
     
-    /// 清理旧的缓存条目
-    async fn cleanup_old_entries(&mut self) {
-        // 按创建时间排序，删除最旧的条目
-        let mut entries: Vec<_> = self.cache_data.entries.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
-        entries.sort_by(|a, b| a.1.created_at.cmp(&b.1.created_at));
+    /// 计算项目中现有关键文件的 SHA-256 摘要，无法读取的文件不记录
+    async fn manifest_hashes(project_path: &Path) -> HashMap<String, String> {
+        use sha2::{Digest, Sha256};
         
-        let remove_count = self.cache_data.entries.len() - self.config.max_entries;
-        for (key, _) in entries.iter().take(remove_count) {
-            self.cache_data.entries.remove(key);
+        let mut hashes = HashMap::new();
+        for file_name in MANIFEST_FILES {
+            if let Ok(content) = fs::read(project_path.join(file_name)).await {
+                let digest = Sha256::digest(&content);
+                let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+                hashes.insert(file_name.to_string(), hex);
+            }
         }
+        hashes
     }
     
-    /// 加载缓存文件
-    async fn load_cache(&mut self) -> Result<()> {
-        if !self.cache_file.exists() {
-            return Ok(());
+    /// 在阻塞线程中计算项目的内容指纹
+    async fn fingerprint(project_path: &Path) -> Option<String> {
+        let project_path = project_path.to_path_buf();
+        tokio::task::spawn_blocking(move || fingerprint::project_fingerprint(&project_path))
+            .await
+            .ok()
+            .flatten()
+    }
+    
+    /// 打开缓存数据库并建表；文件不是有效的数据库或完整性检查失败时返回 None
+    fn open_database(cache_file: &Path) -> Result<Option<Connection>> {
+        let connection = Connection::open(cache_file)
+            .with_context(|| format!("无法打开缓存数据库 {}", cache_file.display()))?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        
+        let opened = connection.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))
+            .and_then(|_| connection.query_row("PRAGMA quick_check", [], |row| row.get::<_, String>(0)));
+        match opened {
+            Ok(check) if check == "ok" => {}
+            Ok(check) => {
+                tracing::warn!("缓存数据库 {} 完整性检查失败: {}", cache_file.display(), check);
+                return Ok(None);
+            }
+            Err(e) if matches!(e.sqlite_error_code(), Some(ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase)) => {
+                tracing::warn!("缓存数据库 {} 已损坏: {}", cache_file.display(), e);
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
         }
         
-        let content = fs::read_to_string(&self.cache_file).await?;
+        connection.execute_batch(SCHEMA)?;
+        
+        // 早期版本的数据库没有指纹列
+        let has_fingerprint: bool = connection.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('entries') WHERE name = 'fingerprint'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_fingerprint {
+            connection.execute("ALTER TABLE entries ADD COLUMN fingerprint TEXT", [])?;
+        }
+        Ok(Some(connection))
+    }
+    
+    /// 数据库损坏时保留一份副本，从最新的可用备份恢复，没有可用备份时重建为空
+    fn recover_database(cache_file: &Path, backup_count: usize) -> Result<(Connection, CacheRecovery)> {
+        let corrupt_copy = sibling_path(cache_file, "corrupt");
+        std::fs::copy(cache_file, &corrupt_copy)?;
+        Self::remove_database(cache_file)?;
         
-        match serde_json::from_str::<CacheData>(&content) {
-            Ok(data) => {
-                self.cache_data = data;
+        let mut restored = None;
+        for index in 1..=backup_count {
+            let backup = sibling_path(cache_file, &index.to_string());
+            if !backup.exists() {
+                continue;
             }
-            Err(_) => {
-                // 缓存文件格式错误，重新开始
-                self.cache_data = CacheData::default();
+            std::fs::copy(&backup, cache_file)?;
+            if let Some(connection) = Self::open_database(cache_file)? {
+                restored = Some((connection, RecoverySource::Backup(backup)));
+                break;
             }
+            Self::remove_database(cache_file)?;
         }
         
+        let (connection, source) = match restored {
+            Some(restored) => restored,
+            None => {
+                let connection = Self::open_database(cache_file)?
+                    .context("无法重建缓存数据库")?;
+                (connection, RecoverySource::Empty)
+            }
+        };
+        let recovered_entries: i64 = connection.query_row("SELECT COUNT(*) FROM entries", [], |row| row.get(0))?;
+        
+        let recovery = CacheRecovery {
+            source,
+            recovered_entries: recovered_entries as usize,
+            discarded_entries: 0,
+            corrupt_copy,
+        };
+        tracing::warn!("{}", recovery);
+        Ok((connection, recovery))
+    }
+    
+    /// 删除数据库文件及其预写日志
+    fn remove_database(cache_file: &Path) -> Result<()> {
+        for path in [cache_file.to_path_buf(), sibling_path(cache_file, "-wal"), sibling_path(cache_file, "-shm")] {
+            match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
         Ok(())
     }
     
-    /// 保存缓存文件
-    async fn save_cache(&self) -> Result<()> {
-        let content = serde_json::to_string_pretty(&self.cache_data)?;
-        fs::write(&self.cache_file, content).await?;
+    /// 迁移旧版本的 JSON 缓存文件：导入其中可以解析的条目（数据库中已有的记录优先），完成后重命名为 `.migrated`
+    ///
+    /// 旧文件部分损坏时记录恢复结果，损坏的原文件保存为 `.corrupt`。
+    async fn migrate_legacy_json(&mut self) -> Result<()> {
+        let legacy_file = self.cache_file.with_extension("json");
+        if legacy_file == self.cache_file || !legacy_file.exists() {
+            return Ok(());
+        }
+        
+        let content = fs::read(&legacy_file).await?;
+        let (entries, discarded) = match serde_json::from_slice::<CacheData>(&content) {
+            Ok(data) => (data.entries, None),
+            Err(e) => {
+                tracing::warn!("旧缓存文件 {} 格式错误: {}", legacy_file.display(), e);
+                match Self::recover_entries(&content) {
+                    Some((data, discarded)) => (data.entries, Some(discarded)),
+                    None => (HashMap::new(), Some(0)),
+                }
+            }
+        };
+        
+        let imported = self.with_connection(|connection| {
+            let transaction = connection.unchecked_transaction()?;
+            let mut imported = 0;
+            {
+                let mut statement = transaction.prepare(IMPORT_ENTRY)?;
+                for entry in entries.values() {
+                    entry.write(&mut statement)?;
+                    imported += 1;
+                }
+            }
+            Self::touch(&transaction)?;
+            transaction.commit()?;
+            Ok(imported)
+        })?;
+        
+        if let Some(discarded_entries) = discarded {
+            let corrupt_copy = sibling_path(&legacy_file, "corrupt");
+            fs::write(&corrupt_copy, &content).await?;
+            let source = if imported > 0 || discarded_entries > 0 { RecoverySource::Partial } else { RecoverySource::Empty };
+            let recovery = CacheRecovery {
+                source,
+                recovered_entries: imported,
+                discarded_entries,
+                corrupt_copy,
+            };
+            tracing::warn!("{}", recovery);
+            self.recovery = Some(recovery);
+        }
+        
+        fs::rename(&legacy_file, sibling_path(&legacy_file, "migrated")).await?;
+        tracing::info!("已将 {} 条大小缓存记录从 {} 迁移到 {}", imported, legacy_file.display(), self.cache_file.display());
         Ok(())
     }
     
-    /// 获取缓存文件大小
+    /// 从结构完整但部分条目无法解析的旧缓存中取出可用的条目，返回数据和丢弃的条目数
+    fn recover_entries(content: &[u8]) -> Option<(CacheData, usize)> {
+        let value: serde_json::Value = serde_json::from_slice(content).ok()?;
+        let raw_entries = value.get("entries")?.as_object()?;
+        
+        let entries: HashMap<String, CacheEntry> = raw_entries.iter()
+            .filter_map(|(key, entry)| {
+                serde_json::from_value(entry.clone()).ok().map(|entry| (key.clone(), entry))
+            })
+            .collect();
+        let discarded = raw_entries.len() - entries.len();
+        let metadata = value.get("metadata")
+            .and_then(|metadata| serde_json::from_value(metadata.clone()).ok())
+            .unwrap_or_default();
+        
+        Some((CacheData { entries, metadata }, discarded))
+    }
+    
+    /// 轮换备份：`.1` 为最新的备份，最多保留 `backup_count` 个；最新备份未过期或缓存为空时不轮换
+    fn rotate_backups(&self) -> Result<()> {
+        if self.config.backup_count == 0 || self.get_stats().total_entries == 0 {
+            return Ok(());
+        }
+        
+        let newest = self.backup_path(1);
+        let recent = std::fs::metadata(&newest)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age < BACKUP_INTERVAL);
+        if recent {
+            return Ok(());
+        }
+        
+        for index in (1..self.config.backup_count).rev() {
+            let from = self.backup_path(index);
+            if from.exists() {
+                std::fs::rename(&from, self.backup_path(index + 1))?;
+            }
+        }
+        if newest.exists() {
+            std::fs::remove_file(&newest)?;
+        }
+        
+        // VACUUM INTO 生成一致的快照，不受预写日志和其他进程写入的影响
+        self.with_connection(|connection| {
+            connection.execute("VACUUM INTO ?1", params![newest.to_string_lossy()])?;
+            Ok(())
+        })
+    }
+    
+    /// 第 `index` 个备份的路径，例如 `size_cache.db.1`
+    fn backup_path(&self, index: usize) -> PathBuf {
+        sibling_path(&self.cache_file, &index.to_string())
+    }
+    
+    /// 获取缓存文件大小（包括尚未合并的预写日志）
     fn get_cache_file_size(&self) -> u64 {
-        std::fs::metadata(&self.cache_file)
-            .map(|m| m.len())
-            .unwrap_or(0)
+        [self.cache_file.clone(), sibling_path(&self.cache_file, "-wal")].iter()
+            .filter_map(|path| std::fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum()
     }
 }
 
+/// 文件名加上后缀的同目录路径：以 `-` 开头的后缀直接拼接（SQLite 的 `-wal` 文件），其他后缀以 `.` 分隔
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut sibling = path.as_os_str().to_owned();
+    if !suffix.starts_with('-') {
+        sibling.push(".");
+    }
+    sibling.push(suffix);
+    PathBuf::from(sibling)
+}
+
 /// 缓存统计信息
 #[derive(Debug, Clone)]
 pub struct CacheStats {
@@ -467,6 +972,9 @@ pub struct CacheStats {
     /// 缓存的总 gitignore 排除大小
     pub total_gitignore_size: u64,
     
+    /// 缓存文件路径
+    pub cache_file: PathBuf,
+    
     /// 缓存文件大小
     pub cache_file_size: u64,
     
@@ -501,6 +1009,9 @@ mod tests {
             expiry_duration: Duration::from_secs(60),
             max_entries: 10,
             enabled: true,
+            backup_count: 3,
+            hash_manifests: false,
+            validation: CacheValidation::Mtime,
         };
         
         let mut cache = SizeCache::new(config).await.unwrap();
@@ -511,15 +1022,16 @@ mod tests {
         
         // 添加缓存
         let size_info = CachedSizeInfo {
-            code_size: 1000,
-            dependency_size: 2000,
-            total_size: 3000,
-            gitignore_excluded_size: 0,
+            code_size: ByteSize::new(1000),
+            dependency_size: ByteSize::new(2000),
+            total_size: ByteSize::new(3000),
+            gitignore_excluded_size: ByteSize::ZERO,
             code_file_count: 10,
             dependency_file_count: 5,
             total_file_count: 15,
             gitignore_excluded_file_count: 0,
             last_modified: Some(SystemTime::now()),
+            truncated: false,
         };
         
         cache.put(project_path, size_info.clone(), false).await.unwrap();
@@ -540,20 +1052,24 @@ mod tests {
             expiry_duration: Duration::from_millis(100), // 100ms 过期
             max_entries: 10,
             enabled: true,
+            backup_count: 3,
+            hash_manifests: false,
+            validation: CacheValidation::Mtime,
         };
         
         let mut cache = SizeCache::new(config).await.unwrap();
         
         let size_info = CachedSizeInfo {
-            code_size: 1000,
-            dependency_size: 0,
-            total_size: 1000,
-            gitignore_excluded_size: 0,
+            code_size: ByteSize::new(1000),
+            dependency_size: ByteSize::ZERO,
+            total_size: ByteSize::new(1000),
+            gitignore_excluded_size: ByteSize::ZERO,
             code_file_count: 1,
             dependency_file_count: 0,
             total_file_count: 1,
             gitignore_excluded_file_count: 0,
             last_modified: Some(SystemTime::now()),
+            truncated: false,
         };
         
         cache.put(project_path, size_info, false).await.unwrap();
@@ -578,21 +1094,22 @@ mod tests {
             expiry_duration: Duration::from_secs(60),
             max_entries: 10,
             enabled: true,
+            backup_count: 3,
+            hash_manifests: false,
+            validation: CacheValidation::Mtime,
         };
         
-        let cache_dir = temp_dir.path().join("cache");
-        fs::create_dir_all(&cache_dir).await.unwrap();
-        let cache_file = cache_dir.join("test_cache.json");
-        
-        let mut cache = SizeCache {
-            cache_file,
-            cache_data: CacheData::default(),
-            config,
-        };
+        let cache_file = temp_dir.path().join("test_cache.db");
+        let mut cache = SizeCache::with_cache_file(cache_file, config).await.unwrap();
         
         let stats = cache.get_stats();
         assert_eq!(stats.total_entries, 0);
         assert_eq!(stats.expired_entries, 0);
+        
+        cache.put(temp_dir.path(), sample_size_info(), true).await.unwrap();
+        let stats = cache.get_stats();
+        assert_eq!((stats.total_entries, stats.git_repositories, stats.total_code_size), (1, 1, 1000));
+        assert!(stats.cache_file_size > 0);
     }
 
     #[tokio::test]
@@ -604,22 +1121,196 @@ mod tests {
         let mut cache = SizeCache::new(config).await.unwrap();
         
         let size_info = CachedSizeInfo {
-            code_size: 1000,
-            dependency_size: 0,
-            total_size: 1000,
-            gitignore_excluded_size: 0,
+            code_size: ByteSize::new(1000),
+            dependency_size: ByteSize::ZERO,
+            total_size: ByteSize::new(1000),
+            gitignore_excluded_size: ByteSize::ZERO,
             code_file_count: 1,
             dependency_file_count: 0,
             total_file_count: 1,
             gitignore_excluded_file_count: 0,
             last_modified: Some(SystemTime::now()),
+            truncated: false,
         };
         
         cache.put(project_path, size_info, false).await.unwrap();
         assert!(cache.get(project_path).await.is_some());
         
+        assert!(cache.invalidate(project_path).await.unwrap());
+        assert!(cache.get(project_path).await.is_none());
+        assert!(!cache.invalidate(project_path).await.unwrap());
+        
+        cache.put(project_path, sample_size_info(), false).await.unwrap();
         cache.clear_all().await.unwrap();
         assert!(cache.get(project_path).await.is_none());
         assert_eq!(cache.get_stats().total_entries, 0);
     }
-}
\ No newline at end of file
+
+    fn sample_size_info() -> CachedSizeInfo {
+        CachedSizeInfo {
+            code_size: ByteSize::new(1000),
+            dependency_size: ByteSize::ZERO,
+            total_size: ByteSize::new(1000),
+            gitignore_excluded_size: ByteSize::ZERO,
+            code_file_count: 1,
+            dependency_file_count: 0,
+            total_file_count: 1,
+            gitignore_excluded_file_count: 0,
+            last_modified: Some(SystemTime::now()),
+            truncated: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_manifest_hashes_ignore_touched_files() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("app");
+        std::fs::create_dir_all(&project).unwrap();
+        let manifest = project.join("Cargo.toml");
+        std::fs::write(&manifest, "[package]\nname = \"app\"\n").unwrap();
+        let touch = |offset: u64| {
+            let later = SystemTime::now() + Duration::from_secs(offset);
+            std::fs::File::options().write(true).open(&manifest).unwrap().set_modified(later).unwrap();
+        };
+
+        let config = CacheConfig { hash_manifests: true, ..CacheConfig::default() };
+        let mut cache = SizeCache::with_cache_file(temp_dir.path().join("hashed.db"), config).await.unwrap();
+        cache.put(&project, sample_size_info(), false).await.unwrap();
+        let mut plain = SizeCache::with_cache_file(temp_dir.path().join("plain.db"), CacheConfig::default()).await.unwrap();
+        plain.put(&project, sample_size_info(), false).await.unwrap();
+
+        // 只更新修改时间：记录了摘要的缓存仍然有效
+        touch(60);
+        assert!(cache.get(&project).await.is_some());
+        assert!(plain.get(&project).await.is_none());
+
+        // 内容变化后失效
+        std::fs::write(&manifest, "[package]\nname = \"renamed\"\n").unwrap();
+        touch(120);
+        assert!(cache.get(&project).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fingerprint_validation_detects_deep_edits() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("app");
+        let deep_file = project.join("src/handlers/mod.rs");
+        std::fs::create_dir_all(deep_file.parent().unwrap()).unwrap();
+        std::fs::write(&deep_file, "fn handle() {}").unwrap();
+
+        let config = CacheConfig { validation: CacheValidation::Fingerprint, ..CacheConfig::default() };
+        let mut cache = SizeCache::with_cache_file(temp_dir.path().join("fingerprint.db"), config).await.unwrap();
+        cache.put(&project, sample_size_info(), false).await.unwrap();
+        let mut plain = SizeCache::with_cache_file(temp_dir.path().join("plain.db"), CacheConfig::default()).await.unwrap();
+        plain.put(&project, sample_size_info(), false).await.unwrap();
+        assert!(cache.get(&project).await.is_some());
+
+        // 深层文件被修改：只比较修改时间的缓存仍然命中，按指纹判断的缓存失效
+        let later = SystemTime::now() + Duration::from_secs(60);
+        std::fs::File::options().write(true).open(&deep_file).unwrap().set_modified(later).unwrap();
+        assert!(plain.get(&project).await.is_some());
+        assert!(cache.get(&project).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_legacy_json_cache() {
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("app");
+        std::fs::create_dir_all(&project).unwrap();
+        let entry = CacheEntry {
+            project_path: project.to_string_lossy().to_string(),
+            size_info: sample_size_info(),
+            created_at: SystemTime::now(),
+            last_modified: SystemTime::now() + Duration::from_secs(60),
+            is_git_repo: false,
+            manifest_hashes: HashMap::new(),
+            fingerprint: None,
+        };
+        let legacy_file = temp_dir.path().join("size_cache.json");
+        let mut value = serde_json::to_value(CacheData {
+            entries: HashMap::from([("app".to_string(), entry)]),
+            metadata: CacheMetadata::default(),
+        }).unwrap();
+        value["entries"]["broken"] = serde_json::json!({ "project_path": 42 });
+        std::fs::write(&legacy_file, value.to_string()).unwrap();
+
+        // 可解析的条目导入数据库，无法解析的条目丢弃并提示
+        let cache_file = temp_dir.path().join("size_cache.db");
+        let cache = SizeCache::with_cache_file(cache_file.clone(), CacheConfig::default()).await.unwrap();
+        let recovery = cache.recovery().unwrap();
+        assert_eq!(recovery.source, RecoverySource::Partial);
+        assert_eq!((recovery.recovered_entries, recovery.discarded_entries), (1, 1));
+        assert!(recovery.corrupt_copy.exists());
+        assert!(cache.get(&project).await.is_some());
+        assert!(!legacy_file.exists());
+        assert!(temp_dir.path().join("size_cache.json.migrated").exists());
+
+        // 迁移只进行一次
+        let cache = SizeCache::with_cache_file(cache_file, CacheConfig::default()).await.unwrap();
+        assert!(cache.recovery().is_none());
+        assert_eq!(cache.get_stats().total_entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_instances_share_entries() {
+        let temp_dir = tempdir().unwrap();
+        let cache_file = temp_dir.path().join("size_cache.db");
+        let mut first = SizeCache::with_cache_file(cache_file.clone(), CacheConfig::default()).await.unwrap();
+        let mut second = SizeCache::with_cache_file(cache_file, CacheConfig::default()).await.unwrap();
+
+        // 每次写入只更新一条记录，另一个实例（或进程）写入的记录立即可见且不会被覆盖
+        let (one, two) = (temp_dir.path().join("one"), temp_dir.path().join("two"));
+        std::fs::create_dir_all(&one).unwrap();
+        std::fs::create_dir_all(&two).unwrap();
+        first.put(&one, sample_size_info(), false).await.unwrap();
+        second.put(&two, sample_size_info(), false).await.unwrap();
+        assert!(first.get(&two).await.is_some());
+        assert!(second.get(&one).await.is_some());
+        assert_eq!(first.get_stats().total_entries, 2);
+    }
+
+    #[tokio::test]
+    async fn test_put_prunes_oldest_entries_over_limit() {
+        let temp_dir = tempdir().unwrap();
+        let config = CacheConfig { max_entries: 2, ..CacheConfig::default() };
+        let mut cache = SizeCache::with_cache_file(temp_dir.path().join("size_cache.db"), config).await.unwrap();
+
+        let projects: Vec<PathBuf> = ["one", "two", "three"].iter().map(|name| temp_dir.path().join(name)).collect();
+        for project in &projects {
+            std::fs::create_dir_all(project).unwrap();
+            cache.put(project, sample_size_info(), false).await.unwrap();
+        }
+
+        // 只保留最新的两条记录
+        assert_eq!(cache.get_stats().total_entries, 2);
+        assert!(cache.get(&projects[0]).await.is_none());
+        assert!(cache.get(&projects[2]).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_recover_corrupt_database_from_backup() {
+        let temp_dir = tempdir().unwrap();
+        let cache_file = temp_dir.path().join("size_cache.db");
+
+        let mut cache = SizeCache::with_cache_file(cache_file.clone(), CacheConfig::default()).await.unwrap();
+        cache.put(temp_dir.path(), sample_size_info(), false).await.unwrap();
+        drop(cache);
+
+        // 加载有数据的缓存时创建备份，且间隔内不重复轮换
+        SizeCache::with_cache_file(cache_file.clone(), CacheConfig::default()).await.unwrap();
+        SizeCache::with_cache_file(cache_file.clone(), CacheConfig::default()).await.unwrap();
+        assert!(temp_dir.path().join("size_cache.db.1").exists());
+        assert!(!temp_dir.path().join("size_cache.db.2").exists());
+
+        SizeCache::remove_database(&cache_file).unwrap();
+        std::fs::write(&cache_file, vec![0x5a; 8192]).unwrap();
+
+        let cache = SizeCache::with_cache_file(cache_file.clone(), CacheConfig::default()).await.unwrap();
+        let recovery = cache.recovery().unwrap();
+        assert_eq!(recovery.source, RecoverySource::Backup(temp_dir.path().join("size_cache.db.1")));
+        assert_eq!(recovery.recovered_entries, 1);
+        assert!(recovery.to_string().starts_with("大小缓存文件已损坏，已从备份"));
+        assert!(temp_dir.path().join("size_cache.db.corrupt").exists());
+        assert_eq!(cache.check_cache_status(temp_dir.path()), CacheStatus::Valid);
+    }
+}
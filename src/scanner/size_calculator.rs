@@ -7,9 +7,71 @@ use tokio::fs;
 use std::fs::Metadata;
 
 use super::git_ignore_analyzer::GitIgnoreAnalyzer;
-use super::size_cache::{SizeCache, CachedSizeInfo, CacheConfig, CacheStatus};
+use super::git_task::run_git_task;
+use super::project_detector::ProjectDetector;
+use super::size_cache::{SizeCache, CachedSizeInfo, CacheConfig, CacheRecovery, CacheStatus};
+use super::symlinks::SymlinkTracker;
 use super::parallel_file_walker::{ParallelFileWalker, SizeCalculationResult, ScanProgress as ParallelScanProgress, ScanStage};
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use crate::models::ByteSize;
+use crate::utils::metrics::metrics;
+
+/// 计算 gitignore 排除大小时跳过的依赖目录（这些目录已单独计入依赖大小）
+pub const DEPENDENCY_DIR_NAMES: &[&str] = &[
+    "node_modules", "target", "build", "dist", "out", "bin", "obj",
+    "__pycache__", "venv", "env", ".venv", ".env", "site-packages",
+    ".git", ".svn", ".hg", ".vscode", ".idea", ".vs", "vendor", "bower_components",
+    ".terraform", ".terragrunt-cache", ".cxx",
+    ".dart_tool", "_build", "deps", "zig-cache", ".zig-cache", "zig-out",
+];
+
+/// 项目的依赖目录名：默认依赖目录加上游戏引擎缓存目录和 Xcode 项目的依赖目录
+pub fn dependency_dir_names_for(project_path: &Path) -> Vec<&'static str> {
+    let mut names = DEPENDENCY_DIR_NAMES.to_vec();
+    names.extend_from_slice(ProjectDetector::game_engine_cache_dir_names(project_path));
+    names.extend_from_slice(ProjectDetector::apple_dependency_dir_names(project_path));
+    names
+}
+
+/// 目录大小树保留子节点的最大深度，更深的目录只计入大小
+const SIZE_TREE_MAX_DEPTH: usize = 8;
+
+/// 未指定时并发计算使用的任务数
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// 快速估算时的最大目录深度
+const ESTIMATE_MAX_DEPTH: usize = 3;
+
+/// 快速估算时最多统计的条目数
+const ESTIMATE_MAX_ENTRIES: usize = 5000;
+
+/// 单个项目大小计算的上限：达到后停止统计，并将结果标记为不完整
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SizeLimits {
+    /// 最多统计的文件数，None 表示不限制
+    pub max_files: Option<usize>,
+    
+    /// 最多统计的字节数，None 表示不限制
+    pub max_bytes: Option<u64>,
+}
+
+impl SizeLimits {
+    /// 已统计的文件数是否达到上限
+    pub fn files_reached(&self, files: usize) -> bool {
+        self.max_files.is_some_and(|max| files >= max)
+    }
+    
+    /// 已统计的字节数是否达到上限
+    pub fn bytes_reached(&self, bytes: u64) -> bool {
+        self.max_bytes.is_some_and(|max| bytes >= max)
+    }
+    
+    /// 文件数或字节数是否达到上限
+    pub fn is_reached(&self, files: usize, bytes: u64) -> bool {
+        self.files_reached(files) || self.bytes_reached(bytes)
+    }
+}
 
 /// 进度回调函数类型
 pub type ProgressCallback = dyn Fn(String, usize, Option<usize>, String, u64, ScanStage) + Send + Sync;
@@ -24,22 +86,34 @@ pub struct SizeCalculator {
     
     /// 缓存管理器（可选）
     cache: Option<SizeCache>,
+    
+    /// 单个项目的统计上限
+    limits: SizeLimits,
+    
+    /// 取消令牌，用于中止在阻塞线程中运行的 Git 分析
+    cancellation_token: CancellationToken,
+    
+    /// 是否跟随指向文件的符号链接，默认按链接自身计算
+    follow_symlinks: bool,
+    
+    /// 并发计算时获取文件元数据的任务数
+    concurrency: usize,
 }
 
 /// 项目大小统计结果
 #[derive(Debug, Clone, Default)]
 pub struct ProjectSizeInfo {
     /// 代码文件总大小（不包含依赖）
-    pub code_size: u64,
+    pub code_size: ByteSize,
     
     /// 依赖总大小
-    pub dependency_size: u64,
+    pub dependency_size: ByteSize,
     
     /// 项目总大小（包含所有文件）
-    pub total_size: u64,
+    pub total_size: ByteSize,
     
     /// 被 gitignore 排除的文件大小（不含依赖目录，避免重复计算）
-    pub gitignore_excluded_size: u64,
+    pub gitignore_excluded_size: ByteSize,
     
     /// 代码文件数量
     pub code_file_count: usize,
@@ -55,6 +129,12 @@ pub struct ProjectSizeInfo {
     
     /// 最后修改时间
     pub last_modified: Option<std::time::SystemTime>,
+    
+    /// 是否因达到统计上限而提前停止（实际大小和文件数更大）
+    pub truncated: bool,
+    
+    /// 目标不存在的符号链接（只在重新计算时报告，缓存结果中为空）
+    pub broken_links: Vec<PathBuf>,
 }
 
 /// 目录大小统计
@@ -73,6 +153,43 @@ pub struct DirectorySizeInfo {
     pub is_dependency: bool,
 }
 
+/// 目录大小树的节点（目录或文件）
+#[derive(Debug, Clone, Default)]
+pub struct SizeTreeNode {
+    /// 路径
+    pub path: PathBuf,
+    
+    /// 大小（目录为其中所有文件的合计）
+    pub size: u64,
+    
+    /// 文件数量
+    pub file_count: usize,
+    
+    /// 是否是目录
+    pub is_dir: bool,
+    
+    /// 是否是依赖目录
+    pub is_dependency: bool,
+    
+    /// 占用最大的子目录和文件，按大小降序
+    pub children: Vec<SizeTreeNode>,
+    
+    /// 未列出的其余子项的合计大小
+    pub other_size: u64,
+    
+    /// 未列出的其余子项数量
+    pub other_count: usize,
+}
+
+impl SizeTreeNode {
+    /// 文件名或目录名
+    pub fn name(&self) -> String {
+        self.path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+}
+
 impl SizeCalculator {
     /// 创建新的大小计算器
     pub fn new() -> Self {
@@ -80,6 +197,10 @@ impl SizeCalculator {
             ignore_dirs: Self::default_ignore_dirs(),
             ignore_extensions: Self::default_ignore_extensions(),
             cache: None,
+            limits: SizeLimits::default(),
+            cancellation_token: CancellationToken::new(),
+            follow_symlinks: false,
+            concurrency: DEFAULT_CONCURRENCY,
         }
     }
     
@@ -95,6 +216,10 @@ impl SizeCalculator {
             ignore_dirs: Self::default_ignore_dirs(),
             ignore_extensions: Self::default_ignore_extensions(),
             cache,
+            limits: SizeLimits::default(),
+            cancellation_token: CancellationToken::new(),
+            follow_symlinks: false,
+            concurrency: DEFAULT_CONCURRENCY,
         })
     }
     
@@ -107,6 +232,10 @@ impl SizeCalculator {
             ignore_dirs,
             ignore_extensions,
             cache: None,
+            limits: SizeLimits::default(),
+            cancellation_token: CancellationToken::new(),
+            follow_symlinks: false,
+            concurrency: DEFAULT_CONCURRENCY,
         }
     }
     
@@ -126,9 +255,67 @@ impl SizeCalculator {
             ignore_dirs,
             ignore_extensions,
             cache,
+            limits: SizeLimits::default(),
+            cancellation_token: CancellationToken::new(),
+            follow_symlinks: false,
+            concurrency: DEFAULT_CONCURRENCY,
         })
     }
     
+    /// 使用指定的大小缓存（如测试中放在临时目录的缓存数据库）
+    pub fn with_size_cache(mut self, cache: SizeCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    
+    /// 设置单个项目的统计上限
+    pub fn with_limits(mut self, limits: SizeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+    
+    /// 设置取消令牌：取消后不再等待 Git 分析的结果
+    pub fn with_cancellation(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = cancellation_token;
+        self
+    }
+    
+    /// 设置是否跟随指向文件的符号链接（对应配置 `scan.follow_symlinks`）
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+    
+    /// 追加按依赖计算的目录名（对应配置 `detection.custom_types` 中的 dependency_dirs）
+    pub fn with_dependency_dirs(mut self, dirs: impl IntoIterator<Item = String>) -> Self {
+        self.ignore_dirs.extend(dirs);
+        self
+    }
+    
+    /// 设置并发计算时的任务数（对应配置 `scan.concurrent_scans`），至少为 1
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+    
+    /// 快速估算项目代码大小：只做浅层遍历并跳过依赖目录，结果偏小，仅用于精确计算完成前的展示
+    pub fn quick_estimate(project_path: &Path) -> ByteSize {
+        walkdir::WalkDir::new(project_path)
+            .max_depth(ESTIMATE_MAX_DEPTH)
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_dir()
+                    || !entry.file_name().to_str().is_some_and(|name| DEPENDENCY_DIR_NAMES.contains(&name))
+            })
+            .take(ESTIMATE_MAX_ENTRIES)
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| entry.metadata().ok())
+            .map(|metadata| ByteSize::new(metadata.len()))
+            .sum()
+    }
+    
     /// 高性能并发计算项目大小（推荐方法）
     pub async fn calculate_project_size_parallel(
         &mut self, 
@@ -147,8 +334,9 @@ impl SizeCalculator {
         let walker = ParallelFileWalker::with_config(
             self.ignore_dirs.clone(),
             self.ignore_extensions.clone(),
-            8, // 使用8个并发任务
-        );
+            self.concurrency,
+        ).with_limits(self.limits)
+            .with_follow_symlinks(self.follow_symlinks);
         
         // 设置进度回调
         let walker_progress_callback = {
@@ -169,46 +357,48 @@ impl SizeCalculator {
         };
         
         // 执行并发扫描
-        let file_infos = walker.scan_parallel(project_path, walker_progress_callback).await?;
+        let (file_infos, truncated, broken_links) = walker.scan_parallel_limited(project_path, walker_progress_callback).await?;
         
         // 计算统计结果
         let calc_result = SizeCalculationResult::from_file_infos(&file_infos);
         
         // 转换为 ProjectSizeInfo
         let mut size_info = ProjectSizeInfo {
-            code_size: calc_result.code_size,
-            dependency_size: calc_result.dependency_size,
-            total_size: calc_result.total_size,
-            gitignore_excluded_size: 0, // 将在下面计算
+            code_size: ByteSize::new(calc_result.code_size),
+            dependency_size: ByteSize::new(calc_result.dependency_size),
+            total_size: ByteSize::new(calc_result.total_size),
+            gitignore_excluded_size: ByteSize::ZERO, // 将在下面计算
             code_file_count: calc_result.code_file_count,
             dependency_file_count: calc_result.dependency_file_count,
             total_file_count: calc_result.total_file_count,
             gitignore_excluded_file_count: 0, // 将在下面计算
-            last_modified: std::time::SystemTime::now().into(),
+            last_modified: calc_result.last_modified,
+            truncated,
+            broken_links,
         };
+        report_broken_links(project_path, &size_info.broken_links);
         
-        // 如果是 git 仓库，计算被忽略的文件
-        if let Ok(git_analyzer) = GitIgnoreAnalyzer::new(project_path) {
-            if git_analyzer.is_git_repository() {
-                let exclude_deps = ["node_modules", "target", "build", "dist", "out", "bin", "obj", 
-                                   "__pycache__", "venv", "env", ".venv", ".env", "site-packages",
-                                   ".git", ".svn", ".hg", ".vscode", ".idea", ".vs", "vendor", "bower_components"];
-                
-                if let Ok((gitignore_size, gitignore_count)) = git_analyzer
-                    .calculate_ignored_files_size_exclude_dependencies(&exclude_deps).await 
-                {
-                    size_info.gitignore_excluded_size = gitignore_size;
-                    size_info.gitignore_excluded_file_count = gitignore_count;
-                }
+        // 如果是 git 仓库，计算被忽略的文件（构建忽略集合会遍历整个项目，放到阻塞线程中运行）
+        let analyzer_path = project_path.to_path_buf();
+        let git_analyzer = run_git_task(&self.cancellation_token, move |cancellation_token| {
+            GitIgnoreAnalyzer::new_cancellable(&analyzer_path, cancellation_token)
+        }).await.and_then(|result| result.ok());
+        if self.cancellation_token.is_cancelled() {
+            anyhow::bail!("计算已取消");
+        }
+        let is_git_repo = git_analyzer.as_ref().is_some_and(|ga| ga.is_git_repository());
+        if let Some(git_analyzer) = git_analyzer.filter(|_| is_git_repo) {
+            if let Ok((gitignore_size, gitignore_count)) = git_analyzer
+                .calculate_ignored_files_size_exclude_dependencies(&dependency_dir_names_for(project_path)).await 
+            {
+                size_info.gitignore_excluded_size = ByteSize::new(gitignore_size);
+                size_info.gitignore_excluded_file_count = gitignore_count;
             }
         }
         
         // 保存到缓存
         if self.cache.is_some() {
             let cached_info = self.convert_project_size_info_to_cached(&size_info);
-            let is_git_repo = GitIgnoreAnalyzer::new(project_path)
-                .map(|ga| ga.is_git_repository())
-                .unwrap_or(false);
             
             if let Some(ref mut cache) = self.cache {
                 let _ = cache.put(project_path, cached_info, is_git_repo).await;
@@ -250,26 +440,40 @@ impl SizeCalculator {
         let git_analyzer = GitIgnoreAnalyzer::new(project_path)?;
         
         let mut size_info = ProjectSizeInfo::default();
+        let mut symlinks = SymlinkTracker::new(project_path, self.follow_symlinks);
         
         if git_analyzer.is_git_repository() {
             // Git 项目：使用 gitignore 规则
-            self.calculate_git_project_size(project_path, &git_analyzer, &mut size_info).await?;
+            self.calculate_git_project_size(project_path, &git_analyzer, &mut size_info, &mut symlinks).await?;
         } else {
             // 非 Git 项目：使用传统方式
-            self.calculate_directory_recursive(project_path, &mut size_info).await?;
+            self.calculate_directory_recursive(project_path, &mut size_info, &mut symlinks).await?;
         }
         
+        size_info.broken_links = symlinks.into_broken_links();
+        report_broken_links(project_path, &size_info.broken_links);
+        
         Ok(size_info)
     }
     
     /// 只计算代码大小（排除依赖）
-    pub async fn calculate_code_size(&mut self, project_path: &Path) -> Result<u64> {
+    pub async fn calculate_code_size(&mut self, project_path: &Path) -> Result<ByteSize> {
         let size_info = self.calculate_project_size(project_path).await?;
         Ok(size_info.code_size)
     }
     
     /// 计算指定目录的大小
-    pub fn calculate_directory_size<'a>(&'a self, dir_path: &'a Path) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<DirectorySizeInfo>> + Send + 'a>> {
+    pub async fn calculate_directory_size(&self, dir_path: &Path) -> Result<DirectorySizeInfo> {
+        let mut symlinks = SymlinkTracker::new(dir_path, self.follow_symlinks);
+        self.calculate_directory_size_with(dir_path, &mut symlinks).await
+    }
+    
+    /// 计算目录大小，符号链接按 `symlinks` 的设置处理；无法读取的子目录和条目跳过，不中止统计
+    fn calculate_directory_size_with<'a>(
+        &'a self,
+        dir_path: &'a Path,
+        symlinks: &'a mut SymlinkTracker,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<DirectorySizeInfo>> + Send + 'a>> {
         Box::pin(async move {
             let mut total_size = 0u64;
             let mut file_count = 0usize;
@@ -278,17 +482,21 @@ impl SizeCalculator {
             
             let mut entries = fs::read_dir(dir_path).await?;
             
-            while let Some(entry) = entries.next_entry().await? {
-                let metadata = entry.metadata().await?;
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let path = entry.path();
+                let Some(metadata) = entry_metadata(&entry, symlinks).await else {
+                    continue;
+                };
                 
-                if metadata.is_file() {
+                if metadata.is_file() || metadata.is_symlink() {
                     total_size += metadata.len();
                     file_count += 1;
                 } else if metadata.is_dir() {
                     // 递归计算子目录大小
-                    let sub_info = self.calculate_directory_size(&entry.path()).await?;
-                    total_size += sub_info.size;
-                    file_count += sub_info.file_count;
+                    if let Ok(sub_info) = self.calculate_directory_size_with(&path, symlinks).await {
+                        total_size += sub_info.size;
+                        file_count += sub_info.file_count;
+                    }
                 }
             }
             
@@ -301,34 +509,112 @@ impl SizeCalculator {
         })
     }
     
+    /// 计算目录的大小树：每个目录保留占用最大的 `top_n` 个子项，其余合并计入 `other_size`
+    pub async fn calculate_size_tree(&self, dir_path: &Path, top_n: usize) -> Result<SizeTreeNode> {
+        let mut symlinks = SymlinkTracker::new(dir_path, self.follow_symlinks);
+        self.calculate_size_tree_with(dir_path, top_n, 0, &mut symlinks).await
+    }
+    
+    fn calculate_size_tree_with<'a>(
+        &'a self,
+        dir_path: &'a Path,
+        top_n: usize,
+        depth: usize,
+        symlinks: &'a mut SymlinkTracker,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<SizeTreeNode>> + Send + 'a>> {
+        Box::pin(async move {
+            let mut node = SizeTreeNode {
+                path: dir_path.to_path_buf(),
+                is_dir: true,
+                is_dependency: self.is_dependency_directory(dir_path),
+                ..SizeTreeNode::default()
+            };
+            let mut children = Vec::new();
+            
+            let mut entries = fs::read_dir(dir_path).await?;
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                if self.cancellation_token.is_cancelled() {
+                    break;
+                }
+                
+                let path = entry.path();
+                let Some(metadata) = entry_metadata(&entry, symlinks).await else {
+                    continue;
+                };
+                
+                let child = if metadata.is_file() || metadata.is_symlink() {
+                    SizeTreeNode { path, size: metadata.len(), file_count: 1, ..SizeTreeNode::default() }
+                } else if metadata.is_dir() && depth < SIZE_TREE_MAX_DEPTH {
+                    match self.calculate_size_tree_with(&path, top_n, depth + 1, symlinks).await {
+                        Ok(child) => child,
+                        Err(_) => continue,
+                    }
+                } else if metadata.is_dir() {
+                    match self.calculate_directory_size_with(&path, symlinks).await {
+                        Ok(info) => SizeTreeNode {
+                            path,
+                            size: info.size,
+                            file_count: info.file_count,
+                            is_dir: true,
+                            is_dependency: info.is_dependency,
+                            ..SizeTreeNode::default()
+                        },
+                        Err(_) => continue,
+                    }
+                } else {
+                    continue;
+                };
+                
+                node.size += child.size;
+                node.file_count += child.file_count;
+                children.push(child);
+            }
+            
+            children.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+            for omitted in children.drain(top_n.min(children.len())..) {
+                node.other_size += omitted.size;
+                node.other_count += 1;
+            }
+            node.children = children;
+            Ok(node)
+        })
+    }
+    
     /// 递归计算目录大小
     fn calculate_directory_recursive<'a>(
         &'a self,
         dir_path: &'a Path,
         size_info: &'a mut ProjectSizeInfo,
+        symlinks: &'a mut SymlinkTracker,
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
         Box::pin(async move {
             let mut entries = fs::read_dir(dir_path).await?;
             
-            while let Some(entry) = entries.next_entry().await? {
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                if self.limits.is_reached(size_info.total_file_count, size_info.total_size.bytes()) {
+                    size_info.truncated = true;
+                    break;
+                }
+                
                 let path = entry.path();
-                let metadata = entry.metadata().await?;
+                let Some(metadata) = entry_metadata(&entry, symlinks).await else {
+                    continue;
+                };
                 
-                if metadata.is_file() {
+                if metadata.is_file() || metadata.is_symlink() {
                     self.process_file(&path, &metadata, size_info).await?;
-                } else if metadata.is_dir() {
-                    if self.should_process_directory(&path) {
-                        if self.is_dependency_directory(&path) {
-                            // 这是依赖目录，只计算总大小
-                            let dep_info = self.calculate_directory_size(&path).await?;
-                            size_info.dependency_size += dep_info.size;
+                } else if metadata.is_dir() && self.should_process_directory(&path) {
+                    if self.is_dependency_directory(&path) {
+                        // 这是依赖目录，只计算总大小
+                        if let Ok(dep_info) = self.calculate_directory_size_with(&path, symlinks).await {
+                            size_info.dependency_size += ByteSize::new(dep_info.size);
                             size_info.dependency_file_count += dep_info.file_count;
-                            size_info.total_size += dep_info.size;
+                            size_info.total_size += ByteSize::new(dep_info.size);
                             size_info.total_file_count += dep_info.file_count;
-                        } else {
-                            // 普通代码目录，递归处理
-                            self.calculate_directory_recursive(&path, size_info).await?;
                         }
+                    } else {
+                        // 普通代码目录，递归处理，无法读取的目录跳过
+                        let _ = self.calculate_directory_recursive(&path, size_info, symlinks).await;
                     }
                 }
             }
@@ -344,7 +630,7 @@ impl SizeCalculator {
         metadata: &Metadata,
         size_info: &mut ProjectSizeInfo,
     ) -> Result<()> {
-        let file_size = metadata.len();
+        let file_size = ByteSize::new(metadata.len());
         let is_ignored = self.should_ignore_file(file_path);
         
         // 更新最后修改时间
@@ -375,8 +661,8 @@ impl SizeCalculator {
             None => return false,
         };
         
-        // 不处理隐藏目录（以 . 开头），除了 .git
-        if dir_name.starts_with('.') && dir_name != ".git" {
+        // 不处理隐藏目录（以 . 开头），除了 .git 和隐藏的依赖目录（如 .venv、.terraform）
+        if dir_name.starts_with('.') && dir_name != ".git" && !self.is_dependency_directory(dir_path) {
             return false;
         }
         
@@ -392,6 +678,13 @@ impl SizeCalculator {
         };
         
         self.ignore_dirs.contains(dir_name.as_ref())
+            || ProjectDetector::game_engine_cache_type(dir_path).is_some()
+            || ProjectDetector::is_apple_dependency_dir(dir_path)
+    }
+    
+    /// 是否是版本库元数据目录：.git 在忽略目录列表中，但不是依赖，不计入依赖大小
+    fn is_repository_metadata(dir_path: &Path) -> bool {
+        dir_path.file_name().is_some_and(|name| name == ".git")
     }
     
     /// 检查是否应该忽略该文件
@@ -457,6 +750,18 @@ impl SizeCalculator {
         dirs.insert("vendor".to_string());
         dirs.insert("bower_components".to_string());
         
+        // 基础设施工具缓存
+        dirs.insert(".terraform".to_string());
+        dirs.insert(".terragrunt-cache".to_string());
+        
+        // Android NDK 构建目录
+        dirs.insert(".cxx".to_string());
+        
+        // Dart、Elixir 和 Zig 的依赖与构建目录
+        for dir in [".dart_tool", "_build", "deps", "zig-cache", ".zig-cache", "zig-out"] {
+            dirs.insert(dir.to_string());
+        }
+        
         dirs
     }
     
@@ -500,8 +805,9 @@ impl SizeCalculator {
         
         while let Some(entry) = entries.next_entry().await? {
             let path = entry.path();
+            let is_dir = entry.file_type().await.is_ok_and(|file_type| file_type.is_dir());
             
-            if path.is_dir() && self.is_dependency_directory(&path) {
+            if is_dir && self.is_dependency_directory(&path) {
                 let dir_info = self.calculate_directory_size(&path).await?;
                 dependency_dirs.push(dir_info);
             }
@@ -519,46 +825,68 @@ impl SizeCalculator {
         project_path: &Path,
         git_analyzer: &GitIgnoreAnalyzer,
         size_info: &mut ProjectSizeInfo,
+        symlinks: &mut SymlinkTracker,
     ) -> Result<()> {
         // 首先处理被 git 跟踪的文件
         let entries = git_analyzer.get_walkable_entries()?;
         
         for entry_path in entries {
-            let metadata = match fs::metadata(&entry_path).await {
+            if self.limits.is_reached(size_info.total_file_count, size_info.total_size.bytes()) {
+                size_info.truncated = true;
+                break;
+            }
+            
+            let metadata = match fs::symlink_metadata(&entry_path).await {
                 Ok(m) => m,
                 Err(_) => continue, // 跳过无法访问的文件
             };
+            let Some(metadata) = symlinks.resolve(&entry_path, metadata) else {
+                continue;
+            };
             
-            if metadata.is_file() {
+            if metadata.is_file() || metadata.is_symlink() {
                 self.process_file(&entry_path, &metadata, size_info).await?;
             }
         }
         
-        // 然后处理被忽略的依赖目录
+        // 然后处理被忽略的依赖目录（指向目录的链接不展开）
         let mut entries = fs::read_dir(project_path).await?;
-        while let Some(entry) = entries.next_entry().await? {
+        while let Ok(Some(entry)) = entries.next_entry().await {
             let path = entry.path();
+            let is_dir = entry.file_type().await.is_ok_and(|file_type| file_type.is_dir());
             
-            if path.is_dir() && self.is_dependency_directory(&path) {
+            if is_dir && self.is_dependency_directory(&path) && !Self::is_repository_metadata(&path) {
                 // 检查该目录是否被 git 忽略
                 if git_analyzer.should_ignore(&path) {
-                    let dep_info = self.calculate_directory_size(&path).await?;
-                    size_info.dependency_size += dep_info.size;
+                    let Ok(dep_info) = self.calculate_directory_size_with(&path, symlinks).await else {
+                        continue;
+                    };
+                    size_info.dependency_size += ByteSize::new(dep_info.size);
                     size_info.dependency_file_count += dep_info.file_count;
-                    size_info.total_size += dep_info.size;
+                    size_info.total_size += ByteSize::new(dep_info.size);
                     size_info.total_file_count += dep_info.file_count;
                 }
             }
         }
         
+        // Android 项目各模块下的 build/ 和 .cxx 目录
+        for module_dir in ProjectDetector::android_module_build_dirs(project_path) {
+            if git_analyzer.should_ignore(&module_dir) {
+                let Ok(dep_info) = self.calculate_directory_size_with(&module_dir, symlinks).await else {
+                    continue;
+                };
+                size_info.dependency_size += ByteSize::new(dep_info.size);
+                size_info.dependency_file_count += dep_info.file_count;
+                size_info.total_size += ByteSize::new(dep_info.size);
+                size_info.total_file_count += dep_info.file_count;
+            }
+        }
+        
         // 最后计算被 gitignore 排除的其他文件大小（排除依赖目录避免重复计算）
-        let exclude_deps = ["node_modules", "target", "build", "dist", "out", "bin", "obj", 
-                           "__pycache__", "venv", "env", ".venv", ".env", "site-packages",
-                           ".git", ".svn", ".hg", ".vscode", ".idea", ".vs", "vendor", "bower_components"];
         let (gitignore_size, gitignore_count) = git_analyzer
-            .calculate_ignored_files_size_exclude_dependencies(&exclude_deps).await?;
+            .calculate_ignored_files_size_exclude_dependencies(&dependency_dir_names_for(project_path)).await?;
         
-        size_info.gitignore_excluded_size = gitignore_size;
+        size_info.gitignore_excluded_size = ByteSize::new(gitignore_size);
         size_info.gitignore_excluded_file_count = gitignore_count;
         
         Ok(())
@@ -576,6 +904,7 @@ impl SizeCalculator {
             total_file_count: size_info.total_file_count,
             gitignore_excluded_file_count: size_info.gitignore_excluded_file_count,
             last_modified: size_info.last_modified,
+            truncated: size_info.truncated,
         }
     }
     
@@ -591,6 +920,8 @@ impl SizeCalculator {
             total_file_count: cached_info.total_file_count,
             gitignore_excluded_file_count: cached_info.gitignore_excluded_file_count,
             last_modified: cached_info.last_modified,
+            truncated: cached_info.truncated,
+            broken_links: Vec::new(),
         }
     }
     
@@ -599,6 +930,11 @@ impl SizeCalculator {
         self.cache.as_ref().map(|cache| cache.check_cache_status(project_path))
     }
     
+    /// 加载时缓存文件已损坏并完成恢复的结果
+    pub fn cache_recovery(&self) -> Option<&CacheRecovery> {
+        self.cache.as_ref().and_then(|cache| cache.recovery())
+    }
+    
     /// 清理过期缓存
     pub async fn cleanup_cache(&mut self) -> Result<usize> {
         match &mut self.cache {
@@ -607,6 +943,14 @@ impl SizeCalculator {
         }
     }
     
+    /// 使指定项目的缓存失效，返回是否存在缓存记录
+    pub async fn invalidate_cache(&mut self, project_path: &Path) -> Result<bool> {
+        match &mut self.cache {
+            Some(cache) => cache.invalidate(project_path).await,
+            None => Ok(false),
+        }
+    }
+    
     /// 清除所有缓存
     pub async fn clear_cache(&mut self) -> Result<()> {
         match &mut self.cache {
@@ -621,6 +965,26 @@ impl SizeCalculator {
     }
 }
 
+/// 读取目录条目的元数据（不跟随符号链接），再按设置处理链接；无法读取或不再计入时返回 None
+async fn entry_metadata(entry: &fs::DirEntry, symlinks: &mut SymlinkTracker) -> Option<Metadata> {
+    let metadata = entry.metadata().await.ok()?;
+    symlinks.resolve(&entry.path(), metadata)
+}
+
+/// 记录项目中的失效符号链接：写入日志并计入运行指标
+fn report_broken_links(project_path: &Path, broken_links: &[PathBuf]) {
+    if broken_links.is_empty() {
+        return;
+    }
+    metrics().size_broken_links.add(broken_links.len() as u64);
+    tracing::warn!(
+        "项目 {} 中有 {} 个失效的符号链接，已按链接自身计算大小: {}",
+        project_path.display(),
+        broken_links.len(),
+        broken_links.iter().map(|link| link.display().to_string()).collect::<Vec<_>>().join(", ")
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -634,12 +998,40 @@ mod tests {
         
         let size_info = calculator.calculate_project_size(temp_dir.path()).await.unwrap();
         
-        assert_eq!(size_info.code_size, 0);
-        assert_eq!(size_info.dependency_size, 0);
-        assert_eq!(size_info.total_size, 0);
+        assert_eq!(size_info.code_size, ByteSize::ZERO);
+        assert_eq!(size_info.dependency_size, ByteSize::ZERO);
+        assert_eq!(size_info.total_size, ByteSize::ZERO);
         assert_eq!(size_info.code_file_count, 0);
     }
 
+    #[tokio::test]
+    async fn test_calculate_size_tree() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("node_modules/react")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("node_modules/react/index.js"), "x".repeat(5000)).unwrap();
+        fs::write(root.join("src/main.js"), "x".repeat(300)).unwrap();
+        fs::write(root.join("src/util.js"), "x".repeat(200)).unwrap();
+        fs::write(root.join("README.md"), "x".repeat(100)).unwrap();
+        fs::write(root.join(".gitignore"), "x".repeat(10)).unwrap();
+
+        let tree = SizeCalculator::new().calculate_size_tree(root, 2).await.unwrap();
+        assert_eq!(tree.size, 5610);
+        assert_eq!(tree.file_count, 5);
+
+        // 只保留最大的 2 个子项，其余合并
+        let names: Vec<String> = tree.children.iter().map(|child| child.name()).collect();
+        assert_eq!(names, ["node_modules", "src"]);
+        assert!(tree.children[0].is_dependency);
+        assert_eq!((tree.other_size, tree.other_count), (110, 2));
+
+        let src = &tree.children[1];
+        assert_eq!(src.size, 500);
+        assert_eq!(src.children[0].name(), "main.js");
+        assert!(!src.children[0].is_dir);
+    }
+
     #[tokio::test]
     async fn test_calculate_simple_project() {
         let mut calculator = SizeCalculator::new();
@@ -657,11 +1049,74 @@ mod tests {
         
         let size_info = calculator.calculate_project_size(temp_dir.path()).await.unwrap();
         
-        assert!(size_info.code_size > 0);
-        assert_eq!(size_info.dependency_size, 0);
+        assert!(size_info.code_size > ByteSize::ZERO);
+        assert_eq!(size_info.dependency_size, ByteSize::ZERO);
         assert_eq!(size_info.total_size, size_info.code_size);
         assert_eq!(size_info.code_file_count, 2);
     }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_symlinks_counted_once_and_broken_links_reported() {
+        use std::os::unix::fs::symlink;
+        
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("app");
+        let shared = temp_dir.path().join("shared");
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(&shared).unwrap();
+        fs::write(project.join("data.bin"), vec![0u8; 4096]).unwrap();
+        fs::write(shared.join("big.bin"), vec![0u8; 64 * 1024]).unwrap();
+        symlink(shared.join("big.bin"), project.join("big-link")).unwrap();
+        symlink(shared.join("big.bin"), project.join("big-link-2")).unwrap();
+        symlink(&shared, project.join("shared-dir")).unwrap();
+        symlink(project.join("missing"), project.join("broken-link")).unwrap();
+        
+        // 默认不跟随：四个链接都按自身计算，失效链接不中止统计
+        let size_info = SizeCalculator::new().calculate_project_size(&project).await.unwrap();
+        assert_eq!(size_info.total_file_count, 5);
+        assert!(size_info.total_size < ByteSize::new(64 * 1024));
+        assert_eq!(size_info.broken_links, [project.join("broken-link")]);
+        
+        // 跟随：指向同一文件的两个链接只计一次，指向目录的链接仍按自身计算
+        let mut calculator = SizeCalculator::new().with_follow_symlinks(true);
+        let size_info = calculator.calculate_project_size(&project).await.unwrap();
+        assert_eq!(size_info.total_file_count, 4);
+        assert!(size_info.total_size >= ByteSize::new(4096 + 64 * 1024) && size_info.total_size < ByteSize::new(4096 + 2 * 64 * 1024));
+        
+        let size_info = calculator.calculate_project_size_parallel(&project, None, "app".to_string()).await.unwrap();
+        assert_eq!(size_info.total_file_count, 4);
+        assert_eq!(size_info.broken_links, [project.join("broken-link")]);
+    }
+
+    #[tokio::test]
+    async fn test_size_limits_truncate_calculation() {
+        let temp_dir = tempdir().unwrap();
+        for i in 0..5 {
+            fs::write(temp_dir.path().join(format!("data_{}.rs", i)), "0123456789").unwrap();
+        }
+        
+        let limits = SizeLimits { max_files: Some(2), max_bytes: None };
+        let mut calculator = SizeCalculator::new().with_limits(limits);
+        let size_info = calculator.calculate_project_size(temp_dir.path()).await.unwrap();
+        assert!(size_info.truncated);
+        assert_eq!(size_info.total_file_count, 2);
+        
+        let size_info = calculator.calculate_project_size_parallel(temp_dir.path(), None, "data".to_string()).await.unwrap();
+        assert!(size_info.truncated);
+        assert_eq!(size_info.total_file_count, 2);
+        
+        let limits = SizeLimits { max_files: None, max_bytes: Some(30) };
+        let mut calculator = SizeCalculator::new().with_limits(limits);
+        let size_info = calculator.calculate_project_size_parallel(temp_dir.path(), None, "data".to_string()).await.unwrap();
+        assert!(size_info.truncated);
+        assert_eq!(size_info.total_size, ByteSize::new(30));
+        
+        let mut calculator = SizeCalculator::new();
+        let size_info = calculator.calculate_project_size_parallel(temp_dir.path(), None, "data".to_string()).await.unwrap();
+        assert!(!size_info.truncated);
+        assert_eq!(size_info.total_file_count, 5);
+    }
 
     #[tokio::test]
     async fn test_ignore_dependency_directories() {
@@ -685,16 +1140,50 @@ mod tests {
         let size_info = calculator.calculate_project_size(temp_dir.path()).await.unwrap();
         
         // 代码大小应该只包含 index.js
-        assert!(size_info.code_size > 0);
-        assert!(size_info.code_size < 100); // 主文件很小
+        assert!(size_info.code_size > ByteSize::ZERO);
+        assert!(size_info.code_size < ByteSize::new(100)); // 主文件很小
         
         // 依赖大小应该包含 node_modules
-        assert!(size_info.dependency_size > 1000); // 依赖文件较大
+        assert!(size_info.dependency_size > ByteSize::new(1000)); // 依赖文件较大
         
         // 总大小应该是两者之和
         assert_eq!(size_info.total_size, size_info.code_size + size_info.dependency_size);
     }
 
+    #[tokio::test]
+    async fn test_git_metadata_not_counted_as_dependency() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        git2::Repository::init(root).unwrap();
+        fs::write(root.join(".gitignore"), "node_modules/\n").unwrap();
+        fs::write(root.join("index.js"), "console.log(1);").unwrap();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        fs::write(root.join("node_modules/pkg/index.js"), "x".repeat(2000)).unwrap();
+        
+        let size_info = SizeCalculator::new().calculate_project_size(root).await.unwrap();
+        
+        // 依赖大小只包含 node_modules，不包含 .git
+        assert_eq!(size_info.dependency_size, ByteSize::new(2000));
+        assert_eq!(size_info.dependency_file_count, 1);
+    }
+
+    #[test]
+    fn test_quick_estimate_skips_dependencies() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("main.rs"), "x".repeat(100)).unwrap();
+        
+        let node_modules = temp_dir.path().join("node_modules");
+        fs::create_dir(&node_modules).unwrap();
+        fs::write(node_modules.join("index.js"), "x".repeat(1000)).unwrap();
+        
+        // 超出估算深度的文件不计入
+        let deep = temp_dir.path().join("a").join("b").join("c");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(deep.join("deep.rs"), "x".repeat(10)).unwrap();
+        
+        assert_eq!(SizeCalculator::quick_estimate(temp_dir.path()), ByteSize::new(100));
+    }
+
     #[test]
     fn test_is_dependency_directory() {
         let calculator = SizeCalculator::new();
@@ -0,0 +1,163 @@
+// 以异步流的形式提供项目扫描结果，供库的使用者统一处理发现和大小计算事件
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::config::Config;
+use crate::models::Project;
+use crate::scanner::access::SkippedLocations;
+use crate::scanner::size_cache::CacheRecovery;
+use crate::scanner::size_calculator::ProjectSizeInfo;
+use crate::scanner::{DetectedProject, FileWalker, GitAnalyzer, SizeCalculator};
+
+/// 事件缓冲区大小，使用者处理不过来时扫描会暂停等待
+const STREAM_BUFFER: usize = 64;
+
+/// 扫描流中的事件
+#[derive(Debug, Clone)]
+pub enum ScanEvent {
+    /// 大小缓存损坏，已恢复
+    CacheRecovered(CacheRecovery),
+
+    /// 发现项目（大小尚未计算）
+    ProjectFound(Box<Project>),
+
+    /// 扫描时跳过了无法访问的位置
+    LocationsSkipped(SkippedLocations),
+
+    /// 项目大小计算完成
+    SizeCalculated {
+        path: PathBuf,
+        size: ProjectSizeInfo,
+    },
+
+    /// 项目大小计算失败
+    SizeFailed {
+        path: PathBuf,
+        error: String,
+    },
+
+    /// 扫描失败，之后不再有其他事件
+    Failed(String),
+
+    /// 扫描和大小计算全部完成
+    Completed {
+        project_count: usize,
+    },
+}
+
+/// 扫描路径中的项目并逐个计算大小，以流的形式返回事件
+///
+/// 先报告全部发现的项目，再依次报告各项目的大小；丢弃流即可停止扫描。
+/// 需要在 tokio 运行时中调用。
+pub fn scan_stream(paths: Vec<String>, config: Config) -> impl Stream<Item = ScanEvent> {
+    let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+    tokio::spawn(run_scan(paths, config, sender));
+    ReceiverStream::new(receiver)
+}
+
+/// 执行扫描并发送事件，接收端关闭后提前结束
+async fn run_scan(paths: Vec<String>, config: Config, sender: mpsc::Sender<ScanEvent>) {
+    let mut size_calculator = SizeCalculator::new_with_cache(config.cache.to_size_cache_config())
+        .await
+        .unwrap_or_else(|_| SizeCalculator::new())
+        .with_limits(config.scan.to_size_limits())
+        .with_follow_symlinks(config.scan.follow_symlinks)
+        .with_dependency_dirs(config.detection.dependency_dirs())
+        .with_concurrency(config.scan.concurrent_scans);
+    if let Some(recovery) = size_calculator.cache_recovery() {
+        if sender.send(ScanEvent::CacheRecovered(recovery.clone())).await.is_err() {
+            return;
+        }
+    }
+
+    let (detected, skipped) = match FileWalker::new(config).scan_paths_with_skipped(&paths).await {
+        Ok(result) => result,
+        Err(e) => {
+            let _ = sender.send(ScanEvent::Failed(e.to_string())).await;
+            return;
+        }
+    };
+    if !skipped.is_empty() && sender.send(ScanEvent::LocationsSkipped(skipped)).await.is_err() {
+        return;
+    }
+
+    let git_analyzer = GitAnalyzer::new();
+    let mut paths = Vec::with_capacity(detected.len());
+    for detected in detected {
+        let project = analyze_detected(detected, &git_analyzer);
+        paths.push(project.path.clone());
+        if sender.send(ScanEvent::ProjectFound(Box::new(project))).await.is_err() {
+            return;
+        }
+    }
+
+    for path in &paths {
+        let event = match size_calculator.calculate_project_size(path).await {
+            Ok(size) => ScanEvent::SizeCalculated { path: path.clone(), size },
+            Err(e) => ScanEvent::SizeFailed { path: path.clone(), error: e.to_string() },
+        };
+        if sender.send(event).await.is_err() {
+            return;
+        }
+    }
+
+    let _ = sender.send(ScanEvent::Completed { project_count: paths.len() }).await;
+}
+
+/// 分析 Git 状态和修改时间，将检测结果转换为项目
+pub fn analyze_detected(detected: DetectedProject, git_analyzer: &GitAnalyzer) -> Project {
+    let git_info = git_analyzer.analyze_repository(&detected.path).unwrap_or(None);
+    let last_modified = directory_modified(&detected.path);
+    detected.into_project(git_info, last_modified)
+}
+
+/// 项目目录的修改时间，无法读取时使用当前时间
+pub fn directory_modified(path: &Path) -> DateTime<Utc> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ByteSize;
+    use futures::StreamExt;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_scan_stream_reports_projects_then_sizes() {
+        let temp_dir = tempdir().unwrap();
+        let project_dir = temp_dir.path().join("app");
+        std::fs::create_dir_all(project_dir.join("src")).unwrap();
+        std::fs::write(project_dir.join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        std::fs::write(project_dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let mut config = Config::default();
+        config.cache.enabled = false;
+        let paths = vec![temp_dir.path().to_string_lossy().to_string()];
+        let events: Vec<ScanEvent> = scan_stream(paths, config).collect().await;
+
+        let kinds: Vec<&str> = events.iter()
+            .map(|event| match event {
+                ScanEvent::ProjectFound(_) => "found",
+                ScanEvent::SizeCalculated { .. } => "size",
+                ScanEvent::Completed { .. } => "completed",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(kinds, ["found", "size", "completed"]);
+
+        let ScanEvent::SizeCalculated { path, size } = &events[1] else {
+            unreachable!();
+        };
+        assert_eq!(path, &project_dir);
+        assert!(size.total_size > ByteSize::ZERO);
+    }
+}
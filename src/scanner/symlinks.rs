@@ -0,0 +1,103 @@
+// 大小统计中符号链接的处理：默认不跟随，链接按自身大小计为一个文件；
+// 跟随时只展开指向文件的链接，同一目标只计一次；目标不存在的链接记录下来用于诊断
+
+use std::collections::HashSet;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+
+/// 一个项目大小统计过程中的符号链接状态
+#[derive(Debug)]
+pub struct SymlinkTracker {
+    /// 项目根目录（规范化后），跟随链接时指向其中的目标已在原位置统计
+    root: PathBuf,
+
+    /// 是否跟随指向文件的链接
+    follow: bool,
+
+    /// 已统计过的项目外部目标
+    seen_targets: HashSet<PathBuf>,
+
+    /// 目标不存在的链接
+    broken: Vec<PathBuf>,
+}
+
+impl SymlinkTracker {
+    pub fn new(root: &Path, follow: bool) -> Self {
+        Self {
+            root: root.canonicalize().unwrap_or_else(|_| root.to_path_buf()),
+            follow,
+            seen_targets: HashSet::new(),
+            broken: Vec::new(),
+        }
+    }
+
+    /// 决定条目按什么元数据统计，`metadata` 是不跟随链接读取的元数据
+    ///
+    /// 普通文件和目录原样返回；不跟随或目标不是文件时返回链接本身，调用方按文件统计（`is_symlink`）。
+    /// 跟随时返回目标的元数据，目标在项目内部或已统计过时返回 None，该条目不再计入。
+    pub fn resolve(&mut self, path: &Path, metadata: Metadata) -> Option<Metadata> {
+        if !metadata.is_symlink() {
+            return Some(metadata);
+        }
+
+        match std::fs::metadata(path) {
+            Err(_) => {
+                self.broken.push(path.to_path_buf());
+                Some(metadata)
+            }
+            Ok(target) if self.follow && target.is_file() => {
+                let canonical = path.canonicalize().ok()?;
+                if canonical.starts_with(&self.root) || !self.seen_targets.insert(canonical) {
+                    return None;
+                }
+                Some(target)
+            }
+            Ok(_) => Some(metadata),
+        }
+    }
+
+    /// 统计过程中发现的失效链接
+    pub fn into_broken_links(self) -> Vec<PathBuf> {
+        self.broken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_tracker() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = tempdir().unwrap();
+        let project = temp_dir.path().join("app");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("data.bin"), vec![0u8; 4096]).unwrap();
+        fs::write(temp_dir.path().join("shared.bin"), vec![0u8; 8192]).unwrap();
+        symlink(project.join("data.bin"), project.join("inner-link")).unwrap();
+        symlink(temp_dir.path().join("shared.bin"), project.join("outer-link")).unwrap();
+        symlink(temp_dir.path().join("shared.bin"), project.join("outer-link-2")).unwrap();
+        symlink(project.join("missing"), project.join("broken-link")).unwrap();
+        let lstat = |name: &str| fs::symlink_metadata(project.join(name)).unwrap();
+
+        // 默认不跟随：链接按自身统计
+        let mut tracker = SymlinkTracker::new(&project, false);
+        let resolved = tracker.resolve(&project.join("outer-link"), lstat("outer-link")).unwrap();
+        assert!(resolved.is_symlink());
+        assert!(tracker.resolve(&project.join("data.bin"), lstat("data.bin")).unwrap().is_file());
+
+        // 跟随：项目内部的目标和重复的外部目标只计一次
+        let mut tracker = SymlinkTracker::new(&project, true);
+        assert!(tracker.resolve(&project.join("inner-link"), lstat("inner-link")).is_none());
+        assert_eq!(tracker.resolve(&project.join("outer-link"), lstat("outer-link")).unwrap().len(), 8192);
+        assert!(tracker.resolve(&project.join("outer-link-2"), lstat("outer-link-2")).is_none());
+
+        // 失效链接按自身统计并记录
+        assert!(tracker.resolve(&project.join("broken-link"), lstat("broken-link")).unwrap().is_symlink());
+        assert_eq!(tracker.into_broken_links(), [project.join("broken-link")]);
+    }
+}
@@ -0,0 +1,262 @@
+// 监视扫描路径：定期比较目录的修改时间，发现项目新增、删除或依赖目录大小明显变化时报告
+//
+// 不依赖系统的文件通知接口，只检查扫描根目录、到各项目之间的目录和各项目的依赖目录，开销与项目数量成正比
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use tokio_util::sync::CancellationToken;
+use walkdir::WalkDir;
+
+/// 依赖目录大小的变化至少达到已知大小的该比例才报告
+const SIGNIFICANT_CHANGE_RATIO: f64 = 0.1;
+
+/// 依赖目录大小的变化至少达到该字节数才报告，避免小目录频繁触发
+const SIGNIFICANT_CHANGE_BYTES: u64 = 1024 * 1024;
+
+/// 监视发现的变化
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    /// 目录内容有变化，可能新增或删除了项目，需要重新发现
+    LayoutChanged(PathBuf),
+
+    /// 项目目录已不存在
+    ProjectDeleted(PathBuf),
+
+    /// 项目依赖目录的大小明显变化
+    DependenciesChanged {
+        project: PathBuf,
+        previous: u64,
+        current: u64,
+    },
+}
+
+/// 要监视的项目
+#[derive(Debug, Clone)]
+pub struct WatchedProject {
+    /// 项目路径
+    pub path: PathBuf,
+
+    /// 依赖目录，可以尚不存在（如还没有安装依赖）
+    pub dependency_dirs: Vec<PathBuf>,
+
+    /// 已知的依赖大小，未知时第一次变化只记录大小，不报告
+    pub dependency_size: Option<u64>,
+}
+
+/// 项目的监视状态
+#[derive(Debug)]
+struct ProjectState {
+    dependency_dirs: Vec<PathBuf>,
+    dependency_size: Option<u64>,
+    fingerprint: Vec<Option<SystemTime>>,
+}
+
+/// 轮询式的项目监视器
+#[derive(Debug)]
+pub struct ProjectWatcher {
+    /// 扫描根目录、根目录下的直接子目录和到各项目之间的目录，及其修改时间
+    containers: BTreeMap<PathBuf, Option<SystemTime>>,
+
+    /// 各项目的依赖目录状态
+    projects: BTreeMap<PathBuf, ProjectState>,
+}
+
+impl ProjectWatcher {
+    /// 记录当前状态作为比较的基准，会读取目录元数据，应在阻塞线程中调用
+    pub fn new(roots: &[PathBuf], projects: Vec<WatchedProject>) -> Self {
+        let mut containers = BTreeMap::new();
+        for root in roots {
+            containers.insert(root.clone(), modified(root));
+            // 根目录下的直接子目录：在新建的分组目录中创建项目时也能发现
+            for entry in std::fs::read_dir(root).into_iter().flatten().flatten() {
+                let path = entry.path();
+                let hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if !hidden && path.is_dir() && !projects.iter().any(|project| project.path == path) {
+                    containers.insert(path.clone(), modified(&path));
+                }
+            }
+        }
+        for project in &projects {
+            let Some(root) = roots.iter().find(|root| project.path.starts_with(root)) else {
+                continue;
+            };
+            for ancestor in project.path.ancestors().skip(1) {
+                if !ancestor.starts_with(root) {
+                    break;
+                }
+                containers.entry(ancestor.to_path_buf()).or_insert_with(|| modified(ancestor));
+            }
+        }
+
+        let projects = projects.into_iter()
+            .map(|project| {
+                let fingerprint = fingerprint(&project.dependency_dirs);
+                (project.path, ProjectState {
+                    dependency_dirs: project.dependency_dirs,
+                    dependency_size: project.dependency_size,
+                    fingerprint,
+                })
+            })
+            .collect();
+        Self { containers, projects }
+    }
+
+    /// 检查自上次以来的变化，应在阻塞线程中调用
+    ///
+    /// 依赖目录只在它或它的直接子目录的修改时间变化后才重新统计大小；
+    /// 大小的变化累计达到阈值才报告，之后以新的大小作为基准。
+    pub fn poll(&mut self) -> Vec<WatchEvent> {
+        let mut events = Vec::new();
+        for (path, last_modified) in &mut self.containers {
+            let current = modified(path);
+            if current != *last_modified {
+                *last_modified = current;
+                events.push(WatchEvent::LayoutChanged(path.clone()));
+            }
+        }
+
+        let deleted: Vec<PathBuf> = self.projects.keys()
+            .filter(|path| !path.exists())
+            .cloned()
+            .collect();
+        for path in deleted {
+            self.projects.remove(&path);
+            events.push(WatchEvent::ProjectDeleted(path));
+        }
+
+        for (path, state) in &mut self.projects {
+            let current_fingerprint = fingerprint(&state.dependency_dirs);
+            if current_fingerprint == state.fingerprint {
+                continue;
+            }
+            state.fingerprint = current_fingerprint;
+
+            let current: u64 = state.dependency_dirs.iter().map(|dir| dir_size(dir)).sum();
+            match state.dependency_size {
+                Some(previous) if is_significant_change(previous, current) => {
+                    state.dependency_size = Some(current);
+                    events.push(WatchEvent::DependenciesChanged { project: path.clone(), previous, current });
+                }
+                Some(_) => {}
+                None => state.dependency_size = Some(current),
+            }
+        }
+        events
+    }
+
+    /// 按间隔持续检查，直到取消令牌被触发；检查在阻塞线程中进行
+    pub async fn run(mut self, interval: Duration, cancellation_token: CancellationToken, mut on_event: impl FnMut(WatchEvent)) {
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => return,
+                _ = tokio::time::sleep(interval) => {}
+            }
+
+            let Ok((watcher, events)) = tokio::task::spawn_blocking(move || {
+                let events = self.poll();
+                (self, events)
+            }).await else {
+                tracing::warn!("监视任务异常退出");
+                return;
+            };
+            self = watcher;
+
+            if cancellation_token.is_cancelled() {
+                return;
+            }
+            for event in events {
+                on_event(event);
+            }
+        }
+    }
+}
+
+/// 变化是否足够大：同时达到绝对阈值和相对阈值
+fn is_significant_change(previous: u64, current: u64) -> bool {
+    let change = previous.abs_diff(current);
+    change >= SIGNIFICANT_CHANGE_BYTES && change as f64 >= previous as f64 * SIGNIFICANT_CHANGE_RATIO
+}
+
+/// 目录的修改时间，不存在时为 None
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// 依赖目录的指纹：每个目录取它和直接子目录中最晚的修改时间（构建产物通常写在子目录中，如 target/debug）
+fn fingerprint(dirs: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    dirs.iter()
+        .map(|dir| {
+            let children = std::fs::read_dir(dir).into_iter().flatten().flatten()
+                .filter_map(|entry| entry.metadata().and_then(|metadata| metadata.modified()).ok());
+            modified(dir).into_iter().chain(children).max()
+        })
+        .collect()
+}
+
+/// 目录中所有文件的总大小，不跟随符号链接
+fn dir_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    /// 把目录的修改时间改到将来，避免文件系统时间精度导致变化检测不到
+    fn touch(path: &Path) {
+        let later = SystemTime::now() + Duration::from_secs(60);
+        fs::File::open(path).unwrap().set_modified(later).unwrap();
+    }
+
+    #[test]
+    fn test_watcher_reports_changes() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        let [api, web] = ["api", "web"].map(|name| {
+            let path = root.join("work").join(name);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("package.json"), "{}").unwrap();
+            path
+        });
+
+        let watched = [&api, &web].map(|path| WatchedProject {
+            path: path.clone(),
+            dependency_dirs: vec![path.join("node_modules")],
+            dependency_size: Some(0),
+        });
+        let mut watcher = ProjectWatcher::new(std::slice::from_ref(&root), watched.to_vec());
+        assert!(watcher.poll().is_empty());
+
+        // 小的依赖变化不报告，安装大量依赖后报告
+        let node_modules = api.join("node_modules");
+        fs::create_dir_all(node_modules.join("left-pad")).unwrap();
+        fs::write(node_modules.join("left-pad/index.js"), "module.exports = 1;").unwrap();
+        assert!(watcher.poll().is_empty());
+        fs::write(node_modules.join("left-pad/big.bin"), vec![0u8; 2 * 1024 * 1024]).unwrap();
+        touch(&node_modules.join("left-pad"));
+        let events = watcher.poll();
+        assert!(matches!(
+            events.as_slice(),
+            [WatchEvent::DependenciesChanged { project, previous: 0, current }] if project == &api && *current > 2 * 1024 * 1024
+        ));
+
+        // 删除项目同时改变了上级目录
+        fs::remove_dir_all(&web).unwrap();
+        touch(&root.join("work"));
+        assert_eq!(watcher.poll(), [
+            WatchEvent::LayoutChanged(root.join("work")),
+            WatchEvent::ProjectDeleted(web),
+        ]);
+        assert!(watcher.poll().is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+// 工作区（monorepo）成员解析：pnpm-workspace.yaml、Cargo.toml [workspace]、lerna.json、go.work
+
+use std::path::{Path, PathBuf};
+
+/// 展开 `**` 时的最大目录深度
+const MAX_GLOB_DEPTH: usize = 5;
+
+/// 展开通配符时不进入的目录
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// 工作区清单的类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WorkspaceKind {
+    /// pnpm-workspace.yaml
+    Pnpm,
+
+    /// Cargo.toml 中的 [workspace]
+    Cargo,
+
+    /// lerna.json
+    Lerna,
+
+    /// go.work
+    GoWork,
+}
+
+/// 列出 `root` 的工作区成员目录（按路径排序，不含根目录本身）
+///
+/// 依次检查各类工作区清单，成员模式支持 `*`、`?` 和 `**`，以 `!` 开头的模式（pnpm）
+/// 和 Cargo 的 `exclude` 会从结果中排除。
+pub fn members(root: &Path) -> Vec<PathBuf> {
+    let mut members: Vec<PathBuf> = Vec::new();
+    for kind in [WorkspaceKind::Pnpm, WorkspaceKind::Cargo, WorkspaceKind::Lerna, WorkspaceKind::GoWork] {
+        let Some((include, exclude)) = patterns(root, kind) else {
+            continue;
+        };
+
+        let excluded: Vec<PathBuf> = exclude.iter().flat_map(|pattern| expand(root, pattern)).collect();
+        members.extend(include.iter()
+            .flat_map(|pattern| expand(root, pattern))
+            .filter(|member| !excluded.contains(member)));
+    }
+
+    // 成员可以用 `..` 引用工作区之外的目录，这些目录作为独立项目扫描
+    members.retain(|member| member != root && member.starts_with(root));
+    members.sort();
+    members.dedup();
+    members
+}
+
+/// 读取工作区清单中的成员模式（包含、排除），没有该类清单时返回 None
+fn patterns(root: &Path, kind: WorkspaceKind) -> Option<(Vec<String>, Vec<String>)> {
+    match kind {
+        WorkspaceKind::Pnpm => {
+            let content = std::fs::read_to_string(root.join("pnpm-workspace.yaml")).ok()?;
+            let (exclude, include): (Vec<String>, Vec<String>) = parse_pnpm_packages(&content)
+                .into_iter()
+                .partition(|pattern| pattern.starts_with('!'));
+            let exclude = exclude.into_iter().map(|pattern| pattern[1..].to_string()).collect();
+            Some((include, exclude))
+        }
+        WorkspaceKind::Cargo => {
+            let content = std::fs::read_to_string(root.join("Cargo.toml")).ok()?;
+            let manifest: toml::Value = toml::from_str(&content).ok()?;
+            let workspace = manifest.get("workspace")?;
+            Some((string_array(workspace.get("members")), string_array(workspace.get("exclude"))))
+        }
+        WorkspaceKind::Lerna => {
+            let content = std::fs::read_to_string(root.join("lerna.json")).ok()?;
+            let lerna: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let packages = lerna.get("packages")
+                .and_then(|packages| packages.as_array())
+                .map(|packages| packages.iter().filter_map(|p| p.as_str().map(str::to_string)).collect())
+                .unwrap_or_else(|| vec!["packages/*".to_string()]);
+            Some((packages, Vec::new()))
+        }
+        WorkspaceKind::GoWork => {
+            let content = std::fs::read_to_string(root.join("go.work")).ok()?;
+            Some((parse_go_work_uses(&content), Vec::new()))
+        }
+    }
+}
+
+/// TOML 字符串数组
+fn string_array(value: Option<&toml::Value>) -> Vec<String> {
+    value.and_then(|value| value.as_array())
+        .map(|items| items.iter().filter_map(|item| item.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// 解析 pnpm-workspace.yaml 中 `packages:` 下的列表项
+fn parse_pnpm_packages(content: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+
+    for line in content.lines() {
+        let line = line.split(" #").next().unwrap_or_default();
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            packages.push(item.trim().trim_matches(['\'', '"']).to_string());
+        }
+    }
+
+    packages
+}
+
+/// 解析 go.work 中的 `use` 指令（单行和括号块两种写法）
+fn parse_go_work_uses(content: &str) -> Vec<String> {
+    let mut uses = Vec::new();
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() {
+                uses.push(line.trim_matches('"').to_string());
+            }
+        } else if let Some(rest) = line.strip_prefix("use").filter(|rest| rest.starts_with([' ', '\t', '('])) {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+            } else if !rest.is_empty() {
+                uses.push(rest.trim_matches('"').to_string());
+            }
+        }
+    }
+
+    uses
+}
+
+/// 将相对 `root` 的成员模式展开为已存在的目录
+fn expand(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let pattern = pattern.trim().trim_start_matches("./").trim_end_matches('/');
+    let components: Vec<&str> = pattern.split('/').filter(|c| !c.is_empty() && *c != ".").collect();
+    let mut matches = Vec::new();
+    expand_components(root, &components, 0, &mut matches);
+    matches
+}
+
+fn expand_components(dir: &Path, components: &[&str], depth: usize, matches: &mut Vec<PathBuf>) {
+    let Some((first, rest)) = components.split_first() else {
+        matches.push(dir.to_path_buf());
+        return;
+    };
+
+    match *first {
+        "**" => {
+            // `**` 匹配零级或多级目录
+            expand_components(dir, rest, depth, matches);
+            if depth < MAX_GLOB_DEPTH {
+                for subdir in subdirs(dir) {
+                    expand_components(&subdir, components, depth + 1, matches);
+                }
+            }
+        }
+        component if component.contains(['*', '?']) => {
+            for subdir in subdirs(dir) {
+                let name = subdir.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                if wildcard_match(component, &name) {
+                    expand_components(&subdir, rest, depth + 1, matches);
+                }
+            }
+        }
+        ".." => {
+            if let Some(parent) = dir.parent() {
+                expand_components(parent, rest, depth, matches);
+            }
+        }
+        component => {
+            let next = dir.join(component);
+            if next.is_dir() {
+                expand_components(&next, rest, depth + 1, matches);
+            }
+        }
+    }
+}
+
+/// 可以作为成员的子目录（跳过隐藏目录和依赖、构建目录）
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut subdirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str())
+        })
+        .map(|entry| entry.path())
+        .collect();
+    subdirs.sort();
+    subdirs
+}
+
+/// 单级目录名的通配符匹配（`*` 匹配任意个字符，`?` 匹配一个字符）
+pub(crate) fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn create_dirs(root: &Path, dirs: &[&str]) {
+        for dir in dirs {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_pnpm_workspace_members() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        create_dirs(root, &["packages/ui", "packages/utils", "apps/web/e2e", "apps/docs", "packages/ui/node_modules/x"]);
+        fs::write(root.join("pnpm-workspace.yaml"), "packages:\n  - 'packages/*'\n  - \"apps/**\" # 所有应用\n  - '!**/e2e'\ncatalog:\n  - react\n").unwrap();
+
+        assert_eq!(members(root), vec![
+            root.join("apps"),
+            root.join("apps/docs"),
+            root.join("apps/web"),
+            root.join("packages/ui"),
+            root.join("packages/utils"),
+        ]);
+    }
+
+    #[test]
+    fn test_cargo_and_go_workspace_members() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        create_dirs(root, &["crates/core", "crates/cli", "crates/legacy", "tools/gen", "svc/api"]);
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\", \"tools/gen\"]\nexclude = [\"crates/legacy\"]\n").unwrap();
+        fs::write(root.join("go.work"), "go 1.22\n\nuse (\n\t./svc/api // 接口服务\n)\nuse ./tools/gen\n").unwrap();
+
+        assert_eq!(members(root), vec![
+            root.join("crates/cli"),
+            root.join("crates/core"),
+            root.join("svc/api"),
+            root.join("tools/gen"),
+        ]);
+    }
+
+    #[test]
+    fn test_lerna_defaults_to_packages_dir() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        create_dirs(root, &["packages/a", "packages/b"]);
+        fs::write(root.join("lerna.json"), "{\"version\": \"1.0.0\"}").unwrap();
+
+        assert_eq!(members(root), vec![root.join("packages/a"), root.join("packages/b")]);
+    }
+
+    #[test]
+    fn test_plain_project_has_no_members() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("Cargo.toml"), "[package]\nname = \"app\"\n").unwrap();
+        assert!(members(temp_dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("pkg-*", "pkg-ui"));
+        assert!(wildcard_match("a?c", "abc"));
+        assert!(!wildcard_match("pkg-*", "app"));
+        assert!(wildcard_match("*-service*", "auth-service-v2"));
+    }
+}
@@ -14,10 +14,59 @@ use crossterm::{
     execute,
 };
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
 
 use crate::config::Config;
-use crate::models::{Project, DependencyCalculationStatus};
-use crate::scanner::FileWalker;
+use crate::config::settings::{IgnoreSource, RowDensity, SortField};
+use crate::models::{Project, BranchInfo, ByteSize, CleanupSuggestion, DependencyCalculationStatus, DependencyInfo, DependencyType, PackageStats, SuggestionStatus};
+use crate::operations::archive::{ArchiveOperation, ConflictPolicy};
+use crate::operations::cleanup::{CleanupOperation, CleanupPlan, DeleteProgressCallback};
+use crate::operations::deleter::DeleteProgress;
+use crate::operations::git_fetch::{self, FetchStatus};
+use crate::operations::telemetry::{UsageEvent, UsageKind, UsageLog};
+use crate::operations::project_report;
+use crate::operations::scan_output::{ExportFormat, ScanRecord};
+use crate::operations::suggest::{self, CleanupCandidate};
+use crate::operations::wizard::{CleanupWizard, WizardAction};
+use crate::scanner::{run_git_task, ConcurrencyLimiter, FileWalker, GitAnalyzer, SizeCache, SizeCalculator, SizeTreeNode};
+use crate::scanner::git_task::max_concurrent_git_tasks;
+use crate::scanner::incremental::{self, ProjectChange};
+use crate::scanner::manifest_analyzer;
+use crate::scanner::stream;
+use crate::scanner::watcher::{ProjectWatcher, WatchEvent, WatchedProject};
+use crate::operations::cleanup_strategy;
+use crate::scanner::access::{self, SkippedLocations};
+use crate::utils::{editor, path_format, signal};
+use crate::utils::clipboard::{self, CopyMethod};
+use crate::utils::metrics::{metrics, MetricRow, MetricsSnapshot};
+
+/// 清理建议的最小目录大小（小于该值的忽略目录不值得提示）
+const CLEANUP_SUGGESTION_MIN_SIZE: ByteSize = ByteSize::new(1024 * 1024);
+
+/// 状态栏中路径的最大显示宽度
+const STATUS_PATH_WIDTH: usize = 40;
+
+/// 详情页中显示的最近提交数量
+const RECENT_COMMITS_LIMIT: usize = 20;
+
+/// 磁盘占用明细中每个目录列出的最大子项数量
+const DISK_USAGE_TOP_N: usize = 30;
+
+/// 清理向导中依次处理的候选项目数量
+const WIZARD_CANDIDATES: usize = 10;
+
+/// 浏览快照时执行会读取或修改磁盘的操作的提示
+const SNAPSHOT_READ_ONLY: &str = "正在浏览快照（只读），该操作不可用";
+
+/// 清理后释放空间徽标的显示时长
+const SIZE_BADGE_DURATION: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// 退出时等待后台任务结束的最长时间
+const TASK_SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// 调试浮层刷新指标的间隔
+const METRICS_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
 /// 统一的进度信息结构
 #[derive(Clone, Debug)]
@@ -31,7 +80,6 @@ pub struct ProgressInfo {
     /// 当前处理的项目/文件名
     pub current_item: String,
     /// 额外的状态信息
-    #[allow(dead_code)]
     pub extra_info: String,
 }
 
@@ -46,6 +94,8 @@ pub enum ProgressType {
     Discovering,
     /// 计算大小
     Calculating,
+    /// 删除依赖目录
+    Deleting,
 }
 
 impl Default for ProgressInfo {
@@ -69,10 +119,15 @@ impl ProgressInfo {
                 format!("扫描中: {} 个项目", self.current)
             }
             ProgressType::Discovering => {
-                if let Some(total) = self.total {
+                let found = if let Some(total) = self.total {
                     format!("发现文件: {}/{}", self.current, total)
                 } else {
                     format!("发现文件: {}", self.current)
+                };
+                if self.extra_info.is_empty() {
+                    found
+                } else {
+                    format!("{} ({})", found, self.extra_info)
                 }
             }
             ProgressType::Calculating => {
@@ -89,15 +144,35 @@ impl ProgressInfo {
                     format!("计算 {}: {} 文件", item_display, self.current)
                 }
             }
+            ProgressType::Deleting => {
+                format!(
+                    "删除 {}: {}/{} 文件 ({})",
+                    self.current_item,
+                    self.current,
+                    self.total.unwrap_or(0),
+                    self.extra_info
+                )
+            }
         }
     }
 }
 use crate::tui::events::{Event, EventHandler, keys};
+use crate::tui::expansion;
+use crate::tui::filter::{self, PinnedOrder, ProjectFilter, ProjectSort};
+use crate::tui::git_hydration::GitHydration;
+use crate::tui::grouping::{GroupRow, ProjectGroups};
+use crate::tui::ignore_editor::{IgnoreChange, IgnoreEditor, IgnoreSection};
+use crate::tui::keymap;
 use crate::tui::screens::MainScreen;
+use crate::tui::screens::disk_usage::DiskUsageView;
+use crate::tui::screens::main_screen::ProjectListView;
+use crate::tui::session::{self, SessionSnapshot};
+use crate::tui::tasks::{Task, TaskContext, TaskId, TaskKind, TaskRegistry, TaskStatus};
 use tokio::sync::mpsc;
-use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// 应用程序状态
 #[derive(Debug, Clone, PartialEq)]
@@ -114,15 +189,36 @@ pub enum AppState {
     /// 显示项目详情
     ProjectDetail,
     
+    /// 显示项目分支列表
+    BranchList,
+    
+    /// 显示项目磁盘占用明细
+    DiskUsage,
+    
     /// 显示帮助信息
     Help,
     
+    /// 编辑忽略列表
+    IgnoreList,
+    
+    /// 后台任务面板
+    TaskList,
+    
     /// 确认对话框
     ConfirmDialog,
     
+    /// 清理向导
+    CleanupWizard,
+    
     /// 外部编辑器状态
     ExternalEditor,
     
+    /// 退出时仍有操作在进行，等待用户选择
+    ConfirmQuit,
+    
+    /// 等待进行中的操作完成后退出
+    WaitingForOperations,
+    
     /// 错误状态
     #[allow(dead_code)]
     Error(String),
@@ -131,12 +227,113 @@ pub enum AppState {
     Quitting,
 }
 
+/// 确认对话框对应的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfirmAction {
+    /// 删除选中的项目
+    DeleteProject,
+    
+    /// 按清理计划清理选中项目的依赖目录
+    CleanProject,
+    
+    /// 批量删除已合并的分支
+    DeleteMergedBranches,
+    
+    /// 接受清理建议，删除建议中的目录
+    AcceptCleanupSuggestion(PathBuf),
+    
+    /// 选中的项目已被手动忽略，确认后取消忽略并继续原来的操作
+    UnignoreProject(Box<ConfirmAction>),
+}
+
+/// 帮助页面的搜索与滚动状态
+#[derive(Debug, Clone, Default)]
+struct HelpView {
+    /// 搜索关键字
+    query: String,
+    
+    /// 是否正在输入搜索关键字
+    editing: bool,
+    
+    /// 滚动偏移（行）
+    scroll: usize,
+}
+
+/// 导出对话框：输入导出文件路径，Tab 切换格式
+#[derive(Debug, Clone)]
+struct ExportPrompt {
+    /// 导出文件路径（相对路径以当前目录为准）
+    path: String,
+    
+    /// 导出格式，路径的扩展名可以识别时以扩展名为准
+    format: ExportFormat,
+}
+
+impl ExportPrompt {
+    /// 默认导出到当前目录下带时间戳的 JSON 文件
+    fn new() -> Self {
+        let format = ExportFormat::Json;
+        Self {
+            path: format!("projects-{}.{}", chrono::Local::now().format("%Y%m%d-%H%M%S"), format.extension()),
+            format,
+        }
+    }
+    
+    /// 切换到下一种格式，同时替换路径的扩展名
+    fn cycle_format(&mut self) {
+        self.format = self.format.next();
+        let mut path = PathBuf::from(&self.path);
+        path.set_extension(self.format.extension());
+        self.path = path.to_string_lossy().to_string();
+    }
+}
+
+/// 调试浮层：定期读取内部运行指标，根据两次读数计算吞吐量
+struct DebugOverlay {
+    /// 上一次读数
+    previous: MetricsSnapshot,
+    
+    /// 上一次读数的时间
+    sampled_at: std::time::Instant,
+    
+    /// 当前显示的指标
+    rows: Vec<MetricRow>,
+}
+
+impl DebugOverlay {
+    fn new() -> Self {
+        let current = metrics().snapshot();
+        Self {
+            previous: current,
+            sampled_at: std::time::Instant::now(),
+            rows: current.rows(&current, std::time::Duration::ZERO),
+        }
+    }
+    
+    /// 距上次读数超过刷新间隔时重新读取，返回是否有更新
+    fn sample(&mut self) -> bool {
+        let elapsed = self.sampled_at.elapsed();
+        if elapsed < METRICS_SAMPLE_INTERVAL {
+            return false;
+        }
+        
+        let current = metrics().snapshot();
+        self.rows = current.rows(&self.previous, elapsed);
+        self.previous = current;
+        self.sampled_at = std::time::Instant::now();
+        true
+    }
+}
+
 /// 主应用程序
 pub struct App {
     /// 应用配置
     #[allow(dead_code)]
     config: Config,
     
+    /// 配置文件路径（`--config` 指定的文件或默认路径），配置修改写回这里
+    config_path: Option<PathBuf>,
+    
     /// 当前状态
     state: AppState,
     
@@ -149,6 +346,12 @@ pub struct App {
     /// 扫描的路径
     scan_paths: Vec<String>,
     
+    /// 限制同时计算大小的项目数（对应配置 `scan.concurrent_scans`）
+    size_limiter: ConcurrencyLimiter,
+    
+    /// 正在计算详细信息的项目及其后台任务，选中项目时按 Esc 可单独取消
+    detail_tasks: HashMap<PathBuf, TaskId>,
+    
     /// 状态消息
     status_message: String,
     
@@ -171,15 +374,110 @@ pub struct App {
     /// 主屏幕
     main_screen: MainScreen,
     
-    /// 项目计算任务管理
-    calculation_tasks: HashMap<String, JoinHandle<()>>,
+    /// 后台任务登记表
+    tasks: TaskRegistry,
     
-    /// 最大并发任务数限制
-    #[allow(dead_code)]
-    max_concurrent_tasks: usize,
+    /// 任务面板中选中的任务（按显示顺序）
+    selected_task: usize,
     
     /// 取消令牌，用于优雅退出任务
     cancellation_token: CancellationToken,
+    
+    /// 监视扫描路径的后台任务的取消令牌，未启用监视时为 None
+    watcher_token: Option<CancellationToken>,
+    
+    /// 详情页中当前选中的清理建议索引（仅针对待处理建议）
+    selected_suggestion: usize,
+    
+    /// 详情页中提交记录面板的滚动偏移
+    commit_log_scroll: usize,
+    
+    /// 分支列表视图中的分支
+    branches: Vec<BranchInfo>,
+    
+    /// 分支列表视图中选中的分支索引
+    selected_branch: usize,
+    
+    /// 磁盘占用明细视图
+    disk_usage: Option<DiskUsageView>,
+    
+    /// 确认对话框待执行的操作
+    confirm_action: ConfirmAction,
+    
+    /// 项目列表过滤条件
+    filter: ProjectFilter,
+    
+    /// 是否正在输入项目搜索关键字
+    searching: bool,
+    
+    /// 正在输入的导出文件路径，未在导出时为 None
+    export_prompt: Option<ExportPrompt>,
+    
+    /// 正在输入的标签，未在设置标签时为 None
+    tag_input: Option<String>,
+    
+    /// 项目列表排序方式
+    sort: ProjectSort,
+    
+    /// 等待确认的清理计划（项目路径和计划）
+    cleanup_plan: Option<(PathBuf, CleanupPlan)>,
+    
+    /// 扫描和大小计算进行期间固定的列表顺序，全部完成后恢复按排序方式排列
+    pinned_order: Option<PinnedOrder>,
+    
+    /// 调试浮层（F12 切换），显示内部运行指标
+    debug_overlay: Option<DebugOverlay>,
+    
+    /// 被标记的项目路径（用于限定统计范围等批量操作）
+    marked_projects: HashSet<PathBuf>,
+    
+    /// 项目列表是否按工作区/扫描根目录分组显示
+    grouped: bool,
+    
+    /// 已折叠的分组
+    collapsed_groups: HashSet<Option<PathBuf>>,
+    
+    /// 在列表中就地展开依赖目录的项目路径
+    expanded_projects: HashSet<PathBuf>,
+    
+    /// 当前列表是否仍是上次会话的快照（尚未与新的扫描结果核对）
+    snapshot_stale: bool,
+    
+    /// 进行中的清理向导
+    cleanup_wizard: Option<CleanupWizard>,
+    
+    /// 清理向导中归档目标已存在时，等待选择覆盖、重命名或跳过的已有目录
+    archive_conflict: Option<PathBuf>,
+    
+    /// 最近清理释放的空间（在项目行旁短暂显示）
+    size_badges: HashMap<PathBuf, (ByteSize, std::time::Instant)>,
+    
+    /// 延迟加载的 Git 信息：先分析可见和选中的项目，其余在后台补全
+    git_hydration: GitHydration,
+    
+    /// 各仓库最近一次 git fetch 的状态
+    git_fetch: HashMap<PathBuf, FetchStatus>,
+    
+    /// 本地使用统计（[telemetry] 未开启时不记录）
+    usage: UsageLog,
+    
+    /// 帮助页面状态
+    help: HelpView,
+    
+    /// 忽略列表编辑界面状态
+    ignore_editor: IgnoreEditor,
+    
+    /// 请求退出时仍在进行的破坏性操作（退出确认对话框中列出）
+    quit_tasks: Vec<TaskId>,
+    
+    /// 正在后台删除的依赖目录及其进度
+    deletion_progress: HashMap<PathBuf, DeleteProgress>,
+    
+    /// 退出后输出到终端的操作结果
+    exit_report: Vec<String>,
+    
+    /// 正在浏览的快照文件（只读模式：不扫描，也不读取或修改项目目录）
+    snapshot_file: Option<PathBuf>,
 }
 
 /// 视图标签
@@ -193,59 +491,188 @@ pub enum TabView {
     
     /// Git 状态
     GitStatus,
+    
+    /// 扫描根目录汇总
+    ScanRoots,
 }
 
 impl App {
     /// 创建新的应用程序
     pub fn new(config: Config, scan_paths: Vec<String>) -> Self {
+        let mut main_screen = MainScreen::new();
+        main_screen.set_row_density(config.display.row_density);
+        main_screen.set_show_ratio_bar(config.display.show_ratio_bar);
+        let filter = ProjectFilter {
+            show_hidden: config.display.show_hidden,
+            ..ProjectFilter::default()
+        };
+        let sort = ProjectSort::from_config(&config.display);
+        
+        let size_limiter = ConcurrencyLimiter::new(config.scan.concurrent_scans);
+        let usage = UsageLog::new(config.telemetry.enabled);
+        
         Self {
             config,
+            config_path: None,
             state: AppState::Starting,
             projects: Vec::new(),
             selected_project: 0,
             scan_paths,
+            size_limiter,
+            detail_tasks: HashMap::new(),
             status_message: "正在启动...".to_string(),
             show_details: false,
             current_tab: TabView::Projects,
             scan_progress: String::new(),
             progress_info: ProgressInfo::default(),
             event_handler: EventHandler::new(),
-            main_screen: MainScreen::new(),
-            calculation_tasks: HashMap::new(),
-            max_concurrent_tasks: 10, // 限制最多10个并发计算任务
+            main_screen,
+            tasks: TaskRegistry::default(),
+            selected_task: 0,
             cancellation_token: CancellationToken::new(),
+            watcher_token: None,
+            selected_suggestion: 0,
+            commit_log_scroll: 0,
+            branches: Vec::new(),
+            selected_branch: 0,
+            disk_usage: None,
+            confirm_action: ConfirmAction::DeleteProject,
+            filter,
+            searching: false,
+            export_prompt: None,
+            tag_input: None,
+            sort,
+            cleanup_plan: None,
+            pinned_order: None,
+            debug_overlay: None,
+            marked_projects: HashSet::new(),
+            grouped: false,
+            collapsed_groups: HashSet::new(),
+            expanded_projects: HashSet::new(),
+            snapshot_stale: false,
+            cleanup_wizard: None,
+            archive_conflict: None,
+            size_badges: HashMap::new(),
+            git_hydration: GitHydration::default(),
+            git_fetch: HashMap::new(),
+            usage,
+            help: HelpView::default(),
+            ignore_editor: IgnoreEditor::default(),
+            quit_tasks: Vec::new(),
+            deletion_progress: HashMap::new(),
+            exit_report: Vec::new(),
+            snapshot_file: None,
         }
     }
     
+    /// 设置配置文件路径，未设置时写回默认配置文件
+    pub fn with_config_path(mut self, config_path: PathBuf) -> Self {
+        self.config_path = Some(config_path);
+        self
+    }
+    
+    /// 以只读模式浏览保存的快照，快照中的目录可能已不存在
+    pub fn from_snapshot(config: Config, snapshot_file: PathBuf, snapshot: SessionSnapshot) -> Self {
+        let mut app = Self::new(config, snapshot.scan_paths);
+        app.projects = snapshot.projects;
+        app.apply_hidden_flags();
+        app.apply_tags();
+        app.apply_ignored_flags();
+        app.apply_ignore_rules();
+        app.state = AppState::ProjectList;
+        app.status_message = format!(
+            "正在浏览快照 {}（保存于 {}，共 {} 个项目），只读模式",
+            path_format::format_path(&snapshot_file, STATUS_PATH_WIDTH),
+            snapshot.saved_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            app.projects.len()
+        );
+        app.snapshot_file = Some(snapshot_file);
+        app
+    }
+    
     /// 运行应用程序
     pub async fn run(&mut self) -> Result<()> {
         // 设置终端
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(stdout, EnterAlternateScreen)?;
+        if self.config.display.mouse {
+            execute!(stdout, EnableMouseCapture)?;
+        }
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
         
         // 启动事件处理
         self.event_handler.start();
         
-        // 开始扫描
-        self.start_scan().await?;
+        // 先显示上次会话的项目列表，再在后台扫描；浏览快照时不扫描
+        if self.snapshot_file.is_none() {
+            self.load_session_snapshot();
+            self.start_scan().await?;
+        }
+        
+        // 收到终止信号时照常退出主循环，保证下面恢复终端
+        let sender = self.event_handler.sender.clone();
+        let signal_listener = tokio::spawn(async move {
+            if signal::wait_for_signal().await.is_ok() {
+                let _ = sender.send(Event::Shutdown);
+            }
+        });
         
         // 主事件循环
         let result = self.main_loop(&mut terminal).await;
+        signal_listener.abort();
         
         // 清理所有运行中的任务
         self.cleanup_all_tasks().await;
+        if self.snapshot_file.is_none() {
+            self.save_session_snapshot();
+        }
         
         // 恢复终端
         disable_raw_mode()?;
-        execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        if self.config.display.mouse {
+            execute!(terminal.backend_mut(), DisableMouseCapture)?;
+        }
         terminal.show_cursor()?;
         
+        for line in &self.exit_report {
+            println!("{}", line);
+        }
+        
         result
     }
     
+    /// 加载上次会话的项目列表快照
+    fn load_session_snapshot(&mut self) {
+        let Ok(snapshot) = SessionSnapshot::default_path()
+            .and_then(|path| SessionSnapshot::load_from_file(&path))
+        else {
+            return;
+        };
+        
+        self.projects = snapshot.projects_under(&self.scan_paths);
+        self.apply_hidden_flags();
+        self.apply_tags();
+        self.apply_ignored_flags();
+        self.apply_ignore_rules();
+    }
+    
+    /// 保存当前项目列表快照，供下次启动时立即显示
+    fn save_session_snapshot(&self) {
+        // 扫描尚未完成时保留原快照
+        if self.snapshot_stale || self.projects.is_empty() {
+            return;
+        }
+        
+        let saved = SessionSnapshot::default_path()
+            .and_then(|path| SessionSnapshot::new(&self.projects).save_to_file(&path));
+        if let Err(e) = saved {
+            tracing::warn!("保存会话快照失败: {}", e);
+        }
+    }
+    
     /// 主事件循环
     async fn main_loop(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
         let mut needs_redraw = true; // 首次绘制
@@ -259,16 +686,27 @@ impl App {
                 needs_redraw = false;
                 last_redraw = std::time::Instant::now();
                 
-                // 定期清理已完成的任务
-                self.cleanup_finished_tasks();
+                // 更新后台任务状态
+                self.tasks.reap();
+                
+                // 为刚显示出来的项目加载 Git 信息
+                self.hydrate_git_info();
             }
             
             // 处理事件
             match self.event_handler.next().await? {
                 Event::Key(key) => {
-                    if keys::is_quit_key(&key) {
-                        self.state = AppState::Quitting;
-                        break;
+                    // 输入搜索关键字时 q 作为普通字符，Ctrl+C 仍然退出
+                    let typing = ((self.state == AppState::Help && self.help.editing)
+                        || (self.state == AppState::ProjectList && (self.searching || self.export_prompt.is_some() || self.tag_input.is_some()))
+                        || (self.state == AppState::IgnoreList && self.ignore_editor.input.is_some()))
+                        && !key.modifiers.contains(crossterm::event::KeyModifiers::CONTROL);
+                    if keys::is_quit_key(&key) && !typing {
+                        if self.request_quit() {
+                            break;
+                        }
+                        needs_redraw = true;
+                        continue;
                     }
                     
                     let force_redraw = self.handle_key_event(key).await?;
@@ -289,12 +727,37 @@ impl App {
                     // 终端大小调整需要重绘
                     needs_redraw = true;
                 }
+                Event::Shutdown => {
+                    self.handle_shutdown_signal();
+                    break;
+                }
                 Event::ScanComplete => {
-                    self.state = AppState::ProjectList;
+                    if matches!(self.state, AppState::Starting | AppState::Scanning) {
+                        self.state = AppState::ProjectList;
+                    }
+                    self.snapshot_stale = false;
                     self.status_message = format!("扫描完成！发现 {} 个项目", self.projects.len());
+                    self.usage.record(UsageEvent::new(UsageKind::Scan).with_projects(self.projects.len()));
                     // 清理扫描进度状态
                     self.progress_info = ProgressInfo::default();
                     self.scan_progress.clear();
+                    self.update_pinned_order();
+                    self.restart_watcher();
+                    needs_redraw = true;
+                }
+                Event::ScanFailed(message) => {
+                    // 保留当前列表，列表仍标记为过期
+                    if matches!(self.state, AppState::Starting | AppState::Scanning) {
+                        self.state = AppState::ProjectList;
+                    }
+                    self.status_message = message;
+                    self.progress_info = ProgressInfo::default();
+                    self.scan_progress.clear();
+                    self.update_pinned_order();
+                    needs_redraw = true;
+                }
+                Event::Watch(event) => {
+                    self.handle_watch_event(event).await?;
                     needs_redraw = true;
                 }
                 Event::ScanProgress(progress) => {
@@ -315,12 +778,40 @@ impl App {
                     self.scan_progress = progress;
                     needs_redraw = true;
                 }
-                Event::ProjectFound(project) => {
+                Event::ScanResults(projects) => {
+                    self.apply_scan_results(projects).await?;
+                    needs_redraw = true;
+                }
+                Event::LocationsSkipped(summary) => {
+                    self.status_message = format!("{}；{}", self.status_message, summary);
+                    needs_redraw = true;
+                }
+                Event::DeletionProgress { path, progress } => {
+                    self.update_deletion_progress(path, progress);
+                    needs_redraw = true;
+                }
+                Event::ProjectFound(mut project) => {
+                    project.is_hidden = self.is_hidden_path(&project.path);
+                    project.tags = self.config.display.tags_for(&project.path);
+                    project.is_ignored = self.config.ignore.projects.contains(&project.path.display().to_string());
+                    let target = (!project.is_ignored).then(|| (project.path.clone(), project.name.clone()));
                     self.projects.push(project);
+                    if let Some((project_path, project_name)) = target {
+                        self.spawn_details_calculation(project_path, project_name);
+                    }
+                    self.update_pinned_order();
+                    needs_redraw = true;
+                }
+                Event::ProjectUpdated(project) => {
+                    self.apply_project_update(project);
+                    needs_redraw = true;
+                }
+                Event::ProjectRemoved(path) => {
+                    self.remove_project(&path);
                     needs_redraw = true;
                 }
                 Event::ProjectSizeUpdated { 
-                    project_index, 
+                    project_path, 
                     code_size, 
                     total_size, 
                     gitignore_excluded_size,
@@ -328,9 +819,11 @@ impl App {
                     dependency_file_count,
                     total_file_count,
                     gitignore_excluded_file_count,
+                    size_truncated,
+                    last_modified,
                 } => {
                     // 更新指定项目的大小信息
-                    if let Some(project) = self.projects.get_mut(project_index) {
+                    if let Some(project) = self.projects.iter_mut().find(|p| p.path == project_path) {
                         project.code_size = code_size;
                         project.total_size = total_size;
                         project.gitignore_excluded_size = gitignore_excluded_size;
@@ -338,6 +831,10 @@ impl App {
                         project.dependency_file_count = dependency_file_count;
                         project.total_file_count = total_file_count;
                         project.gitignore_excluded_file_count = gitignore_excluded_file_count;
+                        project.size_truncated = size_truncated;
+                        if let Some(modified) = last_modified {
+                            project.last_modified = modified;
+                        }
                     }
                     needs_redraw = true;
                 }
@@ -351,7 +848,8 @@ impl App {
                     dependency_file_count,
                     total_file_count,
                     gitignore_excluded_file_count,
-                    git_info,
+                    size_truncated,
+                    last_modified,
                 } => {
                     // 找到对应的项目并更新其详细信息
                     if let Some(project) = self.projects.iter_mut().find(|p| p.name == project_name) {
@@ -362,9 +860,24 @@ impl App {
                         project.dependency_file_count = dependency_file_count;
                         project.total_file_count = total_file_count;
                         project.gitignore_excluded_file_count = gitignore_excluded_file_count;
-                        project.git_info = git_info;
+                        project.size_is_estimate = false;
+                        project.size_truncated = size_truncated;
                         project.cached_dependency_size = Some(dependency_size); // 更新缓存的依赖大小
                         project.dependency_calculation_status = DependencyCalculationStatus::Completed;
+                        if let Some(modified) = last_modified {
+                            project.last_modified = modified;
+                        }
+                    }
+                    self.update_pinned_order();
+                    needs_redraw = true;
+                }
+                Event::ProjectSizeEstimated { project_name, estimated_size } => {
+                    // 只在精确结果到达前显示估算值
+                    if let Some(project) = self.projects.iter_mut().find(|p| p.name == project_name) {
+                        if project.code_size.is_zero() && project.dependency_calculation_status != DependencyCalculationStatus::Completed {
+                            project.code_size = estimated_size;
+                            project.size_is_estimate = true;
+                        }
                     }
                     needs_redraw = true;
                 }
@@ -375,6 +888,93 @@ impl App {
                     }
                     needs_redraw = true;
                 }
+                Event::CleanupSuggestionsReady { project_name, suggestions } => {
+                    if let Some(project) = self.projects.iter_mut().find(|p| p.name == project_name) {
+                        project.cleanup_suggestions = Some(suggestions);
+                    }
+                    self.selected_suggestion = 0;
+                    needs_redraw = true;
+                }
+                Event::CleanupSuggestionApplied { project_name, path, freed_size } => {
+                    if let Some(project) = self.projects.iter_mut().find(|p| p.name == project_name) {
+                        if let Some(suggestion) = project.cleanup_suggestions.as_mut()
+                            .and_then(|suggestions| suggestions.iter_mut().find(|s| s.path == path))
+                        {
+                            suggestion.status = SuggestionStatus::Accepted;
+                        }
+                        self.usage.record(UsageEvent::new(UsageKind::Clean)
+                            .with_directories(1)
+                            .with_freed(freed_size)
+                            .with_project_type(project.project_type.as_str()));
+                        project.gitignore_excluded_size -= freed_size;
+                        let path = project.path.clone();
+                        self.add_size_badge(path, freed_size);
+                    }
+                    self.clamp_selected_suggestion();
+                    needs_redraw = true;
+                }
+                Event::DiskUsageReady { project_path, tree } => {
+                    self.show_disk_usage(project_path, tree);
+                    needs_redraw = true;
+                }
+                Event::CleanupPlanReady { project_path, plan } => {
+                    self.show_cleanup_plan(project_path, plan);
+                    needs_redraw = true;
+                }
+                Event::CleanupCompleted { project_path, freed_size } => {
+                    if let Some(project) = self.projects.iter().find(|p| p.path == project_path) {
+                        self.status_message = format!(
+                            "已清理项目 {} 的依赖，释放了 {} 空间",
+                            project.name,
+                            freed_size
+                        );
+                        self.usage.record(UsageEvent::new(UsageKind::Clean)
+                            .with_directories(project.dependencies.len())
+                            .with_freed(freed_size)
+                            .with_project_type(project.project_type.as_str()));
+                    }
+                    self.apply_cleanup_completed(&project_path, freed_size);
+                    needs_redraw = true;
+                }
+                Event::CleanupWizardStepDone { path, action, result } => {
+                    self.apply_cleanup_wizard_result(path, action, result);
+                    needs_redraw = true;
+                }
+                Event::GitInfoLoaded { project_path, git_info } => {
+                    self.git_hydration.finish(&project_path);
+                    if let Some(project) = self.projects.iter_mut().find(|p| p.path == project_path) {
+                        project.git_info = git_info;
+                    }
+                    // 详情页先于 Git 信息打开时，补充加载依赖 Git 的内容
+                    if self.state == AppState::ProjectDetail
+                        && self.projects.get(self.selected_project).is_some_and(|p| p.path == project_path)
+                    {
+                        self.load_recent_commits();
+                        self.load_cleanup_suggestions();
+                    }
+                    self.update_pinned_order();
+                    needs_redraw = true;
+                }
+                Event::GitFetchUpdated { project_path, status } => {
+                    self.update_git_fetch(project_path, status);
+                    needs_redraw = true;
+                }
+                Event::RecentCommitsReady { project_name, commits } => {
+                    if let Some(project) = self.projects.iter_mut().find(|p| p.name == project_name) {
+                        project.recent_commits = Some(commits);
+                    }
+                    needs_redraw = true;
+                }
+                Event::PackageStatsReady { project_path, stats } => {
+                    if let Some(project) = self.projects.iter_mut().find(|p| p.path == project_path) {
+                        for (dependency_path, package_stats) in stats {
+                            if let Some(dependency) = project.dependencies.iter_mut().find(|d| d.path == dependency_path) {
+                                dependency.packages = Some(package_stats);
+                            }
+                        }
+                    }
+                    needs_redraw = true;
+                }
                 Event::Refresh => {
                     self.start_scan().await?;
                     needs_redraw = true;
@@ -386,7 +986,7 @@ impl App {
                     project_name,
                     processed_files,
                     total_files,
-                    current_path: _,
+                    current_path,
                     bytes_processed: _,
                     stage,
                 } => {
@@ -399,6 +999,13 @@ impl App {
                     self.progress_info.current = processed_files;
                     self.progress_info.total = total_files;
                     self.progress_info.current_item = project_name.clone();
+                    // 发现阶段显示正在遍历的目录名
+                    self.progress_info.extra_info = match stage {
+                        crate::scanner::ScanStage::Discovery => Path::new(&current_path).file_name()
+                            .map(|name| name.to_string_lossy().to_string())
+                            .unwrap_or_default(),
+                        _ => String::new(),
+                    };
                     
                     // 保持旧的扫描进度信息作为后备（兼容性）
                     if stage == crate::scanner::ScanStage::Completed {
@@ -422,6 +1029,33 @@ impl App {
                 Event::Tick => {
                     // 定时更新，不需要每次都重绘
                     // 只有在有变化时才需要重绘
+                    let badge_count = self.size_badges.len();
+                    self.size_badges.retain(|_, (_, shown_at)| shown_at.elapsed() < SIZE_BADGE_DURATION);
+                    if self.size_badges.len() != badge_count {
+                        needs_redraw = true;
+                    }
+                    
+                    metrics().event_queue_depth.set(self.event_handler.pending() as i64);
+                    if let Some(overlay) = self.debug_overlay.as_mut() {
+                        needs_redraw |= overlay.sample();
+                    }
+                    
+                    if self.state == AppState::WaitingForOperations {
+                        self.finish_waiting_if_done();
+                        needs_redraw = true;
+                    } else if self.state == AppState::TaskList {
+                        needs_redraw = true;
+                    }
+                    
+                    // 删除失败的目录不会报告完成，操作全部结束后清除残留的进度
+                    let destructive_running = self.tasks.active().any(|task| task.kind.is_destructive());
+                    if !destructive_running && !self.deletion_progress.is_empty() {
+                        self.deletion_progress.clear();
+                        if self.progress_info.progress_type == ProgressType::Deleting {
+                            self.progress_info = ProgressInfo::default();
+                        }
+                        needs_redraw = true;
+                    }
                 }
             }
             
@@ -457,6 +1091,14 @@ impl App {
     async fn handle_key_event(&mut self, key: crossterm::event::KeyEvent) -> Result<bool> {
         let mut needs_redraw = false;
         
+        if keys::is_debug_key(&key) {
+            self.debug_overlay = match self.debug_overlay {
+                Some(_) => None,
+                None => Some(DebugOverlay::new()),
+            };
+            return Ok(false);
+        }
+        
         match self.state {
             AppState::ProjectList => {
                 needs_redraw = self.handle_project_list_keys(key).await?;
@@ -464,17 +1106,37 @@ impl App {
             AppState::ProjectDetail => {
                 self.handle_project_detail_keys(key).await?;
             }
+            AppState::BranchList => {
+                self.handle_branch_list_keys(key);
+            }
+            AppState::DiskUsage => {
+                self.handle_disk_usage_keys(key);
+            }
             AppState::Help => {
-                if keys::is_enter_key(&key) || keys::is_help_key(&key) {
-                    self.state = AppState::ProjectList;
-                }
+                self.handle_help_keys(key);
+            }
+            AppState::IgnoreList => {
+                self.handle_ignore_list_keys(key);
+            }
+            AppState::TaskList => {
+                self.handle_task_list_keys(key);
             }
             AppState::ConfirmDialog => {
                 self.handle_confirm_dialog_keys(key).await?;
             }
+            AppState::CleanupWizard => {
+                self.handle_cleanup_wizard_keys(key);
+            }
+            AppState::ConfirmQuit => {
+                self.handle_confirm_quit_keys(key);
+            }
+            AppState::WaitingForOperations if key.code == crossterm::event::KeyCode::Esc => {
+                self.state = AppState::ProjectList;
+                self.status_message = "已取消退出，操作继续在后台进行".to_string();
+            }
             AppState::ExternalEditor => {
                 // 在外部编辑器状态下，不处理任何键盘事件
-                // 事件处理将在spawn_nvim方法中完成后自动恢复
+                // 事件处理将在open_in_editor方法中完成后自动恢复
             }
             _ => {}
         }
@@ -484,120 +1146,1575 @@ impl App {
     
     /// 处理项目列表键盘事件（返回true表示需要强制重绘）
     async fn handle_project_list_keys(&mut self, key: crossterm::event::KeyEvent) -> Result<bool> {
-        if keys::is_up_key(&key) && self.selected_project > 0 {
-            self.selected_project -= 1;
-        } else if keys::is_down_key(&key) && self.selected_project < self.projects.len().saturating_sub(1) {
-            self.selected_project += 1;
+        use crossterm::event::KeyCode;
+        
+        if self.searching {
+            self.handle_search_keys(key);
+            return Ok(false);
+        }
+        if self.export_prompt.is_some() {
+            self.handle_export_keys(key);
+            return Ok(false);
+        }
+        if self.tag_input.is_some() {
+            self.handle_tag_keys(key);
+            return Ok(false);
+        }
+        if keys::is_search_key(&key) {
+            self.searching = true;
+            self.current_tab = TabView::Projects;
+            return Ok(false);
+        }
+        if key.code == KeyCode::Esc && !self.filter.query.is_empty() {
+            self.filter.query.clear();
+            self.status_message = "已清除搜索".to_string();
+            self.ensure_selection_visible();
+            return Ok(false);
+        }
+        if key.code == KeyCode::Esc && self.current_tab == TabView::Projects {
+            self.cancel_selected_calculation();
+            return Ok(false);
+        }
+        
+        let fetches = self.current_tab == TabView::GitStatus
+            && (keys::is_fetch_key(&key) || keys::is_fetch_all_key(&key));
+        let touches_disk = fetches
+            || keys::is_refresh_key(&key)
+            || keys::is_delete_key(&key)
+            || keys::is_clean_key(&key)
+            || keys::is_wizard_key(&key)
+            || keys::is_ignore_key(&key)
+            || keys::is_ignore_list_key(&key)
+            || keys::is_edit_key(&key);
+        if self.snapshot_file.is_some() && touches_disk {
+            self.status_message = SNAPSHOT_READ_ONLY.to_string();
+            return Ok(false);
+        }
+        
+        // 过滤、搜索或隐藏后列表为空时，选中的索引指向看不见的项目，不能对它执行操作
+        let acts_on_selection = keys::is_delete_key(&key)
+            || keys::is_clean_key(&key)
+            || keys::is_hide_key(&key)
+            || keys::is_ignore_key(&key)
+            || keys::is_yank_key(&key)
+            || keys::is_edit_key(&key)
+            || keys::is_mark_key(&key)
+            || keys::is_enter_key(&key)
+            || (keys::is_tag_key(&key) && self.marked_projects.is_empty());
+        if acts_on_selection && !self.selection_visible() {
+            self.status_message = "列表中没有可操作的项目".to_string();
+            return Ok(false);
+        }
+        
+        if keys::is_up_key(&key) {
+            self.move_selection(-1);
+        } else if keys::is_down_key(&key) {
+            self.move_selection(1);
+        } else if keys::is_mark_key(&key) {
+            self.toggle_mark_project();
+        } else if keys::is_sort_key(&key) {
+            self.sort.field = self.sort.field.next();
+            self.save_sort();
+            self.repin_order();
+        } else if keys::is_sort_order_key(&key) {
+            self.sort.descending = !self.sort.descending;
+            self.save_sort();
+            self.repin_order();
+        } else if keys::is_group_key(&key) {
+            self.toggle_grouping();
+        } else if key.code == KeyCode::Left {
+            self.collapse_selected();
+        } else if key.code == KeyCode::Right {
+            self.expand_selected();
+        } else if fetches {
+            self.start_git_fetch(keys::is_fetch_all_key(&key));
+        } else if keys::is_filter_key(&key) {
+            self.toggle_directory_filter();
+        } else if keys::is_stale_filter_key(&key) {
+            self.toggle_stale_filter();
+        } else if keys::is_hide_key(&key) {
+            self.toggle_hide_project();
+        } else if keys::is_export_key(&key) {
+            self.export_prompt = Some(ExportPrompt::new());
+        } else if keys::is_tag_key(&key) {
+            if !self.projects.is_empty() {
+                self.tag_input = Some(String::new());
+            }
+        } else if keys::is_show_hidden_key(&key) {
+            self.filter.show_hidden = !self.filter.show_hidden;
+            self.status_message = if self.filter.show_hidden {
+                "已显示隐藏项目".to_string()
+            } else {
+                "已不再显示隐藏项目".to_string()
+            };
+            self.ensure_selection_visible();
+        } else if keys::is_yank_key(&key) {
+            self.yank_project_path();
         } else if keys::is_enter_key(&key) {
-            self.state = AppState::ProjectDetail;
+            self.open_project_detail();
         } else if keys::is_refresh_key(&key) {
-            self.start_scan().await?;
+            self.start_refresh().await?;
         } else if keys::is_help_key(&key) {
+            self.help = HelpView::default();
             self.state = AppState::Help;
         } else if keys::is_tab_key(&key) {
             self.switch_tab();
+        } else if keys::is_density_key(&key) {
+            let row_density = self.main_screen.row_density().toggle();
+            self.main_screen.set_row_density(row_density);
+            self.status_message = match row_density {
+                RowDensity::Compact => "已切换为紧凑模式".to_string(),
+                RowDensity::Comfortable => "已切换为舒适模式".to_string(),
+            };
+            return Ok(true);
+        } else if keys::is_ratio_bar_key(&key) {
+            let show_ratio_bar = !self.main_screen.show_ratio_bar();
+            self.main_screen.set_show_ratio_bar(show_ratio_bar);
+            self.status_message = if show_ratio_bar {
+                "已显示代码/依赖/gitignore 占比列".to_string()
+            } else {
+                "已隐藏占比列".to_string()
+            };
+            return Ok(true);
         } else if keys::is_delete_key(&key) {
-            if !self.projects.is_empty() {
-                self.state = AppState::ConfirmDialog;
-                self.status_message = "确认删除选中的项目？ (y/N)".to_string();
+            if !self.projects.is_empty() && !self.confirm_unignore_before(ConfirmAction::DeleteProject) {
+                self.request_delete();
             }
         } else if keys::is_clean_key(&key) {
-            if !self.projects.is_empty() {
-                self.clean_current_project().await?;
+            if !self.projects.is_empty() && !self.confirm_unignore_before(ConfirmAction::CleanProject) {
+                self.request_cleanup_plan();
             }
+        } else if keys::is_wizard_key(&key) {
+            self.start_cleanup_wizard();
         } else if keys::is_ignore_key(&key) {
-            if !self.projects.is_empty() {
-                self.toggle_ignore_project().await?;
-            }
-        } else if keys::is_nvim_key(&key) {
-            if !self.projects.is_empty() {
-                if let Some(project) = self.projects.get(self.selected_project) {
-                    let project_path = project.path.clone();
-                    return self.spawn_nvim(&project_path).await;
-                }
+            self.toggle_ignore_project();
+        } else if keys::is_show_ignored_key(&key) {
+            self.filter.show_ignored = !self.filter.show_ignored;
+            self.status_message = if self.filter.show_ignored {
+                "已显示已忽略的项目".to_string()
+            } else {
+                "已不再显示已忽略的项目".to_string()
+            };
+            self.ensure_selection_visible();
+        } else if keys::is_ignore_list_key(&key) {
+            self.ignore_editor = IgnoreEditor::default();
+            self.state = AppState::IgnoreList;
+        } else if keys::is_task_list_key(&key) {
+            self.tasks.reap();
+            self.selected_task = 0;
+            self.state = AppState::TaskList;
+        } else if keys::is_edit_key(&key) {
+            if let Some(project) = self.projects.get(self.selected_project) {
+                let project_path = project.path.clone();
+                let type_keys = editor::project_type_keys(project);
+                return self.open_in_editor(&project_path, &type_keys).await;
             }
         }
         
         Ok(false)
     }
     
-    /// 处理项目列表鼠标事件
-    async fn handle_project_list_mouse(&mut self, mouse: crossterm::event::MouseEvent) -> Result<()> {
-        use crossterm::event::{MouseEventKind, MouseButton};
+    /// 输入搜索关键字：边输入边过滤，Enter 保留过滤结果，Esc 清除搜索
+    fn handle_search_keys(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
         
-        match mouse.kind {
-            // 左键点击
-            MouseEventKind::Down(MouseButton::Left) => {
-                // 检查是否点击在标签栏区域 (前3行)
-                if mouse.row <= 2 {
-                    self.handle_tab_click(mouse.column);
-                }
-                // 检查是否点击在项目列表区域 (第4行开始，考虑表头)
-                else if mouse.row >= 5 && !self.projects.is_empty() {
-                    // 获取当前表格的滚动偏移量
-                    let scroll_offset = self.main_screen.get_table_offset();
-                    
-                    // 计算点击的项目索引（减去标签栏和表头的行数，加上滚动偏移）
-                    let clicked_row_in_view = mouse.row as usize - 5; // 3行标签栏 + 2行表头边框
-                    let clicked_project_index = clicked_row_in_view + scroll_offset;
-                    
-                    // 确保索引在有效范围内
-                    if clicked_project_index < self.projects.len() {
-                        self.selected_project = clicked_project_index;
+        match key.code {
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Esc => {
+                self.searching = false;
+                self.filter.query.clear();
+            }
+            KeyCode::Backspace => {
+                self.filter.query.pop();
+            }
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Char(c) => self.filter.query.push(c),
+            _ => {}
+        }
+        self.ensure_selection_visible();
+    }
+    
+    /// 输入导出文件路径：Tab 切换格式，Enter 导出，Esc 取消
+    fn handle_export_keys(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+        
+        let Some(prompt) = self.export_prompt.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => self.export_projects(),
+            KeyCode::Esc => {
+                self.export_prompt = None;
+                self.status_message = "已取消导出".to_string();
+            }
+            KeyCode::Tab => prompt.cycle_format(),
+            KeyCode::Backspace => {
+                prompt.path.pop();
+            }
+            KeyCode::Char(c) => prompt.path.push(c),
+            _ => {}
+        }
+    }
+    
+    /// 输入标签：Enter 添加或移除，Esc 取消
+    fn handle_tag_keys(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+        
+        let Some(input) = self.tag_input.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Enter => {
+                if let Some(tag) = self.tag_input.take() {
+                    self.toggle_tag(&tag);
+                }
+            }
+            KeyCode::Esc => self.tag_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            // 标签用于命令行参数，不包含空白
+            KeyCode::Char(c) if !c.is_whitespace() => input.push(c),
+            _ => {}
+        }
+    }
+    
+    /// 将当前列表中的项目（按过滤条件和排序方式）写入导出文件
+    fn export_projects(&mut self) {
+        let Some(prompt) = self.export_prompt.take() else {
+            return;
+        };
+        let path = match (prompt.path.trim().strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest),
+            _ => PathBuf::from(prompt.path.trim()),
+        };
+        let format = ExportFormat::from_path(&path).unwrap_or(prompt.format);
+        
+        let records: Vec<ScanRecord> = filter::visible_indices(&self.projects, &self.filter, self.sort, self.pinned_order.as_ref())
+            .into_iter()
+            .map(|index| ScanRecord::from(&self.projects[index]))
+            .collect();
+        let result = format.render(&records)
+            .and_then(|content| std::fs::write(&path, content).map_err(Into::into));
+        
+        self.status_message = match result {
+            Ok(()) => format!("已导出 {} 个项目到 {}", records.len(), path_format::format_path(&path, STATUS_PATH_WIDTH)),
+            Err(e) => format!("导出失败: {}", e),
+        };
+    }
+    
+    /// 处理项目列表鼠标事件
+    async fn handle_project_list_mouse(&mut self, mouse: crossterm::event::MouseEvent) -> Result<()> {
+        use crossterm::event::{MouseEventKind, MouseButton};
+        
+        match mouse.kind {
+            // 左键点击
+            MouseEventKind::Down(MouseButton::Left) => {
+                // 检查是否点击在标签栏区域 (前3行)
+                if mouse.row <= 2 {
+                    self.handle_tab_click(mouse.column);
+                }
+                // 检查是否点击在项目列表区域 (第4行开始，考虑表头)
+                else if mouse.row >= 5 && !self.projects.is_empty() {
+                    // 获取当前表格的滚动偏移量
+                    let scroll_offset = self.main_screen.get_table_offset();
+                    
+                    // 计算点击的项目索引（减去标签栏和表头的行数，加上滚动偏移）
+                    let clicked_row_in_view = mouse.row as usize - 5; // 3行标签栏 + 2行表头边框
+                    let row_height = self.main_screen.row_density().row_height() as usize;
+                    let clicked_position = clicked_row_in_view / row_height + scroll_offset;
+                    
+                    // 确保索引在有效范围内；分组显示时点击分组标题选中组内第一个项目，点击依赖子行选中所属项目
+                    if let Some(groups) = self.project_groups() {
+                        let rows = groups.rows(&self.collapsed_groups);
+                        let child_counts: Vec<usize> = rows.iter()
+                            .map(|row| match row {
+                                GroupRow::Project(index) => expansion::child_rows(&self.projects[*index], &self.expanded_projects),
+                                GroupRow::Header(_) => 0,
+                            })
+                            .collect();
+                        match expansion::base_row(&child_counts, clicked_position).and_then(|row| rows.get(row)) {
+                            Some(GroupRow::Project(index)) => self.selected_project = *index,
+                            Some(GroupRow::Header(position)) => {
+                                let group = &groups.groups[*position];
+                                if !group.projects.contains(&self.selected_project) {
+                                    self.selected_project = group.projects[0];
+                                }
+                            }
+                            None => {}
+                        }
+                    } else {
+                        let visible = self.visible_indices();
+                        let child_counts: Vec<usize> = visible.iter()
+                            .map(|&index| expansion::child_rows(&self.projects[index], &self.expanded_projects))
+                            .collect();
+                        if let Some(&index) = expansion::base_row(&child_counts, clicked_position).and_then(|row| visible.get(row)) {
+                            self.selected_project = index;
+                        }
                     }
                 }
             }
-            // 双击功能需要自己实现，crossterm 没有直接的 DoubleClick 事件
-            // 暂时移除双击功能，可以通过键盘 Enter 进入详情
-            // 滚轮滚动
-            MouseEventKind::ScrollUp => {
-                if self.selected_project > 0 {
-                    self.selected_project -= 1;
+            // 双击功能需要自己实现，crossterm 没有直接的 DoubleClick 事件
+            // 暂时移除双击功能，可以通过键盘 Enter 进入详情
+            // 滚轮滚动
+            MouseEventKind::ScrollUp => {
+                self.move_selection(-1);
+            }
+            MouseEventKind::ScrollDown => {
+                self.move_selection(1);
+            }
+            _ => {}
+        }
+        
+        Ok(())
+    }
+    
+    /// 获取满足过滤条件的项目索引（按列表中的显示顺序）；分组显示时折叠的分组只保留一个项目
+    fn visible_indices(&self) -> Vec<usize> {
+        match self.project_groups() {
+            Some(groups) => groups.stops(&self.collapsed_groups, self.selected_project),
+            None => filter::visible_indices(&self.projects, &self.filter, self.sort, self.pinned_order.as_ref()),
+        }
+    }
+    
+    /// 选中的项目是否显示在当前列表中
+    fn selection_visible(&self) -> bool {
+        self.visible_indices().contains(&self.selected_project)
+    }
+    
+    /// 分组显示时按工作区/扫描根目录分组的可见项目
+    fn project_groups(&self) -> Option<ProjectGroups> {
+        if !self.grouped {
+            return None;
+        }
+        let visible = filter::visible_indices(&self.projects, &self.filter, self.sort, self.pinned_order.as_ref());
+        Some(ProjectGroups::compute(&self.projects, &visible, &self.scan_paths, &self.config.display.workspaces))
+    }
+    
+    /// 切换分组显示
+    fn toggle_grouping(&mut self) {
+        self.grouped = !self.grouped;
+        self.status_message = if self.grouped {
+            "已按工作区分组显示，←/→ 折叠/展开分组".to_string()
+        } else {
+            "已取消分组显示".to_string()
+        };
+        self.ensure_selection_visible();
+    }
+    
+    /// → 键：优先展开选中项目所在的折叠分组或折叠的工作区，否则在项目下方展开其依赖目录
+    fn expand_selected(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        let group_collapsed = self.project_groups()
+            .and_then(|groups| groups.group_of(self.selected_project).map(|group| self.collapsed_groups.contains(&group.key)))
+            .unwrap_or(false);
+        
+        if group_collapsed {
+            self.set_group_collapsed(false);
+        } else if self.filter.collapsed_workspaces.contains(&project.path) {
+            self.set_workspace_collapsed(false);
+        } else if project.dependencies.is_empty() {
+            self.status_message = format!("{} 没有依赖目录", project.name);
+        } else {
+            self.expanded_projects.insert(project.path.clone());
+        }
+    }
+    
+    /// ← 键：优先收起选中项目下方的依赖目录，否则折叠所在的分组或工作区
+    fn collapse_selected(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        if self.expanded_projects.remove(&project.path) {
+            return;
+        }
+        
+        if self.grouped {
+            self.set_group_collapsed(true);
+        } else {
+            self.set_workspace_collapsed(true);
+        }
+    }
+    
+    /// 折叠或展开选中项目所在的分组
+    fn set_group_collapsed(&mut self, collapsed: bool) {
+        let Some(key) = self.project_groups()
+            .and_then(|groups| groups.group_of(self.selected_project).map(|group| group.key.clone()))
+        else {
+            return;
+        };
+        if collapsed {
+            self.collapsed_groups.insert(key);
+        } else {
+            self.collapsed_groups.remove(&key);
+        }
+    }
+    
+    /// 折叠或展开选中项目所在的工作区；折叠时选中项是成员则改为选中根项目
+    fn set_workspace_collapsed(&mut self, collapsed: bool) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        let root = match &project.workspace_root {
+            Some(root) => root.clone(),
+            None if self.projects.iter().any(|p| p.workspace_root.as_ref() == Some(&project.path)) => project.path.clone(),
+            None => return,
+        };
+        
+        if collapsed {
+            if let Some(index) = self.projects.iter().position(|p| p.path == root) {
+                self.selected_project = index;
+            }
+            self.filter.collapsed_workspaces.insert(root);
+        } else {
+            self.filter.collapsed_workspaces.remove(&root);
+        }
+        self.ensure_selection_visible();
+    }
+    
+    /// 是否仍有项目在扫描或计算大小
+    fn is_scan_settling(&self) -> bool {
+        self.snapshot_stale
+            || self.state == AppState::Scanning
+            || self.projects.iter().any(|p| !p.is_ignored && matches!(
+                p.dependency_calculation_status,
+                DependencyCalculationStatus::NotCalculated | DependencyCalculationStatus::Calculating
+            ))
+    }
+    
+    /// 扫描和大小计算全部完成后取消固定顺序，按排序方式重新排列
+    fn update_pinned_order(&mut self) {
+        if self.pinned_order.is_some() && !self.is_scan_settling() {
+            self.pinned_order = None;
+        }
+    }
+    
+    /// 排序方式改变后按新的排序方式重新固定顺序
+    fn repin_order(&mut self) {
+        if self.pinned_order.is_some() {
+            self.pinned_order = Some(PinnedOrder::capture(&self.projects, self.sort));
+        }
+    }
+    
+    /// 在可见项目中移动选中位置
+    fn move_selection(&mut self, delta: isize) {
+        let visible = self.visible_indices();
+        if visible.is_empty() {
+            return;
+        }
+        
+        let position = visible.iter()
+            .position(|&index| index == self.selected_project)
+            .unwrap_or(0);
+        let new_position = (position as isize + delta).clamp(0, visible.len() as isize - 1) as usize;
+        self.selected_project = visible[new_position];
+    }
+    
+    /// 确保选中的项目满足过滤条件，否则选中第一个可见项目
+    fn ensure_selection_visible(&mut self) {
+        let visible = self.visible_indices();
+        if !visible.contains(&self.selected_project) {
+            self.selected_project = visible.first().copied().unwrap_or(0);
+        }
+    }
+    
+    /// 切换选中项目的标记状态
+    fn toggle_mark_project(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        
+        if !self.marked_projects.remove(&project.path) {
+            self.marked_projects.insert(project.path.clone());
+        }
+        self.status_message = format!("已标记 {} 个项目", self.marked_projects.len());
+    }
+    
+    /// 检查路径是否在配置的隐藏列表中
+    fn is_hidden_path(&self, path: &std::path::Path) -> bool {
+        self.config.display.hidden_projects.contains(&path.display().to_string())
+    }
+    
+    /// 根据配置中的隐藏列表设置所有项目的隐藏标记
+    fn apply_hidden_flags(&mut self) {
+        let hidden_projects = &self.config.display.hidden_projects;
+        for project in &mut self.projects {
+            project.is_hidden = hidden_projects.contains(&project.path.display().to_string());
+        }
+    }
+    
+    /// 根据配置设置所有项目的标签
+    fn apply_tags(&mut self) {
+        for project in &mut self.projects {
+            project.tags = self.config.display.tags_for(&project.path);
+        }
+    }
+    
+    /// 为标记的项目（没有标记时为选中的项目）添加标签；这些项目都已有该标签时改为移除
+    fn toggle_tag(&mut self, tag: &str) {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return;
+        }
+        
+        let targets: Vec<usize> = if self.marked_projects.is_empty() {
+            (self.selected_project < self.projects.len()).then_some(self.selected_project).into_iter().collect()
+        } else {
+            self.projects.iter().enumerate()
+                .filter(|(_, project)| self.marked_projects.contains(&project.path))
+                .map(|(index, _)| index)
+                .collect()
+        };
+        if targets.is_empty() {
+            return;
+        }
+        
+        let tagged = !targets.iter().all(|&index| self.projects[index].tags.iter().any(|t| t == tag));
+        let paths: Vec<PathBuf> = targets.iter().map(|&index| self.projects[index].path.clone()).collect();
+        let saved = self.update_config(|config| {
+            for path in &paths {
+                config.display.set_tag(path, tag, tagged);
+            }
+        });
+        for &index in &targets {
+            let project = &mut self.projects[index];
+            project.tags = self.config.display.tags_for(&project.path);
+        }
+        self.status_message = match saved {
+            Ok(()) if tagged => format!("已为 {} 个项目添加标签 #{}", targets.len(), tag),
+            Ok(()) => format!("已从 {} 个项目移除标签 #{}", targets.len(), tag),
+            Err(e) => format!("保存标签失败: {}", e),
+        };
+    }
+    
+    /// 根据配置中的忽略项目列表设置所有项目的忽略标记
+    fn apply_ignored_flags(&mut self) {
+        let ignored_projects = &self.config.ignore.projects;
+        for project in &mut self.projects {
+            project.is_ignored = ignored_projects.contains(&project.path.display().to_string());
+        }
+    }
+    
+    /// 切换选中项目的隐藏状态并保存到配置文件
+    fn toggle_hide_project(&mut self) {
+        let Some(project) = self.projects.get_mut(self.selected_project) else {
+            return;
+        };
+        
+        project.is_hidden = !project.is_hidden;
+        let is_hidden = project.is_hidden;
+        let path = project.path.display().to_string();
+        let status = if is_hidden { "已隐藏" } else { "已取消隐藏" };
+        self.status_message = format!("项目 {} {}", project.name, status);
+        
+        let saved = self.update_config(|config| {
+            if is_hidden {
+                config.display.hidden_projects.insert(path.clone());
+            } else {
+                config.display.hidden_projects.remove(&path);
+            }
+        });
+        if let Err(e) = saved {
+            self.status_message = format!("保存隐藏状态失败: {}", e);
+        }
+        
+        self.ensure_selection_visible();
+    }
+    
+    /// 修改配置并写回启动时加载的配置文件
+    ///
+    /// 修改会应用到重新读取的配置文件内容上再保存，命令行参数对本次运行的覆盖不会被写入文件。
+    fn update_config(&mut self, update: impl Fn(&mut Config)) -> Result<()> {
+        update(&mut self.config);
+        
+        let config_path = match &self.config_path {
+            Some(path) => path.clone(),
+            None => Config::default_config_path()?,
+        };
+        let mut saved = if config_path.exists() {
+            Config::load_from_file(&config_path)?
+        } else {
+            Config::default()
+        };
+        update(&mut saved);
+        saved.save_to_file(&config_path)
+    }
+    
+    /// 将当前排序方式写回配置文件
+    fn save_sort(&mut self) {
+        let ProjectSort { field, descending } = self.sort;
+        self.status_message = format!("排序: {}", self.sort.description());
+        
+        let saved = self.update_config(|config| {
+            config.display.default_sort = field;
+            config.display.sort_descending = descending;
+        });
+        if let Err(e) = saved {
+            self.status_message = format!("保存排序方式失败: {}", e);
+        }
+    }
+    
+    /// 复制选中项目的路径到剪贴板
+    fn yank_project_path(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        
+        let path = project.path.display().to_string();
+        self.status_message = match clipboard::copy(&path) {
+            Ok(CopyMethod::Native) => format!("已复制路径: {}", path),
+            Ok(CopyMethod::Osc52) => format!("已通过 OSC 52 复制路径: {}", path),
+            Err(e) => format!("复制路径失败: {}", e),
+        };
+    }
+    
+    /// 复制选中项目的 Markdown 报告到剪贴板
+    fn copy_project_report(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        
+        let report = project_report::to_markdown(project);
+        self.status_message = match clipboard::copy(&report) {
+            Ok(CopyMethod::Native) => format!("已复制 {} 的报告", project.name),
+            Ok(CopyMethod::Osc52) => format!("已通过 OSC 52 复制 {} 的报告", project.name),
+            Err(e) => format!("复制报告失败: {}", e),
+        };
+    }
+    
+    /// 将选中项目的 Markdown 报告保存到当前目录
+    fn save_project_report(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        
+        let path = PathBuf::from(project_report::file_name(project));
+        let path = std::env::current_dir().map(|dir| dir.join(&path)).unwrap_or(path);
+        self.status_message = match std::fs::write(&path, project_report::to_markdown(project)) {
+            Ok(()) => format!("已保存报告到 {}", path_format::format_path(&path, STATUS_PATH_WIDTH)),
+            Err(e) => format!("保存报告失败: {}", e),
+        };
+    }
+    
+    /// 切换目录过滤：只显示与选中项目位于同一父目录下的项目
+    fn toggle_directory_filter(&mut self) {
+        if self.filter.directory.is_some() {
+            self.filter.directory = None;
+            self.status_message = "已清除目录过滤".to_string();
+        } else if let Some(parent) = self.projects.get(self.selected_project)
+            .and_then(|project| project.path.parent())
+        {
+            self.filter.directory = Some(parent.to_path_buf());
+            self.status_message = format!("已过滤: {}", self.filter.description());
+        }
+        
+        self.ensure_selection_visible();
+    }
+    
+    /// 只显示长期未活动的项目，按依赖大小降序排列；再次按下时恢复
+    fn toggle_stale_filter(&mut self) {
+        if self.filter.stale_before.take().is_some() {
+            self.status_message = "已清除未活动项目过滤".to_string();
+        } else {
+            let days = self.config.display.stale_project_days;
+            self.filter.stale_before = Some(chrono::Utc::now() - chrono::Duration::days(days as i64));
+            self.sort = ProjectSort { field: SortField::DependencySize, descending: true };
+            self.repin_order();
+            let stale = self.visible_indices().len();
+            self.status_message = format!("{} 个项目超过 {} 天未活动，按依赖大小排序", stale, days);
+        }
+        
+        self.ensure_selection_visible();
+    }
+    
+    /// 从 origin fetch 选中的仓库或全部仓库（不含已忽略的项目和裸仓库），限制同时运行的数量
+    fn start_git_fetch(&mut self, all: bool) {
+        let candidates: Vec<&Project> = if all {
+            self.projects.iter().filter(|project| !project.is_ignored).collect()
+        } else {
+            self.projects.get(self.selected_project).into_iter().collect()
+        };
+        let paths: Vec<PathBuf> = candidates.into_iter()
+            .filter(|project| project.git_info.as_ref().is_some_and(|git| !git.is_bare))
+            .filter(|project| !self.git_fetch.get(&project.path).is_some_and(FetchStatus::is_pending))
+            .map(|project| project.path.clone())
+            .collect();
+        if paths.is_empty() {
+            self.status_message = if all {
+                "没有需要 fetch 的仓库".to_string()
+            } else {
+                "选中的项目不是 Git 仓库，或正在 fetch".to_string()
+            };
+            return;
+        }
+        
+        for path in &paths {
+            self.git_fetch.insert(path.clone(), FetchStatus::Queued);
+        }
+        let total = paths.len();
+        let target = match paths.as_slice() {
+            [path] => path_format::format_path(path, STATUS_PATH_WIDTH),
+            _ => format!("{} 个仓库", total),
+        };
+        self.status_message = format!("正在 fetch {} 个仓库...", total);
+        
+        let sender = self.event_handler.sender.clone();
+        self.spawn_task(TaskKind::Fetch, target, |task| async move {
+            let mut results = std::pin::pin!(futures::stream::iter(paths.clone())
+                .map(|path| {
+                    let sender = sender.clone();
+                    async move {
+                        let _ = sender.send(Event::GitFetchUpdated { project_path: path.clone(), status: FetchStatus::Fetching });
+                        let status = git_fetch::fetch_and_compare(&path).await;
+                        let _ = sender.send(Event::GitFetchUpdated { project_path: path.clone(), status: status.clone() });
+                        (path, status)
+                    }
+                })
+                .buffer_unordered(git_fetch::CONCURRENT_FETCHES));
+            
+            let mut finished = HashSet::new();
+            let mut failed = 0;
+            loop {
+                tokio::select! {
+                    _ = task.token.cancelled() => {
+                        // 未完成的仓库不再显示为等待中
+                        for path in paths.into_iter().filter(|path| !finished.contains(path)) {
+                            let _ = sender.send(Event::GitFetchUpdated { project_path: path, status: FetchStatus::Failed("已取消".to_string()) });
+                        }
+                        return Err("已取消".to_string());
+                    }
+                    next = results.next() => match next {
+                        Some((path, status)) => {
+                            if matches!(status, FetchStatus::Failed(_)) {
+                                failed += 1;
+                            }
+                            finished.insert(path);
+                            task.set_progress(format!("{}/{}", finished.len(), total));
+                        }
+                        None => break,
+                    },
+                }
+            }
+            Ok(format!("已 fetch {} 个仓库，{} 个失败", total - failed, failed))
+        });
+    }
+    
+    /// 记录仓库的 fetch 状态，在状态栏显示进度；fetch 完成后重新分析 Git 信息
+    fn update_git_fetch(&mut self, project_path: PathBuf, status: FetchStatus) {
+        // 已移除的项目不再记录
+        let Some(name) = self.projects.iter()
+            .find(|project| project.path == project_path)
+            .map(|project| project.name.clone())
+        else {
+            return;
+        };
+        if matches!(status, FetchStatus::Fetched(_)) {
+            self.git_hydration.queue(project_path.clone());
+        }
+        let message = format!("{}: {}", name, status);
+        self.git_fetch.insert(project_path, status);
+        
+        let pending = self.git_fetch.values().filter(|status| status.is_pending()).count();
+        self.status_message = if pending > 0 {
+            format!("{}（还有 {} 个仓库等待 fetch）", message, pending)
+        } else {
+            let behind = self.git_fetch.values()
+                .filter(|status| matches!(status, FetchStatus::Fetched(Some(counts)) if counts.behind > 0))
+                .count();
+            let failed = self.git_fetch.values().filter(|status| matches!(status, FetchStatus::Failed(_))).count();
+            format!("fetch 完成：{} 个仓库落后于 origin，{} 个失败", behind, failed)
+        };
+    }
+    
+    /// 处理标签栏点击
+    fn handle_tab_click(&mut self, column: u16) {
+        // 简单的标签点击检测，基于列位置
+        let tab_width = 20; // 假设每个标签大约20个字符宽
+        let tab_index = (column / tab_width) as usize;
+        
+        match tab_index {
+            0 => self.current_tab = TabView::Projects,
+            1 => self.current_tab = TabView::Statistics,
+            2 => self.current_tab = TabView::GitStatus,
+            3 => self.current_tab = TabView::ScanRoots,
+            _ => {} // 超出范围的点击忽略
+        }
+    }
+    
+    /// 处理项目详情键盘事件
+    async fn handle_project_detail_keys(&mut self, key: crossterm::event::KeyEvent) -> Result<()> {
+        use crossterm::event::KeyCode;
+        
+        if keys::is_enter_key(&key) || matches!(key.code, KeyCode::Backspace) {
+            self.state = AppState::ProjectList;
+        } else if keys::is_up_key(&key) {
+            self.selected_suggestion = self.selected_suggestion.saturating_sub(1);
+        } else if keys::is_down_key(&key) {
+            self.selected_suggestion += 1;
+            self.clamp_selected_suggestion();
+        } else if self.snapshot_file.is_some()
+            && (keys::is_branch_key(&key) || keys::is_disk_usage_key(&key) || matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')))
+        {
+            self.status_message = SNAPSHOT_READ_ONLY.to_string();
+        } else if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            self.request_accept_cleanup_suggestion();
+        } else if matches!(key.code, KeyCode::Char('n') | KeyCode::Char('N')) {
+            self.reject_cleanup_suggestion();
+        } else if keys::is_branch_key(&key) {
+            self.open_branch_list();
+        } else if keys::is_disk_usage_key(&key) {
+            self.request_disk_usage();
+        } else if keys::is_report_key(&key) {
+            self.copy_project_report();
+        } else if keys::is_save_report_key(&key) {
+            self.save_project_report();
+        } else if matches!(key.code, KeyCode::PageUp) {
+            self.commit_log_scroll = self.commit_log_scroll.saturating_sub(5);
+        } else if matches!(key.code, KeyCode::PageDown) {
+            let commit_count = self.projects.get(self.selected_project)
+                .and_then(|p| p.recent_commits.as_ref())
+                .map(|commits| commits.len())
+                .unwrap_or(0);
+            self.commit_log_scroll = (self.commit_log_scroll + 5).min(commit_count.saturating_sub(1));
+        }
+        
+        Ok(())
+    }
+    
+    /// 进入项目详情页，必要时在后台分析 gitignore 清理建议
+    fn open_project_detail(&mut self) {
+        self.state = AppState::ProjectDetail;
+        self.selected_suggestion = 0;
+        self.commit_log_scroll = 0;
+        
+        // 快照中的目录可能已不存在，只显示保存时的信息
+        if self.snapshot_file.is_some() {
+            return;
+        }
+        
+        self.load_recent_commits();
+        self.load_package_stats();
+        self.load_cleanup_suggestions();
+    }
+    
+    /// 在后台分析选中项目中可清理的 gitignore 目录
+    fn load_cleanup_suggestions(&mut self) {
+        let Some(project) = self.projects.get_mut(self.selected_project) else {
+            return;
+        };
+        
+        if project.cleanup_suggestions.is_some() || project.git_info.is_none() {
+            return;
+        }
+        
+        // 先标记为已请求，避免重复分析
+        project.cleanup_suggestions = Some(Vec::new());
+        
+        let project_path = project.path.clone();
+        let project_name = project.name.clone();
+        let project_total_size = project.disk_usage();
+        let sender = self.event_handler.sender.clone();
+        
+        self.spawn_task(TaskKind::Suggestions, project_name.clone(), |task| async move {
+            use crate::scanner::GitIgnoreAnalyzer;
+            use crate::scanner::size_calculator::dependency_dir_names_for;
+            
+            let analyzer_path = project_path.clone();
+            let analyzer = run_git_task(&task.token, move |token| GitIgnoreAnalyzer::new_cancellable(&analyzer_path, token))
+                .await
+                .unwrap_or_else(|| Err(anyhow::anyhow!("分析任务异常退出")));
+            let suggestions = match analyzer {
+                Ok(analyzer) => analyzer
+                    .suggest_cleanup_candidates(&dependency_dir_names_for(&project_path), CLEANUP_SUGGESTION_MIN_SIZE, project_total_size)
+                    .await
+                    .unwrap_or_default(),
+                Err(e) => {
+                    tracing::warn!("分析 {} 的清理建议失败: {}", project_name, e);
+                    Vec::new()
+                }
+            };
+            
+            let message = format!("{} 条建议", suggestions.len());
+            let _ = sender.send(Event::CleanupSuggestionsReady { project_name, suggestions });
+            Ok(message)
+        });
+    }
+    
+    /// 在后台加载选中项目的最近提交记录
+    fn load_recent_commits(&mut self) {
+        let Some(project) = self.projects.get_mut(self.selected_project) else {
+            return;
+        };
+        
+        if project.recent_commits.is_some() || project.git_info.is_none() {
+            return;
+        }
+        
+        // 先标记为已请求，避免重复加载
+        project.recent_commits = Some(Vec::new());
+        
+        let project_path = project.path.clone();
+        let project_name = project.name.clone();
+        let sender = self.event_handler.sender.clone();
+        
+        tokio::task::spawn_blocking(move || {
+            let commits = crate::scanner::GitAnalyzer::new()
+                .get_recent_commits(&project_path, RECENT_COMMITS_LIMIT)
+                .unwrap_or_else(|e| {
+                    tracing::warn!("读取 {} 的提交记录失败: {}", project_name, e);
+                    Vec::new()
+                });
+            
+            let _ = sender.send(Event::RecentCommitsReady { project_name, commits });
+        });
+    }
+    
+    /// 在后台解析选中项目的依赖清单，统计 node_modules 和 target 中的包
+    fn load_package_stats(&mut self) {
+        let Some(project) = self.projects.get_mut(self.selected_project) else {
+            return;
+        };
+        
+        let dependencies: Vec<DependencyInfo> = project.dependencies.iter()
+            .filter(|d| d.packages.is_none() && matches!(d.dependency_type, DependencyType::NodeModules | DependencyType::RustTarget))
+            .cloned()
+            .collect();
+        if dependencies.is_empty() {
+            return;
+        }
+        
+        // 先标记为已请求，避免重复分析
+        for dependency in project.dependencies.iter_mut().filter(|d| dependencies.iter().any(|pending| pending.path == d.path)) {
+            dependency.packages = Some(PackageStats::default());
+        }
+        
+        let project_path = project.path.clone();
+        let sender = self.event_handler.sender.clone();
+        
+        tokio::task::spawn_blocking(move || {
+            let stats = manifest_analyzer::analyze_dependencies(&project_path, &dependencies);
+            let _ = sender.send(Event::PackageStatsReady { project_path, stats });
+        });
+    }
+    
+    /// 将选中的清理建议索引限制在待处理建议范围内
+    fn clamp_selected_suggestion(&mut self) {
+        let pending_count = self.projects.get(self.selected_project)
+            .map(|p| p.pending_cleanup_suggestions().len())
+            .unwrap_or(0);
+        self.selected_suggestion = self.selected_suggestion.min(pending_count.saturating_sub(1));
+    }
+    
+    /// 当前选中的待处理清理建议
+    fn selected_cleanup_suggestion(&self) -> Option<&CleanupSuggestion> {
+        let project = self.projects.get(self.selected_project)?;
+        project.pending_cleanup_suggestions().get(self.selected_suggestion).copied()
+    }
+    
+    /// 请求接受当前选中的清理建议，确认后才删除
+    fn request_accept_cleanup_suggestion(&mut self) {
+        let Some(path) = self.selected_cleanup_suggestion().map(|suggestion| suggestion.path.clone()) else {
+            return;
+        };
+        if !self.operation_allowed(&path) {
+            return;
+        }
+        self.confirm_action = ConfirmAction::AcceptCleanupSuggestion(path);
+        self.state = AppState::ConfirmDialog;
+    }
+    
+    /// 接受清理建议：在后台删除对应目录
+    fn accept_cleanup_suggestion(&mut self, path: &std::path::Path) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        let Some(suggestion) = project.pending_cleanup_suggestions().into_iter().find(|suggestion| suggestion.path == path) else {
+            return;
+        };
+        
+        let project_name = project.name.clone();
+        let path = suggestion.path.clone();
+        let size = suggestion.size;
+        let sender = self.event_handler.sender.clone();
+        
+        self.status_message = format!("正在清理: {}", path_format::format_path(&path, STATUS_PATH_WIDTH));
+        
+        let target = path_format::format_path(&path, STATUS_PATH_WIDTH);
+        let progress = self.deletion_progress_callback();
+        self.spawn_task(TaskKind::Clean, target, |task| async move {
+            let mut summary = CleanupOperation::new()
+                .with_progress(progress)
+                .remove_directories(std::slice::from_ref(&path), &task.token)
+                .await;
+            summary.wait_for_deletion().await;
+            let result = match summary.failed.first() {
+                Some((_, error)) => Err(error.clone()),
+                None if summary.cancelled => Err("已中断，目录已恢复".to_string()),
+                None => Ok(()),
+            };
+            match result {
+                Ok(()) => {
+                    let message = format!(
+                        "已清理 {}，释放了 {} 空间",
+                        path_format::format_path(&path, STATUS_PATH_WIDTH),
+                        size
+                    );
+                    let _ = sender.send(Event::ScanProgress(message.clone()));
+                    let _ = sender.send(Event::CleanupSuggestionApplied {
+                        project_name,
+                        path,
+                        freed_size: size,
+                    });
+                    Ok(message)
+                }
+                Err(e) => {
+                    let message = format!(
+                        "清理 {} 失败: {}",
+                        path_format::format_path(&path, STATUS_PATH_WIDTH),
+                        e
+                    );
+                    let _ = sender.send(Event::ScanProgress(message.clone()));
+                    Err(message)
+                }
+            }
+        });
+    }
+    
+    /// 拒绝当前选中的清理建议
+    fn reject_cleanup_suggestion(&mut self) {
+        let selected = self.selected_suggestion;
+        let Some(project) = self.projects.get_mut(self.selected_project) else {
+            return;
+        };
+        let Some(suggestion) = project.cleanup_suggestions.as_mut()
+            .and_then(|suggestions| suggestions.iter_mut()
+                .filter(|s| s.status == SuggestionStatus::Pending)
+                .nth(selected))
+        else {
+            return;
+        };
+        
+        suggestion.status = SuggestionStatus::Rejected;
+        self.status_message = format!("已忽略清理建议: {}", path_format::format_path(&suggestion.path, STATUS_PATH_WIDTH));
+        self.clamp_selected_suggestion();
+    }
+    
+    /// 在后台统计选中项目的磁盘占用明细，完成后打开明细视图
+    fn request_disk_usage(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        
+        self.status_message = format!("正在统计 {} 的磁盘占用...", project.name);
+        let project_path = project.path.clone();
+        let project_name = project.name.clone();
+        let follow_symlinks = self.config.scan.follow_symlinks;
+        let sender = self.event_handler.sender.clone();
+        self.spawn_task(TaskKind::DiskUsage, project_name, |task| async move {
+            let calculator = SizeCalculator::new()
+                .with_follow_symlinks(follow_symlinks)
+                .with_cancellation(task.token.clone());
+            let tree = calculator.calculate_size_tree(&project_path, DISK_USAGE_TOP_N).await
+                .map_err(|e| e.to_string())?;
+            let message = crate::utils::size_format::format_size(tree.size);
+            let _ = sender.send(Event::DiskUsageReady { project_path, tree });
+            Ok(message)
+        });
+    }
+    
+    /// 显示磁盘占用明细；已离开详情页或选中的项目已改变时不显示
+    fn show_disk_usage(&mut self, project_path: PathBuf, tree: SizeTreeNode) {
+        let is_current = self.projects.get(self.selected_project).is_some_and(|p| p.path == project_path);
+        if !is_current || self.state != AppState::ProjectDetail {
+            return;
+        }
+        
+        self.status_message = format!("磁盘占用: {}", crate::utils::size_format::format_size(tree.size));
+        self.disk_usage = Some(DiskUsageView::new(tree));
+        self.state = AppState::DiskUsage;
+    }
+    
+    /// 处理磁盘占用明细视图的键盘事件
+    fn handle_disk_usage_keys(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+        
+        let Some(view) = self.disk_usage.as_mut() else {
+            self.state = AppState::ProjectDetail;
+            return;
+        };
+        
+        if keys::is_up_key(&key) {
+            view.move_selection(-1);
+        } else if keys::is_down_key(&key) {
+            view.move_selection(1);
+        } else if matches!(key.code, KeyCode::Right | KeyCode::Enter) {
+            view.enter();
+        } else if matches!(key.code, KeyCode::Left | KeyCode::Backspace) {
+            view.leave();
+        } else if key.code == KeyCode::Esc {
+            self.disk_usage = None;
+            self.state = AppState::ProjectDetail;
+        }
+    }
+    
+    /// 打开选中项目的分支列表
+    fn open_branch_list(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        
+        if project.git_info.is_none() {
+            self.status_message = if self.git_hydration.is_waiting(&project.path) {
+                "正在分析 Git 信息，请稍后再试".to_string()
+            } else {
+                "该项目不是 Git 仓库".to_string()
+            };
+            return;
+        }
+        
+        self.refresh_branches();
+        self.state = AppState::BranchList;
+    }
+    
+    /// 重新读取选中项目的本地分支
+    fn refresh_branches(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        
+        match crate::scanner::GitAnalyzer::new().get_branches(&project.path) {
+            Ok(branches) => self.branches = branches,
+            Err(e) => {
+                self.branches.clear();
+                self.status_message = format!("读取分支失败: {}", e);
+            }
+        }
+        self.selected_branch = self.selected_branch.min(self.branches.len().saturating_sub(1));
+    }
+    
+    /// 处理分支列表键盘事件
+    fn handle_branch_list_keys(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+        
+        if keys::is_enter_key(&key) || matches!(key.code, KeyCode::Backspace | KeyCode::Esc) {
+            self.state = AppState::ProjectDetail;
+        } else if keys::is_up_key(&key) {
+            self.selected_branch = self.selected_branch.saturating_sub(1);
+        } else if keys::is_down_key(&key) {
+            self.selected_branch = (self.selected_branch + 1).min(self.branches.len().saturating_sub(1));
+        } else if keys::is_delete_key(&key) {
+            let deletable = self.branches.iter().filter(|b| b.is_deletable()).count();
+            if deletable == 0 {
+                self.status_message = "没有可删除的已合并分支".to_string();
+            } else {
+                self.confirm_action = ConfirmAction::DeleteMergedBranches;
+                self.state = AppState::ConfirmDialog;
+                self.status_message = format!("确认删除 {} 个已合并分支？ (y/N)", deletable);
+            }
+        }
+    }
+    
+    /// 处理帮助页面键盘事件
+    fn handle_help_keys(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+        
+        if self.help.editing {
+            match key.code {
+                KeyCode::Enter => self.help.editing = false,
+                KeyCode::Esc => {
+                    self.help.editing = false;
+                    self.help.query.clear();
+                }
+                KeyCode::Backspace => {
+                    self.help.query.pop();
+                }
+                KeyCode::Char(c) => self.help.query.push(c),
+                _ => {}
+            }
+            self.help.scroll = 0;
+            return;
+        }
+        
+        if keys::is_search_key(&key) {
+            self.help.editing = true;
+        } else if key.code == KeyCode::Esc && !self.help.query.is_empty() {
+            self.help.query.clear();
+            self.help.scroll = 0;
+        } else if keys::is_enter_key(&key) || keys::is_help_key(&key) || key.code == KeyCode::Esc {
+            self.state = AppState::ProjectList;
+        } else if keys::is_up_key(&key) {
+            self.help.scroll = self.help.scroll.saturating_sub(1);
+        } else if keys::is_down_key(&key) {
+            self.help.scroll += 1;
+        } else if key.code == KeyCode::PageUp {
+            self.help.scroll = self.help.scroll.saturating_sub(10);
+        } else if key.code == KeyCode::PageDown {
+            self.help.scroll += 10;
+        }
+    }
+    
+    /// 处理忽略列表界面的按键：输入条目时 Enter 保存、Esc 取消，其余按键编辑列表
+    fn handle_ignore_list_keys(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+        
+        if let Some(input) = self.ignore_editor.input.as_mut() {
+            match key.code {
+                KeyCode::Enter => {
+                    match self.ignore_editor.commit_input(&mut self.config.ignore) {
+                        Ok(change) => self.apply_ignore_change(change),
+                        Err(e) => self.status_message = e.to_string(),
+                    }
+                }
+                KeyCode::Esc => self.ignore_editor.input = None,
+                KeyCode::Backspace => {
+                    input.text.pop();
+                }
+                KeyCode::Char(c) => input.text.push(c),
+                _ => {}
+            }
+            return;
+        }
+        
+        if key.code == KeyCode::Esc {
+            self.state = AppState::ProjectList;
+        } else if keys::is_tab_key(&key) || key.code == KeyCode::Right {
+            self.ignore_editor.switch_section(self.ignore_editor.section.next());
+        } else if key.code == KeyCode::Left {
+            self.ignore_editor.switch_section(self.ignore_editor.section.previous());
+        } else if keys::is_up_key(&key) {
+            self.ignore_editor.move_selection(-1, &self.config.ignore);
+        } else if keys::is_down_key(&key) {
+            self.ignore_editor.move_selection(1, &self.config.ignore);
+        } else if key.code == KeyCode::Char('a') {
+            self.ignore_editor.start_add();
+        } else if keys::is_edit_key(&key) || key.code == KeyCode::Enter {
+            self.ignore_editor.start_edit(&self.config.ignore);
+        } else if keys::is_delete_key(&key) {
+            if let Some(change) = self.ignore_editor.remove_selected(&mut self.config.ignore) {
+                self.apply_ignore_change(change);
+            }
+        }
+    }
+    
+    /// 处理后台任务面板的按键
+    fn handle_task_list_keys(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+        
+        let count = self.tasks.display_order().len();
+        if key.code == KeyCode::Esc || keys::is_task_list_key(&key) {
+            self.state = AppState::ProjectList;
+        } else if keys::is_up_key(&key) {
+            self.selected_task = self.selected_task.saturating_sub(1);
+        } else if keys::is_down_key(&key) {
+            self.selected_task = (self.selected_task + 1).min(count.saturating_sub(1));
+        } else if keys::is_clean_key(&key) || key.code == KeyCode::Delete {
+            let Some((id, label)) = self.tasks.display_order().get(self.selected_task)
+                .map(|task| (task.id, task.label()))
+            else {
+                return;
+            };
+            self.status_message = if self.tasks.cancel(id) {
+                format!("已取消: {}", label)
+            } else {
+                format!("任务已结束: {}", label)
+            };
+        }
+    }
+    
+    /// 保存忽略列表的修改，并按新的规则重新过滤项目列表
+    fn apply_ignore_change(&mut self, change: IgnoreChange) {
+        if change.section == IgnoreSection::Projects {
+            for project in &mut self.projects {
+                let path = project.path.display().to_string();
+                if change.removed.as_ref() == Some(&path) {
+                    project.is_ignored = false;
+                }
+                if change.added.as_ref() == Some(&path) {
+                    project.is_ignored = true;
+                }
+            }
+        }
+        self.apply_ignore_rules();
+        
+        self.status_message = match (&change.removed, &change.added) {
+            (Some(removed), Some(added)) => format!("{}: {} → {}", change.section.label(), removed, added),
+            (None, Some(added)) => format!("{}: 已添加 {}", change.section.label(), added),
+            (Some(removed), None) => format!("{}: 已移除 {}", change.section.label(), removed),
+            (None, None) => String::new(),
+        };
+        let excluded = self.filter.excluded.len();
+        if excluded > 0 {
+            self.status_message.push_str(&format!("（{} 个项目被忽略规则排除）", excluded));
+        }
+        
+        if let Err(e) = self.update_config(|config| change.apply_to(&mut config.ignore)) {
+            self.status_message = format!("保存忽略列表失败: {}", e);
+        }
+    }
+    
+    /// 找出被当前忽略目录和忽略路径规则排除的项目，不再在列表中显示
+    fn apply_ignore_rules(&mut self) {
+        self.filter.excluded = self.projects.iter()
+            .filter(|project| self.config.ignore.excludes(&project.path, &self.scan_paths))
+            .map(|project| project.path.clone())
+            .collect();
+        self.ensure_selection_visible();
+    }
+    
+    /// 后台删除的进度回调：将进度作为事件发回界面
+    fn deletion_progress_callback(&self) -> DeleteProgressCallback {
+        let sender = self.event_handler.sender.clone();
+        std::sync::Arc::new(move |path, progress| {
+            let _ = sender.send(Event::DeletionProgress { path: path.to_path_buf(), progress });
+        })
+    }
+    
+    /// 记录删除进度并汇总到状态栏，删除完成的目录不再显示
+    fn update_deletion_progress(&mut self, path: PathBuf, progress: DeleteProgress) {
+        if progress.is_complete() {
+            self.deletion_progress.remove(&path);
+        } else {
+            self.deletion_progress.insert(path, progress);
+        }
+        
+        if self.deletion_progress.is_empty() {
+            if self.progress_info.progress_type == ProgressType::Deleting {
+                self.progress_info = ProgressInfo::default();
+            }
+            return;
+        }
+        
+        let total = self.deletion_progress.values().fold(DeleteProgress::default(), |sum, p| DeleteProgress {
+            deleted_files: sum.deleted_files + p.deleted_files,
+            total_files: sum.total_files + p.total_files,
+            deleted_bytes: sum.deleted_bytes + p.deleted_bytes,
+            total_bytes: sum.total_bytes + p.total_bytes,
+        });
+        let current_item = match self.deletion_progress.len() {
+            1 => self.deletion_progress.keys()
+                .next()
+                .and_then(|path| path.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            count => format!("{} 个目录", count),
+        };
+        self.progress_info = ProgressInfo {
+            progress_type: ProgressType::Deleting,
+            current: total.deleted_files,
+            total: Some(total.total_files),
+            current_item,
+            extra_info: format!(
+                "{}/{}",
+                crate::utils::size_format::format_size(total.deleted_bytes),
+                crate::utils::size_format::format_size(total.total_bytes)
+            ),
+        };
+    }
+    
+    /// 在后台执行任务并登记到任务面板；破坏性操作在退出时需要确认
+    fn spawn_task<F, Fut>(&mut self, kind: TaskKind, target: impl Into<String>, task: F) -> TaskId
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: std::future::Future<Output = Result<String, String>> + Send + 'static,
+    {
+        self.tasks.spawn(kind, target, &self.cancellation_token, task)
+    }
+    
+    /// 请求退出：没有进行中的破坏性操作时直接退出（返回 true），否则显示退出确认对话框
+    ///
+    /// 在退出确认或等待状态下再次按退出键则不再等待，直接退出。
+    fn request_quit(&mut self) -> bool {
+        if matches!(self.state, AppState::ConfirmQuit | AppState::WaitingForOperations) {
+            self.state = AppState::Quitting;
+            return true;
+        }
+        
+        self.quit_tasks = self.tasks.active()
+            .filter(|task| task.kind.is_destructive())
+            .map(|task| task.id)
+            .collect();
+        if self.quit_tasks.is_empty() {
+            self.state = AppState::Quitting;
+            return true;
+        }
+        
+        self.state = AppState::ConfirmQuit;
+        self.status_message = format!("有 {} 个操作正在进行", self.quit_tasks.len());
+        false
+    }
+    
+    /// 处理退出确认对话框键盘事件
+    fn handle_confirm_quit_keys(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+        
+        match key.code {
+            KeyCode::Char('w') | KeyCode::Char('W') | KeyCode::Enter => {
+                self.state = AppState::WaitingForOperations;
+                self.status_message = format!("正在等待 {} 个操作完成...", self.quit_tasks.len());
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                self.cancellation_token.cancel();
+                for id in self.quit_tasks.drain(..) {
+                    if let Some(task) = self.tasks.get(id) {
+                        self.exit_report.push(format!("⚠️  已取消: {}（可能已部分完成）", task.label()));
+                    }
                 }
+                self.state = AppState::Quitting;
             }
-            MouseEventKind::ScrollDown => {
-                if self.selected_project < self.projects.len().saturating_sub(1) {
-                    self.selected_project += 1;
-                }
+            KeyCode::Esc => {
+                self.state = AppState::ProjectList;
+                self.status_message = "已取消退出".to_string();
             }
             _ => {}
         }
+    }
+    
+    /// 收到终止信号：取消进行中的操作并记录在退出报告中
+    fn handle_shutdown_signal(&mut self) {
+        self.cancellation_token.cancel();
+        for task in self.tasks.active() {
+            self.exit_report.push(format!("⚠️  已取消: {}（可能已部分完成）", task.label()));
+        }
+        self.state = AppState::Quitting;
+    }
+    
+    /// 等待中的操作全部完成时记录各自的结果并退出
+    fn finish_waiting_if_done(&mut self) {
+        self.tasks.reap();
+        let finished: Vec<&Task> = self.quit_tasks.iter().filter_map(|id| self.tasks.get(*id)).collect();
+        if !finished.iter().all(|task| task.status.is_finished()) {
+            return;
+        }
         
-        Ok(())
+        for task in finished {
+            let line = match &task.status {
+                TaskStatus::Succeeded(message) => format!("✅ {}", message),
+                TaskStatus::Failed(message) => format!("❌ {}", message),
+                _ => format!("⚠️  已取消: {}（可能已部分完成）", task.label()),
+            };
+            self.exit_report.push(line);
+        }
+        self.quit_tasks.clear();
+        self.state = AppState::Quitting;
     }
     
-    /// 处理标签栏点击
-    fn handle_tab_click(&mut self, column: u16) {
-        // 简单的标签点击检测，基于列位置
-        let tab_width = 20; // 假设每个标签大约20个字符宽
-        let tab_index = (column / tab_width) as usize;
+    /// 删除选中项目中所有已合并的分支
+    fn delete_merged_branches(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
         
-        match tab_index {
-            0 => self.current_tab = TabView::Projects,
-            1 => self.current_tab = TabView::Statistics,
-            2 => self.current_tab = TabView::GitStatus,
-            _ => {} // 超出范围的点击忽略
+        match crate::scanner::GitAnalyzer::new().delete_merged_branches(&project.path) {
+            Ok(deleted) => {
+                self.status_message = format!("已删除 {} 个已合并分支", deleted.len());
+            }
+            Err(e) => {
+                self.status_message = format!("删除分支失败: {}", e);
+            }
+        }
+        
+        self.refresh_branches();
+    }
+    
+    /// 请求确认删除选中的项目
+    fn request_delete(&mut self) {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return;
+        };
+        let project_path = project.path.clone();
+        let project_name = project.name.clone();
+        if !self.operation_allowed(&project_path) {
+            return;
         }
+        
+        self.confirm_action = ConfirmAction::DeleteProject;
+        self.state = AppState::ConfirmDialog;
+        self.status_message = format!(
+            "确认删除项目 {}（{}）？ (y/N)",
+            project_name,
+            path_format::format_path(&project_path, STATUS_PATH_WIDTH)
+        );
     }
     
-    /// 处理项目详情键盘事件
-    async fn handle_project_detail_keys(&mut self, key: crossterm::event::KeyEvent) -> Result<()> {
-        if keys::is_enter_key(&key) || matches!(key.code, crossterm::event::KeyCode::Backspace) {
-            self.state = AppState::ProjectList;
+    /// 开启 `cleanup.restrict_to_scan_paths` 时检查路径是否在扫描根目录之下，不在时在状态栏说明原因
+    fn operation_allowed(&mut self, path: &std::path::Path) -> bool {
+        match self.config.check_operation_path(path) {
+            Ok(()) => true,
+            Err(e) => {
+                self.status_message = format!("⛔ {}", e);
+                false
+            }
         }
+    }
+    
+    /// 选中的项目已被忽略时不直接执行操作：手动忽略的项目询问是否先取消忽略，被规则忽略的项目提示到忽略列表中修改。
+    /// 返回 true 表示操作已被拦下
+    fn confirm_unignore_before(&mut self, action: ConfirmAction) -> bool {
+        let Some(project) = self.projects.get(self.selected_project) else {
+            return false;
+        };
         
-        Ok(())
+        match self.config.ignore.source(&project.path, &self.scan_paths) {
+            None => return false,
+            Some(IgnoreSource::Manual) => {
+                self.status_message = format!("项目 {} 已被手动忽略，取消忽略后继续？ (y/N)", project.name);
+                self.confirm_action = ConfirmAction::UnignoreProject(Box::new(action));
+                self.state = AppState::ConfirmDialog;
+            }
+            Some(source) => {
+                self.status_message = format!("项目 {} 被{}排除，请按 I 在忽略列表中修改", project.name, source.describe());
+            }
+        }
+        true
     }
     
     /// 处理确认对话框键盘事件
     async fn handle_confirm_dialog_keys(&mut self, key: crossterm::event::KeyEvent) -> Result<()> {
+        let return_state = match self.confirm_action {
+            ConfirmAction::DeleteProject | ConfirmAction::CleanProject | ConfirmAction::UnignoreProject(_) => AppState::ProjectList,
+            ConfirmAction::DeleteMergedBranches => AppState::BranchList,
+            ConfirmAction::AcceptCleanupSuggestion(_) => AppState::ProjectDetail,
+        };
+        
+        // 有未保存工作的项目必须用大写 Y 确认删除
+        let has_unsaved_work = self.confirm_action == ConfirmAction::DeleteProject
+            && self.projects.get(self.selected_project)
+                .map(|p| !p.unsaved_work_warnings().is_empty())
+                .unwrap_or(false);
+        
+        if has_unsaved_work && key.code == crossterm::event::KeyCode::Char('y') {
+            self.status_message = "项目有未保存的工作，请按大写 Y 确认删除".to_string();
+            return Ok(());
+        }
+        
         match key.code {
             crossterm::event::KeyCode::Char('y') | crossterm::event::KeyCode::Char('Y') => {
-                self.delete_current_project().await?;
-                self.state = AppState::ProjectList;
+                match self.confirm_action.clone() {
+                    ConfirmAction::DeleteProject => self.delete_current_project().await?,
+                    ConfirmAction::CleanProject => self.clean_current_project().await?,
+                    ConfirmAction::DeleteMergedBranches => self.delete_merged_branches(),
+                    ConfirmAction::AcceptCleanupSuggestion(path) => self.accept_cleanup_suggestion(&path),
+                    ConfirmAction::UnignoreProject(action) => {
+                        self.toggle_ignore_project();
+                        self.state = return_state;
+                        // 取消忽略后回到原来操作的确认流程
+                        match *action {
+                            ConfirmAction::DeleteProject => self.request_delete(),
+                            ConfirmAction::CleanProject => self.request_cleanup_plan(),
+                            _ => {}
+                        }
+                        return Ok(());
+                    }
+                }
+                self.cleanup_plan = None;
+                self.state = return_state;
             }
             _ => {
-                self.state = AppState::ProjectList;
+                self.cleanup_plan = None;
+                self.state = return_state;
                 self.status_message = "操作已取消".to_string();
             }
         }
@@ -605,6 +2722,246 @@ impl App {
         Ok(())
     }
     
+    /// 启动清理向导，依次处理评分最高的清理候选项目
+    fn start_cleanup_wizard(&mut self) {
+        let candidates: Vec<PathBuf> = suggest::rank_cleanup_candidates(&self.projects, chrono::Utc::now())
+            .into_iter()
+            .take(WIZARD_CANDIDATES)
+            .map(|candidate| candidate.project.path.clone())
+            .collect();
+        
+        if candidates.is_empty() {
+            self.status_message = "没有可清理的依赖目录".to_string();
+            return;
+        }
+        
+        self.status_message = format!("清理向导: 共 {} 个候选项目", candidates.len());
+        self.cleanup_wizard = Some(CleanupWizard::new(candidates));
+        self.state = AppState::CleanupWizard;
+    }
+    
+    /// 清理向导当前的候选项目及其评分
+    fn cleanup_wizard_candidate(&self) -> Option<CleanupCandidate<'_>> {
+        let path = self.cleanup_wizard.as_ref()?.current()?;
+        let project = self.projects.iter().find(|p| &p.path == path)?;
+        suggest::score_project(project, chrono::Utc::now())
+    }
+    
+    /// 处理清理向导键盘事件
+    fn handle_cleanup_wizard_keys(&mut self, key: crossterm::event::KeyEvent) {
+        if self.archive_conflict.is_some() {
+            self.handle_archive_conflict_keys(key);
+            return;
+        }
+        
+        let Some(wizard) = &self.cleanup_wizard else {
+            self.state = AppState::ProjectList;
+            return;
+        };
+        
+        if wizard.is_finished() || key.code == crossterm::event::KeyCode::Esc {
+            self.status_message = format!("清理向导结束: {}", wizard);
+            self.cleanup_wizard = None;
+            self.state = AppState::ProjectList;
+            return;
+        }
+        
+        let Some(candidate) = self.cleanup_wizard_candidate() else {
+            // 候选项目已不在列表中（例如已被删除），直接跳过
+            if let Some(wizard) = self.cleanup_wizard.as_mut() {
+                wizard.advance(WizardAction::Skip);
+            }
+            return;
+        };
+        let project_path = candidate.project.path.clone();
+        let project_name = candidate.project.name.clone();
+        let has_unsaved_work = !candidate.project.unsaved_work_warnings().is_empty();
+        
+        let action = match key.code {
+            crossterm::event::KeyCode::Char('c') | crossterm::event::KeyCode::Char('C') => WizardAction::Clean,
+            crossterm::event::KeyCode::Char('s') | crossterm::event::KeyCode::Char('S') => WizardAction::Skip,
+            crossterm::event::KeyCode::Char('a') | crossterm::event::KeyCode::Char('A') => WizardAction::Archive,
+            // 有未保存工作的项目必须用大写 D 确认删除
+            crossterm::event::KeyCode::Char('d') if has_unsaved_work => {
+                self.status_message = "项目有未保存的工作，请按大写 D 确认删除".to_string();
+                return;
+            }
+            crossterm::event::KeyCode::Char('d') | crossterm::event::KeyCode::Char('D') => WizardAction::Delete,
+            _ => return,
+        };
+        if action != WizardAction::Skip && !self.operation_allowed(&project_path) {
+            return;
+        }
+        if action == WizardAction::Archive {
+            if let Some(existing) = ArchiveOperation::new(&self.config.archive.dir).conflicting_target(&project_path) {
+                self.status_message = format!("归档目录中已有同名项目 {}，请选择覆盖、重命名或跳过", project_name);
+                self.archive_conflict = Some(existing);
+                return;
+            }
+        }
+        
+        self.run_cleanup_wizard_action(action, None);
+    }
+    
+    /// 处理归档目标已存在时的选择：覆盖、重命名或跳过，Esc 返回向导
+    fn handle_archive_conflict_keys(&mut self, key: crossterm::event::KeyEvent) {
+        let policy = match key.code {
+            crossterm::event::KeyCode::Char('o') | crossterm::event::KeyCode::Char('O') => ConflictPolicy::Overwrite,
+            crossterm::event::KeyCode::Char('r') | crossterm::event::KeyCode::Char('R') => ConflictPolicy::Rename,
+            crossterm::event::KeyCode::Char('s') | crossterm::event::KeyCode::Char('S') => ConflictPolicy::Skip,
+            crossterm::event::KeyCode::Esc => {
+                self.archive_conflict = None;
+                self.status_message = "已取消归档".to_string();
+                return;
+            }
+            _ => return,
+        };
+        
+        self.archive_conflict = None;
+        match policy {
+            ConflictPolicy::Skip => self.run_cleanup_wizard_action(WizardAction::Skip, None),
+            policy => self.run_cleanup_wizard_action(WizardAction::Archive, Some(policy)),
+        }
+    }
+    
+    /// 对清理向导的当前项目执行操作并前进到下一个项目，`on_conflict` 为归档目标已存在时的处理方式
+    fn run_cleanup_wizard_action(&mut self, action: WizardAction, on_conflict: Option<ConflictPolicy>) {
+        let Some(candidate) = self.cleanup_wizard_candidate() else {
+            return;
+        };
+        let project_path = candidate.project.path.clone();
+        let project_name = candidate.project.name.clone();
+        let disk_usage = candidate.project.disk_usage();
+        
+        if let Some(wizard) = self.cleanup_wizard.as_mut() {
+            wizard.advance(action);
+        }
+        if action == WizardAction::Skip {
+            self.status_message = format!("已跳过 {}", project_name);
+            return;
+        }
+        
+        self.status_message = format!("正在{}: {}", action.label(), project_name);
+        let sender = self.event_handler.sender.clone();
+        let archive_dir = self.config.archive.dir.clone();
+        let cleanup = CleanupOperation::from_config(&self.config)
+            .with_progress(self.deletion_progress_callback());
+        let kind = match action {
+            WizardAction::Archive => TaskKind::Archive,
+            WizardAction::Delete => TaskKind::Delete,
+            WizardAction::Clean | WizardAction::Skip => TaskKind::Clean,
+        };
+        self.spawn_task(kind, project_name.clone(), |task| async move {
+            let token = task.token;
+            let result = match action {
+                WizardAction::Clean => {
+                    let mut summary = cleanup.clean_project(&project_path, &token).await;
+                    summary.wait_for_deletion().await;
+                    Ok(ByteSize::new(summary.freed_bytes()))
+                }
+                WizardAction::Archive => ArchiveOperation::new(archive_dir)
+                    .with_cleanup(cleanup)
+                    .with_conflict_policy(on_conflict)
+                    .archive_project(&project_path, &token).await
+                    .map(|result| ByteSize::new(result.cleanup.freed_bytes()))
+                    .map_err(|e| e.to_string()),
+                WizardAction::Delete => Self::delete_project_to_trash(&project_path).await
+                    .map(|_| disk_usage)
+                    .map_err(|e| e.to_string()),
+                WizardAction::Skip => Ok(ByteSize::ZERO),
+            };
+            let outcome = match &result {
+                Ok(freed) => Ok(format!("已{} {}，释放了 {} 空间", action.label(), project_name, freed)),
+                Err(e) => Err(format!("{} {} 失败: {}", action.label(), project_name, e)),
+            };
+            let _ = sender.send(Event::CleanupWizardStepDone { path: project_path, action, result });
+            outcome
+        });
+    }
+    
+    /// 记录清理向导中操作的结果，并同步更新项目列表
+    fn apply_cleanup_wizard_result(&mut self, path: PathBuf, action: WizardAction, result: Result<ByteSize, String>) {
+        let name = self.projects.iter()
+            .find(|p| p.path == path)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| path_format::format_path(&path, STATUS_PATH_WIDTH));
+        
+        self.status_message = match &result {
+            Ok(freed) => format!("已{} {}，释放了 {}", action.label(), name, freed),
+            Err(e) => format!("{} {} 失败: {}", action.label(), name, e),
+        };
+        if let (Ok(freed), Some(project)) = (&result, self.projects.iter().find(|p| p.path == path)) {
+            if let Some(event) = UsageEvent::for_wizard_action(action, project, *freed) {
+                self.usage.record(event);
+            }
+        }
+        
+        if result.is_ok() {
+            match action {
+                WizardAction::Clean => {
+                    if let Ok(freed) = &result {
+                        self.apply_cleanup_completed(&path, *freed);
+                    }
+                }
+                WizardAction::Archive | WizardAction::Delete => {
+                    self.projects.retain(|p| p.path != path);
+                    self.marked_projects.remove(&path);
+                    if self.selected_project >= self.projects.len() && !self.projects.is_empty() {
+                        self.selected_project = self.projects.len() - 1;
+                    }
+                    self.ensure_selection_visible();
+                }
+                WizardAction::Skip => {}
+            }
+        }
+        
+        if let Some(wizard) = self.cleanup_wizard.as_mut() {
+            wizard.record_result(action, result);
+        }
+    }
+    
+    /// 清理完成后立即更新项目大小，并显示释放空间的徽标
+    fn apply_cleanup_completed(&mut self, project_path: &std::path::Path, freed_size: ByteSize) {
+        if let Some(project) = self.projects.iter_mut().find(|p| p.path == project_path) {
+            project.cached_dependency_size = Some(project.dependency_size() - freed_size);
+            project.total_size -= freed_size;
+            project.dependencies.retain(|dependency| dependency.path.exists());
+        }
+        self.add_size_badge(project_path.to_path_buf(), freed_size);
+    }
+    
+    /// 记录项目释放的空间，同一项目短时间内多次清理时累加
+    fn add_size_badge(&mut self, project_path: PathBuf, freed_size: ByteSize) {
+        if freed_size.is_zero() {
+            return;
+        }
+        let badge = self.size_badges.entry(project_path)
+            .or_insert((ByteSize::ZERO, std::time::Instant::now()));
+        badge.0 += freed_size;
+        badge.1 = std::time::Instant::now();
+    }
+    
+    /// 分组显示时让视图按分组排列，并在展开的项目下方显示依赖目录
+    fn arrange_view(&self, view: &mut ProjectListView) {
+        if let Some(groups) = self.project_groups() {
+            view.group(&groups, &self.collapsed_groups, self.selected_project);
+        }
+        view.expand(&self.expanded_projects);
+        view.git_waiting = view.projects.iter()
+            .filter(|project| self.git_hydration.is_waiting(&project.path))
+            .map(|project| project.path.clone())
+            .collect();
+        view.git_fetch = self.git_fetch.clone();
+    }
+    
+    /// 仍在显示期内的释放空间徽标
+    fn active_size_badges(&self) -> HashMap<PathBuf, ByteSize> {
+        self.size_badges.iter()
+            .filter(|(_, (_, shown_at))| shown_at.elapsed() < SIZE_BADGE_DURATION)
+            .map(|(path, (freed, _))| (path.clone(), *freed))
+            .collect()
+    }
+    
     /// 绘制界面
     fn draw(&mut self, f: &mut Frame) {
         let full_area = f.area();
@@ -625,23 +2982,93 @@ impl App {
                 self.draw_scanning_screen(f, main_area);
             }
             AppState::ProjectList => {
-                self.main_screen.draw_project_list(f, main_area, &self.projects, self.selected_project, &self.current_tab);
+                let mut view = ProjectListView::new(&self.projects, &self.filter, self.sort, self.pinned_order.as_ref(), &self.marked_projects, self.selected_project, &self.scan_paths);
+                self.arrange_view(&mut view);
+                view.stale = self.snapshot_stale;
+                view.searching = self.searching;
+                view.export_prompt = self.export_prompt.as_ref().map(|prompt| (prompt.path.clone(), prompt.format));
+                view.tag_input = self.tag_input.clone();
+                view.size_badges = self.active_size_badges();
+                self.main_screen.draw_project_list(f, main_area, &view, &self.current_tab);
             }
             AppState::ProjectDetail => {
                 if let Some(project) = self.projects.get(self.selected_project) {
-                    self.main_screen.draw_project_detail(f, main_area, project);
+                    let ignore_source = self.config.ignore.source(&project.path, &self.scan_paths);
+                    self.main_screen.draw_project_detail(f, main_area, project, ignore_source.as_ref(), self.selected_suggestion, self.commit_log_scroll);
+                }
+            }
+            AppState::BranchList => {
+                if let Some(project) = self.projects.get(self.selected_project) {
+                    self.main_screen.draw_branch_list(
+                        f,
+                        main_area,
+                        project,
+                        &self.branches,
+                        self.selected_branch,
+                        self.config.git.stale_branch_days,
+                    );
+                }
+            }
+            AppState::DiskUsage => {
+                if let Some(view) = &self.disk_usage {
+                    view.draw(f, main_area);
                 }
             }
             AppState::Help => {
                 self.draw_help_screen(f, main_area);
             }
+            AppState::IgnoreList => {
+                self.draw_ignore_list(f, main_area);
+            }
+            AppState::TaskList => {
+                self.draw_task_list(f, main_area);
+            }
             AppState::ConfirmDialog => {
-                self.main_screen.draw_project_list(f, main_area, &self.projects, self.selected_project, &self.current_tab);
+                match self.confirm_action {
+                    ConfirmAction::DeleteProject | ConfirmAction::CleanProject | ConfirmAction::UnignoreProject(_) => {
+                        let mut view = ProjectListView::new(&self.projects, &self.filter, self.sort, self.pinned_order.as_ref(), &self.marked_projects, self.selected_project, &self.scan_paths);
+                        self.arrange_view(&mut view);
+                        view.size_badges = self.active_size_badges();
+                        self.main_screen.draw_project_list(f, main_area, &view, &self.current_tab);
+                    }
+                    ConfirmAction::AcceptCleanupSuggestion(_) => {
+                        if let Some(project) = self.projects.get(self.selected_project) {
+                            let ignore_source = self.config.ignore.source(&project.path, &self.scan_paths);
+                            self.main_screen.draw_project_detail(f, main_area, project, ignore_source.as_ref(), self.selected_suggestion, self.commit_log_scroll);
+                        }
+                    }
+                    ConfirmAction::DeleteMergedBranches => {
+                        if let Some(project) = self.projects.get(self.selected_project) {
+                            self.main_screen.draw_branch_list(
+                                f,
+                                main_area,
+                                project,
+                                &self.branches,
+                                self.selected_branch,
+                                self.config.git.stale_branch_days,
+                            );
+                        }
+                    }
+                }
                 self.draw_confirm_dialog(f, main_area);
             }
+            AppState::CleanupWizard => {
+                let mut view = ProjectListView::new(&self.projects, &self.filter, self.sort, self.pinned_order.as_ref(), &self.marked_projects, self.selected_project, &self.scan_paths);
+                self.arrange_view(&mut view);
+                view.size_badges = self.active_size_badges();
+                self.main_screen.draw_project_list(f, main_area, &view, &self.current_tab);
+                self.draw_cleanup_wizard(f, main_area);
+            }
+            AppState::ConfirmQuit | AppState::WaitingForOperations => {
+                let mut view = ProjectListView::new(&self.projects, &self.filter, self.sort, self.pinned_order.as_ref(), &self.marked_projects, self.selected_project, &self.scan_paths);
+                self.arrange_view(&mut view);
+                view.size_badges = self.active_size_badges();
+                self.main_screen.draw_project_list(f, main_area, &view, &self.current_tab);
+                self.draw_quit_dialog(f, main_area);
+            }
             AppState::ExternalEditor => {
                 // 在外部编辑器状态下，显示空屏幕或者保持最后的界面
-                // 由于实际上此时终端被nvim接管，这个状态可能不会被渲染
+                // 由于实际上此时终端被编辑器接管，这个状态可能不会被渲染
                 self.draw_loading_screen(f, main_area);
             }
             AppState::Error(ref error) => {
@@ -652,6 +3079,34 @@ impl App {
         
         // 绘制状态栏（使用完整区域）
         self.draw_status_bar(f, full_area);
+        
+        if let Some(overlay) = &self.debug_overlay {
+            self.draw_debug_overlay(f, main_area, overlay);
+        }
+    }
+    
+    /// 在右上角绘制调试浮层
+    fn draw_debug_overlay(&self, f: &mut Frame, area: Rect, overlay: &DebugOverlay) {
+        let width = 48.min(area.width);
+        let height = (overlay.rows.len() as u16 + 2).min(area.height);
+        let popup_area = Rect {
+            x: area.x + area.width - width,
+            y: area.y,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup_area);
+        
+        let lines: Vec<Line> = overlay.rows.iter()
+            .map(|row| Line::from(vec![
+                Span::styled(format!("{:<7}", row.module), Style::default().fg(Color::DarkGray)),
+                Span::raw(format!("{}: ", row.name)),
+                Span::styled(row.value.clone(), Style::default().fg(Color::Cyan)),
+            ]))
+            .collect();
+        let paragraph = Paragraph::new(lines)
+            .block(Block::default().title("调试指标 (F12 关闭)").borders(Borders::ALL).style(Style::default().fg(Color::Magenta)));
+        f.render_widget(paragraph, popup_area);
     }
     
     /// 绘制加载屏幕
@@ -661,92 +3116,447 @@ impl App {
             .borders(Borders::ALL)
             .style(Style::default().fg(Color::Cyan));
         
-        let paragraph = Paragraph::new("正在启动应用程序...")
+        let paragraph = Paragraph::new("正在启动应用程序...")
+            .block(block)
+            .style(Style::default().fg(Color::White));
+        
+        f.render_widget(paragraph, area);
+    }
+    
+    /// 绘制扫描屏幕
+    fn draw_scanning_screen(&self, f: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title("扫描项目")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Yellow));
+        
+        let text = vec![
+            Line::from("正在扫描项目..."),
+            Line::from(""),
+            Line::from(self.scan_progress.clone()),
+        ];
+        
+        let paragraph = Paragraph::new(text)
+            .block(block)
+            .style(Style::default().fg(Color::White));
+        
+        f.render_widget(paragraph, area);
+    }
+    
+    /// 绘制帮助屏幕
+    fn draw_help_screen(&mut self, f: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title("帮助信息")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Green));
+        
+        let mut help_text = Vec::new();
+        if self.help.editing || !self.help.query.is_empty() {
+            let cursor = if self.help.editing { "▏" } else { "" };
+            help_text.push(Line::from(vec![
+                Span::styled("搜索: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{}{}", self.help.query, cursor), Style::default().fg(Color::Yellow)),
+            ]));
+            help_text.push(Line::from(""));
+        }
+        
+        let groups = keymap::search(&self.help.query);
+        if groups.is_empty() {
+            help_text.push(Line::from(format!("  没有匹配 \"{}\" 的快捷键", self.help.query)));
+            help_text.push(Line::from(""));
+        }
+        for (context, bindings) in groups {
+            help_text.push(Line::from(vec![
+                Span::styled(format!("{}:", context.label()), Style::default().add_modifier(Modifier::BOLD))
+            ]));
+            for binding in bindings {
+                help_text.push(Line::from(format!("  {:<16}- {}", binding.keys, binding.description)));
+            }
+            help_text.push(Line::from(""));
+        }
+        
+        // 禁用鼠标捕获时不显示鼠标操作说明
+        if self.config.display.mouse && self.help.query.is_empty() {
+            help_text.extend(vec![
+                Line::from(vec![
+                    Span::styled("鼠标操作:", Style::default().add_modifier(Modifier::BOLD))
+                ]),
+                Line::from("  点击            - 选择项目"),
+                Line::from("  滚轮            - 滚动项目列表"),
+                Line::from("  点击标签        - 切换视图"),
+                Line::from(""),
+            ]);
+        }
+        
+        help_text.push(Line::from("按 / 搜索，↑/↓ 滚动，Enter 或 h 返回项目列表"));
+        
+        // 内容变短（例如搜索后）时把滚动位置限制在最后一行以内
+        let visible_lines = area.height.saturating_sub(2) as usize;
+        self.help.scroll = self.help.scroll.min(help_text.len().saturating_sub(visible_lines));
+        
+        let paragraph = Paragraph::new(help_text)
+            .block(block)
+            .style(Style::default().fg(Color::White))
+            .scroll((self.help.scroll as u16, 0));
+        
+        f.render_widget(paragraph, area);
+    }
+    
+    /// 绘制忽略列表编辑界面
+    fn draw_ignore_list(&self, f: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title("忽略列表")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Green));
+        
+        let ignore = &self.config.ignore;
+        let editor = &self.ignore_editor;
+        let mut tabs = Vec::new();
+        for section in IgnoreSection::ALL {
+            let label = format!(" {} ({}) ", section.label(), section.entries(ignore).len());
+            let style = if section == editor.section {
+                Style::default().fg(Color::Black).bg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            tabs.push(Span::styled(label, style));
+            tabs.push(Span::raw(" "));
+        }
+        
+        let mut lines = vec![Line::from(tabs), Line::from("")];
+        let entries = editor.entries(ignore);
+        if entries.is_empty() {
+            lines.push(Line::from("  （空）"));
+        }
+        for (i, entry) in entries.iter().enumerate() {
+            let editing = editor.input.as_ref()
+                .is_some_and(|input| input.original.as_ref() == Some(*entry));
+            if i == editor.selected && editor.input.is_none() {
+                lines.push(Line::from(Span::styled(
+                    format!("▶ {}", entry),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                )));
+            } else if !editing {
+                lines.push(Line::from(format!("  {}", entry)));
+            }
+            if editing {
+                lines.push(self.ignore_input_line());
+            }
+        }
+        if editor.input.as_ref().is_some_and(|input| input.original.is_none()) {
+            lines.push(self.ignore_input_line());
+        }
+        
+        lines.push(Line::from(""));
+        let hint = match editor.section {
+            IgnoreSection::Directories => "目录名与扫描根目录下的每一级目录比较",
+            IgnoreSection::Paths => "路径规则支持通配符，不含 / 的规则按目录名匹配",
+            IgnoreSection::Projects => "在项目列表中按 i 忽略的项目，重新扫描时跳过",
+        };
+        lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::Gray))));
+        lines.push(Line::from(if editor.input.is_some() {
+            "Enter 保存，Esc 取消"
+        } else {
+            "Tab 切换分组，a 新增，e 修改，d 删除，Esc 返回项目列表"
+        }));
+        
+        // 保持选中的条目可见（前两行为分组标签）
+        let visible_lines = area.height.saturating_sub(2) as usize;
+        let scroll = (editor.selected + 3).saturating_sub(visible_lines);
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(Color::White))
+            .scroll((scroll as u16, 0));
+        
+        f.render_widget(paragraph, area);
+    }
+    
+    /// 绘制后台任务面板：进行中的任务在前，最近结束的任务在后
+    fn draw_task_list(&mut self, f: &mut Frame, area: Rect) {
+        let tasks = self.tasks.display_order();
+        self.selected_task = self.selected_task.min(tasks.len().saturating_sub(1));
+        let active = tasks.iter().filter(|task| !task.status.is_finished()).count();
+        let block = Block::default()
+            .title(format!("后台任务（{} 个进行中）", active))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::Green));
+        
+        let mut lines = Vec::new();
+        if tasks.is_empty() {
+            lines.push(Line::from("  没有后台任务"));
+        }
+        for (i, task) in tasks.iter().enumerate() {
+            let (status, color, detail) = match &task.status {
+                TaskStatus::Pending => ("等待", Color::Gray, String::new()),
+                TaskStatus::Running => ("运行中", Color::Yellow, task.progress().unwrap_or_default()),
+                TaskStatus::Succeeded(message) => ("完成", Color::Green, message.clone()),
+                TaskStatus::Failed(message) => ("失败", Color::Red, message.clone()),
+                TaskStatus::Cancelled => ("已取消", Color::Gray, String::new()),
+            };
+            let marker = if i == self.selected_task { "▶" } else { " " };
+            let mut style = Style::default().fg(color);
+            if i == self.selected_task {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} {:<6} ", marker, status), style),
+                Span::styled(
+                    format!("{} {}", task.kind.label(), truncate_to_width(&task.target, STATUS_PATH_WIDTH)),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(format!("  {:.1}s", task.elapsed().as_secs_f64()), Style::default().fg(Color::Gray)),
+                Span::styled(if detail.is_empty() { String::new() } else { format!("  {}", detail) }, Style::default().fg(Color::Gray)),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("↑/↓ 选择，c 取消选中的任务，Esc 返回项目列表"));
+        
+        // 保持选中的任务可见
+        let visible_lines = area.height.saturating_sub(2) as usize;
+        let scroll = (self.selected_task + 1).saturating_sub(visible_lines.saturating_sub(2));
+        let paragraph = Paragraph::new(lines)
             .block(block)
-            .style(Style::default().fg(Color::White));
+            .scroll((scroll as u16, 0));
         
         f.render_widget(paragraph, area);
     }
     
-    /// 绘制扫描屏幕
-    fn draw_scanning_screen(&self, f: &mut Frame, area: Rect) {
+    /// 忽略列表中正在输入的条目
+    fn ignore_input_line(&self) -> Line<'static> {
+        let text = self.ignore_editor.input.as_ref().map(|input| input.text.clone()).unwrap_or_default();
+        Line::from(vec![
+            Span::styled("> ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{}▏", text), Style::default().fg(Color::Yellow)),
+        ])
+    }
+    
+    /// 绘制确认对话框
+    fn draw_confirm_dialog(&self, f: &mut Frame, area: Rect) {
+        let message = match &self.confirm_action {
+            ConfirmAction::DeleteProject => format!(
+                "确认删除项目 {}？",
+                self.projects.get(self.selected_project).map(|p| p.name.as_str()).unwrap_or_default()
+            ),
+            ConfirmAction::CleanProject => format!(
+                "确认清理 {} 的依赖目录？预计释放 {}",
+                self.projects.get(self.selected_project).map(|p| p.name.as_str()).unwrap_or_default(),
+                crate::utils::size_format::format_size(self.cleanup_plan.as_ref().map(|(_, plan)| plan.total_size()).unwrap_or(0))
+            ),
+            ConfirmAction::DeleteMergedBranches => format!(
+                "确认删除 {} 个已合并分支？",
+                self.branches.iter().filter(|b| b.is_deletable()).count()
+            ),
+            ConfirmAction::AcceptCleanupSuggestion(path) => format!(
+                "确认删除 {}？预计释放 {}",
+                path_format::format_path(path, STATUS_PATH_WIDTH),
+                self.projects.get(self.selected_project)
+                    .and_then(|p| p.pending_cleanup_suggestions().into_iter().find(|suggestion| &suggestion.path == path))
+                    .map(|suggestion| suggestion.size)
+                    .unwrap_or_default()
+            ),
+            ConfirmAction::UnignoreProject(action) => format!(
+                "项目 {} 已被手动忽略，取消忽略并继续{}？",
+                self.projects.get(self.selected_project).map(|p| p.name.as_str()).unwrap_or_default(),
+                if matches!(action.as_ref(), ConfirmAction::DeleteProject) { "删除" } else { "清理" }
+            ),
+        };
+        
+        let unsaved_warnings = match self.confirm_action {
+            ConfirmAction::DeleteProject => self.projects.get(self.selected_project)
+                .map(|p| p.unsaved_work_warnings())
+                .unwrap_or_default(),
+            ConfirmAction::CleanProject
+            | ConfirmAction::DeleteMergedBranches
+            | ConfirmAction::AcceptCleanupSuggestion(_)
+            | ConfirmAction::UnignoreProject(_) => Vec::new(),
+        };
+        
+        // 清理计划：每个目录的相对路径、大小和来源
+        let mut plan_lines = Vec::new();
+        if let (ConfirmAction::CleanProject, Some((project_path, plan))) = (&self.confirm_action, &self.cleanup_plan) {
+            for entry in &plan.entries {
+                let relative = entry.path.strip_prefix(project_path).unwrap_or(&entry.path);
+                plan_lines.push(Line::from(vec![
+                    Span::raw(format!("  {:>10}  ", crate::utils::size_format::format_size(entry.size))),
+                    Span::styled(relative.display().to_string(), Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("  [{}]", entry.kind), Style::default().fg(Color::Gray)),
+                ]));
+                if let Some(warning) = entry.warning {
+                    plan_lines.push(Line::from(Span::styled(
+                        format!("              ⚠ {}", warning),
+                        Style::default().fg(Color::Yellow),
+                    )));
+                }
+            }
+            plan_lines.push(Line::from(""));
+        }
+        
+        // 有警告或清理计划时加高对话框
+        let popup_height = if !plan_lines.is_empty() {
+            60
+        } else if unsaved_warnings.is_empty() {
+            20
+        } else {
+            35
+        };
+        let popup_area = self.centered_rect(50, popup_height, area);
+        
+        f.render_widget(Clear, popup_area);
+        
         let block = Block::default()
-            .title("扫描项目")
+            .title("确认操作")
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Yellow));
+            .style(Style::default().fg(Color::Red));
         
-        let text = vec![
-            Line::from("正在扫描项目..."),
+        let mut text = vec![
             Line::from(""),
-            Line::from(self.scan_progress.clone()),
+            Line::from(message),
         ];
+        if let (ConfirmAction::DeleteProject, Some(project)) = (&self.confirm_action, self.projects.get(self.selected_project)) {
+            text.push(Line::from(Span::styled(
+                path_format::format_path(&project.path, popup_area.width.saturating_sub(2) as usize),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        text.push(Line::from(""));
+        text.extend(plan_lines);
+        
+        if unsaved_warnings.is_empty() {
+            text.push(Line::from("按 'y' 确认，按任意键取消"));
+        } else {
+            for warning in &unsaved_warnings {
+                text.push(Line::from(Span::styled(
+                    format!("⚠ {}", warning),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                )));
+            }
+            text.push(Line::from(""));
+            text.push(Line::from("按 'Y'（大写）确认，按任意键取消"));
+        }
         
         let paragraph = Paragraph::new(text)
             .block(block)
             .style(Style::default().fg(Color::White));
         
-        f.render_widget(paragraph, area);
+        f.render_widget(paragraph, popup_area);
     }
     
-    /// 绘制帮助屏幕
-    fn draw_help_screen(&self, f: &mut Frame, area: Rect) {
+    /// 绘制清理向导对话框
+    fn draw_cleanup_wizard(&self, f: &mut Frame, area: Rect) {
+        let Some(wizard) = &self.cleanup_wizard else {
+            return;
+        };
+        
+        let popup_area = self.centered_rect(60, 45, area);
+        f.render_widget(Clear, popup_area);
+        
+        let (index, total) = wizard.progress();
         let block = Block::default()
-            .title("帮助信息")
+            .title(format!("清理向导 ({}/{})", index, total))
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Green));
+            .style(Style::default().fg(Color::Cyan));
         
-        let help_text = vec![
-            Line::from(vec![
-                Span::styled("快捷键:", Style::default().add_modifier(Modifier::BOLD))
-            ]),
-            Line::from(""),
-            Line::from("  q, Ctrl+C       - 退出应用程序"),
-            Line::from("  r, F5           - 刷新项目列表"),
-            Line::from("  h, ?, F1        - 显示帮助信息"),
-            Line::from("  Tab             - 切换视图标签"),
-            Line::from(""),
-            Line::from("  ↑/↓, k/j        - 导航项目列表"),
-            Line::from("  Enter, Space    - 查看项目详情"),
-            Line::from("  d, Delete       - 删除项目"),
-            Line::from("  c               - 清理项目依赖"),
-            Line::from("  i               - 切换忽略状态"),
-            Line::from("  e               - 使用nvim编辑项目"),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("鼠标操作:", Style::default().add_modifier(Modifier::BOLD))
-            ]),
-            Line::from(""),
-            Line::from("  点击            - 选择项目"),
-            Line::from("  滚轮            - 滚动项目列表"),
-            Line::from("  点击标签        - 切换视图"),
-            Line::from(""),
-            Line::from("按 Enter 或 h 返回项目列表"),
-        ];
+        let mut text = vec![Line::from("")];
         
-        let paragraph = Paragraph::new(help_text)
+        if wizard.is_finished() {
+            text.push(Line::from(Span::styled("全部候选项目已处理完毕", Style::default().add_modifier(Modifier::BOLD))));
+        } else if let Some(candidate) = self.cleanup_wizard_candidate() {
+            let project = candidate.project;
+            text.push(Line::from(vec![
+                Span::styled(project.name.clone(), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  [评分 {:.1}]", candidate.score), Style::default().fg(Color::Magenta)),
+            ]));
+            text.push(Line::from(Span::styled(
+                path_format::format_path(&project.path, popup_area.width.saturating_sub(2) as usize),
+                Style::default().fg(Color::Gray),
+            )));
+            text.push(Line::from(candidate.reasons().join(" · ")));
+            for warning in project.unsaved_work_warnings() {
+                text.push(Line::from(Span::styled(
+                    format!("⚠ {}", warning),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                )));
+            }
+        } else {
+            text.push(Line::from("该项目已不在列表中，按任意键跳过"));
+        }
+        
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(wizard.to_string(), Style::default().fg(Color::Yellow))));
+        text.push(Line::from(""));
+        if let (Some(existing), Some(project_path)) = (&self.archive_conflict, wizard.current()) {
+            let renamed = ArchiveOperation::new(&self.config.archive.dir).renamed_target(project_path);
+            text.push(Line::from(Span::styled(
+                format!("⚠ 归档目录中已有同名项目: {}", path_format::format_path(existing, popup_area.width.saturating_sub(28) as usize)),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            )));
+            text.push(Line::from(format!(
+                "o 覆盖（已有目录移到回收站） | r 重命名为 {} | s 跳过 | Esc 返回",
+                renamed.file_name().unwrap_or_default().to_string_lossy()
+            )));
+        } else {
+            text.push(Line::from(if wizard.is_finished() {
+                "按任意键返回项目列表"
+            } else {
+                "c 清理依赖 | s 跳过 | a 归档 | d 删除 | Esc 结束"
+            }));
+        }
+        
+        let paragraph = Paragraph::new(text)
             .block(block)
             .style(Style::default().fg(Color::White));
         
-        f.render_widget(paragraph, area);
+        f.render_widget(paragraph, popup_area);
     }
     
-    /// 绘制确认对话框
-    fn draw_confirm_dialog(&self, f: &mut Frame, area: Rect) {
-        let popup_area = self.centered_rect(50, 20, area);
-        
+    /// 绘制退出确认对话框，列出进行中的操作
+    fn draw_quit_dialog(&self, f: &mut Frame, area: Rect) {
+        let popup_area = self.centered_rect(60, 45, area);
         f.render_widget(Clear, popup_area);
         
+        let waiting = self.state == AppState::WaitingForOperations;
         let block = Block::default()
-            .title("确认操作")
+            .title(if waiting { "等待操作完成" } else { "确认退出" })
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::Red));
+            .style(Style::default().fg(Color::Yellow));
         
-        let text = vec![
+        let quit_tasks: Vec<&Task> = self.quit_tasks.iter().filter_map(|id| self.tasks.get(*id)).collect();
+        let running = quit_tasks.iter().filter(|task| !task.is_done()).count();
+        let mut text = vec![
             Line::from(""),
-            Line::from("确认删除选中的项目？"),
+            Line::from(Span::styled(
+                format!("有 {} 个操作正在进行，现在退出会中断它们", running),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
             Line::from(""),
-            Line::from("按 'y' 确认，按任意键取消"),
         ];
+        for task in quit_tasks {
+            let (mark, color) = if task.is_done() {
+                ("✓", Color::Green)
+            } else {
+                ("…", Color::Yellow)
+            };
+            text.push(Line::from(Span::styled(format!("  {} {}", mark, task.label()), Style::default().fg(color))));
+        }
+        for (path, progress) in &self.deletion_progress {
+            text.push(Line::from(Span::styled(
+                format!(
+                    "    删除 {}: {}/{} 文件，{}/{}",
+                    path_format::format_path(path, STATUS_PATH_WIDTH),
+                    progress.deleted_files,
+                    progress.total_files,
+                    crate::utils::size_format::format_size(progress.deleted_bytes),
+                    crate::utils::size_format::format_size(progress.total_bytes)
+                ),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(if waiting {
+            "完成后自动退出 | q 立即退出 | Esc 返回"
+        } else {
+            "w 等待完成 | c 取消操作并退出 | q 仍然退出 | Esc 返回"
+        }));
         
         let paragraph = Paragraph::new(text)
             .block(block)
@@ -781,13 +3591,13 @@ impl App {
         // 构建左侧状态信息
         let left_status_text = match self.state {
             AppState::ProjectList => {
-                let calculating_count = self.calculation_tasks.len();
+                let task_count = self.tasks.active().count();
                 format!("{} | 项目: {} | 选中: {}/{}{}", 
                     self.status_message,
                     self.projects.len(),
                     if self.projects.is_empty() { 0 } else { self.selected_project + 1 },
                     self.projects.len(),
-                    if calculating_count > 0 { format!(" | 计算中: {}", calculating_count) } else { String::new() }
+                    if task_count > 0 { format!(" | 后台任务: {}", task_count) } else { String::new() }
                 )
             }
             _ => self.status_message.clone(),
@@ -801,13 +3611,13 @@ impl App {
             } else if !self.scan_progress.is_empty() {
                 self.scan_progress.clone()
             } else {
-                "快捷键: ↑/↓ 选择 | Enter 详情 | d 删除 | c 清理 | i 忽略 | e 编辑 | r 刷新 | q 退出".to_string()
+                "快捷键: ↑/↓ 选择 | Enter 详情 | d 删除 | c 清理 | w 向导 | i 忽略 | e 编辑 | r 刷新 | q 退出".to_string()
             }
         };
         
         // 计算布局：左侧状态信息，右侧进度信息
-        let left_width = left_status_text.len() as u16;
-        let right_width = right_progress_text.len() as u16;
+        let left_width = left_status_text.width() as u16;
+        let right_width = right_progress_text.width() as u16;
         let total_width = status_area.width;
         
         // 检查是否有进度信息需要高亮显示
@@ -825,7 +3635,7 @@ impl App {
                 let truncated_right = if right_width <= remaining {
                     right_progress_text
                 } else {
-                    format!("{}...", &right_progress_text[..remaining.saturating_sub(3) as usize])
+                    truncate_to_width(&right_progress_text, remaining as usize)
                 };
                 format!("{}   {}", left_status_text, truncated_right)
             } else {
@@ -833,7 +3643,7 @@ impl App {
                 if left_width <= total_width {
                     left_status_text
                 } else {
-                    format!("{}...", &left_status_text[..total_width.saturating_sub(3) as usize])
+                    truncate_to_width(&left_status_text, total_width as usize)
                 }
             }
         };
@@ -874,11 +3684,21 @@ impl App {
             .split(popup_layout[1])[1]
     }
     
-    /// 开始扫描项目
+    /// 开始扫描项目：在后台发现项目，期间继续显示当前列表（如上次会话快照）
     async fn start_scan(&mut self) -> Result<()> {
-        self.state = AppState::Scanning;
-        self.status_message = "正在扫描项目...".to_string();
-        self.projects.clear();
+        // 扫描期间已显示的项目保持位置，新发现的项目排在末尾
+        self.pinned_order = Some(PinnedOrder::capture(&self.projects, self.sort));
+        
+        if self.projects.is_empty() {
+            self.state = AppState::Scanning;
+            self.status_message = "正在扫描项目...".to_string();
+        } else {
+            if self.state == AppState::Starting {
+                self.state = AppState::ProjectList;
+            }
+            self.snapshot_stale = true;
+            self.status_message = "正在后台刷新项目列表...".to_string();
+        }
         
         // 初始化扫描进度状态
         self.progress_info = ProgressInfo {
@@ -889,174 +3709,335 @@ impl App {
             extra_info: String::new(),
         };
         
-        // 使用最简单的扫描方式：直接遍历目录查找项目标识文件
         let scan_paths = self.scan_paths.clone();
-        for path_str in &scan_paths {
-            let path = std::path::Path::new(path_str);
-            
-            if !path.exists() || !path.is_dir() {
-                continue;
+        let config = self.config.clone();
+        let sender = self.event_handler.sender.clone();
+        self.spawn_task(TaskKind::Scan, self.scan_paths.join(", "), |_| async move {
+            let (projects, skipped) = match Self::discover_projects(&scan_paths, &config).await {
+                Ok(result) => result,
+                Err(e) => {
+                    let message = format!("扫描失败: {}", e);
+                    let _ = sender.send(Event::ScanFailed(message.clone()));
+                    return Err(message);
+                }
+            };
+            let message = format!("发现 {} 个项目", projects.len());
+            let _ = sender.send(Event::ScanResults(projects));
+            if !skipped.is_empty() {
+                let _ = sender.send(Event::LocationsSkipped(skipped.to_string()));
             }
-            
-            self.scan_directory_simple(path).await?;
+            Ok(message)
+        });
+        
+        Ok(())
+    }
+    
+    /// 刷新项目列表：列表为空时完整扫描，否则只重新检测新增、消失和修改过的项目，未修改的项目保留现有信息
+    async fn start_refresh(&mut self) -> Result<()> {
+        if self.projects.is_empty() {
+            return self.start_scan().await;
         }
         
-        // 扫描完成，开始异步计算大小
-        self.state = AppState::ProjectList;
-        self.status_message = format!("扫描完成！发现 {} 个项目", self.projects.len());
+        self.pinned_order = Some(PinnedOrder::capture(&self.projects, self.sort));
+        self.snapshot_stale = true;
+        self.status_message = "正在后台刷新项目列表...".to_string();
         
-        // 启动异步大小计算任务
-        self.start_async_size_calculation().await?;
+        let scan_paths = self.scan_paths.clone();
+        let config = self.config.clone();
+        let cache_config = self.config.cache.to_size_cache_config();
+        let known: Vec<PathBuf> = self.projects.iter().map(|p| p.path.clone()).collect();
+        let sender = self.event_handler.sender.clone();
+        self.spawn_task(TaskKind::Scan, self.scan_paths.join(", "), |_| async move {
+            let (projects, skipped) = match Self::discover_projects(&scan_paths, &config).await {
+                Ok(result) => result,
+                Err(e) => {
+                    let message = format!("扫描失败: {}", e);
+                    let _ = sender.send(Event::ScanFailed(message.clone()));
+                    return Err(message);
+                }
+            };
+            let discovered: Vec<PathBuf> = projects.iter().map(|p| p.path.clone()).collect();
+            let cache = SizeCache::new(cache_config).await.ok();
+            let changes = incremental::diff_projects(&discovered, &known, cache.as_ref()).await;
+            
+            let count = |f: fn(&ProjectChange) -> bool| changes.iter().filter(|change| f(change)).count();
+            let message = format!(
+                "新增 {} 个，更新 {} 个，移除 {} 个项目",
+                count(|change| matches!(change, ProjectChange::Found(_))),
+                count(|change| matches!(change, ProjectChange::Updated(_))),
+                count(|change| matches!(change, ProjectChange::Removed(_))),
+            );
+            let mut projects: HashMap<PathBuf, Project> = projects.into_iter().map(|p| (p.path.clone(), p)).collect();
+            for change in changes {
+                let event = match change {
+                    ProjectChange::Found(path) => projects.remove(&path).map(Event::ProjectFound),
+                    ProjectChange::Updated(path) => projects.remove(&path).map(Event::ProjectUpdated),
+                    ProjectChange::Removed(path) => Some(Event::ProjectRemoved(path)),
+                };
+                if let Some(event) = event {
+                    let _ = sender.send(event);
+                }
+            }
+            let _ = sender.send(Event::ScanComplete);
+            if !skipped.is_empty() {
+                let _ = sender.send(Event::LocationsSkipped(skipped.to_string()));
+            }
+            Ok(message)
+        });
         
         Ok(())
     }
     
-    /// 简单扫描目录
-    async fn scan_directory_simple(&mut self, dir: &std::path::Path) -> Result<()> {
-        use tokio::fs;
-        use std::collections::VecDeque;
+    /// 用重新检测的结果替换修改过的项目并重新计算详细信息，计算完成前保留原有的大小和 Git 信息
+    fn apply_project_update(&mut self, project: Project) {
+        let Some(index) = self.projects.iter().position(|p| p.path == project.path) else {
+            return;
+        };
+        
+        let mut project = session::reconcile(vec![project], &self.projects[index..=index]).remove(0);
+        project.is_hidden = self.is_hidden_path(&project.path);
+        project.tags = self.config.display.tags_for(&project.path);
+        project.is_ignored = self.projects[index].is_ignored;
+        let target = (!project.is_ignored).then(|| (project.path.clone(), project.name.clone()));
+        self.projects[index] = project;
+        if let Some((project_path, project_name)) = target {
+            self.spawn_details_calculation(project_path, project_name);
+        }
+    }
+    
+    /// 从列表中移除已不存在的项目；选中的项目被移除时退回项目列表，避免后续操作落到其他项目上
+    fn remove_project(&mut self, path: &Path) {
+        let Some(index) = self.projects.iter().position(|p| p.path == path) else {
+            return;
+        };
+        
+        self.projects.remove(index);
+        self.marked_projects.remove(path);
+        self.detail_tasks.remove(path);
+        self.git_hydration.remove(path);
+        self.git_fetch.remove(path);
+        if self.selected_project > index {
+            self.selected_project -= 1;
+        } else if self.selected_project == index
+            && matches!(self.state, AppState::ProjectDetail | AppState::BranchList | AppState::DiskUsage | AppState::ConfirmDialog)
+        {
+            self.state = AppState::ProjectList;
+            self.status_message = format!("项目已不存在: {}", path_format::format_path(path, STATUS_PATH_WIDTH));
+        }
+        self.selected_project = self.selected_project.min(self.projects.len().saturating_sub(1));
+        self.ensure_selection_visible();
+    }
+    
+    /// 用后台扫描结果替换当前列表，并为每个项目启动详细信息计算
+    async fn apply_scan_results(&mut self, fresh: Vec<Project>) -> Result<()> {
+        let selected_path = self.projects.get(self.selected_project).map(|p| p.path.clone());
         
-        let mut queue = VecDeque::new();
-        queue.push_back(dir.to_path_buf());
-        let mut scanned_count = 0;
+        self.projects = session::reconcile(fresh, &self.projects);
+        self.apply_hidden_flags();
+        self.apply_tags();
+        self.apply_ignored_flags();
         
-        while let Some(current_dir) = queue.pop_front() {
-            scanned_count += 1;
-            
-            // 限制扫描深度和数量，避免卡死
-            if scanned_count > 1000 {
-                break;
+        let paths: HashSet<PathBuf> = self.projects.iter().map(|p| p.path.clone()).collect();
+        self.marked_projects.retain(|path| paths.contains(path));
+        self.selected_project = selected_path
+            .and_then(|path| self.projects.iter().position(|p| p.path == path))
+            .unwrap_or(0);
+        self.ensure_selection_visible();
+        
+        self.snapshot_stale = false;
+        if matches!(self.state, AppState::Starting | AppState::Scanning) {
+            self.state = AppState::ProjectList;
+        }
+        self.status_message = format!("扫描完成！发现 {} 个项目", self.projects.len());
+        self.update_pinned_order();
+        
+        let targets: Vec<(PathBuf, String)> = self.projects.iter()
+            .filter(|p| !p.is_ignored)
+            .map(|p| (p.path.clone(), p.name.clone()))
+            .collect();
+        for (project_path, project_name) in targets {
+            self.spawn_details_calculation(project_path, project_name);
+        }
+        
+        self.restart_watcher();
+        
+        // 启动异步大小计算任务
+        self.start_async_size_calculation().await
+    }
+    
+    /// 按当前项目列表重新开始监视扫描路径；未启用监视或浏览快照时不监视
+    fn restart_watcher(&mut self) {
+        if let Some(token) = self.watcher_token.take() {
+            token.cancel();
+        }
+        let Some(interval) = self.config.scan.watch_interval() else {
+            return;
+        };
+        if self.snapshot_file.is_some() {
+            return;
+        }
+        
+        let roots: Vec<PathBuf> = self.scan_paths.iter().map(PathBuf::from).collect();
+        let projects: Vec<(PathBuf, Option<u64>)> = self.projects.iter()
+            .filter(|p| !p.is_ignored)
+            .map(|p| (p.path.clone(), p.cached_dependency_size.map(ByteSize::bytes)))
+            .collect();
+        let cleanup_config = self.config.cleanup.clone();
+        let token = self.cancellation_token.child_token();
+        let sender = self.event_handler.sender.clone();
+        self.watcher_token = Some(token.clone());
+        tokio::spawn(async move {
+            let watcher = tokio::task::spawn_blocking(move || {
+                let projects = projects.into_iter()
+                    .map(|(path, dependency_size)| WatchedProject {
+                        dependency_dirs: cleanup_strategy::strategies_for_project(&path, &cleanup_config).iter()
+                            .flat_map(|strategy| strategy.dirs.iter().map(|dir| path.join(dir)))
+                            .collect(),
+                        path,
+                        dependency_size,
+                    })
+                    .collect();
+                ProjectWatcher::new(&roots, projects)
+            }).await;
+            if let Ok(watcher) = watcher {
+                watcher.run(interval, token, |event| {
+                    let _ = sender.send(Event::Watch(event));
+                }).await;
             }
-            
-            // 跳过大型目录
-            if let Some(dir_name) = current_dir.file_name().and_then(|n| n.to_str()) {
-                if matches!(dir_name, "node_modules" | ".git" | "target" | "dist" | "build" | "venv" | ".venv") {
-                    continue;
+        });
+    }
+    
+    /// 处理监视发现的变化：目录有变化时增量刷新，项目消失时移除，依赖大小变化时重新计算详细信息
+    async fn handle_watch_event(&mut self, event: WatchEvent) -> Result<()> {
+        match event {
+            WatchEvent::LayoutChanged(path) => {
+                // 一次检查可能报告多个目录，正在刷新时不重复刷新
+                if !self.tasks.active().any(|task| task.kind == TaskKind::Scan) {
+                    tracing::debug!("{} 有变化，增量刷新项目列表", path.display());
+                    self.start_refresh().await?;
                 }
             }
-            
-            // 检查是否是项目
-            if self.is_project_directory(&current_dir).await {
-                let project_name = current_dir
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("Unknown")
-                    .to_string();
-                
-                let project = Project {
-                    name: project_name.clone(),
-                    path: current_dir.clone(),
-                    project_type: self.detect_project_type(&current_dir).await,
-                    code_size: 0,
-                    total_size: 0,
-                    gitignore_excluded_size: 0,
-                    code_file_count: 0,
-                    dependency_file_count: 0,
-                    total_file_count: 0,
-                    gitignore_excluded_file_count: 0,
-                    last_modified: chrono::Utc::now(),
-                    git_info: None,
-                    dependencies: Vec::new(),
-                    is_ignored: false,
-                    description: None,
-                    dependency_calculation_status: DependencyCalculationStatus::NotCalculated,
-                    cached_dependency_size: None,
-                };
-                
-                self.projects.push(project.clone());
-                
-                // 为当前目录项目也启动异步计算详细信息
-                let project_path = current_dir.clone();
-                let project_name = project.name.clone();
-                let sender = self.event_handler.sender.clone();
-                
-                let cancel_token = self.cancellation_token.clone();
-                tokio::spawn(async move {
-                    // 先发送开始计算事件
-                    let _ = sender.send(Event::ProjectCalculationStarted {
-                        project_name: project_name.clone(),
-                    });
-                    
-                    Self::calculate_project_details(project_path, project_name, sender, cancel_token).await;
-                });
-                
-                // 发现项目后不再扫描其子目录
-                continue;
-            }
-            
-            // 扫描子目录
-            if let Ok(mut entries) = fs::read_dir(&current_dir).await {
-                while let Ok(Some(entry)) = entries.next_entry().await {
-                    if let Ok(metadata) = entry.metadata().await {
-                        if metadata.is_dir() {
-                            queue.push_back(entry.path());
-                        }
-                    }
+            WatchEvent::ProjectDeleted(path) => {
+                if let Some(project) = self.projects.iter().find(|p| p.path == path) {
+                    self.status_message = format!("项目 {} 已被删除", project.name);
                 }
+                self.remove_project(&path);
             }
-            
-            // 防止界面卡死，定期让出控制权
-            if scanned_count % 10 == 0 {
-                tokio::task::yield_now().await;
+            WatchEvent::DependenciesChanged { project, previous, current } => {
+                let Some(project) = self.projects.iter().find(|p| p.path == project && !p.is_ignored) else {
+                    return Ok(());
+                };
+                self.status_message = format!(
+                    "{} 的依赖目录大小变化: {} → {}",
+                    project.name,
+                    crate::utils::size_format::format_size(previous),
+                    crate::utils::size_format::format_size(current)
+                );
+                let (project_path, project_name) = (project.path.clone(), project.name.clone());
+                self.spawn_details_calculation(project_path, project_name);
             }
         }
-        
         Ok(())
     }
     
-    /// 检查目录是否是项目
-    async fn is_project_directory(&self, dir: &std::path::Path) -> bool {
-        let project_files = [
-            "package.json",    // Node.js
-            "Cargo.toml",      // Rust
-            "pyproject.toml",  // Python
-            "requirements.txt", // Python
-            "go.mod",          // Go
-            "pom.xml",         // Java Maven
-            "build.gradle",    // Java Gradle
-            "CMakeLists.txt",  // C++
-            ".git",            // Git repo
-        ];
+    /// 在后台计算项目的详细信息；Git 信息等项目显示出来或被选中时再分析
+    fn spawn_details_calculation(&mut self, project_path: PathBuf, project_name: String) {
+        self.git_hydration.queue(project_path.clone());
+        let sender = self.event_handler.sender.clone();
+        let limiter = self.size_limiter.clone();
+        let task_path = project_path.clone();
         
-        for file in &project_files {
-            if dir.join(file).exists() {
-                return true;
-            }
+        let id = self.spawn_task(TaskKind::Details, project_name.clone(), |task| async move {
+            // 先发送开始计算事件
+            let _ = sender.send(Event::ProjectCalculationStarted {
+                project_name: project_name.clone(),
+            });
+            
+            Self::calculate_project_details(project_path, project_name, sender, limiter, task.token).await;
+            Ok(String::new())
+        });
+        self.detail_tasks.insert(task_path, id);
+    }
+    
+    /// 取消选中项目正在进行（或等待名额）的详细信息计算，释放占用的计算名额
+    fn cancel_selected_calculation(&mut self) {
+        let Some(project) = self.projects.get_mut(self.selected_project) else {
+            return;
+        };
+        let cancelled = self.detail_tasks.remove(&project.path)
+            .is_some_and(|id| self.tasks.cancel(id));
+        if !cancelled {
+            self.status_message = format!("{} 没有正在进行的大小计算", project.name);
+            return;
         }
         
-        false
+        project.dependency_calculation_status = DependencyCalculationStatus::Cancelled;
+        self.status_message = format!("已取消计算 {} 的大小", project.name);
+        self.update_pinned_order();
     }
     
-    /// 检测项目类型
-    async fn detect_project_type(&self, dir: &std::path::Path) -> crate::models::ProjectType {
-        use crate::models::ProjectType;
-        
-        if dir.join("package.json").exists() {
-            ProjectType::NodeJs
-        } else if dir.join("Cargo.toml").exists() {
-            ProjectType::Rust
-        } else if dir.join("pyproject.toml").exists() || dir.join("requirements.txt").exists() {
-            ProjectType::Python
-        } else if dir.join("go.mod").exists() {
-            ProjectType::Go
-        } else if dir.join("pom.xml").exists() || dir.join("build.gradle").exists() {
-            ProjectType::Java
-        } else if dir.join("CMakeLists.txt").exists() {
-            ProjectType::Cpp
-        } else if dir.join(".git").exists() {
-            ProjectType::Git
-        } else {
-            ProjectType::Unknown
+    /// 分析等待中的项目的 Git 信息：选中和列表中可见的项目立即分析，其余按列表顺序在后台逐个分析
+    fn hydrate_git_info(&mut self) {
+        if self.snapshot_file.is_some() {
+            return;
+        }
+        
+        let selected = self.projects.get(self.selected_project).map(|p| p.path.as_path());
+        let visible = self.main_screen.visible_projects().iter().map(PathBuf::as_path);
+        let background = self.projects.iter().filter(|p| !p.is_ignored).map(|p| p.path.as_path());
+        let batch = self.git_hydration.next_batch(selected.into_iter().chain(visible), background, max_concurrent_git_tasks());
+        for project_path in batch {
+            self.spawn_git_analysis(project_path);
         }
     }
     
+    /// 在后台分析项目的 Git 信息，完成后发送 GitInfoLoaded
+    fn spawn_git_analysis(&self, project_path: PathBuf) {
+        let sender = self.event_handler.sender.clone();
+        let token = self.cancellation_token.child_token();
+        tokio::spawn(async move {
+            let git_path = project_path.clone();
+            let git_info = match run_git_task(&token, move |_| GitAnalyzer::new().analyze_repository(&git_path)).await {
+                Some(Ok(info)) => info,
+                Some(Err(e)) => {
+                    tracing::warn!("分析 {} 的 Git 信息失败: {}", project_path.display(), e);
+                    None
+                }
+                None if token.is_cancelled() => return,
+                None => None,
+            };
+            let _ = sender.send(Event::GitInfoLoaded { project_path, git_info });
+        });
+    }
+    
+    /// 按配置在所有扫描路径中查找项目，同时返回因权限等原因跳过的位置
+    ///
+    /// 与命令行使用相同的 FileWalker，遵循忽略规则、扫描深度、隐藏目录和琐碎项目的设置；
+    /// 手动忽略的项目仍然列出并标记为已忽略，以便在界面中显示和取消忽略。
+    async fn discover_projects(scan_paths: &[String], config: &Config) -> Result<(Vec<Project>, SkippedLocations)> {
+        let mut config = config.clone();
+        config.ignore.projects.clear();
+        let walker = FileWalker::new(config).with_progress_bar(false);
+        
+        let (detected, skipped) = walker.scan_paths_with_skipped(scan_paths).await?;
+        let projects = detected.into_iter()
+            .map(|detected| {
+                let last_modified = stream::directory_modified(&detected.path);
+                detected.into_project(None, last_modified)
+            })
+            .collect();
+        Ok((projects, skipped))
+    }
+    
     /// 启动异步大小计算任务
     async fn start_async_size_calculation(&mut self) -> Result<()> {
         let projects_for_calc = self.projects.clone();
         let sender = self.event_handler.sender.clone();
+        let limiter = self.size_limiter.clone();
+        let target = format!("{} 个项目", projects_for_calc.iter().filter(|p| !p.is_ignored).count());
         
         // 在后台异步计算每个项目的大小
-        tokio::spawn(async move {
+        self.spawn_task(TaskKind::Size, target, |task| async move {
             use crate::scanner::SizeCalculator;
             use crate::config::Config;
             
@@ -1064,14 +4045,33 @@ impl App {
             let config = Config::load_or_create_default().unwrap_or_default();
             let mut size_calculator = SizeCalculator::new_with_cache(config.cache.to_size_cache_config())
                 .await
-                .unwrap_or_else(|_| SizeCalculator::new());
+                .unwrap_or_else(|_| SizeCalculator::new())
+                .with_limits(config.scan.to_size_limits())
+                .with_follow_symlinks(config.scan.follow_symlinks)
+                .with_dependency_dirs(config.detection.dependency_dirs())
+                .with_concurrency(config.scan.concurrent_scans);
+            if let Some(recovery) = size_calculator.cache_recovery() {
+                let _ = sender.send(Event::ScanProgress(recovery.to_string()));
+            }
             
-            for (index, project) in projects_for_calc.iter().enumerate() {
+
+            let total = projects_for_calc.iter().filter(|p| !p.is_ignored).count();
+            for (done, project) in projects_for_calc.iter().filter(|p| !p.is_ignored).enumerate() {
+                task.set_progress(format!("{}/{} {}", done + 1, total, project.name));
+                // 与详情计算共享名额，避免同时计算的项目过多
+                let Some(_permit) = limiter.acquire(&task.token).await else {
+                    break;
+                };
                 // 计算项目大小
-                if let Ok(size_info) = size_calculator.calculate_project_size(&project.path).await {
+                let result = size_calculator.calculate_project_size(&project.path).await;
+                if access::is_removed(&project.path) {
+                    let _ = sender.send(Event::ProjectRemoved(project.path.clone()));
+                    continue;
+                }
+                if let Ok(size_info) = result {
                     // 发送更新事件
                     let _ = sender.send(Event::ProjectSizeUpdated {
-                        project_index: index,
+                        project_path: project.path.clone(),
                         code_size: size_info.code_size,
                         total_size: size_info.total_size,
                         gitignore_excluded_size: size_info.gitignore_excluded_size,
@@ -1079,12 +4079,15 @@ impl App {
                         dependency_file_count: size_info.dependency_file_count,
                         total_file_count: size_info.total_file_count,
                         gitignore_excluded_file_count: size_info.gitignore_excluded_file_count,
+                        size_truncated: size_info.truncated,
+                        last_modified: size_info.last_modified.map(DateTime::<Utc>::from),
                     });
                 }
                 
                 // 防止计算过快导致界面更新频繁
                 tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
             }
+            Ok(format!("已计算 {} 个项目", total))
         });
         
         Ok(())
@@ -1101,6 +4104,7 @@ impl App {
         tracing::info!("开始异步扫描项目，路径: {:?}", paths);
         
         let mut all_projects = Vec::new();
+        let limiter = ConcurrencyLimiter::new(config.scan.concurrent_scans);
         let file_walker = FileWalker::new(config.clone());
         
         for path in paths {
@@ -1112,48 +4116,29 @@ impl App {
                 Ok(detected_projects) => {
                     tracing::info!("FileWalker 返回了 {} 个检测到的项目", detected_projects.len());
                     for detected in detected_projects {
-                        // 立即计算依赖大小（基于已检测的依赖信息）
-                        let immediate_dependency_size: u64 = detected.dependencies.iter().map(|d| d.size).sum();
-                        let dependency_file_count: usize = detected.dependencies.iter().map(|d| d.package_count.unwrap_or(0)).sum();
-                        
-                        // 快速创建项目对象，显示初始依赖大小
-                        let project = Project {
-                            name: detected.name.clone(),
-                            path: detected.path.clone(),
-                            project_type: detected.project_type,
-                            code_size: 0, // 稍后异步计算
-                            total_size: immediate_dependency_size, // 使用立即计算的依赖大小
-                            gitignore_excluded_size: 0, // 稍后异步计算
-                            code_file_count: 0, // 稍后异步计算
-                            dependency_file_count, // 使用立即计算的依赖文件数
-                            total_file_count: dependency_file_count, // 临时使用依赖文件数
-                            gitignore_excluded_file_count: 0, // 稍后异步计算
-                            last_modified: chrono::Utc::now(), // 使用当前时间作为默认值
-                            git_info: None, // 稍后异步分析
-                            dependencies: detected.dependencies,
-                            is_ignored: false,
-                            description: detected.description,
-                            // 总是设为未计算状态，即使有立即计算的依赖大小
-                            // 这样用户能看到"等待计算"状态，然后看到异步计算的进度
-                            dependency_calculation_status: DependencyCalculationStatus::NotCalculated,
-                            cached_dependency_size: Some(immediate_dependency_size), // 使用立即计算的依赖大小作为初始值
-                        };
+                        // 快速创建项目对象，显示检测时得到的依赖大小；代码大小和 Git 信息稍后异步计算
+                        let last_modified = stream::directory_modified(&detected.path);
+                        let mut project = detected.into_project(None, last_modified);
+                        // 总是设为未计算状态，即使有立即计算的依赖大小
+                        // 这样用户能看到"等待计算"状态，然后看到异步计算的进度
+                        project.dependency_calculation_status = DependencyCalculationStatus::NotCalculated;
                         
                         // 立即发送项目，让用户能快速看到项目列表
                         tracing::info!("发送项目到 TUI: {}", project.name);
                         if let Err(e) = progress_sender.send(Event::ProjectFound(project.clone())) {
                             tracing::error!("发送项目失败: {}", e);
                         }
-                        all_projects.push(project);
                         
                         // 在后台异步计算详细信息
-                        let project_path = detected.path.clone();
-                        let project_name = detected.name.clone();
+                        let project_path = project.path.clone();
+                        let project_name = project.name.clone();
                         let sender = progress_sender.clone();
+                        let limiter = limiter.clone();
                         
                         // 标记项目为正在计算状态
                         let sender_for_status = progress_sender.clone();
-                        let project_name_for_status = detected.name.clone();
+                        let project_name_for_status = project.name.clone();
+                        all_projects.push(project);
                         
                         tokio::spawn(async move {
                             // 先发送开始计算事件，更新项目状态
@@ -1161,7 +4146,7 @@ impl App {
                                 project_name: project_name_for_status,
                             });
                             
-                            Self::calculate_project_details(project_path, project_name, sender, CancellationToken::new()).await;
+                            Self::calculate_project_details(project_path, project_name, sender, limiter, CancellationToken::new()).await;
                         });
                     }
                 }
@@ -1176,13 +4161,20 @@ impl App {
         Ok(all_projects)
     }
     
-    /// 在后台异步计算项目的详细信息（大小、Git 信息等）
+    /// 在后台异步计算项目的详细信息（大小、Git 信息等），等待限制器的空闲名额后开始
     async fn calculate_project_details(
         project_path: std::path::PathBuf,
         project_name: String,
         progress_sender: mpsc::UnboundedSender<Event>,
+        limiter: ConcurrencyLimiter,
         cancellation_token: CancellationToken,
     ) {
+        let Some(_permit) = limiter.acquire(&cancellation_token).await else {
+            tracing::debug!("计算 {} 的项目详细信息被取消", project_name);
+            return;
+        };
+        let _running = metrics().detail_tasks.track();
+        
         // 使用select来同时监听取消信号和计算任务
         tokio::select! {
             // 监听取消信号
@@ -1216,7 +4208,7 @@ impl App {
         progress_sender: mpsc::UnboundedSender<Event>,
         cancellation_token: CancellationToken,
     ) {
-        use crate::scanner::{GitAnalyzer, SizeCalculator};
+        use crate::scanner::{CacheStatus, SizeCalculator};
         use crate::config::Config;
         
         // 通知开始计算
@@ -1224,30 +4216,38 @@ impl App {
             format!("开始计算 {} 的详细信息...", project_name)
         ));
         
-        let git_analyzer = GitAnalyzer::new();
-        
         // 加载配置并创建带缓存的大小计算器
         let config = Config::load_or_create_default().unwrap_or_default();
         let mut size_calculator = SizeCalculator::new_with_cache(config.cache.to_size_cache_config())
             .await
-            .unwrap_or_else(|_| SizeCalculator::new());
-        
-        // 通知开始分析Git信息
-        let _ = progress_sender.send(Event::ScanProgress(
-            format!("分析 {} 的Git信息...", project_name)
-        ));
+            .unwrap_or_else(|_| SizeCalculator::new())
+            .with_limits(config.scan.to_size_limits())
+            .with_follow_symlinks(config.scan.follow_symlinks)
+            .with_dependency_dirs(config.detection.dependency_dirs())
+            .with_concurrency(config.scan.concurrent_scans)
+            .with_cancellation(cancellation_token.clone());
+        if let Some(recovery) = size_calculator.cache_recovery() {
+            let _ = progress_sender.send(Event::ScanProgress(recovery.to_string()));
+        }
         
-        // 分析 Git 信息（相对较快）
-        let git_info = match git_analyzer.analyze_repository(&project_path) {
-            Ok(info) => info,
-            Err(e) => {
-                tracing::warn!("分析 {} 的 Git 信息失败: {}", project_name, e);
-                let _ = progress_sender.send(Event::ScanProgress(
-                    format!("分析 {} 的 Git 信息失败，跳过", project_name)
-                ));
-                None
+
+        // 没有有效缓存的项目先给出快速估算值，精确计算完成后再替换
+        if size_calculator.get_cache_status(&project_path) != Some(CacheStatus::Valid) {
+            let estimate_path = project_path.clone();
+            if let Ok(estimated_size) = tokio::task::spawn_blocking(move || SizeCalculator::quick_estimate(&estimate_path)).await {
+                let _ = progress_sender.send(Event::ProjectSizeEstimated {
+                    project_name: project_name.clone(),
+                    estimated_size,
+                });
             }
-        };
+        }
+        
+        // 项目目录已被外部删除时不再计算大小
+        if access::is_removed(&project_path) {
+            tracing::info!("项目 {} 已不存在: {}", project_name, project_path.display());
+            let _ = progress_sender.send(Event::ProjectRemoved(project_path));
+            return;
+        }
         
         // 通知开始计算大小
         let _ = progress_sender.send(Event::ScanProgress(
@@ -1263,7 +4263,7 @@ impl App {
                     processed_files: processed,
                     total_files: total,
                     current_path: path,
-                    bytes_processed: bytes,
+                    bytes_processed: ByteSize::new(bytes),
                     stage,
                 });
             })
@@ -1284,6 +4284,13 @@ impl App {
             ) => result
         };
         
+        // 计算过程中项目目录被删除
+        if access::is_removed(&project_path) {
+            tracing::info!("项目 {} 已不存在: {}", project_name, project_path.display());
+            let _ = progress_sender.send(Event::ProjectRemoved(project_path));
+            return;
+        }
+        
         match calculation_result {
             Ok(size_info) => {
                 // 发送详细信息更新事件
@@ -1297,7 +4304,8 @@ impl App {
                     dependency_file_count: size_info.dependency_file_count,
                     total_file_count: size_info.total_file_count,
                     gitignore_excluded_file_count: size_info.gitignore_excluded_file_count,
-                    git_info,
+                    size_truncated: size_info.truncated,
+                    last_modified: size_info.last_modified.map(DateTime::<Utc>::from),
                 });
                 
                 // 发送完成消息
@@ -1321,15 +4329,16 @@ impl App {
                 // 发送带有默认值的更新事件，确保项目状态更新
                 let _ = progress_sender.send(Event::ProjectDetailsUpdated {
                     project_name: project_name.clone(),
-                    code_size: 0,
-                    dependency_size: 0,
-                    total_size: 0,
-                    gitignore_excluded_size: 0,
+                    code_size: ByteSize::ZERO,
+                    dependency_size: ByteSize::ZERO,
+                    total_size: ByteSize::ZERO,
+                    gitignore_excluded_size: ByteSize::ZERO,
                     code_file_count: 0,
                     dependency_file_count: 0,
                     total_file_count: 0,
                     gitignore_excluded_file_count: 0,
-                    git_info,
+                    size_truncated: false,
+                    last_modified: None,
                 });
             }
         }
@@ -1340,33 +4349,99 @@ impl App {
         self.current_tab = match self.current_tab {
             TabView::Projects => TabView::Statistics,
             TabView::Statistics => TabView::GitStatus,
-            TabView::GitStatus => TabView::Projects,
+            TabView::GitStatus => TabView::ScanRoots,
+            TabView::ScanRoots => TabView::Projects,
+        };
+    }
+    
+    /// 在后台计算选中项目的清理计划，完成后显示确认对话框
+    fn request_cleanup_plan(&mut self) {
+        let Some(project_path) = self.projects.get(self.selected_project).map(|p| p.path.clone()) else {
+            return;
+        };
+        if !self.operation_allowed(&project_path) {
+            return;
+        }
+        let project_name = self.projects[self.selected_project].name.clone();
+        
+        self.status_message = format!("正在计算 {} 的清理计划...", project_name);
+        let operation = CleanupOperation::from_config(&self.config);
+        let sender = self.event_handler.sender.clone();
+        self.spawn_task(TaskKind::CleanupPlan, project_name, |_| async move {
+            let plan = operation.plan(&project_path).await;
+            let message = format!("{} 个目录", plan.entries.len());
+            let _ = sender.send(Event::CleanupPlanReady { project_path, plan });
+            Ok(message)
+        });
+    }
+    
+    /// 显示清理计划并请求确认；计划为空或选中的项目已改变时不显示
+    fn show_cleanup_plan(&mut self, project_path: PathBuf, plan: CleanupPlan) {
+        let Some(project) = self.projects.get(self.selected_project).filter(|p| p.path == project_path) else {
+            return;
         };
+        if self.state != AppState::ProjectList {
+            return;
+        }
+        
+        if plan.is_empty() {
+            self.status_message = format!("项目 {} 没有需要清理的依赖目录", project.name);
+            return;
+        }
+        
+        self.status_message = format!(
+            "清理 {} 将删除 {} 个目录，释放 {} (y/N)",
+            project.name,
+            plan.entries.len(),
+            crate::utils::size_format::format_size(plan.total_size())
+        );
+        self.cleanup_plan = Some((project_path, plan));
+        self.confirm_action = ConfirmAction::CleanProject;
+        self.state = AppState::ConfirmDialog;
     }
     
     /// 清理当前项目
     async fn clean_current_project(&mut self) -> Result<()> {
         if let Some(project) = self.projects.get(self.selected_project) {
-            self.status_message = format!("正在清理项目: {}", project.name);
+            let operation = CleanupOperation::from_config(&self.config)
+                .with_progress(self.deletion_progress_callback());
+            let warning = operation.find_dependency_dirs(&project.path)
+                .iter()
+                .find_map(|dir| operation.regeneration_warning(dir));
+            self.status_message = match warning {
+                Some(warning) => format!("正在清理项目: {}（{}）", project.name, warning),
+                None => format!("正在清理项目: {}", project.name),
+            };
+            let kept: Vec<String> = operation.kept_dirs(&project.path)
+                .iter()
+                .filter_map(|dir| dir.strip_prefix(&project.path).ok())
+                .map(|dir| dir.display().to_string())
+                .collect();
+            if !kept.is_empty() {
+                self.status_message.push_str(&format!("，保留 {}", kept.join(", ")));
+            }
             
             let project_path = project.path.clone();
             let project_name = project.name.clone();
             let sender = self.event_handler.sender.clone();
             
-            tokio::spawn(async move {
-                match Self::clean_project_dependencies(&project_path).await {
+            self.spawn_task(TaskKind::Clean, project_name.clone(), |task| async move {
+                match Self::clean_project_dependencies(&project_path, &operation, &task.token).await {
                     Ok(cleaned_size) => {
-                        let _ = sender.send(Event::ScanProgress(
-                            format!("已清理项目 {} 的依赖，释放了 {} 空间", 
-                                project_name, 
-                                crate::utils::size_format::format_size(cleaned_size)
-                            )
-                        ));
+                        let _ = sender.send(Event::CleanupCompleted {
+                            project_path,
+                            freed_size: cleaned_size,
+                        });
+                        Ok(format!(
+                            "已清理项目 {}，释放了 {} 空间",
+                            project_name,
+                            cleaned_size
+                        ))
                     }
                     Err(e) => {
-                        let _ = sender.send(Event::ScanProgress(
-                            format!("清理项目 {} 失败: {}", project_name, e)
-                        ));
+                        let message = format!("清理项目 {} 失败: {}", project_name, e);
+                        let _ = sender.send(Event::ScanProgress(message.clone()));
+                        Err(message)
                     }
                 }
             });
@@ -1374,19 +4449,32 @@ impl App {
         Ok(())
     }
     
-    /// 切换忽略项目状态
-    async fn toggle_ignore_project(&mut self) -> Result<()> {
-        if let Some(project) = self.projects.get_mut(self.selected_project) {
-            project.is_ignored = !project.is_ignored;
-            let status = if project.is_ignored { "已忽略" } else { "已取消忽略" };
-            self.status_message = format!("项目 {} {}", project.name, status);
-            
-            // TODO: 将忽略状态保存到配置文件
-            let project_path = project.path.clone();
-            let is_ignored = project.is_ignored;
-            self.save_ignore_status(&project_path, is_ignored).await?;
+    /// 切换忽略项目状态并保存到配置文件：忽略的项目不再显示（按 g 可重新显示），也不再计算详细信息
+    fn toggle_ignore_project(&mut self) {
+        let Some(project) = self.projects.get_mut(self.selected_project) else {
+            return;
+        };
+        
+        project.is_ignored = !project.is_ignored;
+        let project_path = project.path.clone();
+        let project_name = project.name.clone();
+        let is_ignored = project.is_ignored;
+        let needs_details = !is_ignored
+            && project.dependency_calculation_status == DependencyCalculationStatus::NotCalculated;
+        
+        self.status_message = if is_ignored {
+            format!("项目 {} 已忽略，重新扫描时跳过（按 g 显示已忽略的项目）", project_name)
+        } else {
+            format!("项目 {} 已取消忽略", project_name)
+        };
+        self.save_ignore_status(&project_path, is_ignored);
+        
+        // 扫描时跳过了已忽略项目的详细信息计算，取消忽略后补上
+        if needs_details {
+            self.spawn_details_calculation(project_path, project_name);
         }
-        Ok(())
+        
+        self.ensure_selection_visible();
     }
     
     /// 删除当前项目
@@ -1398,65 +4486,50 @@ impl App {
             self.status_message = format!("正在删除项目: {}", project_name);
             
             let sender = self.event_handler.sender.clone();
-            tokio::spawn(async move {
-                match Self::delete_project_to_trash(&project_path).await {
+            let usage = self.usage.clone();
+            let event = UsageEvent::new(UsageKind::Delete)
+                .with_freed(project.disk_usage())
+                .with_project_type(project.project_type.as_str());
+            self.spawn_task(TaskKind::Delete, project_name.clone(), |_| async move {
+                let outcome = match Self::delete_project_to_trash(&project_path).await {
                     Ok(_) => {
-                        let _ = sender.send(Event::ScanProgress(
-                            format!("已将项目 {} 移动到回收站", project_name)
-                        ));
+                        usage.record(event);
+                        Ok(format!("已将项目 {} 移动到回收站", project_name))
                     }
-                    Err(e) => {
-                        let _ = sender.send(Event::ScanProgress(
-                            format!("删除项目 {} 失败: {}", project_name, e)
-                        ));
-                    }
-                }
+                    Err(e) => Err(format!("删除项目 {} 失败: {}", project_name, e)),
+                };
+                let message = outcome.clone().unwrap_or_else(|e| e);
+                let _ = sender.send(Event::ScanProgress(message));
+                outcome
             });
             
             // 从列表中移除项目
-            self.projects.remove(self.selected_project);
+            let removed = self.projects.remove(self.selected_project);
+            self.marked_projects.remove(&removed.path);
             if self.selected_project >= self.projects.len() && !self.projects.is_empty() {
                 self.selected_project = self.projects.len() - 1;
             }
+            self.ensure_selection_visible();
         }
         Ok(())
     }
     
-    /// 清理项目依赖
-    async fn clean_project_dependencies(project_path: &std::path::Path) -> Result<u64> {
-        use std::fs;
-        
-        let mut total_cleaned = 0u64;
-        
-        // 清理常见的依赖目录
-        let dependency_dirs = [
-            "node_modules",
-            "target",
-            "build",
-            "dist",
-            "__pycache__",
-            ".venv",
-            "venv",
-        ];
-        
-        for dep_dir in dependency_dirs {
-            let dep_path = project_path.join(dep_dir);
-            if dep_path.exists() && dep_path.is_dir() {
-                match Self::calculate_directory_size(&dep_path).await {
-                    Ok(size) => {
-                        total_cleaned += size;
-                        if let Err(e) = fs::remove_dir_all(&dep_path) {
-                            eprintln!("删除目录 {} 失败: {}", dep_path.display(), e);
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("计算目录 {} 大小失败: {}", dep_path.display(), e);
-                    }
-                }
-            }
+    /// 清理项目依赖（跳过按项目保留的目录），中断时已移走的目录会恢复原位
+    async fn clean_project_dependencies(
+        project_path: &std::path::Path,
+        operation: &CleanupOperation,
+        token: &CancellationToken,
+    ) -> Result<ByteSize> {
+        let mut summary = operation.clean_project(project_path, token).await;
+        if summary.cancelled {
+            anyhow::bail!("清理已中断，依赖目录已恢复");
         }
+        if let (true, Some((path, error))) = (summary.removed.is_empty(), summary.failed.first()) {
+            anyhow::bail!("删除 {} 失败: {}", path.display(), error);
+        }
+        summary.wait_for_deletion().await;
         
-        Ok(total_cleaned)
+        Ok(ByteSize::new(summary.freed_bytes()))
     }
     
     /// 删除项目到回收站
@@ -1467,63 +4540,58 @@ impl App {
     }
     
     /// 保存项目忽略状态到配置
-    async fn save_ignore_status(&self, project_path: &std::path::Path, is_ignored: bool) -> Result<()> {
-        // TODO: 实现配置文件更新逻辑
-        // 这里可以将忽略的项目路径保存到配置文件中
-        println!("保存忽略状态: {} -> {}", project_path.display(), is_ignored);
-        Ok(())
-    }
-    
-    /// 计算目录大小
-    async fn calculate_directory_size(dir: &std::path::Path) -> Result<u64> {
-        use std::fs;
-        use tokio::task;
-        
-        let dir = dir.to_path_buf();
-        task::spawn_blocking(move || {
-            fn calculate_size(path: &std::path::Path) -> Result<u64> {
-                let mut total = 0u64;
-                
-                if path.is_file() {
-                    return Ok(fs::metadata(path)?.len());
-                }
-                
-                if path.is_dir() {
-                    for entry in fs::read_dir(path)? {
-                        let entry = entry?;
-                        total += calculate_size(&entry.path())?;
-                    }
-                }
-                
-                Ok(total)
+    fn save_ignore_status(&mut self, project_path: &std::path::Path, is_ignored: bool) {
+        let path = project_path.display().to_string();
+        let saved = self.update_config(|config| {
+            if is_ignored {
+                config.ignore.projects.insert(path.clone());
+            } else {
+                config.ignore.projects.remove(&path);
             }
-            
-            calculate_size(&dir)
-        }).await?
+        });
+        if let Err(e) = saved {
+            self.status_message = format!("保存忽略状态失败: {}", e);
+        }
     }
     
     /// 暂停终端（为启动外部编辑器做准备）
-    fn suspend_terminal() -> Result<()> {
+    fn suspend_terminal(mouse: bool) -> Result<()> {
         // 离开备用屏幕
-        execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+        execute!(io::stdout(), LeaveAlternateScreen)?;
+        if mouse {
+            execute!(io::stdout(), DisableMouseCapture)?;
+        }
         // 禁用原始模式
         disable_raw_mode()?;
         Ok(())
     }
     
     /// 恢复终端（从外部编辑器返回后）
-    fn restore_terminal() -> Result<()> {
+    fn restore_terminal(mouse: bool) -> Result<()> {
         // 启用原始模式
         enable_raw_mode()?;
         // 进入备用屏幕
-        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        if mouse {
+            execute!(io::stdout(), EnableMouseCapture)?;
+        }
         Ok(())
     }
     
-    /// 使用nvim打开项目目录  
-    async fn spawn_nvim(&mut self, project_path: &std::path::Path) -> Result<bool> {
+    /// 用编辑器打开项目目录，按项目类型从配置中选择第一个已安装的命令
+    async fn open_in_editor(&mut self, project_path: &std::path::Path, type_keys: &[String]) -> Result<bool> {
         use std::process::Command;
         
+        let Some(command) = editor::select_command(&self.config.editor, type_keys, |program| {
+            editor::find_in_path(program).is_some()
+        }) else {
+            self.status_message = "未找到可用的编辑器，请检查配置中的 [editor] 设置".to_string();
+            return Ok(false);
+        };
+        let mut parts = command.split_whitespace();
+        let program = parts.next().unwrap_or_default().to_string();
+        let args: Vec<String> = parts.map(str::to_string).collect();
+        
         // 设置状态为外部编辑器
         self.state = AppState::ExternalEditor;
         
@@ -1531,16 +4599,17 @@ impl App {
         self.event_handler.pause();
         
         // 暂停终端
-        Self::suspend_terminal()?;
+        Self::suspend_terminal(self.config.display.mouse)?;
         
-        // 启动nvim（设置工作目录为项目路径）
-        let status = Command::new("nvim")
+        // 启动编辑器（设置工作目录为项目路径）
+        let status = Command::new(&program)
             .current_dir(project_path)  // 设置工作目录
+            .args(&args)
             .arg(".")                   // 在当前目录打开
             .status();
         
         // 恢复终端
-        Self::restore_terminal()?;
+        Self::restore_terminal(self.config.display.mouse)?;
         
         // 恢复事件处理器
         self.event_handler.resume();
@@ -1553,12 +4622,12 @@ impl App {
                 if exit_status.success() {
                     self.status_message = "已完成编辑".to_string();
                 } else {
-                    self.status_message = "编辑器异常退出".to_string();
+                    self.status_message = format!("{} 异常退出", program);
                 }
             }
             Err(e) => {
-                self.status_message = format!("启动nvim失败: {}. 请确保nvim已安装", e);
-                return Err(anyhow::anyhow!("启动nvim失败: {}", e));
+                self.status_message = format!("启动 {} 失败: {}", program, e);
+                return Err(anyhow::anyhow!("启动 {} 失败: {}", program, e));
             }
         }
         
@@ -1566,89 +4635,972 @@ impl App {
         Ok(true)
     }
     
-    /// 清理已完成的任务
-    fn cleanup_finished_tasks(&mut self) {
-        self.calculation_tasks.retain(|_project_name, handle| {
-            !handle.is_finished()
-        });
+    /// 取消所有后台任务，并给它们一些时间优雅退出
+    async fn cleanup_all_tasks(&mut self) {
+        self.cancellation_token.cancel();
+        self.tasks.shutdown(TASK_SHUTDOWN_TIMEOUT).await;
+    }
+}
+/// 按显示宽度截断文本，超出时以 "..." 结尾（避免在多字节字符中间切分）
+fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
     }
     
-    /// 检查是否可以启动新任务
-    #[allow(dead_code)]
-    fn can_start_new_task(&self) -> bool {
-        self.calculation_tasks.len() < self.max_concurrent_tasks
+    let budget = max_width.saturating_sub(3);
+    let mut width = 0;
+    let mut truncated = String::new();
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if width + char_width > budget {
+            break;
+        }
+        width += char_width;
+        truncated.push(c);
     }
     
-    /// 等待任务槽位可用
-    #[allow(dead_code)]
-    async fn wait_for_task_slot(&mut self) {
-        while !self.can_start_new_task() {
-            self.cleanup_finished_tasks();
-            if !self.can_start_new_task() {
-                tokio::task::yield_now().await;
-                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    format!("{}...", truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ProjectType;
+    use crate::tui::fixtures::{GitState, ProjectFixture};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::backend::TestBackend;
+
+    /// 创建已加载指定项目、处于项目列表状态的应用
+    fn app_with(projects: Vec<Project>) -> App {
+        let mut app = App::new(Config::default(), vec!["/code".to_string()]);
+        app.projects = projects;
+        app.state = AppState::ProjectList;
+        app
+    }
+
+    fn sample_projects() -> Vec<Project> {
+        vec![
+            ProjectFixture::new("/code/work/api").sizes(1000, 5000).git(GitState::Clean).build(),
+            ProjectFixture::new("/code/work/web").sizes(2000, 8000).git(GitState::Dirty).build(),
+            ProjectFixture::new("/code/personal/blog").project_type(ProjectType::Python).sizes(300, 0).build(),
+        ]
+    }
+
+    async fn press(app: &mut App, code: KeyCode) {
+        app.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE)).await.unwrap();
+    }
+
+    /// 使用 TestBackend 渲染一帧，返回按行拼接的屏幕文本（宽字符占用的后续单元格会被跳过）
+    fn render(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let mut lines = Vec::new();
+        for y in 0..buffer.area.height {
+            let mut line = String::new();
+            let mut x = 0;
+            while x < buffer.area.width {
+                let symbol = buffer[(x, y)].symbol();
+                line.push_str(symbol);
+                x += symbol.width().max(1) as u16;
             }
+            lines.push(line);
         }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("扫描完成！发现 3 个项目", 10), "扫描完...");
+        assert_eq!(truncate_to_width("abcdef", 2), "...");
+    }
+
+    #[tokio::test]
+    async fn test_navigation_and_directory_filter() {
+        let mut app = app_with(sample_projects());
+
+        press(&mut app, KeyCode::Char('j')).await;
+        assert_eq!(app.selected_project, 1);
+
+        // 按选中项目的父目录过滤，只剩 work 下的两个项目
+        press(&mut app, KeyCode::Char('f')).await;
+        assert_eq!(app.visible_indices(), vec![0, 1]);
+
+        // 选择不会越过过滤后的末尾
+        press(&mut app, KeyCode::Char('j')).await;
+        press(&mut app, KeyCode::Char('j')).await;
+        assert_eq!(app.selected_project, 1);
+
+        let screen = render(&mut app);
+        assert!(screen.contains("api"));
+        assert!(!screen.contains("blog"));
+        // 底部合计行只汇总过滤后的项目
+        assert!(screen.contains("合计 2 个项目"));
+        assert!(screen.contains("可释放约 12.7 KB"));
+
+        press(&mut app, KeyCode::Char('f')).await;
+        assert_eq!(app.visible_indices().len(), 3);
+        assert!(render(&mut app).contains("合计 3 个项目"));
+    }
+
+    #[test]
+    fn test_refresh_removes_vanished_projects() {
+        let mut app = app_with(sample_projects());
+        app.selected_project = 2;
+        app.marked_projects.insert(PathBuf::from("/code/work/api"));
+        
+        // 移除选中项目之前的项目，选中的仍是同一个项目
+        app.remove_project(Path::new("/code/work/api"));
+        assert_eq!(app.projects.len(), 2);
+        assert_eq!(app.projects[app.selected_project].name, "blog");
+        assert!(app.marked_projects.is_empty());
+        
+        // 正在查看的项目消失时退回列表
+        app.state = AppState::ProjectDetail;
+        app.remove_project(Path::new("/code/personal/blog"));
+        assert_eq!(app.state, AppState::ProjectList);
+        assert_eq!(app.projects[app.selected_project].name, "web");
     }
     
-    /// 启动项目计算任务（带并发控制）
-    #[allow(dead_code)]
-    async fn start_project_calculation_task(
-        &mut self, 
-        project_path: std::path::PathBuf,
-        project_name: String,
-        sender: mpsc::UnboundedSender<Event>
-    ) {
-        // 清理已完成的任务
-        self.cleanup_finished_tasks();
+    #[test]
+    fn test_detail_shows_package_stats() {
+        let mut project = ProjectFixture::new("/code/personal/blog").dependency("node_modules", 4096).build();
+        project.dependencies[0].dependency_type = DependencyType::NodeModules;
+        project.dependencies[0].packages = Some(PackageStats {
+            direct_count: 12,
+            transitive_count: 340,
+            largest: vec![crate::models::PackageSize { name: "typescript".to_string(), size: ByteSize::new(2048) }],
+        });
+        let mut app = app_with(vec![project]);
+        app.state = AppState::ProjectDetail;
+        
+        let screen = render(&mut app);
+        assert!(screen.contains("依赖包 (node_modules)"));
+        assert!(screen.contains("直接依赖: 12  间接依赖: 340"));
+        assert!(screen.contains("2.0 KB  typescript"));
+    }
+    
+    #[tokio::test]
+    async fn test_watch_event_removes_deleted_project() {
+        let mut app = app_with(sample_projects());
+        app.handle_watch_event(WatchEvent::ProjectDeleted(PathBuf::from("/code/work/web"))).await.unwrap();
         
-        // 等待任务槽位可用
-        self.wait_for_task_slot().await;
+        assert_eq!(app.projects.len(), 2);
+        assert_eq!(app.status_message, "项目 web 已被删除");
+        assert!(app.watcher_token.is_none());
+    }
+    
+    #[tokio::test]
+    async fn test_marked_projects_scope_statistics() {
+        let mut app = app_with(sample_projects());
+
+        press(&mut app, KeyCode::Char('m')).await;
+        press(&mut app, KeyCode::Tab).await;
+        assert_eq!(app.current_tab, TabView::Statistics);
+
+        let screen = render(&mut app);
+        assert!(screen.contains("1 个项目"));
+    }
+
+    #[tokio::test]
+    async fn test_reject_cleanup_suggestion_in_detail_view() {
+        let mut projects = sample_projects();
+        projects[0] = ProjectFixture::new("/code/work/api")
+            .git(GitState::Clean)
+            .cleanup_suggestions(&[("coverage", 4096), ("tmp", 1024)])
+            .build();
+        let mut app = app_with(projects);
+
+        press(&mut app, KeyCode::Enter).await;
+        assert_eq!(app.state, AppState::ProjectDetail);
+        assert!(render(&mut app).contains("coverage"));
+
+        press(&mut app, KeyCode::Char('n')).await;
+        let pending = app.projects[0].pending_cleanup_suggestions();
+        assert_eq!(pending.len(), 1);
+        assert!(pending[0].path.ends_with("tmp"));
+
+        press(&mut app, KeyCode::Backspace).await;
+        assert_eq!(app.state, AppState::ProjectList);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_suggestions_scroll_with_selection() {
+        let names: Vec<String> = (0..12).map(|i| format!("dir{:02}", i)).collect();
+        let dirs: Vec<(&str, u64)> = names.iter().enumerate()
+            .map(|(i, name)| (name.as_str(), (12 - i as u64) * 1024))
+            .collect();
+        let mut projects = sample_projects();
+        projects[0] = ProjectFixture::new("/code/work/api")
+            .git(GitState::Clean)
+            .cleanup_suggestions(&dirs)
+            .build();
+        let mut app = app_with(projects);
+
+        press(&mut app, KeyCode::Enter).await;
+        let screen = render(&mut app);
+        assert!(screen.contains("清理建议 (.gitignore) 1/12"));
+        assert!(!screen.contains("dir11"));
+
+        // 选中最后一条建议时面板滚动到底部
+        for _ in 0..11 {
+            press(&mut app, KeyCode::Down).await;
+        }
+        let screen = render(&mut app);
+        assert!(screen.contains("清理建议 (.gitignore) 12/12"));
+        assert!(screen.contains("dir11"));
+        assert!(!screen.contains("dir00"));
+    }
+
+    #[tokio::test]
+    async fn test_accept_cleanup_suggestion_requires_confirmation() {
+        let mut projects = sample_projects();
+        projects[0] = ProjectFixture::new("/code/work/api")
+            .git(GitState::Clean)
+            .cleanup_suggestions(&[("coverage", 4096)])
+            .build();
+        let mut app = app_with(projects);
+
+        press(&mut app, KeyCode::Enter).await;
+        press(&mut app, KeyCode::Char('y')).await;
+        assert_eq!(app.state, AppState::ConfirmDialog);
+        assert_eq!(app.confirm_action, ConfirmAction::AcceptCleanupSuggestion(PathBuf::from("/code/work/api/coverage")));
+        let screen = render(&mut app);
+        assert!(screen.contains("确认删除 /code/work/api/coverage？预计释放 4.0 KB"));
+
+        // 取消后回到详情页，不启动删除
+        press(&mut app, KeyCode::Char('n')).await;
+        assert_eq!(app.state, AppState::ProjectDetail);
+        assert!(app.tasks.display_order().is_empty());
+        assert_eq!(app.projects[0].pending_cleanup_suggestions().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_unsaved_work_requires_uppercase_confirmation() {
+        let mut app = app_with(sample_projects());
+        press(&mut app, KeyCode::Char('j')).await;
+
+        press(&mut app, KeyCode::Char('d')).await;
+        assert_eq!(app.state, AppState::ConfirmDialog);
+        assert!(render(&mut app).contains("有未提交的更改"));
+
+        // 小写 y 不会删除有未提交更改的项目
+        press(&mut app, KeyCode::Char('y')).await;
+        assert_eq!(app.state, AppState::ConfirmDialog);
+        assert_eq!(app.projects.len(), 3);
+
+        press(&mut app, KeyCode::Char('n')).await;
+        assert_eq!(app.state, AppState::ProjectList);
+        assert_eq!(app.projects.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_actions_ignored_when_filter_hides_every_project() {
+        let mut app = app_with(sample_projects());
+        app.filter.query = "nothing-matches".to_string();
+        app.ensure_selection_visible();
+        assert!(app.visible_indices().is_empty());
         
-        // 保存项目名用于任务管理
-        let task_key = project_name.clone();
+        press(&mut app, KeyCode::Char('d')).await;
+        press(&mut app, KeyCode::Char('y')).await;
+        assert_eq!(app.state, AppState::ProjectList);
+        assert_eq!(app.projects.len(), 3);
         
-        // 启动新任务
-        let handle = tokio::spawn(async move {
-            // 先发送开始计算事件
-            let _ = sender.send(Event::ProjectCalculationStarted {
-                project_name: project_name.clone(),
-            });
-            
-            Self::calculate_project_details(project_path, project_name, sender, CancellationToken::new()).await;
-        });
+        press(&mut app, KeyCode::Char('m')).await;
+        press(&mut app, KeyCode::Enter).await;
+        assert!(app.marked_projects.is_empty());
+        assert_eq!(app.state, AppState::ProjectList);
+        assert_eq!(app.status_message, "列表中没有可操作的项目");
+    }
+    
+    #[tokio::test]
+    async fn test_delete_confirmation_names_the_project() {
+        let mut app = app_with(sample_projects());
         
-        self.calculation_tasks.insert(task_key, handle);
+        press(&mut app, KeyCode::Char('d')).await;
+        assert_eq!(app.state, AppState::ConfirmDialog);
+        assert_eq!(app.status_message, "确认删除项目 api（/code/work/api）？ (y/N)");
+        let screen = render(&mut app);
+        assert!(screen.contains("确认删除项目 api？"));
+        assert!(screen.contains("/code/work/api"));
     }
     
-    /// 清理所有运行中的任务
-    async fn cleanup_all_tasks(&mut self) {
-        if self.calculation_tasks.is_empty() {
-            return;
+    #[tokio::test]
+    async fn test_ignore_list_refilters_projects() {
+        let mut app = app_with(sample_projects());
+
+        press(&mut app, KeyCode::Char('I')).await;
+        assert_eq!(app.state, AppState::IgnoreList);
+        let screen = render(&mut app);
+        assert!(screen.contains("忽略路径 (0)"));
+        assert!(screen.contains(".git"));
+
+        // 在忽略路径分组中新增规则
+        press(&mut app, KeyCode::Tab).await;
+        press(&mut app, KeyCode::Char('a')).await;
+        for c in "personal".chars() {
+            press(&mut app, KeyCode::Char(c)).await;
         }
+        assert!(render(&mut app).contains("> personal"));
+        // 直接提交而不写入配置文件
+        let change = app.ignore_editor.commit_input(&mut app.config.ignore).unwrap();
+        assert_eq!(change.added.as_deref(), Some("personal"));
+        app.apply_ignore_rules();
+
+        press(&mut app, KeyCode::Esc).await;
+        let screen = render(&mut app);
+        assert!(screen.contains("api"));
+        assert!(!screen.contains("blog"));
+
+        // 移除规则后项目重新出现
+        app.config.ignore.paths.clear();
+        app.apply_ignore_rules();
+        assert!(render(&mut app).contains("blog"));
+    }
+
+    #[tokio::test]
+    async fn test_ignoring_project_saves_to_loaded_config_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("custom.toml");
+        let mut app = app_with(sample_projects()).with_config_path(config_path.clone());
         
-        tracing::debug!("开始清理 {} 个计算任务", self.calculation_tasks.len());
+        press(&mut app, KeyCode::Char('i')).await;
         
-        // 首先发送取消信号给所有任务
-        self.cancellation_token.cancel();
+        let saved = Config::load_from_file(&config_path).unwrap();
+        assert!(saved.ignore.projects.contains("/code/work/api"));
+    }
+    
+    #[tokio::test]
+    async fn test_operating_on_ignored_project_asks_to_unignore() {
+        let mut app = app_with(sample_projects());
+        app.config.ignore.projects.insert("/code/work/api".to_string());
+        app.apply_ignored_flags();
+        app.filter.show_ignored = true;
+        
+        press(&mut app, KeyCode::Enter).await;
+        assert!(render(&mut app).contains("已忽略（手动忽略）"));
+        press(&mut app, KeyCode::Backspace).await;
+        
+        press(&mut app, KeyCode::Char('d')).await;
+        assert_eq!(app.state, AppState::ConfirmDialog);
+        assert_eq!(app.confirm_action, ConfirmAction::UnignoreProject(Box::new(ConfirmAction::DeleteProject)));
+        assert!(render(&mut app).contains("取消忽略并继续删除"));
+        
+        press(&mut app, KeyCode::Char('n')).await;
+        assert_eq!(app.state, AppState::ProjectList);
+        assert!(app.projects[0].is_ignored);
+        
+        // 被忽略目录名排除的项目提示到忽略列表中修改
+        app.config.ignore.projects.clear();
+        app.config.ignore.directories.insert("work".to_string());
+        app.apply_ignored_flags();
+        press(&mut app, KeyCode::Char('c')).await;
+        assert_eq!(app.state, AppState::ProjectList);
+        assert!(app.status_message.contains("忽略目录名 work"));
+    }
+    
+    #[tokio::test]
+    async fn test_clean_shows_plan_before_confirmation() {
+        use crate::operations::cleanup::{CleanupDirKind, CleanupPlanEntry};
+
+        let mut app = app_with(sample_projects());
+        let project_path = app.projects[0].path.clone();
+        let plan = CleanupPlan {
+            entries: vec![CleanupPlanEntry {
+                path: project_path.join("node_modules"),
+                size: 3 * 1024 * 1024,
+                kind: CleanupDirKind::Strategy(ProjectType::NodeJs),
+                warning: None,
+            }],
+        };
+
+        app.show_cleanup_plan(project_path, plan);
+        assert_eq!(app.state, AppState::ConfirmDialog);
+        let screen = render(&mut app);
+        assert!(screen.contains("预计释放 3.0 MB"));
+        assert!(screen.contains("node_modules  [nodejs]"));
+
+        press(&mut app, KeyCode::Char('n')).await;
+        assert_eq!(app.state, AppState::ProjectList);
+        assert!(app.cleanup_plan.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_wizard_walks_candidates() {
+        let mut app = app_with(sample_projects());
+
+        press(&mut app, KeyCode::Char('w')).await;
+        assert_eq!(app.state, AppState::CleanupWizard);
+        let screen = render(&mut app);
+        assert!(screen.contains("清理向导 (1/2)"));
+        assert!(screen.contains("api"));
+
+        press(&mut app, KeyCode::Char('s')).await;
+        assert!(render(&mut app).contains("清理向导 (2/2)"));
+
+        // 小写 d 不会删除有未提交更改的项目
+        press(&mut app, KeyCode::Char('d')).await;
+        assert_eq!(app.cleanup_wizard.as_ref().unwrap().progress(), (2, 2));
+        assert_eq!(app.projects.len(), 3);
+
+        // 删除完成后累计释放空间并从列表中移除
+        let web = PathBuf::from("/code/work/web");
+        app.cleanup_wizard.as_mut().unwrap().advance(WizardAction::Delete);
+        app.apply_cleanup_wizard_result(web.clone(), WizardAction::Delete, Ok(ByteSize::new(10000)));
+        assert!(app.projects.iter().all(|p| p.path != web));
+
+        press(&mut app, KeyCode::Esc).await;
+        assert_eq!(app.state, AppState::ProjectList);
+        assert_eq!(app.status_message, "清理向导结束: 已释放 9.8 KB（清理 0 · 归档 0 · 删除 1 · 跳过 1）");
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_wizard_archive_conflict_dialog() {
+        let archive_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(archive_dir.path().join("api")).unwrap();
+        let mut app = app_with(sample_projects());
+        app.config.archive.dir = archive_dir.path().to_path_buf();
+
+        press(&mut app, KeyCode::Char('w')).await;
+        press(&mut app, KeyCode::Char('a')).await;
+        assert_eq!(app.archive_conflict, Some(archive_dir.path().join("api")));
+        let screen = render(&mut app);
+        assert!(screen.contains("归档目录中已有同名项目"));
+        assert!(screen.contains("r 重命名为 api-2"));
+
+        // Esc 返回向导，不前进
+        press(&mut app, KeyCode::Esc).await;
+        assert!(app.archive_conflict.is_none());
+        assert_eq!(app.state, AppState::CleanupWizard);
+        assert_eq!(app.cleanup_wizard.as_ref().unwrap().progress(), (1, 2));
+
+        // 选择跳过后前进到下一个项目
+        press(&mut app, KeyCode::Char('a')).await;
+        press(&mut app, KeyCode::Char('s')).await;
+        assert!(app.archive_conflict.is_none());
+        assert_eq!(app.cleanup_wizard.as_ref().unwrap().skipped, 1);
+        assert_eq!(app.cleanup_wizard.as_ref().unwrap().progress(), (2, 2));
+    }
+
+    #[test]
+    fn test_cleanup_completed_updates_sizes_and_shows_badge() {
+        let mut app = app_with(sample_projects());
+        let api = PathBuf::from("/code/work/api");
+
+        app.apply_cleanup_completed(&api, ByteSize::new(4096));
+        let project = app.projects.iter().find(|p| p.path == api).unwrap();
+        assert_eq!(project.dependency_size(), ByteSize::new(904));
+        assert_eq!(project.total_size, ByteSize::new(1904));
+        assert!(render(&mut app).contains("904 B -4.0 KB"));
+
+        // 徽标过期后不再显示
+        app.size_badges.get_mut(&api).unwrap().1 -= SIZE_BADGE_DURATION;
+        assert!(!render(&mut app).contains("-4.0 KB"));
+    }
+
+    #[test]
+    fn test_deletion_progress_shown_in_status_bar() {
+        let mut app = app_with(sample_projects());
+        let target = PathBuf::from("/code/work/api/target");
+        let progress = |deleted_files, deleted_bytes| DeleteProgress {
+            deleted_files,
+            total_files: 1000,
+            deleted_bytes,
+            total_bytes: 4096,
+        };
+
+        app.update_deletion_progress(target.clone(), progress(256, 1024));
+        assert_eq!(app.progress_info.format_display(), "删除 target: 256/1000 文件 (1.0 KB/4.0 KB)");
+
+        // 删除完成后恢复空闲
+        app.update_deletion_progress(target, progress(1000, 4096));
+        assert!(app.deletion_progress.is_empty());
+        assert_eq!(app.progress_info.progress_type, ProgressType::Idle);
+    }
+
+    /// 配置文件内容为 `on_disk`、内存中的配置为 `config` 时隐藏一个项目，返回应用和重新读取的配置文件
+    async fn hide_project_and_reload(on_disk: &Config, config: Config) -> (App, Config) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        on_disk.save_to_file(&config_path).unwrap();
+        
+        let mut app = App::new(config, vec!["/code".to_string()]).with_config_path(config_path.clone());
+        app.projects = sample_projects();
+        app.state = AppState::ProjectList;
+        press(&mut app, KeyCode::Char('x')).await;
+        
+        let saved = Config::load_from_file(&config_path).unwrap();
+        (app, saved)
+    }
+    
+    #[tokio::test]
+    async fn test_cli_overrides_are_not_saved_to_config_file() {
+        let mut on_disk = Config::default();
+        on_disk.cleanup.restrict_to_scan_paths = true;
+        let mut config = on_disk.clone();
+        // --allow-outside-roots
+        config.cleanup.restrict_to_scan_paths = false;
+        // --no-mouse
+        config.display.mouse = false;
+        // --min-size / --skip-trivial
+        config.scan.min_project_size = 1024 * 1024;
+        config.scan.trivial_projects = crate::config::settings::TrivialProjectMode::Skip;
+        // --notify
+        config.notify.webhook_url = Some("https://example.com/hook".to_string());
+        
+        let (app, saved) = hide_project_and_reload(&on_disk, config).await;
+        assert!(!app.config.cleanup.restrict_to_scan_paths);
+        assert!(saved.cleanup.restrict_to_scan_paths);
+        assert!(!app.config.display.mouse);
+        assert!(saved.display.mouse);
+        assert_eq!(saved.scan.min_project_size, 0);
+        assert_eq!(saved.scan.trivial_projects, crate::config::settings::TrivialProjectMode::Dim);
+        assert_eq!(saved.notify.webhook_url, None);
+        assert!(saved.display.hidden_projects.contains("/code/work/api"));
+    }
+    
+    #[tokio::test]
+    async fn test_hiding_project_saves_to_loaded_config_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("custom.toml");
+        let mut app = app_with(sample_projects()).with_config_path(config_path.clone());
+        
+        press(&mut app, KeyCode::Char('x')).await;
+        
+        let saved = Config::load_from_file(&config_path).unwrap();
+        assert!(saved.display.hidden_projects.contains("/code/work/api"));
+    }
+    
+    #[tokio::test]
+    async fn test_snapshot_browse_is_read_only() {
+        let snapshot = SessionSnapshot::new(&sample_projects()).with_scan_paths(vec!["/code".to_string()]);
+        let mut app = App::from_snapshot(Config::default(), PathBuf::from("disk-2024.json"), snapshot);
+        assert_eq!(app.state, AppState::ProjectList);
+        assert!(render(&mut app).contains("blog"));
+
+        press(&mut app, KeyCode::Char('c')).await;
+        assert_eq!(app.status_message, SNAPSHOT_READ_ONLY);
+        assert_eq!(app.tasks.active().count(), 0);
+
+        // 详情页只显示快照中的信息，不在后台读取项目目录
+        press(&mut app, KeyCode::Enter).await;
+        assert_eq!(app.state, AppState::ProjectDetail);
+        assert!(app.projects[app.selected_project].cleanup_suggestions.is_none());
+    }
+
+    #[test]
+    fn test_git_status_tab_summarizes_unpushed_work() {
+        let mut app = app_with(sample_projects());
+        app.current_tab = TabView::GitStatus;
+
+        let screen = render(&mut app);
+        assert!(screen.contains("未保存的工作 (1 个仓库)"));
+        assert!(screen.contains("⚠ web (/code/work/web) [main]: 有未提交的更改"));
+    }
+
+    #[tokio::test]
+    async fn test_git_status_tab_fetches_repos() {
+        let mut app = app_with(sample_projects());
+        app.current_tab = TabView::GitStatus;
+        let api = PathBuf::from("/code/work/api");
+        let web = PathBuf::from("/code/work/web");
+        app.selected_project = app.projects.iter().position(|p| p.path == api).unwrap();
+
+        // f 只 fetch 选中的仓库，F 再补上其余仓库（跳过正在 fetch 的仓库和非 Git 项目）
+        press(&mut app, KeyCode::Char('f')).await;
+        assert_eq!(app.git_fetch.keys().collect::<Vec<_>>(), [&api]);
+        press(&mut app, KeyCode::Char('F')).await;
+        assert_eq!(app.status_message, "正在 fetch 1 个仓库...");
+        assert_eq!(app.git_fetch.get(&web), Some(&FetchStatus::Queued));
+        assert_eq!(app.git_fetch.len(), 2);
+        assert_eq!(app.tasks.active().filter(|task| task.kind == TaskKind::Fetch).count(), 2);
+
+        app.update_git_fetch(api.clone(), FetchStatus::Fetched(Some(git_fetch::AheadBehind { ahead: 0, behind: 2 })));
+        assert_eq!(app.status_message, "api: ↓2（还有 1 个仓库等待 fetch）");
+        assert!(app.git_hydration.is_waiting(&api));
+        assert!(render(&mut app).contains("[↓2]"));
+
+        // 重新分析仓库后显示仓库自身的领先/落后数，不再重复显示 fetch 结果
+        let api_git = app.projects.iter_mut().find(|p| p.path == api).and_then(|p| p.git_info.as_mut()).unwrap();
+        api_git.ahead_behind = Some(git_fetch::AheadBehind { ahead: 3, behind: 1 });
+        let screen = render(&mut app);
+        assert!(screen.contains("↑3 ↓1"));
+        assert!(!screen.contains("[↓2]"));
+
+        app.update_git_fetch(web.clone(), FetchStatus::Failed("could not read from remote".to_string()));
+        assert_eq!(app.status_message, "fetch 完成：1 个仓库落后于 origin，1 个失败");
+        assert!(render(&mut app).contains("[fetch 失败: could not read from remote]"));
+    }
+
+    #[tokio::test]
+    async fn test_project_list_search() {
+        let mut app = app_with(sample_projects());
+
+        // 输入时 e 等快捷键作为普通字符
+        press(&mut app, KeyCode::Char('/')).await;
+        for c in "web".chars() {
+            press(&mut app, KeyCode::Char(c)).await;
+        }
+        assert!(app.searching);
+        assert_eq!(app.filter.query, "web");
+        assert_eq!(app.visible_indices(), vec![1]);
+        assert_eq!(app.selected_project, 1);
+        assert!(render(&mut app).contains("/web"));
+
+        press(&mut app, KeyCode::Backspace).await;
+        press(&mut app, KeyCode::Backspace).await;
+        assert_eq!(app.visible_indices(), vec![0, 1]);
+
+        // Enter 保留过滤结果，Esc 清除
+        press(&mut app, KeyCode::Enter).await;
+        assert!(!app.searching);
+        assert_eq!(app.visible_indices(), vec![0, 1]);
+        press(&mut app, KeyCode::Esc).await;
+        assert!(app.filter.query.is_empty());
+        assert_eq!(app.visible_indices().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_export_visible_projects() {
+        let mut app = app_with(sample_projects());
+        app.filter.query = "code/work".to_string();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("projects.csv");
+
+        // 默认导出为 JSON，Tab 切换格式时替换扩展名
+        press(&mut app, KeyCode::Char('X')).await;
+        assert!(app.export_prompt.as_ref().unwrap().path.ends_with(".json"));
+        press(&mut app, KeyCode::Tab).await;
+        assert!(app.export_prompt.as_ref().unwrap().path.ends_with(".csv"));
+        assert!(render(&mut app).contains("导出 2 个项目为 CSV"));
+
+        app.export_prompt.as_mut().unwrap().path.clear();
+        for c in path.to_string_lossy().chars() {
+            press(&mut app, KeyCode::Char(c)).await;
+        }
+        press(&mut app, KeyCode::Enter).await;
+
+        assert!(app.export_prompt.is_none());
+        assert!(app.status_message.starts_with("已导出 2 个项目"));
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("api,/code/work/api,"));
+        assert!(lines[2].starts_with("web,/code/work/web,"));
+    }
+
+    #[tokio::test]
+    async fn test_debug_overlay_toggle() {
+        let mut app = app_with(sample_projects());
+
+        press(&mut app, KeyCode::F(12)).await;
+        let screen = render(&mut app);
+        assert!(screen.contains("调试指标"));
+        assert!(screen.contains("事件队列深度"));
+
+        press(&mut app, KeyCode::F(12)).await;
+        assert!(!render(&mut app).contains("调试指标"));
+    }
+
+    #[tokio::test]
+    async fn test_help_screen_search() {
+        let mut app = app_with(sample_projects());
+
+        press(&mut app, KeyCode::Char('h')).await;
+        assert_eq!(app.state, AppState::Help);
+        let screen = render(&mut app);
+        assert!(screen.contains("项目列表:"));
+        assert!(screen.contains("刷新项目列表"));
+
+        press(&mut app, KeyCode::Char('/')).await;
+        for c in "向导".chars() {
+            press(&mut app, KeyCode::Char(c)).await;
+        }
+        press(&mut app, KeyCode::Enter).await;
+        let screen = render(&mut app);
+        assert!(screen.contains("搜索: 向导"));
+        assert!(screen.contains("清理向导:"));
+        assert!(!screen.contains("刷新项目列表"));
+
+        // 第一次 Esc 清除搜索，第二次返回项目列表
+        press(&mut app, KeyCode::Esc).await;
+        assert!(render(&mut app).contains("刷新项目列表"));
+        press(&mut app, KeyCode::Esc).await;
+        assert_eq!(app.state, AppState::ProjectList);
+    }
+
+    #[tokio::test]
+    async fn test_quit_waits_for_pending_operations() {
+        let mut app = app_with(sample_projects());
+        let (done, finished) = tokio::sync::oneshot::channel::<()>();
+        app.spawn_task(TaskKind::Clean, "api", |_| async move {
+            let _ = finished.await;
+            Ok("已清理项目 api，释放了 4.0 KB 空间".to_string())
+        });
+
+        assert!(!app.request_quit());
+        assert_eq!(app.state, AppState::ConfirmQuit);
+        let screen = render(&mut app);
+        assert!(screen.contains("有 1 个操作正在进行"));
+        assert!(screen.contains("清理 api"));
+
+        press(&mut app, KeyCode::Char('w')).await;
+        assert_eq!(app.state, AppState::WaitingForOperations);
+        app.finish_waiting_if_done();
+        assert_eq!(app.state, AppState::WaitingForOperations);
+
+        done.send(()).unwrap();
+        while app.tasks.active().next().is_some() {
+            tokio::task::yield_now().await;
+        }
+        app.finish_waiting_if_done();
+        assert_eq!(app.state, AppState::Quitting);
+        assert_eq!(app.exit_report, ["✅ 已清理项目 api，释放了 4.0 KB 空间"]);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_signal_cancels_pending_operations() {
+        let mut app = app_with(sample_projects());
+        app.spawn_task(TaskKind::Clean, "api", |task| async move {
+            task.token.cancelled().await;
+            Ok(String::new())
+        });
+
+        app.handle_shutdown_signal();
+        assert_eq!(app.state, AppState::Quitting);
+        assert!(app.cancellation_token.is_cancelled());
+        assert_eq!(app.exit_report, ["⚠️  已取消: 清理 api（可能已部分完成）"]);
+    }
+
+    #[tokio::test]
+    async fn test_task_list_cancels_selected_task() {
+        let mut app = app_with(sample_projects());
+        let size = app.spawn_task(TaskKind::Size, "api", |task| async move {
+            task.set_progress("1/3 api");
+            std::future::pending::<()>().await;
+            Ok(String::new())
+        });
         
-        let tasks: Vec<_> = self.calculation_tasks.drain().collect();
-        let mut handles = Vec::new();
+        press(&mut app, KeyCode::Char('o')).await;
+        assert_eq!(app.state, AppState::TaskList);
+        let screen = render(&mut app);
+        assert!(screen.contains("后台任务（1 个进行中）"));
+        assert!(screen.contains("计算大小 api"));
         
-        for (_project_name, handle) in tasks {
-            handles.push(handle);
+        press(&mut app, KeyCode::Char('c')).await;
+        assert_eq!(app.status_message, "已取消: 计算大小 api");
+        while app.tasks.active().next().is_some() {
+            tokio::task::yield_now().await;
         }
+        app.tasks.reap();
+        assert_eq!(app.tasks.get(size).unwrap().status, TaskStatus::Cancelled);
+        assert!(render(&mut app).contains("已取消"));
         
-        if !handles.is_empty() {
-            // 给任务一些时间优雅退出
-            let timeout = tokio::time::Duration::from_secs(3);
-            match tokio::time::timeout(timeout, futures::future::join_all(handles)).await {
-                Ok(_) => tracing::debug!("所有计算任务已优雅完成"),
-                Err(_) => {
-                    tracing::debug!("等待任务完成超时，任务将被丢弃");
-                    // 超时的任务会自动被丢弃
-                }
-            }
+        press(&mut app, KeyCode::Esc).await;
+        assert_eq!(app.state, AppState::ProjectList);
+    }
+    
+    #[tokio::test]
+    async fn test_esc_cancels_selected_project_calculation() {
+        let mut app = app_with(sample_projects());
+        // 名额被占满，计算任务停在等待名额处
+        app.size_limiter = ConcurrencyLimiter::new(1);
+        let _held = app.size_limiter.acquire(&CancellationToken::new()).await;
+        let (path, name) = (app.projects[app.selected_project].path.clone(), app.projects[app.selected_project].name.clone());
+        app.spawn_details_calculation(path, name.clone());
+        
+        press(&mut app, KeyCode::Esc).await;
+        assert_eq!(app.status_message, format!("已取消计算 {} 的大小", name));
+        assert_eq!(app.projects[app.selected_project].dependency_calculation_status, DependencyCalculationStatus::Cancelled);
+        while app.tasks.active().next().is_some() {
+            tokio::task::yield_now().await;
+        }
+        app.tasks.reap();
+        assert!(app.tasks.display_order().iter().all(|task| task.status == TaskStatus::Cancelled));
+        
+        press(&mut app, KeyCode::Esc).await;
+        assert_eq!(app.status_message, format!("{} 没有正在进行的大小计算", name));
+    }
+    
+    #[tokio::test]
+    async fn test_quit_without_pending_operations() {
+        let mut app = app_with(sample_projects());
+        app.spawn_task(TaskKind::Delete, "web", |_| async { Ok(String::new()) });
+        while app.tasks.active().next().is_some() {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(app.request_quit());
+        assert_eq!(app.state, AppState::Quitting);
+    }    
+    #[tokio::test]
+    async fn test_discover_projects_follows_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        for project in ["work/api", "work/legacy/old", ".hidden/tool", "work/manual", "a/b/c/d/deep"] {
+            std::fs::create_dir_all(root.join(project)).unwrap();
+            std::fs::write(root.join(project).join("package.json"), "{}").unwrap();
+        }
+        
+        let mut config = Config::default();
+        config.ignore.directories.insert("legacy".to_string());
+        config.ignore.projects.insert(root.join("work/manual").display().to_string());
+        config.scan.max_depth = Some(3);
+        
+        let scan_paths = [root.display().to_string()];
+        let (projects, skipped) = App::discover_projects(&scan_paths, &config).await.unwrap();
+        let mut names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        names.sort();
+        
+        // 忽略目录、隐藏目录和超出深度的项目不列出，手动忽略的项目仍然列出
+        assert_eq!(names, ["api", "manual"]);
+        assert!(skipped.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_workspace_members_listed_as_collapsible_tree() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("mono");
+        for krate in ["crates/core", "crates/cli"] {
+            std::fs::create_dir_all(root.join(krate)).unwrap();
+            std::fs::write(root.join(krate).join("Cargo.toml"), "[package]\nname = \"member\"\n").unwrap();
         }
+        std::fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n").unwrap();
+        
+        let scan_paths = [temp_dir.path().display().to_string()];
+        let (projects, _) = App::discover_projects(&scan_paths, &Config::default()).await.unwrap();
+        let members: Vec<&str> = projects.iter()
+            .filter(|p| p.workspace_root.as_deref() == Some(root.as_path()))
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(projects.len(), 3);
+        assert_eq!(members, ["cli", "core"]);
+        
+        let mut app = app_with(projects);
+        app.selected_project = app.projects.iter().position(|p| p.name == "core").unwrap();
+        assert_eq!(app.visible_indices().len(), 3);
+        
+        // 折叠后只显示根项目并选中它
+        press(&mut app, KeyCode::Left).await;
+        assert_eq!(app.visible_indices().len(), 1);
+        assert_eq!(app.projects[app.selected_project].path, root);
+        assert!(render(&mut app).contains("▸ mono"));
+        
+        press(&mut app, KeyCode::Right).await;
+        assert_eq!(app.visible_indices().len(), 3);
+    }
+    
+    #[tokio::test]
+    async fn test_grouped_project_list_collapses_groups() {
+        let mut app = app_with(sample_projects());
+        app.config.display.workspaces = vec!["/code/work".to_string()];
+        app.selected_project = 0;
+
+        press(&mut app, KeyCode::Char('G')).await;
+        assert!(app.grouped);
+        let screen = render(&mut app);
+        assert!(screen.contains("▼ /code/work"));
+        assert!(screen.contains(&format!("合计 {}", crate::utils::size_format::format_size(16000))));
+        assert!(screen.contains("▼ /code"));
+
+        // ← 折叠选中项目所在的分组，折叠的分组在上下移动时只经过一次
+        press(&mut app, KeyCode::Left).await;
+        assert!(app.collapsed_groups.contains(&Some(PathBuf::from("/code/work"))));
+        assert_eq!(app.visible_indices().len(), 2);
+        assert!(render(&mut app).contains("▶ /code/work"));
+        assert_eq!(app.selected_project, 0);
+
+        press(&mut app, KeyCode::Right).await;
+        assert!(app.collapsed_groups.is_empty());
+        assert_eq!(app.visible_indices().len(), 3);
+    }
+    
+    #[tokio::test]
+    async fn test_expand_dependency_rows_in_place() {
+        let projects = vec![
+            ProjectFixture::new("/code/web").sizes(100, 3072).dependency("node_modules", 2048).dependency(".next", 1024).build(),
+            ProjectFixture::new("/code/tool").sizes(10, 0).build(),
+        ];
+        let mut app = app_with(projects);
+        app.sort.field = crate::config::settings::SortField::TotalSize;
+        app.sort.descending = true;
+        app.selected_project = 0;
+        
+        // → 在项目下方展开依赖目录，子行不参与上下移动
+        press(&mut app, KeyCode::Right).await;
+        assert!(app.expanded_projects.contains(&PathBuf::from("/code/web")));
+        let screen = render(&mut app);
+        assert!(screen.contains("├─ node_modules"));
+        assert!(screen.contains("└─ .next"));
+        press(&mut app, KeyCode::Down).await;
+        assert_eq!(app.projects[app.selected_project].name, "tool");
+        
+        // 没有依赖目录的项目不展开
+        press(&mut app, KeyCode::Right).await;
+        assert_eq!(app.expanded_projects.len(), 1);
+        assert!(app.status_message.contains("没有依赖目录"));
+        
+        // ← 收起依赖目录
+        press(&mut app, KeyCode::Up).await;
+        press(&mut app, KeyCode::Left).await;
+        assert!(app.expanded_projects.is_empty());
+        assert!(!render(&mut app).contains("node_modules"));
+    }
+    
+    #[tokio::test]
+    async fn test_toggle_ratio_bar_column() {
+        let mut app = app_with(vec![ProjectFixture::new("/code/web").sizes(300, 700).build()]);
+        let screen = render(&mut app);
+        assert!(screen.contains("占比"));
+        assert!(screen.contains("███"));
+        
+        press(&mut app, KeyCode::Char('a')).await;
+        assert!(!app.main_screen.show_ratio_bar());
+        assert!(!render(&mut app).contains("███"));
+    }
+    
+    #[tokio::test]
+    async fn test_git_info_loaded_for_visible_projects_first() {
+        let mut app = app_with(sample_projects());
+        let blog = PathBuf::from("/code/personal/blog");
+        app.git_hydration.queue(blog.clone());
+        
+        // Git 信息加载前显示占位符
+        let screen = render(&mut app);
+        assert!(screen.lines().any(|line| line.contains("blog") && line.contains('…')));
+        assert_eq!(app.main_screen.visible_projects().len(), 3);
+        
+        app.selected_project = 2;
+        app.state = AppState::ProjectDetail;
+        press(&mut app, KeyCode::Char('b')).await;
+        assert_eq!(app.status_message, "正在分析 Git 信息，请稍后再试");
+        
+        // 可见的项目立即开始分析，分析中不会重复请求
+        app.hydrate_git_info();
+        assert!(app.git_hydration.next_batch([blog.as_path()], [], 4).is_empty());
+        app.git_hydration.finish(&blog);
+        assert!(!app.git_hydration.is_waiting(&blog));
+    }
+    
+    #[tokio::test]
+    async fn test_stale_filter_ranks_by_dependency_size() {
+        let mut recent = ProjectFixture::new("/code/recent").sizes(100, 9000).build();
+        recent.last_modified = chrono::Utc::now();
+        let mut app = app_with(vec![
+            ProjectFixture::new("/code/small").sizes(100, 10).build(),
+            recent,
+            ProjectFixture::new("/code/big").sizes(100, 5000).build(),
+        ]);
+        
+        press(&mut app, KeyCode::Char('z')).await;
+        let names: Vec<&str> = app.visible_indices().iter().map(|&index| app.projects[index].name.as_str()).collect();
+        assert_eq!(names, ["big", "small"]);
+        assert_eq!(app.status_message, "2 个项目超过 180 天未活动，按依赖大小排序");
+        assert!(render(&mut app).contains("之后未活动"));
+        
+        press(&mut app, KeyCode::Char('z')).await;
+        assert_eq!(app.visible_indices().len(), 3);
     }
-}
\ No newline at end of file
+}
@@ -3,10 +3,16 @@
 use std::time::Duration;
 use crossterm::event::{self, KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use tokio::sync::mpsc;
 
-use crate::models::{Project, GitInfo};
-use crate::scanner::{ScanStage};
+use crate::models::{ByteSize, Project, GitInfo, CleanupSuggestion, CommitSummary, PackageStats};
+use crate::operations::cleanup::CleanupPlan;
+use crate::operations::deleter::DeleteProgress;
+use crate::operations::git_fetch::FetchStatus;
+use crate::operations::wizard::WizardAction;
+use crate::scanner::{ScanStage, SizeTreeNode};
+use crate::scanner::watcher::WatchEvent;
 
 /// 应用程序事件枚举
 #[derive(Clone, Debug)]
@@ -23,36 +29,67 @@ pub enum Event {
     /// 扫描完成事件
     ScanComplete,
     
+    /// 收到 SIGTERM 等终止信号，需要恢复终端后退出
+    Shutdown,
+    
     /// 扫描进度更新
     ScanProgress(String),
     
     /// 发现新项目
     ProjectFound(Project),
     
+    /// 增量刷新时发现项目已修改，携带重新检测的结果
+    ProjectUpdated(Project),
+    
+    /// 项目已不存在（增量刷新时发现，或计算大小、分析 Git 时发现目录已被外部删除）
+    ProjectRemoved(std::path::PathBuf),
+    
+    /// 监视扫描路径时发现的变化
+    Watch(WatchEvent),
+    
+    /// 后台扫描完成，携带发现的全部项目
+    ScanResults(Vec<Project>),
+    /// 后台扫描失败，携带原因
+    ScanFailed(String),
+    
+    /// 扫描时跳过了无法访问的位置，携带汇总说明
+    LocationsSkipped(String),
+    
     /// 项目大小更新事件（已弃用，使用ProjectDetailsUpdated替代）
     ProjectSizeUpdated {
-        project_index: usize,
-        code_size: u64,
-        total_size: u64,
-        gitignore_excluded_size: u64,
+        project_path: std::path::PathBuf,
+        code_size: ByteSize,
+        total_size: ByteSize,
+        gitignore_excluded_size: ByteSize,
         code_file_count: usize,
         dependency_file_count: usize,
         total_file_count: usize,
         gitignore_excluded_file_count: usize,
+        size_truncated: bool,
+        /// 最新的文件修改时间，无法读取时为 None
+        last_modified: Option<DateTime<Utc>>,
     },
     
     /// 项目详情更新事件
     ProjectDetailsUpdated {
         project_name: String,
-        code_size: u64,
-        dependency_size: u64,
-        total_size: u64,
-        gitignore_excluded_size: u64,
+        code_size: ByteSize,
+        dependency_size: ByteSize,
+        total_size: ByteSize,
+        gitignore_excluded_size: ByteSize,
         code_file_count: usize,
         dependency_file_count: usize,
         total_file_count: usize,
         gitignore_excluded_file_count: usize,
-        git_info: Option<GitInfo>,
+        size_truncated: bool,
+        /// 最新的文件修改时间，无法读取时为 None
+        last_modified: Option<DateTime<Utc>>,
+    },
+    
+    /// 项目代码大小的快速估算值（精确计算完成前显示）
+    ProjectSizeEstimated {
+        project_name: String,
+        estimated_size: ByteSize,
     },
     
     /// 项目开始计算事件
@@ -66,10 +103,78 @@ pub enum Event {
         processed_files: usize,
         total_files: Option<usize>,
         current_path: String,
-        bytes_processed: u64,
+        bytes_processed: ByteSize,
         stage: ScanStage,
     },
     
+    /// 清理建议分析完成
+    CleanupSuggestionsReady {
+        project_name: String,
+        suggestions: Vec<CleanupSuggestion>,
+    },
+    
+    /// 清理建议已执行（目录已删除）
+    CleanupSuggestionApplied {
+        project_name: String,
+        path: std::path::PathBuf,
+        freed_size: ByteSize,
+    },
+    
+    /// 项目磁盘占用明细统计完成
+    DiskUsageReady {
+        project_path: std::path::PathBuf,
+        tree: SizeTreeNode,
+    },
+    
+    /// 清理计划计算完成，等待确认
+    CleanupPlanReady {
+        project_path: std::path::PathBuf,
+        plan: CleanupPlan,
+    },
+    
+    /// 项目依赖清理完成
+    CleanupCompleted {
+        project_path: std::path::PathBuf,
+        freed_size: ByteSize,
+    },
+    
+    /// 后台删除依赖目录的进度
+    DeletionProgress {
+        path: std::path::PathBuf,
+        progress: DeleteProgress,
+    },
+    
+    /// 清理向导中的操作已完成（成功时携带释放的空间）
+    CleanupWizardStepDone {
+        path: std::path::PathBuf,
+        action: WizardAction,
+        result: Result<ByteSize, String>,
+    },
+    
+    /// 项目的 Git 信息分析完成（不是 Git 仓库或分析失败时为 None）
+    GitInfoLoaded {
+        project_path: std::path::PathBuf,
+        git_info: Option<GitInfo>,
+    },
+    
+    /// 仓库的 git fetch 状态更新（开始 fetch、完成或失败）
+    GitFetchUpdated {
+        project_path: std::path::PathBuf,
+        status: FetchStatus,
+    },
+    
+    /// 最近提交记录加载完成
+    RecentCommitsReady {
+        project_name: String,
+        commits: Vec<CommitSummary>,
+    },
+    
+    /// 依赖包统计完成（依赖目录 → 统计）
+    PackageStatsReady {
+        project_path: std::path::PathBuf,
+        stats: Vec<(std::path::PathBuf, PackageStats)>,
+    },
+    
     /// 应用程序退出
     Quit,
     
@@ -163,6 +268,11 @@ impl EventHandler {
             .ok_or_else(|| anyhow::anyhow!("事件通道已关闭"))
     }
     
+    /// 事件通道中等待处理的事件数
+    pub fn pending(&self) -> usize {
+        self.receiver.len()
+    }
+    
     /// 发送自定义事件
     pub fn send(&self, event: Event) -> Result<()> {
         self.sender.send(event)
@@ -258,7 +368,12 @@ pub mod keys {
     
     /// 检查是否是忽略键 (i)
     pub fn is_ignore_key(key: &KeyEvent) -> bool {
-        matches!(key.code, KeyCode::Char('i') | KeyCode::Char('I'))
+        matches!(key.code, KeyCode::Char('i'))
+    }
+    
+    /// 检查是否是编辑忽略列表键 (I)
+    pub fn is_ignore_list_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('I'))
     }
     
     /// 检查是否是帮助键 (h, ?, F1)
@@ -271,10 +386,135 @@ pub mod keys {
         matches!(key.code, KeyCode::Tab)
     }
     
-    /// 检查是否是nvim编辑键 (e)
-    pub fn is_nvim_key(key: &KeyEvent) -> bool {
+    /// 检查是否是编辑键 (e)
+    pub fn is_edit_key(key: &KeyEvent) -> bool {
         matches!(key.code, KeyCode::Char('e') | KeyCode::Char('E'))
     }
+    
+    /// 检查是否是行密度切换键 (v)
+    pub fn is_density_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('v') | KeyCode::Char('V'))
+    }
+    
+    /// 检查是否是占比列切换键 (a)
+    pub fn is_ratio_bar_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('a') | KeyCode::Char('A'))
+    }
+    
+    /// 检查是否是长期未活动项目过滤键 (z)
+    pub fn is_stale_filter_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('z') | KeyCode::Char('Z'))
+    }
+    
+    /// 检查是否是标记键 (m)
+    pub fn is_mark_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('m') | KeyCode::Char('M'))
+    }
+    
+    /// 检查是否是目录过滤键 (f)
+    pub fn is_filter_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('f') | KeyCode::Char('F'))
+    }
+    
+    /// 检查是否是复制路径键 (y)
+    pub fn is_yank_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y'))
+    }
+    
+    /// 检查是否是隐藏项目键 (x)
+    pub fn is_hide_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('x'))
+    }
+    
+    /// 检查是否是磁盘占用明细键 (u)
+    pub fn is_disk_usage_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('u') | KeyCode::Char('U'))
+    }
+    
+    /// 检查是否是设置标签键 (t)
+    pub fn is_tag_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('t') | KeyCode::Char('T'))
+    }
+    
+    /// 检查是否是复制项目报告键 (p)
+    pub fn is_report_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('p'))
+    }
+    
+    /// 检查是否是保存项目报告键 (P)
+    pub fn is_save_report_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('P'))
+    }
+    
+    /// 检查是否是导出项目列表键 (X)
+    pub fn is_export_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('X'))
+    }
+    
+    /// 检查是否是显示隐藏项目切换键 (.)
+    pub fn is_show_hidden_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('.'))
+    }
+    
+    /// 检查是否是显示已忽略项目切换键 (g)
+    pub fn is_show_ignored_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('g'))
+    }
+    
+    /// 检查是否是后台任务面板键 (o)
+    pub fn is_task_list_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('o') | KeyCode::Char('O'))
+    }
+    
+    /// 检查是否是分支列表键 (b)
+    pub fn is_branch_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('b') | KeyCode::Char('B'))
+    }
+    
+    /// 检查是否是清理向导键 (w)
+    pub fn is_wizard_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('w') | KeyCode::Char('W'))
+    }
+    
+    /// 检查是否是翻页键 (PageUp, PageDown)
+    pub fn is_page_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::PageUp | KeyCode::PageDown)
+    }
+    
+    /// 检查是否是切换排序字段键 (s)
+    pub fn is_sort_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('s'))
+    }
+    
+    /// 检查是否是切换升序/降序键 (S)
+    pub fn is_sort_order_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('S'))
+    }
+    
+    /// 检查是否是调试浮层键 (F12)
+    pub fn is_debug_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::F(12))
+    }
+    
+    /// 检查是否是搜索键 (/)
+    pub fn is_search_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('/'))
+    }
+    
+    /// 检查是否是分组显示键 (G)
+    pub fn is_group_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('G'))
+    }
+    
+    /// 检查是否是 fetch 选中仓库键 (f，Git 状态标签页)
+    pub fn is_fetch_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('f'))
+    }
+    
+    /// 检查是否是 fetch 全部仓库键 (F，Git 状态标签页)
+    pub fn is_fetch_all_key(key: &KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Char('F'))
+    }
 }
 
 #[cfg(test)]
@@ -299,9 +539,18 @@ mod tests {
     }
 
     #[test]
-    fn test_nvim_key() {
-        assert!(keys::is_nvim_key(&KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE)));
-        assert!(keys::is_nvim_key(&KeyEvent::new(KeyCode::Char('E'), KeyModifiers::NONE)));
-        assert!(!keys::is_nvim_key(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+    fn test_edit_key() {
+        assert!(keys::is_edit_key(&KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE)));
+        assert!(keys::is_edit_key(&KeyEvent::new(KeyCode::Char('E'), KeyModifiers::NONE)));
+        assert!(!keys::is_edit_key(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+        assert!(keys::is_branch_key(&KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE)));
+        assert!(keys::is_yank_key(&KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn test_hide_and_export_keys() {
+        assert!(keys::is_hide_key(&KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE)));
+        assert!(!keys::is_hide_key(&KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT)));
+        assert!(keys::is_export_key(&KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT)));
     }
 }
\ No newline at end of file
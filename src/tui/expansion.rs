@@ -0,0 +1,60 @@
+// 项目行就地展开：展开的项目下方以缩进的子行列出依赖目录和大小，子行不能被选中
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::models::Project;
+
+/// 项目行下方展开的依赖子行数，未展开时为 0
+pub fn child_rows(project: &Project, expanded: &HashSet<PathBuf>) -> usize {
+    if expanded.contains(&project.path) {
+        project.dependencies.len()
+    } else {
+        0
+    }
+}
+
+/// 第 `base` 个基础行（项目或分组标题）在展开后列表中的位置
+///
+/// `child_counts` 为每个基础行下方的子行数，顺序与列表一致。
+pub fn display_row(child_counts: &[usize], base: usize) -> usize {
+    base + child_counts.iter().take(base).sum::<usize>()
+}
+
+/// 展开后列表中第 `row` 行对应的基础行，依赖子行对应其所属的项目；超出列表时返回 None
+pub fn base_row(child_counts: &[usize], row: usize) -> Option<usize> {
+    let mut start = 0;
+    for (base, &children) in child_counts.iter().enumerate() {
+        if row <= start + children {
+            return Some(base);
+        }
+        start += children + 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::ProjectFixture;
+
+    #[test]
+    fn test_rows_with_expanded_dependencies() {
+        let project = ProjectFixture::new("/code/web")
+            .dependency("node_modules", 2048)
+            .dependency(".next", 1024)
+            .build();
+        let mut expanded = HashSet::new();
+        assert_eq!(child_rows(&project, &expanded), 0);
+        expanded.insert(project.path.clone());
+        assert_eq!(child_rows(&project, &expanded), 2);
+
+        // 第 0 行展开了两个依赖子行：列表为 [0, 0.a, 0.b, 1, 2, 2.a]
+        let counts = [2, 0, 1];
+        assert_eq!(display_row(&counts, 0), 0);
+        assert_eq!(display_row(&counts, 1), 3);
+        assert_eq!(display_row(&counts, 2), 4);
+        let bases: Vec<Option<usize>> = (0..7).map(|row| base_row(&counts, row)).collect();
+        assert_eq!(bases, [Some(0), Some(0), Some(0), Some(1), Some(2), Some(2), None]);
+    }
+}
@@ -0,0 +1,503 @@
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::config::settings::{DisplayConfig, SortField};
+use crate::models::Project;
+use crate::utils::path_format;
+
+/// 项目列表过滤条件
+#[derive(Debug, Clone, Default)]
+pub struct ProjectFilter {
+    /// 只显示该目录下的项目
+    pub directory: Option<PathBuf>,
+    
+    /// 是否显示被隐藏的项目
+    pub show_hidden: bool,
+    
+    /// 是否显示已忽略的项目（以便取消忽略）
+    pub show_ignored: bool,
+    
+    /// 搜索关键字，按名称、路径或类型模糊匹配
+    pub query: String,
+    
+    /// 被当前忽略规则排除的项目（在界面中修改忽略列表后、重新扫描前不再显示）
+    pub excluded: HashSet<PathBuf>,
+    
+    /// 已折叠的工作区根项目，其成员不在列表中显示
+    pub collapsed_workspaces: HashSet<PathBuf>,
+    
+    /// 只显示在该时间之前最后活动的长期未活动项目
+    pub stale_before: Option<DateTime<Utc>>,
+}
+
+impl ProjectFilter {
+    /// 是否有生效的过滤条件
+    pub fn is_active(&self) -> bool {
+        self.directory.is_some() || !self.query.trim().is_empty() || self.stale_before.is_some()
+    }
+
+    /// 检查项目是否满足过滤条件
+    pub fn matches(&self, project: &Project) -> bool {
+        if let Some(directory) = &self.directory {
+            if !project.path.starts_with(directory) {
+                return false;
+            }
+        }
+
+        if self.stale_before.is_some_and(|cutoff| project.last_touched() >= cutoff) {
+            return false;
+        }
+
+        matches_query(&self.query, project)
+    }
+
+    /// 检查项目是否应出现在列表中（满足过滤条件、未被忽略规则排除，未被隐藏和忽略或已选择显示这些项目）
+    pub fn is_visible(&self, project: &Project) -> bool {
+        (self.show_hidden || !project.is_hidden)
+            && (self.show_ignored || !project.is_ignored)
+            && !self.excluded.contains(&project.path)
+            && self.matches(project)
+    }
+
+    /// 清除所有过滤条件
+    pub fn clear(&mut self) {
+        self.directory = None;
+        self.query.clear();
+        self.stale_before = None;
+    }
+
+    /// 过滤条件的简短描述
+    pub fn description(&self) -> String {
+        let mut parts = Vec::new();
+
+        if let Some(directory) = &self.directory {
+            parts.push(format!("目录 {}", path_format::format_path(directory, 40)));
+        }
+
+        if !self.query.trim().is_empty() {
+            parts.push(format!("搜索 \"{}\"", self.query.trim()));
+        }
+
+        if let Some(cutoff) = self.stale_before {
+            parts.push(format!("{} 之后未活动", cutoff.with_timezone(&Local).format("%Y-%m-%d")));
+        }
+
+        parts.join(", ")
+    }
+}
+
+/// 项目列表排序方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectSort {
+    /// 排序字段
+    pub field: SortField,
+    
+    /// 是否降序
+    pub descending: bool,
+}
+
+impl ProjectSort {
+    /// 使用配置中保存的排序方式
+    pub fn from_config(display: &DisplayConfig) -> Self {
+        Self {
+            field: display.default_sort,
+            descending: display.sort_descending,
+        }
+    }
+
+    /// 比较两个项目的先后顺序
+    pub fn compare(&self, a: &Project, b: &Project) -> Ordering {
+        let ordering = match self.field {
+            SortField::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortField::Size => a.size().cmp(&b.size()),
+            SortField::TotalSize => a.disk_usage().cmp(&b.disk_usage()),
+            SortField::DependencySize => a.dependency_size().cmp(&b.dependency_size()),
+            SortField::LastModified => a.last_touched().cmp(&b.last_touched()),
+            SortField::ProjectType => a.type_display_name().cmp(&b.type_display_name()),
+        };
+        let ordering = if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+        // 排序字段相同时按路径排列，保证顺序不受项目发现先后的影响
+        ordering.then_with(|| a.path.cmp(&b.path))
+    }
+
+    /// 排序方式的简短描述，例如 "总大小 ↓"
+    pub fn description(&self) -> String {
+        format!("{} {}", self.field.label(), if self.descending { "↓" } else { "↑" })
+    }
+
+    /// 列表头中的列标题，按该列排序时附上排序方向
+    pub fn column_title(&self, title: &str, field: SortField) -> String {
+        if self.field == field {
+            format!("{} {}", title, if self.descending { "↓" } else { "↑" })
+        } else {
+            title.to_string()
+        }
+    }
+}
+
+/// 固定的显示顺序：扫描和大小计算进行期间已显示的项目保持位置，列表不随结果到达而跳动
+///
+/// 固定之后加入的项目按排序方式排在已固定的项目之后。
+#[derive(Debug, Clone, Default)]
+pub struct PinnedOrder {
+    /// 项目路径 → 固定时的显示位置
+    positions: HashMap<PathBuf, usize>,
+}
+
+impl PinnedOrder {
+    /// 按排序方式固定当前所有项目（包括被过滤掉的项目）的顺序
+    pub fn capture(projects: &[Project], sort: ProjectSort) -> Self {
+        let mut indices: Vec<usize> = (0..projects.len()).collect();
+        indices.sort_by(|&a, &b| sort.compare(&projects[a], &projects[b]));
+        Self {
+            positions: indices.into_iter()
+                .enumerate()
+                .map(|(position, index)| (projects[index].path.clone(), position))
+                .collect(),
+        }
+    }
+
+    /// 比较两个项目的先后：已固定的项目按固定位置，新项目排在其后
+    fn compare(&self, a: &Project, b: &Project, sort: ProjectSort) -> Ordering {
+        match (self.positions.get(&a.path), self.positions.get(&b.path)) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => sort.compare(a, b),
+        }
+    }
+}
+
+/// 满足过滤条件、应出现在列表中的项目索引，按排序方式排列；顺序已固定时按固定顺序排列
+///
+/// 工作区成员紧跟在其根项目之后，根项目折叠时不显示成员。
+pub fn visible_indices(projects: &[Project], filter: &ProjectFilter, sort: ProjectSort, pinned: Option<&PinnedOrder>) -> Vec<usize> {
+    let mut indices: Vec<usize> = projects.iter()
+        .enumerate()
+        .filter(|(_, project)| filter.is_visible(project))
+        .map(|(index, _)| index)
+        .collect();
+    indices.sort_by(|&a, &b| match pinned {
+        Some(pinned) => pinned.compare(&projects[a], &projects[b], sort),
+        None => sort.compare(&projects[a], &projects[b]),
+    });
+    nest_workspace_members(projects, indices, &filter.collapsed_workspaces)
+}
+
+/// 将工作区成员移到根项目之后（成员之间保持原有顺序）；根项目不在列表中时成员留在原位
+fn nest_workspace_members(projects: &[Project], indices: Vec<usize>, collapsed: &HashSet<PathBuf>) -> Vec<usize> {
+    let listed: HashSet<&Path> = indices.iter().map(|&index| projects[index].path.as_path()).collect();
+    let mut members: HashMap<&Path, Vec<usize>> = HashMap::new();
+    for &index in &indices {
+        if let Some(root) = projects[index].workspace_root.as_deref().filter(|root| listed.contains(root)) {
+            members.entry(root).or_default().push(index);
+        }
+    }
+    if members.is_empty() {
+        return indices;
+    }
+
+    let mut nested = Vec::with_capacity(indices.len());
+    for index in indices {
+        let project = &projects[index];
+        if project.workspace_root.as_deref().is_some_and(|root| listed.contains(root)) {
+            continue;
+        }
+        nested.push(index);
+        if !collapsed.contains(&project.path) {
+            nested.extend(members.get(project.path.as_path()).into_iter().flatten());
+        }
+    }
+    nested
+}
+
+/// 工作区树中项目名称前的标识：有成员的根项目为 ▾/▸（展开/折叠），
+/// 紧跟在根项目之后的成员为 ├/└；其他项目没有标识
+pub fn workspace_tree_labels(projects: &[Project], visible: &[usize], collapsed: &HashSet<PathBuf>) -> HashMap<PathBuf, &'static str> {
+    let roots: HashSet<&Path> = projects.iter()
+        .filter_map(|project| project.workspace_root.as_deref())
+        .collect();
+    let mut labels = HashMap::new();
+
+    for (position, &index) in visible.iter().enumerate() {
+        let project = &projects[index];
+        if roots.contains(project.path.as_path()) {
+            let label = if collapsed.contains(&project.path) { "▸ " } else { "▾ " };
+            labels.insert(project.path.clone(), label);
+            continue;
+        }
+
+        let Some(root) = project.workspace_root.as_deref() else {
+            continue;
+        };
+        let follows_root = visible[..position].iter().rev()
+            .map(|&previous| &projects[previous])
+            .find(|previous| previous.workspace_root.as_deref() != Some(root))
+            .is_some_and(|previous| previous.path == root);
+        if follows_root {
+            let is_last = visible.get(position + 1)
+                .is_none_or(|&next| projects[next].workspace_root.as_deref() != Some(root));
+            labels.insert(project.path.clone(), if is_last { "└ " } else { "├ " });
+        }
+    }
+
+    labels
+}
+
+/// 检查项目是否匹配搜索关键字（不区分大小写）
+///
+/// 关键字按空白分成多个词，每个词都需匹配：按顺序出现在名称或类型中（中间可以间隔其他字符），
+/// 或者是路径的一部分。
+fn matches_query(query: &str, project: &Project) -> bool {
+    let name = project.name.to_lowercase();
+    let type_name = project.type_display_name().to_lowercase();
+    let path = project.path.to_string_lossy().to_lowercase();
+
+    query.to_lowercase().split_whitespace().all(|term| {
+        is_subsequence(term, &name) || is_subsequence(term, &type_name) || path.contains(term)
+    })
+}
+
+/// `pattern` 的字符是否按顺序出现在 `text` 中
+fn is_subsequence(pattern: &str, text: &str) -> bool {
+    let mut chars = text.chars();
+    pattern.chars().all(|c| chars.any(|t| t == c))
+}
+
+/// 统计信息的作用范围
+#[derive(Debug)]
+pub struct StatsScope<'a> {
+    /// 范围说明（显示在统计页标题中）
+    pub label: String,
+
+    /// 参与统计的项目
+    pub projects: Vec<&'a Project>,
+}
+
+impl<'a> StatsScope<'a> {
+    /// 确定统计范围：优先使用标记的项目，其次是过滤结果，否则为全部项目
+    pub fn resolve(projects: &'a [Project], filter: &ProjectFilter, marked: &HashSet<PathBuf>) -> Self {
+        if !marked.is_empty() {
+            let scoped: Vec<&Project> = projects.iter()
+                .filter(|p| marked.contains(&p.path))
+                .collect();
+            return Self {
+                label: format!("已标记的 {} 个项目", scoped.len()),
+                projects: scoped,
+            };
+        }
+
+        if filter.is_active() {
+            let scoped: Vec<&Project> = projects.iter()
+                .filter(|p| filter.matches(p))
+                .collect();
+            return Self {
+                label: format!("过滤结果（{}）", filter.description()),
+                projects: scoped,
+            };
+        }
+
+        Self {
+            label: "全部项目".to_string(),
+            projects: projects.iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ByteSize;
+    use crate::tui::fixtures::{fixed_time, GitState, ProjectFixture};
+
+    fn project(path: &str) -> Project {
+        ProjectFixture::new(path).build()
+    }
+
+    #[test]
+    fn test_stats_scope_resolution() {
+        let projects = vec![
+            project("/code/work/api"),
+            project("/code/work/web"),
+            project("/code/personal/blog"),
+        ];
+
+        let mut filter = ProjectFilter::default();
+        let mut marked = HashSet::new();
+
+        // 没有过滤和标记时统计全部项目
+        let scope = StatsScope::resolve(&projects, &filter, &marked);
+        assert_eq!(scope.projects.len(), 3);
+
+        // 目录过滤
+        filter.directory = Some(PathBuf::from("/code/work"));
+        let scope = StatsScope::resolve(&projects, &filter, &marked);
+        assert_eq!(scope.projects.len(), 2);
+        assert!(scope.label.contains("过滤"));
+
+        // 标记的项目优先于过滤条件
+        marked.insert(PathBuf::from("/code/personal/blog"));
+        let scope = StatsScope::resolve(&projects, &filter, &marked);
+        assert_eq!(scope.projects.len(), 1);
+        assert_eq!(scope.projects[0].name, "blog");
+    }
+
+    #[test]
+    fn test_fuzzy_search() {
+        let projects = [
+            project("/code/work/payment-service"),
+            ProjectFixture::new("/code/personal/blog").project_type(crate::models::ProjectType::NodeJs).build(),
+            project("/code/work/api"),
+        ];
+        let names = |filter: &ProjectFilter| -> Vec<String> {
+            projects.iter().filter(|p| filter.matches(p)).map(|p| p.name.clone()).collect()
+        };
+
+        let mut filter = ProjectFilter { query: "pysvc".to_string(), ..ProjectFilter::default() };
+        assert!(filter.is_active());
+        assert_eq!(names(&filter), ["payment-service"]);
+
+        // 按类型匹配
+        filter.query = "NODE".to_string();
+        assert_eq!(names(&filter), ["blog"]);
+
+        // 多个词都需匹配，路径按子串匹配
+        filter.query = "work a".to_string();
+        assert_eq!(names(&filter), ["payment-service", "api"]);
+        assert!(filter.description().contains("搜索 \"work a\""));
+
+        filter.clear();
+        assert!(!filter.is_active());
+        assert_eq!(names(&filter).len(), 3);
+    }
+
+    #[test]
+    fn test_project_sort() {
+        let projects = [
+            ProjectFixture::new("/code/web").sizes(2000, 100).build(),
+            ProjectFixture::new("/code/Api").sizes(500, 9000).build(),
+            ProjectFixture::new("/code/blog").sizes(800, 0).build(),
+        ];
+        let filter = ProjectFilter::default();
+        let mut sort = ProjectSort { field: SortField::Name, descending: false };
+        assert_eq!(visible_indices(&projects, &filter, sort, None), [1, 2, 0]);
+
+        sort.field = sort.field.next();
+        sort.descending = true;
+        assert_eq!(sort.field, SortField::Size);
+        assert_eq!(visible_indices(&projects, &filter, sort, None), [0, 2, 1]);
+
+        sort.field = sort.field.next();
+        assert_eq!(sort.description(), "总大小 ↓");
+        assert_eq!(visible_indices(&projects, &filter, sort, None), [1, 0, 2]);
+    }
+
+    #[test]
+    fn test_sort_by_last_modified_uses_latest_commit_or_file_change() {
+        let day = |days: i64| fixed_time() + chrono::Duration::days(days);
+        let mut edited = project("/code/edited");
+        edited.last_modified = day(30);
+        let mut committed = ProjectFixture::new("/code/committed").git(GitState::Clean).build();
+        committed.last_modified = day(-30);
+        if let Some(git_info) = committed.git_info.as_mut() {
+            git_info.last_commit_time = Some(day(10));
+        }
+        let untouched = project("/code/untouched");
+        let projects = [untouched, committed, edited];
+
+        // 有提交的项目按最后提交时间排序，未提交的改动按文件修改时间排序
+        let sort = ProjectSort { field: SortField::LastModified, descending: true };
+        assert_eq!(visible_indices(&projects, &ProjectFilter::default(), sort, None), [2, 1, 0]);
+        assert_eq!(sort.column_title("最后修改", SortField::LastModified), "最后修改 ↓");
+        assert_eq!(sort.column_title("大小", SortField::Size), "大小");
+    }
+
+    #[test]
+    fn test_pinned_order() {
+        let mut projects = vec![
+            ProjectFixture::new("/code/web").sizes(100, 0).build(),
+            ProjectFixture::new("/code/api").sizes(100, 0).build(),
+        ];
+        let filter = ProjectFilter::default();
+        let sort = ProjectSort { field: SortField::Size, descending: true };
+        // 大小相同时按路径排列
+        assert_eq!(visible_indices(&projects, &filter, sort, None), [1, 0]);
+
+        let pinned = PinnedOrder::capture(&projects, sort);
+        projects[0].code_size = ByteSize::new(5000);
+        projects.push(ProjectFixture::new("/code/docs").sizes(10, 0).build());
+        projects.push(ProjectFixture::new("/code/blog").sizes(9000, 0).build());
+
+        // 已显示的项目不因大小变化而移动，新项目按排序方式排在后面
+        assert_eq!(visible_indices(&projects, &filter, sort, Some(&pinned)), [1, 0, 3, 2]);
+        assert_eq!(visible_indices(&projects, &filter, sort, None), [3, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_hidden_projects_visibility() {
+        let projects = vec![
+            project("/code/work/api"),
+            ProjectFixture::new("/code/work/legacy").hidden().build(),
+        ];
+
+        let mut filter = ProjectFilter::default();
+        assert!(filter.is_visible(&projects[0]));
+        assert!(!filter.is_visible(&projects[1]));
+
+        // 隐藏项目仍参与统计
+        let scope = StatsScope::resolve(&projects, &filter, &HashSet::new());
+        assert_eq!(scope.projects.len(), 2);
+
+        filter.show_hidden = true;
+        assert!(filter.is_visible(&projects[1]));
+        assert!(!filter.is_active());
+
+        // 已忽略的项目同样默认不显示
+        let ignored = ProjectFixture::new("/code/work/old").ignored().build();
+        assert!(!filter.is_visible(&ignored));
+        filter.show_ignored = true;
+        assert!(filter.is_visible(&ignored));
+    }
+
+    #[test]
+    fn test_workspace_members_nested_under_root() {
+        let projects = vec![
+            ProjectFixture::new("/code/mono/packages/ui").sizes(30, 0).workspace_member("/code/mono").build(),
+            ProjectFixture::new("/code/blog").sizes(200, 0).build(),
+            ProjectFixture::new("/code/mono").sizes(100, 0).build(),
+            ProjectFixture::new("/code/mono/packages/api").sizes(50, 0).workspace_member("/code/mono").build(),
+        ];
+        let sort = ProjectSort { field: SortField::Size, descending: true };
+        let mut filter = ProjectFilter::default();
+
+        // 成员跟在根项目之后，成员之间按排序方式排列
+        let visible = visible_indices(&projects, &filter, sort, None);
+        assert_eq!(visible, [1, 2, 3, 0]);
+        let labels = workspace_tree_labels(&projects, &visible, &filter.collapsed_workspaces);
+        assert_eq!(labels.get(Path::new("/code/mono")), Some(&"▾ "));
+        assert_eq!(labels.get(Path::new("/code/mono/packages/api")), Some(&"├ "));
+        assert_eq!(labels.get(Path::new("/code/mono/packages/ui")), Some(&"└ "));
+        assert_eq!(labels.get(Path::new("/code/blog")), None);
+
+        // 折叠后不显示成员
+        filter.collapsed_workspaces.insert(PathBuf::from("/code/mono"));
+        let visible = visible_indices(&projects, &filter, sort, None);
+        assert_eq!(visible, [1, 2]);
+        let labels = workspace_tree_labels(&projects, &visible, &filter.collapsed_workspaces);
+        assert_eq!(labels.get(Path::new("/code/mono")), Some(&"▸ "));
+
+        // 根项目被过滤掉时成员按原有位置显示
+        filter.collapsed_workspaces.clear();
+        filter.query = "packages".to_string();
+        assert_eq!(visible_indices(&projects, &filter, sort, None), [3, 0]);
+    }
+}
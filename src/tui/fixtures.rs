@@ -0,0 +1,140 @@
+//! TUI 逻辑测试使用的项目数据构造器，不访问真实文件系统
+
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+
+use crate::models::{
+    ByteSize, CleanupSuggestion, CloudProvider, DependencyCalculationStatus, DependencyInfo, DependencyType, GitInfo, Project, ProjectType, SuggestionStatus,
+};
+
+/// 固定的时间基准，保证测试结果稳定
+pub fn fixed_time() -> DateTime<Utc> {
+    DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+}
+
+/// Git 仓库状态
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GitState {
+    /// 工作区干净
+    Clean,
+
+    /// 有未提交的更改
+    Dirty,
+
+    /// 有未推送的提交
+    Unpushed,
+}
+
+/// 项目构造器
+pub struct ProjectFixture {
+    project: Project,
+}
+
+impl ProjectFixture {
+    /// 以指定路径创建项目，名称取路径最后一级
+    pub fn new(path: &str) -> Self {
+        Self {
+            project: Project::builder(path.rsplit('/').next().unwrap_or(path), path)
+                .project_type(ProjectType::Rust)
+                .last_modified(fixed_time())
+                .build(),
+        }
+    }
+
+    /// 设置项目类型
+    pub fn project_type(mut self, project_type: ProjectType) -> Self {
+        self.project.project_type = project_type;
+        self
+    }
+
+    /// 设置代码和依赖大小（依赖大小视为已计算完成）
+    pub fn sizes(mut self, code_size: u64, dependency_size: u64) -> Self {
+        self.project.code_size = ByteSize::new(code_size);
+        self.project.total_size = ByteSize::new(code_size + dependency_size);
+        self.project.cached_dependency_size = Some(ByteSize::new(dependency_size));
+        self.project.dependency_calculation_status = DependencyCalculationStatus::Completed;
+        self
+    }
+
+    /// 设置为 Git 仓库及其状态
+    pub fn git(mut self, state: GitState) -> Self {
+        self.project.git_info = Some(GitInfo {
+            remote_url: Some(format!("git@example.com:{}.git", self.project.name)),
+            current_branch: Some("main".to_string()),
+            last_commit_time: Some(fixed_time()),
+            last_commit_message: Some("initial commit".to_string()),
+            last_commit_author: Some("tester".to_string()),
+            has_uncommitted_changes: state == GitState::Dirty,
+            has_unpushed_commits: state == GitState::Unpushed,
+            stash_count: 0,
+            untracked_count: 0,
+            changed_file_count: 0,
+            staged_file_count: 0,
+            modified_file_count: 0,
+            head_detached: false,
+            unpushed_commit_count: 0,
+            is_bare: false,
+            worktree_of: None,
+            ahead_behind: None,
+        });
+        // 预置空提交记录，避免测试中读取真实仓库
+        self.project.recent_commits = Some(Vec::new());
+        self
+    }
+
+    /// 设置待处理的清理建议（相对项目路径的目录名及大小）
+    pub fn cleanup_suggestions(mut self, dirs: &[(&str, u64)]) -> Self {
+        let total: u64 = dirs.iter().map(|(_, size)| size).sum();
+        self.project.cleanup_suggestions = Some(dirs.iter()
+            .map(|(name, size)| CleanupSuggestion {
+                path: self.project.path.join(name),
+                size: ByteSize::new(*size),
+                file_count: 1,
+                share: if total > 0 { *size as f64 / total as f64 } else { 0.0 },
+                status: SuggestionStatus::Pending,
+            })
+            .collect());
+        self
+    }
+
+    /// 添加依赖目录（相对项目路径的目录名及大小）
+    pub fn dependency(mut self, name: &str, size: u64) -> Self {
+        self.project.dependencies.push(DependencyInfo {
+            dependency_type: DependencyType::Other(name.to_string()),
+            path: self.project.path.join(name),
+            size: ByteSize::new(size),
+            package_count: None,
+            packages: None,
+        });
+        self
+    }
+
+    /// 标记为忽略
+    pub fn ignored(mut self) -> Self {
+        self.project.is_ignored = true;
+        self
+    }
+
+    /// 标记为隐藏
+    pub fn hidden(mut self) -> Self {
+        self.project.is_hidden = true;
+        self
+    }
+
+    /// 标记为位于云同步目录中
+    pub fn cloud_synced(mut self, provider: CloudProvider) -> Self {
+        self.project.cloud_sync = Some(provider);
+        self
+    }
+
+    /// 设置为工作区成员
+    pub fn workspace_member(mut self, root: &str) -> Self {
+        self.project.workspace_root = Some(PathBuf::from(root));
+        self
+    }
+
+    /// 生成项目
+    pub fn build(self) -> Project {
+        self.project
+    }
+}
@@ -0,0 +1,99 @@
+// 延迟加载 Git 信息：扫描后不再一次性分析全部仓库，先分析列表中可见和选中的项目，其余项目在后台逐个补全
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// 项目 Git 信息的加载状态
+#[derive(Debug, Default)]
+pub struct GitHydration {
+    /// 等待分析的项目
+    pending: HashSet<PathBuf>,
+
+    /// 正在分析的项目
+    loading: HashSet<PathBuf>,
+}
+
+impl GitHydration {
+    /// 将项目加入等待分析的队列（已在分析中的项目在结果返回后重新分析）
+    pub fn queue(&mut self, path: PathBuf) {
+        self.pending.insert(path);
+    }
+
+    /// 不再分析已移除的项目
+    pub fn remove(&mut self, path: &Path) {
+        self.pending.remove(path);
+        self.loading.remove(path);
+    }
+
+    /// 项目的 Git 信息是否还在等待或正在分析
+    pub fn is_waiting(&self, path: &Path) -> bool {
+        self.pending.contains(path) || self.loading.contains(path)
+    }
+
+    /// 取出接下来要分析的项目：`priority`（选中和可见的项目）中等待的项目全部取出；
+    /// 正在分析的项目少于 `background_limit` 时，再按 `background` 的顺序补足后台分析的项目
+    pub fn next_batch<'a>(
+        &mut self,
+        priority: impl IntoIterator<Item = &'a Path>,
+        background: impl IntoIterator<Item = &'a Path>,
+        background_limit: usize,
+    ) -> Vec<PathBuf> {
+        let mut batch = Vec::new();
+        for path in priority {
+            self.start(path, &mut batch);
+        }
+        for path in background {
+            if self.loading.len() >= background_limit || self.pending.is_empty() {
+                break;
+            }
+            self.start(path, &mut batch);
+        }
+        batch
+    }
+
+    /// 分析完成（分析期间再次加入队列的项目仍在等待，之后重新分析）
+    pub fn finish(&mut self, path: &Path) {
+        self.loading.remove(path);
+    }
+
+    fn start(&mut self, path: &Path, batch: &mut Vec<PathBuf>) {
+        if !self.loading.contains(path) && self.pending.remove(path) {
+            self.loading.insert(path.to_path_buf());
+            batch.push(path.to_path_buf());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_projects_first_then_background() {
+        let paths: Vec<PathBuf> = (0..6).map(|i| PathBuf::from(format!("/code/p{}", i))).collect();
+        let mut hydration = GitHydration::default();
+        for path in &paths {
+            hydration.queue(path.clone());
+        }
+
+        // 可见的项目全部取出，后台名额已被占满时不再补充
+        let batch = hydration.next_batch([paths[4].as_path(), paths[5].as_path()], paths.iter().map(PathBuf::as_path), 2);
+        assert_eq!(batch, [paths[4].clone(), paths[5].clone()]);
+        assert!(hydration.is_waiting(&paths[0]));
+
+        // 分析完成后按列表顺序在后台补全
+        hydration.finish(&paths[4]);
+        let batch = hydration.next_batch([], paths.iter().map(PathBuf::as_path), 2);
+        assert_eq!(batch, [paths[0].clone()]);
+        assert!(!hydration.is_waiting(&paths[4]));
+
+        // 分析期间再次加入队列的项目在完成后重新分析，已移除的项目不再分析
+        hydration.queue(paths[5].clone());
+        assert!(hydration.next_batch([paths[5].as_path()], [], 2).is_empty());
+        hydration.finish(&paths[5]);
+        assert!(hydration.is_waiting(&paths[5]));
+        hydration.remove(&paths[1]);
+        let batch = hydration.next_batch([], paths.iter().map(PathBuf::as_path), 3);
+        assert_eq!(batch, [paths[2].clone(), paths[3].clone()]);
+    }
+}
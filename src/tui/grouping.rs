@@ -0,0 +1,168 @@
+// 项目列表分组：项目归入路径最长（最具体）的工作区前缀或扫描根目录，分组可以折叠
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::models::{ByteSize, Project};
+
+/// 一个分组及其中可见项目的汇总
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectGroup {
+    /// 分组的路径前缀（None 表示不属于任何工作区或扫描根目录的项目）
+    pub key: Option<PathBuf>,
+
+    /// 组内项目的索引，按列表中的显示顺序
+    pub projects: Vec<usize>,
+
+    /// 代码总大小（不含已忽略的项目和工作区成员）
+    pub code_size: ByteSize,
+
+    /// 依赖总大小（不含已忽略的项目和工作区成员）
+    pub dependency_size: ByteSize,
+}
+
+impl ProjectGroup {
+    /// 代码与依赖的合计大小
+    pub fn total_size(&self) -> ByteSize {
+        self.code_size + self.dependency_size
+    }
+}
+
+/// 分组列表中的一行
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupRow {
+    /// 分组标题（分组在 `ProjectGroups::groups` 中的位置）
+    Header(usize),
+
+    /// 项目（项目索引）
+    Project(usize),
+}
+
+/// 按分组排列的可见项目
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectGroups {
+    /// 分组按其中第一个项目在列表中的位置排列
+    pub groups: Vec<ProjectGroup>,
+}
+
+impl ProjectGroups {
+    /// 将可见项目（按显示顺序排列的索引）分组
+    ///
+    /// 工作区前缀和扫描根目录一起参与匹配，项目归入其中路径最长的一个，因此工作区可以把扫描根目录再细分。
+    pub fn compute(projects: &[Project], visible: &[usize], scan_roots: &[String], workspaces: &[String]) -> Self {
+        let prefixes: Vec<PathBuf> = workspaces.iter().chain(scan_roots).map(PathBuf::from).collect();
+        let mut groups: Vec<ProjectGroup> = Vec::new();
+
+        for &index in visible {
+            let project = &projects[index];
+            let key = prefixes.iter()
+                .filter(|prefix| project.path.starts_with(prefix))
+                .max_by_key(|prefix| prefix.components().count())
+                .cloned();
+
+            let position = match groups.iter().position(|group| group.key == key) {
+                Some(position) => position,
+                None => {
+                    groups.push(ProjectGroup { key, projects: Vec::new(), code_size: ByteSize::ZERO, dependency_size: ByteSize::ZERO });
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[position];
+            group.projects.push(index);
+            if !project.is_ignored && !project.is_workspace_member() {
+                group.code_size += project.size();
+                group.dependency_size += project.dependency_size();
+            }
+        }
+
+        Self { groups }
+    }
+
+    /// 项目所在的分组
+    pub fn group_of(&self, project: usize) -> Option<&ProjectGroup> {
+        self.groups.iter().find(|group| group.projects.contains(&project))
+    }
+
+    /// 列表中显示的行：每个分组的标题，以及展开的分组中的项目
+    pub fn rows(&self, collapsed: &HashSet<Option<PathBuf>>) -> Vec<GroupRow> {
+        let mut rows = Vec::new();
+        for (position, group) in self.groups.iter().enumerate() {
+            rows.push(GroupRow::Header(position));
+            if !collapsed.contains(&group.key) {
+                rows.extend(group.projects.iter().map(|&index| GroupRow::Project(index)));
+            }
+        }
+        rows
+    }
+
+    /// 上下移动时依次经过的项目
+    ///
+    /// 展开的分组经过其中每个项目；折叠的分组只经过一次，由选中的项目（在组内时）或组内第一个项目代表，
+    /// 此时列表中高亮的是分组标题。
+    pub fn stops(&self, collapsed: &HashSet<Option<PathBuf>>, selected: usize) -> Vec<usize> {
+        let mut stops = Vec::new();
+        for group in &self.groups {
+            if !collapsed.contains(&group.key) {
+                stops.extend(&group.projects);
+            } else if group.projects.contains(&selected) {
+                stops.push(selected);
+            } else {
+                stops.extend(group.projects.first());
+            }
+        }
+        stops
+    }
+
+    /// 选中项目在 `rows` 中对应的行：项目所在分组折叠时为分组标题
+    pub fn selected_row(&self, rows: &[GroupRow], selected: usize) -> usize {
+        rows.iter()
+            .position(|row| *row == GroupRow::Project(selected))
+            .or_else(|| rows.iter().position(|row| {
+                matches!(*row, GroupRow::Header(position) if self.groups[position].projects.contains(&selected))
+            }))
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::ProjectFixture;
+
+    #[test]
+    fn test_group_by_workspace_and_scan_root() {
+        let roots = vec!["/code".to_string(), "/src".to_string()];
+        let workspaces = vec!["/code/work".to_string()];
+        let projects = vec![
+            ProjectFixture::new("/code/blog").sizes(10, 20).build(),
+            ProjectFixture::new("/code/work/api").sizes(100, 1000).build(),
+            ProjectFixture::new("/elsewhere/tool").sizes(5, 5).build(),
+            ProjectFixture::new("/code/work/web").sizes(50, 500).build(),
+            ProjectFixture::new("/code/work/archived").sizes(9999, 0).ignored().build(),
+        ];
+        let visible = [1, 0, 3, 2, 4];
+
+        // 工作区比扫描根目录更具体；分组按第一个项目的位置排列，组内保持列表顺序，已忽略的项目不计入大小
+        let groups = ProjectGroups::compute(&projects, &visible, &roots, &workspaces);
+        let keys: Vec<Option<PathBuf>> = groups.groups.iter().map(|group| group.key.clone()).collect();
+        assert_eq!(keys, [Some(PathBuf::from("/code/work")), Some(PathBuf::from("/code")), None]);
+        assert_eq!(groups.groups[0].projects, [1, 3, 4]);
+        assert_eq!(groups.groups[0].total_size(), ByteSize::new(1650));
+        assert_eq!(groups.group_of(0).unwrap().key, Some(PathBuf::from("/code")));
+
+        // 折叠的分组只显示标题，上下移动时只经过一次
+        let collapsed = HashSet::from([Some(PathBuf::from("/code/work"))]);
+        let rows = groups.rows(&collapsed);
+        assert_eq!(rows, [
+            GroupRow::Header(0),
+            GroupRow::Header(1),
+            GroupRow::Project(0),
+            GroupRow::Header(2),
+            GroupRow::Project(2),
+        ]);
+        assert_eq!(groups.stops(&collapsed, 0), [1, 0, 2]);
+        assert_eq!(groups.stops(&collapsed, 3), [3, 0, 2]);
+        assert_eq!(groups.selected_row(&rows, 3), 0);
+        assert_eq!(groups.selected_row(&rows, 0), 2);
+    }
+}
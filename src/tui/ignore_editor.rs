@@ -0,0 +1,192 @@
+// 忽略列表编辑界面的状态：按分组列出配置中的忽略目录名、忽略路径和手动忽略的项目，支持新增、修改和删除
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::config::settings::ProjectIgnoreConfig;
+
+/// 忽略列表的分组
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum IgnoreSection {
+    /// 忽略的目录名
+    #[default]
+    Directories,
+
+    /// 忽略的路径规则
+    Paths,
+
+    /// 手动忽略的项目
+    Projects,
+}
+
+impl IgnoreSection {
+    /// 界面中按此顺序显示
+    pub const ALL: [IgnoreSection; 3] = [IgnoreSection::Directories, IgnoreSection::Paths, IgnoreSection::Projects];
+
+    /// 分组标题
+    pub fn label(&self) -> &'static str {
+        match self {
+            IgnoreSection::Directories => "忽略目录名",
+            IgnoreSection::Paths => "忽略路径",
+            IgnoreSection::Projects => "忽略项目",
+        }
+    }
+
+    /// 下一个分组（循环）
+    pub fn next(&self) -> Self {
+        match self {
+            IgnoreSection::Directories => IgnoreSection::Paths,
+            IgnoreSection::Paths => IgnoreSection::Projects,
+            IgnoreSection::Projects => IgnoreSection::Directories,
+        }
+    }
+
+    /// 上一个分组（循环）
+    pub fn previous(&self) -> Self {
+        self.next().next()
+    }
+
+    /// 该分组在配置中对应的列表
+    pub fn entries<'a>(&self, ignore: &'a ProjectIgnoreConfig) -> &'a HashSet<String> {
+        match self {
+            IgnoreSection::Directories => &ignore.directories,
+            IgnoreSection::Paths => &ignore.paths,
+            IgnoreSection::Projects => &ignore.projects,
+        }
+    }
+
+    fn entries_mut<'a>(&self, ignore: &'a mut ProjectIgnoreConfig) -> &'a mut HashSet<String> {
+        match self {
+            IgnoreSection::Directories => &mut ignore.directories,
+            IgnoreSection::Paths => &mut ignore.paths,
+            IgnoreSection::Projects => &mut ignore.projects,
+        }
+    }
+}
+
+/// 正在输入的条目
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreInput {
+    /// 输入的内容
+    pub text: String,
+
+    /// 正在修改的原条目，新增时为 None
+    pub original: Option<String>,
+}
+
+/// 对忽略列表的一次修改
+#[derive(Debug, Clone, PartialEq)]
+pub struct IgnoreChange {
+    /// 修改的分组
+    pub section: IgnoreSection,
+
+    /// 移除的条目
+    pub removed: Option<String>,
+
+    /// 加入的条目
+    pub added: Option<String>,
+}
+
+impl IgnoreChange {
+    /// 将这次修改应用到另一份忽略配置上
+    pub fn apply_to(&self, ignore: &mut ProjectIgnoreConfig) {
+        let entries = self.section.entries_mut(ignore);
+        if let Some(removed) = &self.removed {
+            entries.remove(removed);
+        }
+        if let Some(added) = &self.added {
+            entries.insert(added.clone());
+        }
+    }
+}
+
+/// 忽略列表编辑界面的状态
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreEditor {
+    /// 当前分组
+    pub section: IgnoreSection,
+
+    /// 当前分组中选中的条目（按排序后的位置）
+    pub selected: usize,
+
+    /// 正在新增或修改的条目
+    pub input: Option<IgnoreInput>,
+}
+
+impl IgnoreEditor {
+    /// 当前分组的条目，按字母顺序排列
+    pub fn entries<'a>(&self, ignore: &'a ProjectIgnoreConfig) -> Vec<&'a String> {
+        let mut entries: Vec<&String> = self.section.entries(ignore).iter().collect();
+        entries.sort();
+        entries
+    }
+
+    /// 选中的条目
+    pub fn selected_entry(&self, ignore: &ProjectIgnoreConfig) -> Option<String> {
+        self.entries(ignore).get(self.selected).map(|entry| entry.to_string())
+    }
+
+    /// 切换分组
+    pub fn switch_section(&mut self, section: IgnoreSection) {
+        self.section = section;
+        self.selected = 0;
+    }
+
+    /// 移动选中的条目
+    pub fn move_selection(&mut self, delta: isize, ignore: &ProjectIgnoreConfig) {
+        let count = self.section.entries(ignore).len();
+        self.selected = self.selected.saturating_add_signed(delta).min(count.saturating_sub(1));
+    }
+
+    /// 开始输入新条目
+    pub fn start_add(&mut self) {
+        self.input = Some(IgnoreInput::default());
+    }
+
+    /// 开始修改选中的条目，没有条目时不做任何事
+    pub fn start_edit(&mut self, ignore: &ProjectIgnoreConfig) {
+        if let Some(entry) = self.selected_entry(ignore) {
+            self.input = Some(IgnoreInput { text: entry.clone(), original: Some(entry) });
+        }
+    }
+
+    /// 保存输入的条目并选中它；内容为空或已在列表中时返回错误，输入保持打开
+    pub fn commit_input(&mut self, ignore: &mut ProjectIgnoreConfig) -> Result<IgnoreChange> {
+        let Some(input) = &self.input else {
+            anyhow::bail!("没有正在输入的条目");
+        };
+        let mut text = input.text.trim().to_string();
+        if text.is_empty() {
+            anyhow::bail!("条目不能为空");
+        }
+        // 路径中开头的 ~ 展开为用户主目录，与命令行 `config ignore` 一致
+        if self.section != IgnoreSection::Directories {
+            if let (Some(rest), Some(home)) = (text.strip_prefix("~/"), dirs::home_dir()) {
+                text = home.join(rest).display().to_string();
+            }
+        }
+
+        let original = input.original.clone();
+        let entries = self.section.entries_mut(ignore);
+        if original.as_deref() != Some(text.as_str()) && entries.contains(&text) {
+            anyhow::bail!("{} 已在列表中", text);
+        }
+        if let Some(original) = &original {
+            entries.remove(original);
+        }
+        entries.insert(text.clone());
+
+        self.input = None;
+        self.selected = self.entries(ignore).iter().position(|entry| **entry == text).unwrap_or(0);
+        Ok(IgnoreChange { section: self.section, removed: original, added: Some(text) })
+    }
+
+    /// 删除选中的条目
+    pub fn remove_selected(&mut self, ignore: &mut ProjectIgnoreConfig) -> Option<IgnoreChange> {
+        let entry = self.selected_entry(ignore)?;
+        self.section.entries_mut(ignore).remove(&entry);
+        self.move_selection(0, ignore);
+        Some(IgnoreChange { section: self.section, removed: Some(entry), added: None })
+    }
+}
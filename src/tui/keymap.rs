@@ -0,0 +1,275 @@
+// 快捷键注册表：帮助页面由此生成
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::events::keys;
+
+/// 快捷键生效的界面
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyContext {
+    /// 所有界面
+    Global,
+
+    /// 项目列表
+    ProjectList,
+
+    /// Git 状态标签页
+    GitStatus,
+
+    /// 项目详情
+    ProjectDetail,
+
+    /// 分支列表
+    BranchList,
+
+    /// 确认对话框
+    ConfirmDialog,
+
+    /// 清理向导
+    CleanupWizard,
+
+    /// 退出确认（有操作进行中时）
+    ConfirmQuit,
+
+    /// 忽略列表
+    IgnoreList,
+
+    /// 后台任务面板
+    TaskList,
+
+    /// 磁盘占用明细
+    DiskUsage,
+
+    /// 帮助页面
+    Help,
+}
+
+impl KeyContext {
+    /// 帮助页面中按此顺序分组显示
+    pub const ALL: [KeyContext; 12] = [
+        KeyContext::Global,
+        KeyContext::ProjectList,
+        KeyContext::GitStatus,
+        KeyContext::ProjectDetail,
+        KeyContext::BranchList,
+        KeyContext::DiskUsage,
+        KeyContext::ConfirmDialog,
+        KeyContext::CleanupWizard,
+        KeyContext::ConfirmQuit,
+        KeyContext::IgnoreList,
+        KeyContext::TaskList,
+        KeyContext::Help,
+    ];
+
+    /// 分组标题
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyContext::Global => "全局",
+            KeyContext::ProjectList => "项目列表",
+            KeyContext::GitStatus => "Git 状态",
+            KeyContext::ProjectDetail => "项目详情",
+            KeyContext::BranchList => "分支列表",
+            KeyContext::DiskUsage => "磁盘占用",
+            KeyContext::ConfirmDialog => "确认对话框",
+            KeyContext::CleanupWizard => "清理向导",
+            KeyContext::ConfirmQuit => "退出确认",
+            KeyContext::IgnoreList => "忽略列表",
+            KeyContext::TaskList => "后台任务",
+            KeyContext::Help => "帮助",
+        }
+    }
+}
+
+/// 一条快捷键绑定
+pub struct KeyBinding {
+    /// 生效的界面
+    pub context: KeyContext,
+
+    /// 按键的显示文本，多个按键以 ", " 分隔，成对的按键以 "/" 分隔
+    pub keys: &'static str,
+
+    /// 功能说明
+    pub description: &'static str,
+
+    /// 判断按键是否触发该绑定，与事件处理使用同一组判断函数（测试据此校验显示的按键）
+    #[allow(dead_code)]
+    pub matches: fn(&KeyEvent) -> bool,
+}
+
+impl KeyBinding {
+    /// 是否匹配搜索关键字（按键、说明或分组标题，不区分大小写）
+    pub fn matches_query(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [self.keys, self.description, self.context.label()]
+            .iter()
+            .any(|text| text.to_lowercase().contains(&query))
+    }
+}
+
+/// 全部快捷键绑定
+pub const KEY_BINDINGS: &[KeyBinding] = &[
+    KeyBinding { context: KeyContext::Global, keys: "q, Ctrl+C", description: "退出应用程序", matches: keys::is_quit_key },
+
+    KeyBinding { context: KeyContext::ProjectList, keys: "↑/↓, k/j", description: "导航项目列表", matches: |key| keys::is_up_key(key) || keys::is_down_key(key) },
+    KeyBinding { context: KeyContext::ProjectList, keys: "Enter, Space", description: "查看项目详情", matches: keys::is_enter_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "r, F5", description: "刷新项目列表（只重新检测有变化的项目）", matches: keys::is_refresh_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "h, ?, F1", description: "显示帮助信息", matches: keys::is_help_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "Tab", description: "切换视图标签", matches: keys::is_tab_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "v", description: "切换紧凑/舒适行模式", matches: keys::is_density_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "a", description: "显示/隐藏代码、依赖和 gitignore 排除部分的占比列", matches: keys::is_ratio_bar_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "m", description: "标记/取消标记项目（统计仅包含标记项目）", matches: keys::is_mark_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "/", description: "搜索项目（按名称、路径或类型模糊匹配，Esc 清除）", matches: keys::is_search_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "Esc", description: "取消选中项目正在进行的大小计算（有搜索时先清除搜索）", matches: |key| key.code == KeyCode::Esc },
+    KeyBinding { context: KeyContext::ProjectList, keys: "s", description: "切换排序字段（名称、代码大小、总大小、依赖大小、最后修改、类型）", matches: keys::is_sort_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "S", description: "切换升序/降序", matches: keys::is_sort_order_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "G", description: "按工作区/扫描根目录分组显示，显示组内大小合计", matches: keys::is_group_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "←/→", description: "在项目下方展开/收起依赖目录；选中的工作区或分组已折叠时 → 先展开，未展开依赖时 ← 折叠工作区成员或分组", matches: |key| matches!(key.code, KeyCode::Left | KeyCode::Right) },
+    KeyBinding { context: KeyContext::ProjectList, keys: "f", description: "按选中项目的父目录过滤/清除过滤", matches: keys::is_filter_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "z", description: "只显示长期未活动的项目（display.stale_project_days），按依赖大小排序，便于决定归档或删除", matches: keys::is_stale_filter_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "y", description: "复制项目路径（SSH 下使用 OSC 52）", matches: keys::is_yank_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "x", description: "隐藏/取消隐藏项目（仍参与统计）", matches: keys::is_hide_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "t", description: "为标记的项目（未标记时为选中项目）添加标签，都已有该标签时移除", matches: keys::is_tag_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "X", description: "导出当前列表（按过滤和排序）为 JSON、CSV 或 Markdown 文件", matches: keys::is_export_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: ".", description: "显示/不显示隐藏项目", matches: keys::is_show_hidden_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "d, Delete", description: "删除项目", matches: keys::is_delete_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "c", description: "清理项目依赖（先预览将删除的目录和大小）", matches: keys::is_clean_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "w", description: "清理向导（逐个处理最值得清理的项目）", matches: keys::is_wizard_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "i", description: "忽略/取消忽略项目（保存到配置，重新扫描时跳过）", matches: keys::is_ignore_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "g", description: "显示/不显示已忽略的项目", matches: keys::is_show_ignored_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "o", description: "后台任务面板（查看进度、取消任务）", matches: keys::is_task_list_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "I", description: "编辑忽略列表（修改后立即重新过滤项目列表）", matches: keys::is_ignore_list_key },
+    KeyBinding { context: KeyContext::ProjectList, keys: "e", description: "用编辑器打开项目（按项目类型选择）", matches: keys::is_edit_key },
+
+    KeyBinding { context: KeyContext::GitStatus, keys: "f", description: "git fetch 选中的仓库，显示相对 origin 的领先/落后提交数", matches: keys::is_fetch_key },
+    KeyBinding { context: KeyContext::GitStatus, keys: "F", description: "git fetch 全部仓库（限制并发数，状态栏显示进度）", matches: keys::is_fetch_all_key },
+
+    KeyBinding { context: KeyContext::ProjectDetail, keys: "Enter, Backspace", description: "返回项目列表", matches: |key| keys::is_enter_key(key) || key.code == KeyCode::Backspace },
+    KeyBinding { context: KeyContext::ProjectDetail, keys: "↑/↓, k/j", description: "选择清理建议", matches: |key| keys::is_up_key(key) || keys::is_down_key(key) },
+    KeyBinding { context: KeyContext::ProjectDetail, keys: "y", description: "接受清理建议（确认后删除目录）", matches: keys::is_yank_key },
+    KeyBinding { context: KeyContext::ProjectDetail, keys: "n", description: "拒绝清理建议", matches: |key| matches!(key.code, KeyCode::Char('n') | KeyCode::Char('N')) },
+    KeyBinding { context: KeyContext::ProjectDetail, keys: "b", description: "查看分支列表", matches: keys::is_branch_key },
+    KeyBinding { context: KeyContext::ProjectDetail, keys: "PageUp/PageDown", description: "滚动提交记录", matches: keys::is_page_key },
+    KeyBinding { context: KeyContext::ProjectDetail, keys: "u", description: "查看磁盘占用明细（最大的子目录和文件）", matches: keys::is_disk_usage_key },
+    KeyBinding { context: KeyContext::ProjectDetail, keys: "p", description: "复制项目报告（Markdown：大小、Git 状态、依赖和清理建议）", matches: keys::is_report_key },
+    KeyBinding { context: KeyContext::ProjectDetail, keys: "P", description: "保存项目报告到当前目录的 <项目名>-report.md", matches: keys::is_save_report_key },
+
+    KeyBinding { context: KeyContext::DiskUsage, keys: "↑/↓, k/j", description: "选择子目录或文件", matches: |key| keys::is_up_key(key) || keys::is_down_key(key) },
+    KeyBinding { context: KeyContext::DiskUsage, keys: "→, Enter", description: "进入选中的目录", matches: |key| matches!(key.code, KeyCode::Right | KeyCode::Enter) },
+    KeyBinding { context: KeyContext::DiskUsage, keys: "←, Backspace", description: "返回上级目录", matches: |key| matches!(key.code, KeyCode::Left | KeyCode::Backspace) },
+    KeyBinding { context: KeyContext::DiskUsage, keys: "Esc", description: "返回项目详情", matches: |key| key.code == KeyCode::Esc },
+
+    KeyBinding { context: KeyContext::BranchList, keys: "↑/↓, k/j", description: "选择分支", matches: |key| keys::is_up_key(key) || keys::is_down_key(key) },
+    KeyBinding { context: KeyContext::BranchList, keys: "d, Delete", description: "删除已合并分支", matches: keys::is_delete_key },
+    KeyBinding { context: KeyContext::BranchList, keys: "Esc, Backspace", description: "返回项目详情", matches: |key| matches!(key.code, KeyCode::Esc | KeyCode::Backspace) },
+
+    KeyBinding { context: KeyContext::ConfirmDialog, keys: "y", description: "确认（项目有未保存的工作时需按大写 Y）", matches: |key| matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) },
+    KeyBinding { context: KeyContext::ConfirmDialog, keys: "n, Esc", description: "取消（除 y 以外的任意键）", matches: |key| !matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) },
+
+    KeyBinding { context: KeyContext::CleanupWizard, keys: "c", description: "清理依赖目录", matches: |key| matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C')) },
+    KeyBinding { context: KeyContext::CleanupWizard, keys: "s", description: "跳过", matches: |key| matches!(key.code, KeyCode::Char('s') | KeyCode::Char('S')) },
+    KeyBinding { context: KeyContext::CleanupWizard, keys: "a", description: "清理后移动到归档目录", matches: |key| matches!(key.code, KeyCode::Char('a') | KeyCode::Char('A')) },
+    KeyBinding { context: KeyContext::CleanupWizard, keys: "d", description: "删除到回收站（项目有未保存的工作时需按大写 D）", matches: |key| matches!(key.code, KeyCode::Char('d') | KeyCode::Char('D')) },
+    KeyBinding { context: KeyContext::CleanupWizard, keys: "o", description: "归档目录中已有同名项目时覆盖（已有目录移到回收站）", matches: |key| matches!(key.code, KeyCode::Char('o') | KeyCode::Char('O')) },
+    KeyBinding { context: KeyContext::CleanupWizard, keys: "r", description: "归档目录中已有同名项目时重命名（加数字后缀）", matches: |key| matches!(key.code, KeyCode::Char('r') | KeyCode::Char('R')) },
+    KeyBinding { context: KeyContext::CleanupWizard, keys: "Esc", description: "结束向导", matches: |key| key.code == KeyCode::Esc },
+
+    KeyBinding { context: KeyContext::ConfirmQuit, keys: "w, Enter", description: "等待进行中的操作完成后退出，并输出结果", matches: |key| matches!(key.code, KeyCode::Char('w') | KeyCode::Char('W') | KeyCode::Enter) },
+    KeyBinding { context: KeyContext::ConfirmQuit, keys: "c", description: "取消进行中的操作并退出", matches: |key| matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C')) },
+    KeyBinding { context: KeyContext::ConfirmQuit, keys: "q", description: "不等待，仍然退出", matches: keys::is_quit_key },
+    KeyBinding { context: KeyContext::ConfirmQuit, keys: "Esc", description: "返回，不退出", matches: |key| key.code == KeyCode::Esc },
+
+    KeyBinding { context: KeyContext::IgnoreList, keys: "Tab, ←/→", description: "切换分组（忽略目录名、忽略路径、忽略项目）", matches: |key| keys::is_tab_key(key) || matches!(key.code, KeyCode::Left | KeyCode::Right) },
+    KeyBinding { context: KeyContext::IgnoreList, keys: "↑/↓, k/j", description: "选择条目", matches: |key| keys::is_up_key(key) || keys::is_down_key(key) },
+    KeyBinding { context: KeyContext::IgnoreList, keys: "a", description: "新增条目", matches: |key| key.code == KeyCode::Char('a') },
+    KeyBinding { context: KeyContext::IgnoreList, keys: "e, Enter", description: "修改选中的条目（Enter 保存，Esc 取消）", matches: |key| keys::is_edit_key(key) || key.code == KeyCode::Enter },
+    KeyBinding { context: KeyContext::IgnoreList, keys: "d, Delete", description: "删除选中的条目", matches: keys::is_delete_key },
+    KeyBinding { context: KeyContext::IgnoreList, keys: "Esc", description: "返回项目列表", matches: |key| key.code == KeyCode::Esc },
+
+    KeyBinding { context: KeyContext::TaskList, keys: "↑/↓, k/j", description: "选择任务", matches: |key| keys::is_up_key(key) || keys::is_down_key(key) },
+    KeyBinding { context: KeyContext::TaskList, keys: "c, Delete", description: "取消选中的任务", matches: |key| keys::is_clean_key(key) || key.code == KeyCode::Delete },
+    KeyBinding { context: KeyContext::TaskList, keys: "Esc, o", description: "返回项目列表", matches: |key| key.code == KeyCode::Esc || keys::is_task_list_key(key) },
+
+    KeyBinding { context: KeyContext::Help, keys: "/", description: "搜索快捷键", matches: keys::is_search_key },
+    KeyBinding { context: KeyContext::Help, keys: "↑/↓, k/j", description: "滚动", matches: |key| keys::is_up_key(key) || keys::is_down_key(key) },
+    KeyBinding { context: KeyContext::Help, keys: "PageUp/PageDown", description: "翻页", matches: keys::is_page_key },
+    KeyBinding { context: KeyContext::Help, keys: "Esc", description: "清除搜索，没有搜索时返回项目列表", matches: |key| key.code == KeyCode::Esc },
+    KeyBinding { context: KeyContext::Help, keys: "Enter, h", description: "返回项目列表", matches: |key| keys::is_enter_key(key) || keys::is_help_key(key) },
+];
+
+/// 按分组顺序返回匹配搜索关键字的绑定（关键字为空时返回全部）
+pub fn search(query: &str) -> Vec<(KeyContext, Vec<&'static KeyBinding>)> {
+    let query = query.trim();
+    KeyContext::ALL.iter()
+        .map(|context| {
+            let bindings: Vec<_> = KEY_BINDINGS.iter()
+                .filter(|binding| binding.context == *context)
+                .filter(|binding| query.is_empty() || binding.matches_query(query))
+                .collect();
+            (*context, bindings)
+        })
+        .filter(|(_, bindings)| !bindings.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    /// 将显示文本中的单个按键解析为按键事件
+    fn parse_key(text: &str) -> KeyEvent {
+        let (code, modifiers) = match text {
+            "↑" => (KeyCode::Up, KeyModifiers::NONE),
+            "↓" => (KeyCode::Down, KeyModifiers::NONE),
+            "←" => (KeyCode::Left, KeyModifiers::NONE),
+            "→" => (KeyCode::Right, KeyModifiers::NONE),
+            "Enter" => (KeyCode::Enter, KeyModifiers::NONE),
+            "Space" => (KeyCode::Char(' '), KeyModifiers::NONE),
+            "Tab" => (KeyCode::Tab, KeyModifiers::NONE),
+            "Esc" => (KeyCode::Esc, KeyModifiers::NONE),
+            "Backspace" => (KeyCode::Backspace, KeyModifiers::NONE),
+            "Delete" => (KeyCode::Delete, KeyModifiers::NONE),
+            "PageUp" => (KeyCode::PageUp, KeyModifiers::NONE),
+            "PageDown" => (KeyCode::PageDown, KeyModifiers::NONE),
+            "F1" => (KeyCode::F(1), KeyModifiers::NONE),
+            "F5" => (KeyCode::F(5), KeyModifiers::NONE),
+            _ => match text.strip_prefix("Ctrl+") {
+                Some(key) => (KeyCode::Char(key.to_lowercase().chars().next().unwrap()), KeyModifiers::CONTROL),
+                None => {
+                    let mut chars = text.chars();
+                    let c = chars.next().unwrap();
+                    assert!(chars.next().is_none(), "无法解析的按键: {}", text);
+                    (KeyCode::Char(c), KeyModifiers::NONE)
+                }
+            },
+        };
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_displayed_keys_trigger_their_binding() {
+        for binding in KEY_BINDINGS {
+            for text in binding.keys.split(", ").flat_map(|keys| {
+                // "/" 本身也是一个按键
+                if keys == "/" { vec![keys] } else { keys.split('/').collect() }
+            }) {
+                assert!(
+                    (binding.matches)(&parse_key(text)),
+                    "{} 的按键 {} 与实际处理不一致",
+                    binding.description,
+                    text
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_groups_by_context() {
+        let results = search("清理");
+        let contexts: Vec<_> = results.iter().map(|(context, _)| *context).collect();
+        assert_eq!(contexts, [KeyContext::ProjectList, KeyContext::ProjectDetail, KeyContext::CleanupWizard]);
+
+        assert_eq!(search("").len(), KeyContext::ALL.len());
+        assert!(search("不存在的功能").is_empty());
+    }
+}
@@ -1,5 +1,17 @@
 pub mod app;
 pub mod events;
+pub mod expansion;
+pub mod filter;
+pub mod git_hydration;
+pub mod grouping;
+pub mod ignore_editor;
+pub mod keymap;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod ratio_bar;
+pub mod rollup;
+pub mod session;
+pub mod tasks;
 pub mod components;
 pub mod screens;
 
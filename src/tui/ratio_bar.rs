@@ -0,0 +1,97 @@
+// 项目行中的占比条：代码、依赖和 gitignore 排除部分占磁盘总占用的比例，一眼看出哪些仓库主要是构建产物
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+use crate::models::{ByteSize, Project};
+
+/// 占比条的字符宽度
+pub const WIDTH: usize = 10;
+
+/// 代码、依赖、gitignore 排除部分的颜色，顺序与 `segments` 一致
+const COLORS: [Color; 3] = [Color::Green, Color::Yellow, Color::DarkGray];
+
+/// 把宽度按大小比例分给各部分（最大余数法），各部分宽度之和等于 `width`；总大小为 0 时都为 0
+///
+/// 非零的部分至少占一格，避免很小的依赖目录在条中完全看不到。
+pub fn segments(sizes: [ByteSize; 3], width: usize) -> [usize; 3] {
+    let total: u128 = sizes.iter().map(|size| size.bytes() as u128).sum();
+    if total == 0 || width == 0 {
+        return [0; 3];
+    }
+
+    let mut cells = [0usize; 3];
+    let mut remainders = [0u128; 3];
+    for (i, size) in sizes.iter().enumerate() {
+        let scaled = size.bytes() as u128 * width as u128;
+        cells[i] = (scaled / total) as usize;
+        remainders[i] = scaled % total;
+    }
+
+    let mut order = [0, 1, 2];
+    order.sort_by(|&a, &b| remainders[b].cmp(&remainders[a]));
+    let mut left = width - cells.iter().sum::<usize>();
+    for &i in &order {
+        if left == 0 {
+            break;
+        }
+        if remainders[i] > 0 {
+            cells[i] += 1;
+            left -= 1;
+        }
+    }
+
+    // 非零部分至少一格，从最宽的部分中让出
+    for i in 0..3 {
+        if cells[i] == 0 && !sizes[i].is_zero() {
+            let widest = (0..3).max_by_key(|&j| cells[j]).unwrap_or(0);
+            if cells[widest] > 1 {
+                cells[widest] -= 1;
+                cells[i] = 1;
+            }
+        }
+    }
+    cells
+}
+
+/// 按代码、依赖、gitignore 排除部分的大小绘制占比条，总大小为 0 时显示 "-"
+pub fn line(code: ByteSize, dependencies: ByteSize, ignored: ByteSize) -> Line<'static> {
+    let cells = segments([code, dependencies, ignored], WIDTH);
+    if cells.iter().all(|&cell| cell == 0) {
+        return Line::from("-");
+    }
+    Line::from(
+        cells.iter().zip(COLORS)
+            .filter(|(&cell, _)| cell > 0)
+            .map(|(&cell, color)| Span::styled("█".repeat(cell), Style::default().fg(color)))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// 项目的占比条
+pub fn project_line(project: &Project) -> Line<'static> {
+    line(project.size(), project.dependency_size(), project.gitignore_excluded_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sizes(code: u64, dependencies: u64, ignored: u64) -> [ByteSize; 3] {
+        [ByteSize::new(code), ByteSize::new(dependencies), ByteSize::new(ignored)]
+    }
+
+    #[test]
+    fn test_ratio_segments() {
+        assert_eq!(segments(sizes(0, 0, 0), WIDTH), [0, 0, 0]);
+        assert_eq!(segments(sizes(100, 0, 0), WIDTH), [10, 0, 0]);
+        assert_eq!(segments(sizes(25, 50, 25), WIDTH), [3, 5, 2]);
+        assert_eq!(segments(sizes(1, 1, 1), WIDTH), [4, 3, 3]);
+        // 很小的依赖目录也至少占一格
+        assert_eq!(segments(sizes(1, 10_000, 0), WIDTH), [1, 9, 0]);
+        assert_eq!(line(ByteSize::ZERO, ByteSize::ZERO, ByteSize::ZERO).to_string(), "-");
+        assert_eq!(line(ByteSize::new(30), ByteSize::new(70), ByteSize::ZERO).to_string(), "██████████");
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::models::{ByteSize, Project};
+
+/// 单个扫描根目录下的项目汇总
+#[derive(Debug, Clone, PartialEq)]
+pub struct RootRollup {
+    /// 扫描根目录（None 表示不属于任何已配置根目录的项目）
+    pub root: Option<PathBuf>,
+
+    /// 根目录下的项目数量
+    pub project_count: usize,
+
+    /// 代码总大小
+    pub code_size: ByteSize,
+
+    /// 依赖总大小
+    pub dependency_size: ByteSize,
+}
+
+impl RootRollup {
+    fn new(root: Option<PathBuf>) -> Self {
+        Self {
+            root,
+            project_count: 0,
+            code_size: ByteSize::ZERO,
+            dependency_size: ByteSize::ZERO,
+        }
+    }
+
+    /// 代码与依赖的合计大小
+    pub fn total_size(&self) -> ByteSize {
+        self.code_size + self.dependency_size
+    }
+
+    /// 按扫描根目录汇总项目，结果按合计大小降序排列
+    ///
+    /// 嵌套的根目录中，项目归属于路径最长（最具体）的根目录；被忽略的项目不计入，
+    /// 工作区成员只计入项目数（大小已包含在根项目中）。
+    pub fn compute(scan_roots: &[String], projects: &[Project]) -> Vec<Self> {
+        let mut rollups: Vec<Self> = scan_roots.iter()
+            .map(|root| Self::new(Some(PathBuf::from(root))))
+            .collect();
+        let mut unassigned = Self::new(None);
+
+        for project in projects.iter().filter(|p| !p.is_ignored) {
+            let rollup = rollups.iter_mut()
+                .filter(|rollup| rollup.contains(&project.path))
+                .max_by_key(|rollup| rollup.root.as_ref().map_or(0, |root| root.components().count()))
+                .unwrap_or(&mut unassigned);
+
+            rollup.project_count += 1;
+            if !project.is_workspace_member() {
+                rollup.code_size += project.size();
+                rollup.dependency_size += project.dependency_size();
+            }
+        }
+
+        if unassigned.project_count > 0 {
+            rollups.push(unassigned);
+        }
+
+        rollups.sort_by_key(|rollup| std::cmp::Reverse(rollup.total_size()));
+        rollups
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.root.as_ref().is_some_and(|root| path.starts_with(root))
+    }
+}
+
+/// 当前列出的项目的合计，固定显示在项目列表底部
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListTotals {
+    /// 列出的项目数量
+    pub project_count: usize,
+
+    /// 代码总大小
+    pub code_size: ByteSize,
+
+    /// 依赖总大小
+    pub dependency_size: ByteSize,
+
+    /// 可通过清理依赖释放的估算大小（不含已忽略的项目）
+    pub reclaimable: ByteSize,
+}
+
+impl ListTotals {
+    /// 汇总列出的项目；根项目也在列表中的工作区成员只计入项目数，避免重复计算大小
+    pub fn compute(projects: &[&Project]) -> Self {
+        let listed: HashSet<&Path> = projects.iter().map(|p| p.path.as_path()).collect();
+        let mut totals = Self {
+            project_count: projects.len(),
+            ..Self::default()
+        };
+
+        for project in projects {
+            if project.workspace_root.as_deref().is_some_and(|root| listed.contains(root)) {
+                continue;
+            }
+            totals.code_size += project.size();
+            totals.dependency_size += project.dependency_size();
+            if !project.is_ignored {
+                totals.reclaimable += project.dependency_size();
+            }
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::ProjectFixture;
+
+    fn project(path: &str, code_size: u64, dependency_size: u64) -> Project {
+        ProjectFixture::new(path).sizes(code_size, dependency_size).build()
+    }
+
+    #[test]
+    fn test_rollup_by_scan_root() {
+        let roots = vec!["/code".to_string(), "/code/work".to_string(), "/empty".to_string()];
+        let projects = vec![
+            project("/code/work/api", 100, 1000),
+            project("/code/work/web", 50, 500),
+            project("/code/blog", 10, 20),
+            project("/elsewhere/tool", 5, 5),
+            ProjectFixture::new("/code/work/archived").sizes(9999, 0).ignored().build(),
+        ];
+
+        let rollups = RootRollup::compute(&roots, &projects);
+        assert_eq!(rollups.len(), 4);
+
+        // 嵌套根目录下的项目只计入最具体的根目录，并按合计大小排序
+        assert_eq!(rollups[0].root, Some(PathBuf::from("/code/work")));
+        assert_eq!(rollups[0].project_count, 2);
+        assert_eq!(rollups[0].code_size, ByteSize::new(150));
+        assert_eq!(rollups[0].dependency_size, ByteSize::new(1500));
+
+        assert_eq!(rollups[1].root, Some(PathBuf::from("/code")));
+        assert_eq!(rollups[1].project_count, 1);
+
+        assert_eq!(rollups[2].root, None);
+        assert_eq!(rollups[2].total_size(), ByteSize::new(10));
+
+        assert_eq!(rollups[3].root, Some(PathBuf::from("/empty")));
+        assert_eq!(rollups[3].project_count, 0);
+    }
+
+    #[test]
+    fn test_list_totals_skip_members_of_listed_roots() {
+        let root = ProjectFixture::new("/code/mono").sizes(300, 3000).build();
+        let member = ProjectFixture::new("/code/mono/app").sizes(100, 1000).workspace_member("/code/mono").build();
+        let orphan = ProjectFixture::new("/code/other/lib").sizes(40, 400).workspace_member("/code/other").build();
+        let ignored = ProjectFixture::new("/code/old").sizes(7, 70).ignored().build();
+
+        let totals = ListTotals::compute(&[&root, &member, &orphan, &ignored]);
+        assert_eq!(totals.project_count, 4);
+        assert_eq!(totals.code_size, ByteSize::new(347));
+        assert_eq!(totals.dependency_size, ByteSize::new(3470));
+        assert_eq!(totals.reclaimable, ByteSize::new(3400));
+
+        // 根项目被过滤掉时，成员自身的大小计入合计
+        let totals = ListTotals::compute(&[&member]);
+        assert_eq!((totals.code_size, totals.reclaimable), (ByteSize::new(100), ByteSize::new(1000)));
+        assert_eq!(ListTotals::compute(&[]), ListTotals::default());
+    }
+}
@@ -0,0 +1,210 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::scanner::SizeTreeNode;
+use crate::utils::size_format;
+
+/// 占比条的宽度（字符）
+const BAR_WIDTH: usize = 20;
+
+/// 磁盘占用视图：逐级查看项目中最大的子目录和文件
+pub struct DiskUsageView {
+    /// 项目根目录的大小树
+    root: SizeTreeNode,
+
+    /// 从根目录进入当前目录经过的子项位置
+    stack: Vec<usize>,
+
+    /// 当前目录中选中的子项
+    selected: usize,
+}
+
+impl DiskUsageView {
+    pub fn new(root: SizeTreeNode) -> Self {
+        Self { root, stack: Vec::new(), selected: 0 }
+    }
+
+    /// 当前所在的目录
+    pub fn current(&self) -> &SizeTreeNode {
+        self.stack.iter().fold(&self.root, |node, &index| &node.children[index])
+    }
+
+    /// 当前目录中选中的子项
+    pub fn selected(&self) -> Option<&SizeTreeNode> {
+        self.current().children.get(self.selected)
+    }
+
+    /// 上下移动选中位置
+    pub fn move_selection(&mut self, delta: isize) {
+        let count = self.current().children.len();
+        if count > 0 {
+            self.selected = self.selected.saturating_add_signed(delta).min(count - 1);
+        }
+    }
+
+    /// 进入选中的目录，没有可显示的子项时返回 false
+    pub fn enter(&mut self) -> bool {
+        let can_enter = self.selected().is_some_and(|node| node.is_dir && !node.children.is_empty());
+        if can_enter {
+            self.stack.push(self.selected);
+            self.selected = 0;
+        }
+        can_enter
+    }
+
+    /// 返回上级目录并选中刚才所在的目录，已在根目录时返回 false
+    pub fn leave(&mut self) -> bool {
+        match self.stack.pop() {
+            Some(index) => {
+                self.selected = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 绘制当前目录的子项列表
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // 子项列表
+                Constraint::Length(3), // 操作提示
+            ])
+            .split(area);
+
+        let current = self.current();
+        let total = current.size.max(1);
+        let mut rows: Vec<Row> = current.children.iter().map(|node| {
+            let share = node.size as f64 / total as f64;
+            let (icon, name) = if node.is_dir {
+                ("📁", format!("{}/", node.name()))
+            } else {
+                ("📄", node.name())
+            };
+            let style = if node.is_dependency {
+                Style::default().fg(Color::Yellow)
+            } else if node.is_dir {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default()
+            };
+
+            Row::new(vec![
+                Cell::from(size_format::format_size(node.size)).style(Style::default().fg(Color::Green)),
+                Cell::from(format!("{:>5.1}%", share * 100.0)),
+                Cell::from(share_bar(share)).style(Style::default().fg(Color::Blue)),
+                Cell::from(format!("{} {}", icon, name)).style(style),
+            ])
+        }).collect();
+
+        if current.other_count > 0 {
+            rows.push(Row::new(vec![
+                Cell::from(size_format::format_size(current.other_size)),
+                Cell::from(format!("{:>5.1}%", current.other_size as f64 / total as f64 * 100.0)),
+                Cell::from(""),
+                Cell::from(format!("   其余 {} 项", current.other_count)),
+            ]).style(Style::default().fg(Color::DarkGray)));
+        }
+
+        let relative = current.path.strip_prefix(&self.root.path)
+            .ok()
+            .filter(|relative| !relative.as_os_str().is_empty())
+            .map(|relative| format!("/{}", relative.display()))
+            .unwrap_or_default();
+        let title = format!(
+            "磁盘占用: {}{} (共 {}，{} 个文件)",
+            self.root.name(),
+            relative,
+            size_format::format_size(current.size),
+            current.file_count
+        );
+
+        let table = Table::new(rows, [
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(BAR_WIDTH as u16),
+            Constraint::Min(20),
+        ])
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .row_highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+        let mut table_state = TableState::default();
+        if !current.children.is_empty() {
+            table_state.select(Some(self.selected));
+        }
+        f.render_stateful_widget(table, chunks[0], &mut table_state);
+
+        let help_text = Line::from(vec![
+            Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 选择 | "),
+            Span::styled("→/Enter", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 进入目录 | "),
+            Span::styled("←/Backspace", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 返回上级 | "),
+            Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 返回详情"),
+        ]);
+
+        let help_paragraph = Paragraph::new(help_text)
+            .block(Block::default().title("操作").borders(Borders::ALL).style(Style::default().fg(Color::Green)));
+
+        f.render_widget(help_paragraph, chunks[1]);
+    }
+}
+
+/// 按占比填充的条形图
+fn share_bar(share: f64) -> String {
+    let filled = ((share * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+    format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn node(path: &str, size: u64, children: Vec<SizeTreeNode>) -> SizeTreeNode {
+        SizeTreeNode {
+            path: PathBuf::from(path),
+            size,
+            is_dir: !children.is_empty(),
+            children,
+            ..SizeTreeNode::default()
+        }
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut view = DiskUsageView::new(node("/code/app", 1000, vec![
+            node("/code/app/target", 800, vec![node("/code/app/target/debug", 800, vec![node("/code/app/target/debug/app", 800, vec![])])]),
+            node("/code/app/src", 150, vec![node("/code/app/src/main.rs", 150, vec![])]),
+            node("/code/app/Cargo.lock", 50, vec![]),
+        ]));
+
+        view.move_selection(5);
+        assert_eq!(view.selected().unwrap().name(), "Cargo.lock");
+        assert!(!view.enter());
+
+        view.move_selection(-1);
+        assert!(view.enter());
+        assert_eq!(view.current().name(), "src");
+        assert_eq!(view.selected().unwrap().name(), "main.rs");
+
+        // 返回上级时选中刚才所在的目录
+        assert!(view.leave());
+        assert_eq!(view.selected().unwrap().name(), "src");
+        assert!(!view.leave());
+    }
+
+    #[test]
+    fn test_share_bar() {
+        assert_eq!(share_bar(0.5), format!("{}{}", "█".repeat(10), "░".repeat(10)));
+        assert_eq!(share_bar(1.0), "█".repeat(BAR_WIDTH));
+    }
+}
@@ -1,20 +1,246 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table, TableState, Tabs},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState, Tabs},
     Frame,
 };
 
-use crate::models::Project;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::config::settings::{IgnoreSource, RowDensity, SortField};
+use crate::models::{Project, ProjectType, BranchInfo, ByteSize, DependencyInfo, GitBadge};
+use crate::operations::{suggest, unpushed};
+use crate::operations::git_fetch::FetchStatus;
+use crate::operations::scan_output::ExportFormat;
 use crate::tui::app::TabView;
-use crate::utils::{size_format, time_format};
+use crate::tui::expansion;
+use crate::tui::filter::{self, PinnedOrder, ProjectFilter, ProjectSort, StatsScope};
+use crate::tui::grouping::{GroupRow, ProjectGroup, ProjectGroups};
+use crate::tui::ratio_bar;
+use crate::tui::rollup::{ListTotals, RootRollup};
+use crate::utils::{path_format, time_format};
 use crate::models::DependencyCalculationStatus;
 
+/// 统计页中显示的优先清理项目数
+const TOP_CLEANUP_CANDIDATES: usize = 5;
+
+/// Git 状态页顶部最多列出的有未保存工作的仓库数
+const UNPUSHED_SUMMARY_ROWS: usize = 6;
+
+/// 项目列表中 Git 状态列的宽度：最宽的标记组合 "游离 暂存 修改" 占 14 列（未提交、未跟踪不会与暂存、修改同时出现）
+const GIT_COLUMN_WIDTH: u16 = 14;
+
+/// 分组显示时列表中的一行
+pub enum ListRow<'a> {
+    /// 分组标题
+    Group { group: ProjectGroup, collapsed: bool },
+    
+    /// 项目
+    Project(&'a Project),
+}
+
+/// 项目列表视图所需的数据
+pub struct ProjectListView<'a> {
+    /// 当前可见（满足过滤条件）的项目
+    pub projects: Vec<&'a Project>,
+    
+    /// 选中项目在可见列表中的位置（分组显示时为在分组行中的位置）
+    pub selected: usize,
+    
+    /// 分组显示时的列表行，None 表示不分组
+    pub grouped_rows: Option<Vec<ListRow<'a>>>,
+    
+    /// 被标记的项目路径
+    pub marked: &'a HashSet<PathBuf>,
+    
+    /// 当前过滤条件
+    pub filter: &'a ProjectFilter,
+    
+    /// 当前排序方式
+    pub sort: ProjectSort,
+    
+    /// 当前未显示的隐藏项目数量
+    pub hidden_count: usize,
+    
+    /// 当前未显示的已忽略项目数量
+    pub ignored_count: usize,
+    
+    /// 按扫描根目录汇总的项目信息
+    pub root_rollups: Vec<RootRollup>,
+    
+    /// 列表是否来自上次会话快照、正在后台刷新
+    pub stale: bool,
+    
+    /// 统计信息的作用范围
+    pub stats_scope: StatsScope<'a>,
+    
+    /// 当前列出的项目合计，随过滤条件实时更新
+    pub totals: ListTotals,
+    
+    /// 最近清理释放的空间（按项目路径）
+    pub size_badges: HashMap<PathBuf, ByteSize>,
+    
+    /// 是否正在输入搜索关键字
+    pub searching: bool,
+    
+    /// 正在输入的导出文件路径和格式
+    pub export_prompt: Option<(String, ExportFormat)>,
+    
+    /// 正在输入的标签
+    pub tag_input: Option<String>,
+    
+    /// 顺序是否已固定（扫描进行中，新项目排在末尾）
+    pub order_pinned: bool,
+    
+    /// 工作区树中项目名称前的标识（按项目路径）
+    pub tree_labels: HashMap<PathBuf, &'static str>,
+    
+    /// 就地展开依赖目录的项目路径
+    pub expanded: HashSet<PathBuf>,
+    
+    /// Git 信息尚未加载完成的项目路径
+    pub git_waiting: HashSet<PathBuf>,
+    
+    /// 各仓库的 git fetch 状态（Git 状态标签页中显示）
+    pub git_fetch: HashMap<PathBuf, FetchStatus>,
+    
+    /// 选中项目的路径
+    pub selected_path: Option<PathBuf>,
+    
+    /// 全部项目，分组行据此取得项目
+    source: &'a [Project],
+}
+
+impl<'a> ProjectListView<'a> {
+    /// 根据过滤条件、排序方式和标记状态构建视图数据
+    pub fn new(
+        projects: &'a [Project],
+        filter: &'a ProjectFilter,
+        sort: ProjectSort,
+        pinned: Option<&PinnedOrder>,
+        marked: &'a HashSet<PathBuf>,
+        selected_project: usize,
+        scan_roots: &[String],
+    ) -> Self {
+        let visible = filter::visible_indices(projects, filter, sort, pinned);
+        
+        let selected = visible.iter()
+            .position(|&index| index == selected_project)
+            .unwrap_or(0);
+        
+        let hidden_count = if filter.show_hidden {
+            0
+        } else {
+            projects.iter().filter(|p| p.is_hidden).count()
+        };
+        let ignored_count = if filter.show_ignored {
+            0
+        } else {
+            projects.iter().filter(|p| p.is_ignored).count()
+        };
+        
+        let tree_labels = filter::workspace_tree_labels(projects, &visible, &filter.collapsed_workspaces);
+        let listed: Vec<&Project> = visible.into_iter().map(|index| &projects[index]).collect();
+        
+        Self {
+            totals: ListTotals::compute(&listed),
+            projects: listed,
+            selected,
+            grouped_rows: None,
+            marked,
+            filter,
+            sort,
+            hidden_count,
+            ignored_count,
+            root_rollups: RootRollup::compute(scan_roots, projects),
+            stale: false,
+            stats_scope: StatsScope::resolve(projects, filter, marked),
+            size_badges: HashMap::new(),
+            searching: false,
+            export_prompt: None,
+            tag_input: None,
+            order_pinned: pinned.is_some(),
+            tree_labels,
+            expanded: HashSet::new(),
+            git_waiting: HashSet::new(),
+            git_fetch: HashMap::new(),
+            selected_path: projects.get(selected_project).map(|project| project.path.clone()),
+            source: projects,
+        }
+    }
+    
+    /// 按分组显示列表，选中位置换算为分组行中的位置
+    pub fn group(&mut self, groups: &ProjectGroups, collapsed: &HashSet<Option<PathBuf>>, selected_project: usize) {
+        let rows = groups.rows(collapsed);
+        self.selected = groups.selected_row(&rows, selected_project);
+        self.grouped_rows = Some(rows.into_iter().map(|row| match row {
+            GroupRow::Header(position) => {
+                let group = groups.groups[position].clone();
+                let collapsed = collapsed.contains(&group.key);
+                ListRow::Group { group, collapsed }
+            }
+            GroupRow::Project(index) => ListRow::Project(&self.source[index]),
+        }).collect());
+    }
+    
+    /// 在展开的项目下方显示依赖子行，选中位置换算为展开后列表中的位置；需在 `group` 之后调用
+    pub fn expand(&mut self, expanded: &HashSet<PathBuf>) {
+        self.expanded = expanded.clone();
+        self.selected = expansion::display_row(&self.child_counts(), self.selected);
+    }
+    
+    /// 每个项目行或分组标题行下方的依赖子行数
+    fn child_counts(&self) -> Vec<usize> {
+        match &self.grouped_rows {
+            Some(rows) => rows.iter()
+                .map(|row| match row {
+                    ListRow::Group { .. } => 0,
+                    ListRow::Project(project) => expansion::child_rows(project, &self.expanded),
+                })
+                .collect(),
+            None => self.projects.iter().map(|project| expansion::child_rows(project, &self.expanded)).collect(),
+        }
+    }
+}
+
 /// 主屏幕组件 - 负责绘制项目列表和详情页面
 pub struct MainScreen {
     /// 表格状态
     table_state: TableState,
+    
+    /// 项目列表行密度
+    row_density: RowDensity,
+    
+    /// 是否显示代码/依赖/gitignore 占比列
+    show_ratio_bar: bool,
+    
+    /// 上次绘制时列表中可见的项目
+    visible_projects: Vec<PathBuf>,
+}
+
+/// 项目列表各行共用的布局参数
+#[derive(Debug, Clone, Copy)]
+struct RowLayout {
+    /// 行密度
+    density: RowDensity,
+    
+    /// 名称列宽度
+    name_width: usize,
+    
+    /// 是否有占比列
+    ratio_bar: bool,
+}
+
+impl RowLayout {
+    /// 组装一行的单元格，有占比列时插入到依赖列之后
+    fn row(&self, mut cells: Vec<Cell<'static>>, ratio: impl FnOnce() -> Cell<'static>) -> Row<'static> {
+        if self.ratio_bar {
+            cells.insert(4, ratio());
+        }
+        Row::new(cells).height(self.density.row_height())
+    }
 }
 
 impl MainScreen {
@@ -22,6 +248,9 @@ impl MainScreen {
     pub fn new() -> Self {
         Self {
             table_state: TableState::default(),
+            row_density: RowDensity::default(),
+            show_ratio_bar: true,
+            visible_projects: Vec::new(),
         }
     }
     
@@ -30,13 +259,37 @@ impl MainScreen {
         self.table_state.offset()
     }
     
+    /// 获取当前行密度
+    pub fn row_density(&self) -> RowDensity {
+        self.row_density
+    }
+    
+    /// 设置行密度
+    pub fn set_row_density(&mut self, row_density: RowDensity) {
+        self.row_density = row_density;
+    }
+    
+    /// 是否显示占比列
+    pub fn show_ratio_bar(&self) -> bool {
+        self.show_ratio_bar
+    }
+    
+    /// 设置是否显示占比列
+    pub fn set_show_ratio_bar(&mut self, show_ratio_bar: bool) {
+        self.show_ratio_bar = show_ratio_bar;
+    }
+    
+    /// 上次绘制项目列表时滚动区域中可见的项目
+    pub fn visible_projects(&self) -> &[PathBuf] {
+        &self.visible_projects
+    }
+    
     /// 绘制项目列表视图
     pub fn draw_project_list(
         &mut self,
         f: &mut Frame,
         area: Rect,
-        projects: &[Project],
-        selected_index: usize,
+        view: &ProjectListView,
         current_tab: &TabView,
     ) {
         // 创建布局
@@ -54,26 +307,50 @@ impl MainScreen {
         // 根据当前标签绘制不同内容
         match current_tab {
             TabView::Projects => {
-                self.draw_projects_view(f, chunks[1], projects, selected_index);
+                self.draw_projects_view(f, chunks[1], view);
             }
             TabView::Statistics => {
-                self.draw_statistics_view(f, chunks[1], projects);
+                self.draw_statistics_view(f, chunks[1], &view.stats_scope);
             }
             TabView::GitStatus => {
-                self.draw_git_status_view(f, chunks[1], projects);
+                self.draw_git_status_view(f, chunks[1], view);
+            }
+            TabView::ScanRoots => {
+                self.draw_scan_roots_view(f, chunks[1], &view.root_rollups);
             }
         }
     }
     
     /// 绘制项目详情页面
-    pub fn draw_project_detail(&mut self, f: &mut Frame, area: Rect, project: &Project) {
+    pub fn draw_project_detail(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        project: &Project,
+        ignore_source: Option<&IgnoreSource>,
+        selected_suggestion: usize,
+        commit_log_scroll: usize,
+    ) {
+        let pending_suggestions = project.pending_cleanup_suggestions();
+        let suggestions_height = if pending_suggestions.is_empty() {
+            0
+        } else {
+            (pending_suggestions.len() as u16 + 2).min(10)
+        };
+        let commits_height = match &project.recent_commits {
+            Some(commits) if !commits.is_empty() => (commits.len() as u16 + 2).min(12),
+            _ => 0,
+        };
+        
         // 创建布局
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3),  // 标题
-                Constraint::Min(0),     // 详情内容
-                Constraint::Length(3),  // 操作提示
+                Constraint::Length(3),                  // 标题
+                Constraint::Min(0),                     // 详情内容
+                Constraint::Length(suggestions_height), // 清理建议
+                Constraint::Length(commits_height),     // 最近提交
+                Constraint::Length(3),                  // 操作提示
             ])
             .split(area);
         
@@ -89,19 +366,61 @@ impl MainScreen {
         f.render_widget(title_paragraph, chunks[0]);
         
         // 绘制详情内容
-        self.draw_project_details(f, chunks[1], project);
+        self.draw_project_details(f, chunks[1], project, ignore_source);
+        
+        // 绘制清理建议
+        if !pending_suggestions.is_empty() {
+            self.draw_cleanup_suggestions(f, chunks[2], project, &pending_suggestions, selected_suggestion);
+        }
+        
+        // 绘制最近提交
+        if commits_height > 0 {
+            self.draw_recent_commits(f, chunks[3], project, commit_log_scroll);
+        }
         
         // 绘制操作提示
-        let help_text = vec![
-            Line::from(vec![
-                Span::raw("按 "),
-                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" 或 "),
-                Span::styled("Backspace", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" 返回项目列表"),
-            ])
+        let mut help_spans = vec![
+            Span::raw("按 "),
+            Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 或 "),
+            Span::styled("Backspace", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 返回项目列表 | "),
+            Span::styled("u", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 磁盘占用 | "),
+            Span::styled("p/P", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 复制/保存报告"),
         ];
         
+        if !pending_suggestions.is_empty() {
+            help_spans.extend([
+                Span::raw(" | "),
+                Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" 选择建议 | "),
+                Span::styled("y", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" 清理 | "),
+                Span::styled("n", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" 忽略"),
+            ]);
+        }
+        
+        if project.git_info.is_some() {
+            help_spans.extend([
+                Span::raw(" | "),
+                Span::styled("b", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" 分支列表"),
+            ]);
+        }
+        
+        if commits_height > 0 {
+            help_spans.extend([
+                Span::raw(" | "),
+                Span::styled("PgUp/PgDn", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" 滚动提交记录"),
+            ]);
+        }
+        
+        let help_text = vec![Line::from(help_spans)];
+        
         let help_block = Block::default()
             .title("操作")
             .borders(Borders::ALL)
@@ -110,17 +429,177 @@ impl MainScreen {
         let help_paragraph = Paragraph::new(help_text)
             .block(help_block);
         
-        f.render_widget(help_paragraph, chunks[2]);
+        f.render_widget(help_paragraph, chunks[4]);
+    }
+    
+    /// 绘制分支列表视图
+    pub fn draw_branch_list(
+        &mut self,
+        f: &mut Frame,
+        area: Rect,
+        project: &Project,
+        branches: &[BranchInfo],
+        selected_branch: usize,
+        stale_branch_days: u32,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),    // 分支列表
+                Constraint::Length(3), // 操作提示
+            ])
+            .split(area);
+        
+        let rows: Vec<Row> = branches.iter().map(|branch| {
+            let age = branch.last_commit_time
+                .map(|time| {
+                    let modified = std::time::SystemTime::UNIX_EPOCH
+                        + std::time::Duration::from_secs(time.timestamp().max(0) as u64);
+                    time_format::format_relative_time(modified)
+                })
+                .unwrap_or_else(|| "-".to_string());
+            
+            let name = if branch.is_current {
+                format!("* {}", branch.name)
+            } else {
+                format!("  {}", branch.name)
+            };
+            
+            let merged_cell = if branch.is_default {
+                Cell::from("默认分支").style(Style::default().fg(Color::Cyan))
+            } else if branch.is_merged {
+                Cell::from("已合并").style(Style::default().fg(Color::Green))
+            } else {
+                Cell::from("未合并").style(Style::default().fg(Color::Yellow))
+            };
+            
+            let stale_cell = if branch.is_stale(stale_branch_days) {
+                Cell::from("陈旧").style(Style::default().fg(Color::Red))
+            } else {
+                Cell::from("")
+            };
+            
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(age).style(Style::default().fg(Color::Magenta)),
+                merged_cell,
+                stale_cell,
+            ])
+        }).collect();
+        
+        let header = Row::new(vec!["分支", "最后提交", "合并状态", ""])
+            .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+        
+        let table = Table::new(rows, [
+            Constraint::Min(30),
+            Constraint::Length(16),
+            Constraint::Length(10),
+            Constraint::Length(6),
+        ])
+            .header(header)
+            .block(Block::default()
+                .title(format!("分支列表: {} (超过 {} 天未提交视为陈旧)", project.name, stale_branch_days))
+                .borders(Borders::ALL))
+            .row_highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+        
+        let mut table_state = TableState::default();
+        if !branches.is_empty() {
+            table_state.select(Some(selected_branch));
+        }
+        f.render_stateful_widget(table, chunks[0], &mut table_state);
+        
+        let help_text = Line::from(vec![
+            Span::styled("↑/↓", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 选择 | "),
+            Span::styled("d", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 删除已合并分支 | "),
+            Span::styled("Enter/Backspace", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" 返回详情"),
+        ]);
+        
+        let help_paragraph = Paragraph::new(help_text)
+            .block(Block::default().title("操作").borders(Borders::ALL).style(Style::default().fg(Color::Green)));
+        
+        f.render_widget(help_paragraph, chunks[1]);
+    }
+    
+    /// 绘制最近提交记录
+    fn draw_recent_commits(&self, f: &mut Frame, area: Rect, project: &Project, scroll: usize) {
+        let commits = project.recent_commits.as_deref().unwrap_or_default();
+        
+        let lines: Vec<Line> = commits.iter().map(|commit| {
+            Line::from(vec![
+                Span::styled(format!("{} ", commit.id), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!("{} ", commit.time.with_timezone(&chrono::Local).format("%Y-%m-%d")),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(format!("{:<16} ", commit.author), Style::default().fg(Color::Cyan)),
+                Span::raw(commit.subject.clone()),
+            ])
+        }).collect();
+        
+        let paragraph = Paragraph::new(lines)
+            .block(Block::default().title(format!("最近提交 ({})", commits.len())).borders(Borders::ALL))
+            .style(Style::default().fg(Color::White))
+            .scroll((scroll as u16, 0));
+        
+        f.render_widget(paragraph, area);
+    }
+    
+    /// 绘制基于 .gitignore 的清理建议
+    fn draw_cleanup_suggestions(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        project: &Project,
+        suggestions: &[&crate::models::CleanupSuggestion],
+        selected_suggestion: usize,
+    ) {
+        let lines: Vec<Line> = suggestions.iter().enumerate().map(|(i, suggestion)| {
+            let relative_path = suggestion.path
+                .strip_prefix(&project.path)
+                .unwrap_or(&suggestion.path)
+                .display()
+                .to_string();
+            
+            let style = if i == selected_suggestion {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            
+            Line::from(vec![
+                Span::styled(format!("{:<30}", relative_path), style),
+                Span::styled(format!(" {:>10}", suggestion.size), Style::default().fg(Color::Yellow)),
+                Span::styled(format!(" {:>5.1}%", suggestion.share * 100.0), Style::default().fg(Color::Cyan)),
+                Span::styled(format!(" {} 个文件", suggestion.file_count), Style::default().fg(Color::Gray)),
+            ])
+        }).collect();
+        
+        // 建议多于面板行数时随选中项滚动
+        let visible_rows = area.height.saturating_sub(2) as usize;
+        let scroll = (selected_suggestion + 1).saturating_sub(visible_rows);
+        
+        let paragraph = Paragraph::new(lines)
+            .block(Block::default()
+                .title(format!("清理建议 (.gitignore) {}/{}", selected_suggestion + 1, suggestions.len()))
+                .borders(Borders::ALL))
+            .style(Style::default().fg(Color::White))
+            .scroll((scroll as u16, 0));
+        
+        f.render_widget(paragraph, area);
     }
     
     /// 绘制标签栏
     fn draw_tab_bar(&self, f: &mut Frame, area: Rect, current_tab: &TabView) {
-        let tab_titles = vec!["项目列表", "统计信息", "Git状态"];
+        let tab_titles = vec!["项目列表", "统计信息", "Git状态", "扫描根目录"];
         
         let selected_tab = match current_tab {
             TabView::Projects => 0,
             TabView::Statistics => 1,
             TabView::GitStatus => 2,
+            TabView::ScanRoots => 3,
         };
         
         let tabs = Tabs::new(tab_titles)
@@ -133,62 +612,167 @@ impl MainScreen {
     }
     
     /// 绘制项目列表视图
-    fn draw_projects_view(&mut self, f: &mut Frame, area: Rect, projects: &[Project], selected_index: usize) {
+    fn draw_projects_view(&mut self, f: &mut Frame, area: Rect, view: &ProjectListView) {
+        let projects = &view.projects;
+        
+        // 搜索和导出输入框显示在列表下方，不影响鼠标点击的行号计算
+        let area = if view.export_prompt.is_some() || view.tag_input.is_some() || view.searching || !view.filter.query.is_empty() {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(3)])
+                .split(area);
+            match (&view.export_prompt, &view.tag_input) {
+                (Some((path, format)), _) => self.draw_export_input(f, chunks[1], path, *format, projects.len()),
+                (None, Some(tag)) => self.draw_tag_input(f, chunks[1], tag, view.marked.len().max(1)),
+                (None, None) => self.draw_search_input(f, chunks[1], view),
+            }
+            chunks[0]
+        } else {
+            area
+        };
+        
         if projects.is_empty() {
-            let empty_message = Paragraph::new("未发现任何项目\n\n按 'r' 刷新扫描")
+            let message = if !view.filter.query.is_empty() {
+                "没有匹配搜索的项目\n\n按 Esc 清除搜索"
+            } else if view.filter.is_active() {
+                "没有满足过滤条件的项目\n\n按 'f' 清除过滤"
+            } else {
+                "未发现任何项目\n\n按 'r' 刷新扫描"
+            };
+            let empty_message = Paragraph::new(message)
                 .block(Block::default().title("项目列表").borders(Borders::ALL))
                 .style(Style::default().fg(Color::Gray));
             
             f.render_widget(empty_message, area);
+            self.visible_projects.clear();
             return;
         }
 
         // 定义列宽约束
-        let constraints = [
+        let mut constraints = vec![
             Constraint::Min(25),      // 项目名称
             Constraint::Length(15),   // 语言 
             Constraint::Length(12),   // 大小
             Constraint::Length(16),   // 依赖
-            Constraint::Length(10),   // Git状态
+            Constraint::Length(GIT_COLUMN_WIDTH), // Git状态
             Constraint::Min(20),      // 最后修改
         ];
+        if self.show_ratio_bar {
+            constraints.insert(4, Constraint::Length(ratio_bar::WIDTH as u16)); // 占比
+        }
+        let ratio_width = if self.show_ratio_bar { ratio_bar::WIDTH + 1 } else { 0 };
+        let layout = RowLayout {
+            density: self.row_density,
+            // 名称列宽度 = 总宽度 - 其他列宽度 - 列间距 - 边框
+            name_width: (area.width as usize).saturating_sub(15 + 12 + 16 + GIT_COLUMN_WIDTH as usize + 20 + 5 + 2 + ratio_width).max(25),
+            ratio_bar: self.show_ratio_bar,
+        };
 
         // 创建表头
-        let header = Row::new(vec![
-            Cell::from("项目名称"),
+        let sort = view.sort;
+        let header = RowLayout { density: RowDensity::Compact, ..layout }.row(vec![
+            Cell::from(sort.column_title("项目名称", SortField::Name)),
             Cell::from("语言"),
-            Cell::from("大小"),
-            Cell::from("依赖"),
+            Cell::from(sort.column_title("大小", SortField::Size)),
+            Cell::from(sort.column_title("依赖", SortField::DependencySize)),
             Cell::from("Git状态"),
-            Cell::from("最后修改"),
-        ])
-        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
-        .height(1);
+            Cell::from(sort.column_title("最后修改", SortField::LastModified)),
+        ], || Cell::from("占比"))
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+
+        // 合计行固定在表格底部，滚动时始终可见
+        let totals = &view.totals;
+        let footer = RowLayout { density: RowDensity::Compact, ..layout }.row(vec![
+            Cell::from(format!("合计 {} 个项目", totals.project_count)),
+            Cell::from(""),
+            Cell::from(totals.code_size.to_string()),
+            Cell::from(totals.dependency_size.to_string()),
+            Cell::from(""),
+            Cell::from(format!("可释放约 {}", totals.reclaimable)),
+        ], || Cell::from(ratio_bar::line(totals.code_size, totals.dependency_size, ByteSize::ZERO)))
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
 
         // 创建数据行
-        let rows: Vec<Row> = projects
-            .iter()
-            .enumerate()
-            .map(|(_i, project)| {
-                let row_style = if project.is_ignored {
-                    Style::default().fg(Color::Gray)
-                } else {
-                    Style::default().fg(Color::White)
-                };
-                
-                Self::create_project_row(project, row_style)
-            })
-            .collect();
+        let project_row = |project: &Project| {
+            let row_style = if project.is_hidden {
+                Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC)
+            } else if project.is_ignored {
+                Style::default().fg(Color::Gray)
+            } else if project.is_trivial {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            
+            let is_marked = view.marked.contains(&project.path);
+            let freed_badge = view.size_badges.get(&project.path).copied();
+            let tree_label = view.tree_labels.get(&project.path).copied().unwrap_or("");
+            let git_waiting = view.git_waiting.contains(&project.path);
+            let mut rows = vec![Self::create_project_row(project, row_style, layout, is_marked, freed_badge, tree_label, git_waiting)];
+            let children = expansion::child_rows(project, &view.expanded);
+            rows.extend(project.dependencies.iter().take(children).enumerate().map(|(position, dependency)| {
+                Self::create_dependency_row(project, dependency, position + 1 == children, layout)
+            }));
+            rows
+        };
+        let rows: Vec<Row> = match &view.grouped_rows {
+            Some(grouped_rows) => grouped_rows.iter()
+                .flat_map(|row| match row {
+                    ListRow::Group { group, collapsed } => vec![Self::create_group_row(group, *collapsed, layout)],
+                    ListRow::Project(project) => project_row(project),
+                })
+                .collect(),
+            None => projects.iter().flat_map(|project| project_row(project)).collect(),
+        };
+        // 每一行所属的项目（分组标题为 None），用于找出滚动区域中可见的项目
+        let project_rows = |project: &Project| vec![Some(project.path.clone()); 1 + expansion::child_rows(project, &view.expanded)];
+        let row_projects: Vec<Option<PathBuf>> = match &view.grouped_rows {
+            Some(grouped_rows) => grouped_rows.iter()
+                .flat_map(|row| match row {
+                    ListRow::Group { .. } => vec![None],
+                    ListRow::Project(project) => project_rows(project),
+                })
+                .collect(),
+            None => projects.iter().flat_map(|project| project_rows(project)).collect(),
+        };
 
         // 更新表格状态
-        self.table_state.select(Some(selected_index));
+        self.table_state.select(Some(view.selected));
+
+        let mut title = if view.filter.is_active() {
+            format!("项目列表 ({} 个项目, 过滤: {})", projects.len(), view.filter.description())
+        } else {
+            format!("项目列表 ({} 个项目)", projects.len())
+        };
+        title.push_str(&format!(" [排序: {}]", view.sort.description()));
+        if view.hidden_count > 0 {
+            title.push_str(&format!(" [{} 个已隐藏]", view.hidden_count));
+        }
+        if view.ignored_count > 0 {
+            title.push_str(&format!(" [{} 个已忽略]", view.ignored_count));
+        }
+        if view.stale {
+            title.push_str(" [上次会话数据，正在刷新...]");
+        }
+        if view.order_pinned {
+            title.push_str(" [顺序已固定，新项目排在末尾]");
+        }
+        if let Some(grouped_rows) = &view.grouped_rows {
+            let group_count = grouped_rows.iter().filter(|row| matches!(row, ListRow::Group { .. })).count();
+            title.push_str(&format!(" [分组: {} 组，←/→ 折叠/展开]", group_count));
+        } else if view.tree_labels.values().any(|label| matches!(*label, "▾ " | "▸ ")) {
+            title.push_str(" [工作区: ←/→ 折叠/展开成员]");
+        } else if view.projects.iter().any(|project| view.expanded.contains(&project.path)) {
+            title.push_str(" [依赖已展开，← 收起]");
+        }
 
         // 创建表格
         let table = Table::new(rows, constraints)
             .header(header)
+            .footer(footer)
             .block(
                 Block::default()
-                    .title(format!("项目列表 ({} 个项目)", projects.len()))
+                    .title(title)
                     .borders(Borders::ALL)
             )
             .column_spacing(1)
@@ -199,17 +783,158 @@ impl MainScreen {
             );
 
         f.render_stateful_widget(table, area, &mut self.table_state);
+        
+        // 边框、表头和合计行之外可显示的行数
+        let capacity = (area.height.saturating_sub(4) / layout.density.row_height()) as usize;
+        let mut visible_projects: Vec<PathBuf> = Vec::new();
+        for path in row_projects.into_iter().skip(self.table_state.offset()).take(capacity).flatten() {
+            if visible_projects.last() != Some(&path) {
+                visible_projects.push(path);
+            }
+        }
+        self.visible_projects = visible_projects;
     }
 
+    /// 绘制搜索输入框
+    fn draw_search_input(&self, f: &mut Frame, area: Rect, view: &ProjectListView) {
+        let (title, style) = if view.searching {
+            ("搜索 (Enter 确认, Esc 清除)", Style::default().fg(Color::Yellow))
+        } else {
+            ("搜索 (/ 修改, Esc 清除)", Style::default().fg(Color::Gray))
+        };
+        let cursor = if view.searching { "▏" } else { "" };
+        let input = Paragraph::new(format!("/{}{}", view.filter.query, cursor))
+            .block(Block::default().title(title).borders(Borders::ALL).border_style(style));
+        
+        f.render_widget(input, area);
+    }
+    
+    /// 绘制导出文件路径输入框
+    fn draw_export_input(&self, f: &mut Frame, area: Rect, path: &str, format: ExportFormat, count: usize) {
+        let title = format!("导出 {} 个项目为 {} (Tab 切换格式, Enter 导出, Esc 取消)", count, format.name());
+        let input = Paragraph::new(format!("{}▏", path))
+            .block(Block::default().title(title).borders(Borders::ALL).border_style(Style::default().fg(Color::Yellow)));
+        
+        f.render_widget(input, area);
+    }
+    
+    /// 绘制标签输入框
+    fn draw_tag_input(&self, f: &mut Frame, area: Rect, tag: &str, count: usize) {
+        let title = format!("为 {} 个项目设置标签 (Enter 添加，都已有时移除, Esc 取消)", count);
+        let input = Paragraph::new(format!("#{}▏", tag))
+            .block(Block::default().title(title).borders(Borders::ALL).border_style(Style::default().fg(Color::Yellow)));
+        
+        f.render_widget(input, area);
+    }
+    
+    /// 创建分组标题行：组内项目数和大小合计
+    fn create_group_row(group: &ProjectGroup, collapsed: bool, layout: RowLayout) -> Row<'static> {
+        let marker = if collapsed { "▶" } else { "▼" };
+        let label = match &group.key {
+            Some(key) => path_format::format_path(key, layout.name_width.saturating_sub(2)),
+            None => "(其他位置)".to_string(),
+        };
+        let style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+        
+        layout.row(vec![
+            Cell::from(format!("{} {}", marker, label)),
+            Cell::from(format!("{} 个项目", group.projects.len())),
+            Cell::from(group.code_size.to_string()),
+            Cell::from(group.dependency_size.to_string()),
+            Cell::from(""),
+            Cell::from(format!("合计 {}", group.total_size())),
+        ], || Cell::from(ratio_bar::line(group.code_size, group.dependency_size, ByteSize::ZERO)))
+        .style(style)
+    }
+    
+    /// 创建展开项目下方的依赖子行：缩进的目录名、依赖类型、大小和包数量
+    fn create_dependency_row(
+        project: &Project,
+        dependency: &DependencyInfo,
+        is_last: bool,
+        layout: RowLayout,
+    ) -> Row<'static> {
+        let branch = if is_last { "└─" } else { "├─" };
+        let relative = dependency.path.strip_prefix(&project.path).unwrap_or(&dependency.path);
+        let name = format!("    {} {}", branch, relative.display());
+        let name_text = match layout.density {
+            RowDensity::Compact => Text::from(name),
+            RowDensity::Comfortable => Text::from(vec![
+                Line::from(name),
+                Line::from(format!("       {}", path_format::format_path(&dependency.path, layout.name_width.saturating_sub(7)))),
+            ]),
+        };
+        let packages = dependency.package_count
+            .map(|count| format!("{} 个包", count))
+            .unwrap_or_default();
+        
+        layout.row(vec![
+            Cell::from(name_text),
+            Cell::from(dependency.dependency_type.display_name().to_string()),
+            Cell::from(""),
+            Cell::from(dependency.size.to_string()),
+            Cell::from(""),
+            Cell::from(packages),
+        ], || Cell::from(""))
+        .style(Style::default().fg(Color::DarkGray))
+    }
+    
     /// 创建项目数据行
-    fn create_project_row(project: &Project, base_style: Style) -> Row {
+    fn create_project_row(
+        project: &Project,
+        base_style: Style,
+        layout: RowLayout,
+        is_marked: bool,
+        freed_badge: Option<ByteSize>,
+        tree_label: &str,
+        git_waiting: bool,
+    ) -> Row<'static> {
         // 项目名称
         let project_name = if project.name.len() > 23 {
             format!("{}...", &project.name[..20])
         } else {
             project.name.clone()
         };
-        let mut name_cell = Cell::from(project_name).style(base_style.add_modifier(Modifier::BOLD));
+        
+        // 位于云同步目录中的项目加上云标识
+        let project_name = if project.cloud_sync.is_some() {
+            format!("☁ {}", project_name)
+        } else {
+            project_name
+        };
+        
+        // 标记的项目在名称前加上标识
+        let project_name = if is_marked {
+            format!("● {}", project_name)
+        } else {
+            project_name
+        };
+        
+        // 工作区树的层级标识放在最前面，保持成员对齐
+        let mut project_name = format!("{}{}", tree_label, project_name);
+        for tag in &project.tags {
+            project_name.push_str(" #");
+            project_name.push_str(tag);
+        }
+        
+        // 舒适模式下在名称下方显示路径和描述
+        let name_text = match layout.density {
+            RowDensity::Compact => Text::from(project_name),
+            RowDensity::Comfortable => {
+                let mut detail_line = path_format::format_path(&project.path, layout.name_width);
+                if let Some(description) = &project.description {
+                    detail_line.push_str(" · ");
+                    detail_line.push_str(description);
+                }
+                
+                Text::from(vec![
+                    Line::from(project_name),
+                    Line::styled(detail_line, Style::default().fg(Color::DarkGray).remove_modifier(Modifier::BOLD)),
+                ])
+            }
+        };
+        
+        let mut name_cell = Cell::from(name_text).style(base_style.add_modifier(Modifier::BOLD));
         
         // 如果项目被忽略，添加标识
         if project.is_ignored {
@@ -217,25 +942,47 @@ impl MainScreen {
         }
 
         // 语言列 - icon + 名称
-        let (type_icon, type_name) = match project.project_type.as_str() {
-            "git" => ("📁", "Git"),
-            "nodejs" => ("📦", "Node.js"),
-            "python" => ("🐍", "Python"), 
-            "rust" => ("🦀", "Rust"),
-            "go" => ("🐹", "Go"),
-            "java" => ("☕", "Java"),
-            "cpp" => ("⚡", "C++"),
-            _ => ("📄", "Other"),
+        let (type_icon, type_name) = match &project.project_type {
+            ProjectType::Custom { name, icon } => (icon.as_str(), name.as_str()),
+            project_type => match project_type.as_str() {
+                "git" => ("📁", "Git"),
+                "nodejs" => ("📦", "Node.js"),
+                "python" => ("🐍", "Python"), 
+                "rust" => ("🦀", "Rust"),
+                "go" => ("🐹", "Go"),
+                "java" => ("☕", "Java"),
+                "cpp" => ("⚡", "C++"),
+                "terraform" => ("🌍", "Terraform"),
+                "ansible" => ("🔧", "Ansible"),
+                "helm" => ("⛵", "Helm"),
+                "nix" => ("🧊", "Nix"),
+                "unity" => ("🎮", "Unity"),
+                "unreal" => ("👾", "Unreal"),
+                "swift" => ("🍎", "Swift"),
+                "android" => ("🤖", "Android"),
+                "dotnet" => ("🟣", ".NET"),
+                "dart" => ("🎯", "Dart"),
+                "php" => ("🐘", "PHP"),
+                "elixir" => ("💧", "Elixir"),
+                "zig" => ("🦎", "Zig"),
+                _ => ("📄", "Other"),
+            },
         };
         let language_cell = Cell::from(format!("{} {}", type_icon, type_name)).style(base_style);
 
-        // 大小列
-        let size_cell = Cell::from(size_format::format_size(project.size())).style(base_style);
+        // 大小列（估算值以 ≈ 前缀标注，达到统计上限的以 ≥ 前缀标注）
+        let size_cell = if project.size_is_estimate {
+            Cell::from(format!("≈{}", project.size())).style(base_style.fg(Color::DarkGray))
+        } else if project.size_truncated {
+            Cell::from(format!("≥{}", project.size())).style(base_style.fg(Color::Yellow))
+        } else {
+            Cell::from(project.size().to_string()).style(base_style)
+        };
 
         // 依赖列
-        let dependency_text = if project.dependency_size() > 0 {
+        let dependency_text = if !project.dependency_size().is_zero() {
             let dependency_status = project.dependency_status_display();
-            let size_str = size_format::format_size(project.dependency_size());
+            let size_str = project.dependency_size().to_string();
             if dependency_status.is_empty() {
                 size_str
             } else {
@@ -255,65 +1002,108 @@ impl MainScreen {
             DependencyCalculationStatus::Calculating => Color::Cyan,
             DependencyCalculationStatus::NotCalculated => Color::Gray,
             DependencyCalculationStatus::Failed(_) => Color::Red,
+            DependencyCalculationStatus::Cancelled => Color::DarkGray,
             _ => base_style.fg.unwrap_or(Color::White),
         };
         
-        let dependency_cell = Cell::from(dependency_text).style(base_style.fg(dependency_color));
+        // 刚清理过的项目在依赖大小后显示释放的空间
+        let mut dependency_spans = vec![Span::raw(dependency_text)];
+        if let Some(freed) = freed_badge {
+            dependency_spans.push(Span::styled(
+                format!(" -{}", freed),
+                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+            ));
+        }
+        let dependency_cell = Cell::from(Line::from(dependency_spans)).style(base_style.fg(dependency_color));
 
-        // Git状态列
-        let git_status_text = if project.git_info.is_some() {
-            if project.has_uncommitted_changes() {
-                "未提交"
-            } else {
-                "清洁"
+        // Git状态列：游离 HEAD、暂存、修改和只有未跟踪文件分别显示不同颜色的标记（Git 信息尚未加载时显示占位符）
+        let git_status_cell = match &project.git_info {
+            None if git_waiting => Cell::from("…").style(base_style.fg(Color::Gray)),
+            None => Cell::from("-").style(base_style.fg(Color::Gray)),
+            Some(git_info) => {
+                let badges = git_info.badges();
+                if badges.is_empty() {
+                    Cell::from("清洁").style(base_style.fg(Color::Green))
+                } else {
+                    let mut spans = Vec::new();
+                    for badge in badges {
+                        if !spans.is_empty() {
+                            spans.push(Span::raw(" "));
+                        }
+                        spans.push(Span::styled(badge.to_string(), base_style.fg(git_badge_color(badge))));
+                    }
+                    Cell::from(Line::from(spans))
+                }
             }
-        } else {
-            "-"
         };
 
-        let git_status_color = if project.has_uncommitted_changes() {
-            Color::Red
-        } else if project.git_info.is_some() {
-            Color::Green
-        } else {
-            Color::Gray
-        };
-
-        let git_status_cell = Cell::from(git_status_text).style(base_style.fg(git_status_color));
-
-        // 最后修改时间列 - 显示 Git 最后提交时间
-        let time_cell = if let Some(git_info) = &project.git_info {
-            if let Some(last_commit) = git_info.last_commit_time {
-                let commit_time = std::time::SystemTime::UNIX_EPOCH + 
-                    std::time::Duration::from_secs(last_commit.timestamp() as u64);
-                Cell::from(time_format::format_time(commit_time)).style(base_style.fg(Color::Gray))
-            } else {
-                Cell::from("-").style(base_style.fg(Color::Gray))
-            }
-        } else {
-            Cell::from("-").style(base_style.fg(Color::Gray))
-        };
+        // 最后修改时间列 - 最后提交和文件修改时间中较晚的一个，与按最后修改排序一致
+        let time_cell = Cell::from(time_format::format_time(project.last_touched().into()))
+            .style(base_style.fg(Color::Gray));
 
-        Row::new(vec![
+        layout.row(vec![
             name_cell,
             language_cell,
             size_cell,
             dependency_cell,
             git_status_cell,
             time_cell,
-        ])
+        ], || Cell::from(ratio_bar::project_line(project)))
     }
 
     
     /// 绘制统计信息视图
-    fn draw_statistics_view(&self, f: &mut Frame, area: Rect, projects: &[Project]) {
+    fn draw_statistics_view(&self, f: &mut Frame, area: Rect, scope: &StatsScope) {
+        let projects = &scope.projects;
+        
         let mut stats_text = vec![
             Line::from(vec![
                 Span::styled("项目统计信息", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))
             ]),
+            Line::from(vec![
+                Span::styled("统计范围: ", Style::default().fg(Color::White)),
+                Span::styled(scope.label.clone(), Style::default().fg(Color::Magenta)),
+            ]),
             Line::from(""),
         ];
         
+        // 优先清理候选
+        let candidates = suggest::rank_cleanup_candidates(projects.iter().copied(), chrono::Utc::now());
+        if !candidates.is_empty() {
+            stats_text.push(Line::from(vec![
+                Span::styled("优先清理:", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow))
+            ]));
+            
+            for (i, candidate) in candidates.iter().take(TOP_CLEANUP_CANDIDATES).enumerate() {
+                stats_text.push(Line::from(vec![
+                    Span::styled(format!("  {}. ", i + 1), Style::default().fg(Color::White)),
+                    Span::styled(candidate.project.name.clone(), Style::default().fg(Color::Green)),
+                    Span::styled(format!(" [{:.1}] ", candidate.score), Style::default().fg(Color::Magenta)),
+                    Span::styled(candidate.reasons().join(" · "), Style::default().fg(Color::Gray)),
+                ]));
+            }
+            
+            stats_text.push(Line::from(""));
+        }
+        
+        // 云同步目录中的依赖
+        let cloud_warnings = suggest::cloud_synced_projects(projects.iter().copied());
+        if !cloud_warnings.is_empty() {
+            stats_text.push(Line::from(vec![
+                Span::styled("云同步目录中的依赖:", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow))
+            ]));
+            
+            for warning in cloud_warnings.iter().take(TOP_CLEANUP_CANDIDATES) {
+                stats_text.push(Line::from(vec![
+                    Span::styled("  ☁ ", Style::default().fg(Color::White)),
+                    Span::styled(warning.project.name.clone(), Style::default().fg(Color::Green)),
+                    Span::styled(format!(" {}", warning.advice()), Style::default().fg(Color::Gray)),
+                ]));
+            }
+            
+            stats_text.push(Line::from(""));
+        }
+        
         // 总体统计
         let total_projects = projects.len();
         let ignored_projects = projects.iter().filter(|p| p.is_ignored).count();
@@ -342,7 +1132,7 @@ impl MainScreen {
         ]));
         
         let mut type_counts = std::collections::HashMap::new();
-        for project in projects {
+        for project in projects.iter() {
             if !project.is_ignored {
                 *type_counts.entry(project.project_type.as_str()).or_insert(0) += 1;
             }
@@ -357,6 +1147,19 @@ impl MainScreen {
                 "go" => "Go",
                 "java" => "Java",
                 "cpp" => "C++",
+                "terraform" => "Terraform",
+                "ansible" => "Ansible",
+                "helm" => "Helm",
+                "nix" => "Nix",
+                "unity" => "Unity",
+                "unreal" => "Unreal",
+                "swift" => "Swift",
+                "android" => "Android",
+                "dotnet" => ".NET",
+                "dart" => "Dart",
+                "php" => "PHP",
+                "elixir" => "Elixir",
+                "zig" => "Zig",
                 _ => "其他",
             };
             
@@ -369,9 +1172,12 @@ impl MainScreen {
         stats_text.push(Line::from(""));
         
         // 大小统计
-        let total_size: u64 = projects.iter().filter(|p| !p.is_ignored).map(|p| p.size()).sum();
-        let total_dependency_size: u64 = projects.iter()
-            .filter(|p| !p.is_ignored)
+        let total_size: ByteSize = projects.iter()
+            .filter(|p| !p.is_ignored && !p.is_workspace_member())
+            .map(|p| p.size())
+            .sum();
+        let total_dependency_size: ByteSize = projects.iter()
+            .filter(|p| !p.is_ignored && !p.is_workspace_member())
             .map(|p| p.dependency_size())
             .sum();
         
@@ -381,12 +1187,12 @@ impl MainScreen {
         
         stats_text.push(Line::from(vec![
             Span::styled("  代码总大小: ", Style::default().fg(Color::White)),
-            Span::styled(size_format::format_size(total_size), Style::default().fg(Color::Green)),
+            Span::styled(total_size.to_string(), Style::default().fg(Color::Green)),
         ]));
         
         stats_text.push(Line::from(vec![
             Span::styled("  依赖总大小: ", Style::default().fg(Color::White)),
-            Span::styled(size_format::format_size(total_dependency_size), Style::default().fg(Color::Yellow)),
+            Span::styled(total_dependency_size.to_string(), Style::default().fg(Color::Yellow)),
         ]));
         
         // Git 统计
@@ -415,9 +1221,73 @@ impl MainScreen {
         f.render_widget(stats_paragraph, area);
     }
     
+    /// 绘制扫描根目录汇总视图
+    fn draw_scan_roots_view(&self, f: &mut Frame, area: Rect, rollups: &[RootRollup]) {
+        if rollups.is_empty() {
+            let empty_message = Paragraph::new("未配置扫描根目录")
+                .block(Block::default().title("扫描根目录").borders(Borders::ALL))
+                .style(Style::default().fg(Color::Gray));
+            
+            f.render_widget(empty_message, area);
+            return;
+        }
+        
+        let grand_total: ByteSize = rollups.iter().map(|r| r.total_size()).sum();
+        
+        let header = Row::new(vec![
+            Cell::from("根目录"),
+            Cell::from("项目数"),
+            Cell::from("代码大小"),
+            Cell::from("依赖大小"),
+            Cell::from("合计"),
+            Cell::from("占比"),
+        ])
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .height(1);
+        
+        let root_width = (area.width as usize).saturating_sub(8 + 12 + 12 + 12 + 8 + 5 + 2).max(20);
+        let rows: Vec<Row> = rollups.iter().map(|rollup| {
+            let root = match &rollup.root {
+                Some(root) => path_format::format_path(root, root_width),
+                None => "(其他位置)".to_string(),
+            };
+            let share = if !grand_total.is_zero() {
+                rollup.total_size().bytes() as f64 / grand_total.bytes() as f64 * 100.0
+            } else {
+                0.0
+            };
+            
+            Row::new(vec![
+                Cell::from(root).style(Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                Cell::from(rollup.project_count.to_string()),
+                Cell::from(rollup.code_size.to_string()).style(Style::default().fg(Color::Green)),
+                Cell::from(rollup.dependency_size.to_string()).style(Style::default().fg(Color::Yellow)),
+                Cell::from(rollup.total_size().to_string()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(format!("{:>5.1}%", share)),
+            ])
+        }).collect();
+        
+        let table = Table::new(rows, [
+            Constraint::Min(20),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(8),
+        ])
+        .header(header)
+        .block(
+            Block::default()
+                .title(format!("扫描根目录 (合计 {})", grand_total))
+                .borders(Borders::ALL)
+        );
+        
+        f.render_widget(table, area);
+    }
+    
     /// 绘制 Git 状态视图
-    fn draw_git_status_view(&self, f: &mut Frame, area: Rect, projects: &[Project]) {
-        let git_projects: Vec<_> = projects.iter()
+    fn draw_git_status_view(&self, f: &mut Frame, area: Rect, view: &ProjectListView) {
+        let git_projects: Vec<_> = view.projects.iter()
             .filter(|p| !p.is_ignored && p.git_info.is_some())
             .collect();
         
@@ -430,6 +1300,39 @@ impl MainScreen {
             return;
         }
         
+        // 有未保存工作的仓库汇总在顶部，清空机器前逐一确认
+        let unpushed = unpushed::repos_with_unpushed_work(git_projects.iter().map(|project| **project));
+        let area = if unpushed.is_empty() {
+            area
+        } else {
+            let summary_height = unpushed.len().min(UNPUSHED_SUMMARY_ROWS) as u16 + 2;
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(summary_height), Constraint::Min(0)])
+                .split(area);
+            
+            let mut lines: Vec<Line> = unpushed.iter()
+                .take(UNPUSHED_SUMMARY_ROWS)
+                .map(|repo| Line::from(Span::styled(format!("⚠ {}", repo), Style::default().fg(Color::Yellow))))
+                .collect();
+            if unpushed.len() > UNPUSHED_SUMMARY_ROWS {
+                lines.pop();
+                lines.push(Line::from(Span::styled(
+                    format!("… 另有 {} 个仓库，运行 git unpushed 查看完整列表", unpushed.len() - UNPUSHED_SUMMARY_ROWS + 1),
+                    Style::default().fg(Color::Gray),
+                )));
+            }
+            let summary = Paragraph::new(lines)
+                .block(
+                    Block::default()
+                        .title(format!("未保存的工作 ({} 个仓库)", unpushed.len()))
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Yellow))
+                );
+            f.render_widget(summary, chunks[0]);
+            chunks[1]
+        };
+        
         let items: Vec<ListItem> = git_projects
             .iter()
             .map(|project| {
@@ -447,6 +1350,9 @@ impl MainScreen {
                     format!(" [{}]", git_info.current_branch.as_deref().unwrap_or("unknown")),
                     Style::default().fg(Color::Green)
                 ));
+                if git_info.head_detached {
+                    spans.push(Span::styled(" [HEAD 游离]", Style::default().fg(Color::Magenta)));
+                }
                 
                 // 远程仓库
                 if let Some(remote_url) = &git_info.remote_url {
@@ -461,8 +1367,18 @@ impl MainScreen {
                     ));
                 }
                 
-                // 未提交更改状态
-                if project.has_uncommitted_changes() {
+                // 未提交更改状态（裸仓库没有工作区）
+                if git_info.is_bare {
+                    spans.push(Span::styled(
+                        " [裸仓库]",
+                        Style::default().fg(Color::Cyan)
+                    ));
+                } else if git_info.changed_file_count > 0 {
+                    spans.push(Span::styled(
+                        format!(" [未提交 {} 个文件]", git_info.changed_file_count),
+                        Style::default().fg(Color::Red)
+                    ));
+                } else if project.has_uncommitted_changes() {
                     spans.push(Span::styled(
                         " [未提交更改]",
                         Style::default().fg(Color::Red)
@@ -474,6 +1390,47 @@ impl MainScreen {
                     ));
                 }
                 
+                if git_info.unpushed_commit_count > 0 {
+                    spans.push(Span::styled(
+                        format!(" [未推送 {} 个提交]", git_info.unpushed_commit_count),
+                        Style::default().fg(Color::Red)
+                    ));
+                }
+                
+                // 未跟踪文件和 stash 数量
+                if git_info.untracked_count > 0 {
+                    spans.push(Span::styled(
+                        format!(" [未跟踪 {}]", git_info.untracked_count),
+                        Style::default().fg(Color::Yellow)
+                    ));
+                }
+                
+                if git_info.stash_count > 0 {
+                    spans.push(Span::styled(
+                        format!(" [stash {}]", git_info.stash_count),
+                        Style::default().fg(Color::Magenta)
+                    ));
+                }
+                
+                // 相对上游分支的领先/落后提交数，如 ↑3 ↓1（fetch 完成后重新分析仓库时更新）
+                if let Some(counts) = git_info.ahead_behind.filter(|counts| !counts.is_synced()) {
+                    let color = if counts.behind > 0 { Color::Yellow } else { Color::Cyan };
+                    spans.push(Span::styled(format!(" {}", counts), Style::default().fg(color)));
+                }
+                
+                // 最近一次 fetch 的状态；已完成且仓库有领先/落后数时由上面的指示显示
+                let fetch_status = view.git_fetch.get(&project.path)
+                    .filter(|status| !(matches!(status, FetchStatus::Fetched(Some(_))) && git_info.ahead_behind.is_some()));
+                if let Some(status) = fetch_status {
+                    let color = match status {
+                        FetchStatus::Fetched(Some(counts)) if counts.behind > 0 => Color::Yellow,
+                        FetchStatus::Fetched(_) => Color::Green,
+                        FetchStatus::Failed(_) => Color::Red,
+                        FetchStatus::Queued | FetchStatus::Fetching => Color::Gray,
+                    };
+                    spans.push(Span::styled(format!(" [{}]", status), Style::default().fg(color)));
+                }
+                
                 // 最后提交时间
                 if let Some(last_commit) = git_info.last_commit_time {
                     let commit_time = std::time::SystemTime::UNIX_EPOCH + 
@@ -488,18 +1445,23 @@ impl MainScreen {
             })
             .collect();
         
+        let fetching = view.git_fetch.values().filter(|status| status.is_pending()).count();
+        let title = if fetching > 0 {
+            format!("Git 状态 ({} 个仓库，{} 个正在 fetch)", git_projects.len(), fetching)
+        } else {
+            format!("Git 状态 ({} 个仓库，f fetch 选中的仓库，F fetch 全部)", git_projects.len())
+        };
         let list = List::new(items)
-            .block(
-                Block::default()
-                    .title(format!("Git 状态 ({} 个仓库)", git_projects.len()))
-                    .borders(Borders::ALL)
-            );
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .highlight_style(Style::default().bg(Color::DarkGray));
         
-        f.render_widget(list, area);
+        let mut state = ListState::default();
+        state.select(git_projects.iter().position(|project| view.selected_path.as_ref() == Some(&project.path)));
+        f.render_stateful_widget(list, area, &mut state);
     }
     
     /// 绘制项目详情内容
-    fn draw_project_details(&self, f: &mut Frame, area: Rect, project: &Project) {
+    fn draw_project_details(&self, f: &mut Frame, area: Rect, project: &Project, ignore_source: Option<&IgnoreSource>) {
         // 创建两列布局
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
@@ -510,14 +1472,14 @@ impl MainScreen {
             .split(area);
         
         // 左列：基本信息
-        self.draw_basic_info(f, chunks[0], project);
+        self.draw_basic_info(f, chunks[0], project, ignore_source);
         
         // 右列：Git 信息和统计
         self.draw_extended_info(f, chunks[1], project);
     }
     
     /// 绘制基本信息
-    fn draw_basic_info(&self, f: &mut Frame, area: Rect, project: &Project) {
+    fn draw_basic_info(&self, f: &mut Frame, area: Rect, project: &Project, ignore_source: Option<&IgnoreSource>) {
         let mut info_text = vec![
             Line::from(vec![
                 Span::styled("基本信息", Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan))
@@ -532,7 +1494,8 @@ impl MainScreen {
         
         info_text.push(Line::from(vec![
             Span::styled("项目路径: ", Style::default().fg(Color::White)),
-            Span::raw(project.path.display().to_string()),
+            // 减去边框和标签的宽度
+            Span::raw(path_format::format_path(&project.path, area.width.saturating_sub(12) as usize)),
         ]));
         
         info_text.push(Line::from(vec![
@@ -540,26 +1503,51 @@ impl MainScreen {
             Span::styled(project.type_display_name(), Style::default().fg(Color::Yellow)),
         ]));
         
-        info_text.push(Line::from(vec![
+        let mut size_spans = vec![
             Span::styled("项目大小: ", Style::default().fg(Color::White)),
-            Span::styled(size_format::format_size(project.size()), Style::default().fg(Color::Green)),
-        ]));
+            Span::styled(project.size().to_string(), Style::default().fg(Color::Green)),
+        ];
+        if project.size_is_estimate {
+            size_spans.push(Span::styled(" (估算值，正在精确计算...)", Style::default().fg(Color::DarkGray)));
+        } else if project.size_truncated {
+            size_spans.push(Span::styled(" (已达到统计上限，实际更大)", Style::default().fg(Color::Yellow)));
+        }
+        info_text.push(Line::from(size_spans));
         
         // 依赖大小显示（根据计算状态）
         let dependency_status = project.dependency_status_display();
         if dependency_status.is_empty() {
             info_text.push(Line::from(vec![
                 Span::styled("依赖大小: ", Style::default().fg(Color::White)),
-                Span::styled(size_format::format_size(project.dependency_size()), Style::default().fg(Color::Yellow)),
+                Span::styled(project.dependency_size().to_string(), Style::default().fg(Color::Yellow)),
             ]));
         } else {
             info_text.push(Line::from(vec![
                 Span::styled("依赖大小: ", Style::default().fg(Color::White)),
-                Span::styled(size_format::format_size(project.dependency_size()), Style::default().fg(Color::Yellow)),
+                Span::styled(project.dependency_size().to_string(), Style::default().fg(Color::Yellow)),
                 Span::styled(format!(" ({})", dependency_status), Style::default().fg(Color::Gray)),
             ]));
         }
         
+        if let Some(provider) = project.cloud_sync {
+            let mut sync_spans = vec![
+                Span::styled("云同步: ", Style::default().fg(Color::White)),
+                Span::styled(provider.display_name(), Style::default().fg(Color::Yellow)),
+            ];
+            if !project.dependency_size().is_zero() {
+                sync_spans.push(Span::styled(" (依赖目录也会被同步，见统计页建议)", Style::default().fg(Color::Red)));
+            }
+            info_text.push(Line::from(sync_spans));
+        }
+        
+        if !project.tags.is_empty() {
+            let tags: Vec<String> = project.tags.iter().map(|tag| format!("#{}", tag)).collect();
+            info_text.push(Line::from(vec![
+                Span::styled("标签: ", Style::default().fg(Color::White)),
+                Span::styled(tags.join(" "), Style::default().fg(Color::Cyan)),
+            ]));
+        }
+        
         let modified_time = std::time::SystemTime::UNIX_EPOCH + 
             std::time::Duration::from_secs(project.last_modified.timestamp() as u64);
         info_text.push(Line::from(vec![
@@ -569,10 +1557,9 @@ impl MainScreen {
         
         info_text.push(Line::from(vec![
             Span::styled("状态: ", Style::default().fg(Color::White)),
-            if project.is_ignored {
-                Span::styled("已忽略", Style::default().fg(Color::Red))
-            } else {
-                Span::styled("活跃", Style::default().fg(Color::Green))
+            match ignore_source {
+                Some(source) => Span::styled(format!("已忽略（{}）", source.describe()), Style::default().fg(Color::Red)),
+                None => Span::styled("活跃", Style::default().fg(Color::Green)),
             },
         ]));
         
@@ -606,6 +1593,13 @@ impl MainScreen {
                 ]));
             }
             
+            if let Some(main_repo) = &git_info.worktree_of {
+                info_text.push(Line::from(vec![
+                    Span::styled("工作树，主仓库: ", Style::default().fg(Color::White)),
+                    Span::styled(path_format::format_path(main_repo, area.width.saturating_sub(18) as usize), Style::default().fg(Color::Cyan)),
+                ]));
+            }
+            
             if let Some(last_commit) = git_info.last_commit_time {
                 let commit_time = std::time::SystemTime::UNIX_EPOCH + 
                     std::time::Duration::from_secs(last_commit.timestamp() as u64);
@@ -617,7 +1611,9 @@ impl MainScreen {
             
             info_text.push(Line::from(vec![
                 Span::styled("工作区状态: ", Style::default().fg(Color::White)),
-                if project.has_uncommitted_changes() {
+                if git_info.is_bare {
+                    Span::styled("裸仓库（无工作区）", Style::default().fg(Color::Cyan))
+                } else if project.has_uncommitted_changes() {
                     Span::styled("有未提交更改", Style::default().fg(Color::Red))
                 } else {
                     Span::styled("清洁", Style::default().fg(Color::Green))
@@ -659,18 +1655,44 @@ impl MainScreen {
             ]));
         }
         
-        if project.dependency_size() > 0 {
+        if !project.dependency_size().is_zero() {
             info_text.push(Line::from(vec![
                 Span::styled("依赖占比: ", Style::default().fg(Color::White)),
                 Span::styled(
                     format!("{:.1}%", 
-                        (project.dependency_size() as f64 / (project.size() + project.dependency_size()) as f64) * 100.0
+                        (project.dependency_size().bytes() as f64 / (project.size() + project.dependency_size()).bytes() as f64) * 100.0
                     ),
                     Style::default().fg(Color::Yellow)
                 ),
             ]));
         }
         
+        // 依赖包统计（node_modules、target）
+        for dependency in &project.dependencies {
+            let Some(stats) = dependency.packages.as_ref().filter(|stats| !stats.is_empty()) else {
+                continue;
+            };
+            info_text.push(Line::from(""));
+            info_text.push(Line::from(vec![
+                Span::styled(
+                    format!("依赖包 ({})", dependency.dependency_type.display_name()),
+                    Style::default().add_modifier(Modifier::BOLD).fg(Color::Cyan),
+                )
+            ]));
+            info_text.push(Line::from(vec![
+                Span::styled("直接依赖: ", Style::default().fg(Color::White)),
+                Span::styled(stats.direct_count.to_string(), Style::default().fg(Color::Green)),
+                Span::styled("  间接依赖: ", Style::default().fg(Color::White)),
+                Span::styled(stats.transitive_count.to_string(), Style::default().fg(Color::Yellow)),
+            ]));
+            for package in &stats.largest {
+                info_text.push(Line::from(vec![
+                    Span::styled(format!("{:>10}  ", package.size), Style::default().fg(Color::Yellow)),
+                    Span::raw(package.name.clone()),
+                ]));
+            }
+        }
+        
         let info_paragraph = Paragraph::new(info_text)
             .block(Block::default().title("Git & 统计").borders(Borders::ALL))
             .style(Style::default().fg(Color::White));
@@ -683,4 +1705,14 @@ impl Default for MainScreen {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Git 列中各状态标记的颜色：游离 HEAD 和已暂存/修改的更改最需要注意
+fn git_badge_color(badge: GitBadge) -> Color {
+    match badge {
+        GitBadge::Detached => Color::Magenta,
+        GitBadge::Staged | GitBadge::Uncommitted => Color::Red,
+        GitBadge::Modified => Color::LightRed,
+        GitBadge::UntrackedOnly => Color::Yellow,
+    }
+}
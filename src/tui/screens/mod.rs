@@ -1,3 +1,4 @@
+pub mod disk_usage;
 pub mod main_screen;
 
 pub use main_screen::MainScreen;
\ No newline at end of file
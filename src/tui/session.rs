@@ -0,0 +1,144 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::models::Project;
+
+/// 项目列表快照：用于启动时在扫描完成前立即显示列表，也可手动保存后离线浏览
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// 快照保存时间
+    pub saved_at: DateTime<Utc>,
+
+    /// 扫描的根目录
+    #[serde(default)]
+    pub scan_paths: Vec<String>,
+
+    /// 快照中的项目
+    pub projects: Vec<Project>,
+}
+
+impl SessionSnapshot {
+    /// 由当前项目列表创建快照（不保存按需加载的详情数据）
+    pub fn new(projects: &[Project]) -> Self {
+        let projects = projects.iter()
+            .cloned()
+            .map(|mut project| {
+                project.cleanup_suggestions = None;
+                project.recent_commits = None;
+                project.size_is_estimate = false;
+                project
+            })
+            .collect();
+
+        Self {
+            saved_at: Utc::now(),
+            scan_paths: Vec::new(),
+            projects,
+        }
+    }
+
+    /// 记录扫描的根目录
+    pub fn with_scan_paths(mut self, scan_paths: Vec<String>) -> Self {
+        self.scan_paths = scan_paths;
+        self
+    }
+
+    /// 默认快照文件路径
+    pub fn default_path() -> Result<PathBuf> {
+        let mut path = dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("无法找到配置目录"))?;
+        path.push("project-manager-cli");
+        path.push("session.json");
+        Ok(path)
+    }
+
+    /// 从文件加载快照
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 保存快照到文件
+    pub fn save_to_file(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// 只保留位于指定扫描路径下的项目
+    pub fn projects_under(self, scan_paths: &[String]) -> Vec<Project> {
+        self.projects.into_iter()
+            .filter(|project| scan_paths.iter().any(|root| project.path.starts_with(root)))
+            .collect()
+    }
+}
+
+/// 用新扫描结果替换旧列表：仍存在的项目沿用之前的大小和 Git 信息，直到重新计算完成
+pub fn reconcile(fresh: Vec<Project>, previous: &[Project]) -> Vec<Project> {
+    let previous: HashMap<&Path, &Project> = previous.iter()
+        .map(|project| (project.path.as_path(), project))
+        .collect();
+
+    fresh.into_iter()
+        .map(|mut project| {
+            if let Some(old) = previous.get(project.path.as_path()) {
+                project.code_size = old.code_size;
+                project.total_size = old.total_size;
+                project.gitignore_excluded_size = old.gitignore_excluded_size;
+                project.code_file_count = old.code_file_count;
+                project.dependency_file_count = old.dependency_file_count;
+                project.total_file_count = old.total_file_count;
+                project.gitignore_excluded_file_count = old.gitignore_excluded_file_count;
+                project.last_modified = old.last_modified;
+                project.git_info = old.git_info.clone();
+                project.cached_dependency_size = old.cached_dependency_size;
+                project.description = project.description.or_else(|| old.description.clone());
+            }
+            project
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ByteSize;
+    use crate::tui::fixtures::ProjectFixture;
+    use tempfile::tempdir;
+
+    fn project(path: &str, code_size: u64) -> Project {
+        ProjectFixture::new(path).sizes(code_size, 0).build()
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("session.json");
+
+        let snapshot = SessionSnapshot::new(&[project("/code/api", 100), project("/other/web", 50)]);
+        snapshot.save_to_file(&path).unwrap();
+
+        let loaded = SessionSnapshot::load_from_file(&path).unwrap();
+        let projects = loaded.projects_under(&["/code".to_string()]);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].code_size, ByteSize::new(100));
+    }
+
+    #[test]
+    fn test_reconcile_keeps_previous_sizes() {
+        let previous = vec![project("/code/api", 100), project("/code/removed", 10)];
+        let fresh = vec![project("/code/api", 0), project("/code/new", 0)];
+
+        let merged = reconcile(fresh, &previous);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].code_size, ByteSize::new(100));
+        assert_eq!(merged[1].name, "new");
+        assert_eq!(merged[1].code_size, ByteSize::ZERO);
+    }
+}
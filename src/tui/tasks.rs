@@ -0,0 +1,376 @@
+// 后台任务登记表：统一记录扫描、大小计算、清理等后台任务的状态和进度，供任务面板显示和逐个取消
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures::FutureExt;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// 保留的已结束任务数量，超出后丢弃最早结束的
+const FINISHED_HISTORY: usize = 50;
+
+/// 任务编号，按创建顺序递增
+pub type TaskId = u64;
+
+/// 任务类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskKind {
+    /// 查找项目
+    Scan,
+
+    /// 计算项目详细信息（依赖大小、Git 状态）
+    Details,
+
+    /// 计算项目大小
+    Size,
+
+    /// 计算清理计划
+    CleanupPlan,
+
+    /// 分析清理建议
+    Suggestions,
+
+    /// 清理依赖目录
+    Clean,
+
+    /// 删除项目
+    Delete,
+
+    /// 清理后归档项目
+    Archive,
+
+    /// 统计项目的磁盘占用明细
+    DiskUsage,
+
+    /// 从 origin fetch 仓库
+    Fetch,
+}
+
+impl TaskKind {
+    /// 显示名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskKind::Scan => "扫描",
+            TaskKind::Details => "分析",
+            TaskKind::Size => "计算大小",
+            TaskKind::CleanupPlan => "清理计划",
+            TaskKind::Suggestions => "清理建议",
+            TaskKind::Clean => "清理",
+            TaskKind::Delete => "删除",
+            TaskKind::Archive => "归档",
+            TaskKind::DiskUsage => "磁盘占用",
+            TaskKind::Fetch => "Git fetch",
+        }
+    }
+
+    /// 是否会修改磁盘内容（退出时需要确认）
+    pub fn is_destructive(&self) -> bool {
+        matches!(self, TaskKind::Clean | TaskKind::Delete | TaskKind::Archive)
+    }
+}
+
+/// 任务状态
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskStatus {
+    /// 已创建，尚未开始运行
+    Pending,
+
+    /// 运行中
+    Running,
+
+    /// 已完成，附带结果说明
+    Succeeded(String),
+
+    /// 失败，附带原因
+    Failed(String),
+
+    /// 已取消
+    Cancelled,
+}
+
+impl TaskStatus {
+    /// 任务是否已结束
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, TaskStatus::Pending | TaskStatus::Running)
+    }
+}
+
+/// 任务与登记表共享的状态
+#[derive(Debug, Default)]
+struct SharedState {
+    /// 任务是否已开始运行
+    started: AtomicBool,
+
+    /// 任务报告的进度
+    progress: Mutex<Option<String>>,
+}
+
+/// 传给任务的上下文：取消令牌和进度报告
+#[derive(Debug, Clone)]
+pub struct TaskContext {
+    /// 任务被取消（或应用退出）时触发
+    pub token: CancellationToken,
+
+    shared: Arc<SharedState>,
+}
+
+impl TaskContext {
+    /// 报告进度，任务面板中显示
+    pub fn set_progress(&self, progress: impl Into<String>) {
+        if let Ok(mut current) = self.shared.progress.lock() {
+            *current = Some(progress.into());
+        }
+    }
+}
+
+/// 登记的后台任务
+#[derive(Debug)]
+pub struct Task {
+    /// 任务编号
+    pub id: TaskId,
+
+    /// 任务类型
+    pub kind: TaskKind,
+
+    /// 任务对象，如项目名称或路径
+    pub target: String,
+
+    /// 当前状态（已结束的任务在 `reap` 时更新）
+    pub status: TaskStatus,
+
+    /// 创建时间
+    pub created_at: Instant,
+
+    /// 结束时的运行时长
+    pub duration: Option<Duration>,
+
+    token: CancellationToken,
+    shared: Arc<SharedState>,
+    handle: Option<JoinHandle<Result<String, String>>>,
+}
+
+impl Task {
+    /// 任务说明，例如 "清理 api"
+    pub fn label(&self) -> String {
+        format!("{} {}", self.kind.label(), self.target)
+    }
+
+    /// 任务最近报告的进度
+    pub fn progress(&self) -> Option<String> {
+        self.shared.progress.lock().ok().and_then(|progress| progress.clone())
+    }
+
+    /// 已运行的时长（结束的任务为总时长）
+    pub fn elapsed(&self) -> Duration {
+        self.duration.unwrap_or_else(|| self.created_at.elapsed())
+    }
+
+    /// 后台任务是否已退出（状态可能尚未在 `reap` 中更新）
+    pub fn is_done(&self) -> bool {
+        self.handle.as_ref().is_none_or(|handle| handle.is_finished())
+    }
+
+    /// 根据后台任务的运行情况更新状态
+    fn refresh(&mut self) {
+        if self.status.is_finished() {
+            return;
+        }
+        let Some(handle) = self.handle.as_mut() else {
+            return;
+        };
+        if !handle.is_finished() {
+            if self.shared.started.load(Ordering::Relaxed) {
+                self.status = TaskStatus::Running;
+            }
+            return;
+        }
+
+        let result = handle.now_or_never();
+        self.handle = None;
+        self.duration = Some(self.created_at.elapsed());
+        self.status = match result {
+            Some(Ok(Ok(message))) => TaskStatus::Succeeded(message),
+            Some(Ok(Err(_))) if self.token.is_cancelled() => TaskStatus::Cancelled,
+            Some(Ok(Err(message))) => TaskStatus::Failed(message),
+            Some(Err(e)) => TaskStatus::Failed(format!("任务异常终止 ({})", e)),
+            None => TaskStatus::Failed("无法读取任务结果".to_string()),
+        };
+    }
+}
+
+/// 后台任务登记表
+#[derive(Debug, Default)]
+pub struct TaskRegistry {
+    next_id: TaskId,
+    tasks: Vec<Task>,
+}
+
+impl TaskRegistry {
+    /// 在后台运行任务并登记
+    ///
+    /// 任务使用 `parent` 的子令牌，单独取消任务或触发 `parent` 都会取消它。一般任务在下一个等待点退出；
+    /// 破坏性操作不会被中途丢弃，需要自行检查令牌，以便把已移走的目录恢复原状。
+    pub fn spawn<F, Fut>(&mut self, kind: TaskKind, target: impl Into<String>, parent: &CancellationToken, task: F) -> TaskId
+    where
+        F: FnOnce(TaskContext) -> Fut,
+        Fut: Future<Output = Result<String, String>> + Send + 'static,
+    {
+        let token = parent.child_token();
+        let shared = Arc::new(SharedState::default());
+        let future = task(TaskContext { token: token.clone(), shared: shared.clone() });
+
+        let cancel = token.clone();
+        let started = shared.clone();
+        let handle = tokio::spawn(async move {
+            started.started.store(true, Ordering::Relaxed);
+            if kind.is_destructive() {
+                return future.await;
+            }
+            tokio::select! {
+                biased;
+                _ = cancel.cancelled() => Err("已取消".to_string()),
+                result = future => result,
+            }
+        });
+
+        self.next_id += 1;
+        self.tasks.push(Task {
+            id: self.next_id,
+            kind,
+            target: target.into(),
+            status: TaskStatus::Pending,
+            created_at: Instant::now(),
+            duration: None,
+            token,
+            shared,
+            handle: Some(handle),
+        });
+        self.next_id
+    }
+
+    /// 更新所有任务的状态，并丢弃过旧的已结束任务
+    pub fn reap(&mut self) {
+        for task in &mut self.tasks {
+            task.refresh();
+        }
+
+        let finished = self.tasks.iter().filter(|task| task.status.is_finished()).count();
+        let mut excess = finished.saturating_sub(FINISHED_HISTORY);
+        if excess > 0 {
+            // 任务按创建顺序排列，先丢弃最早创建的已结束任务
+            self.tasks.retain(|task| {
+                let drop = excess > 0 && task.status.is_finished();
+                if drop {
+                    excess -= 1;
+                }
+                !drop
+            });
+        }
+    }
+
+    /// 取消任务，任务已结束或不存在时返回 false
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        match self.get(id) {
+            Some(task) if !task.status.is_finished() && !task.is_done() => {
+                task.token.cancel();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 按编号查找任务
+    pub fn get(&self, id: TaskId) -> Option<&Task> {
+        self.tasks.iter().find(|task| task.id == id)
+    }
+
+    /// 未结束的任务
+    pub fn active(&self) -> impl Iterator<Item = &Task> {
+        self.tasks.iter().filter(|task| !task.is_done())
+    }
+
+    /// 面板中的显示顺序：未结束的任务按创建顺序在前，已结束的任务按结束先后倒序在后
+    pub fn display_order(&self) -> Vec<&Task> {
+        let mut finished: Vec<&Task> = self.tasks.iter().filter(|task| task.status.is_finished()).collect();
+        finished.sort_by_key(|task| std::cmp::Reverse(task.created_at + task.elapsed()));
+        self.tasks.iter()
+            .filter(|task| !task.status.is_finished())
+            .chain(finished)
+            .collect()
+    }
+
+    /// 取消所有任务，并在超时前等待它们退出
+    pub async fn shutdown(&mut self, timeout: Duration) {
+        let handles: Vec<_> = self.tasks.iter_mut()
+            .filter_map(|task| {
+                task.token.cancel();
+                task.handle.take()
+            })
+            .collect();
+        if handles.is_empty() {
+            return;
+        }
+
+        tracing::debug!("等待 {} 个后台任务退出", handles.len());
+        if tokio::time::timeout(timeout, futures::future::join_all(handles)).await.is_err() {
+            tracing::debug!("等待后台任务退出超时，任务将被丢弃");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 等待任务退出并更新状态
+    async fn settle(registry: &mut TaskRegistry, id: TaskId) {
+        while !registry.get(id).unwrap().is_done() {
+            tokio::task::yield_now().await;
+        }
+        registry.reap();
+    }
+
+    #[tokio::test]
+    async fn test_task_registry_tracks_and_cancels() {
+        let parent = CancellationToken::new();
+        let mut registry = TaskRegistry::default();
+
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+        let clean = registry.spawn(TaskKind::Clean, "api", &parent, |task| async move {
+            task.set_progress("1/2");
+            let _ = released.await;
+            Ok("已清理项目 api".to_string())
+        });
+        let scan = registry.spawn(TaskKind::Scan, "/code", &parent, |task| async move {
+            task.token.cancelled().await;
+            Ok(String::new())
+        });
+        let failed = registry.spawn(TaskKind::Delete, "web", &parent, |_| async { Err("权限不足".to_string()) });
+
+        settle(&mut registry, failed).await;
+        assert_eq!(registry.get(failed).unwrap().status, TaskStatus::Failed("权限不足".to_string()));
+        assert_eq!(registry.active().count(), 2);
+
+        // 单独取消一个任务不影响其他任务
+        assert!(registry.cancel(scan));
+        settle(&mut registry, scan).await;
+        assert_eq!(registry.get(scan).unwrap().status, TaskStatus::Cancelled);
+        assert!(!registry.cancel(scan));
+
+        while registry.get(clean).unwrap().progress().is_none() {
+            tokio::task::yield_now().await;
+        }
+        registry.reap();
+        assert_eq!(registry.get(clean).unwrap().status, TaskStatus::Running);
+        assert_eq!(registry.get(clean).unwrap().progress().as_deref(), Some("1/2"));
+        let order: Vec<TaskId> = registry.display_order().iter().map(|task| task.id).collect();
+        assert_eq!(order[0], clean);
+
+        release.send(()).unwrap();
+        settle(&mut registry, clean).await;
+        assert_eq!(registry.get(clean).unwrap().status, TaskStatus::Succeeded("已清理项目 api".to_string()));
+    }
+}
@@ -0,0 +1,112 @@
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// 剪贴板写入方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CopyMethod {
+    /// 本地剪贴板命令（pbcopy、wl-copy、xclip 等）
+    Native,
+
+    /// OSC 52 终端转义序列
+    Osc52,
+}
+
+/// 本地剪贴板命令及参数，按优先级排列
+const NATIVE_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// 复制文本到剪贴板
+///
+/// 本地会话优先使用系统剪贴板命令；SSH 会话或本地命令不可用时回退到 OSC 52，
+/// 由支持该序列的终端写入本地剪贴板。
+pub fn copy(text: &str) -> io::Result<CopyMethod> {
+    if !is_ssh_session() && copy_native(text) {
+        return Ok(CopyMethod::Native);
+    }
+
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52_sequence(text, std::env::var_os("TMUX").is_some()).as_bytes())?;
+    stdout.flush()?;
+    Ok(CopyMethod::Osc52)
+}
+
+/// 当前是否运行在 SSH 会话中
+fn is_ssh_session() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+/// 尝试使用本地剪贴板命令复制，成功返回 true
+fn copy_native(text: &str) -> bool {
+    NATIVE_COMMANDS.iter().any(|(program, args)| {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return false;
+        };
+
+        let written = child.stdin.take()
+            .map(|mut stdin| stdin.write_all(text.as_bytes()).is_ok())
+            .unwrap_or(false);
+
+        child.wait().map(|status| status.success()).unwrap_or(false) && written
+    })
+}
+
+/// 生成 OSC 52 剪贴板序列；在 tmux 中需要用 DCS 透传包裹
+pub fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()));
+
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+/// 标准 Base64 编码（带填充）
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        encoded.push(ALPHABET[(triple >> 18) as usize & 0x3f] as char);
+        encoded.push(ALPHABET[(triple >> 12) as usize & 0x3f] as char);
+        encoded.push(if chunk.len() > 1 { ALPHABET[(triple >> 6) as usize & 0x3f] as char } else { '=' });
+        encoded.push(if chunk.len() > 2 { ALPHABET[triple as usize & 0x3f] as char } else { '=' });
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode("项目".as_bytes()), "6aG555uu");
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("foo", false), "\x1b]52;c;Zm9v\x07");
+        assert_eq!(osc52_sequence("foo", true), "\x1bPtmux;\x1b\x1b]52;c;Zm9v\x07\x1b\\");
+    }
+}
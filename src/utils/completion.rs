@@ -0,0 +1,54 @@
+// Shell 补全的数据源：从上次会话缓存的项目列表生成补全候选
+
+use crate::models::Project;
+
+/// 生成 zsh `_describe` 使用的补全候选，每行一个 `路径:名称 (类型)`
+///
+/// 已不存在的项目不列出；路径中的冒号和反斜杠按 `_describe` 的要求转义。
+pub fn zsh_candidates(projects: &[Project]) -> String {
+    let mut projects: Vec<&Project> = projects.iter()
+        .filter(|project| project.path.exists())
+        .collect();
+    projects.sort_by(|a, b| a.path.cmp(&b.path));
+
+    projects.iter()
+        .map(|project| format!(
+            "{}:{} ({})\n",
+            escape_value(&project.path.to_string_lossy()),
+            project.name,
+            project.type_display_name()
+        ))
+        .collect()
+}
+
+/// 转义 `_describe` 候选值中的反斜杠和冒号
+fn escape_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(':', "\\:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::ProjectFixture;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_zsh_candidates() {
+        let temp_dir = tempdir().unwrap();
+        let web = temp_dir.path().join("web:v2");
+        let api = temp_dir.path().join("api");
+        std::fs::create_dir(&web).unwrap();
+        std::fs::create_dir(&api).unwrap();
+
+        let projects = [
+            ProjectFixture::new(&web.to_string_lossy()).build(),
+            ProjectFixture::new(&api.to_string_lossy()).build(),
+            ProjectFixture::new(&temp_dir.path().join("deleted").to_string_lossy()).build(),
+        ];
+
+        let root = temp_dir.path().to_string_lossy().replace(':', "\\:");
+        assert_eq!(zsh_candidates(&projects), format!(
+            "{root}/api:api (Rust)\n{root}/web\\:v2:web:v2 (Rust)\n"
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+/// 转义 CSV 字段：包含逗号、引号或换行时加引号
+pub fn escape_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 转义 TSV 字段：TSV 没有引号规则，制表符和换行替换为空格
+pub fn escape_tsv_field(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+/// 分隔符格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// 逗号分隔（CSV）
+    Comma,
+
+    /// 制表符分隔（TSV）
+    Tab,
+}
+
+impl Delimiter {
+    /// 按该格式转义字段
+    pub fn escape(&self, value: &str) -> String {
+        match self {
+            Delimiter::Comma => escape_field(value),
+            Delimiter::Tab => escape_tsv_field(value),
+        }
+    }
+
+    /// 拼接一行（字段需已转义），以换行结尾
+    pub fn join(&self, fields: &[String]) -> String {
+        let separator = match self {
+            Delimiter::Comma => ",",
+            Delimiter::Tab => "\t",
+        };
+        fields.join(separator) + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("api"), "api");
+        assert_eq!(escape_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_tsv_row() {
+        let fields = [Delimiter::Tab.escape("a,b"), Delimiter::Tab.escape("tab\there")];
+        assert_eq!(Delimiter::Tab.join(&fields), "a,b\ttab here\n");
+    }
+}
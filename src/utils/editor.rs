@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::settings::EditorConfig;
+use crate::models::{Project, ProjectType};
+
+/// 选择项目打开命令时使用的类型键，按优先级排列
+///
+/// 根目录下有 `.ipynb` 文件的项目优先使用 `notebook`，混合项目依次使用各子类型。
+pub fn project_type_keys(project: &Project) -> Vec<String> {
+    let mut keys = Vec::new();
+    if has_notebooks(&project.path) {
+        keys.push("notebook".to_string());
+    }
+
+    match &project.project_type {
+        ProjectType::Mixed(types) => keys.extend(types.iter().map(|t| t.as_str().to_string())),
+        project_type => keys.push(project_type.as_str().to_string()),
+    }
+    keys
+}
+
+/// 项目根目录下是否有 Jupyter 笔记本
+fn has_notebooks(path: &Path) -> bool {
+    std::fs::read_dir(path)
+        .map(|entries| entries
+            .filter_map(|entry| entry.ok())
+            .any(|entry| entry.path().extension().is_some_and(|ext| ext == "ipynb")))
+        .unwrap_or(false)
+}
+
+/// 按 类型命令 → 默认命令 的顺序选出第一个可用的打开命令
+pub fn select_command<'a>(
+    config: &'a EditorConfig,
+    type_keys: &[String],
+    is_available: impl Fn(&str) -> bool,
+) -> Option<&'a str> {
+    type_keys.iter()
+        .filter_map(|key| config.by_type.get(key))
+        .flatten()
+        .chain(&config.default)
+        .map(|command| command.as_str())
+        .find(|command| command.split_whitespace().next().is_some_and(&is_available))
+}
+
+/// 在 PATH 中查找可执行文件
+pub fn find_in_path(program: &str) -> Option<PathBuf> {
+    if program.contains(std::path::MAIN_SEPARATOR) {
+        let path = PathBuf::from(program);
+        return path.is_file().then_some(path);
+    }
+
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .flat_map(|dir| {
+            let candidate = dir.join(program);
+            let exe = candidate.with_extension(std::env::consts::EXE_EXTENSION);
+            [candidate, exe]
+        })
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::fixtures::ProjectFixture;
+    use std::collections::HashMap;
+
+    fn editor_config() -> EditorConfig {
+        EditorConfig {
+            default: vec!["nvim".to_string(), "vim".to_string()],
+            by_type: HashMap::from([
+                ("rust".to_string(), vec!["nvim".to_string()]),
+                ("nodejs".to_string(), vec!["code".to_string()]),
+                ("notebook".to_string(), vec!["jupyter lab".to_string()]),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_select_command_by_type_with_fallback() {
+        let config = editor_config();
+        let all = |_: &str| true;
+        let keys = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(select_command(&config, &keys(&["nodejs"]), all), Some("code"));
+        assert_eq!(select_command(&config, &keys(&["notebook", "python"]), all), Some("jupyter lab"));
+        assert_eq!(select_command(&config, &keys(&["go"]), all), Some("nvim"));
+
+        // 类型命令未安装时回退到默认命令
+        assert_eq!(select_command(&config, &keys(&["notebook"]), |p| p == "vim"), Some("vim"));
+        assert_eq!(select_command(&config, &keys(&["nodejs"]), |_| false), None);
+    }
+
+    #[test]
+    fn test_project_type_keys() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("analysis.ipynb"), "{}").unwrap();
+
+        let notebook = ProjectFixture::new(temp_dir.path().to_str().unwrap())
+            .project_type(ProjectType::Python)
+            .build();
+        assert_eq!(project_type_keys(&notebook), ["notebook", "python"]);
+
+        let mixed = ProjectFixture::new("/work/app")
+            .project_type(ProjectType::Mixed(vec![ProjectType::Rust, ProjectType::NodeJs]))
+            .build();
+        assert_eq!(project_type_keys(&mixed), ["rust", "nodejs"]);
+    }
+}
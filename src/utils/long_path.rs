@@ -0,0 +1,62 @@
+// Windows 长路径处理：深层 node_modules 的路径常超过 MAX_PATH（260 个字符）
+
+use std::path::{Path, PathBuf};
+
+/// 扩展长度路径前缀
+const VERBATIM_PREFIX: &str = r"\\?\";
+
+/// UNC 路径的扩展长度前缀
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+/// 转换为不受 MAX_PATH 限制的扩展长度路径（`\\?\C:\...`、`\\?\UNC\server\share\...`）
+///
+/// 只在 Windows 上生效；其他平台、相对路径和已带前缀的路径原样返回。
+/// 扩展长度路径不再解析 `.`、`..` 和 `/`，因此先转换为规范化的绝对路径。
+pub fn extended(path: &Path) -> PathBuf {
+    if !cfg!(windows) {
+        return path.to_path_buf();
+    }
+
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    match absolute.to_str().and_then(to_verbatim) {
+        Some(verbatim) => PathBuf::from(verbatim),
+        None => absolute,
+    }
+}
+
+/// 为 Windows 绝对路径字符串加上扩展长度前缀，不是绝对路径或已带前缀时返回 None
+fn to_verbatim(path: &str) -> Option<String> {
+    if path.starts_with(VERBATIM_PREFIX) || path.starts_with(r"\\.\") {
+        return None;
+    }
+
+    let path = path.replace('/', r"\");
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return Some(format!("{}{}", VERBATIM_UNC_PREFIX, unc));
+    }
+
+    let bytes = path.as_bytes();
+    let is_drive_absolute = bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\';
+    is_drive_absolute.then(|| format!("{}{}", VERBATIM_PREFIX, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_verbatim() {
+        assert_eq!(to_verbatim(r"C:\code\app\node_modules").as_deref(), Some(r"\\?\C:\code\app\node_modules"));
+        assert_eq!(to_verbatim("D:/code/app").as_deref(), Some(r"\\?\D:\code\app"));
+        assert_eq!(to_verbatim(r"\\nas\share\app").as_deref(), Some(r"\\?\UNC\nas\share\app"));
+        assert_eq!(to_verbatim(r"\\?\C:\code"), None);
+        assert_eq!(to_verbatim(r"code\app"), None);
+        assert_eq!(to_verbatim("/home/me/app"), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_extended_is_identity_off_windows() {
+        assert_eq!(extended(Path::new("/home/me/app")), PathBuf::from("/home/me/app"));
+    }
+}
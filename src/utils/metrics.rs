@@ -0,0 +1,237 @@
+// 内部运行指标：各模块用原子计数记录运行中的任务、队列深度、缓存命中和遍历量，供 TUI 调试浮层实时显示
+
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// 只增不减的计数器
+#[derive(Debug)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    pub fn add(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// 表示当前状态的数值，如运行中的任务数、队列深度
+#[derive(Debug)]
+pub struct Gauge(AtomicI64);
+
+impl Gauge {
+    const fn new() -> Self {
+        Self(AtomicI64::new(0))
+    }
+
+    pub fn set(&self, value: i64) {
+        self.0.store(value, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// 加一，返回的守卫被丢弃时减一（用于统计运行中的任务）
+    pub fn track(&self) -> GaugeGuard<'_> {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        GaugeGuard(self)
+    }
+}
+
+/// `Gauge::track` 返回的守卫
+pub struct GaugeGuard<'a>(&'a Gauge);
+
+impl Drop for GaugeGuard<'_> {
+    fn drop(&mut self) {
+        self.0.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// 全部运行指标
+#[derive(Debug)]
+pub struct Metrics {
+    /// 项目发现：遍历的条目数
+    pub walker_entries: Counter,
+
+    /// 项目发现：遍历的目录数
+    pub walker_dirs: Counter,
+
+    /// 项目发现：结果通道中等待处理的消息数
+    pub walker_queue_depth: Gauge,
+
+    /// 项目发现：运行中的目录检测任务
+    pub walker_tasks: Gauge,
+
+    /// 大小计算：运行中的元数据读取任务
+    pub size_tasks: Gauge,
+
+    /// 大小计算：已读取元数据的文件数
+    pub size_files: Counter,
+
+    /// 大小计算：结果通道中等待处理的文件数
+    pub size_queue_depth: Gauge,
+
+    /// 大小计算：发现的失效符号链接数
+    pub size_broken_links: Counter,
+
+    /// 大小缓存命中次数
+    pub cache_hits: Counter,
+
+    /// 大小缓存未命中次数
+    pub cache_misses: Counter,
+
+    /// Git 分析：运行中的阻塞任务
+    pub git_tasks: Gauge,
+
+    /// TUI：运行中的项目详情计算任务
+    pub detail_tasks: Gauge,
+
+    /// TUI：事件通道中等待处理的事件数
+    pub event_queue_depth: Gauge,
+}
+
+static METRICS: Metrics = Metrics::new();
+
+/// 全局指标
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            walker_entries: Counter::new(),
+            walker_dirs: Counter::new(),
+            walker_queue_depth: Gauge::new(),
+            walker_tasks: Gauge::new(),
+            size_tasks: Gauge::new(),
+            size_files: Counter::new(),
+            size_queue_depth: Gauge::new(),
+            size_broken_links: Counter::new(),
+            cache_hits: Counter::new(),
+            cache_misses: Counter::new(),
+            git_tasks: Gauge::new(),
+            detail_tasks: Gauge::new(),
+            event_queue_depth: Gauge::new(),
+        }
+    }
+
+    /// 读取当前的全部指标
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            walker_entries: self.walker_entries.get(),
+            walker_dirs: self.walker_dirs.get(),
+            walker_queue_depth: self.walker_queue_depth.get(),
+            walker_tasks: self.walker_tasks.get(),
+            size_tasks: self.size_tasks.get(),
+            size_files: self.size_files.get(),
+            size_queue_depth: self.size_queue_depth.get(),
+            size_broken_links: self.size_broken_links.get(),
+            cache_hits: self.cache_hits.get(),
+            cache_misses: self.cache_misses.get(),
+            git_tasks: self.git_tasks.get(),
+            detail_tasks: self.detail_tasks.get(),
+            event_queue_depth: self.event_queue_depth.get(),
+        }
+    }
+}
+
+/// 某一时刻的指标读数
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    pub walker_entries: u64,
+    pub walker_dirs: u64,
+    pub walker_queue_depth: i64,
+    pub walker_tasks: i64,
+    pub size_tasks: i64,
+    pub size_files: u64,
+    pub size_queue_depth: i64,
+    pub size_broken_links: u64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    pub git_tasks: i64,
+    pub detail_tasks: i64,
+    pub event_queue_depth: i64,
+}
+
+/// 指标的一行显示：模块、名称和数值
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricRow {
+    pub module: &'static str,
+    pub name: &'static str,
+    pub value: String,
+}
+
+impl MetricsSnapshot {
+    /// 按模块列出指标；吞吐量根据与上一次读数之间的增量计算
+    pub fn rows(&self, previous: &MetricsSnapshot, elapsed: Duration) -> Vec<MetricRow> {
+        let rate = |current: u64, previous: u64| {
+            let seconds = elapsed.as_secs_f64();
+            if seconds > 0.0 {
+                format!("{:.0}/s", current.saturating_sub(previous) as f64 / seconds)
+            } else {
+                "-".to_string()
+            }
+        };
+        let lookups = self.cache_hits + self.cache_misses;
+        let hit_rate = if lookups > 0 {
+            format!("{} / {}（{:.0}%）", self.cache_hits, lookups, self.cache_hits as f64 * 100.0 / lookups as f64)
+        } else {
+            "0 / 0".to_string()
+        };
+        let row = |module, name, value: String| MetricRow { module, name, value };
+
+        vec![
+            row("walker", "已遍历条目", format!("{}（{}）", self.walker_entries, rate(self.walker_entries, previous.walker_entries))),
+            row("walker", "已遍历目录", format!("{}（{}）", self.walker_dirs, rate(self.walker_dirs, previous.walker_dirs))),
+            row("walker", "通道深度", self.walker_queue_depth.to_string()),
+            row("walker", "运行中的检测", self.walker_tasks.to_string()),
+            row("size", "运行中的任务", self.size_tasks.to_string()),
+            row("size", "已处理文件", format!("{}（{}）", self.size_files, rate(self.size_files, previous.size_files))),
+            row("size", "通道深度", self.size_queue_depth.to_string()),
+            row("size", "失效的符号链接", self.size_broken_links.to_string()),
+            row("cache", "命中", hit_rate),
+            row("git", "运行中的分析", self.git_tasks.to_string()),
+            row("tui", "详情计算任务", self.detail_tasks.to_string()),
+            row("tui", "事件队列深度", self.event_queue_depth.to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_rows() {
+        let metrics = Metrics::new();
+        let previous = metrics.snapshot();
+
+        metrics.walker_entries.add(500);
+        metrics.cache_hits.add(3);
+        metrics.cache_misses.inc();
+        {
+            let _first = metrics.size_tasks.track();
+            let _second = metrics.size_tasks.track();
+            assert_eq!(metrics.size_tasks.get(), 2);
+        }
+        assert_eq!(metrics.size_tasks.get(), 0);
+
+        let rows = metrics.snapshot().rows(&previous, Duration::from_secs(2));
+        let value = |name: &str| rows.iter().find(|row| row.name == name).map(|row| row.value.clone()).unwrap();
+        assert_eq!(value("已遍历条目"), "500（250/s）");
+        assert_eq!(value("命中"), "3 / 4（75%）");
+        assert_eq!(value("运行中的任务"), "0");
+    }
+}
@@ -1,3 +1,12 @@
+pub mod clipboard;
+pub mod completion;
+pub mod csv;
+pub mod editor;
+pub mod long_path;
+pub mod metrics;
+pub mod path_format;
+pub mod path_match;
+pub mod signal;
 pub mod size_format;
+pub mod table;
 pub mod time_format;
-
@@ -0,0 +1,132 @@
+use std::path::{Path, MAIN_SEPARATOR};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// 省略号
+const ELLIPSIS: &str = "…";
+
+/// 将用户主目录前缀替换为 `~`
+pub fn abbreviate_home(path: &Path) -> String {
+    if let Some(home) = dirs::home_dir() {
+        if let Ok(relative) = path.strip_prefix(&home) {
+            if relative.as_os_str().is_empty() {
+                return "~".to_string();
+            }
+            return format!("~{}{}", MAIN_SEPARATOR, relative.display());
+        }
+    }
+    
+    path.display().to_string()
+}
+
+/// 格式化路径用于显示：先缩写主目录，超出宽度时省略中间部分 (例如: "~/Code/…/service-api")
+pub fn format_path(path: &Path, max_width: usize) -> String {
+    shorten_path(&abbreviate_home(path), max_width)
+}
+
+/// 将路径字符串缩短到指定显示宽度，优先保留开头两级和尽可能多的末尾目录
+fn shorten_path(full: &str, max_width: usize) -> String {
+    if full.width() <= max_width {
+        return full.to_string();
+    }
+    
+    let separator = MAIN_SEPARATOR.to_string();
+    let parts: Vec<&str> = full.split(MAIN_SEPARATOR).collect();
+    
+    if parts.len() > 3 {
+        // 开头保留两级，例如 "~/Code" 或 "/Users"
+        let head = parts[..2].join(&separator);
+        let last = parts.len() - 1;
+        
+        // 从末尾开始尽可能多地保留目录
+        let mut tail_start = last;
+        let mut best = None;
+        while tail_start >= 2 {
+            let tail = parts[tail_start..].join(&separator);
+            let candidate = format!("{}{}{}{}{}", head, separator, ELLIPSIS, separator, tail);
+            if candidate.width() > max_width {
+                break;
+            }
+            best = Some(candidate);
+            tail_start -= 1;
+        }
+        
+        if let Some(best) = best {
+            return best;
+        }
+    }
+    
+    // 只保留最后一级目录
+    if let Some(last) = parts.last() {
+        let candidate = format!("{}{}{}", ELLIPSIS, separator, last);
+        if candidate.width() <= max_width {
+            return candidate;
+        }
+    }
+    
+    truncate_start(full, max_width)
+}
+
+/// 从开头截断字符串，保留末尾部分并加上省略号
+fn truncate_start(text: &str, max_width: usize) -> String {
+    if max_width == 0 {
+        return String::new();
+    }
+    
+    let budget = max_width - ELLIPSIS.width();
+    let mut width = 0;
+    let mut kept = Vec::new();
+    for c in text.chars().rev() {
+        let char_width = c.width().unwrap_or(0);
+        if width + char_width > budget {
+            break;
+        }
+        width += char_width;
+        kept.push(c);
+    }
+    
+    let tail: String = kept.into_iter().rev().collect();
+    format!("{}{}", ELLIPSIS, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shorten_path() {
+        // 不超出宽度时保持原样
+        assert_eq!(shorten_path("~/Code/app", 20), "~/Code/app");
+        
+        // 省略中间目录，尽可能保留末尾
+        assert_eq!(
+            shorten_path("~/Code/company/backend/services/service-api", 30),
+            "~/Code/…/services/service-api"
+        );
+        assert_eq!(
+            shorten_path("~/Code/company/backend/services/service-api", 20),
+            "~/Code/…/service-api"
+        );
+        
+        // 宽度不足时只保留最后一级
+        assert_eq!(shorten_path("/Users/me/Code/service-api", 14), "…/service-api");
+        
+        // 最后一级也放不下时从开头截断
+        assert_eq!(shorten_path("/a/very-long-project-name", 8), "…ct-name");
+        
+        // 结果不超过指定宽度
+        assert!(shorten_path("/Users/me/代码/项目/服务接口", 12).width() <= 12);
+    }
+
+    #[test]
+    fn test_abbreviate_home() {
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(abbreviate_home(&home), "~");
+            assert_eq!(
+                abbreviate_home(&home.join("Code")),
+                format!("~{}Code", MAIN_SEPARATOR)
+            );
+        }
+        
+        assert_eq!(abbreviate_home(Path::new("/opt/project")), "/opt/project");
+    }
+}
@@ -0,0 +1,147 @@
+use regex::Regex;
+use std::path::{Component, Path, PathBuf, MAIN_SEPARATOR};
+
+/// 检查模式中是否包含通配符
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// 展开开头的 `~` 为用户主目录，无法获取主目录时原样返回
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with(['/', MAIN_SEPARATOR]) => {
+            home.join(rest.trim_start_matches(['/', MAIN_SEPARATOR]))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+/// 按字面去掉路径中的 `.` 和 `..`（不访问文件系统，用于无法解析的路径）
+pub fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// 检查路径是否匹配忽略模式
+///
+/// - 不含分隔符的模式（如 `tmp`、`*-archive`）与路径中的任意一级目录名比较
+/// - 含分隔符的模式按路径组件前缀匹配：`/tmp` 匹配 `/tmp/a`，但不匹配 `/home/me/tmp-project`
+/// - 支持 `*`、`?`、`[...]` 通配符，`**` 可跨越多级目录
+pub fn matches_ignore_pattern(path: &Path, pattern: &str) -> bool {
+    let pattern = pattern.trim_end_matches(['/', MAIN_SEPARATOR]);
+    if pattern.is_empty() {
+        return false;
+    }
+
+    if !pattern.contains(['/', MAIN_SEPARATOR]) {
+        let Some(regex) = compile(pattern) else {
+            return false;
+        };
+        return path.components()
+            .any(|component| regex.is_match(&component.as_os_str().to_string_lossy()));
+    }
+
+    if !is_glob(pattern) {
+        return path.starts_with(pattern);
+    }
+
+    // 通配模式需匹配路径本身或它的某个祖先目录
+    let Some(regex) = compile(pattern) else {
+        return false;
+    };
+    path.ancestors()
+        .any(|ancestor| regex.is_match(&ancestor.to_string_lossy().replace(MAIN_SEPARATOR, "/")))
+}
+
+/// 将通配模式编译为完整匹配的正则表达式
+fn compile(pattern: &str) -> Option<Regex> {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // "**/" 可以匹配零级或多级目录
+                if chars.peek().is_some_and(|next| *next == '/' || *next == MAIN_SEPARATOR) {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                regex.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    regex.push('^');
+                }
+                for class_char in chars.by_ref() {
+                    if class_char == ']' {
+                        break;
+                    }
+                    if class_char == '\\' {
+                        regex.push('\\');
+                    }
+                    regex.push(class_char);
+                }
+                regex.push(']');
+            }
+            c if c == MAIN_SEPARATOR => regex.push('/'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    regex.push('$');
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_prefix_matching() {
+        assert!(matches_ignore_pattern(Path::new("/tmp"), "/tmp"));
+        assert!(matches_ignore_pattern(Path::new("/tmp/project"), "/tmp"));
+        assert!(matches_ignore_pattern(Path::new("/tmp/project"), "/tmp/"));
+        assert!(!matches_ignore_pattern(Path::new("/home/me/tmp-project"), "/tmp"));
+        assert!(!matches_ignore_pattern(Path::new("/tmpfiles"), "/tmp"));
+    }
+
+    #[test]
+    fn test_normalize_lexically() {
+        assert_eq!(normalize_lexically(Path::new("/code/app/../other/./x")), Path::new("/code/other/x"));
+        assert_eq!(normalize_lexically(Path::new("/code/../../etc")), Path::new("/etc"));
+    }
+
+    #[test]
+    fn test_directory_name_matching() {
+        assert!(matches_ignore_pattern(Path::new("/home/me/tmp/a"), "tmp"));
+        assert!(!matches_ignore_pattern(Path::new("/home/me/tmp-project"), "tmp"));
+        assert!(matches_ignore_pattern(Path::new("/code/old-archive/x"), "*-archive"));
+    }
+
+    #[test]
+    fn test_glob_matching() {
+        assert!(matches_ignore_pattern(Path::new("/code/clients/acme/app"), "/code/clients/*"));
+        assert!(!matches_ignore_pattern(Path::new("/code/internal/app"), "/code/clients/*"));
+        assert!(matches_ignore_pattern(Path::new("/code/a/b/sandbox/x"), "/code/**/sandbox"));
+        assert!(matches_ignore_pattern(Path::new("/code/sandbox"), "/code/**/sandbox"));
+        assert!(matches_ignore_pattern(Path::new("/code/v1/app"), "/code/v[0-9]"));
+        assert!(!matches_ignore_pattern(Path::new("/code/vx/app"), "/code/v[0-9]"));
+        assert!(matches_ignore_pattern(Path::new("/code/a.b/app"), "/code/a?b"));
+        assert!(!matches_ignore_pattern(Path::new("/code/a/b/app"), "/code/a?b"));
+    }
+}
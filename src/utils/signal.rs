@@ -0,0 +1,41 @@
+use tokio_util::sync::CancellationToken;
+
+/// 安装 SIGINT/SIGTERM 处理器，收到信号时取消返回的令牌
+///
+/// 第一次收到信号时只取消令牌，让正在进行的操作安全收尾；再次收到信号时立即退出。
+pub fn install_shutdown_handler() -> CancellationToken {
+    let token = CancellationToken::new();
+    let handler_token = token.clone();
+
+    tokio::spawn(async move {
+        if wait_for_signal().await.is_err() {
+            return;
+        }
+        tracing::info!("收到中断信号，正在取消操作...");
+        handler_token.cancel();
+
+        if wait_for_signal().await.is_ok() {
+            std::process::exit(130);
+        }
+    });
+
+    token
+}
+
+/// 等待 Ctrl+C 或 SIGTERM
+#[cfg(unix)]
+pub async fn wait_for_signal() -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = signal(SignalKind::terminate())?;
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => result,
+        _ = terminate.recv() => Ok(()),
+    }
+}
+
+/// 等待 Ctrl+C
+#[cfg(not(unix))]
+pub async fn wait_for_signal() -> std::io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
@@ -1,5 +1,45 @@
-/// 格式化文件大小为人类可读的格式
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::config::settings::SizeUnit;
+
+/// 全局的大小显示单位（`display.size_unit`），启动时由配置设置
+static DISPLAY_UNIT: AtomicU8 = AtomicU8::new(SizeUnit::Auto as u8);
+
+/// 设置之后所有大小显示使用的单位
+pub fn set_display_unit(unit: SizeUnit) {
+    DISPLAY_UNIT.store(unit as u8, Ordering::Relaxed);
+}
+
+/// 当前的大小显示单位
+pub fn display_unit() -> SizeUnit {
+    match DISPLAY_UNIT.load(Ordering::Relaxed) {
+        unit if unit == SizeUnit::Bytes as u8 => SizeUnit::Bytes,
+        unit if unit == SizeUnit::KB as u8 => SizeUnit::KB,
+        unit if unit == SizeUnit::MB as u8 => SizeUnit::MB,
+        unit if unit == SizeUnit::GB as u8 => SizeUnit::GB,
+        _ => SizeUnit::Auto,
+    }
+}
+
+/// 按配置的显示单位格式化文件大小
 pub fn format_size(bytes: u64) -> String {
+    format_size_as(bytes, display_unit())
+}
+
+/// 按指定单位格式化文件大小，`Auto` 自动选择合适的单位
+pub fn format_size_as(bytes: u64, unit: SizeUnit) -> String {
+    let (divisor, label) = match unit {
+        SizeUnit::Auto => return format_size_auto(bytes),
+        SizeUnit::Bytes => return format!("{} B", bytes),
+        SizeUnit::KB => (1u64 << 10, "KB"),
+        SizeUnit::MB => (1u64 << 20, "MB"),
+        SizeUnit::GB => (1u64 << 30, "GB"),
+    };
+    format!("{:.1} {}", bytes as f64 / divisor as f64, label)
+}
+
+/// 自动选择单位：不足 1 KB 显示字节数，其余保留一位小数
+fn format_size_auto(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     
     if bytes == 0 {
@@ -21,10 +61,39 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+/// 解析人类可读的大小（如 `1MB`、`500 KiB`、`1.5G`、`2048`），单位按 1024 进制
+pub fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        "T" | "TB" | "TIB" => 1 << 40,
+        _ => return None,
+    };
+    
+    Some((number * multiplier as f64) as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("2048"), Some(2048));
+        assert_eq!(parse_size("1MB"), Some(1048576));
+        assert_eq!(parse_size("500 KiB"), Some(512000));
+        assert_eq!(parse_size("1.5g"), Some(1610612736));
+        assert_eq!(parse_size("MB"), None);
+        assert_eq!(parse_size("10 parsecs"), None);
+    }
+
     #[test]
     fn test_format_size() {
         assert_eq!(format_size(0), "0 B");
@@ -34,4 +103,13 @@ mod tests {
         assert_eq!(format_size(1048576), "1.0 MB");
         assert_eq!(format_size(1073741824), "1.0 GB");
     }
+
+    #[test]
+    fn test_format_size_as_fixed_unit() {
+        assert_eq!(format_size_as(1536, SizeUnit::Auto), "1.5 KB");
+        assert_eq!(format_size_as(1536, SizeUnit::Bytes), "1536 B");
+        assert_eq!(format_size_as(1536, SizeUnit::KB), "1.5 KB");
+        assert_eq!(format_size_as(3 << 20, SizeUnit::GB), "0.0 GB");
+        assert_eq!(format_size_as(5 << 30, SizeUnit::MB), "5120.0 MB");
+    }
 }
\ No newline at end of file
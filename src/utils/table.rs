@@ -0,0 +1,185 @@
+// 终端表格：按显示宽度（而不是字节数或字符数）计算列宽，中文项目名不会把后面的列挤歪；
+// 大小列按数值对齐，小数点和单位上下对齐，KB/MB/GB 混排时也能一眼比较大小
+
+use unicode_width::UnicodeWidthStr;
+
+/// 列之间的间隔
+const COLUMN_GAP: &str = "  ";
+
+/// 列的对齐方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    /// 左对齐（名称、路径等文本）
+    Left,
+
+    /// 数值对齐：整数部分右对齐，小数点和单位上下对齐，例如 `512 B`、`1.5 KB`、`+12.0 MB`；
+    /// 不是数值的单元格（如 `-`）整体右对齐
+    Number,
+}
+
+/// 数值单元格拆成的三部分：整数部分（含正负号等前缀）、小数部分（含小数点）、单位（含前面的空格）
+#[derive(Debug, Default, Clone, Copy)]
+struct NumberParts<'a> {
+    integer: &'a str,
+    fraction: &'a str,
+    unit: &'a str,
+}
+
+impl<'a> NumberParts<'a> {
+    /// 拆分数值单元格，不含数字时返回 None
+    fn parse(cell: &'a str) -> Option<Self> {
+        let end = cell.rfind(|c: char| c.is_ascii_digit())? + 1;
+        let (number, unit) = cell.split_at(end);
+        let (integer, fraction) = number.split_at(number.find('.').unwrap_or(number.len()));
+        Some(Self { integer, fraction, unit })
+    }
+}
+
+/// 每个数值列中三部分的最大宽度
+#[derive(Debug, Default, Clone, Copy)]
+struct NumberWidths {
+    integer: usize,
+    fraction: usize,
+    unit: usize,
+}
+
+impl NumberWidths {
+    fn total(&self) -> usize {
+        self.integer + self.fraction + self.unit
+    }
+}
+
+/// 按列对齐输出的文本表格
+#[derive(Debug, Clone)]
+pub struct Table {
+    headers: Vec<String>,
+    aligns: Vec<Align>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// 创建表格，`columns` 为各列的标题和对齐方式
+    pub fn new(columns: &[(&str, Align)]) -> Self {
+        Self {
+            headers: columns.iter().map(|(header, _)| header.to_string()).collect(),
+            aligns: columns.iter().map(|&(_, align)| align).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// 添加一行，单元格数与列数不同时多余的忽略、缺少的留空
+    pub fn push_row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    /// 渲染为文本，每行以换行结尾；最后一列左对齐时不补空格，避免行尾多余的空白
+    pub fn render(&self) -> String {
+        let number_widths = self.number_widths();
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|column| {
+                let cells = self.rows.iter().map(|row| match self.aligns[column] {
+                    Align::Number => number_widths[column].total(),
+                    _ => row.get(column).map_or(0, |cell| cell.width()),
+                });
+                cells.chain([self.headers[column].width()]).max().unwrap_or(0)
+            })
+            .collect();
+
+        let header: Vec<String> = self.headers.iter().enumerate()
+            .map(|(column, header)| match self.aligns[column] {
+                Align::Left => pad_right(header, widths[column]),
+                Align::Number => pad_left(header, widths[column]),
+            })
+            .collect();
+        let mut output = join_line(header);
+        for row in &self.rows {
+            let cells: Vec<String> = (0..self.headers.len())
+                .map(|column| {
+                    let cell = row.get(column).map_or("", String::as_str);
+                    match self.aligns[column] {
+                        Align::Left => pad_right(cell, widths[column]),
+                        Align::Number => pad_left(&align_number(cell, number_widths[column]), widths[column]),
+                    }
+                })
+                .collect();
+            output.push_str(&join_line(cells));
+        }
+        output
+    }
+
+    fn number_widths(&self) -> Vec<NumberWidths> {
+        let mut widths = vec![NumberWidths::default(); self.headers.len()];
+        for row in &self.rows {
+            for (column, cell) in row.iter().enumerate().take(self.headers.len()) {
+                if self.aligns[column] != Align::Number {
+                    continue;
+                }
+                let width = &mut widths[column];
+                match NumberParts::parse(cell) {
+                    Some(parts) => {
+                        width.integer = width.integer.max(parts.integer.width());
+                        width.fraction = width.fraction.max(parts.fraction.width());
+                        width.unit = width.unit.max(parts.unit.width());
+                    }
+                    None => width.integer = width.integer.max(cell.width()),
+                }
+            }
+        }
+        widths
+    }
+}
+
+/// 数值单元格按列中各部分的最大宽度补齐；不是数值的单元格原样返回，由调用方整体右对齐
+fn align_number(cell: &str, widths: NumberWidths) -> String {
+    match NumberParts::parse(cell) {
+        Some(parts) => format!(
+            "{}{}{}",
+            pad_left(parts.integer, widths.integer),
+            pad_right(parts.fraction, widths.fraction),
+            pad_right(parts.unit, widths.unit)
+        ),
+        None => cell.to_string(),
+    }
+}
+
+/// 在左侧补空格到指定显示宽度
+pub fn pad_left(text: &str, width: usize) -> String {
+    format!("{}{}", " ".repeat(width.saturating_sub(text.width())), text)
+}
+
+/// 在右侧补空格到指定显示宽度
+pub fn pad_right(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(text.width())))
+}
+
+fn join_line(cells: Vec<String>) -> String {
+    let line = cells.join(COLUMN_GAP);
+    format!("{}\n", line.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_aligns_cjk_names_and_mixed_units() {
+        let mut table = Table::new(&[("项目", Align::Left), ("大小", Align::Number), ("路径", Align::Left)]);
+        table.push_row(vec!["博客".to_string(), "512 B".to_string(), "/code/博客".to_string()]);
+        table.push_row(vec!["api".to_string(), "1.5 KB".to_string(), "/code/api".to_string()]);
+        table.push_row(vec!["web-frontend".to_string(), "120.3 MB".to_string(), "/code/web".to_string()]);
+        table.push_row(vec!["空项目".to_string(), "-".to_string(), "/code/空项目".to_string()]);
+
+        let output = table.render();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines, [
+            "项目              大小  路径",
+            "博客          512   B   /code/博客",
+            "api             1.5 KB  /code/api",
+            "web-frontend  120.3 MB  /code/web",
+            "空项目               -  /code/空项目",
+        ]);
+        // 路径列在每行的显示位置相同
+        let offsets: Vec<usize> = lines.iter().map(|line| line[..line.rfind("/code").unwrap_or(0)].width()).collect();
+        assert!(offsets[2..].iter().all(|&offset| offset == offsets[1]));
+    }
+}
@@ -38,6 +38,24 @@ pub fn format_relative_time(time: SystemTime) -> String {
     }
 }
 
+/// 解析以天为单位的时长（如 `180d`、`26w`、`6m`、`1y`、`90`），月按 30 天、年按 365 天计算
+pub fn parse_days(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    
+    let days_per_unit = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "d" => 1,
+        "w" => 7,
+        "m" => 30,
+        "y" => 365,
+        _ => return None,
+    };
+    
+    number.checked_mul(days_per_unit)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,4 +88,15 @@ mod tests {
         let two_days_ago = now - std::time::Duration::from_secs(172800);
         assert_eq!(format_relative_time(two_days_ago), "2 天前");
     }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("180d"), Some(180));
+        assert_eq!(parse_days("90"), Some(90));
+        assert_eq!(parse_days("2w"), Some(14));
+        assert_eq!(parse_days("6M"), Some(180));
+        assert_eq!(parse_days("1y"), Some(365));
+        assert_eq!(parse_days("d"), None);
+        assert_eq!(parse_days("3 weeks"), None);
+    }
 }
\ No newline at end of file
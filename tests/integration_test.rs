@@ -3,7 +3,9 @@ use tempfile::tempdir;
 use std::fs;
 use git2::Repository;
 
-use project_manager_cli::scanner::{SizeCalculator, GitIgnoreAnalyzer, SizeCache, CacheConfig};
+use project_manager_cli::config::settings::CacheValidation;
+use project_manager_cli::scanner::{SizeCalculator, GitIgnoreAnalyzer, SizeCache};
+use project_manager_cli::scanner::size_cache::CacheConfig;
 
 #[tokio::test]
 async fn test_integrated_git_project_size_calculation_with_cache() {
@@ -42,20 +44,26 @@ async fn test_integrated_git_project_size_calculation_with_cache() {
         enabled: true,
         expiry_duration: std::time::Duration::from_secs(60),
         max_entries: 100,
+        backup_count: 3,
+        hash_manifests: false,
+        validation: CacheValidation::Mtime,
     };
     
-    let mut calculator = SizeCalculator::new_with_cache(cache_config).await.unwrap();
+    // 缓存数据库放在临时目录，不读写用户的默认缓存
+    let cache_dir = tempdir().unwrap();
+    let cache = SizeCache::with_cache_file(cache_dir.path().join("size_cache.db"), cache_config).await.unwrap();
+    let mut calculator = SizeCalculator::new().with_size_cache(cache);
     
     // 第一次计算（应该缓存结果）
     let size_info1 = calculator.calculate_project_size(project_path).await.unwrap();
     
     // 验证大小计算结果
-    assert!(size_info1.code_size > 0, "代码大小应该大于 0");
+    assert!(!size_info1.code_size.is_zero(), "代码大小应该大于 0");
     assert!(size_info1.total_size >= size_info1.code_size, "总大小应该大于等于代码大小");
     
     // target 目录应该被忽略，所以代码大小应该小于总文件系统大小
     let all_files_size = get_directory_size(project_path);
-    assert!(size_info1.total_size < all_files_size, "计算的大小应该小于实际文件系统大小（因为忽略了 target）");
+    assert!(size_info1.total_size.bytes() < all_files_size, "计算的大小应该小于实际文件系统大小（因为忽略了 target）");
     
     // 第二次计算（应该从缓存获取）
     let size_info2 = calculator.calculate_project_size(project_path).await.unwrap();
@@ -105,8 +113,8 @@ async fn test_non_git_project_size_calculation() {
     let size_info = calculator.calculate_project_size(project_path).await.unwrap();
     
     // 验证结果
-    assert!(size_info.code_size > 0, "代码大小应该大于 0");
-    assert!(size_info.dependency_size > 0, "依赖大小应该大于 0（node_modules）");
+    assert!(!size_info.code_size.is_zero(), "代码大小应该大于 0");
+    assert!(!size_info.dependency_size.is_zero(), "依赖大小应该大于 0（node_modules）");
     assert_eq!(size_info.total_size, size_info.code_size + size_info.dependency_size);
     assert!(size_info.code_file_count > 0);
     assert!(size_info.dependency_file_count > 0);